@@ -18,6 +18,9 @@ contract Governance is Config {
 
     event TokenPausedUpdate(address indexed token, bool paused);
 
+    /// @notice Address of the contract allowed to add tokens changed
+    event NewTokenGovernance(address newTokenGovernance);
+
     /// @notice Address which will exercise governance over the network i.e. add tokens, change validator set, conduct upgrades
     address public networkGovernor;
 
@@ -36,6 +39,10 @@ contract Governance is Config {
     /// @notice Paused tokens list, deposits are impossible to create for paused tokens
     mapping(uint16 => bool) public pausedTokens;
 
+    /// @notice Address of the contract which is allowed to add tokens along with the governor,
+    /// used for the permissionless token listing (see `TokenGovernance`)
+    address public tokenGovernance;
+
     /// @notice Governance contract initialization. Can be external because Proxy contract intercepts illegal calls of this function.
     /// @param initializationParameters Encoded representation of initialization parameters:
     ///     _networkGovernor The address of network governor
@@ -59,10 +66,20 @@ contract Governance is Config {
         }
     }
 
+    /// @notice Change the contract allowed to add tokens
+    /// @param _newTokenGovernance Address of the new token governance contract
+    function changeTokenGovernance(address _newTokenGovernance) external {
+        requireGovernor(msg.sender);
+        if (tokenGovernance != _newTokenGovernance) {
+            tokenGovernance = _newTokenGovernance;
+            emit NewTokenGovernance(_newTokenGovernance);
+        }
+    }
+
     /// @notice Add token to the list of networks tokens
     /// @param _token Token address
     function addToken(address _token) external {
-        requireGovernor(msg.sender);
+        require(msg.sender == networkGovernor || msg.sender == tokenGovernance, "1j"); // only by governor or token governance
         require(tokenIds[_token] == 0, "1e"); // token exists
         require(totalTokens < MAX_AMOUNT_OF_REGISTERED_TOKENS, "1f"); // no free identifiers for tokens
 
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+pragma solidity ^0.7.0;
+
+/**
+ * @dev Subset of the Chainlink `AggregatorV3Interface` implemented by the token price feeds,
+ * e.g. ETH / USD. Used by the server to price the tokens the fees are paid in.
+ */
+interface IChainlinkAggregator {
+    /**
+     * @dev Returns the number of the decimals the answer is given with.
+     */
+    function decimals() external view returns (uint8);
+
+    /**
+     * @dev Returns the latest token price as `answer`, together with the time it was updated at.
+     */
+    function latestRoundData()
+        external
+        view
+        returns (
+            uint80 roundId,
+            int256 answer,
+            uint256 startedAt,
+            uint256 updatedAt,
+            uint80 answeredInRound
+        );
+}
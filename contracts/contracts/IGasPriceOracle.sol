@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+pragma solidity ^0.7.0;
+
+/**
+ * @dev Subset of the Chainlink `AggregatorV3Interface` implemented by the gas price feeds,
+ * e.g. Fast Gas / Gwei. Used by the server to price the gas of the L1 operations in the fees.
+ */
+interface IGasPriceOracle {
+    /**
+     * @dev Returns the latest gas price in wei as `answer`, together with the time it was updated at.
+     */
+    function latestRoundData()
+        external
+        view
+        returns (
+            uint80 roundId,
+            int256 answer,
+            uint256 startedAt,
+            uint256 updatedAt,
+            uint80 answeredInRound
+        );
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+pragma solidity ^0.7.0;
+
+/**
+ * @dev Subset of the Uniswap V3 pool interface needed to calculate the time-weighted average price
+ * of the pool tokens. Used by the server to price the tokens the fees are paid in.
+ */
+interface IUniswapV3Pool {
+    /**
+     * @dev Returns the first of the pool tokens, sorted by address.
+     */
+    function token0() external view returns (address);
+
+    /**
+     * @dev Returns the second of the pool tokens, sorted by address.
+     */
+    function token1() external view returns (address);
+
+    /**
+     * @dev Returns the cumulative tick values as of each `secondsAgos` from the current block
+     * timestamp. Reverts if the pool doesn't keep the observations old enough.
+     */
+    function observe(uint32[] calldata secondsAgos)
+        external
+        view
+        returns (int56[] memory tickCumulatives, uint160[] memory secondsPerLiquidityCumulativeX128s);
+}
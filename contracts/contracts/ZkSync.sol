@@ -9,6 +9,7 @@ import "./SafeMath.sol";
 import "./SafeMathUInt128.sol";
 import "./SafeCast.sol";
 import "./Utils.sol";
+import "./IERC20Permit.sol";
 
 import "./Storage.sol";
 import "./Config.sol";
@@ -234,6 +235,54 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
         registerDeposit(tokenId, depositAmount, _zkSyncAddress);
     }
 
+    /// @notice Deposit ERC20 token authorized by the EIP-2612 permit signature of the token owner
+    /// @dev May be called by anyone (e.g. by the relayer), `_fee` is transferred to the caller to compensate the L1 gas
+    /// @param _token Token address, token must support EIP-2612 permits
+    /// @param _owner Address of the tokens owner which is also the zkSync account address
+    /// @param _amount Token amount to deposit
+    /// @param _fee Token amount paid to the caller
+    /// @param _deadline Permit expiration timestamp
+    /// @param _v Permit signature recovery id
+    /// @param _r Permit signature R value
+    /// @param _s Permit signature S value
+    function depositERC20WithPermit(
+        IERC20 _token,
+        address _owner,
+        uint104 _amount,
+        uint104 _fee,
+        uint256 _deadline,
+        uint8 _v,
+        bytes32 _r,
+        bytes32 _s
+    ) external nonReentrant {
+        requireActive();
+
+        // Get token id by its address
+        uint16 tokenId = governance.validateTokenAddress(address(_token));
+        require(!governance.pausedTokens(tokenId), "b"); // token deposits are paused
+
+        IERC20Permit(address(_token)).permit(
+            _owner,
+            address(this),
+            uint256(_amount).add(_fee),
+            _deadline,
+            _v,
+            _r,
+            _s
+        );
+
+        uint256 balanceBefore = _token.balanceOf(address(this));
+        require(Utils.transferFromERC20(_token, _owner, address(this), SafeCast.toUint128(_amount)), "c"); // token transfer failed deposit
+        uint256 balanceAfter = _token.balanceOf(address(this));
+        uint128 depositAmount = SafeCast.toUint128(balanceAfter.sub(balanceBefore));
+
+        if (_fee > 0) {
+            require(Utils.transferFromERC20(_token, _owner, msg.sender, _fee), "c1"); // relayer fee transfer failed
+        }
+
+        registerDeposit(tokenId, depositAmount, _owner);
+    }
+
     /// @notice Returns amount of tokens that can be withdrawn by `address` from zkSync contract
     /// @param _address Address of the tokens owner
     /// @param _token Address of token, zero address is used for ETH
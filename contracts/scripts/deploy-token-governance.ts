@@ -0,0 +1,59 @@
+// This script deploys the `TokenGovernance` contract allowing anyone to list
+// the ERC20 tokens for a fee, and makes it the token governance of the zkSync network.
+
+import { Command } from 'commander';
+import { BigNumber, Wallet } from 'ethers';
+import { deployContract } from 'ethereum-waffle';
+import { Deployer, readContractCode } from '../src.ts/deploy';
+import * as fs from 'fs';
+import * as path from 'path';
+import { web3Provider } from './utils';
+
+const testConfigPath = path.join(process.env.ZKSYNC_HOME as string, `etc/test_config/constant`);
+const ethTestConfig = JSON.parse(fs.readFileSync(`${testConfigPath}/eth.json`, { encoding: 'utf-8' }));
+
+async function main() {
+    const program = new Command();
+
+    program
+        .version('0.1.0')
+        .name('deploy-token-governance')
+        .description('deploy the permissionless token listing contract')
+        .requiredOption('--fee-token <address>', 'token to pay the listing fee in')
+        .requiredOption('--fee <amount>', 'listing fee in the smallest units of the fee token')
+        .option('--cap <amount>', 'maximum amount of the listed tokens', '1023')
+        .option('--treasury <address>', 'address receiving the listing fees, the governor by default');
+    program.parse(process.argv);
+
+    const provider = web3Provider();
+    const governorWallet = Wallet.fromMnemonic(
+        process.env.MNEMONIC ? process.env.MNEMONIC : ethTestConfig.mnemonic,
+        "m/44'/60'/0'/0/1"
+    ).connect(provider);
+    const deployer = new Deployer({ deployWallet: governorWallet });
+
+    const treasury = program.treasury ? program.treasury : governorWallet.address;
+    const tokenGovernance = await deployContract(
+        governorWallet,
+        readContractCode('TokenGovernance'),
+        [deployer.addresses.Governance, program.feeToken, BigNumber.from(program.fee), program.cap, treasury],
+        { gasLimit: 2000000 }
+    );
+    console.log(`CONTRACTS_TOKEN_GOVERNANCE_ADDR=${tokenGovernance.address}`);
+
+    const tx = await deployer
+        .governanceContract(governorWallet)
+        .changeTokenGovernance(tokenGovernance.address, { gasLimit: BigNumber.from('1000000') });
+    const receipt = await tx.wait();
+    if (!receipt.status) {
+        throw new Error('failed to set the token governance');
+    }
+    console.log('Token governance is set, tx hash:', tx.hash);
+}
+
+main()
+    .then(() => process.exit(0))
+    .catch((err) => {
+        console.error('Error:', err.message || err);
+        process.exit(1);
+    });
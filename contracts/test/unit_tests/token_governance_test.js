@@ -0,0 +1,64 @@
+const hardhat = require('hardhat');
+const { expect } = require('chai');
+const { getCallRevertReason } = require('./common');
+
+describe('TokenGovernance unit tests', function () {
+    this.timeout(50000);
+
+    const LISTING_FEE = 100;
+
+    let governor, lister, treasury;
+    let governance, tokenGovernance, feeToken;
+    before(async () => {
+        [governor, lister, treasury] = await hardhat.ethers.getSigners();
+
+        const governanceFactory = await hardhat.ethers.getContractFactory('Governance');
+        governance = await governanceFactory.deploy();
+        await governance.initialize(hardhat.ethers.utils.defaultAbiCoder.encode(['address'], [governor.address]));
+
+        const tokenFactory = await hardhat.ethers.getContractFactory('TestnetERC20Token');
+        feeToken = await tokenFactory.deploy('Fee token', 'FEE', 18);
+        await feeToken.mint(lister.address, LISTING_FEE);
+
+        const tokenGovernanceFactory = await hardhat.ethers.getContractFactory('TokenGovernance');
+        tokenGovernance = await tokenGovernanceFactory.deploy(
+            governance.address,
+            feeToken.address,
+            LISTING_FEE,
+            3,
+            treasury.address
+        );
+    });
+
+    it('only the governor can set the token governance', async () => {
+        const { revertReason } = await getCallRevertReason(() =>
+            governance.connect(lister).changeTokenGovernance(tokenGovernance.address)
+        );
+        expect(revertReason).equal('1g');
+
+        await governance.changeTokenGovernance(tokenGovernance.address);
+        expect(await governance.tokenGovernance()).equal(tokenGovernance.address);
+    });
+
+    it('token is listed only after the fee is paid', async () => {
+        const token = '0x' + '1'.padStart(40, '0');
+        const { revertReason } = await getCallRevertReason(() => tokenGovernance.connect(lister).addToken(token));
+        expect(revertReason).equal('fee transfer failed');
+
+        await feeToken.connect(lister).approve(tokenGovernance.address, LISTING_FEE);
+        await tokenGovernance.connect(lister).addToken(token);
+        expect(await governance.tokenIds(token)).equal(1);
+        expect(await feeToken.balanceOf(treasury.address)).equal(LISTING_FEE);
+    });
+
+    it('governor lists tokens without the fee up to the listing cap', async () => {
+        await tokenGovernance.addToken('0x' + '2'.padStart(40, '0'));
+        await tokenGovernance.addToken('0x' + '3'.padStart(40, '0'));
+        expect(await governance.totalTokens()).equal(3);
+
+        const { revertReason } = await getCallRevertReason(() =>
+            tokenGovernance.addToken('0x' + '4'.padStart(40, '0'))
+        );
+        expect(revertReason).equal("can't add more tokens");
+    });
+});
@@ -43,6 +43,7 @@ pub fn rollup_ops_blocks_from_bytes(input_data: Vec<u8>) -> Result<RollupOpsBloc
             block_num: BlockNumber(block_num.as_u32()),
             ops,
             fee_account,
+            root_hash: None,
         };
         Ok(block)
     } else {
@@ -1,6 +1,7 @@
 use ethabi::{ParamType, Token};
 
 use crate::{contract::default::get_rollup_ops_from_data, rollup_ops::RollupOpsBlock};
+use zksync_crypto::{convert::FeConvert, Fr};
 use zksync_types::{AccountId, BlockNumber};
 
 fn decode_commitment_parameters(input_data: Vec<u8>) -> anyhow::Result<Vec<Token>> {
@@ -39,6 +40,7 @@ pub fn rollup_ops_blocks_from_bytes(data: Vec<u8>) -> anyhow::Result<Vec<RollupO
     let fee_account_argument_id = 5;
     let op_block_number_argument_id = 4;
     let public_data_argument_id = 1;
+    let root_hash_argument_id = 0;
 
     let decoded_commitment_parameters = decode_commitment_parameters(data)?;
     assert_eq!(decoded_commitment_parameters.len(), 2);
@@ -54,16 +56,19 @@ pub fn rollup_ops_blocks_from_bytes(data: Vec<u8>) -> anyhow::Result<Vec<RollupO
                     ethabi::Token::Uint(fee_acc),
                     ethabi::Token::Bytes(public_data),
                     ethabi::Token::Uint(block_number),
+                    ethabi::Token::FixedBytes(root_hash),
                 ) = (
                     &operation[fee_account_argument_id],
                     &operation[public_data_argument_id],
                     &operation[op_block_number_argument_id],
+                    &operation[root_hash_argument_id],
                 ) {
                     let ops = get_rollup_ops_from_data(public_data.as_slice())?;
                     blocks.push(RollupOpsBlock {
                         block_num: BlockNumber(block_number.as_u32()),
                         ops,
                         fee_account: AccountId(fee_acc.as_u32()),
+                        root_hash: Some(Fr::from_bytes(root_hash)?),
                     })
                 } else {
                     return Err(std::io::Error::new(
@@ -129,5 +134,6 @@ mod test {
         assert_eq!(block.block_num, BlockNumber(25));
         assert_eq!(block.fee_account, AccountId(0));
         assert_eq!(block.ops.len(), 5);
+        assert!(block.root_hash.is_some());
     }
 }
@@ -69,6 +69,9 @@ pub struct DataRestoreDriver<T: Transport, I> {
     /// Expected root hash to be observed after restoring process. Only
     /// available in finite mode, and intended for tests.
     pub final_hash: Option<Fr>,
+    /// Root hashes verification flag. If set, the root hash of every restored block
+    /// is checked against the one committed to the contract.
+    pub verify_root_hashes: bool,
     phantom_data: PhantomData<I>,
 }
 
@@ -87,6 +90,7 @@ where
     /// * `end_eth_blocks_offset` - The distance to the last ethereum block
     /// * `finite_mode` - Finite mode flag.
     /// * `final_hash` - Hash of the last block which we want to restore
+    /// * `verify_root_hashes` - Root hashes verification flag.
     /// * `zksync_contract` - Current deployed zksync contract
     ///
     #[allow(clippy::too_many_arguments)]
@@ -97,6 +101,7 @@ where
         end_eth_blocks_offset: u64,
         finite_mode: bool,
         final_hash: Option<Fr>,
+        verify_root_hashes: bool,
         zksync_contract: ZkSyncDeployedContract<T>,
     ) -> Self {
         let governance_contract = {
@@ -120,6 +125,7 @@ where
             end_eth_blocks_offset,
             finite_mode,
             final_hash,
+            verify_root_hashes,
             phantom_data: Default::default(),
         }
     }
@@ -358,6 +364,15 @@ where
                 .tree_state
                 .update_tree_states_from_ops_block(&op_block)
                 .expect("Updating tree state: cant update tree from operations");
+            if self.verify_root_hashes {
+                if let Some(root_hash) = op_block.root_hash {
+                    assert!(
+                        block.new_root_hash == root_hash,
+                        "Root hash of the restored block {} doesn't match the one committed to the contract",
+                        *block.block_number
+                    );
+                }
+            }
             blocks.push(block);
             updates.push(acc_updates);
             count += 1;
@@ -37,6 +37,10 @@ struct Opt {
     #[structopt(long)]
     final_hash: Option<String>,
 
+    /// Checks the root hash of every restored block against the one committed to the contract
+    #[structopt(long)]
+    verify_roots: bool,
+
     /// Sets the web3 API to be used to interact with the Ethereum blockchain
     #[structopt(long = "web3", name = "web3")]
     web3_url: Option<String>,
@@ -113,6 +117,7 @@ async fn main() {
         END_ETH_BLOCKS_OFFSET,
         finite_mode,
         final_hash,
+        opt.verify_roots,
         contract,
     );
 
@@ -1,5 +1,6 @@
 use web3::{Transport, Web3};
 
+use zksync_crypto::Fr;
 use zksync_types::operations::ZkSyncOp;
 
 use crate::contract;
@@ -16,6 +17,10 @@ pub struct RollupOpsBlock {
     pub ops: Vec<ZkSyncOp>,
     /// Fee account
     pub fee_account: AccountId,
+    /// Root hash of the state after the block, as committed to the contract.
+    /// Not known for the blocks committed by the old contract versions and
+    /// the blocks loaded from the storage.
+    pub root_hash: Option<Fr>,
 }
 
 impl RollupOpsBlock {
@@ -388,6 +388,7 @@ async fn test_run_state_update(mut storage: StorageProcessor<'_>) {
         END_ETH_BLOCKS_OFFSET,
         true,
         None,
+        false,
         ZkSyncDeployedContract::version4(eth, [1u8; 20].into()),
     );
 
@@ -421,6 +422,7 @@ async fn test_run_state_update(mut storage: StorageProcessor<'_>) {
         END_ETH_BLOCKS_OFFSET,
         true,
         None,
+        false,
         ZkSyncDeployedContract::version4(eth, [1u8; 20].into()),
     );
 
@@ -592,6 +594,7 @@ async fn test_with_inmemory_storage() {
         END_ETH_BLOCKS_OFFSET,
         true,
         None,
+        false,
         ZkSyncDeployedContract::version4(eth, [1u8; 20].into()),
     );
 
@@ -618,6 +621,7 @@ async fn test_with_inmemory_storage() {
         END_ETH_BLOCKS_OFFSET,
         true,
         None,
+        false,
         ZkSyncDeployedContract::version4(eth, [1u8; 20].into()),
     );
 
@@ -626,3 +630,69 @@ async fn test_with_inmemory_storage() {
     assert_eq!(driver.events_state.committed_events.len(), events.len());
     assert_eq!(*driver.tree_state.state.block_number, 4)
 }
+
+/// Checks that the restoring fails if the root hash committed to the contract
+/// doesn't match the restored one.
+#[tokio::test]
+#[should_panic(expected = "doesn't match the one committed to the contract")]
+async fn test_root_hash_mismatch() {
+    let mut transport = Web3Transport::new();
+
+    let mut interactor = InMemoryStorageInteractor::new();
+    let contract = zksync_contract();
+
+    let block_verified_topic = contract
+        .event("BlockVerification")
+        .expect("Main contract abi error")
+        .signature();
+    transport.insert_logs(
+        format!("{:?}", block_verified_topic),
+        vec![create_log(
+            block_verified_topic,
+            vec![u32_to_32bytes(1).into()],
+            Bytes(vec![]),
+            1,
+            u32_to_32bytes(1).into(),
+        )],
+    );
+
+    let block_committed_topic = contract
+        .event("BlockCommit")
+        .expect("Main contract abi error")
+        .signature();
+    transport.insert_logs(
+        format!("{:?}", block_committed_topic),
+        vec![create_log(
+            block_committed_topic,
+            vec![u32_to_32bytes(1).into()],
+            Bytes(vec![]),
+            1,
+            u32_to_32bytes(1).into(),
+        )],
+    );
+
+    // The block is committed with the default root hash, while the deposit changes the state.
+    transport.push_transactions(vec![create_transaction_v4(
+        1,
+        create_block(BlockNumber(0), vec![]),
+        vec![create_block(
+            BlockNumber(1),
+            vec![create_deposit(Default::default(), Default::default(), 50)],
+        )],
+    )]);
+
+    let web3 = Web3::new(transport.clone());
+    let eth = Eth::new(transport.clone());
+    let mut driver = DataRestoreDriver::new(
+        web3,
+        [1u8; 20].into(),
+        ETH_BLOCKS_STEP,
+        END_ETH_BLOCKS_OFFSET,
+        true,
+        None,
+        true,
+        ZkSyncDeployedContract::version4(eth, [1u8; 20].into()),
+    );
+
+    driver.run_state_update(&mut interactor).await;
+}
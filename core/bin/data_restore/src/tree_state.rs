@@ -460,6 +460,7 @@ mod test {
             block_num: BlockNumber(1),
             ops: ops1,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         // Withdraw 20 with 1 fee from 7 to 10
@@ -484,6 +485,7 @@ mod test {
             block_num: BlockNumber(2),
             ops: ops2,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         // Transfer 40 with 1 fee from 7 to 8
@@ -509,6 +511,7 @@ mod test {
             block_num: BlockNumber(3),
             ops: ops3,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         // Transfer 19 with 1 fee from 8 to 7
@@ -534,6 +537,7 @@ mod test {
             block_num: BlockNumber(4),
             ops: ops4,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         let pub_key_hash_7 = PubKeyHash::from_hex("sync:8888888888888888888888888888888888888888")
@@ -559,6 +563,7 @@ mod test {
             block_num: BlockNumber(5),
             ops: ops5,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         // Full exit for 8
@@ -577,6 +582,7 @@ mod test {
             block_num: BlockNumber(5),
             ops: ops6,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         // Forced exit for 7
@@ -600,6 +606,7 @@ mod test {
             block_num: BlockNumber(7),
             ops: ops7,
             fee_account: AccountId(1),
+            root_hash: None,
         };
         // This transaction have to be deleted, do not uncomment. Delete it after removing the corresponding code        // let tx6 = Close {
         //     account: Address::from_hex("sync:8888888888888888888888888888888888888888").unwrap(),
@@ -798,6 +805,7 @@ mod test {
             block_num: BlockNumber(1),
             ops,
             fee_account: AccountId(0),
+            root_hash: None,
         };
 
         let mut tree = TreeState::new();
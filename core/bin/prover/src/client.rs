@@ -144,6 +144,10 @@ impl crate::ApiClient for ApiClient {
             if response.status() == reqwest::StatusCode::UNAUTHORIZED {
                 return Err(Permanent(format_err!("authorization error")));
             }
+            // The job was given to another prover, there is no point in retrying.
+            if response.status() == reqwest::StatusCode::CONFLICT {
+                return Err(Permanent(format_err!("job lease expired")));
+            }
 
             Ok(())
         });
@@ -64,6 +64,7 @@ impl Default for MockProverConfigs {
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
+                priority_op_deadline_margin: 86400,
             },
         };
 
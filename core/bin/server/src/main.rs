@@ -1,5 +1,9 @@
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
-use std::cell::RefCell;
+use futures::{
+    channel::{mpsc, oneshot},
+    executor::block_on,
+    SinkExt, StreamExt,
+};
+use std::{cell::RefCell, path::PathBuf, time::Duration};
 use structopt::StructOpt;
 use zksync_api::run_api;
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
@@ -10,6 +14,9 @@ use zksync_witness_generator::run_prover_server;
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 
+/// Time given to the fee ticker to answer the accepted requests on shutdown.
+const TICKER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Copy)]
 pub enum ServerCommand {
     Genesis,
@@ -22,12 +29,25 @@ struct Opt {
     /// Generate genesis block for the first contract deployment
     #[structopt(long)]
     genesis: bool,
+    /// TOML config file in the same format as the files in `etc/env/base`.
+    /// Can be specified several times, later files override the earlier ones.
+    /// Environment variables take precedence over the files.
+    #[structopt(long = "config", parse(from_os_str))]
+    config_files: Vec<PathBuf>,
+    /// Print the resulting configuration (including the secrets) and exit
+    #[structopt(long)]
+    print_config: bool,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
-    let config = ZkSyncConfig::from_env();
+    let config = ZkSyncConfig::load(&opt.config_files)?;
+    if opt.print_config {
+        println!("{}", serde_json::to_string_pretty(&config)?);
+        return Ok(());
+    }
+
     let server_mode = if opt.genesis {
         ServerCommand::Genesis
     } else {
@@ -69,7 +89,13 @@ async fn main() -> anyhow::Result<()> {
 
     // Run API actors.
     vlog::info!("Starting the API server actors");
-    let api_task_handle = run_api(connection_pool.clone(), stop_signal_sender.clone(), &config);
+    let (ticker_shutdown_sender, ticker_shutdown) = oneshot::channel();
+    let mut api_task_handle = run_api(
+        connection_pool.clone(),
+        stop_signal_sender.clone(),
+        ticker_shutdown,
+        &config,
+    );
 
     // Run Ethereum sender actors.
     vlog::info!("Starting the Ethereum sender actors");
@@ -78,13 +104,13 @@ async fn main() -> anyhow::Result<()> {
     // Run prover server & witness generator.
     vlog::info!("Starting the Prover server actors");
     let database = zksync_witness_generator::database::Database::new(connection_pool);
-    run_prover_server(database, stop_signal_sender, ZkSyncConfig::from_env());
+    run_prover_server(database, stop_signal_sender, config);
 
     tokio::select! {
         _ = async { wait_for_tasks(core_task_handles).await } => {
             // We don't need to do anything here, since Core actors will panic upon future resolving.
         },
-        _ = async { api_task_handle.await } => {
+        _ = async { (&mut api_task_handle).await } => {
             panic!("API server actors aren't supposed to finish their execution")
         },
         _ = async { eth_sender_task_handle.await } => {
@@ -101,5 +127,14 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    // Let the fee ticker answer the accepted requests before exiting.
+    ticker_shutdown_sender.send(()).unwrap_or_default();
+    if tokio::time::timeout(TICKER_SHUTDOWN_TIMEOUT, api_task_handle)
+        .await
+        .is_err()
+    {
+        vlog::warn!("Fee ticker didn't shut down in time");
+    }
+
     Ok(())
 }
@@ -33,6 +33,7 @@ fn get_txs_fee(client: Client, url: String) {
         tx_type: TxFeeTypes::Withdraw,
         address: Address::random(),
         token_like: TokenLike::Symbol("wBTC".to_string()),
+        sender: None,
     };
 
     let res = client
@@ -10,15 +10,33 @@ use actix_web_httpauth::extractors::{
     AuthenticationError,
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
-use futures::channel::mpsc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
+use num::Zero;
 use serde::{Deserialize, Serialize};
 
-// Local uses
+// Workspace uses
 use zksync_storage::ConnectionPool;
-use zksync_types::{tokens, Address, TokenId};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, tokens, Address, OutputFeeType, TokenId, TokenLike,
+};
 use zksync_utils::panic_notify::ThreadPanicNotify;
+// Local uses
+use crate::fee_ticker::{
+    discounts::NewDiscountRequest,
+    dry_run::FeeDryRunRequest,
+    freeze::FeeFreezeRequest,
+    gas_costs::TokenGasCost,
+    health::TickerHealthHandle,
+    settings::TokenRiskFactor,
+    subsidies::{NewSubsidyRequest, SubsidyReportRequest, SubsidySpendTotal},
+    waivers::NewWaiverRequest,
+    TickerConfigHandle, TickerRequest, TickerSettingsUpdate,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct PayloadAuthToken {
@@ -32,6 +50,9 @@ struct PayloadAuthToken {
 struct AppState {
     secret_auth: String,
     connection_pool: ConnectionPool,
+    ticker_config: TickerConfigHandle,
+    ticker_health: TickerHealthHandle,
+    ticker_requests: mpsc::Sender<TickerRequest>,
 }
 
 impl AppState {
@@ -57,6 +78,63 @@ struct AddTokenRequest {
     pub decimals: u8,
 }
 
+/// Request to pause or resume sending of the L1 operations of a certain type.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct EthSenderActionRequest {
+    /// Type of the operations: `CommitBlocks`, `PublishProofBlocksOnchain` or `ExecuteBlocks`.
+    pub action_type: AggregatedActionType,
+}
+
+/// Request to prune the data of the old executed blocks.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct PruningRequest {
+    /// Amount of the latest executed blocks which data must be kept.
+    pub retention_blocks: u32,
+}
+
+/// Request to remove the risk factor of the token, so the default one is used.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RemoveRiskFactorRequest {
+    pub token: TokenId,
+}
+
+/// Request to get the last measured liquidity of the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct TokenLiquidityRequest {
+    pub token: TokenLike,
+}
+
+/// Request to remove the gas cost of the operation paid in the token, so the standard one is used.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RemoveGasCostRequest {
+    pub token: TokenId,
+    pub fee_type: OutputFeeType,
+}
+
+/// Request to enable or disable the fee subsidy.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct SubsidyIdRequest {
+    pub id: i32,
+}
+
+/// Request to remove the fee discount.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RemoveDiscountRequest {
+    pub id: i32,
+}
+
+/// Request to remove the fee waiver.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RemoveWaiverRequest {
+    pub id: i32,
+}
+
+/// Filter of the logs in the `RUST_LOG` format, e.g. `info,zksync_api=debug`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct LogFilterRequest {
+    pub filter: String,
+}
+
 struct AuthTokenValidator<'a> {
     decoding_key: DecodingKey<'a>,
 }
@@ -126,6 +204,541 @@ async fn add_token(
     Ok(HttpResponse::Ok().json(token))
 }
 
+fn check_sent_action_type(action_type: AggregatedActionType) -> actix_web::Result<()> {
+    if let AggregatedActionType::CreateProofBlocks = action_type {
+        return Err(actix_web::error::ErrorBadRequest(
+            "proof creation is not sent to Ethereum",
+        ));
+    }
+    Ok(())
+}
+
+async fn pause_eth_sender_action(
+    data: web::Data<AppState>,
+    request: web::Json<EthSenderActionRequest>,
+) -> actix_web::Result<HttpResponse> {
+    check_sent_action_type(request.action_type)?;
+    let mut storage = data.access_storage().await?;
+
+    storage
+        .ethereum_schema()
+        .pause_action(request.action_type)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to pause eth sender operations: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Sending of {} operations was paused by the admin request",
+        request.action_type.to_string()
+    );
+
+    Ok(HttpResponse::Ok().json(request.into_inner()))
+}
+
+async fn resume_eth_sender_action(
+    data: web::Data<AppState>,
+    request: web::Json<EthSenderActionRequest>,
+) -> actix_web::Result<HttpResponse> {
+    check_sent_action_type(request.action_type)?;
+    let mut storage = data.access_storage().await?;
+
+    storage
+        .ethereum_schema()
+        .resume_action(request.action_type)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to resume eth sender operations: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Sending of {} operations was resumed by the admin request",
+        request.action_type.to_string()
+    );
+
+    Ok(HttpResponse::Ok().json(request.into_inner()))
+}
+
+async fn paused_eth_sender_actions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let paused_actions = storage
+        .ethereum_schema()
+        .load_paused_actions()
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load paused eth sender operations: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().json(paused_actions))
+}
+
+async fn prune_old_data(
+    data: web::Data<AppState>,
+    request: web::Json<PruningRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let stats = storage
+        .pruning_schema()
+        .prune_with_retention(request.retention_blocks)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to prune the old data: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    vlog::info!(
+        "Pruned the data of the old blocks by the admin request: {:?}",
+        stats
+    );
+
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+async fn log_filter() -> actix_web::Result<HttpResponse> {
+    let filter = vlog::log_filter()
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("logger is not initialized"))?;
+
+    Ok(HttpResponse::Ok().json(LogFilterRequest { filter }))
+}
+
+async fn set_log_filter(request: web::Json<LogFilterRequest>) -> actix_web::Result<HttpResponse> {
+    vlog::set_log_filter(&request.filter).map_err(actix_web::error::ErrorBadRequest)?;
+    vlog::info!(
+        "Log filter was changed by the admin request: {}",
+        request.filter
+    );
+
+    Ok(HttpResponse::Ok().json(request.into_inner()))
+}
+
+async fn ticker_settings(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn ticker_health(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(data.ticker_health.report()))
+}
+
+async fn fee_dry_run(
+    data: web::Data<AppState>,
+    request: web::Json<FeeDryRunRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    let (response, receiver) = oneshot::channel();
+    data.ticker_requests
+        .clone()
+        .send(TickerRequest::GetTxFeeDryRun {
+            tx_type: request.tx_type,
+            address: request.address,
+            sender: request.sender,
+            token: request.token,
+            response,
+            span: tracing::Span::current(),
+        })
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let dry_run = receiver
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map_err(actix_web::error::ErrorBadRequest)?;
+
+    Ok(HttpResponse::Ok().json(dry_run))
+}
+
+async fn update_ticker_settings(
+    data: web::Data<AppState>,
+    request: web::Json<TickerSettingsUpdate>,
+) -> actix_web::Result<HttpResponse> {
+    let settings = data
+        .ticker_config
+        .update(request.into_inner())
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    vlog::info!(
+        "Fee ticker settings were changed by the admin request: {:?}",
+        settings
+    );
+
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+async fn set_risk_factor(
+    data: web::Data<AppState>,
+    request: web::Json<TokenRiskFactor>,
+) -> actix_web::Result<HttpResponse> {
+    if request.risk_factor.is_zero() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "risk factor must be positive",
+        ));
+    }
+    let mut storage = data.access_storage().await?;
+
+    storage
+        .tokens_schema()
+        .store_risk_factor(request.token, &request.risk_factor)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the token risk factor: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Risk factor of the token {} was set by the admin request: {}",
+        request.token,
+        request.risk_factor
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn remove_risk_factor(
+    data: web::Data<AppState>,
+    request: web::Json<RemoveRiskFactorRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let removed = storage
+        .tokens_schema()
+        .remove_risk_factor(request.token)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to remove the token risk factor: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("token has no risk factor"));
+    }
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Risk factor of the token {} was removed by the admin request",
+        request.token
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn token_liquidity(
+    data: web::Data<AppState>,
+    request: web::Json<TokenLiquidityRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let (response, receiver) = oneshot::channel();
+    data.ticker_requests
+        .clone()
+        .send(TickerRequest::GetTokenLiquidity {
+            token: request.into_inner().token,
+            response,
+            span: tracing::Span::current(),
+        })
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let liquidity = receiver
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map_err(actix_web::error::ErrorBadRequest)?;
+
+    Ok(HttpResponse::Ok().json(liquidity))
+}
+
+async fn freeze_fees(
+    data: web::Data<AppState>,
+    request: web::Json<FeeFreezeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let freeze = request
+        .into_inner()
+        .into_freeze()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let settings = data.ticker_config.freeze(freeze);
+    vlog::warn!(
+        "Fees were frozen by the admin request: {:?}",
+        settings.fee_freeze
+    );
+
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+async fn unfreeze_fees(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    if !data.ticker_config.unfreeze() {
+        return Err(actix_web::error::ErrorNotFound("fees are not frozen"));
+    }
+    vlog::warn!("Fee freeze was lifted by the admin request");
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn set_gas_cost(
+    data: web::Data<AppState>,
+    request: web::Json<TokenGasCost>,
+) -> actix_web::Result<HttpResponse> {
+    let gas_cost = request
+        .clone()
+        .into_record()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let mut storage = data.access_storage().await?;
+
+    storage
+        .token_gas_costs_schema()
+        .store_gas_cost(gas_cost)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the token gas cost: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Gas cost of {:?} paid in the token {} was set by the admin request: {}",
+        request.fee_type,
+        request.token,
+        request.gas_cost
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn remove_gas_cost(
+    data: web::Data<AppState>,
+    request: web::Json<RemoveGasCostRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let fee_type =
+        serde_json::to_value(request.fee_type).map_err(actix_web::error::ErrorBadRequest)?;
+    let mut storage = data.access_storage().await?;
+
+    let removed = storage
+        .token_gas_costs_schema()
+        .remove_gas_cost(i32::from(*request.token), fee_type)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to remove the token gas cost: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "token has no gas cost for the fee type",
+        ));
+    }
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Gas cost of {:?} paid in the token {} was removed by the admin request",
+        request.fee_type,
+        request.token
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn add_subsidy(
+    data: web::Data<AppState>,
+    request: web::Json<NewSubsidyRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let subsidy = request
+        .into_inner()
+        .into_record()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let mut storage = data.access_storage().await?;
+
+    let id = storage
+        .fee_subsidies_schema()
+        .store_subsidy(subsidy)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the fee subsidy: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    reload_ticker_settings(&data).await?;
+    vlog::info!("Fee subsidy {} was added by the admin request", id);
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn enable_subsidy(
+    data: web::Data<AppState>,
+    request: web::Json<SubsidyIdRequest>,
+) -> actix_web::Result<HttpResponse> {
+    set_subsidy_enabled(&data, request.id, true).await
+}
+
+async fn disable_subsidy(
+    data: web::Data<AppState>,
+    request: web::Json<SubsidyIdRequest>,
+) -> actix_web::Result<HttpResponse> {
+    set_subsidy_enabled(&data, request.id, false).await
+}
+
+async fn set_subsidy_enabled(
+    data: &AppState,
+    id: i32,
+    enabled: bool,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let updated = storage
+        .fee_subsidies_schema()
+        .set_subsidy_enabled(id, enabled)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to update the fee subsidy: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    if !updated {
+        return Err(actix_web::error::ErrorNotFound("subsidy not found"));
+    }
+    reload_ticker_settings(data).await?;
+    vlog::info!(
+        "Fee subsidy {} was {} by the admin request",
+        id,
+        if enabled { "enabled" } else { "disabled" }
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn subsidy_report(
+    data: web::Data<AppState>,
+    request: web::Json<SubsidyReportRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let to = request.to.unwrap_or_else(chrono::Utc::now);
+    if to < request.from {
+        return Err(actix_web::error::ErrorBadRequest(
+            "report must end after it starts",
+        ));
+    }
+    let mut storage = data.access_storage().await?;
+
+    let totals = storage
+        .fee_subsidies_schema()
+        .load_subsidy_spend_totals(request.from, to)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to load the subsidies spent: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    let report = totals
+        .into_iter()
+        .map(SubsidySpendTotal::from_stored)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+async fn add_discount(
+    data: web::Data<AppState>,
+    request: web::Json<NewDiscountRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let address = request.address;
+    let discount = request
+        .into_inner()
+        .into_record()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let mut storage = data.access_storage().await?;
+
+    let id = storage
+        .fee_discounts_schema()
+        .store_discount(discount)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the fee discount: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Fee discount {} for the account {:?} was added by the admin request",
+        id,
+        address
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn remove_discount(
+    data: web::Data<AppState>,
+    request: web::Json<RemoveDiscountRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let removed = storage
+        .fee_discounts_schema()
+        .remove_discount(request.id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to remove the fee discount: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("discount not found"));
+    }
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Fee discount {} was removed by the admin request",
+        request.id
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn add_waiver(
+    data: web::Data<AppState>,
+    request: web::Json<NewWaiverRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let fee_type = request.fee_type;
+    let waiver = request
+        .into_inner()
+        .into_record()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let mut storage = data.access_storage().await?;
+
+    let id = storage
+        .fee_waivers_schema()
+        .store_waiver(waiver)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to store the fee waiver: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    reload_ticker_settings(&data).await?;
+    vlog::info!(
+        "Fee waiver {} for {:?} was added by the admin request",
+        id,
+        fee_type
+    );
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+async fn remove_waiver(
+    data: web::Data<AppState>,
+    request: web::Json<RemoveWaiverRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let removed = storage
+        .fee_waivers_schema()
+        .remove_waiver(request.id)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to remove the fee waiver: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound("waiver not found"));
+    }
+    reload_ticker_settings(&data).await?;
+    vlog::info!("Fee waiver {} was removed by the admin request", request.id);
+
+    Ok(HttpResponse::Ok().json(data.ticker_config.settings()))
+}
+
+/// Applies the changed stored settings right away instead of waiting for the periodic reload.
+async fn reload_ticker_settings(data: &AppState) -> actix_web::Result<()> {
+    data.ticker_config
+        .reload(&data.connection_pool)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to reload the ticker settings: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })
+}
+
 async fn run_server(app_state: AppState, bind_to: SocketAddr) {
     HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -143,6 +756,58 @@ async fn run_server(app_state: AppState, bind_to: SocketAddr) {
             .wrap(auth)
             .app_data(web::Data::new(app_state.clone()))
             .route("/tokens", web::post().to(add_token))
+            .route("/eth_sender/pause", web::post().to(pause_eth_sender_action))
+            .route(
+                "/eth_sender/resume",
+                web::post().to(resume_eth_sender_action),
+            )
+            .route(
+                "/eth_sender/paused",
+                web::get().to(paused_eth_sender_actions),
+            )
+            .route("/pruning", web::post().to(prune_old_data))
+            .route("/log_filter", web::get().to(log_filter))
+            .route("/log_filter", web::post().to(set_log_filter))
+            .route("/fee_ticker/settings", web::get().to(ticker_settings))
+            .route(
+                "/fee_ticker/settings",
+                web::post().to(update_ticker_settings),
+            )
+            .route("/fee_ticker/health", web::get().to(ticker_health))
+            .route("/fee_ticker/dry_run", web::post().to(fee_dry_run))
+            .route("/fee_ticker/liquidity", web::post().to(token_liquidity))
+            .route("/fee_ticker/freeze", web::post().to(freeze_fees))
+            .route("/fee_ticker/unfreeze", web::post().to(unfreeze_fees))
+            .route("/fee_ticker/risk_factors", web::post().to(set_risk_factor))
+            .route(
+                "/fee_ticker/risk_factors/remove",
+                web::post().to(remove_risk_factor),
+            )
+            .route("/fee_ticker/gas_costs", web::post().to(set_gas_cost))
+            .route(
+                "/fee_ticker/gas_costs/remove",
+                web::post().to(remove_gas_cost),
+            )
+            .route("/fee_ticker/subsidies", web::post().to(add_subsidy))
+            .route(
+                "/fee_ticker/subsidies/enable",
+                web::post().to(enable_subsidy),
+            )
+            .route(
+                "/fee_ticker/subsidies/disable",
+                web::post().to(disable_subsidy),
+            )
+            .route(
+                "/fee_ticker/subsidies/report",
+                web::post().to(subsidy_report),
+            )
+            .route("/fee_ticker/discounts", web::post().to(add_discount))
+            .route(
+                "/fee_ticker/discounts/remove",
+                web::post().to(remove_discount),
+            )
+            .route("/fee_ticker/waivers", web::post().to(add_waiver))
+            .route("/fee_ticker/waivers/remove", web::post().to(remove_waiver))
     })
     .workers(1)
     .bind(&bind_to)
@@ -156,6 +821,9 @@ pub fn start_admin_server(
     bind_to: SocketAddr,
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    ticker_config: TickerConfigHandle,
+    ticker_health: TickerHealthHandle,
+    ticker_requests: mpsc::Sender<TickerRequest>,
     panic_notify: mpsc::Sender<bool>,
 ) {
     thread::Builder::new()
@@ -166,6 +834,9 @@ pub fn start_admin_server(
                 let app_state = AppState {
                     connection_pool,
                     secret_auth,
+                    ticker_config,
+                    ticker_health,
+                    ticker_requests,
                 };
 
                 run_server(app_state, bind_to).await;
@@ -1,7 +1,11 @@
 use super::ExecutedOps;
 use futures::{channel::mpsc, SinkExt};
 use std::time::{Duration, Instant};
-use zksync_storage::ConnectionPool;
+use tokio::time::Interval;
+use zksync_storage::{
+    listener::{StorageEvent, StorageListener},
+    ConnectionPool,
+};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::ExecutedOperations,
@@ -23,9 +27,16 @@ macro_rules! await_db {
     };
 }
 
+/// When the database notifications are available, the database is only checked
+/// after an event is received, but at least once per this interval.
+const STORAGE_EVENTS_FALLBACK_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Event fetcher is an actor which polls the database from time to time in order to see
 /// whether new blocks were committed or verified.
 ///
+/// To avoid the tight polling loop, fetcher subscribes to the database notifications
+/// and only checks the database once something has changed.
+///
 /// Once tha new data is available, it is sent to the `OperationNotifier`, which broadcasts it
 /// to the subscribers.
 #[derive(Debug)]
@@ -78,9 +89,23 @@ impl EventFetcher {
 
     pub async fn run(mut self) -> anyhow::Result<()> {
         let mut interval = tokio::time::interval(self.miniblock_interval);
+        let mut listener = match StorageListener::connect().await {
+            Ok(listener) => Some(listener),
+            Err(err) => {
+                vlog::warn!(
+                    "Unable to subscribe to the database notifications, falling back to polling: {}",
+                    err
+                );
+                None
+            }
+        };
 
         loop {
+            // Interval limits the frequency of the database checks in both modes.
             interval.tick().await;
+            if let Some(listener) = listener.as_mut() {
+                Self::wait_for_changes(listener, &mut interval).await;
+            }
 
             // 1. Update last verified block.
             let last_verified_block = await_db!(self.last_verified_block(), continue);
@@ -117,6 +142,27 @@ impl EventFetcher {
         }
     }
 
+    /// Waits until the database notifies about the changes the fetcher is interested in.
+    async fn wait_for_changes(listener: &mut StorageListener, interval: &mut Interval) {
+        let deadline = tokio::time::Instant::now() + STORAGE_EVENTS_FALLBACK_INTERVAL;
+        loop {
+            match tokio::time::timeout_at(deadline, listener.recv()).await {
+                // Mempool changes don't affect the pending block until the transactions are executed.
+                Ok(Ok(StorageEvent::MempoolChanged)) => continue,
+                Ok(Ok(_)) => return,
+                Ok(Err(err)) => {
+                    vlog::warn!("Failed to receive the database notification: {}", err);
+                    // Don't let the connection errors turn into the busy loop.
+                    interval.tick().await;
+                    return;
+                }
+                // Nothing happened, but the database state is checked anyway in case
+                // some notifications were missed.
+                Err(_) => return,
+            }
+        }
+    }
+
     fn update_pending_block(&mut self, new: PendingBlock) -> Option<ExecutedOps> {
         let start = Instant::now();
         if new.number <= self.last_committed_block {
@@ -1,14 +1,16 @@
 use super::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp};
-use futures::{channel::mpsc, select, stream::StreamExt};
+use crate::fee_ticker::price_feed::{PriceFeed, TokenPriceUpdate};
+use futures::{channel::mpsc, select, stream::StreamExt, FutureExt};
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
     SubscriptionId,
 };
 use std::time::Duration;
+use tokio::sync::broadcast::RecvError;
 use zksync_storage::ConnectionPool;
 use zksync_types::tx::TxHash;
 use zksync_types::BlockNumber;
-use zksync_types::{block::ExecutedOperations, ActionType, Address};
+use zksync_types::{block::ExecutedOperations, ActionType, Address, TokenLike};
 
 use self::{event_fetcher::EventFetcher, operation_notifier::OperationNotifier};
 
@@ -41,6 +43,10 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    TokenPrice {
+        token: TokenLike,
+        subscriber: Subscriber<TokenPriceUpdate>,
+    },
 }
 
 pub enum EventNotifierRequest {
@@ -59,11 +65,14 @@ pub fn start_sub_notifier(
     mut subscription_stream: mpsc::Receiver<EventNotifierRequest>,
     api_requests_caches_size: usize,
     miniblock_interval: Duration,
+    price_feed: PriceFeed,
 ) -> tokio::task::JoinHandle<()> {
     let (new_block_sender, mut new_block_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
     let (new_txs_sender, mut new_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
 
-    let mut notifier = OperationNotifier::new(api_requests_caches_size, db_pool.clone());
+    let mut price_updates = price_feed.subscribe();
+    let mut notifier =
+        OperationNotifier::new(api_requests_caches_size, db_pool.clone(), price_feed);
 
     tokio::spawn(async move {
         let fetcher = EventFetcher::new(
@@ -102,6 +111,16 @@ pub fn start_sub_notifier(
                             .unwrap_or_default();
                     }
                 },
+                price_update = price_updates.recv().fuse() => {
+                    match price_update {
+                        Ok(update) => notifier.handle_token_price_update(update),
+                        Err(RecvError::Lagged(skipped)) => {
+                            vlog::warn!("Notifier skipped {} token price updates", skipped)
+                        }
+                        // The notifier holds the feed, so it's never closed.
+                        Err(RecvError::Closed) => {}
+                    }
+                },
                 complete => break,
             }
         }
@@ -1,17 +1,21 @@
 use crate::api_server::rpc_server::types::{
     BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
 };
+use crate::fee_ticker::price_feed::{PriceFeed, TokenPriceUpdate};
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::Instant;
 use zksync_storage::ConnectionPool;
 use zksync_types::aggregated_operations::AggregatedOperation;
 use zksync_types::tx::TxHash;
 use zksync_types::BlockNumber;
-use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
+use zksync_types::{
+    block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId, TokenLike,
+};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{SubStorage, TokenPriceSubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps,
 };
 
 pub struct OperationNotifier {
@@ -20,15 +24,17 @@ pub struct OperationNotifier {
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    token_price_subs: TokenPriceSubStorage,
 }
 
 impl OperationNotifier {
-    pub fn new(cache_capacity: usize, db_pool: ConnectionPool) -> Self {
+    pub fn new(cache_capacity: usize, db_pool: ConnectionPool, price_feed: PriceFeed) -> Self {
         Self {
             state: NotifierState::new(cache_capacity, db_pool),
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            token_price_subs: TokenPriceSubStorage::new(price_feed),
         }
     }
 
@@ -60,6 +66,9 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::TokenPrice { token, subscriber } => {
+                    self.add_token_price_sub(token, subscriber)
+                }
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -172,11 +181,17 @@ impl OperationNotifier {
         Ok(())
     }
 
+    /// Notifies the subscribers of the token about its new price.
+    pub fn handle_token_price_update(&mut self, update: TokenPriceUpdate) {
+        self.token_price_subs.notify(update);
+    }
+
     /// Removes provided subscription from the list.
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.token_price_subs.remove(sub_id)?;
         Ok(())
     }
 
@@ -291,4 +306,14 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add token price subscription.
+    fn add_token_price_sub(
+        &mut self,
+        token: TokenLike,
+        sub: Subscriber<TokenPriceUpdate>,
+    ) -> Result<(), anyhow::Error> {
+        let sub_id = self.token_price_subs.generate_sub_id();
+        self.token_price_subs.insert_new(sub_id, sub, token)
+    }
 }
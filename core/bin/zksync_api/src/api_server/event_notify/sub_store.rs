@@ -1,8 +1,13 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
+use crate::fee_ticker::price_feed::{PriceFeed, TokenPriceUpdate};
 use futures::{compat::Future01CompatExt, FutureExt};
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
-use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
+use std::{
+    cmp::Ord,
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId, TokenLike};
 
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -13,6 +18,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const TOKEN_PRICE_SUB_PREFIX: &str = "tpsub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -157,3 +163,97 @@ where
         Ok(())
     }
 }
+
+/// Storage for the token price subscriptions. Unlike the other subscriptions, they are notified
+/// about every price update until the subscriber unsubscribes.
+#[derive(Debug)]
+pub struct TokenPriceSubStorage {
+    feed: PriceFeed,
+    storage: HashMap<TokenLike, Vec<SubscriptionSender<TokenPriceUpdate>>>,
+    /// Last update of every watched token, sent to its new subscribers right away.
+    last_updates: HashMap<TokenLike, TokenPriceUpdate>,
+}
+
+impl TokenPriceSubStorage {
+    pub fn new(feed: PriceFeed) -> Self {
+        Self {
+            feed,
+            storage: HashMap::new(),
+            last_updates: HashMap::new(),
+        }
+    }
+
+    fn send(sink: &Sink<TokenPriceUpdate>, val: TokenPriceUpdate) {
+        tokio::spawn(sink.notify(Ok(val)).compat().map(drop));
+    }
+
+    pub fn generate_sub_id(&self) -> SubscriptionId {
+        SubscriptionId::String(format!(
+            "{}/{}",
+            TOKEN_PRICE_SUB_PREFIX,
+            zksync_crypto::rand::random::<u64>()
+        ))
+    }
+
+    pub fn insert_new(
+        &mut self,
+        sub_id: SubscriptionId,
+        sub: Subscriber<TokenPriceUpdate>,
+        token: TokenLike,
+    ) -> anyhow::Result<()> {
+        let subs = self.storage.entry(token.clone()).or_default();
+        if subs.len() < MAX_LISTENERS_PER_ENTITY {
+            let sink = sub
+                .assign_id(sub_id.clone())
+                .map_err(|_| anyhow::format_err!("SubIdAssign"))?;
+            if let Some(update) = self.last_updates.get(&token) {
+                Self::send(&sink, update.clone());
+            }
+            subs.push(SubscriptionSender { id: sub_id, sink });
+            self.feed.watch(token);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, sub_id: SubscriptionId) -> anyhow::Result<()> {
+        let str_sub_id = if let SubscriptionId::String(str_sub_id) = &sub_id {
+            str_sub_id
+        } else {
+            anyhow::bail!("SubscriptionId should be String");
+        };
+        if !str_sub_id.starts_with(TOKEN_PRICE_SUB_PREFIX) {
+            // Not our type, do nothing.
+            return Ok(());
+        }
+
+        let token = self
+            .storage
+            .iter()
+            .find(|(_, subs)| subs.iter().any(|sub| sub.id == sub_id))
+            .map(|(token, _)| token.clone());
+        if let Some(token) = token {
+            let subs = self
+                .storage
+                .get_mut(&token)
+                .expect("Token subscriptions exist");
+            subs.retain(|sub| sub.id != sub_id);
+            if subs.is_empty() {
+                self.storage.remove(&token);
+                self.last_updates.remove(&token);
+            }
+            self.feed.unwatch(&token);
+        }
+
+        Ok(())
+    }
+
+    pub fn notify(&mut self, update: TokenPriceUpdate) {
+        if let Some(subs) = self.storage.get(&update.token) {
+            for sub in subs {
+                Self::send(&sub.sink, update.clone());
+            }
+            self.last_updates.insert(update.token.clone(), update);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Internal gRPC API of the fee ticker.
+//!
+//! Allows the internal services to quote fees and token prices without going through
+//! the public API. Requests are forwarded to the fee ticker as `TickerRequest`s.
+
+// Built-in deps
+use std::net::SocketAddr;
+use std::str::FromStr;
+// External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tonic::{transport::Server, Request, Response, Status};
+use tracing::{Instrument, Span};
+// Workspace uses
+use zksync_types::{tokens::PriceConfidence, Address, TokenLike, TxFeeTypes};
+// Local uses
+use crate::fee_ticker::{
+    correlation::{api_request_span, CORRELATION_ID_HEADER},
+    TickerRequest, TokenPriceRequestType,
+};
+
+mod proto {
+    tonic::include_proto!("fee_ticker");
+}
+
+use proto::{
+    fee_ticker_server::{FeeTicker, FeeTickerServer},
+    BatchFeeResponse, BatchTxFeeRequest, FeeResponse, PriceType, TokenAllowedResponse, TokenPrice,
+    TokenPriceBatchRequest, TokenPriceBatchResponse, TokenPriceRequest, TokenPriceResponse,
+    TokenRequest, TxFee, TxFeeRequest,
+};
+
+#[derive(Debug, Clone)]
+struct FeeTickerService {
+    ticker_request_sender: mpsc::Sender<TickerRequest>,
+}
+
+impl FeeTickerService {
+    async fn ticker_request<T>(
+        &self,
+        request: impl FnOnce(oneshot::Sender<T>) -> TickerRequest,
+    ) -> Result<T, Status> {
+        let (sender, receiver) = oneshot::channel();
+        self.ticker_request_sender
+            .clone()
+            .send(request(sender))
+            .await
+            .map_err(|_| Status::unavailable("Fee ticker is not available"))?;
+        receiver
+            .await
+            .map_err(|_| Status::internal("Fee ticker dropped the request"))
+    }
+}
+
+/// Creates the span of the request with the correlation ID taken from the request metadata.
+fn request_span<T>(method: &str, request: &Request<T>) -> Span {
+    let correlation_id = request
+        .metadata()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok());
+    api_request_span(method, correlation_id)
+}
+
+fn parse_address(address: &str) -> Result<Address, Status> {
+    Address::from_str(address.trim_start_matches("0x"))
+        .map_err(|_| Status::invalid_argument(format!("Invalid address: {}", address)))
+}
+
+fn parse_tx_fee(tx: TxFee) -> Result<(TxFeeTypes, Address), Status> {
+    let tx_type = serde_json::from_str(&tx.tx_type)
+        .map_err(|_| Status::invalid_argument(format!("Invalid tx type: {}", tx.tx_type)))?;
+    Ok((tx_type, parse_address(&tx.address)?))
+}
+
+fn price_request_type(price_type: i32) -> Result<TokenPriceRequestType, Status> {
+    match PriceType::from_i32(price_type) {
+        Some(PriceType::UsdForOneToken) => Ok(TokenPriceRequestType::USDForOneToken),
+        Some(PriceType::UsdForOneWei) => Ok(TokenPriceRequestType::USDForOneWei),
+        None => Err(Status::invalid_argument(format!(
+            "Invalid price type: {}",
+            price_type
+        ))),
+    }
+}
+
+#[tonic::async_trait]
+impl FeeTicker for FeeTickerService {
+    async fn get_tx_fee(
+        &self,
+        request: Request<TxFeeRequest>,
+    ) -> Result<Response<FeeResponse>, Status> {
+        let span = request_span("GetTxFee", &request);
+        let request = request.into_inner();
+        let tx = request
+            .tx
+            .ok_or_else(|| Status::invalid_argument("Transaction is not specified"))?;
+        let (tx_type, address) = parse_tx_fee(tx)?;
+        let sender = if request.sender.is_empty() {
+            None
+        } else {
+            Some(parse_address(&request.sender)?)
+        };
+        let token = TokenLike::parse(&request.token);
+
+        let fee = self
+            .ticker_request(|response| TickerRequest::GetTxFee {
+                tx_type,
+                address,
+                sender,
+                token,
+                response,
+                span: Span::current(),
+            })
+            .instrument(span)
+            .await?
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(FeeResponse {
+            gas_tx_amount: fee.gas_tx_amount.to_string(),
+            gas_price_wei: fee.gas_price_wei.to_string(),
+            gas_fee: fee.gas_fee.to_string(),
+            zkp_fee: fee.zkp_fee.to_string(),
+            total_fee: fee.total_fee.to_string(),
+        }))
+    }
+
+    async fn get_batch_tx_fee(
+        &self,
+        request: Request<BatchTxFeeRequest>,
+    ) -> Result<Response<BatchFeeResponse>, Status> {
+        let span = request_span("GetBatchTxFee", &request);
+        let request = request.into_inner();
+        let transactions = request
+            .transactions
+            .into_iter()
+            .map(parse_tx_fee)
+            .collect::<Result<Vec<_>, _>>()?;
+        let token = TokenLike::parse(&request.token);
+
+        let fee = self
+            .ticker_request(|response| TickerRequest::GetBatchTxFee {
+                transactions,
+                token,
+                response,
+                span: Span::current(),
+            })
+            .instrument(span)
+            .await?
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(BatchFeeResponse {
+            total_fee: fee.total_fee.to_string(),
+        }))
+    }
+
+    async fn get_token_price(
+        &self,
+        request: Request<TokenPriceRequest>,
+    ) -> Result<Response<TokenPriceResponse>, Status> {
+        let span = request_span("GetTokenPrice", &request);
+        let request = request.into_inner();
+        let req_type = price_request_type(request.price_type)?;
+        let token = TokenLike::parse(&request.token);
+
+        let quote = self
+            .ticker_request(|response| TickerRequest::GetTokenPrice {
+                token,
+                response,
+                req_type,
+                span: Span::current(),
+            })
+            .instrument(span)
+            .await?
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(TokenPriceResponse {
+            price: quote.price.to_string(),
+            last_updated: quote.last_updated.to_rfc3339(),
+            age_secs: quote.age_secs(),
+            fallback: quote.confidence == PriceConfidence::Fallback,
+            source: quote.source,
+        }))
+    }
+
+    async fn get_token_price_batch(
+        &self,
+        request: Request<TokenPriceBatchRequest>,
+    ) -> Result<Response<TokenPriceBatchResponse>, Status> {
+        let span = request_span("GetTokenPriceBatch", &request);
+        let request = request.into_inner();
+        let req_type = price_request_type(request.price_type)?;
+        let tokens = request
+            .tokens
+            .iter()
+            .map(|token| TokenLike::parse(token))
+            .collect();
+
+        let prices = self
+            .ticker_request(|response| TickerRequest::GetTokenPriceBatch {
+                tokens,
+                response,
+                req_type,
+                span: Span::current(),
+            })
+            .instrument(span)
+            .await?;
+
+        Ok(Response::new(TokenPriceBatchResponse {
+            prices: prices
+                .into_iter()
+                .map(|price| TokenPrice {
+                    price: price.map(|price| price.to_string()).unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn is_token_allowed(
+        &self,
+        request: Request<TokenRequest>,
+    ) -> Result<Response<TokenAllowedResponse>, Status> {
+        let span = request_span("IsTokenAllowed", &request);
+        let token = TokenLike::parse(&request.into_inner().token);
+
+        let allowed = self
+            .ticker_request(|response| TickerRequest::IsTokenAllowed {
+                token,
+                response,
+                span: Span::current(),
+            })
+            .instrument(span)
+            .await?
+            .map_err(|err| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(TokenAllowedResponse { allowed }))
+    }
+}
+
+pub fn start_grpc_server(
+    bind_to: SocketAddr,
+    ticker_request_sender: mpsc::Sender<TickerRequest>,
+    mut panic_notify: mpsc::Sender<bool>,
+) {
+    let service = FeeTickerService {
+        ticker_request_sender,
+    };
+
+    tokio::spawn(async move {
+        let result = Server::builder()
+            .add_service(FeeTickerServer::new(service))
+            .serve(bind_to)
+            .await;
+        if let Err(err) = result {
+            vlog::error!("Fee ticker gRPC server failed: {}", err);
+            panic_notify.send(true).await.unwrap_or_default();
+        }
+    });
+}
@@ -3,6 +3,7 @@
 //! `mod rest` - api is used for block explorer.
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
+//! `mod grpc_server` - internal gRPC API of the fee ticker
 
 // Public uses
 pub use rest::v1;
@@ -13,11 +14,12 @@ use futures::channel::mpsc;
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 // Local uses
-use crate::fee_ticker::TickerRequest;
+use crate::fee_ticker::{health::TickerHealthHandle, TickerConfigHandle, TickerRequest};
 use crate::signature_checker;
 
 mod admin_server;
 mod event_notify;
+mod grpc_server;
 mod helpers;
 mod rest;
 pub mod rpc_server;
@@ -32,6 +34,9 @@ pub fn start_api_server(
     connection_pool: ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
+    ticker_config: TickerConfigHandle,
+    ticker_health: TickerHealthHandle,
     config: &ZkSyncConfig,
 ) {
     let (sign_check_sender, sign_check_receiver) = mpsc::channel(32768);
@@ -48,6 +53,7 @@ pub fn start_api_server(
         config.contracts.contract_addr,
         panic_notify.clone(),
         ticker_request_sender.clone(),
+        ticker_priority_request_sender.clone(),
         sign_check_sender.clone(),
         config.clone(),
     );
@@ -56,6 +62,7 @@ pub fn start_api_server(
         connection_pool.clone(),
         sign_check_sender.clone(),
         ticker_request_sender.clone(),
+        ticker_priority_request_sender.clone(),
         panic_notify.clone(),
         config,
     );
@@ -64,13 +71,25 @@ pub fn start_api_server(
         config.api.admin.bind_addr(),
         config.api.admin.secret_auth.clone(),
         connection_pool.clone(),
+        ticker_config,
+        ticker_health,
+        ticker_request_sender.clone(),
         panic_notify.clone(),
     );
 
+    if config.api.fee_ticker_grpc.enabled {
+        grpc_server::start_grpc_server(
+            config.api.fee_ticker_grpc.bind_addr(),
+            ticker_request_sender.clone(),
+            panic_notify.clone(),
+        );
+    }
+
     rpc_server::start_rpc_server(
         connection_pool,
         sign_check_sender,
         ticker_request_sender,
+        ticker_priority_request_sender,
         panic_notify,
         config,
     );
@@ -1,14 +1,21 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{dev::Service, middleware::DefaultHeaders, web, App, HttpResponse, HttpServer};
 use futures::channel::mpsc;
 use std::net::SocketAddr;
+use tracing::Instrument;
 use zksync_storage::ConnectionPool;
 use zksync_types::H160;
 
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 use self::v01::api_decl::ApiV01;
-use crate::{fee_ticker::TickerRequest, signature_checker::VerifyTxSignatureRequest};
+use crate::{
+    fee_ticker::{
+        correlation::{api_request_span, CORRELATION_ID_HEADER},
+        TickerRequest,
+    },
+    signature_checker::VerifyTxSignatureRequest,
+};
 
 use super::tx_sender::TxSender;
 use zksync_config::ZkSyncConfig;
@@ -17,9 +24,13 @@ mod helpers;
 mod v01;
 pub mod v1;
 
+/// Name of the response header containing the network the server runs on.
+pub const NETWORK_HEADER: &str = "zksync-network";
+
 async fn start_server(
     api_v01: ApiV01,
     fee_ticker: mpsc::Sender<TickerRequest>,
+    fee_ticker_priority: mpsc::Sender<TickerRequest>,
     sign_verifier: mpsc::Sender<VerifyTxSignatureRequest>,
     bind_to: SocketAddr,
 ) {
@@ -31,13 +42,29 @@ async fn start_server(
                 api_v01.connection_pool.clone(),
                 sign_verifier.clone(),
                 fee_ticker.clone(),
+                fee_ticker_priority.clone(),
                 &api_v01.config,
             );
             v1::api_scope(tx_sender, &api_v01.config)
         };
 
+        // Every response carries the network name, so the clients can detect
+        // the network they're talking to.
+        let network = api_v01.config.chain.eth.network.to_string();
+
         App::new()
             .wrap(Cors::new().send_wildcard().max_age(3600).finish())
+            .wrap(DefaultHeaders::new().header(NETWORK_HEADER, network))
+            // Every request is handled within a span carrying the correlation ID,
+            // so the logs of the fee ticker can be attributed to the request.
+            .wrap_fn(|req, srv| {
+                let correlation_id = req
+                    .headers()
+                    .get(CORRELATION_ID_HEADER)
+                    .and_then(|value| value.to_str().ok());
+                let span = api_request_span(req.path(), correlation_id);
+                srv.call(req).instrument(span)
+            })
             .service(api_v01.into_scope())
             .service(api_v1_scope)
             // Endpoint needed for js isReachable
@@ -63,6 +90,7 @@ pub(super) fn start_server_thread_detached(
     contract_address: H160,
     panic_notify: mpsc::Sender<bool>,
     fee_ticker: mpsc::Sender<TickerRequest>,
+    fee_ticker_priority: mpsc::Sender<TickerRequest>,
     sign_verifier: mpsc::Sender<VerifyTxSignatureRequest>,
     config: ZkSyncConfig,
 ) {
@@ -75,7 +103,14 @@ pub(super) fn start_server_thread_detached(
                 let api_v01 = ApiV01::new(connection_pool, contract_address, config.clone());
                 api_v01.spawn_network_status_updater(panic_notify);
 
-                start_server(api_v01, fee_ticker, sign_verifier, listen_addr).await;
+                start_server(
+                    api_v01,
+                    fee_ticker,
+                    fee_ticker_priority,
+                    sign_verifier,
+                    listen_addr,
+                )
+                .await;
             });
         })
         .expect("Api server thread");
@@ -103,11 +103,15 @@ impl ApiV01 {
             )
     }
 
+    /// Explorer API is read-only, so it is served by the read replica (if configured).
     pub(crate) async fn access_storage(&self) -> ActixResult<StorageProcessor<'_>> {
-        self.connection_pool.access_storage().await.map_err(|err| {
-            vlog::warn!("DB await timeout: '{}';", err);
-            HttpResponse::RequestTimeout().finish().into()
-        })
+        self.connection_pool
+            .access_read_only_storage()
+            .await
+            .map_err(|err| {
+                vlog::warn!("DB await timeout: '{}';", err);
+                HttpResponse::RequestTimeout().finish().into()
+            })
     }
 
     pub(crate) fn db_error(error: anyhow::Error) -> HttpResponse {
@@ -74,7 +74,10 @@ impl ApiAccountsData {
     }
 
     async fn access_storage(&self) -> QueryResult<StorageProcessor<'_>> {
-        self.pool.access_storage().await.map_err(From::from)
+        self.pool
+            .access_read_only_storage()
+            .await
+            .map_err(From::from)
     }
 
     async fn find_account_address(&self, query: String) -> Result<Address, ApiError> {
@@ -173,6 +176,41 @@ impl ApiAccountsData {
         Ok(Some(info))
     }
 
+    async fn account_state_at_block(
+        &self,
+        query: AccountQuery,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<AccountState>> {
+        let mut storage = self.access_storage().await?;
+        let account_id = if let Some(id) = Self::account_id(&mut storage, query).await? {
+            id
+        } else {
+            return Ok(None);
+        };
+
+        let account = storage
+            .chain()
+            .account_schema()
+            .account_state_at_block(account_id, block_number)
+            .await?;
+
+        match account {
+            Some(account) => account_state_from_storage(&mut storage, &self.tokens, &account)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn last_committed_block(&self) -> QueryResult<BlockNumber> {
+        let mut storage = self.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await
+    }
+
     async fn tx_receipts(
         &self,
         address: Address,
@@ -250,6 +288,31 @@ async fn account_info(
         .map_err(ApiError::internal)
 }
 
+async fn account_state_at_block(
+    data: web::Data<ApiAccountsData>,
+    web::Path((query, block_number)): web::Path<(String, BlockNumber)>,
+) -> JsonResult<Option<AccountState>> {
+    let query = parse_account_query(query)?;
+
+    let last_committed_block = data
+        .last_committed_block()
+        .await
+        .map_err(ApiError::internal)?;
+    if block_number > last_committed_block {
+        return Err(
+            ApiError::bad_request("Block is not committed yet.").detail(format!(
+                "The last committed block is {}",
+                *last_committed_block
+            )),
+        );
+    }
+
+    data.account_state_at_block(query, block_number)
+        .await
+        .map(Json)
+        .map_err(ApiError::internal)
+}
+
 async fn account_tx_receipts(
     data: web::Data<ApiAccountsData>,
     web::Path(account_query): web::Path<String>,
@@ -312,6 +375,10 @@ pub fn api_scope(
     web::scope("accounts")
         .data(data)
         .route("{id}", web::get().to(account_info))
+        .route(
+            "{id}/blocks/{block}/state",
+            web::get().to(account_state_at_block),
+        )
         .route(
             "{id}/transactions/receipts",
             web::get().to(account_tx_receipts),
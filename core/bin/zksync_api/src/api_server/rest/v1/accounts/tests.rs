@@ -166,7 +166,36 @@ async fn accounts_scope() -> anyhow::Result<()> {
 
     let account_info = client.account_info(account_id).await?.unwrap();
     let address = account_info.address;
-    assert_eq!(client.account_info(address).await?, Some(account_info));
+    assert_eq!(
+        client.account_info(address).await?,
+        Some(account_info.clone())
+    );
+
+    // Get account state at the specific blocks.
+    let last_committed_block = server
+        .pool
+        .access_storage()
+        .await?
+        .chain()
+        .block_schema()
+        .get_last_committed_block()
+        .await?;
+    assert_eq!(
+        client
+            .account_state_at_block(account_id, last_committed_block)
+            .await?,
+        Some(account_info.committed)
+    );
+    assert_eq!(
+        client
+            .account_state_at_block(account_id, BlockNumber(0))
+            .await?,
+        None
+    );
+    assert!(client
+        .account_state_at_block(account_id, BlockNumber(*last_committed_block + 1))
+        .await
+        .is_err());
 
     // Provide unconfirmed pending deposits.
     *server.pending_deposits.lock().await = json!([
@@ -4,21 +4,28 @@
 
 // External uses
 use actix_web::{
-    web::{self, Json},
-    Scope,
+    web::{self, Bytes, Json},
+    HttpResponse, Scope,
 };
+use futures::{stream, StreamExt};
 
 // Workspace uses
-pub use zksync_api_client::rest::v1::{BlockInfo, TransactionInfo};
+pub use zksync_api_client::rest::v1::{BlockExportQuery, BlockInfo, TransactionInfo};
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::{convert::FeConvert, Fr};
-use zksync_storage::{chain::block::records, ConnectionPool, QueryResult};
+use zksync_storage::{
+    chain::block::records, connection::retry::retry_on_transient_errors, ConnectionPool,
+    QueryResult,
+};
 use zksync_types::{tx::TxHash, BlockNumber};
 
 // Local uses
 use super::{Error as ApiError, JsonResult, Pagination, PaginationQuery};
 use crate::{api_server::helpers::try_parse_tx_hash, utils::shared_lru_cache::AsyncLruCache};
 
+/// Maximum number of blocks that can be exported by a single request.
+const MAX_EXPORTED_BLOCKS: u32 = 1000;
+
 /// Shared data between `api/v1/blocks` endpoints.
 #[derive(Debug, Clone)]
 struct ApiBlocksData {
@@ -75,12 +82,40 @@ impl ApiBlocksData {
     ) -> QueryResult<Vec<records::BlockDetails>> {
         let max_block = max_block.unwrap_or(BlockNumber(u32::MAX));
 
-        let mut storage = self.pool.access_storage().await?;
-        storage
+        retry_on_transient_errors(|| async move {
+            let mut storage = self.pool.access_read_only_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .load_block_range(max_block, limit)
+                .await
+        })
+        .await
+    }
+
+    /// Returns the full block data (block itself, state diff and proof) serialized as a single
+    /// line of the newline-delimited JSON export.
+    ///
+    /// Returns `None` if there is no block with the specified number.
+    async fn block_export_line(
+        pool: ConnectionPool,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<Bytes>> {
+        let mut storage = pool.access_read_only_storage().await?;
+        let export = storage
             .chain()
             .block_schema()
-            .load_block_range(max_block, limit)
-            .await
+            .load_block_export(block_number)
+            .await?;
+
+        let export = match export {
+            Some(export) => export,
+            None => return Ok(None),
+        };
+
+        let mut line = serde_json::to_vec(&export)?;
+        line.push(b'\n');
+        Ok(Some(Bytes::from(line)))
     }
 
     /// Return transactions stored in the block with the specified number.
@@ -88,12 +123,15 @@ impl ApiBlocksData {
         &self,
         block_number: BlockNumber,
     ) -> QueryResult<Vec<records::BlockTransactionItem>> {
-        let mut storage = self.pool.access_storage().await?;
-        storage
-            .chain()
-            .block_schema()
-            .get_block_transactions(block_number)
-            .await
+        retry_on_transient_errors(|| async move {
+            let mut storage = self.pool.access_read_only_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(block_number)
+                .await
+        })
+        .await
     }
 }
 
@@ -218,12 +256,45 @@ async fn blocks_range(
     Ok(Json(range))
 }
 
+async fn blocks_export(
+    data: web::Data<ApiBlocksData>,
+    web::Query(query): web::Query<BlockExportQuery>,
+) -> Result<HttpResponse, ApiError> {
+    if query.from > query.to {
+        return Err(ApiError::bad_request("Incorrect blocks range")
+            .detail("The beginning of the range must not exceed its end"));
+    }
+    if *query.to - *query.from >= MAX_EXPORTED_BLOCKS {
+        return Err(
+            ApiError::bad_request("Incorrect blocks range").detail(format!(
+                "At most {} blocks can be exported at once",
+                MAX_EXPORTED_BLOCKS
+            )),
+        );
+    }
+
+    // Blocks are loaded one by one while the response is being sent, so the whole
+    // range is never kept in memory.
+    let pool = data.pool.clone();
+    let lines = stream::iter(*query.from..=*query.to)
+        .then(move |block_number| {
+            ApiBlocksData::block_export_line(pool.clone(), BlockNumber(block_number))
+        })
+        .filter_map(|line| async move { line.map_err(ApiError::internal).transpose() })
+        .boxed_local();
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(lines))
+}
+
 pub fn api_scope(config: &ZkSyncConfig, pool: ConnectionPool) -> Scope {
     let data = ApiBlocksData::new(pool, config.api.common.caches_size);
 
     web::scope("blocks")
         .data(data)
         .route("", web::get().to(blocks_range))
+        .route("export", web::get().to(blocks_export))
         .route("{id}", web::get().to(block_by_id))
         .route("{id}/transactions", web::get().to(block_transactions))
 }
@@ -1,33 +1,95 @@
 //! Config part of API implementation.
 
 // Built-in uses
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 // External uses
 use actix_web::{web, Scope};
+use num::BigUint;
+use web3::contract::{tokens::Detokenize, Options};
 
 // Workspace uses
-use zksync_api_client::rest::v1::Contracts;
+use zksync_api_client::rest::v1::{Contracts, TokenListing};
 use zksync_config::ZkSyncConfig;
-use zksync_types::{network::Network, Address};
+use zksync_contracts::token_governance_contract;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{network::Network, Address, U256};
 
 // Local uses
-use super::Json;
+use super::{ApiError, Json, JsonResult};
+
+/// Listing parameters are rarely changed, so they're reloaded from the contract
+/// no more often than this.
+const TOKEN_LISTING_CACHE_TTL: Duration = Duration::from_secs(60);
 
 /// Shared data between `api/v1/config` endpoints.
 #[derive(Debug, Clone)]
 struct ApiConfigData {
     contract_address: Address,
+    token_governance_address: Address,
     deposit_confirmations: u64,
     network: Network,
+    eth_client: EthereumGateway,
+    token_listing: Arc<Mutex<Option<(Instant, TokenListing)>>>,
 }
 
 impl ApiConfigData {
     fn new(config: &ZkSyncConfig) -> Self {
         Self {
             contract_address: config.contracts.contract_addr,
+            token_governance_address: config.contracts.token_governance_addr,
             deposit_confirmations: config.eth_watch.confirmations_for_eth_event,
             network: config.chain.eth.network,
+            eth_client: EthereumGateway::from_config(config),
+            token_listing: Arc::default(),
+        }
+    }
+
+    async fn token_listing(&self) -> anyhow::Result<Option<TokenListing>> {
+        if self.token_governance_address.is_zero() {
+            return Ok(None);
+        }
+        if let Some((loaded_at, listing)) = self.token_listing.lock().unwrap().as_ref() {
+            if loaded_at.elapsed() < TOKEN_LISTING_CACHE_TTL {
+                return Ok(Some(listing.clone()));
+            }
         }
+
+        let fee_token: Address = self.call_token_governance("listingFeeToken").await?;
+        let fee: U256 = self.call_token_governance("listingFee").await?;
+        let cap: U256 = self.call_token_governance("listingCap").await?;
+
+        let mut fee_bytes = [0u8; 32];
+        fee.to_big_endian(&mut fee_bytes);
+        let listing = TokenListing {
+            contract: self.token_governance_address,
+            fee_token,
+            fee: BigUint::from_bytes_be(&fee_bytes),
+            cap: cap.as_u32() as u16,
+        };
+        *self.token_listing.lock().unwrap() = Some((Instant::now(), listing.clone()));
+        Ok(Some(listing))
+    }
+
+    async fn call_token_governance<R>(&self, func: &str) -> anyhow::Result<R>
+    where
+        R: Detokenize + Unpin,
+    {
+        self.eth_client
+            .call_contract_function(
+                func,
+                (),
+                None,
+                Options::default(),
+                None,
+                self.token_governance_address,
+                token_governance_contract(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query token governance {}: {}", func, e))
     }
 }
 
@@ -47,6 +109,11 @@ async fn network(data: web::Data<ApiConfigData>) -> Json<Network> {
     Json(data.network)
 }
 
+async fn token_listing(data: web::Data<ApiConfigData>) -> JsonResult<Option<TokenListing>> {
+    let listing = data.token_listing().await.map_err(ApiError::internal)?;
+    Ok(Json(listing))
+}
+
 pub fn api_scope(config: &ZkSyncConfig) -> Scope {
     let data = ApiConfigData::new(config);
 
@@ -54,6 +121,7 @@ pub fn api_scope(config: &ZkSyncConfig) -> Scope {
         .data(data)
         .route("contracts", web::get().to(contracts))
         .route("network", web::get().to(network))
+        .route("token_listing", web::get().to(token_listing))
         .route(
             "deposit_confirmations",
             web::get().to(deposit_confirmations),
@@ -35,6 +35,11 @@ impl Error {
         Self::with_code(StatusCode::NOT_IMPLEMENTED, title)
     }
 
+    /// Creates a new Error with the SERVICE_UNAVAILABLE (503) status code.
+    pub fn service_unavailable(title: impl Display) -> Self {
+        Self::with_code(StatusCode::SERVICE_UNAVAILABLE, title)
+    }
+
     fn with_code(http_code: StatusCode, title: impl Display) -> Self {
         Self {
             http_code,
@@ -36,7 +36,7 @@ impl ApiOperationsData {
         &self,
         query: PriorityOpQuery,
     ) -> QueryResult<Option<PriorityOpData>> {
-        let mut storage = self.pool.access_storage().await?;
+        let mut storage = self.pool.access_read_only_storage().await?;
 
         let executed_op = executed_priority_op_for_query(query, &mut storage).await?;
         Ok(executed_op.map(convert::priority_op_data_from_stored))
@@ -46,7 +46,7 @@ impl ApiOperationsData {
         &self,
         query: PriorityOpQuery,
     ) -> QueryResult<Option<PriorityOpReceipt>> {
-        let mut storage = self.pool.access_storage().await?;
+        let mut storage = self.pool.access_read_only_storage().await?;
 
         let executed_op = executed_priority_op_for_query(query, &mut storage).await?;
         let executed_op = if let Some(executed_op) = executed_op {
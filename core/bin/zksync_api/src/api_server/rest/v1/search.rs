@@ -30,7 +30,7 @@ impl ApiSearchData {
     }
 
     async fn search_block(&self, query: String) -> QueryResult<Option<BlockInfo>> {
-        let mut storage = self.pool.access_storage().await?;
+        let mut storage = self.pool.access_read_only_storage().await?;
 
         let block = storage
             .chain()
@@ -404,12 +404,12 @@ impl TestServerConfig {
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover")
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover")
                     .await?
                     .unwrap()
                     .job_id;
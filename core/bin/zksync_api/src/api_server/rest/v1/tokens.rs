@@ -8,18 +8,22 @@ use actix_web::{
     Scope,
 };
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
 };
 
 // Workspace uses
-use zksync_api_client::rest::v1::{TokenPriceKind, TokenPriceQuery};
+use zksync_api_client::rest::v1::{
+    PriceAtQuery, PriceHistoryQuery, PriceObservation, TokenPriceKind, TokenPriceQuery,
+    TokenPriceQuote, TokenPricesRequest,
+};
 use zksync_storage::{ConnectionPool, QueryResult};
-use zksync_types::{Token, TokenLike};
+use zksync_types::{tokens::FeeTokenStatus, Token, TokenLike};
 
 use crate::{
-    fee_ticker::{TickerRequest, TokenPriceRequestType},
+    fee_ticker::{PriceQuote, TickerRequest, TokenPriceRequestType},
     utils::token_db_cache::TokenDBCache,
 };
 
@@ -65,7 +69,7 @@ impl ApiTokensData {
         self.tokens.get_token(&mut storage, token_like).await
     }
 
-    async fn token_price_usd(&self, token: TokenLike) -> QueryResult<Option<BigDecimal>> {
+    async fn token_price_usd(&self, token: TokenLike) -> QueryResult<Option<PriceQuote>> {
         let (price_sender, price_receiver) = oneshot::channel();
         self.fee_ticker
             .clone()
@@ -73,6 +77,7 @@ impl ApiTokensData {
                 token,
                 response: price_sender,
                 req_type: TokenPriceRequestType::USDForOneToken,
+                span: tracing::Span::current(),
             })
             .await?;
 
@@ -89,6 +94,91 @@ impl ApiTokensData {
             }
         }
     }
+
+    async fn token_prices_usd(
+        &self,
+        tokens: Vec<TokenLike>,
+    ) -> QueryResult<Vec<Option<BigDecimal>>> {
+        let (prices_sender, prices_receiver) = oneshot::channel();
+        self.fee_ticker
+            .clone()
+            .send(TickerRequest::GetTokenPriceBatch {
+                tokens,
+                response: prices_sender,
+                req_type: TokenPriceRequestType::USDForOneToken,
+                span: tracing::Span::current(),
+            })
+            .await?;
+
+        Ok(prices_receiver.await?)
+    }
+
+    async fn token_fee_status(&self, token: TokenLike) -> QueryResult<FeeTokenStatus> {
+        let (status_sender, status_receiver) = oneshot::channel();
+        self.fee_ticker
+            .clone()
+            .send(TickerRequest::GetTokenFeeStatus {
+                token,
+                response: status_sender,
+                span: tracing::Span::current(),
+            })
+            .await?;
+
+        status_receiver.await?
+    }
+
+    async fn token_price_history(
+        &self,
+        token_like: TokenLike,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Option<Vec<PriceObservation>>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let token = match self.tokens.get_token(&mut storage, token_like).await? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        let history = storage
+            .tokens_schema()
+            .load_price_history(token.id, from, to)
+            .await?
+            .into_iter()
+            .map(|observation| PriceObservation {
+                source: observation.source,
+                usd_price: observation.usd_price,
+                observed_at: observation.observed_at,
+            })
+            .collect();
+
+        Ok(Some(history))
+    }
+
+    async fn token_price_at(
+        &self,
+        token_like: TokenLike,
+        timestamp: DateTime<Utc>,
+    ) -> QueryResult<Option<Option<PriceObservation>>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let token = match self.tokens.get_token(&mut storage, token_like).await? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        let observation = storage
+            .tokens_schema()
+            .load_price_at(token.id, timestamp)
+            .await?
+            .map(|observation| PriceObservation {
+                source: observation.source,
+                usd_price: observation.usd_price,
+                observed_at: observation.observed_at,
+            });
+
+        Ok(Some(observation))
+    }
 }
 
 // Server implementation
@@ -120,7 +210,8 @@ async fn token_price(
         TokenPriceKind::Currency => data
             .token_price_usd(token_like)
             .await
-            .map_err(ApiError::internal)?,
+            .map_err(ApiError::internal)?
+            .map(|quote| quote.price),
 
         TokenPriceKind::Token => {
             return Err(ApiError::not_implemented(
@@ -132,6 +223,102 @@ async fn token_price(
     Ok(Json(price))
 }
 
+async fn token_price_quote(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+    web::Query(token_query): web::Query<TokenPriceQuery>,
+) -> JsonResult<Option<TokenPriceQuote>> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let quote = match token_query.kind {
+        TokenPriceKind::Currency => data
+            .token_price_usd(token_like)
+            .await
+            .map_err(ApiError::internal)?,
+
+        TokenPriceKind::Token => {
+            return Err(ApiError::not_implemented(
+                "price in tokens not yet implemented",
+            ))
+        }
+    };
+
+    let quote = quote.map(|quote| TokenPriceQuote {
+        age_secs: quote.age_secs(),
+        price: quote.price,
+        source: quote.source,
+        last_updated: quote.last_updated,
+        confidence: quote.confidence,
+    });
+    Ok(Json(quote))
+}
+
+async fn token_prices(
+    data: web::Data<ApiTokensData>,
+    Json(body): Json<TokenPricesRequest>,
+) -> JsonResult<Vec<Option<BigDecimal>>> {
+    let prices = match body.kind {
+        TokenPriceKind::Currency => data
+            .token_prices_usd(body.tokens)
+            .await
+            .map_err(ApiError::internal)?,
+
+        TokenPriceKind::Token => {
+            return Err(ApiError::not_implemented(
+                "price in tokens not yet implemented",
+            ))
+        }
+    };
+
+    Ok(Json(prices))
+}
+
+async fn token_fee_status(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+) -> JsonResult<FeeTokenStatus> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let status = data
+        .token_fee_status(token_like)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(status))
+}
+
+async fn token_price_history(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+    web::Query(query): web::Query<PriceHistoryQuery>,
+) -> JsonResult<Vec<PriceObservation>> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let history = data
+        .token_price_history(token_like, query.from, query.to)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::bad_request("Token not found"))?;
+
+    Ok(Json(history))
+}
+
+async fn token_price_at(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+    web::Query(query): web::Query<PriceAtQuery>,
+) -> JsonResult<Option<PriceObservation>> {
+    let token_like = TokenLike::parse(&token_like);
+
+    let observation = data
+        .token_price_at(token_like, query.timestamp)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::bad_request("Token not found"))?;
+
+    Ok(Json(observation))
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
@@ -142,15 +329,20 @@ pub fn api_scope(
     web::scope("tokens")
         .data(data)
         .route("", web::get().to(tokens))
+        .route("prices", web::post().to(token_prices))
         .route("{id}", web::get().to(token_by_id))
         .route("{id}/price", web::get().to(token_price))
+        .route("{id}/price_quote", web::get().to(token_price_quote))
+        .route("{id}/price_history", web::get().to(token_price_history))
+        .route("{id}/price_at", web::get().to(token_price_at))
+        .route("{id}/fee_status", web::get().to(token_fee_status))
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use zksync_types::{Address, TokenId};
+    use zksync_types::{tokens::PriceConfidence, Address, TokenId};
 
     use super::{super::test_utils::TestServerConfig, *};
 
@@ -165,6 +357,7 @@ mod tests {
                         token,
                         response,
                         req_type,
+                        ..
                     } => {
                         assert_eq!(
                             req_type,
@@ -173,7 +366,12 @@ mod tests {
                         );
 
                         let msg = if let Some(price) = prices.get(&token) {
-                            Ok(price.clone())
+                            Ok(PriceQuote {
+                                price: price.clone(),
+                                source: "test".to_string(),
+                                last_updated: Utc::now(),
+                                confidence: PriceConfidence::Fresh,
+                            })
                         } else {
                             // To provide compatibility with the `token_price_usd` hack.
                             Err(anyhow::format_err!("Token not found: {:?}", token))
@@ -181,6 +379,24 @@ mod tests {
 
                         response.send(msg).expect("Unable to send response");
                     }
+                    TickerRequest::GetTokenPriceBatch {
+                        tokens,
+                        response,
+                        req_type,
+                        ..
+                    } => {
+                        assert_eq!(
+                            req_type,
+                            TokenPriceRequestType::USDForOneToken,
+                            "Unsupported price request type"
+                        );
+
+                        let msg = tokens
+                            .iter()
+                            .map(|token| prices.get(token).cloned())
+                            .collect();
+                        response.send(msg).expect("Unable to send response");
+                    }
                     _ => unreachable!("Unsupported request"),
                 }
             }
@@ -226,12 +442,42 @@ mod tests {
                 .await?,
             None
         );
+
+        let quote = client
+            .token_price_quote(&prices[1].0, TokenPriceKind::Currency)
+            .await?
+            .expect("price is known");
+        assert_eq!(quote.price, prices[1].1);
+        assert_eq!(quote.source, "test");
+        assert_eq!(quote.confidence, PriceConfidence::Fresh);
+        assert!(quote.age_secs <= 1);
+        assert_eq!(
+            client
+                .token_price_quote(&TokenLike::Id(TokenId(2)), TokenPriceKind::Currency)
+                .await?,
+            None
+        );
         // TODO Check error (ZKS-125)
         client
             .token_price(&TokenLike::Id(TokenId(2)), TokenPriceKind::Token)
             .await
             .unwrap_err();
 
+        let batch_prices = client
+            .token_prices(
+                vec![
+                    prices[1].0.clone(),
+                    TokenLike::Id(TokenId(2)),
+                    prices[0].0.clone(),
+                ],
+                TokenPriceKind::Currency,
+            )
+            .await?;
+        assert_eq!(
+            batch_prices,
+            vec![Some(prices[1].1.clone()), None, Some(prices[0].1.clone())]
+        );
+
         // Tokens requests
         let expected_tokens = {
             let mut storage = cfg.pool.access_storage().await?;
@@ -281,6 +527,53 @@ mod tests {
         );
         assert_eq!(client.token_by_id(&TokenLike::parse("XM")).await?, None);
 
+        // Price history requests
+        let now = chrono::Utc::now();
+        let price = zksync_types::TokenPrice {
+            usd_price: num::rational::Ratio::from_integer(10_u32.into()),
+            last_updated: now,
+        };
+        cfg.pool
+            .access_storage()
+            .await?
+            .tokens_schema()
+            .store_price_observation(TokenId(0), "CoinGecko", &price)
+            .await?;
+
+        let history = client
+            .token_price_history(
+                &TokenLike::Id(TokenId(0)),
+                now - chrono::Duration::seconds(1),
+                now + chrono::Duration::seconds(1),
+            )
+            .await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].source, "CoinGecko");
+        assert_eq!(history[0].usd_price, BigDecimal::from(10));
+        client
+            .token_price_history(&TokenLike::parse("XM"), now, now)
+            .await
+            .unwrap_err();
+
+        let observation = client
+            .token_price_at(
+                &TokenLike::Id(TokenId(0)),
+                now + chrono::Duration::seconds(1),
+            )
+            .await?
+            .expect("no price observed");
+        assert_eq!(observation.usd_price, BigDecimal::from(10));
+        assert_eq!(
+            client
+                .token_price_at(&TokenLike::Id(TokenId(0)), now - chrono::Duration::hours(1))
+                .await?,
+            None
+        );
+        client
+            .token_price_at(&TokenLike::parse("XM"), now)
+            .await
+            .unwrap_err();
+
         server.stop().await;
         Ok(())
     }
@@ -10,13 +10,23 @@ use actix_web::{
 
 // Workspace uses
 pub use zksync_api_client::rest::v1::{
-    FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-    Receipt, TxData,
+    FastProcessingQuery, ForcedExitRequestInfo, ForcedExitRequestStatus, IncomingForcedExitRequest,
+    IncomingPermitDeposit, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
+    PermitDepositStatus, Receipt, TxData,
 };
 use zksync_storage::{
-    chain::operations_ext::records::TxReceiptResponse, QueryResult, StorageProcessor,
+    chain::operations_ext::records::TxReceiptResponse,
+    forced_exit_requests::{
+        records::StoredForcedExitRequest, FORCED_EXIT_REQUEST_FULFILLED, FORCED_EXIT_REQUEST_PAID,
+        FORCED_EXIT_REQUEST_PENDING,
+    },
+    permit_deposits::{PERMIT_DEPOSIT_PENDING, PERMIT_DEPOSIT_SENT},
+    QueryResult, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, BatchFee, BlockNumber, Fee, SignedZkSyncTx};
+use zksync_types::{
+    tx::TxHash, Address, BatchFee, BlockNumber, Fee, SignedZkSyncTx, TokenId, H256,
+};
+use zksync_utils::big_decimal_to_ratio;
 // Local uses
 use super::{Error as ApiError, JsonResult, Pagination, PaginationQuery};
 use crate::api_server::rpc_server::types::TxWithSignature;
@@ -30,10 +40,14 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    PermitDepositsDisabled = 107,
+    ForcedExitRequestsDisabled = 108,
 
     Internal = 110,
     CommunicationCoreServer = 111,
     Other = 112,
+    NetworkMismatch = 114,
+    TickerOverloaded = 115,
 }
 
 impl SumbitErrorCode {
@@ -45,6 +59,10 @@ impl SumbitErrorCode {
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::PermitDepositsDisabled => Self::PermitDepositsDisabled,
+            SubmitError::ForcedExitRequestsDisabled => Self::ForcedExitRequestsDisabled,
+            SubmitError::NetworkMismatch(..) => Self::NetworkMismatch,
+            SubmitError::TickerOverloaded => Self::TickerOverloaded,
             SubmitError::CommunicationCoreServer(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
@@ -60,10 +78,10 @@ impl From<SubmitError> for ApiError {
     fn from(inner: SubmitError) -> Self {
         let internal_code = SumbitErrorCode::from_err(&inner).as_code();
 
-        if let SubmitError::Internal(err) = &inner {
-            ApiError::internal(err)
-        } else {
-            ApiError::bad_request(inner)
+        match &inner {
+            SubmitError::Internal(err) => ApiError::internal(err),
+            SubmitError::TickerOverloaded => ApiError::service_unavailable(inner),
+            _ => ApiError::bad_request(inner),
         }
         .code(internal_code)
     }
@@ -176,6 +194,74 @@ impl ApiTransactionsData {
             storage.chain().mempool_schema().get_tx(tx_hash).await
         }
     }
+
+    async fn permit_deposit_status(&self, id: i64) -> QueryResult<Option<PermitDepositStatus>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let request = storage.permit_deposits_schema().get_request(id).await?;
+        let status = request.map(|request| match request.status.as_str() {
+            PERMIT_DEPOSIT_PENDING => PermitDepositStatus::Pending,
+            PERMIT_DEPOSIT_SENT => PermitDepositStatus::Sent {
+                eth_tx_hash: request
+                    .eth_tx_hash
+                    .map(|hash| H256::from_slice(&hash))
+                    .unwrap_or_default(),
+            },
+            _ => PermitDepositStatus::Failed {
+                reason: request.error,
+            },
+        });
+        Ok(status)
+    }
+
+    fn forced_exit_request_info(&self, request: StoredForcedExitRequest) -> ForcedExitRequestInfo {
+        let payment_tx_hash = request
+            .payment_tx_hash
+            .as_deref()
+            .and_then(TxHash::from_slice)
+            .unwrap_or_default();
+        let status = match request.status.as_str() {
+            FORCED_EXIT_REQUEST_PENDING => ForcedExitRequestStatus::Pending,
+            FORCED_EXIT_REQUEST_PAID => ForcedExitRequestStatus::Paid { payment_tx_hash },
+            FORCED_EXIT_REQUEST_FULFILLED => ForcedExitRequestStatus::Fulfilled {
+                payment_tx_hash,
+                fulfilled_by: request
+                    .fulfilled_by
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|hash| hash.parse().ok())
+                    .collect(),
+            },
+            _ => ForcedExitRequestStatus::Expired,
+        };
+
+        ForcedExitRequestInfo {
+            id: request.id,
+            target: Address::from_slice(&request.target),
+            tokens: request
+                .tokens
+                .iter()
+                .map(|&token| TokenId(token as u16))
+                .collect(),
+            requester: Address::from_slice(&request.requester),
+            price_in_wei: big_decimal_to_ratio(&request.price_in_wei)
+                .map(|price| price.to_integer())
+                .unwrap_or_default(),
+            funding_account: self.tx_sender.forced_exit_requests.funding_account_addr,
+            valid_until: request.valid_until,
+            status,
+        }
+    }
+
+    async fn forced_exit_request(&self, id: i64) -> QueryResult<Option<ForcedExitRequestInfo>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let request = storage
+            .forced_exit_requests_schema()
+            .get_request(id)
+            .await?;
+        Ok(request.map(|request| self.forced_exit_request_info(request)))
+    }
 }
 
 // Server implementation
@@ -239,9 +325,17 @@ async fn submit_tx(
     Json(body): Json<IncomingTx>,
     web::Query(query): web::Query<FastProcessingQuery>,
 ) -> JsonResult<TxHash> {
+    data.tx_sender
+        .check_network(body.network)
+        .map_err(ApiError::from)?;
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, query.fast_processing)
+        .submit_tx(
+            body.tx,
+            body.signature,
+            query.fast_processing,
+            body.fee_quote,
+        )
         .await
         .map_err(ApiError::from)?;
 
@@ -252,6 +346,9 @@ async fn submit_tx_batch(
     data: web::Data<ApiTransactionsData>,
     Json(body): Json<IncomingTxBatch>,
 ) -> JsonResult<Vec<TxHash>> {
+    data.tx_sender
+        .check_network(body.network)
+        .map_err(ApiError::from)?;
     let txs = body
         .txs
         .into_iter()
@@ -271,13 +368,63 @@ async fn submit_tx_batch(
     Ok(Json(tx_hashes))
 }
 
+async fn submit_permit_deposit(
+    data: web::Data<ApiTransactionsData>,
+    Json(body): Json<IncomingPermitDeposit>,
+) -> JsonResult<i64> {
+    let request_id = data
+        .tx_sender
+        .submit_permit_deposit(body)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(request_id))
+}
+
+async fn permit_deposit_status(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(request_id): web::Path<i64>,
+) -> JsonResult<Option<PermitDepositStatus>> {
+    let status = data
+        .permit_deposit_status(request_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(status))
+}
+
+async fn submit_forced_exit_request(
+    data: web::Data<ApiTransactionsData>,
+    Json(body): Json<IncomingForcedExitRequest>,
+) -> JsonResult<ForcedExitRequestInfo> {
+    let request = data
+        .tx_sender
+        .submit_forced_exit_request(body)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(data.forced_exit_request_info(request)))
+}
+
+async fn forced_exit_request(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(request_id): web::Path<i64>,
+) -> JsonResult<Option<ForcedExitRequestInfo>> {
+    let request = data
+        .forced_exit_request(request_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(request))
+}
+
 async fn get_txs_fee_in_wei(
     data: web::Data<ApiTransactionsData>,
     Json(body): Json<IncomingTxForFee>,
 ) -> JsonResult<Fee> {
     let fee = data
         .tx_sender
-        .get_txs_fee_in_wei(body.tx_type, body.address, body.token_like)
+        .get_txs_fee_in_wei(body.tx_type, body.address, body.sender, body.token_like)
         .await?;
     Ok(Json(fee))
 }
@@ -314,6 +461,22 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .route("{tx_hash}/receipts", web::get().to(tx_receipts))
         .route("submit", web::post().to(submit_tx))
         .route("submit/batch", web::post().to(submit_tx_batch))
+        .route(
+            "submit/permit_deposit",
+            web::post().to(submit_permit_deposit),
+        )
+        .route(
+            "permit_deposits/{request_id}",
+            web::get().to(permit_deposit_status),
+        )
+        .route(
+            "submit/forced_exit_request",
+            web::post().to(submit_forced_exit_request),
+        )
+        .route(
+            "forced_exit_requests/{request_id}",
+            web::get().to(forced_exit_request),
+        )
         .route("fee/batch", web::post().to(get_txs_batch_fee_in_wei))
         .route("fee", web::post().to(get_txs_fee_in_wei))
 }
@@ -323,13 +486,14 @@ mod tests {
     use actix_web::App;
     use bigdecimal::BigDecimal;
     use futures::{channel::mpsc, StreamExt};
-    use num::BigUint;
+    use num::{BigUint, Zero};
 
     use zksync_api_client::rest::v1::Client;
     use zksync_storage::ConnectionPool;
     use zksync_test_account::ZkSyncAccount;
     use zksync_types::{
-        tokens::{Token, TokenLike},
+        network::Network,
+        tokens::{PriceConfidence, Token, TokenLike},
         tx::{EthBatchSignData, EthBatchSignatures, PackedEthSignature, TxEthSignature},
         AccountId, BlockNumber, Fee, Nonce,
         OutputFeeType::Withdraw,
@@ -339,11 +503,14 @@ mod tests {
     use crate::{
         api_server::helpers::try_parse_tx_hash,
         core_api_client::CoreApiClient,
-        fee_ticker::TickerRequest,
+        fee_ticker::{PriceQuote, TickerRequest},
         signature_checker::{VerifiedTx, VerifyTxSignatureRequest},
     };
 
-    use super::super::test_utils::{TestServerConfig, TestTransactions};
+    use super::super::{
+        test_utils::{TestServerConfig, TestTransactions},
+        ErrorBody,
+    };
     use super::*;
 
     fn submit_txs_loopback() -> (CoreApiClient, actix_web::test::TestServer) {
@@ -381,16 +548,24 @@ mod tests {
                             BigUint::from(1_u64).into(),
                             1_u64.into(),
                             1_u64.into(),
+                            &Default::default(),
                         ));
 
                         response.send(fee).expect("Unable to send response");
                     }
                     TickerRequest::GetTokenPrice { response, .. } => {
-                        let price = Ok(BigDecimal::from(1_u64));
+                        let price = Ok(PriceQuote {
+                            price: BigDecimal::from(1_u64),
+                            source: "test".to_string(),
+                            last_updated: chrono::Utc::now(),
+                            confidence: PriceConfidence::Fresh,
+                        });
 
                         response.send(price).expect("Unable to send response");
                     }
-                    TickerRequest::IsTokenAllowed { token, response } => {
+                    TickerRequest::IsTokenAllowed {
+                        token, response, ..
+                    } => {
                         // For test purposes, PHNX token is not allowed.
                         let is_phnx = match token {
                             TokenLike::Id(id) => *id == 1,
@@ -399,6 +574,10 @@ mod tests {
                         };
                         response.send(Ok(!is_phnx)).unwrap_or_default();
                     }
+                    TickerRequest::GetTokenFeeStatus { .. } => unreachable!(),
+                    TickerRequest::GetTokenLiquidity { .. } => unreachable!(),
+                    TickerRequest::GetTxFeeDryRun { .. } => unreachable!(),
+                    TickerRequest::GetTokenPriceBatch { .. } => unreachable!(),
                     TickerRequest::GetBatchTxFee {
                         response,
                         transactions,
@@ -406,6 +585,10 @@ mod tests {
                     } => {
                         let fee = BatchFee {
                             total_fee: BigUint::from(transactions.len()),
+                            exact_total_fee: BigUint::from(transactions.len()),
+                            capped: false,
+                            waived: false,
+                            subsidy: BigUint::zero(),
                         };
 
                         response.send(Ok(fee)).expect("Unable to send response");
@@ -456,6 +639,7 @@ mod tests {
                     cfg.pool.clone(),
                     sign_verifier.clone(),
                     fee_ticker.clone(),
+                    fee_ticker.clone(),
                     &cfg.config,
                 ))
             });
@@ -777,6 +961,48 @@ mod tests {
     /// - Attempt to submit non-withdraw transaction with the enabled fast-processing.
     /// - Attempt to submit non-withdraw transaction with the disabled fast-processing.
     /// - Attempt to submit withdraw transaction with the enabled fast-processing.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_network_mismatch() -> anyhow::Result<()> {
+        let (_client, server) = TestServer::new().await?;
+
+        let from = ZkSyncAccount::rand();
+        from.set_account_id(Some(AccountId(0xdead)));
+        let to = ZkSyncAccount::rand();
+        let (tx, eth_sig) = from.sign_transfer(
+            TokenId(0),
+            "ETH",
+            10_u64.into(),
+            10_u64.into(),
+            &to.address,
+            None,
+            false,
+            Default::default(),
+        );
+
+        // Test server runs on the localhost network.
+        let submit_url = server.api_server.url("/api/v1/transactions/submit");
+        let response = reqwest::Client::new()
+            .post(&submit_url)
+            .json(&IncomingTx {
+                tx: ZkSyncTx::Transfer(Box::new(tx)),
+                signature: Some(TxEthSignature::EthereumSignature(eth_sig)),
+                network: Some(Network::Mainnet),
+                fee_quote: None,
+            })
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        let error: ErrorBody = response.json().await?;
+        assert_eq!(error.code, Some(SumbitErrorCode::NetworkMismatch.as_code()));
+
+        server.stop().await;
+        Ok(())
+    }
+
     #[actix_rt::test]
     #[cfg_attr(
         not(feature = "api_test"),
@@ -20,6 +20,9 @@ pub enum RpcErrorCodes {
     AccountCloseDisabled = 301,
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
+    FeatureDisabled = 304,
+    NetworkMismatch = 305,
+    TickerOverloaded = 306,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -80,6 +83,21 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::PermitDepositsDisabled | SubmitError::ForcedExitRequestsDisabled => Self {
+                code: RpcErrorCodes::FeatureDisabled.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::NetworkMismatch(..) => Self {
+                code: RpcErrorCodes::NetworkMismatch.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::TickerOverloaded => Self {
+                code: RpcErrorCodes::TickerOverloaded.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::CommunicationCoreServer(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
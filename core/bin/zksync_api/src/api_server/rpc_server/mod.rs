@@ -24,7 +24,7 @@ use zksync_types::{tx::TxHash, Address, BatchFee, BlockNumber, Fee, TokenLike, T
 use crate::{
     fee_ticker::{TickerRequest, TokenPriceRequestType},
     signature_checker::VerifyTxSignatureRequest,
-    utils::shared_lru_cache::SharedLruCache,
+    utils::{account_state_cache::AccountStateCache, shared_lru_cache::SharedLruCache},
 };
 use bigdecimal::BigDecimal;
 use zksync_utils::panic_notify::ThreadPanicNotify;
@@ -46,6 +46,7 @@ pub struct RpcApp {
     cache_of_blocks_info: SharedLruCache<i64, BlockDetails>,
     cache_of_transaction_receipts: SharedLruCache<Vec<u8>, TxReceiptResponse>,
     cache_of_complete_withdrawal_tx_hashes: SharedLruCache<TxHash, String>,
+    cache_of_account_states: Option<AccountStateCache>,
 
     pub confirmations_for_eth_event: u64,
 
@@ -57,6 +58,7 @@ impl RpcApp {
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
         ticker_request_sender: mpsc::Sender<TickerRequest>,
+        ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
         config: &ZkSyncConfig,
     ) -> Self {
         let runtime_handle = tokio::runtime::Handle::try_current()
@@ -65,10 +67,23 @@ impl RpcApp {
         let api_requests_caches_size = config.api.common.caches_size;
         let confirmations_for_eth_event = config.eth_watch.confirmations_for_eth_event;
 
+        let cache_of_account_states = if config.api.common.account_state_cache_enabled {
+            let cache = AccountStateCache::new(api_requests_caches_size);
+            cache.spawn_invalidator(
+                &runtime_handle,
+                connection_pool.clone(),
+                config.api.common.account_state_cache_poll_interval(),
+            );
+            Some(cache)
+        } else {
+            None
+        };
+
         let tx_sender = TxSender::new(
             connection_pool,
             sign_verify_request_sender,
             ticker_request_sender,
+            ticker_priority_request_sender,
             config,
         );
 
@@ -79,6 +94,7 @@ impl RpcApp {
             cache_of_blocks_info: SharedLruCache::new(api_requests_caches_size),
             cache_of_transaction_receipts: SharedLruCache::new(api_requests_caches_size),
             cache_of_complete_withdrawal_tx_hashes: SharedLruCache::new(api_requests_caches_size),
+            cache_of_account_states,
 
             confirmations_for_eth_event,
 
@@ -254,6 +270,7 @@ impl RpcApp {
             .send(TickerRequest::IsTokenAllowed {
                 token: token.clone(),
                 response: sender,
+                span: tracing::Span::current(),
             })
             .await
             .expect("ticker receiver dropped");
@@ -277,6 +294,7 @@ impl RpcApp {
                 transactions,
                 token: token.clone(),
                 response: req.0,
+                span: tracing::Span::current(),
             })
             .await
             .expect("ticker receiver dropped");
@@ -291,6 +309,7 @@ impl RpcApp {
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         tx_type: TxFeeTypes,
         address: Address,
+        sender: Option<Address>,
         token: TokenLike,
     ) -> Result<Fee> {
         let req = oneshot::channel();
@@ -298,8 +317,10 @@ impl RpcApp {
             .send(TickerRequest::GetTxFee {
                 tx_type,
                 address,
+                sender,
                 token: token.clone(),
                 response: req.0,
+                span: tracing::Span::current(),
             })
             .await
             .expect("ticker receiver dropped");
@@ -326,11 +347,12 @@ impl RpcApp {
                 token: token.clone(),
                 response: req.0,
                 req_type,
+                span: tracing::Span::current(),
             })
             .await
             .expect("ticker receiver dropped");
         let resp = req.1.await.expect("ticker answer sender dropped");
-        resp.map_err(|err| {
+        resp.map(|quote| quote.price).map_err(|err| {
             vlog::warn!("Internal Server Error: '{}'; input: {:?}", err, token);
             Error::internal_error()
         })
@@ -339,12 +361,16 @@ impl RpcApp {
     async fn get_account_state(&self, address: Address) -> Result<AccountStateInfo> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
-        let account_info = storage
-            .chain()
-            .account_schema()
-            .account_state_by_address(address)
-            .await
-            .map_err(|_| Error::internal_error())?;
+        let account_info = if let Some(cache) = &self.cache_of_account_states {
+            cache.account_state_by_address(&mut storage, address).await
+        } else {
+            storage
+                .chain()
+                .account_schema()
+                .account_state_by_address(address)
+                .await
+        }
+        .map_err(|_| Error::internal_error())?;
 
         let mut result = AccountStateInfo {
             account_id: None,
@@ -414,6 +440,7 @@ pub fn start_rpc_server(
     connection_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
@@ -423,6 +450,7 @@ pub fn start_rpc_server(
         connection_pool,
         sign_verify_request_sender,
         ticker_request_sender,
+        ticker_priority_request_sender,
         &config,
     );
     std::thread::spawn(move || {
@@ -5,8 +5,9 @@ use bigdecimal::BigDecimal;
 use jsonrpc_core::{Error, Result};
 // Workspace uses
 use zksync_types::{
+    network::Network,
     tx::{EthBatchSignatures, TxEthSignature, TxHash},
-    Address, BatchFee, Fee, Token, TokenLike, TxFeeTypes, ZkSyncTx,
+    Address, BatchFee, Fee, FeeQuote, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
 // Local uses
@@ -97,11 +98,14 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        network: Option<Network>,
+        fee_quote: Option<FeeQuote>,
     ) -> Result<TxHash> {
         let start = Instant::now();
+        self.tx_sender.check_network(network)?;
         let result = self
             .tx_sender
-            .submit_tx(*tx, *signature, fast_processing)
+            .submit_tx(*tx, *signature, fast_processing, fee_quote)
             .await
             .map_err(Error::from);
         metrics::histogram!("api.rpc.tx_submit", start.elapsed());
@@ -112,8 +116,10 @@ impl RpcApp {
         self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        network: Option<Network>,
     ) -> Result<Vec<TxHash>> {
         let start = Instant::now();
+        self.tx_sender.check_network(network)?;
         let result = self
             .tx_sender
             .submit_txs_batch(txs, eth_signatures)
@@ -150,6 +156,7 @@ impl RpcApp {
         Ok(ContractAddressResp {
             main_contract,
             gov_contract,
+            network: self.tx_sender.network,
         })
     }
 
@@ -199,6 +206,7 @@ impl RpcApp {
         tx_type: TxFeeTypes,
         address: Address,
         token: TokenLike,
+        sender: Option<Address>,
     ) -> Result<Fee> {
         let start = Instant::now();
         let ticker = self.tx_sender.ticker_requests.clone();
@@ -207,7 +215,7 @@ impl RpcApp {
             return Err(SubmitError::InappropriateFeeToken.into());
         }
 
-        let result = Self::ticker_request(ticker.clone(), tx_type, address, token).await;
+        let result = Self::ticker_request(ticker.clone(), tx_type, address, sender, token).await;
         metrics::histogram!("api.rpc.get_tx_fee", start.elapsed());
         result
     }
@@ -4,16 +4,19 @@ use bigdecimal::BigDecimal;
 use futures::{FutureExt, TryFutureExt};
 use jsonrpc_core::Error;
 use jsonrpc_derive::rpc;
+use tracing::Instrument;
 
 // Workspace uses
 use zksync_crypto::params::ZKSYNC_VERSION;
 use zksync_types::{
+    network::Network,
     tx::{EthBatchSignatures, TxEthSignature, TxHash},
-    Address, BatchFee, Fee, Token, TokenLike, TxFeeTypes, ZkSyncTx,
+    Address, BatchFee, Fee, FeeQuote, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
 // Local uses
 use super::{types::*, RpcApp};
+use crate::fee_ticker::correlation::api_request_span;
 
 pub type FutureResp<T> = Box<dyn futures01::Future<Item = T, Error = Error> + Send>;
 
@@ -34,6 +37,8 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        network: Option<Network>,
+        fee_quote: Option<FeeQuote>,
     ) -> FutureResp<TxHash>;
 
     #[rpc(name = "submit_txs_batch", returns = "Vec<TxHash>")]
@@ -41,6 +46,7 @@ pub trait Rpc {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        network: Option<Network>,
     ) -> FutureResp<Vec<TxHash>>;
 
     #[rpc(name = "contract_address", returns = "ContractAddressResp")]
@@ -51,12 +57,14 @@ pub trait Rpc {
     fn tokens(&self) -> FutureResp<HashMap<String, Token>>;
 
     // _address argument is left for the backward compatibility.
+    // Fee discounts of the sender are applied if it's provided.
     #[rpc(name = "get_tx_fee", returns = "Fee")]
     fn get_tx_fee(
         &self,
         tx_type: TxFeeTypes,
         _address: Address,
         token_like: TokenLike,
+        sender: Option<Address>,
     ) -> FutureResp<Fee>;
 
     // _addresses argument is left for the backward compatibility.
@@ -113,12 +121,18 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        network: Option<Network>,
+        fee_quote: Option<FeeQuote>,
     ) -> FutureResp<TxHash> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_tx_submit(tx, signature, fast_processing))
+                .spawn(
+                    self_
+                        ._impl_tx_submit(tx, signature, fast_processing, network, fee_quote)
+                        .instrument(api_request_span("tx_submit", None)),
+                )
                 .await
                 .unwrap()
         };
@@ -129,12 +143,17 @@ impl Rpc for RpcApp {
         &self,
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
+        network: Option<Network>,
     ) -> FutureResp<Vec<TxHash>> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_submit_txs_batch(txs, eth_signatures))
+                .spawn(
+                    self_
+                        ._impl_submit_txs_batch(txs, eth_signatures, network)
+                        .instrument(api_request_span("submit_txs_batch", None)),
+                )
                 .await
                 .unwrap()
         };
@@ -160,12 +179,17 @@ impl Rpc for RpcApp {
         tx_type: TxFeeTypes,
         address: Address,
         token_like: TokenLike,
+        sender: Option<Address>,
     ) -> FutureResp<Fee> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_get_tx_fee(tx_type, address, token_like))
+                .spawn(
+                    self_
+                        ._impl_get_tx_fee(tx_type, address, token_like, sender)
+                        .instrument(api_request_span("get_tx_fee", None)),
+                )
                 .await
                 .unwrap()
         };
@@ -182,7 +206,11 @@ impl Rpc for RpcApp {
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_get_txs_batch_fee_in_wei(tx_types, addresses, token_like))
+                .spawn(
+                    self_
+                        ._impl_get_txs_batch_fee_in_wei(tx_types, addresses, token_like)
+                        .instrument(api_request_span("get_txs_batch_fee_in_wei", None)),
+                )
                 .await
                 .unwrap()
         };
@@ -194,7 +222,11 @@ impl Rpc for RpcApp {
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_get_token_price(token_like))
+                .spawn(
+                    self_
+                        ._impl_get_token_price(token_like)
+                        .instrument(api_request_span("get_token_price", None)),
+                )
                 .await
                 .unwrap()
         };
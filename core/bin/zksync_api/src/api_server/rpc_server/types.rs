@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_storage::StorageProcessor;
 use zksync_types::{
-    tx::TxEthSignature, Account, AccountId, Address, Nonce, PriorityOp, PubKeyHash, TokenId,
-    ZkSyncPriorityOp, ZkSyncTx,
+    network::Network, tx::TxEthSignature, Account, AccountId, Address, Nonce, PriorityOp,
+    PubKeyHash, TokenId, ZkSyncPriorityOp, ZkSyncTx,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
@@ -169,6 +169,8 @@ pub struct ETHOpInfoResp {
 pub struct ContractAddressResp {
     pub main_contract: String,
     pub gov_contract: String,
+    /// Network the server runs on.
+    pub network: Network,
 }
 
 /// Flattened `PriorityOp` object representing a deposit operation.
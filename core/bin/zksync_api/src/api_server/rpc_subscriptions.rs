@@ -10,9 +10,12 @@ use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
 use jsonrpc_ws_server::RequestContext;
 // Workspace uses
 use zksync_storage::ConnectionPool;
-use zksync_types::{tx::TxHash, ActionType, Address};
+use zksync_types::{tx::TxHash, ActionType, Address, TokenLike};
 // Local uses
-use crate::fee_ticker::TickerRequest;
+use crate::fee_ticker::{
+    price_feed::{run_price_feed, PriceFeed, TokenPriceUpdate},
+    TickerRequest,
+};
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
     api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
@@ -79,6 +82,30 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    /// Pushes the price of the token and the fees paid in it every time its quote changes.
+    #[pubsub(
+        subscription = "token_price",
+        subscribe,
+        name = "token_price_subscribe",
+        alias("token_price_sub")
+    )]
+    fn subscribe_token_price(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<TokenPriceUpdate>,
+        token: TokenLike,
+    );
+    #[pubsub(
+        subscription = "token_price",
+        unsubscribe,
+        name = "token_price_unsubscribe"
+    )]
+    fn unsubscribe_token_price(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -167,6 +194,32 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_token_price(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<TokenPriceUpdate>,
+        token: TokenLike,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::TokenPrice { token, subscriber },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_token_price(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -178,6 +231,7 @@ pub fn start_ws_server(
     db_pool: ConnectionPool,
     sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
     ticker_request_sender: mpsc::Sender<TickerRequest>,
+    ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
@@ -185,17 +239,25 @@ pub fn start_ws_server(
 
     let (event_sub_sender, event_sub_receiver) = mpsc::channel(2048);
 
+    let price_feed = PriceFeed::new();
+    tokio::spawn(vlog::supervised(
+        "price_feed",
+        run_price_feed(price_feed.clone(), ticker_request_sender.clone()),
+    ));
+
     start_sub_notifier(
         db_pool.clone(),
         event_sub_receiver,
         config.api.common.caches_size,
         config.chain.state_keeper.miniblock_iteration_interval(),
+        price_feed,
     );
 
     let req_rpc_app = super::rpc_server::RpcApp::new(
         db_pool,
         sign_verify_request_sender,
         ticker_request_sender,
+        ticker_priority_request_sender,
         config,
     );
 
@@ -15,20 +15,30 @@ use num::{bigint::ToBigInt, BigUint, Zero};
 use thiserror::Error;
 
 // Workspace uses
-use zksync_config::ZkSyncConfig;
-use zksync_storage::{chain::account::records::EthAccountType, ConnectionPool};
+use zksync_api_client::rest::v1::{IncomingForcedExitRequest, IncomingPermitDeposit};
+use zksync_config::{configs::api::ForcedExitRequests, ZkSyncConfig};
+use zksync_storage::{
+    chain::account::records::EthAccountType,
+    fee_subsidies::records::NewSubsidySpend,
+    forced_exit_requests::records::{NewForcedExitRequest, StoredForcedExitRequest},
+    permit_deposits::records::NewPermitDeposit,
+    ConnectionPool,
+};
 use zksync_types::{
+    helpers::closest_greater_or_eq_packable_token_amount,
+    network::Network,
     tx::{
         EthBatchSignData, EthBatchSignatures, EthSignData, SignedZkSyncTx, TxEthSignature, TxHash,
     },
-    Address, BatchFee, Fee, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
+    Address, BatchFee, Fee, FeeQuote, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx,
+    H160,
 };
 
 // Local uses
 use crate::api_server::rpc_server::types::TxWithSignature;
 use crate::{
     core_api_client::CoreApiClient,
-    fee_ticker::{TickerRequest, TokenPriceRequestType},
+    fee_ticker::{quote::FeeQuoteSigner, TickerOverloaded, TickerRequest, TokenPriceRequestType},
     signature_checker::{TxVariant, VerifiedTx, VerifyTxSignatureRequest},
     tx_error::TxAddError,
     utils::token_db_cache::TokenDBCache,
@@ -39,6 +49,9 @@ pub struct TxSender {
     pub core_api_client: CoreApiClient,
     pub sign_verify_requests: mpsc::Sender<VerifyTxSignatureRequest>,
     pub ticker_requests: mpsc::Sender<TickerRequest>,
+    /// Sender of the fee ticker requests handled ahead of the public ones, used to check
+    /// the fees of the submitted transactions.
+    pub ticker_priority_requests: mpsc::Sender<TickerRequest>,
 
     pub pool: ConnectionPool,
     pub tokens: TokenDBCache,
@@ -48,8 +61,22 @@ pub struct TxSender {
     // Limit the number of both transactions and Ethereum signatures per batch.
     pub max_number_of_transactions_per_batch: usize,
     pub max_number_of_authors_per_batch: usize,
+    /// Whether the deposits authorized by the permit signatures are accepted.
+    pub permit_deposits_enabled: bool,
+    /// Configuration of the paid forced exit requests.
+    pub forced_exit_requests: ForcedExitRequests,
+    /// Network the server runs on.
+    pub network: Network,
+    /// Verifier of the signed fee quotes, if they are enabled.
+    pub fee_quote_signer: Option<FeeQuoteSigner>,
 }
 
+/// Minimum time left until the permit deadline (in seconds) for the deposit to be accepted,
+/// so the relayer has enough time to send it to L1.
+const PERMIT_DEADLINE_MIN_MARGIN_SECS: i64 = 5 * 60;
+/// Maximum value of the `uint104` type used for the deposit amounts in the contract.
+const MAX_DEPOSIT_AMOUNT_BITS: u64 = 104;
+
 #[derive(Debug, Error)]
 pub enum SubmitError {
     #[error("Account close tx is disabled.")]
@@ -64,6 +91,14 @@ pub enum SubmitError {
     TxAdd(TxAddError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Permit-based deposits are disabled.")]
+    PermitDepositsDisabled,
+    #[error("Forced exit requests are disabled.")]
+    ForcedExitRequestsDisabled,
+    #[error("Transaction is intended for the {0} network, while the server runs on {1}.")]
+    NetworkMismatch(Network, Network),
+    #[error("Fee ticker is overloaded, try again later.")]
+    TickerOverloaded,
 
     #[error("Communication error with the core server: {0}.")]
     CommunicationCoreServer(String),
@@ -89,6 +124,14 @@ impl SubmitError {
     fn invalid_params(msg: impl Display) -> Self {
         Self::InvalidParams(msg.to_string())
     }
+
+    fn ticker(err: anyhow::Error) -> Self {
+        if err.is::<TickerOverloaded>() {
+            return Self::TickerOverloaded;
+        }
+        vlog::warn!("Internal Server error: {}, input: N/A", err);
+        Self::internal(err)
+    }
 }
 
 macro_rules! internal_error {
@@ -107,6 +150,7 @@ impl TxSender {
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
         ticker_request_sender: mpsc::Sender<TickerRequest>,
+        ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
         config: &ZkSyncConfig,
     ) -> Self {
         let core_api_client = CoreApiClient::new(config.api.private.url.clone());
@@ -116,6 +160,7 @@ impl TxSender {
             connection_pool,
             sign_verify_request_sender,
             ticker_request_sender,
+            ticker_priority_request_sender,
             config,
         )
     }
@@ -125,6 +170,7 @@ impl TxSender {
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
         ticker_request_sender: mpsc::Sender<TickerRequest>,
+        ticker_priority_request_sender: mpsc::Sender<TickerRequest>,
         config: &ZkSyncConfig,
     ) -> Self {
         let forced_exit_minimum_account_age = chrono::Duration::seconds(
@@ -141,12 +187,28 @@ impl TxSender {
             pool: connection_pool,
             sign_verify_requests: sign_verify_request_sender,
             ticker_requests: ticker_request_sender,
+            ticker_priority_requests: ticker_priority_request_sender,
             tokens: TokenDBCache::new(),
 
             enforce_pubkey_change_fee: config.api.common.enforce_pubkey_change_fee,
             forced_exit_minimum_account_age,
             max_number_of_transactions_per_batch,
             max_number_of_authors_per_batch,
+            permit_deposits_enabled: config.api.permit_relayer.enabled,
+            forced_exit_requests: config.api.forced_exit_requests.clone(),
+            network: config.chain.eth.network,
+            fee_quote_signer: FeeQuoteSigner::from_config(&config.ticker),
+        }
+    }
+
+    /// Checks that the transactions intended for the specific network are submitted
+    /// to the server of this network.
+    pub fn check_network(&self, network: Option<Network>) -> Result<(), SubmitError> {
+        match network {
+            Some(network) if network != self.network => {
+                Err(SubmitError::NetworkMismatch(network, self.network))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -187,6 +249,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: Option<TxEthSignature>,
         fast_processing: Option<bool>,
+        fee_quote: Option<FeeQuote>,
     ) -> Result<TxHash, SubmitError> {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
@@ -224,8 +287,11 @@ impl TxSender {
         let tx_fee_info = tx.get_fee_info();
 
         let sign_verify_channel = self.sign_verify_requests.clone();
-        let ticker_request_sender = self.ticker_requests.clone();
+        let ticker_request_sender = self.ticker_priority_requests.clone();
 
+        // Subsidy covering the fee required from the transaction, recorded once it's accepted.
+        // Only the subsidies of the successfully executed transactions are counted in the reports.
+        let mut subsidy_spend = None;
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
             let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
                 || self.enforce_pubkey_change_fee;
@@ -234,28 +300,72 @@ impl TxSender {
                 Self::token_allowed_for_fees(ticker_request_sender.clone(), token.clone()).await?;
 
             if !fee_allowed {
+                report_fee_rejection("inappropriate_fee_token", &token);
                 return Err(SubmitError::InappropriateFeeToken);
             }
 
-            let required_fee =
-                Self::ticker_request(ticker_request_sender, tx_type, address, token.clone())
-                    .await?;
-            // Converting `BitUint` to `BigInt` is safe.
-            let required_fee: BigDecimal = required_fee.total_fee.to_bigint().unwrap().into();
-            let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
-            // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee = scale_user_fee_up(provided_fee.clone());
-            if required_fee >= scaled_provided_fee && should_enforce_fee {
-                vlog::error!(
-                    "User provided fee is too low, required: {}, provided: {} (scaled: {}); difference {}, token: {:?}",
-                    required_fee.to_string(),
-                    provided_fee.to_string(),
-                    scaled_provided_fee.to_string(),
-                    (&required_fee - &scaled_provided_fee).to_string(),
-                    token
-                );
+            // Exactly the quoted fee is accepted until the signed quote expires.
+            let quote_accepted = match (&fee_quote, &self.fee_quote_signer, &token) {
+                (Some(quote), Some(signer), TokenLike::Id(token_id)) => signer.verify(
+                    quote,
+                    tx_type,
+                    address,
+                    tx.account(),
+                    *token_id,
+                    &provided_fee,
+                ),
+                _ => false,
+            };
+            if let Some(quote) = &fee_quote {
+                vlog::debug!("Fee quote {} accepted: {}", quote.id, quote_accepted);
+            }
+
+            let mut subsidy = BigUint::zero();
+            if quote_accepted {
+                // The signed quote carries the subsidy the quoted fee was calculated with.
+                if let Some(quote) = &fee_quote {
+                    subsidy = quote.subsidy.clone();
+                }
+            } else {
+                let required_fee = Self::ticker_request(
+                    ticker_request_sender,
+                    tx_type,
+                    address,
+                    Some(tx.account()),
+                    token.clone(),
+                )
+                .await?;
+                // Waived fee is accepted regardless of the provided amount.
+                let fee_waived = required_fee.waived;
+                subsidy = required_fee.subsidy.clone();
+                // Converting `BitUint` to `BigInt` is safe.
+                let required_fee: BigDecimal = required_fee.total_fee.to_bigint().unwrap().into();
+                let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
+                // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
+                let scaled_provided_fee = scale_user_fee_up(provided_fee.clone());
+                if required_fee >= scaled_provided_fee && should_enforce_fee && !fee_waived {
+                    vlog::error!(
+                        "User provided fee is too low, required: {}, provided: {} (scaled: {}); difference {}, token: {:?}",
+                        required_fee.to_string(),
+                        provided_fee.to_string(),
+                        scaled_provided_fee.to_string(),
+                        (&required_fee - &scaled_provided_fee).to_string(),
+                        token
+                    );
+
+                    report_fee_rejection("fee_too_low", &token);
+                    return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                }
+            }
 
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+            if !subsidy.is_zero() {
+                subsidy_spend = Some(NewSubsidySpend {
+                    tx_hash: tx.hash().as_ref().to_vec(),
+                    token_id: i32::from(*tx.token_id()),
+                    fee_type: serde_json::to_value(&tx_type).map_err(SubmitError::internal)?,
+                    paid_fee: provided_fee.to_bigint().unwrap().into(),
+                    subsidy: subsidy.to_bigint().unwrap().into(),
+                });
             }
         }
 
@@ -282,10 +392,33 @@ impl TxSender {
             .await
             .map_err(SubmitError::communication_core_server)?
             .map_err(SubmitError::TxAdd)?;
+        if let Some(subsidy_spend) = subsidy_spend {
+            self.record_subsidy_spend(subsidy_spend).await;
+        }
         // if everything is OK, return the transactions hashes.
         Ok(tx.hash())
     }
 
+    /// Records the subsidy spent on the accepted transaction. The transaction is in the mempool
+    /// already, so the failure is only logged.
+    async fn record_subsidy_spend(&self, subsidy_spend: NewSubsidySpend) {
+        let result: anyhow::Result<()> = async {
+            self.pool
+                .access_storage()
+                .await?
+                .fee_subsidies_schema()
+                .store_subsidy_spend(subsidy_spend)
+                .await
+        }
+        .await;
+        if let Err(e) = result {
+            vlog::warn!(
+                "Failed to record the subsidy spent on the transaction: {}",
+                e
+            );
+        }
+    }
+
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -329,13 +462,16 @@ impl TxSender {
                 if provided_fee == BigUint::zero() {
                     continue;
                 }
-                let fee_allowed =
-                    Self::token_allowed_for_fees(self.ticker_requests.clone(), token.clone())
-                        .await?;
+                let fee_allowed = Self::token_allowed_for_fees(
+                    self.ticker_priority_requests.clone(),
+                    token.clone(),
+                )
+                .await?;
 
                 // In batches, transactions with non-popular token are allowed to be included, but should not
                 // used to pay fees. Fees must be covered by some more common token.
                 if !fee_allowed && provided_fee != 0u64.into() {
+                    report_fee_rejection("inappropriate_fee_token", &token);
                     return Err(SubmitError::InappropriateFeeToken);
                 }
 
@@ -349,7 +485,7 @@ impl TxSender {
                 };
 
                 let token_price_in_usd = Self::ticker_price_request(
-                    self.ticker_requests.clone(),
+                    self.ticker_priority_requests.clone(),
                     check_token.clone(),
                     TokenPriceRequestType::USDForOneWei,
                 )
@@ -362,16 +498,20 @@ impl TxSender {
         }
 
         // Calculate required fee for ethereum token
+        let batch_fee_types: Vec<_> = transaction_types
+            .iter()
+            .map(|(tx_type, _)| *tx_type)
+            .collect();
         let required_eth_fee = Self::ticker_batch_fee_request(
-            self.ticker_requests.clone(),
+            self.ticker_priority_requests.clone(),
             transaction_types,
             eth_token.clone(),
         )
         .await?;
 
         let eth_price_in_usd = Self::ticker_price_request(
-            self.ticker_requests.clone(),
-            eth_token,
+            self.ticker_priority_requests.clone(),
+            eth_token.clone(),
             TokenPriceRequestType::USDForOneWei,
         )
         .await?;
@@ -381,7 +521,7 @@ impl TxSender {
 
         // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
         let scaled_provided_fee_in_usd = scale_user_fee_up(provided_total_usd_fee.clone());
-        if required_total_usd_fee >= scaled_provided_fee_in_usd {
+        if required_total_usd_fee >= scaled_provided_fee_in_usd && !required_eth_fee.waived {
             vlog::error!(
                 "User provided batch fee is too low, required: {}, provided: {} (scaled: {}); difference {}",
                 required_total_usd_fee.to_string(),
@@ -389,6 +529,8 @@ impl TxSender {
                 scaled_provided_fee_in_usd.to_string(),
                 (required_total_usd_fee.clone() - scaled_provided_fee_in_usd.clone()).to_string(),
             );
+            // The batch fee is checked in ETH regardless of the tokens it's paid in.
+            report_fee_rejection("batch_fee_too_low", &eth_token);
             return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
         }
 
@@ -492,16 +634,207 @@ impl TxSender {
             .map_err(SubmitError::communication_core_server)?
             .map_err(SubmitError::TxAdd)?;
 
+        if !required_eth_fee.subsidy.is_zero() {
+            // The batch fee and its subsidy are calculated in ETH. The batch is executed as a whole,
+            // so the spend is recorded for its first transaction.
+            let paid_fee = if eth_price_in_usd.is_zero() {
+                BigDecimal::zero()
+            } else {
+                (provided_total_usd_fee / eth_price_in_usd).with_scale(0)
+            };
+            let subsidy_spend = NewSubsidySpend {
+                tx_hash: tx_hashes[0].as_ref().to_vec(),
+                token_id: 0,
+                fee_type: serde_json::to_value(&batch_fee_types).map_err(SubmitError::internal)?,
+                paid_fee,
+                subsidy: required_eth_fee.subsidy.to_bigint().unwrap().into(),
+            };
+            self.record_subsidy_spend(subsidy_spend).await;
+        }
+
         Ok(tx_hashes)
     }
 
+    /// Accepts the deposit authorized by the EIP-2612 permit signature and stores it to be sent to L1
+    /// by the relayer. The fee paid to the relayer is checked the same way as the fee of L2 transactions.
+    /// Returns the ID of the stored deposit request.
+    ///
+    /// The permit signature itself is verified by the token contract once the deposit is relayed.
+    pub async fn submit_permit_deposit(
+        &self,
+        deposit: IncomingPermitDeposit,
+    ) -> Result<i64, SubmitError> {
+        if !self.permit_deposits_enabled {
+            return Err(SubmitError::PermitDepositsDisabled);
+        }
+
+        let token = {
+            let mut storage = self
+                .pool
+                .access_storage()
+                .await
+                .map_err(SubmitError::internal)?;
+            self.tokens
+                .get_token(&mut storage, deposit.token_like.clone())
+                .await
+                .map_err(SubmitError::internal)?
+                .ok_or_else(|| SubmitError::other("Token not found in the DB"))?
+        };
+        if token.id.0 == 0 {
+            return Err(SubmitError::invalid_params(
+                "ETH deposits can't be authorized by the permit",
+            ));
+        }
+        if (&deposit.amount + &deposit.fee).bits() > MAX_DEPOSIT_AMOUNT_BITS {
+            return Err(SubmitError::invalid_params("Deposit amount is too big"));
+        }
+        if deposit.amount.is_zero() {
+            return Err(SubmitError::invalid_params("Deposit amount is zero"));
+        }
+
+        let min_deadline = Utc::now().timestamp() + PERMIT_DEADLINE_MIN_MARGIN_SECS;
+        if (deposit.deadline as i64) < min_deadline {
+            return Err(SubmitError::invalid_params(
+                "Permit deadline is too close or has already passed",
+            ));
+        }
+
+        let token_like = TokenLike::Id(token.id);
+        let fee_allowed =
+            Self::token_allowed_for_fees(self.ticker_priority_requests.clone(), token_like.clone())
+                .await?;
+        if !fee_allowed {
+            report_fee_rejection("inappropriate_fee_token", &token_like);
+            return Err(SubmitError::InappropriateFeeToken);
+        }
+
+        let required_fee = Self::ticker_request(
+            self.ticker_priority_requests.clone(),
+            TxFeeTypes::PermitDeposit,
+            deposit.owner,
+            Some(deposit.owner),
+            token_like,
+        )
+        .await?;
+        let fee_waived = required_fee.waived;
+        // Converting `BitUint` to `BigInt` is safe.
+        let required_fee: BigDecimal = required_fee.total_fee.to_bigint().unwrap().into();
+        let provided_fee: BigDecimal = deposit.fee.to_bigint().unwrap().into();
+        // Scaling the fee required since the price may change between signing the permit and sending it to the server.
+        let scaled_provided_fee = scale_user_fee_up(provided_fee.clone());
+        if required_fee >= scaled_provided_fee && !fee_waived {
+            vlog::error!(
+                "User provided permit deposit fee is too low, required: {}, provided: {} (scaled: {}); token: {:?}",
+                required_fee.to_string(),
+                provided_fee.to_string(),
+                scaled_provided_fee.to_string(),
+                token.symbol
+            );
+
+            report_fee_rejection("fee_too_low", &TokenLike::Id(token.id));
+            return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+        }
+
+        let request = NewPermitDeposit {
+            token_id: token.id.0 as i32,
+            owner: deposit.owner.as_bytes().to_vec(),
+            amount: deposit.amount.to_bigint().unwrap().into(),
+            fee: provided_fee,
+            deadline: deposit.deadline as i64,
+            signature: deposit.signature.serialize_packed().to_vec(),
+        };
+        let stored = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .permit_deposits_schema()
+            .store_request(request)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        Ok(stored.id)
+    }
+
+    /// Accepts the request to withdraw the funds of the account which signing key is not set,
+    /// and stores it to be fulfilled once the requester pays for it. The price is the sum of the
+    /// `ForcedExit` fees for all the requested tokens, scaled by the configured factor.
+    pub async fn submit_forced_exit_request(
+        &self,
+        request: IncomingForcedExitRequest,
+    ) -> Result<StoredForcedExitRequest, SubmitError> {
+        if !self.forced_exit_requests.enabled {
+            return Err(SubmitError::ForcedExitRequestsDisabled);
+        }
+
+        let max_tokens = self.forced_exit_requests.max_tokens_per_request as usize;
+        if request.tokens.is_empty() || request.tokens.len() > max_tokens {
+            return Err(SubmitError::InvalidParams(format!(
+                "Request must contain from 1 to {} tokens",
+                max_tokens
+            )));
+        }
+        let unique_tokens: HashSet<_> = request.tokens.iter().collect();
+        if unique_tokens.len() != request.tokens.len() {
+            return Err(SubmitError::invalid_params("Tokens must not repeat"));
+        }
+        for &token in &request.tokens {
+            self.token_info_from_id(token).await?;
+        }
+        self.check_forced_exit_target(request.target).await?;
+
+        let forced_exit_fee = Self::ticker_request(
+            self.ticker_priority_requests.clone(),
+            TxFeeTypes::ForcedExit,
+            request.target,
+            None,
+            TokenLike::Id(TokenId(0)),
+        )
+        .await?
+        .total_fee;
+        // The scaling factor is applied with the per mille precision, rounding the price up.
+        let scaling_factor =
+            (self.forced_exit_requests.price_scaling_factor * 1000.0).round() as u64;
+        let price = (forced_exit_fee * BigUint::from(request.tokens.len()) * scaling_factor
+            + BigUint::from(999u32))
+            / BigUint::from(1000u32);
+        let price = closest_greater_or_eq_packable_token_amount(&price);
+
+        let valid_until = Utc::now()
+            + chrono::Duration::from_std(self.forced_exit_requests.request_lifetime())
+                .map_err(SubmitError::internal)?;
+        let new_request = NewForcedExitRequest {
+            target: request.target.as_bytes().to_vec(),
+            tokens: request.tokens.iter().map(|token| token.0 as i32).collect(),
+            requester: request.requester.as_bytes().to_vec(),
+            price_in_wei: price.to_bigint().unwrap().into(),
+            valid_until,
+        };
+        self.pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .forced_exit_requests_schema()
+            .store_request(new_request)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
     pub async fn get_txs_fee_in_wei(
         &self,
         tx_type: TxFeeTypes,
         address: Address,
+        sender: Option<Address>,
         token: TokenLike,
     ) -> Result<Fee, SubmitError> {
-        Self::ticker_request(self.ticker_requests.clone(), tx_type, address, token).await
+        Self::ticker_request(
+            self.ticker_requests.clone(),
+            tx_type,
+            address,
+            sender,
+            token,
+        )
+        .await
     }
 
     pub async fn get_txs_batch_fee_in_wei(
@@ -521,20 +854,22 @@ impl TxSender {
         &self,
         forced_exit: &zksync_types::ForcedExit,
     ) -> Result<(), SubmitError> {
+        self.check_forced_exit_age(forced_exit.target).await
+    }
+
+    async fn check_forced_exit_age(&self, target: Address) -> Result<(), SubmitError> {
         let mut storage = self
             .pool
             .access_storage()
             .await
             .map_err(SubmitError::internal)?;
 
-        let target_account_address = forced_exit.target;
-
         let account_age = storage
             .chain()
             .operations_ext_schema()
-            .account_created_on(&target_account_address)
+            .account_created_on(&target)
             .await
-            .map_err(|err| internal_error!(err, forced_exit))?;
+            .map_err(|err| internal_error!(err, target))?;
 
         match account_age {
             Some(age) if Utc::now() - age < self.forced_exit_minimum_account_age => {
@@ -551,6 +886,30 @@ impl TxSender {
         }
     }
 
+    /// Checks that the forced exit can be requested for the account: besides the account age,
+    /// its signing key must not be set, otherwise the `ForcedExit` transaction will be rejected.
+    async fn check_forced_exit_target(&self, target: Address) -> Result<(), SubmitError> {
+        self.check_forced_exit_age(target).await?;
+
+        let account = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .account_schema()
+            .account_state_by_address(target)
+            .await
+            .map_err(|err| internal_error!(err, target))?
+            .committed;
+        match account {
+            Some((_, account)) if account.pub_key_hash != PubKeyHash::default() => Err(
+                SubmitError::invalid_params("Target account has the signing key set"),
+            ),
+            _ => Ok(()),
+        }
+    }
+
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// If any error is encountered during the message generation, returns `jsonrpc_core::Error`.
@@ -604,17 +963,19 @@ impl TxSender {
                 transactions,
                 token: token.clone(),
                 response: req.0,
+                span: tracing::Span::current(),
             })
             .await
             .map_err(SubmitError::internal)?;
         let resp = req.1.await.map_err(SubmitError::internal)?;
-        resp.map_err(|err| internal_error!(err))
+        resp.map_err(SubmitError::ticker)
     }
 
     async fn ticker_request(
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         tx_type: TxFeeTypes,
         address: Address,
+        sender: Option<Address>,
         token: TokenLike,
     ) -> Result<Fee, SubmitError> {
         let req = oneshot::channel();
@@ -622,14 +983,16 @@ impl TxSender {
             .send(TickerRequest::GetTxFee {
                 tx_type,
                 address,
+                sender,
                 token: token.clone(),
                 response: req.0,
+                span: tracing::Span::current(),
             })
             .await
             .map_err(SubmitError::internal)?;
 
         let resp = req.1.await.map_err(SubmitError::internal)?;
-        resp.map_err(|err| internal_error!(err))
+        resp.map_err(SubmitError::ticker)
     }
 
     async fn token_allowed_for_fees(
@@ -641,13 +1004,14 @@ impl TxSender {
             .send(TickerRequest::IsTokenAllowed {
                 token: token.clone(),
                 response: sender,
+                span: tracing::Span::current(),
             })
             .await
             .expect("ticker receiver dropped");
         receiver
             .await
             .expect("ticker answer sender dropped")
-            .map_err(SubmitError::internal)
+            .map_err(SubmitError::ticker)
     }
 
     async fn ticker_price_request(
@@ -661,11 +1025,12 @@ impl TxSender {
                 token: token.clone(),
                 response: req.0,
                 req_type,
+                span: tracing::Span::current(),
             })
             .await
             .map_err(SubmitError::internal)?;
         let resp = req.1.await.map_err(SubmitError::internal)?;
-        resp.map_err(|err| internal_error!(err))
+        resp.map(|quote| quote.price).map_err(SubmitError::ticker)
     }
 }
 
@@ -778,6 +1143,16 @@ async fn verify_txs_batch_signature(
     send_verify_request_and_recv(request, req_channel, receiver).await
 }
 
+/// Counts the transactions rejected because of the fee, broken down by the reason and the fee token.
+fn report_fee_rejection(reason: &'static str, token: &TokenLike) {
+    metrics::counter!(
+        "api.tx_sender.fee_rejections",
+        1,
+        "reason" => reason,
+        "token" => token.to_string()
+    );
+}
+
 /// Scales the fee provided by user up to check whether the provided fee is enough to cover our expenses for
 /// maintaining the protocol.
 ///
@@ -2,6 +2,8 @@
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
 
+use std::time::{Duration, Instant};
+
 use web3::{contract::Options, types::Address};
 use zksync_contracts::eip1271_contract;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
@@ -10,18 +12,37 @@ use zksync_types::{
     {Nonce, PubKeyHash},
 };
 
+use crate::utils::shared_lru_cache::SharedLruCache;
+
 /// isValidSignature return value according to EIP1271 standard
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Amount of the successfully verified EIP1271 signatures kept in the cache.
+const EIP1271_CACHE_CAPACITY: usize = 10_000;
+/// Time during which the successfully verified EIP1271 signature is considered valid
+/// without calling the contract again. The wallet owners may change, so the result
+/// can't be cached forever.
+const EIP1271_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Key of the EIP1271 signatures cache: wallet address, signed message hash and the signature itself.
+type EIP1271CacheKey = (Address, [u8; 32], Vec<u8>);
+
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
+    /// Successfully verified EIP1271 signatures with the time of verification.
+    /// Only the positive results are cached, since the signature may become valid later
+    /// (e.g. after the message hash is approved on-chain by the wallet owners).
+    eip1271_cache: SharedLruCache<EIP1271CacheKey, Instant>,
 }
 
 impl EthereumChecker {
     pub fn new(client: EthereumGateway) -> Self {
-        Self { client }
+        Self {
+            client,
+            eip1271_cache: SharedLruCache::new(EIP1271_CACHE_CAPACITY),
+        }
     }
 
     /// Transforms the message into an array expected by EIP-1271 standard.
@@ -42,6 +63,14 @@ impl EthereumChecker {
     ) -> Result<bool, anyhow::Error> {
         let sign_message = Self::get_sign_message(message);
 
+        let cache_key = (address, sign_message, signature.0.clone());
+        if let Some(verified_at) = self.eip1271_cache.get(&cache_key) {
+            if verified_at.elapsed() < EIP1271_CACHE_TTL {
+                metrics::counter!("eth_checker.eip1271_cache_hits", 1);
+                return Ok(true);
+            }
+        }
+
         let call_result = self
             .client
             .call_contract_function(
@@ -65,7 +94,11 @@ impl EthereumChecker {
             }
         };
 
-        Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
+        let is_correct = received == EIP1271_SUCCESS_RETURN_VALUE;
+        if is_correct {
+            self.eip1271_cache.insert(cache_key, Instant::now());
+        }
+        Ok(is_correct)
     }
 
     pub async fn is_new_pubkey_hash_authorized(
@@ -91,8 +124,9 @@ impl EthereumChecker {
 
 #[cfg(test)]
 mod tests {
-    use super::EthereumChecker;
+    use super::{EthereumChecker, EIP1271_CACHE_TTL};
     use std::str::FromStr;
+    use std::time::{Duration, Instant};
     use zksync_config::test_config::TestConfig;
     use zksync_contracts::zksync_contract;
     use zksync_eth_client::ethereum_gateway::EthereumGateway;
@@ -144,6 +178,58 @@ mod tests {
         assert_eq!(result, true, "Signature is incorrect");
     }
 
+    /// Checks that the successfully verified EIP1271 signatures are cached
+    /// only for the limited period of time.
+    #[tokio::test]
+    async fn eip1271_cache() {
+        // Nothing listens on this port, so every contract call fails.
+        let transport = web3::transports::Http::new("http://127.0.0.1:1").unwrap();
+        let client = EthereumGateway::Direct(ETHDirectClient::new(
+            transport,
+            zksync_contract(),
+            Default::default(),
+            PrivateKeySigner::new(Default::default()),
+            Default::default(),
+            0,
+            1.0,
+        ));
+        let eth_checker = EthereumChecker::new(client);
+
+        let address = Address::random();
+        let message = b"hello-world";
+        let signature = EIP1271Signature(vec![0xAB; 65]);
+        let cache_key = (
+            address,
+            EthereumChecker::get_sign_message(message),
+            signature.0.clone(),
+        );
+
+        // Unknown signature is checked via the contract call.
+        assert!(!eth_checker
+            .is_eip1271_signature_correct(address, message, signature.clone())
+            .await
+            .unwrap());
+
+        // Recently verified signature is taken from the cache.
+        eth_checker
+            .eip1271_cache
+            .insert(cache_key.clone(), Instant::now());
+        assert!(eth_checker
+            .is_eip1271_signature_correct(address, message, signature.clone())
+            .await
+            .unwrap());
+
+        // Outdated entry requires the contract call.
+        eth_checker.eip1271_cache.insert(
+            cache_key,
+            Instant::now() - EIP1271_CACHE_TTL - Duration::from_secs(1),
+        );
+        assert!(!eth_checker
+            .is_eip1271_signature_correct(address, message, signature)
+            .await
+            .unwrap());
+    }
+
     /// This test checks that the actual signature data taken from
     /// mainnet / Argent smart wallet is valid in our codebase.
     #[test]
@@ -0,0 +1,284 @@
+//! Events published by the forwarder and their extraction from the blocks.
+
+// External uses
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_crypto::{serialization::FrSerde, Fr};
+use zksync_types::{
+    block::Block, tx::TxHash, AccountId, Address, BlockNumber, ExecutedOperations, SerialId,
+    TokenId, ZkSyncOp, ZkSyncTx, H256,
+};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Event published to the message broker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ForwardedEvent {
+    Block(BlockEvent),
+    Transaction(TransactionEvent),
+    Deposit(DepositEvent),
+    Withdrawal(WithdrawalEvent),
+}
+
+impl ForwardedEvent {
+    /// Returns the subject the event is published to, without the configured prefix.
+    pub fn subject(&self) -> &'static str {
+        match self {
+            ForwardedEvent::Block(_) => "blocks",
+            ForwardedEvent::Transaction(_) => "transactions",
+            ForwardedEvent::Deposit(_) => "deposits",
+            ForwardedEvent::Withdrawal(_) => "withdrawals",
+        }
+    }
+}
+
+/// Block verified on Ethereum.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockEvent {
+    pub block_number: BlockNumber,
+    #[serde(with = "FrSerde")]
+    pub new_root_hash: Fr,
+    pub fee_account: AccountId,
+    pub block_size: usize,
+    pub timestamp: u64,
+}
+
+/// L2 transaction included into the block, either successful or failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEvent {
+    pub block_number: BlockNumber,
+    pub block_index: Option<u32>,
+    pub tx_hash: TxHash,
+    pub tx: ZkSyncTx,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+/// Deposit processed in the block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositEvent {
+    pub block_number: BlockNumber,
+    pub serial_id: SerialId,
+    pub eth_hash: H256,
+    pub from: Address,
+    pub to: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+/// Withdrawal to Ethereum processed in the block. Withdrawals initiated in L2 are identified
+/// by the transaction hash, full exits are identified by the priority operation serial ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalEvent {
+    pub block_number: BlockNumber,
+    pub tx_hash: Option<TxHash>,
+    pub serial_id: Option<SerialId>,
+    pub to: Address,
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+/// Returns the events of the block: the block event goes first, followed by the events
+/// of the block operations in the order of execution.
+pub fn block_events(block: &Block) -> Vec<ForwardedEvent> {
+    let block_number = block.block_number;
+    let mut events = vec![ForwardedEvent::Block(BlockEvent {
+        block_number,
+        new_root_hash: block.new_root_hash,
+        fee_account: block.fee_account,
+        block_size: block.block_chunks_size,
+        timestamp: block.timestamp,
+    })];
+
+    for operation in &block.block_transactions {
+        match operation {
+            ExecutedOperations::Tx(tx) => {
+                let tx_hash = tx.signed_tx.tx.hash();
+                events.push(ForwardedEvent::Transaction(TransactionEvent {
+                    block_number,
+                    block_index: tx.block_index,
+                    tx_hash,
+                    tx: tx.signed_tx.tx.clone(),
+                    success: tx.success,
+                    fail_reason: tx.fail_reason.clone(),
+                }));
+                if !tx.success {
+                    continue;
+                }
+
+                let withdrawal = match &tx.op {
+                    Some(ZkSyncOp::Withdraw(op)) => {
+                        Some((op.tx.to, op.tx.token, op.tx.amount.clone()))
+                    }
+                    Some(ZkSyncOp::ForcedExit(op)) => op
+                        .withdraw_amount
+                        .as_ref()
+                        .map(|amount| (op.tx.target, op.tx.token, amount.0.clone())),
+                    _ => None,
+                };
+                if let Some((to, token, amount)) = withdrawal {
+                    events.push(ForwardedEvent::Withdrawal(WithdrawalEvent {
+                        block_number,
+                        tx_hash: Some(tx_hash),
+                        serial_id: None,
+                        to,
+                        token,
+                        amount,
+                    }));
+                }
+            }
+            ExecutedOperations::PriorityOp(priority_op) => {
+                let serial_id = priority_op.priority_op.serial_id;
+                match &priority_op.op {
+                    ZkSyncOp::Deposit(op) => {
+                        events.push(ForwardedEvent::Deposit(DepositEvent {
+                            block_number,
+                            serial_id,
+                            eth_hash: priority_op.priority_op.eth_hash,
+                            from: op.priority_op.from,
+                            to: op.priority_op.to,
+                            token: op.priority_op.token,
+                            amount: op.priority_op.amount.clone(),
+                        }));
+                    }
+                    ZkSyncOp::FullExit(op) => {
+                        if let Some(amount) = &op.withdraw_amount {
+                            events.push(ForwardedEvent::Withdrawal(WithdrawalEvent {
+                                block_number,
+                                tx_hash: None,
+                                serial_id: Some(serial_id),
+                                to: op.priority_op.eth_address,
+                                token: op.priority_op.token,
+                                amount: amount.0.clone(),
+                            }));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use zksync_types::{
+        Deposit, DepositOp, ExecutedPriorityOp, ExecutedTx, Nonce, PriorityOp, Withdraw,
+        WithdrawOp, ZkSyncPriorityOp,
+    };
+
+    fn withdraw(success: bool) -> ExecutedOperations {
+        let tx = Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            BigUint::from(100u32),
+            BigUint::from(1u32),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        let op = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
+            tx: tx.clone(),
+            account_id: AccountId(1),
+        }));
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: ZkSyncTx::from(tx).into(),
+            success,
+            op: Some(op),
+            fail_reason: None,
+            block_index: Some(1),
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    fn deposit() -> ExecutedOperations {
+        let deposit = Deposit {
+            from: Address::repeat_byte(3),
+            token: TokenId(0),
+            amount: BigUint::from(500u32),
+            to: Address::repeat_byte(1),
+        };
+        let op = ZkSyncOp::Deposit(Box::new(DepositOp {
+            priority_op: deposit.clone(),
+            account_id: AccountId(1),
+        }));
+        ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id: 7,
+                data: ZkSyncPriorityOp::Deposit(deposit),
+                deadline_block: 0,
+                eth_hash: H256::repeat_byte(4),
+                eth_block: 0,
+            },
+            op,
+            block_index: 0,
+            created_at: Utc::now(),
+        }))
+    }
+
+    /// Checks that the events are extracted from the block in the order of execution
+    /// and the failed transactions produce no withdrawal events.
+    #[test]
+    fn events_from_block() {
+        let block = Block::new(
+            BlockNumber(5),
+            Fr::default(),
+            AccountId(0),
+            vec![deposit(), withdraw(true), withdraw(false)],
+            (7, 8),
+            10,
+            0.into(),
+            0.into(),
+            H256::zero(),
+            0,
+        );
+
+        let events = block_events(&block);
+        let subjects: Vec<_> = events.iter().map(ForwardedEvent::subject).collect();
+        assert_eq!(
+            subjects,
+            vec![
+                "blocks",
+                "deposits",
+                "transactions",
+                "withdrawals",
+                "transactions"
+            ]
+        );
+
+        match &events[1] {
+            ForwardedEvent::Deposit(deposit) => {
+                assert_eq!(deposit.block_number, BlockNumber(5));
+                assert_eq!(deposit.serial_id, 7);
+                assert_eq!(deposit.amount, BigUint::from(500u32));
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+        match &events[3] {
+            ForwardedEvent::Withdrawal(withdrawal) => {
+                assert_eq!(withdrawal.to, Address::repeat_byte(2));
+                assert_eq!(withdrawal.amount, BigUint::from(100u32));
+                assert!(withdrawal.tx_hash.is_some());
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+
+        let json = serde_json::to_value(&events[1]).unwrap();
+        assert_eq!(json["type"], "deposit");
+        assert_eq!(json["amount"], "500");
+    }
+}
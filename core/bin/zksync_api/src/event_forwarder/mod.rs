@@ -0,0 +1,117 @@
+//! Forwarder of the zkSync events to the external message broker.
+//!
+//! Once the block is verified on Ethereum, its events (the block itself, executed transactions,
+//! deposits and withdrawals) are published to the broker, so the indexers don't have to poll
+//! the API. The number of the last forwarded block is stored to the database, and the cursor
+//! is only moved after the broker has accepted all the events of the block. Thus the events
+//! are delivered at least once: after the restart, events of the last block may be repeated.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use async_trait::async_trait;
+use tokio::{task::JoinHandle, time};
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+// Local deps
+use self::nats::NatsSink;
+
+pub use self::events::{
+    block_events, BlockEvent, DepositEvent, ForwardedEvent, TransactionEvent, WithdrawalEvent,
+};
+
+mod events;
+mod nats;
+
+/// Destination of the forwarded events.
+#[async_trait]
+pub trait EventSink: Send {
+    /// Publishes the events in order, returns once all of them are accepted by the broker.
+    async fn publish(&mut self, events: &[ForwardedEvent]) -> anyhow::Result<()>;
+}
+
+struct EventForwarder<S> {
+    pool: ConnectionPool,
+    sink: S,
+    poll_interval: Duration,
+    /// Block to forward next, loaded from the database if not set.
+    next_block: Option<BlockNumber>,
+}
+
+impl<S: EventSink> EventForwarder<S> {
+    async fn run(mut self) {
+        let mut timer = time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(e) = self.forward_verified_blocks().await {
+                vlog::error!("Failed to forward the block events: {}", e);
+            }
+        }
+    }
+
+    async fn forward_verified_blocks(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let mut next_block = match self.next_block {
+            Some(block) => block,
+            None => storage
+                .event_forwarder_schema()
+                .last_forwarded_block()
+                .await?
+                .map(|block| block + 1)
+                .unwrap_or(BlockNumber(1)),
+        };
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        while next_block <= last_verified_block {
+            let block = storage
+                .chain()
+                .block_schema()
+                .get_block(next_block)
+                .await?
+                .ok_or_else(|| anyhow::format_err!("Verified block {} is not found", next_block))?;
+
+            let events = block_events(&block);
+            self.sink.publish(&events).await?;
+            storage
+                .event_forwarder_schema()
+                .update_last_forwarded_block(next_block)
+                .await?;
+            metrics::counter!("event_forwarder.forwarded_events", events.len() as u64);
+
+            next_block = next_block + 1;
+            self.next_block = Some(next_block);
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the event forwarder task if it's enabled in the config.
+pub fn run_event_forwarder(pool: ConnectionPool, config: &ZkSyncConfig) -> Option<JoinHandle<()>> {
+    let forwarder_config = &config.api.event_forwarder;
+    if !forwarder_config.enabled {
+        return None;
+    }
+
+    let forwarder = EventForwarder {
+        pool,
+        sink: NatsSink::new(
+            &forwarder_config.nats_url,
+            forwarder_config.subject_prefix.clone(),
+        ),
+        poll_interval: forwarder_config.poll_interval(),
+        next_block: forwarder_config.replay_from_block.map(BlockNumber),
+    };
+    Some(tokio::spawn(vlog::supervised(
+        "event_forwarder",
+        forwarder.run(),
+    )))
+}
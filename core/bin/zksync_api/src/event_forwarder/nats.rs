@@ -0,0 +1,173 @@
+//! Minimal client of the NATS text protocol, sufficient for publishing the events.
+//!
+//! Every batch of events is followed by `PING`, and the batch is considered delivered once
+//! the server answers with `PONG`: the server processes the commands of the connection in
+//! order, so all the published messages are accepted by then.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use anyhow::{bail, format_err};
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    time,
+};
+// Local deps
+use super::{events::ForwardedEvent, EventSink};
+
+/// Timeout of establishing the connection and delivering one batch of events.
+const NATS_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct NatsSink {
+    address: String,
+    subject_prefix: String,
+    connection: Option<BufReader<TcpStream>>,
+}
+
+impl NatsSink {
+    pub fn new(url: &str, subject_prefix: String) -> Self {
+        let address = url.trim_start_matches("nats://").trim_end_matches('/');
+        Self {
+            address: address.to_string(),
+            subject_prefix,
+            connection: None,
+        }
+    }
+
+    async fn connect(address: &str) -> anyhow::Result<BufReader<TcpStream>> {
+        let mut connection = BufReader::new(TcpStream::connect(address).await?);
+
+        // The server greets the client with its `INFO`.
+        let info = read_line(&mut connection).await?;
+        if !info.starts_with("INFO") {
+            bail!("Unexpected NATS server greeting: {}", info);
+        }
+        connection
+            .write_all(b"CONNECT {\"verbose\":false,\"pedantic\":false}\r\n")
+            .await?;
+
+        Ok(connection)
+    }
+
+    async fn publish_events(
+        connection: &mut BufReader<TcpStream>,
+        subject_prefix: &str,
+        events: &[ForwardedEvent],
+    ) -> anyhow::Result<()> {
+        let mut commands = Vec::new();
+        for event in events {
+            let payload = serde_json::to_vec(event)?;
+            commands.extend_from_slice(
+                format!(
+                    "PUB {}.{} {}\r\n",
+                    subject_prefix,
+                    event.subject(),
+                    payload.len()
+                )
+                .as_bytes(),
+            );
+            commands.extend_from_slice(&payload);
+            commands.extend_from_slice(b"\r\n");
+        }
+        commands.extend_from_slice(b"PING\r\n");
+        connection.write_all(&commands).await?;
+
+        loop {
+            let line = read_line(connection).await?;
+            match line.as_str() {
+                "PONG" => return Ok(()),
+                "PING" => connection.write_all(b"PONG\r\n").await?,
+                line if line.starts_with("-ERR") => bail!("NATS server error: {}", line),
+                // `+OK` and updated `INFO` messages.
+                _ => {}
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&mut self, events: &[ForwardedEvent]) -> anyhow::Result<()> {
+        if self.connection.is_none() {
+            let connection = time::timeout(NATS_TIMEOUT, Self::connect(&self.address))
+                .await
+                .map_err(|_| format_err!("Connection to the NATS server timed out"))??;
+            self.connection = Some(connection);
+        }
+
+        let connection = self.connection.as_mut().unwrap();
+        let result = time::timeout(
+            NATS_TIMEOUT,
+            Self::publish_events(connection, &self.subject_prefix, events),
+        )
+        .await
+        .map_err(|_| format_err!("Publishing the events to the NATS server timed out"))
+        .and_then(|result| result);
+
+        // The connection state is unknown after the failure, so it's established anew.
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+}
+
+async fn read_line(connection: &mut BufReader<TcpStream>) -> anyhow::Result<String> {
+    let mut line = String::new();
+    if connection.read_line(&mut line).await? == 0 {
+        bail!("NATS server closed the connection");
+    }
+    Ok(line.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_forwarder::events::{BlockEvent, ForwardedEvent};
+    use tokio::net::TcpListener;
+    use zksync_types::{AccountId, BlockNumber};
+
+    /// Checks that the events are published to the prefixed subjects and the batch
+    /// is delivered once the server answers the `PING`.
+    #[tokio::test]
+    async fn publish_to_nats() {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = BufReader::new(socket);
+            socket.write_all(b"INFO {}\r\n").await.unwrap();
+
+            let mut lines = Vec::new();
+            loop {
+                let line = read_line(&mut socket).await.unwrap();
+                if line == "PING" {
+                    break;
+                }
+                lines.push(line);
+            }
+            socket.write_all(b"PONG\r\n").await.unwrap();
+            lines
+        });
+
+        let event = ForwardedEvent::Block(BlockEvent {
+            block_number: BlockNumber(1),
+            new_root_hash: Default::default(),
+            fee_account: AccountId(0),
+            block_size: 10,
+            timestamp: 0,
+        });
+        let payload = serde_json::to_string(&event).unwrap();
+
+        let mut sink = NatsSink::new(&format!("nats://{}", address), "zksync".to_string());
+        sink.publish(&[event]).await.unwrap();
+
+        let lines = server.await.unwrap();
+        assert!(lines[0].starts_with("CONNECT"));
+        assert_eq!(lines[1], format!("PUB zksync.blocks {}", payload.len()));
+        assert_eq!(lines[2], payload);
+    }
+}
@@ -0,0 +1,196 @@
+//! Fee audit log.
+//!
+//! Fee quotes returned by the ticker (and the records about transactions which paid more
+//! than they were quoted) are sent to a dedicated writer task, which stores them to the database
+//! in batches. Recording never blocks the ticker: if the writer can't keep up, records are dropped.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use bigdecimal::BigDecimal;
+use futures::{
+    channel::mpsc::{self, Receiver, Sender},
+    StreamExt,
+};
+use num::{bigint::ToBigInt, BigUint};
+use tokio::time::{self, Instant};
+// Workspace deps
+use zksync_storage::{
+    fee_audit::records::{NewFeeOverpayment, NewFeeQuote},
+    ConnectionPool,
+};
+use zksync_types::{tx::TxHash, Address, Fee, TokenId};
+
+/// Capacity of the channel between the fee ticker and the audit writer.
+const AUDIT_CHANNEL_SIZE: usize = 32000;
+/// Amount of records after which the writer flushes the batch.
+const AUDIT_BATCH_SIZE: usize = 100;
+/// Maximum time the record can wait in the batch before being flushed.
+const AUDIT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// Sleep time between the removals of the outdated audit records.
+const AUDIT_CLEANER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug)]
+enum FeeAuditRecord {
+    Quote(NewFeeQuote),
+    Overpayment(NewFeeOverpayment),
+}
+
+/// Handle used to send the records to the fee audit writer.
+#[derive(Debug, Clone)]
+pub struct FeeAuditLog {
+    sender: Sender<FeeAuditRecord>,
+}
+
+impl FeeAuditLog {
+    /// Creates the audit log handle and spawns the writer task storing the records to the database.
+    /// Records older than `retention` are removed periodically.
+    pub fn spawn(db_pool: ConnectionPool, retention: chrono::Duration) -> Self {
+        let (sender, receiver) = mpsc::channel(AUDIT_CHANNEL_SIZE);
+        tokio::spawn(vlog::supervised(
+            "fee_audit_writer",
+            run_fee_audit_writer(db_pool.clone(), receiver),
+        ));
+        tokio::spawn(vlog::supervised(
+            "fee_audit_cleaner",
+            run_fee_audit_cleaner(db_pool, retention),
+        ));
+        Self { sender }
+    }
+
+    /// Records the fee quoted for the transaction.
+    pub fn record_quote(&mut self, fee: &Fee, address: Address, token_id: TokenId) {
+        let quote = NewFeeQuote {
+            fee_type: serde_json::to_value(&fee.fee_type).expect("Fee type serialization failed"),
+            address: address.as_bytes().to_vec(),
+            token_id: token_id.0 as i32,
+            gas_tx_amount: to_big_decimal(&fee.gas_tx_amount),
+            gas_price_wei: to_big_decimal(&fee.gas_price_wei),
+            gas_fee: to_big_decimal(&fee.gas_fee),
+            zkp_fee: to_big_decimal(&fee.zkp_fee),
+            total_fee: to_big_decimal(&fee.total_fee),
+            quoted_at: chrono::Utc::now(),
+        };
+        self.send(FeeAuditRecord::Quote(quote));
+    }
+
+    /// Records the transaction which paid more fee than it was quoted.
+    pub fn record_overpayment(
+        &mut self,
+        tx_hash: TxHash,
+        token_id: TokenId,
+        quoted_fee: &BigUint,
+        paid_fee: &BigUint,
+    ) {
+        let overpayment = NewFeeOverpayment {
+            tx_hash: tx_hash.as_ref().to_vec(),
+            token_id: token_id.0 as i32,
+            quoted_fee: to_big_decimal(quoted_fee),
+            paid_fee: to_big_decimal(paid_fee),
+            recorded_at: chrono::Utc::now(),
+        };
+        self.send(FeeAuditRecord::Overpayment(overpayment));
+    }
+
+    fn send(&mut self, record: FeeAuditRecord) {
+        if let Err(e) = self.sender.try_send(record) {
+            vlog::debug!("Fee audit record was dropped: {}", e);
+            metrics::counter!("ticker.fee_audit.dropped_records", 1);
+        }
+    }
+}
+
+fn to_big_decimal(value: &BigUint) -> BigDecimal {
+    // Converting `BigUint` to `BigInt` is safe.
+    BigDecimal::from(value.to_bigint().unwrap())
+}
+
+/// Receives the audit records and stores them to the database in batches.
+async fn run_fee_audit_writer(db_pool: ConnectionPool, mut records: Receiver<FeeAuditRecord>) {
+    let mut quotes = Vec::new();
+    let mut overpayments = Vec::new();
+    let mut flush_deadline = Instant::now() + AUDIT_FLUSH_INTERVAL;
+
+    loop {
+        match time::timeout_at(flush_deadline, records.next()).await {
+            Ok(Some(FeeAuditRecord::Quote(quote))) => quotes.push(quote),
+            Ok(Some(FeeAuditRecord::Overpayment(overpayment))) => overpayments.push(overpayment),
+            // All the senders are dropped, flush the remaining records and exit.
+            Ok(None) => {
+                flush_records(&db_pool, &mut quotes, &mut overpayments).await;
+                return;
+            }
+            Err(_) => {
+                flush_records(&db_pool, &mut quotes, &mut overpayments).await;
+                flush_deadline = Instant::now() + AUDIT_FLUSH_INTERVAL;
+                continue;
+            }
+        }
+
+        if quotes.len() + overpayments.len() >= AUDIT_BATCH_SIZE {
+            flush_records(&db_pool, &mut quotes, &mut overpayments).await;
+            flush_deadline = Instant::now() + AUDIT_FLUSH_INTERVAL;
+        }
+    }
+}
+
+async fn flush_records(
+    db_pool: &ConnectionPool,
+    quotes: &mut Vec<NewFeeQuote>,
+    overpayments: &mut Vec<NewFeeOverpayment>,
+) {
+    if quotes.is_empty() && overpayments.is_empty() {
+        return;
+    }
+
+    let start = Instant::now();
+    let records_count = quotes.len() + overpayments.len();
+    let quotes = std::mem::take(quotes);
+    let overpayments = std::mem::take(overpayments);
+
+    let result = async {
+        let mut storage = db_pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        transaction
+            .fee_audit_schema()
+            .store_fee_quotes(quotes)
+            .await?;
+        transaction
+            .fee_audit_schema()
+            .store_fee_overpayments(overpayments)
+            .await?;
+        transaction.commit().await
+    }
+    .await;
+
+    match result {
+        Ok(()) => metrics::counter!("ticker.fee_audit.stored_records", records_count as u64),
+        Err(e) => {
+            vlog::error!("Failed to store {} fee audit records: {}", records_count, e);
+            metrics::counter!("ticker.fee_audit.dropped_records", records_count as u64);
+        }
+    }
+    metrics::histogram!("ticker.fee_audit.flush", start.elapsed());
+}
+
+/// Periodically removes the audit records which are older than the retention period.
+async fn run_fee_audit_cleaner(db_pool: ConnectionPool, retention: chrono::Duration) {
+    let mut timer = time::interval(AUDIT_CLEANER_INTERVAL);
+    loop {
+        timer.tick().await;
+
+        let result = async {
+            let mut storage = db_pool.access_storage().await?;
+            storage
+                .fee_audit_schema()
+                .remove_records_before(chrono::Utc::now() - retention)
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(removed) => vlog::debug!("Removed {} outdated fee audit records", removed),
+            Err(e) => vlog::error!("Failed to remove outdated fee audit records: {}", e),
+        }
+    }
+}
@@ -2,61 +2,65 @@ use futures::{
     channel::mpsc::{self, Receiver, Sender},
     SinkExt, StreamExt,
 };
-use std::{collections::HashMap, sync::Arc, time::Instant};
-
-use tokio::sync::Mutex;
-use zksync_storage::ConnectionPool;
-
-use crate::{
-    fee_ticker::{
-        ticker_api::{TickerApi, TokenPriceAPI},
-        ticker_info::FeeTickerInfo,
-        validator::{watcher::TokenWatcher, FeeTokenValidator},
-        FeeTicker, TickerConfig, TickerRequest,
-    },
-    utils::token_db_cache::TokenDBCache,
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
 };
 
-static TICKER_CHANNEL_SIZE: usize = 32000;
+use tokio::task::JoinHandle;
+use zksync_types::TokenLike;
+
+use crate::fee_ticker::{
+    audit::FeeAuditLog,
+    block_fullness::PendingBlockFullness,
+    price_twap::PriceTwap,
+    quote::FeeQuoteSigner,
+    single_flight::InflightFees,
+    ticker_api::FeeTickerAPI,
+    ticker_info::FeeTickerInfo,
+    validator::{watcher::TokenWatcher, FeeTokenValidator},
+    FeeTicker, TickerConfigHandle, TickerRequest,
+};
+
+/// Kept small, so the backlog of the requests is held by the request queue,
+/// which sheds the public requests on overload.
+static TICKER_CHANNEL_SIZE: usize = 16;
 
 /// `TickerBalancer` is a struct used for scaling the ticker.
-/// Create `n` tickers and balance the load between them.
-pub(crate) struct TickerBalancer<API: TokenPriceAPI, INFO, WATCHER> {
-    tickers: Vec<FeeTicker<TickerApi<API>, INFO, WATCHER>>,
+/// Create `n` tickers and balance the load between them. The requests about the same token
+/// are handled by the same ticker, so its fee cache isn't duplicated by the other tickers.
+/// Identical fee requests received at once are calculated by the ticker only once.
+pub(crate) struct TickerBalancer<API, INFO, WATCHER> {
+    tickers: Vec<FeeTicker<API, INFO, WATCHER>>,
     channels: Vec<Sender<TickerRequest>>,
     requests: Receiver<TickerRequest>,
+    inflight_fees: InflightFees,
 }
 
 impl<API, INFO, WATCHER> TickerBalancer<API, INFO, WATCHER>
 where
-    API: TokenPriceAPI + Clone + Sync + Send + 'static,
+    API: FeeTickerAPI + Clone + Sync + Send + 'static,
     INFO: FeeTickerInfo + Clone + Sync + Send + 'static,
     WATCHER: TokenWatcher + Clone + Sync + Send + 'static,
 {
+    /// Creates `number_of_tickers` tickers. Every ticker gets a clone of `ticker_api`,
+    /// so the caches of the API are shared by all the tickers.
     pub fn new(
-        token_price_api: API,
+        ticker_api: API,
         ticker_info: INFO,
-        ticker_config: TickerConfig,
+        ticker_config: TickerConfigHandle,
         validator: FeeTokenValidator<WATCHER>,
         requests: Receiver<TickerRequest>,
-        db_pool: ConnectionPool,
         number_of_tickers: u8,
     ) -> Self {
         let mut tickers = vec![];
         let mut channels = vec![];
 
-        let token_db_cache = TokenDBCache::new();
-        let price_cache = Arc::new(Mutex::new(HashMap::new()));
-        let gas_price_cache = Arc::new(Mutex::new(None));
-
         for _ in 0..number_of_tickers {
-            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api.clone())
-                .with_token_db_cache(token_db_cache.clone())
-                .with_price_cache(price_cache.clone())
-                .with_gas_price_cache(gas_price_cache.clone());
             let (request_sender, request_receiver) = mpsc::channel(TICKER_CHANNEL_SIZE);
             tickers.push(FeeTicker::new(
-                ticker_api,
+                ticker_api.clone(),
                 ticker_info.clone(),
                 request_receiver,
                 ticker_config.clone(),
@@ -69,44 +73,129 @@ where
             tickers,
             channels,
             requests,
+            inflight_fees: InflightFees::default(),
         }
     }
-    pub fn spawn_tickers(&mut self) {
+
+    /// Sets the log used by every ticker to record the returned fee quotes.
+    pub fn with_audit_log(self, audit_log: Option<FeeAuditLog>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_audit_log(audit_log.clone()))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Sets the signer used by every ticker to sign the returned fee quotes.
+    pub fn with_quote_signer(self, quote_signer: Option<FeeQuoteSigner>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_quote_signer(quote_signer.clone()))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Sets the fullness of the pending block used by every ticker to scale the fast processing surcharge.
+    pub fn with_block_fullness(self, block_fullness: Option<PendingBlockFullness>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_block_fullness(block_fullness.clone()))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Sets the time the calculated fees are reused for by every ticker.
+    pub fn with_fee_cache_ttl(self, fee_cache_ttl: Option<Duration>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_fee_cache_ttl(fee_cache_ttl))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Sets the averaged token prices shared by all the tickers.
+    pub fn with_price_twap(self, price_twap: Option<PriceTwap>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_price_twap(price_twap.clone()))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Sets the time every ticker handles a single request for.
+    pub fn with_request_timeout(self, request_timeout: Option<Duration>) -> Self {
+        let tickers = self
+            .tickers
+            .into_iter()
+            .map(|ticker| ticker.with_request_timeout(request_timeout))
+            .collect();
+        Self { tickers, ..self }
+    }
+
+    /// Spawns the tickers, returning their handles. The tickers stop once the balancer
+    /// is stopped and the requests they have taken are handled.
+    pub fn spawn_tickers(&mut self) -> Vec<JoinHandle<()>> {
+        let mut handles = Vec::with_capacity(self.tickers.len());
         while let Some(ticker) = self.tickers.pop() {
-            tokio::spawn(ticker.run());
+            handles.push(tokio::spawn(vlog::supervised("fee_ticker", ticker.run())));
         }
+        handles
     }
 
     pub async fn run(mut self) {
-        // It's an obvious way of balancing. Send an equal number of requests to each ticker
+        // Requests about several tokens are spread evenly between the tickers.
         let mut channel_indexes = (0..self.channels.len()).into_iter().cycle();
         // it's the easiest way how to cycle over channels, because cycle required clone trait
         while let Some(request) = self.requests.next().await {
-            let channel_index = channel_indexes
-                .next()
-                .expect("Exactly one channel should exists");
+            let request = match self.inflight_fees.dedup(request) {
+                Some(request) => request,
+                None => continue,
+            };
+            let channel_index = match request.token() {
+                Some(token) => token_shard(token, self.channels.len()),
+                None => channel_indexes
+                    .next()
+                    .expect("Exactly one channel should exists"),
+            };
             let start = Instant::now();
-            self.channels[channel_index]
-                .send(request)
-                .await
-                .unwrap_or_default();
+            // Once one of the tickers has stopped, the balancer stops as well,
+            // so the requests are taken over by the standby ticker.
+            if self.channels[channel_index].send(request).await.is_err() {
+                vlog::error!("Fee ticker #{} has stopped", channel_index);
+                return;
+            }
             metrics::histogram!("ticker.dispatcher.request", start.elapsed());
         }
     }
 }
 
+/// Returns the index of the ticker handling the requests about the token, so the fees and the
+/// prices of the token are cached by a single ticker. The token given by its id, address
+/// and symbol may be handled by the different tickers.
+fn token_shard(token: &TokenLike, shards_count: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+    (hasher.finish() % shards_count as u64) as usize
+}
+
 #[cfg(test)]
 mod tests {
-    use super::TickerBalancer;
-    use crate::fee_ticker::ticker_api::coingecko::CoinGeckoAPI;
+    use super::{token_shard, TickerBalancer};
+    use crate::fee_ticker::single_flight::InflightFees;
+    use crate::fee_ticker::ticker_api::{coingecko::CoinGeckoAPI, TickerApi};
     use crate::fee_ticker::ticker_info::TickerInfo;
     use crate::fee_ticker::validator::watcher::UniswapTokenWatcher;
-    use crate::fee_ticker::TickerRequest;
+    use crate::fee_ticker::{TickerRequest, TokenPriceRequestType};
     use futures::{
         channel::{mpsc, oneshot},
         SinkExt, StreamExt,
     };
-    use zksync_types::{TokenId, TxFeeTypes};
+    use zksync_types::{Address, TokenId, TokenLike, TxFeeTypes};
 
     #[tokio::test]
     async fn dispatch() {
@@ -119,31 +208,56 @@ mod tests {
         }
         let (mut request_sender, request_receiver) = mpsc::channel(2);
 
-        let dispatcher = TickerBalancer::<CoinGeckoAPI, TickerInfo, UniswapTokenWatcher> {
+        let dispatcher = TickerBalancer::<TickerApi<CoinGeckoAPI>, TickerInfo, UniswapTokenWatcher> {
             tickers: vec![],
             channels: senders,
             requests: request_receiver,
+            inflight_fees: InflightFees::default(),
         };
         tokio::spawn(dispatcher.run());
+        // Requests about the token are always sent to the same ticker.
         for i in 0..50 {
+            let token = TokenLike::from(TokenId(i % 5));
             let channel = oneshot::channel();
             request_sender
                 .send(TickerRequest::GetTxFee {
                     tx_type: TxFeeTypes::Withdraw,
-                    token: TokenId(i).into(),
-                    address: Default::default(),
+                    token: token.clone(),
+                    // Identical requests are not dispatched until the first one is answered.
+                    address: Address::from_low_u64_be(u64::from(i)),
+                    sender: None,
                     response: channel.0,
+                    span: tracing::Span::current(),
                 })
                 .await
                 .unwrap();
             if let Some(TickerRequest::GetTxFee {
-                tx_type: _,
-                token,
-                address: _,
-                response: _,
-            }) = receivers[(i % 10) as usize].next().await
+                token: received_token,
+                ..
+            }) = receivers[token_shard(&token, 10)].next().await
+            {
+                assert_eq!(received_token, token);
+            } else {
+                panic!("Wrong type")
+            }
+        }
+
+        // Requests about several tokens are sent to the tickers in turn.
+        for i in 0..20 {
+            let channel = oneshot::channel();
+            request_sender
+                .send(TickerRequest::GetTokenPriceBatch {
+                    tokens: vec![TokenId(i).into()],
+                    response: channel.0,
+                    req_type: TokenPriceRequestType::USDForOneToken,
+                    span: tracing::Span::current(),
+                })
+                .await
+                .unwrap();
+            if let Some(TickerRequest::GetTokenPriceBatch { tokens, .. }) =
+                receivers[(i % 10) as usize].next().await
             {
-                assert_eq!(token, TokenId(i).into());
+                assert_eq!(tokens, vec![TokenId(i).into()]);
             } else {
                 panic!("Wrong type")
             }
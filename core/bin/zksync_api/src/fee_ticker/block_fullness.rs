@@ -0,0 +1,81 @@
+//! Fullness of the pending block.
+//!
+//! Fast withdrawal makes the state keeper seal the pending block right away, so its surcharge
+//! pays for the chunks left unused. The fewer chunks are left, the cheaper it is to seal the block,
+//! so the surcharge is scaled by the share of the free chunks. The pending block is stored by
+//! the state keeper, and its fullness is periodically sampled from the database.
+
+// Built-in deps
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+// External deps
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use zksync_storage::ConnectionPool;
+
+/// Share of the free chunks in the pending block, shared between the sampler and the tickers.
+#[derive(Debug, Clone)]
+pub struct PendingBlockFullness {
+    sample: Arc<RwLock<Option<(Ratio<BigUint>, Instant)>>>,
+    /// Samples older than this are not used, so the static surcharge is applied if the sampler lags.
+    max_age: Duration,
+}
+
+impl PendingBlockFullness {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            sample: Default::default(),
+            max_age,
+        }
+    }
+
+    pub fn set_chunks_left(&self, chunks_left: usize, block_size: usize) {
+        let free_share = Ratio::new(
+            BigUint::from(chunks_left.min(block_size)),
+            BigUint::from(block_size),
+        );
+        *self.sample.write().expect("block fullness lock poisoned") =
+            Some((free_share, Instant::now()));
+    }
+
+    /// Returns the share of the free chunks in the pending block, or `None` if it's not known.
+    pub fn free_share(&self) -> Option<Ratio<BigUint>> {
+        let sample = self.sample.read().expect("block fullness lock poisoned");
+        sample
+            .as_ref()
+            .filter(|(_, sampled_at)| sampled_at.elapsed() <= self.max_age)
+            .map(|(free_share, _)| free_share.clone())
+    }
+}
+
+/// Periodically samples the fullness of the pending block stored by the state keeper.
+pub async fn run_block_fullness_sampler(
+    db_pool: ConnectionPool,
+    fullness: PendingBlockFullness,
+    block_size: usize,
+    interval: Duration,
+) {
+    let mut timer = tokio::time::interval(interval);
+    loop {
+        timer.tick().await;
+
+        let chunks_left = async {
+            let mut storage = db_pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .load_pending_block_chunks_left()
+                .await
+        }
+        .await;
+        match chunks_left {
+            Ok(chunks_left) => {
+                // No pending block is stored right after the block is sealed.
+                let chunks_left = chunks_left.unwrap_or(block_size);
+                fullness.set_chunks_left(chunks_left, block_size);
+                metrics::gauge!("ticker.pending_block_chunks_left", chunks_left as f64);
+            }
+            Err(err) => vlog::warn!("Failed to load the pending block: {}", err),
+        }
+    }
+}
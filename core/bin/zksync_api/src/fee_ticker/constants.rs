@@ -1,10 +1,15 @@
 use zksync_types::{
     gas_counter::{CommitCost, VerifyCost},
-    ChangePubKeyOp, TransferOp, TransferToNewOp, WithdrawOp,
+    ChangePubKeyOp, ForcedExitOp, TransferOp, TransferToNewOp, WithdrawOp,
 };
 
 /// Gas cost per chunk to cover constant cost of commit, execute and prove transactions
 pub(crate) const AMORTIZED_COST_PER_CHUNK: u64 = 200;
+// Chunks of the NFT operations and the `Swap` operation. The circuit doesn't support
+// these operations yet, so their fees are quoted for the upstream pubdata formats.
+pub(crate) const MINT_NFT_CHUNKS: usize = 5;
+pub(crate) const WITHDRAW_NFT_CHUNKS: usize = 11;
+pub(crate) const SWAP_CHUNKS: usize = 5;
 // Base operation costs estimated via `gas_price` test.
 // Factor of AMORTIZED_COST_PER_CHUNK * CHUNKS accounts for constant overhead of the commit, execute, prove for blocks of 680 chunks
 // where we assume that we commit 5 blocks at once, prove 10 and execute 5
@@ -27,10 +32,28 @@ pub(crate) const BASE_CHANGE_PUBKEY_OFFCHAIN_COST: u64 = CommitCost::CHANGE_PUBK
 pub(crate) const BASE_CHANGE_PUBKEY_ONCHAIN_COST: u64 = CommitCost::CHANGE_PUBKEY_COST_ONCHAIN
     + VerifyCost::CHANGE_PUBKEY_COST
     + AMORTIZED_COST_PER_CHUNK * (ChangePubKeyOp::CHUNKS as u64);
+pub(crate) const BASE_FORCED_EXIT_COST: u64 = VerifyCost::FORCED_EXIT_COST
+    + CommitCost::FORCED_EXIT_COST
+    + AMORTIZED_COST_PER_CHUNK * (ForcedExitOp::CHUNKS as u64);
+pub(crate) const BASE_MINT_NFT_COST: u64 = VerifyCost::MINT_NFT_COST
+    + CommitCost::MINT_NFT_COST
+    + AMORTIZED_COST_PER_CHUNK * (MINT_NFT_CHUNKS as u64);
+pub(crate) const BASE_WITHDRAW_NFT_COST: u64 = VerifyCost::WITHDRAW_NFT_COST
+    + CommitCost::WITHDRAW_NFT_COST
+    + AMORTIZED_COST_PER_CHUNK * (WITHDRAW_NFT_CHUNKS as u64);
+pub(crate) const BASE_SWAP_COST: u64 =
+    VerifyCost::SWAP_COST + CommitCost::SWAP_COST + AMORTIZED_COST_PER_CHUNK * (SWAP_CHUNKS as u64);
+/// Cost of the `depositERC20WithPermit` call sent by the relayer: the deposit itself,
+/// the permit signature verification and the fee transfer to the relayer.
+pub(crate) const BASE_PERMIT_DEPOSIT_COST: u64 = 180_000;
 
 // The Subsidized cost of operations.
 // Represent the cost of performing operations after recursion is introduced to mainnet.
 pub(crate) const SUBSIDY_TRANSFER_COST: u64 = 550;
 pub(crate) const SUBSIDY_TRANSFER_TO_NEW_COST: u64 = 550 * 3;
 pub(crate) const SUBSIDY_WITHDRAW_COST: u64 = 45000;
+pub(crate) const SUBSIDY_FORCED_EXIT_COST: u64 = SUBSIDY_WITHDRAW_COST;
 pub(crate) const SUBSIDY_CHANGE_PUBKEY_OFFCHAIN_COST: u64 = 10000;
+pub(crate) const SUBSIDY_MINT_NFT_COST: u64 = 550 * 5;
+pub(crate) const SUBSIDY_WITHDRAW_NFT_COST: u64 = 180000;
+pub(crate) const SUBSIDY_SWAP_COST: u64 = 550 * 5;
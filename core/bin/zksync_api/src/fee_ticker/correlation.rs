@@ -0,0 +1,29 @@
+//! Correlation of the fee ticker logs with the API requests.
+//!
+//! The API layer handles every request affecting the fees within a span carrying
+//! the `correlation_id` field. Ticker requests capture the span they are created in,
+//! and the ticker handles them within a nested span, so the logs of the ticker
+//! (including the calls to the price API) are attributed to the API request.
+
+// Built-in deps
+use std::sync::atomic::{AtomicU64, Ordering};
+// External deps
+use tracing::Span;
+
+/// Name of the header (or the gRPC metadata key) the callers can pass their correlation ID in.
+pub const CORRELATION_ID_HEADER: &str = "x-request-id";
+
+static NEXT_CORRELATION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn new_correlation_id() -> String {
+    format!("{:x}", NEXT_CORRELATION_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Creates the span of the API request. The correlation ID provided by the caller is used
+/// if there is one, otherwise the new one is generated.
+pub fn api_request_span(method: &str, correlation_id: Option<&str>) -> Span {
+    let correlation_id = correlation_id
+        .map(str::to_string)
+        .unwrap_or_else(new_correlation_id);
+    tracing::info_span!("api_request", method, correlation_id = %correlation_id)
+}
@@ -0,0 +1,72 @@
+//! Per-account fee discounts.
+//!
+//! Accounts may be granted a discount, e.g. the market makers may pay a half of the transfer
+//! fee. The discount is applied to the fee calculated by the usual formula, and only when
+//! the sender of the transaction is known to the ticker. Discounts are stored in the database
+//! and reloaded by the ticker together with the other settings.
+
+// External deps
+use anyhow::ensure;
+use num::{rational::Ratio, BigUint, One, Zero};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_storage::fee_discounts::records::{NewFeeDiscount, StoredFeeDiscount};
+use zksync_types::{Address, OutputFeeType};
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
+
+/// Precision of the discounts stored in the database.
+const STORED_DISCOUNT_PRECISION: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeDiscount {
+    pub id: i32,
+    /// Account sending the transaction.
+    pub address: Address,
+    /// Fee type, the discount applies to all the fee types if not set.
+    pub fee_type: Option<OutputFeeType>,
+    /// Share of the fee the account doesn't pay.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub discount: Ratio<BigUint>,
+}
+
+impl FeeDiscount {
+    pub fn from_stored(discount: StoredFeeDiscount) -> anyhow::Result<Self> {
+        let fee_type = discount.fee_type.map(serde_json::from_value).transpose()?;
+        Ok(Self {
+            id: discount.id,
+            address: Address::from_slice(&discount.address),
+            fee_type,
+            discount: big_decimal_to_ratio(&discount.discount)?,
+        })
+    }
+
+    /// Returns `true` if the discount applies to the fee of the given type.
+    pub fn applies_to(&self, fee_type: OutputFeeType) -> bool {
+        self.fee_type
+            .map_or(true, |discount_fee_type| discount_fee_type == fee_type)
+    }
+}
+
+/// Request to grant the discount to the account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewDiscountRequest {
+    pub address: Address,
+    pub fee_type: Option<OutputFeeType>,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub discount: Ratio<BigUint>,
+}
+
+impl NewDiscountRequest {
+    pub fn into_record(self) -> anyhow::Result<NewFeeDiscount> {
+        ensure!(
+            !self.discount.is_zero() && self.discount <= Ratio::one(),
+            "discount must be greater than 0 and not greater than 1"
+        );
+
+        Ok(NewFeeDiscount {
+            address: self.address.as_bytes().to_vec(),
+            fee_type: self.fee_type.map(serde_json::to_value).transpose()?,
+            discount: ratio_to_big_decimal(&self.discount, STORED_DISCOUNT_PRECISION),
+        })
+    }
+}
@@ -0,0 +1,59 @@
+//! Fee dry run.
+//!
+//! Support has to explain the fee charged from the user, which depends on the prices and
+//! the settings at the moment of the quote. Dry run calculates the fee exactly as the usual
+//! quote does, but also reports every input of the formula. The dry run fee is neither signed
+//! nor recorded to the audit log.
+
+// External deps
+use bigdecimal::BigDecimal;
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_types::{Address, Fee, Token, TokenLike, TokenPrice, TxFeeTypes};
+use zksync_utils::{BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
+
+/// Request to calculate the fee of a single transaction, see `TickerRequest::GetTxFee`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeeDryRunRequest {
+    pub tx_type: TxFeeTypes,
+    /// Recipient of the transaction.
+    pub address: Address,
+    /// Sender of the transaction, its discounts are applied if set.
+    #[serde(default)]
+    pub sender: Option<Address>,
+    /// Token the fee is paid in.
+    pub token: TokenLike,
+}
+
+/// Fee of a single transaction together with the inputs it's calculated from.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeeDryRun {
+    /// Resulting fee, the same as quoted to the user at the moment.
+    pub fee: Fee,
+    /// Token the fee is paid in, including its decimals.
+    pub token: Token,
+    /// Last quotes of ETH and of the fee token with the time they were received, averaged
+    /// over time if configured. Prices are not requested if the fee is waived.
+    pub eth_quote: Option<TokenPrice>,
+    pub token_quote: Option<TokenPrice>,
+    /// Gas price sample the fee is calculated from.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub base_fee_wei: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub priority_fee_wei: BigUint,
+    /// Number of the block chunks occupied by the operation.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub op_chunks: BigUint,
+    /// Whether the subsidized gas cost of the operation is used.
+    pub subsidized: bool,
+    /// Cost of the proof generation for one chunk, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Share of the fee the sender doesn't pay.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub discount: Ratio<BigUint>,
+    /// Share of the free chunks in the pending block the fast processing surcharge
+    /// is scaled by, if known.
+    pub pending_block_free_share: Option<BigDecimal>,
+}
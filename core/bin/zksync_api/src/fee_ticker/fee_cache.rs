@@ -0,0 +1,66 @@
+//! Short-lived cache of the calculated fees.
+//!
+//! Wallets request the fee of the same operation several times in a row, and every request
+//! runs the whole formula and looks up the prices. Instead, the fee of the operation paid
+//! in the token is reused for a short time. The cached fees are dropped once the gas price
+//! or the ticker settings change, so they never outlive the inputs they are calculated from.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::time::Duration;
+// External deps
+use tokio::time::Instant;
+// Workspace deps
+use zksync_types::{Fee, OutputFeeType, TokenId};
+// Local deps
+use super::ticker_api::GasPriceWei;
+
+#[derive(Debug)]
+pub(super) struct FeeCache {
+    ttl: Duration,
+    /// Gas price the cached fees are calculated with.
+    gas_price: Option<GasPriceWei>,
+    fees: HashMap<(OutputFeeType, TokenId), (Fee, Instant)>,
+}
+
+impl FeeCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            gas_price: None,
+            fees: HashMap::new(),
+        }
+    }
+
+    /// Returns the fee of the operation paid in the token, if it's calculated recently.
+    pub fn get(&self, fee_type: OutputFeeType, token: TokenId) -> Option<Fee> {
+        self.fees
+            .get(&(fee_type, token))
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.ttl)
+            .map(|(fee, _)| fee.clone())
+    }
+
+    /// Stores the fee of the operation paid in the token, calculated with the given gas price.
+    pub fn insert(
+        &mut self,
+        fee_type: OutputFeeType,
+        token: TokenId,
+        gas_price: &GasPriceWei,
+        fee: Fee,
+    ) {
+        self.set_gas_price(gas_price);
+        self.fees.insert((fee_type, token), (fee, Instant::now()));
+    }
+
+    /// Drops all the cached fees if the gas price changed since they were calculated.
+    pub fn set_gas_price(&mut self, gas_price: &GasPriceWei) {
+        if self.gas_price.as_ref() != Some(gas_price) {
+            self.fees.clear();
+            self.gas_price = Some(gas_price.clone());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.fees.clear();
+    }
+}
@@ -0,0 +1,165 @@
+//! Fee freeze.
+//!
+//! During the planned maintenance of the price sources or the L1 node the fresh fee inputs
+//! are not available, so the fees jump between the fallbacks. Instead, the operator may freeze
+//! the fees: the gas price and the token prices are fixed the first time they are needed after
+//! the freeze, unless pinned explicitly, and the fees are calculated from this snapshot until
+//! the freeze is lifted. Only the fee inputs are frozen, the token price requests are served
+//! as usual. The freeze is kept in the memory of the API server, so it's lifted on restart.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::RwLock;
+// External deps
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint, Zero};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_types::{TokenId, TokenPrice};
+use zksync_utils::{BigUintSerdeWrapper, UnsignedRatioSerializeAsDecimal};
+// Local deps
+use super::ticker_api::GasPriceWei;
+
+/// USD price of the token fixed by the freeze.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FrozenTokenPrice {
+    pub token: TokenId,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub usd_price: Ratio<BigUint>,
+}
+
+/// Request to freeze the fees. Inputs which are not pinned are fixed once they are needed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeeFreezeRequest {
+    /// Base fee of the block, or the legacy gas price.
+    #[serde(default)]
+    pub base_fee_wei: Option<BigUintSerdeWrapper>,
+    /// Priority fee, zero if not set while the base fee is pinned.
+    #[serde(default)]
+    pub priority_fee_wei: Option<BigUintSerdeWrapper>,
+    #[serde(default)]
+    pub token_prices: Vec<FrozenTokenPrice>,
+}
+
+impl FeeFreezeRequest {
+    pub fn into_freeze(self) -> anyhow::Result<FeeFreeze> {
+        anyhow::ensure!(
+            self.base_fee_wei.is_some() || self.priority_fee_wei.is_none(),
+            "priority fee can't be pinned without the base fee"
+        );
+        anyhow::ensure!(
+            self.token_prices
+                .iter()
+                .all(|price| !price.usd_price.is_zero()),
+            "token prices must be positive"
+        );
+
+        let frozen_at = Utc::now();
+        let gas_price = self.base_fee_wei.map(|base_fee| GasPriceWei {
+            base_fee: base_fee.0,
+            priority_fee: self
+                .priority_fee_wei
+                .map_or_else(BigUint::zero, |priority_fee| priority_fee.0),
+        });
+        let token_prices = self
+            .token_prices
+            .into_iter()
+            .map(|price| {
+                let price_at_freeze = TokenPrice {
+                    usd_price: price.usd_price,
+                    last_updated: frozen_at,
+                };
+                (price.token, price_at_freeze)
+            })
+            .collect();
+
+        Ok(FeeFreeze {
+            frozen_at,
+            inputs: RwLock::new(FrozenInputs {
+                gas_price,
+                token_prices,
+            }),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct FrozenInputs {
+    gas_price: Option<GasPriceWei>,
+    token_prices: HashMap<TokenId, TokenPrice>,
+}
+
+/// Fee inputs snapshot shared by all the ticker actors while the fees are frozen.
+#[derive(Debug)]
+pub struct FeeFreeze {
+    frozen_at: DateTime<Utc>,
+    inputs: RwLock<FrozenInputs>,
+}
+
+impl FeeFreeze {
+    /// Returns the frozen gas price, if it's fixed already.
+    pub(super) fn gas_price(&self) -> Option<GasPriceWei> {
+        self.inputs
+            .read()
+            .expect("fee freeze lock poisoned")
+            .gas_price
+            .clone()
+    }
+
+    /// Fixes the gas price unless it's fixed already. Returns the frozen gas price.
+    pub(super) fn freeze_gas_price(&self, gas_price: GasPriceWei) -> GasPriceWei {
+        let mut inputs = self.inputs.write().expect("fee freeze lock poisoned");
+        inputs.gas_price.get_or_insert(gas_price).clone()
+    }
+
+    /// Returns the frozen price of the token, if it's fixed already.
+    pub(super) fn token_price(&self, token: TokenId) -> Option<TokenPrice> {
+        self.inputs
+            .read()
+            .expect("fee freeze lock poisoned")
+            .token_prices
+            .get(&token)
+            .cloned()
+    }
+
+    /// Fixes the price of the token unless it's fixed already. Returns the frozen price.
+    pub(super) fn freeze_token_price(&self, token: TokenId, price: TokenPrice) -> TokenPrice {
+        let mut inputs = self.inputs.write().expect("fee freeze lock poisoned");
+        inputs.token_prices.entry(token).or_insert(price).clone()
+    }
+
+    pub fn report(&self) -> FeeFreezeReport {
+        let inputs = self.inputs.read().expect("fee freeze lock poisoned");
+        let mut token_prices: Vec<_> = inputs
+            .token_prices
+            .iter()
+            .map(|(&token, price)| FrozenTokenPrice {
+                token,
+                usd_price: price.usd_price.clone(),
+            })
+            .collect();
+        token_prices.sort_by_key(|price| price.token);
+
+        FeeFreezeReport {
+            frozen_at: self.frozen_at,
+            base_fee_wei: inputs
+                .gas_price
+                .as_ref()
+                .map(|gas_price| gas_price.base_fee.clone().into()),
+            priority_fee_wei: inputs
+                .gas_price
+                .as_ref()
+                .map(|gas_price| gas_price.priority_fee.clone().into()),
+            token_prices,
+        }
+    }
+}
+
+/// Fee inputs fixed by the freeze so far.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeFreezeReport {
+    pub frozen_at: DateTime<Utc>,
+    pub base_fee_wei: Option<BigUintSerdeWrapper>,
+    pub priority_fee_wei: Option<BigUintSerdeWrapper>,
+    pub token_prices: Vec<FrozenTokenPrice>,
+}
@@ -0,0 +1,51 @@
+//! Token gas costs.
+//!
+//! Some tokens cost noticeably more gas than the standard costs assume, e.g. the fee-on-transfer
+//! tokens or the ones with unusual `approve` semantics are more expensive to withdraw. The gas cost
+//! of the operation may be overridden for the token the fee is paid in. The override replaces both
+//! the standard and the subsidized gas cost and is used as is, i.e. the fast processing surcharge
+//! is not scaled for it. Overrides are stored in the database and reloaded by the ticker together
+//! with the other settings.
+
+// External deps
+use bigdecimal::BigDecimal;
+use num::{bigint::ToBigInt, BigUint, Zero};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_storage::token_gas_costs::records::{NewTokenGasCost, StoredTokenGasCost};
+use zksync_types::{OutputFeeType, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+/// Gas cost of the operation paid in the token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TokenGasCost {
+    pub token: TokenId,
+    pub fee_type: OutputFeeType,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_cost: BigUint,
+}
+
+impl TokenGasCost {
+    pub fn from_stored(gas_cost: StoredTokenGasCost) -> anyhow::Result<Self> {
+        let gas_cost_int = gas_cost
+            .gas_cost
+            .to_bigint()
+            .and_then(|gas_cost| gas_cost.to_biguint());
+        Ok(Self {
+            token: TokenId(gas_cost.token_id as u16),
+            fee_type: serde_json::from_value(gas_cost.fee_type)?,
+            gas_cost: gas_cost_int.ok_or_else(|| anyhow::anyhow!("gas cost can't be negative"))?,
+        })
+    }
+
+    pub fn into_record(self) -> anyhow::Result<NewTokenGasCost> {
+        anyhow::ensure!(!self.gas_cost.is_zero(), "gas cost must be positive");
+
+        Ok(NewTokenGasCost {
+            token_id: i32::from(*self.token),
+            fee_type: serde_json::to_value(self.fee_type)?,
+            // Converting `BigUint` to `BigInt` is safe.
+            gas_cost: BigDecimal::from(self.gas_cost.to_bigint().unwrap()),
+        })
+    }
+}
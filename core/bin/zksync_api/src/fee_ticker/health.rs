@@ -0,0 +1,164 @@
+//! Health of the fee ticker reported to the operator.
+//!
+//! The tickers record the outcome of every request to the price API, and the request queue
+//! records its depth. The admin server reports the collected state, so the outage of the price
+//! source is noticed before the users start complaining about the fees.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+// External deps
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default)]
+struct TokenQuoteState {
+    last_quote: Option<DateTime<Utc>>,
+    last_error: Option<(DateTime<Utc>, String)>,
+}
+
+#[derive(Debug, Default)]
+struct TickerHealthState {
+    /// Quote outcomes of the tokens, grouped by the price source.
+    sources: HashMap<String, HashMap<String, TokenQuoteState>>,
+    pending_priority_requests: usize,
+    pending_public_requests: usize,
+}
+
+/// Freshness of the token price received from the price source.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenQuoteHealth {
+    pub token: String,
+    /// Time of the last successful quote, if any.
+    pub last_quote: Option<DateTime<Utc>>,
+    /// Age of the last successful quote in seconds.
+    pub last_quote_age_secs: Option<i64>,
+    /// Time of the last failed request to the price source.
+    pub last_error_time: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceSourceHealth {
+    pub source: String,
+    pub tokens: Vec<TokenQuoteHealth>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TickerHealth {
+    pub sources: Vec<PriceSourceHealth>,
+    /// Requests waiting in the ticker request queue.
+    pub pending_priority_requests: usize,
+    pub pending_public_requests: usize,
+}
+
+/// Handle to the ticker health shared between the ticker actors and the admin server.
+#[derive(Debug, Clone, Default)]
+pub struct TickerHealthHandle {
+    state: Arc<RwLock<TickerHealthState>>,
+}
+
+impl TickerHealthHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update_token(&self, source: &str, token: &str, update: impl FnOnce(&mut TokenQuoteState)) {
+        let mut state = self.state.write().expect("ticker health lock poisoned");
+        let token_state = state
+            .sources
+            .entry(source.to_string())
+            .or_default()
+            .entry(token.to_string())
+            .or_default();
+        update(token_state);
+    }
+
+    /// Records the price of the token successfully received from the price source.
+    pub fn record_quote(&self, source: &str, token: &str) {
+        self.update_token(source, token, |state| state.last_quote = Some(Utc::now()));
+    }
+
+    /// Records the failed request of the token price to the price source.
+    pub fn record_error(&self, source: &str, token: &str, error: &anyhow::Error) {
+        self.update_token(source, token, |state| {
+            state.last_error = Some((Utc::now(), error.to_string()))
+        });
+    }
+
+    /// Records the current depth of the ticker request queue.
+    pub fn set_pending_requests(&self, priority: usize, public: usize) {
+        let mut state = self.state.write().expect("ticker health lock poisoned");
+        state.pending_priority_requests = priority;
+        state.pending_public_requests = public;
+    }
+
+    /// Returns the current health of the ticker.
+    pub fn report(&self) -> TickerHealth {
+        let state = self.state.read().expect("ticker health lock poisoned");
+        let now = Utc::now();
+
+        let mut sources: Vec<_> = state
+            .sources
+            .iter()
+            .map(|(source, tokens)| {
+                let mut tokens: Vec<_> = tokens
+                    .iter()
+                    .map(|(token, token_state)| TokenQuoteHealth {
+                        token: token.clone(),
+                        last_quote: token_state.last_quote,
+                        last_quote_age_secs: token_state
+                            .last_quote
+                            .map(|time| now.signed_duration_since(time).num_seconds()),
+                        last_error_time: token_state.last_error.as_ref().map(|(time, _)| *time),
+                        last_error: token_state
+                            .last_error
+                            .as_ref()
+                            .map(|(_, error)| error.clone()),
+                    })
+                    .collect();
+                tokens.sort_by(|a, b| a.token.cmp(&b.token));
+                PriceSourceHealth {
+                    source: source.clone(),
+                    tokens,
+                }
+            })
+            .collect();
+        sources.sort_by(|a, b| a.source.cmp(&b.source));
+
+        TickerHealth {
+            sources,
+            pending_priority_requests: state.pending_priority_requests,
+            pending_public_requests: state.pending_public_requests,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_report() {
+        let health = TickerHealthHandle::new();
+        health.record_quote("CoinGecko", "ETH");
+        health.record_error("CoinGecko", "DAI", &anyhow::format_err!("timeout"));
+        health.record_quote("CoinGecko", "DAI");
+        health.set_pending_requests(1, 5);
+
+        let report = health.report();
+        assert_eq!(report.pending_priority_requests, 1);
+        assert_eq!(report.pending_public_requests, 5);
+        assert_eq!(report.sources.len(), 1);
+        assert_eq!(report.sources[0].source, "CoinGecko");
+
+        let tokens = &report.sources[0].tokens;
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].token, "DAI");
+        assert!(tokens[0].last_quote.is_some());
+        assert_eq!(tokens[0].last_error.as_deref(), Some("timeout"));
+        assert_eq!(tokens[1].token, "ETH");
+        assert_eq!(tokens[1].last_quote_age_secs, Some(0));
+        assert!(tokens[1].last_error.is_none());
+    }
+}
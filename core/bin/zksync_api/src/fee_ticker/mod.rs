@@ -7,38 +7,58 @@
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::iter::FromIterator;
+use std::sync::Arc;
 // External deps
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use futures::{
-    channel::{mpsc::Receiver, oneshot},
-    StreamExt,
-};
-use num::{
-    rational::Ratio,
-    traits::{Inv, Pow},
-    BigUint, Zero,
+    channel::{
+        mpsc::{Receiver, Sender},
+        oneshot,
+    },
+    FutureExt, StreamExt,
 };
+use num::{rational::Ratio, traits::Pow, BigUint, One, ToPrimitive, Zero};
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
+use tracing::Instrument;
 
 // Workspace deps
-use zksync_config::{configs::ticker::TokenPriceSource, ZkSyncConfig};
+use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
-    Address, BatchFee, ChangePubKeyOp, Fee, OutputFeeType, Token, TokenId, TokenLike, TransferOp,
-    TransferToNewOp, TxFeeTypes, WithdrawOp,
+    Address, BatchFee, BatchFeeModel, ChangePubKeyOp, Fee, FeeBreakdown, FeeRoundingPolicy,
+    ForcedExitOp, OutputFeeType, Token, TokenId, TokenLike, TokenPrice, TransferOp,
+    TransferToNewOp, TxFeeTypes, TxGasCost, WithdrawOp,
 };
 use zksync_utils::ratio_to_big_decimal;
 
 // Local deps
+use crate::fee_ticker::audit::FeeAuditLog;
 use crate::fee_ticker::balancer::TickerBalancer;
+use crate::fee_ticker::block_fullness::{run_block_fullness_sampler, PendingBlockFullness};
+use crate::fee_ticker::discounts::FeeDiscount;
+use crate::fee_ticker::dry_run::FeeDryRun;
+use crate::fee_ticker::fee_cache::FeeCache;
+use crate::fee_ticker::freeze::FeeFreeze;
+use crate::fee_ticker::health::TickerHealthHandle;
+use crate::fee_ticker::observed_gas::run_observed_gas_updater;
+use crate::fee_ticker::price_twap::PriceTwap;
+use crate::fee_ticker::queue::{ticker_channel, TickerRequestQueue};
+use crate::fee_ticker::quote::FeeQuoteSigner;
+use crate::fee_ticker::settings::run_settings_updater;
+use crate::fee_ticker::subsidies::FeeSubsidy;
 use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::MarketUpdater;
+use crate::fee_ticker::waivers::FeeWaiver;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        gas_oracle::GasPriceOracle,
+        gas_price_window::{run_gas_price_sampler, GasPriceWindow},
+        registry::{create_price_source, PriceSourceContext},
+        replay::{RecordedSamples, ReplayTickerApi},
+        FeeTickerAPI, GasPriceWei, TickerApi, CONNECTION_TIMEOUT,
     },
     validator::{
         watcher::{TokenWatcher, UniswapTokenWatcher},
@@ -46,17 +66,47 @@ use crate::fee_ticker::{
     },
 };
 use crate::utils::token_db_cache::TokenDBCache;
-use zksync_types::tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg};
+use zksync_types::tokens::{
+    ChangePubKeyFeeType, ChangePubKeyFeeTypeArg, FeeTokenStatus, PriceConfidence, TokenLiquidity,
+};
 
+mod audit;
+mod block_fullness;
 mod constants;
+pub mod correlation;
+pub mod discounts;
+pub mod dry_run;
+mod fee_cache;
+pub mod freeze;
+pub mod gas_costs;
+pub mod health;
+mod observed_gas;
+pub mod price_feed;
+mod price_twap;
+pub mod quote;
+pub mod settings;
+pub mod subsidies;
 mod ticker_api;
 mod ticker_info;
 pub mod validator;
+pub mod waivers;
 
 mod balancer;
+mod queue;
+mod single_flight;
 #[cfg(test)]
 mod tests;
 
+pub use self::queue::TickerOverloaded;
+pub use self::settings::{
+    MaximumFee, MinimumFee, TickerConfigHandle, TickerSettings, TickerSettingsUpdate,
+    TokenFeeRounding, TokenMinimumFee,
+};
+pub use self::ticker_api::storage::{TickerDBStorage, TickerInMemoryStorage, TickerStorage};
+
+/// Sleep time of the actor responsible for removing the outdated token price observations.
+const PRICE_HISTORY_CLEANER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
 /// Contains cost of zkSync operations in Wei.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GasOperationsCost {
@@ -72,6 +122,10 @@ impl GasOperationsCost {
             (constants::BASE_WITHDRAW_COST as f64 * fast_processing_coeff) as u32;
         let subsidy_fast_withdrawal_cost =
             (constants::SUBSIDY_WITHDRAW_COST as f64 * fast_processing_coeff) as u32;
+        let standard_fast_withdrawal_nft_cost =
+            (constants::BASE_WITHDRAW_NFT_COST as f64 * fast_processing_coeff) as u32;
+        let subsidy_fast_withdrawal_nft_cost =
+            (constants::SUBSIDY_WITHDRAW_NFT_COST as f64 * fast_processing_coeff) as u32;
 
         let standard_cost = vec![
             (
@@ -120,6 +174,24 @@ impl GasOperationsCost {
                 )),
                 constants::BASE_CHANGE_PUBKEY_OFFCHAIN_COST.into(),
             ),
+            (
+                OutputFeeType::ForcedExit,
+                constants::BASE_FORCED_EXIT_COST.into(),
+            ),
+            (
+                OutputFeeType::PermitDeposit,
+                constants::BASE_PERMIT_DEPOSIT_COST.into(),
+            ),
+            (OutputFeeType::MintNFT, constants::BASE_MINT_NFT_COST.into()),
+            (
+                OutputFeeType::WithdrawNFT,
+                constants::BASE_WITHDRAW_NFT_COST.into(),
+            ),
+            (
+                OutputFeeType::FastWithdrawNFT,
+                standard_fast_withdrawal_nft_cost.into(),
+            ),
+            (OutputFeeType::Swap, constants::BASE_SWAP_COST.into()),
         ]
         .into_iter()
         .collect::<HashMap<_, _>>();
@@ -141,6 +213,10 @@ impl GasOperationsCost {
                 OutputFeeType::FastWithdraw,
                 subsidy_fast_withdrawal_cost.into(),
             ),
+            (
+                OutputFeeType::ForcedExit,
+                constants::SUBSIDY_FORCED_EXIT_COST.into(),
+            ),
             (
                 OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::PreContracts4Version {
                     onchain_pubkey_auth: false,
@@ -171,6 +247,23 @@ impl GasOperationsCost {
                 )),
                 constants::SUBSIDY_CHANGE_PUBKEY_OFFCHAIN_COST.into(),
             ),
+            (
+                OutputFeeType::PermitDeposit,
+                constants::BASE_PERMIT_DEPOSIT_COST.into(),
+            ),
+            (
+                OutputFeeType::MintNFT,
+                constants::SUBSIDY_MINT_NFT_COST.into(),
+            ),
+            (
+                OutputFeeType::WithdrawNFT,
+                constants::SUBSIDY_WITHDRAW_NFT_COST.into(),
+            ),
+            (
+                OutputFeeType::FastWithdrawNFT,
+                subsidy_fast_withdrawal_nft_cost.into(),
+            ),
+            (OutputFeeType::Swap, constants::SUBSIDY_SWAP_COST.into()),
         ]
         .into_iter()
         .collect::<HashMap<_, _>>();
@@ -180,67 +273,306 @@ impl GasOperationsCost {
             subsidize_cost,
         }
     }
+
+    /// Replaces the standard costs with the observed ones. The fast processing costs
+    /// are derived from the observed costs of the regular operations.
+    pub fn with_observed_costs(
+        mut self,
+        observed_costs: &HashMap<OutputFeeType, BigUint>,
+        fast_processing_coeff: f64,
+    ) -> Self {
+        for (&fee_type, gas_cost) in observed_costs {
+            self.standard_cost.insert(fee_type, gas_cost.clone());
+
+            let fast_fee_type = match fee_type {
+                OutputFeeType::Withdraw => OutputFeeType::FastWithdraw,
+                OutputFeeType::WithdrawNFT => OutputFeeType::FastWithdrawNFT,
+                _ => continue,
+            };
+            let fast_gas_cost = gas_cost.to_f64().unwrap_or(f64::MAX) * fast_processing_coeff;
+            self.standard_cost
+                .insert(fast_fee_type, BigUint::from(fast_gas_cost as u64));
+        }
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TickerConfig {
     zkp_cost_chunk_usd: Ratio<BigUint>,
+    fast_processing_coeff: f64,
     gas_cost_tx: GasOperationsCost,
+    /// Gas costs of the operations observed on L1, replacing the standard ones from `gas_cost_tx`.
+    observed_gas_costs: HashMap<OutputFeeType, BigUint>,
+    /// Gas costs of the operations paid in the tokens, overriding the ones from `gas_cost_tx`.
+    token_gas_costs: HashMap<TokenId, HashMap<OutputFeeType, BigUint>>,
     tokens_risk_factors: HashMap<TokenId, Ratio<BigUint>>,
+    /// Risk factors of the tokens set in the config, used unless set in the database.
+    config_risk_factors: HashMap<Address, Ratio<BigUint>>,
     not_subsidized_tokens: HashSet<Address>,
+    subsidies: Vec<FeeSubsidy>,
+    fee_discounts: HashMap<Address, Vec<FeeDiscount>>,
+    fee_waivers: Vec<FeeWaiver>,
+    /// Rounding of the fees paid in the token, if other than the default one.
+    fee_rounding: HashMap<TokenId, FeeRoundingPolicy>,
+    /// Minimum fees paid in the tokens.
+    minimum_fees: HashMap<TokenId, MinimumFee>,
+    /// Maximum fees of the transaction types in USD.
+    maximum_fees: HashMap<OutputFeeType, Ratio<BigUint>>,
+    /// Model of the gas cost of the transactions batch.
+    batch_fee_model: BatchFeeModel,
+    /// Fee inputs snapshot the fees are calculated from, if the fees are frozen.
+    fee_freeze: Option<Arc<FeeFreeze>>,
+}
+
+impl TickerConfig {
+    pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self {
+            zkp_cost_chunk_usd: config.ticker.zkp_cost_chunk_usd.clone(),
+            fast_processing_coeff: config.ticker.fast_processing_coeff,
+            gas_cost_tx: GasOperationsCost::from_constants(config.ticker.fast_processing_coeff),
+            observed_gas_costs: HashMap::new(),
+            token_gas_costs: HashMap::new(),
+            tokens_risk_factors: HashMap::new(),
+            config_risk_factors: config
+                .ticker
+                .tokens_risk_factors
+                .iter()
+                .map(|factor| (factor.token, factor.risk_factor.clone()))
+                .collect(),
+            not_subsidized_tokens: HashSet::from_iter(config.ticker.not_subsidized_tokens.clone()),
+            subsidies: Vec::new(),
+            fee_discounts: HashMap::new(),
+            fee_waivers: Vec::new(),
+            fee_rounding: HashMap::new(),
+            minimum_fees: HashMap::new(),
+            maximum_fees: HashMap::new(),
+            batch_fee_model: BatchFeeModel::new(config.ticker.batch_overhead_share()),
+            fee_freeze: None,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenPriceRequestType {
     USDForOneWei,
     USDForOneToken,
 }
 
+/// Price of the token in the requested units together with its origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceQuote {
+    pub price: BigDecimal,
+    /// Name of the price API the price is received from.
+    pub source: String,
+    /// Time the price was received from the price API.
+    pub last_updated: DateTime<Utc>,
+    pub confidence: PriceConfidence,
+}
+
+impl PriceQuote {
+    /// Age of the price at the moment, in seconds.
+    pub fn age_secs(&self) -> i64 {
+        Utc::now()
+            .signed_duration_since(self.last_updated)
+            .num_seconds()
+    }
+}
+
 #[derive(Debug)]
 pub enum TickerRequest {
+    /// Quotes the fee of a single transaction.
     GetTxFee {
         tx_type: TxFeeTypes,
+        /// Recipient of the transaction.
         address: Address,
+        /// Sender of the transaction, if known. Fee discounts of the sender are applied.
+        sender: Option<Address>,
+        /// Token the fee is paid in, which is not necessarily the token of the transaction:
+        /// `ChangePubKey` has a separate fee token.
+        /// Since `Transfer` has a single token, paying its fee in another token requires
+        /// a batch quoted with `GetBatchTxFee`.
         token: TokenLike,
         response: oneshot::Sender<Result<Fee, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
     },
+    /// Calculates the fee of a single transaction like `GetTxFee` does, and reports every input
+    /// of the calculation. Used by the support to explain the charged fee.
+    GetTxFeeDryRun {
+        tx_type: TxFeeTypes,
+        address: Address,
+        sender: Option<Address>,
+        token: TokenLike,
+        response: oneshot::Sender<Result<FeeDryRun, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
+    },
+    /// Quotes the total fee of a transactions batch.
     GetBatchTxFee {
+        /// Types and recipients of the batch transactions.
         transactions: Vec<(TxFeeTypes, Address)>,
+        /// Token the fee of the whole batch is paid in.
         token: TokenLike,
         response: oneshot::Sender<Result<BatchFee, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
     },
     GetTokenPrice {
         token: TokenLike,
-        response: oneshot::Sender<Result<BigDecimal, anyhow::Error>>,
+        response: oneshot::Sender<Result<PriceQuote, anyhow::Error>>,
+        req_type: TokenPriceRequestType,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
+    },
+    /// Resolves the prices of several tokens at once. The price is `None` if the token
+    /// is not found or its price is not available.
+    GetTokenPriceBatch {
+        tokens: Vec<TokenLike>,
+        response: oneshot::Sender<Vec<Option<BigDecimal>>>,
         req_type: TokenPriceRequestType,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
     },
     IsTokenAllowed {
         token: TokenLike,
         response: oneshot::Sender<Result<bool, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
+    },
+    GetTokenFeeStatus {
+        token: TokenLike,
+        response: oneshot::Sender<Result<FeeTokenStatus, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
+    },
+    /// Returns the last measured liquidity of the token, or `None` if it's not measured yet.
+    GetTokenLiquidity {
+        token: TokenLike,
+        response: oneshot::Sender<Result<Option<TokenLiquidity>, anyhow::Error>>,
+        /// Span of the API request the request is issued for.
+        span: tracing::Span,
     },
 }
 
+impl TickerRequest {
+    /// Responds to the request with the error without handling it.
+    /// Batch price requests have no error response, so all the prices are reported as unavailable.
+    fn reject(self, error: anyhow::Error) {
+        match self {
+            TickerRequest::GetTxFee { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetTxFeeDryRun { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetBatchTxFee { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetTokenPrice { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetTokenPriceBatch {
+                tokens, response, ..
+            } => response.send(vec![None; tokens.len()]).unwrap_or_default(),
+            TickerRequest::IsTokenAllowed { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetTokenFeeStatus { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+            TickerRequest::GetTokenLiquidity { response, .. } => {
+                response.send(Err(error)).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Returns the token the request is about, or `None` if it covers several tokens.
+    fn token(&self) -> Option<&TokenLike> {
+        match self {
+            TickerRequest::GetTxFee { token, .. }
+            | TickerRequest::GetTxFeeDryRun { token, .. }
+            | TickerRequest::GetBatchTxFee { token, .. }
+            | TickerRequest::GetTokenPrice { token, .. }
+            | TickerRequest::IsTokenAllowed { token, .. }
+            | TickerRequest::GetTokenFeeStatus { token, .. }
+            | TickerRequest::GetTokenLiquidity { token, .. } => Some(token),
+            TickerRequest::GetTokenPriceBatch { .. } => None,
+        }
+    }
+
+    /// Creates the span of the request handling, nested into the span of the API request.
+    fn handling_span(&self) -> tracing::Span {
+        let (kind, parent) = match self {
+            TickerRequest::GetTxFee { span, .. } => ("get_tx_fee", span),
+            TickerRequest::GetTxFeeDryRun { span, .. } => ("get_tx_fee_dry_run", span),
+            TickerRequest::GetBatchTxFee { span, .. } => ("get_batch_tx_fee", span),
+            TickerRequest::GetTokenPrice { span, .. } => ("get_token_price", span),
+            TickerRequest::GetTokenPriceBatch { span, .. } => ("get_token_price_batch", span),
+            TickerRequest::IsTokenAllowed { span, .. } => ("is_token_allowed", span),
+            TickerRequest::GetTokenFeeStatus { span, .. } => ("get_token_fee_status", span),
+            TickerRequest::GetTokenLiquidity { span, .. } => ("get_token_liquidity", span),
+        };
+        tracing::info_span!(parent: parent, "ticker_request", kind)
+    }
+}
+
 struct FeeTicker<API, INFO, WATCHER> {
     api: API,
     info: INFO,
     requests: Receiver<TickerRequest>,
-    config: TickerConfig,
+    /// Config used to process the current request.
+    config: Arc<TickerConfig>,
+    config_handle: TickerConfigHandle,
     validator: FeeTokenValidator<WATCHER>,
+    audit_log: Option<FeeAuditLog>,
+    quote_signer: Option<FeeQuoteSigner>,
+    /// If set, the fast processing surcharge is scaled by the share of the free chunks
+    /// in the pending block.
+    block_fullness: Option<PendingBlockFullness>,
+    /// If set, the calculated fees are reused for the identical requests for a short time.
+    fee_cache: Option<FeeCache>,
+    /// If set, the fees are calculated from the time-weighted average token prices.
+    price_twap: Option<PriceTwap>,
+    /// If set, the requests not handled in time are answered with `TickerRequestTimeout`.
+    request_timeout: Option<std::time::Duration>,
+}
+
+/// Error returned for the requests the ticker failed to handle in time, e.g. because
+/// the price API hangs.
+#[derive(Debug, thiserror::Error)]
+#[error("Fee ticker request timed out")]
+pub struct TickerRequestTimeout;
+
+/// Awaits the handling of the request for at most `timeout`, if it's set.
+async fn with_timeout<T>(
+    timeout: Option<std::time::Duration>,
+    handling: impl std::future::Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return handling.await,
+    };
+    tokio::time::timeout(timeout, handling)
+        .await
+        .unwrap_or_else(|_| {
+            metrics::counter!("ticker.request_timeout", 1);
+            Err(TickerRequestTimeout.into())
+        })
 }
 
+/// Runs the fee ticker. The returned task finishes once the `shutdown` signal is received
+/// (or its sender is dropped) and all the accepted requests are answered.
 #[must_use]
 pub fn run_ticker_task(
     db_pool: ConnectionPool,
-    tricker_requests: Receiver<TickerRequest>,
+    priority_requests: Receiver<TickerRequest>,
+    public_requests: Receiver<TickerRequest>,
+    ticker_config: TickerConfigHandle,
+    ticker_health: TickerHealthHandle,
+    shutdown: oneshot::Receiver<()>,
     config: &ZkSyncConfig,
 ) -> JoinHandle<()> {
-    let ticker_config = TickerConfig {
-        zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
-        gas_cost_tx: GasOperationsCost::from_constants(config.ticker.fast_processing_coeff),
-        tokens_risk_factors: HashMap::new(),
-        not_subsidized_tokens: HashSet::from_iter(config.ticker.not_subsidized_tokens.clone()),
-    };
-
     let cache = (db_pool.clone(), TokenDBCache::new());
     let watcher = UniswapTokenWatcher::new(config.ticker.uniswap_url.clone());
     let validator = FeeTokenValidator::new(
@@ -252,48 +584,239 @@ pub fn run_ticker_task(
     );
 
     let updater = MarketUpdater::new(cache, watcher);
-    tokio::spawn(updater.keep_updated(config.ticker.token_market_update_time));
+    tokio::spawn(vlog::supervised(
+        "ticker_market_updater",
+        updater.keep_updated(config.ticker.token_market_update_time),
+    ));
     let client = reqwest::ClientBuilder::new()
         .timeout(CONNECTION_TIMEOUT)
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.ticker.price_source();
-    match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
-            let ticker_info = TickerInfo::new(db_pool);
-            let fee_ticker = FeeTicker::new(
-                ticker_api,
-                ticker_info,
-                tricker_requests,
-                ticker_config,
-                validator,
-            );
+    tokio::spawn(vlog::supervised(
+        "price_history_cleaner",
+        run_price_history_cleaner(db_pool.clone(), config.ticker.price_history_retention()),
+    ));
+    tokio::spawn(vlog::supervised(
+        "ticker_settings_updater",
+        run_settings_updater(db_pool.clone(), ticker_config.clone()),
+    ));
+    if let Some(window) = config.ticker.observed_gas_window() {
+        tokio::spawn(vlog::supervised(
+            "observed_gas_updater",
+            run_observed_gas_updater(
+                db_pool.clone(),
+                ticker_config.clone(),
+                window,
+                config.ticker.observed_gas_min_ops,
+                config.ticker.observed_gas_multiplier(),
+            ),
+        ));
+    }
+    let gas_price_oracle = GasPriceOracle::from_config(config);
+    let gas_price_window = Arc::new(tokio::sync::Mutex::new(GasPriceWindow::new(
+        config.ticker.gas_price_window_size,
+        config.ticker.gas_price_window(),
+    )));
+    tokio::spawn(vlog::supervised(
+        "gas_price_sampler",
+        run_gas_price_sampler(
+            TickerDBStorage::new(db_pool.clone()),
+            gas_price_oracle.clone(),
+            gas_price_window.clone(),
+            config.ticker.gas_price_sampling_interval(),
+        ),
+    ));
 
-            tokio::spawn(fee_ticker.run())
-        }
+    let audit_log = if config.ticker.fee_audit_enabled {
+        Some(FeeAuditLog::spawn(
+            db_pool.clone(),
+            config.ticker.fee_audit_retention(),
+        ))
+    } else {
+        None
+    };
+    let quote_signer = FeeQuoteSigner::from_config(&config.ticker);
+    let block_fullness = if config.ticker.dynamic_fast_processing_enabled {
+        let block_fullness =
+            PendingBlockFullness::new(config.ticker.block_fullness_sampling_interval() * 3);
+        let block_size = *config
+            .chain
+            .state_keeper
+            .block_chunk_sizes
+            .iter()
+            .max()
+            .expect("Expected at least one block chunks size");
+        tokio::spawn(vlog::supervised(
+            "block_fullness_sampler",
+            run_block_fullness_sampler(
+                db_pool.clone(),
+                block_fullness.clone(),
+                block_size,
+                config.ticker.block_fullness_sampling_interval(),
+            ),
+        ));
+        Some(block_fullness)
+    } else {
+        None
+    };
 
-        TokenPriceSource::CoinGecko => {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .expect("failed to init CoinGecko client");
-            let ticker_info = TickerInfo::new(db_pool.clone());
+    // The standby ticker shares the caches with the primary one, so it's warmed up
+    // by the time it takes over.
+    let setup = TickerSetup {
+        db_pool: db_pool.clone(),
+        ticker_config,
+        validator,
+        audit_log,
+        quote_signer,
+        block_fullness,
+        fee_cache_ttl: config.ticker.fee_cache_ttl(),
+        price_twap: config.ticker.price_twap_window().map(PriceTwap::new),
+        request_timeout: config.ticker.request_timeout(),
+        number_of_balancers: if config.ticker.standby_ticker_enabled {
+            2
+        } else {
+            1
+        },
+        number_of_ticker_actors: config.ticker.number_of_ticker_actors,
+    };
+
+    let (tickers, ticker_tasks) = if let Some((samples_file, at)) = config.ticker.replay() {
+        vlog::warn!(
+            "Fee ticker replays the fee inputs recorded in {}",
+            samples_file
+        );
+        let samples = RecordedSamples::load(samples_file).expect("Failed to load fee samples");
+        let ticker_api = ReplayTickerApi::new(TickerDBStorage::new(db_pool), samples, at)
+            .expect("Failed to init fee replay");
+        setup.spawn(ticker_api)
+    } else {
+        let (price_source, base_url) = config.ticker.price_source();
+        let context = PriceSourceContext {
+            config,
+            base_url,
+            client: &client,
+        };
+        let token_price_api =
+            create_price_source(&price_source, &context).expect("Failed to create the price API");
+        let fallback_price_apis = config
+            .ticker
+            .fallback_price_sources()
+            .into_iter()
+            .map(|(source, base_url)| {
+                let context = PriceSourceContext {
+                    config,
+                    base_url,
+                    client: &client,
+                };
+                let api = create_price_source(&source, &context)
+                    .expect("Failed to create the fallback price API");
+                (source.name().to_string(), api)
+            })
+            .collect();
+        let ticker_api = TickerApi::new(db_pool, token_price_api)
+            .with_token_db_cache(TokenDBCache::new())
+            .with_price_source(price_source.name())
+            .with_fallback_price_sources(fallback_price_apis)
+            .with_price_aggregation(config.ticker.token_price_aggregation)
+            .with_max_source_price_deviation(config.ticker.max_source_price_deviation())
+            .with_health(ticker_health.clone())
+            .with_gas_price_window(gas_price_window)
+            .with_gas_price_oracle(gas_price_oracle)
+            .with_max_price_deviation(config.ticker.max_price_deviation())
+            .with_max_price_staleness(config.ticker.max_price_staleness());
+        setup.spawn(ticker_api)
+    };
+
+    let request_queue = TickerRequestQueue::new(
+        priority_requests,
+        public_requests,
+        tickers,
+        config.ticker.max_pending_requests,
+        config.ticker.hang_timeout(),
+    )
+    .with_health(ticker_health)
+    .with_shutdown(shutdown);
+    tokio::spawn(vlog::supervised("ticker_request_queue", async move {
+        request_queue.run().await;
+        // The queue is dropped by now, so the tickers stop once they handle the requests
+        // they have already taken.
+        futures::future::join_all(ticker_tasks).await;
+    }))
+}
+
+/// Settings of the tickers which don't depend on the ticker API.
+struct TickerSetup {
+    db_pool: ConnectionPool,
+    ticker_config: TickerConfigHandle,
+    validator: FeeTokenValidator<UniswapTokenWatcher>,
+    audit_log: Option<FeeAuditLog>,
+    quote_signer: Option<FeeQuoteSigner>,
+    block_fullness: Option<PendingBlockFullness>,
+    fee_cache_ttl: Option<std::time::Duration>,
+    price_twap: Option<PriceTwap>,
+    request_timeout: Option<std::time::Duration>,
+    /// Number of the balancers, including the standby one.
+    number_of_balancers: usize,
+    number_of_ticker_actors: u8,
+}
+
+impl TickerSetup {
+    /// Spawns the balancers and their tickers sharing the ticker API. Returns the channels
+    /// of the balancers and the handles of all the spawned tasks.
+    fn spawn<API>(self, ticker_api: API) -> (Vec<Sender<TickerRequest>>, Vec<JoinHandle<()>>)
+    where
+        API: FeeTickerAPI + Clone + Sync + Send + 'static,
+    {
+        let mut tickers = Vec::with_capacity(self.number_of_balancers);
+        let mut ticker_tasks = Vec::new();
+        for _ in 0..self.number_of_balancers {
+            let (ticker_sender, ticker_requests) = ticker_channel();
+            let ticker_info = TickerInfo::new(self.db_pool.clone());
 
             let mut ticker_balancer = TickerBalancer::new(
-                token_price_api,
+                ticker_api.clone(),
                 ticker_info,
-                ticker_config,
-                validator,
-                tricker_requests,
-                db_pool,
-                config.ticker.number_of_ticker_actors,
-            );
-            ticker_balancer.spawn_tickers();
-            tokio::spawn(ticker_balancer.run())
+                self.ticker_config.clone(),
+                self.validator.clone(),
+                ticker_requests,
+                self.number_of_ticker_actors,
+            )
+            .with_audit_log(self.audit_log.clone())
+            .with_quote_signer(self.quote_signer.clone())
+            .with_block_fullness(self.block_fullness.clone())
+            .with_fee_cache_ttl(self.fee_cache_ttl)
+            .with_price_twap(self.price_twap.clone())
+            .with_request_timeout(self.request_timeout);
+            ticker_tasks.extend(ticker_balancer.spawn_tickers());
+            ticker_tasks.push(tokio::spawn(vlog::supervised(
+                "ticker_balancer",
+                ticker_balancer.run(),
+            )));
+            tickers.push(ticker_sender);
+        }
+        (tickers, ticker_tasks)
+    }
+}
+
+/// Periodically removes the price observations which are older than the retention period.
+async fn run_price_history_cleaner(db_pool: ConnectionPool, retention: chrono::Duration) {
+    let mut timer = tokio::time::interval(PRICE_HISTORY_CLEANER_INTERVAL);
+    loop {
+        timer.tick().await;
+
+        let result = async {
+            let mut storage = db_pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .remove_price_history_before(chrono::Utc::now() - retention)
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(removed) => vlog::debug!("Removed {} outdated token price observations", removed),
+            Err(e) => vlog::error!("Failed to remove outdated token price observations: {}", e),
         }
     }
 }
@@ -303,65 +826,185 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         api: API,
         info: INFO,
         requests: Receiver<TickerRequest>,
-        config: TickerConfig,
+        config_handle: TickerConfigHandle,
         validator: FeeTokenValidator<WATCHER>,
     ) -> Self {
         Self {
             api,
             info,
             requests,
-            config,
+            config: config_handle.get(),
+            config_handle,
             validator,
+            audit_log: None,
+            quote_signer: None,
+            block_fullness: None,
+            fee_cache: None,
+            price_twap: None,
+            request_timeout: None,
         }
     }
 
-    /// Increases the gas price by a constant coefficient.
+    /// Sets the log used to record the returned fee quotes.
+    fn with_audit_log(self, audit_log: Option<FeeAuditLog>) -> Self {
+        Self { audit_log, ..self }
+    }
+
+    /// Sets the signer of the fee quotes, so the quoted fees are accepted until expiry.
+    fn with_quote_signer(self, quote_signer: Option<FeeQuoteSigner>) -> Self {
+        Self {
+            quote_signer,
+            ..self
+        }
+    }
+
+    /// Sets the fullness of the pending block the fast processing surcharge is scaled by.
+    fn with_block_fullness(self, block_fullness: Option<PendingBlockFullness>) -> Self {
+        Self {
+            block_fullness,
+            ..self
+        }
+    }
+
+    /// Sets the time the calculated fees are reused for. Fees are not cached if not set.
+    fn with_fee_cache_ttl(self, fee_cache_ttl: Option<std::time::Duration>) -> Self {
+        Self {
+            fee_cache: fee_cache_ttl.map(FeeCache::new),
+            ..self
+        }
+    }
+
+    /// Sets the averaged token prices the fees are calculated from. Spot prices are used if not set.
+    fn with_price_twap(self, price_twap: Option<PriceTwap>) -> Self {
+        Self { price_twap, ..self }
+    }
+
+    /// Sets the time the requests are handled for. The handling is not limited if not set.
+    fn with_request_timeout(self, request_timeout: Option<std::time::Duration>) -> Self {
+        Self {
+            request_timeout,
+            ..self
+        }
+    }
+
+    /// Increases the base fee by a constant coefficient and adds the priority fee to it.
     /// Due to the high volatility of gas prices, we are include the risk
-    /// in the fee in order not to go into negative territory.
-    fn risk_gas_price_estimate(gas_price: BigUint) -> BigUint {
-        gas_price * BigUint::from(130u32) / BigUint::from(100u32)
+    /// in the fee in order not to go into negative territory. The priority fee
+    /// doesn't depend on the network congestion that much, so it's not scaled.
+    fn risk_gas_price_estimate(gas_price: &GasPriceWei) -> BigUint {
+        &gas_price.base_fee * BigUint::from(130u32) / BigUint::from(100u32)
+            + &gas_price.priority_fee
     }
 
     async fn run(mut self) {
         while let Some(request) = self.requests.next().await {
-            let start = Instant::now();
-            match request {
-                TickerRequest::GetTxFee {
-                    tx_type,
-                    token,
-                    response,
-                    address,
-                } => {
-                    let fee = self
-                        .get_fee_from_ticker_in_wei(tx_type, token, address)
-                        .await;
-                    metrics::histogram!("ticker.get_tx_fee", start.elapsed());
-                    response.send(fee).unwrap_or_default()
-                }
-                TickerRequest::GetTokenPrice {
-                    token,
-                    response,
-                    req_type,
-                } => {
-                    let price = self.get_token_price(token, req_type).await;
-                    metrics::histogram!("ticker.get_token_price", start.elapsed());
-                    response.send(price).unwrap_or_default();
-                }
-                TickerRequest::IsTokenAllowed { token, response } => {
-                    let allowed = self.validator.token_allowed(token).await;
-                    metrics::histogram!("ticker.is_token_allowed", start.elapsed());
-                    response.send(allowed).unwrap_or_default();
-                }
-                TickerRequest::GetBatchTxFee {
-                    transactions,
-                    token,
-                    response,
-                } => {
-                    let fee = self.get_batch_from_ticker_in_wei(token, transactions).await;
-                    metrics::histogram!("ticker.get_tx_fee", start.elapsed());
-                    response.send(fee).unwrap_or_default()
+            // Pick up the settings adjusted by the operator.
+            let config = self.config_handle.get();
+            if !Arc::ptr_eq(&config, &self.config) {
+                if let Some(fee_cache) = self.fee_cache.as_mut() {
+                    fee_cache.clear();
                 }
             }
+            self.config = config;
+            let span = request.handling_span();
+            self.handle_request(request).instrument(span).await;
+        }
+    }
+
+    /// Handles the request and sends the response. The request not handled in time is answered
+    /// with the timeout error, so the following requests are not stalled.
+    async fn handle_request(&mut self, request: TickerRequest) {
+        let start = Instant::now();
+        let timeout = self.request_timeout;
+        match request {
+            TickerRequest::GetTxFee {
+                tx_type,
+                token,
+                response,
+                address,
+                sender,
+                ..
+            } => {
+                let fee = with_timeout(
+                    timeout,
+                    self.get_fee_from_ticker_in_wei(tx_type, token, address, sender),
+                )
+                .await;
+                metrics::histogram!("ticker.get_tx_fee", start.elapsed());
+                response.send(fee).unwrap_or_default()
+            }
+            TickerRequest::GetTxFeeDryRun {
+                tx_type,
+                address,
+                sender,
+                token,
+                response,
+                ..
+            } => {
+                let dry_run =
+                    with_timeout(timeout, self.fee_dry_run(tx_type, token, address, sender)).await;
+                metrics::histogram!("ticker.get_tx_fee_dry_run", start.elapsed());
+                response.send(dry_run).unwrap_or_default()
+            }
+            TickerRequest::GetTokenPrice {
+                token,
+                response,
+                req_type,
+                ..
+            } => {
+                let quote =
+                    with_timeout(timeout, self.get_token_price_quote(token, req_type)).await;
+                metrics::histogram!("ticker.get_token_price", start.elapsed());
+                response.send(quote).unwrap_or_default();
+            }
+            TickerRequest::GetTokenPriceBatch {
+                tokens,
+                response,
+                req_type,
+                ..
+            } => {
+                let tokens_count = tokens.len();
+                let prices = with_timeout(timeout, self.get_token_prices(tokens, req_type).map(Ok))
+                    .await
+                    .unwrap_or_else(|_| vec![None; tokens_count]);
+                metrics::histogram!("ticker.get_token_price_batch", start.elapsed());
+                response.send(prices).unwrap_or_default();
+            }
+            TickerRequest::IsTokenAllowed {
+                token, response, ..
+            } => {
+                let allowed = with_timeout(timeout, self.validator.token_allowed(token)).await;
+                metrics::histogram!("ticker.is_token_allowed", start.elapsed());
+                response.send(allowed).unwrap_or_default();
+            }
+            TickerRequest::GetTokenFeeStatus {
+                token, response, ..
+            } => {
+                let status = with_timeout(timeout, self.validator.token_status(token)).await;
+                metrics::histogram!("ticker.get_token_fee_status", start.elapsed());
+                response.send(status).unwrap_or_default();
+            }
+            TickerRequest::GetTokenLiquidity {
+                token, response, ..
+            } => {
+                let liquidity = with_timeout(timeout, self.validator.token_liquidity(token)).await;
+                metrics::histogram!("ticker.get_token_liquidity", start.elapsed());
+                response.send(liquidity).unwrap_or_default();
+            }
+            TickerRequest::GetBatchTxFee {
+                transactions,
+                token,
+                response,
+                ..
+            } => {
+                let fee = with_timeout(
+                    timeout,
+                    self.get_batch_from_ticker_in_wei(token, transactions),
+                )
+                .await;
+                metrics::histogram!("ticker.get_tx_fee", start.elapsed());
+                response.send(fee).unwrap_or_default()
+            }
         }
     }
 
@@ -370,6 +1013,16 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         token: TokenLike,
         request_type: TokenPriceRequestType,
     ) -> Result<BigDecimal, anyhow::Error> {
+        self.get_token_price_quote(token, request_type)
+            .await
+            .map(|quote| quote.price)
+    }
+
+    async fn get_token_price_quote(
+        &self,
+        token: TokenLike,
+        request_type: TokenPriceRequestType,
+    ) -> Result<PriceQuote, anyhow::Error> {
         let factor = match request_type {
             TokenPriceRequestType::USDForOneWei => {
                 let token_decimals = self.api.get_token(token.clone()).await?.decimals;
@@ -378,26 +1031,51 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
             TokenPriceRequestType::USDForOneToken => BigUint::from(1u32),
         };
 
-        self.api
-            .get_last_quote(token)
-            .await
-            .map(|price| ratio_to_big_decimal(&(price.usd_price / factor), 100))
-    }
-
-    /// Returns `true` if the token is subsidized.
-    fn is_token_subsidized(&self, token: &Token) -> bool {
-        // We have disabled the subsidies up until the contract upgrade (when the prices will indeed become that
-        // low), but however we want to leave ourselves the possibility to easily enable them if required.
-        // Thus:
-        // TODO: Remove subsidies completely (ZKS-226)
-        let subsidies_enabled = std::env::var("TICKER_SUBSIDIES_ENABLED")
-            .map(|val| val == "true")
-            .unwrap_or(false);
-        if !subsidies_enabled {
+        let sourced_price = self.api.get_sourced_quote(token).await?;
+        Ok(PriceQuote {
+            price: ratio_to_big_decimal(&(sourced_price.price.usd_price / factor), 100),
+            source: sourced_price.source,
+            last_updated: sourced_price.price.last_updated,
+            confidence: sourced_price.confidence,
+        })
+    }
+
+    /// Resolves the prices of the tokens in one pass, so every distinct token is only looked up once.
+    async fn get_token_prices(
+        &self,
+        tokens: Vec<TokenLike>,
+        request_type: TokenPriceRequestType,
+    ) -> Vec<Option<BigDecimal>> {
+        let mut resolved: HashMap<TokenLike, Option<BigDecimal>> = HashMap::new();
+        let mut prices = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            if let Some(price) = resolved.get(&token) {
+                prices.push(price.clone());
+                continue;
+            }
+
+            let price = self
+                .get_token_price(token.clone(), request_type)
+                .await
+                .map_err(|e| vlog::debug!("Failed to get the price of {:?}: {}", token, e))
+                .ok();
+            resolved.insert(token, price.clone());
+            prices.push(price);
+        }
+        prices
+    }
+
+    /// Returns `true` if the fee of the given type paid in the token is subsidized.
+    fn is_subsidized(&self, token: &Token, fee_type: OutputFeeType) -> bool {
+        if self.config.not_subsidized_tokens.contains(&token.address) {
             return false;
         }
 
-        !self.config.not_subsidized_tokens.contains(&token.address)
+        let now = chrono::Utc::now();
+        self.config
+            .subsidies
+            .iter()
+            .any(|subsidy| subsidy.is_active(token.id, fee_type, now))
     }
 
     async fn get_fee_from_ticker_in_wei(
@@ -405,31 +1083,255 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         tx_type: TxFeeTypes,
         token: TokenLike,
         recipient: Address,
+        sender: Option<Address>,
     ) -> Result<Fee, anyhow::Error> {
-        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
-        let token = self.api.get_token(token).await?;
+        let start = Instant::now();
+        let (token, gas_price_wei) = self.token_and_gas_price(token).await?;
+        let op = self.gas_tx_amount(&token, tx_type, recipient).await;
+        let fee_type = op.0;
+
+        let cacheable = self
+            .is_fee_cacheable(&token, fee_type, recipient, sender)
+            .await;
+        let cached_fee = if cacheable {
+            self.cached_fee(&token, fee_type, &gas_price_wei)
+        } else {
+            None
+        };
+        let mut fee = match cached_fee {
+            Some(fee) => {
+                metrics::counter!("ticker.fee_cache.hits", 1);
+                fee
+            }
+            None => {
+                let dry_run = self
+                    .calculate_fee(token.clone(), op, gas_price_wei.clone(), recipient, sender)
+                    .await?;
+                if cacheable {
+                    self.cache_fee(&dry_run, &gas_price_wei);
+                }
+                dry_run.fee
+            }
+        };
+        Self::report_fee_quote(
+            &token,
+            fee_type,
+            self.is_subsidized(&token, fee_type),
+            start.elapsed(),
+        );
+        if fee.waived {
+            return Ok(fee);
+        }
+
+        if let Some(signer) = &self.quote_signer {
+            fee.quote = Some(signer.sign(
+                tx_type,
+                recipient,
+                sender,
+                token.id,
+                &fee.total_fee,
+                &fee.subsidy,
+            ));
+        }
+        if let Some(audit_log) = self.audit_log.as_mut() {
+            audit_log.record_quote(&fee, recipient, token.id);
+        }
+
+        Ok(fee)
+    }
+
+    /// Returns `true` if the fee calculated for the request can be reused for the identical ones.
+    /// Fees depending on the sender or the recipient account are not cached.
+    async fn is_fee_cacheable(
+        &mut self,
+        token: &Token,
+        fee_type: OutputFeeType,
+        recipient: Address,
+        sender: Option<Address>,
+    ) -> bool {
+        if self.fee_cache.is_none() {
+            return false;
+        }
+        let discounted = sender.map_or(false, |sender| !self.discount(sender, fee_type).is_zero());
+        !discounted && !self.is_waived(token, fee_type, recipient).await
+    }
 
+    /// Returns the fee of the operation paid in the token calculated with the current gas price
+    /// recently, if any.
+    fn cached_fee(
+        &mut self,
+        token: &Token,
+        fee_type: OutputFeeType,
+        gas_price_wei: &GasPriceWei,
+    ) -> Option<Fee> {
+        let fee_cache = self.fee_cache.as_mut()?;
+        fee_cache.set_gas_price(gas_price_wei);
+        fee_cache.get(fee_type, token.id)
+    }
+
+    fn cache_fee(&mut self, dry_run: &FeeDryRun, gas_price_wei: &GasPriceWei) {
+        if let Some(fee_cache) = self.fee_cache.as_mut() {
+            fee_cache.insert(
+                dry_run.fee.fee_type,
+                dry_run.token.id,
+                gas_price_wei,
+                dry_run.fee.clone(),
+            );
+        }
+    }
+
+    /// Resolves the token and requests the current gas price concurrently.
+    async fn token_and_gas_price(&self, token: TokenLike) -> anyhow::Result<(Token, GasPriceWei)> {
+        let (token, gas_price_wei) =
+            futures::join!(self.api.get_token(token), self.gas_price_wei());
+        Ok((token?, gas_price_wei?))
+    }
+
+    /// Returns the current gas price, or the frozen one while the fees are frozen.
+    async fn gas_price_wei(&self) -> anyhow::Result<GasPriceWei> {
+        let freeze = match &self.config.fee_freeze {
+            Some(freeze) => freeze,
+            None => return self.api.get_gas_price_wei().await,
+        };
+        if let Some(gas_price_wei) = freeze.gas_price() {
+            return Ok(gas_price_wei);
+        }
         let gas_price_wei = self.api.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
-        let is_token_subsidized = self.is_token_subsidized(&token);
-        let wei_price_usd = self.wei_price_usd().await?;
-        let token_usd_risk = self.token_usd_risk(&token).await?;
+        Ok(freeze.freeze_gas_price(gas_price_wei))
+    }
 
-        let (fee_type, gas_tx_amount, op_chunks) = self
-            .gas_tx_amount(is_token_subsidized, tx_type, recipient)
-            .await;
+    /// Returns the last quote of the token used in the fee formula, averaged over time if
+    /// configured, or the frozen one while the fees are frozen.
+    async fn last_quote(&self, token_id: TokenId) -> anyhow::Result<TokenPrice> {
+        let freeze = self.config.fee_freeze.as_ref();
+        if let Some(quote) = freeze.and_then(|freeze| freeze.token_price(token_id)) {
+            return Ok(quote);
+        }
+        let mut quote = self.api.get_last_quote(TokenLike::Id(token_id)).await?;
+        if let Some(price_twap) = &self.price_twap {
+            quote = price_twap.average(token_id, quote);
+        }
+        Ok(match freeze {
+            Some(freeze) => freeze.freeze_token_price(token_id, quote),
+            None => quote,
+        })
+    }
+
+    /// Returns the last quotes of ETH and of the token in USD, requested concurrently.
+    async fn eth_and_token_quotes(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<(TokenPrice, TokenPrice)> {
+        let eth_id = TokenId(0);
+        if token_id == eth_id {
+            let eth_quote = self.last_quote(eth_id).await?;
+            return Ok((eth_quote.clone(), eth_quote));
+        }
+
+        let (eth_quote, token_quote) =
+            futures::join!(self.last_quote(eth_id), self.last_quote(token_id));
+        Ok((eth_quote?, token_quote?))
+    }
+
+    /// Calculates the fee of a single transaction and reports every input it's calculated from.
+    async fn fee_dry_run(
+        &mut self,
+        tx_type: TxFeeTypes,
+        token: TokenLike,
+        recipient: Address,
+        sender: Option<Address>,
+    ) -> anyhow::Result<FeeDryRun> {
+        let (token, gas_price_wei) = self.token_and_gas_price(token).await?;
+        let op = self.gas_tx_amount(&token, tx_type, recipient).await;
+        self.calculate_fee(token, op, gas_price_wei, recipient, sender)
+            .await
+    }
+
+    /// Calculates the fee of the operation the transaction is resolved to. The operation
+    /// is described by its fee type, gas cost and number of chunks, see `gas_tx_amount`.
+    async fn calculate_fee(
+        &mut self,
+        token: Token,
+        op: (OutputFeeType, BigUint, BigUint),
+        gas_price_wei: GasPriceWei,
+        recipient: Address,
+        sender: Option<Address>,
+    ) -> anyhow::Result<FeeDryRun> {
+        let (fee_type, gas_tx_amount, op_chunks) = op;
+        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
+        let discount = sender.map_or_else(Ratio::zero, |sender| self.discount(sender, fee_type));
+        let mut dry_run = FeeDryRun {
+            fee: Fee::waived(fee_type, gas_price_wei.effective_price()),
+            token: token.clone(),
+            eth_quote: None,
+            token_quote: None,
+            base_fee_wei: gas_price_wei.base_fee.clone(),
+            priority_fee_wei: gas_price_wei.priority_fee.clone(),
+            op_chunks: op_chunks.clone(),
+            subsidized: self.is_subsidized(&token, fee_type),
+            zkp_cost_chunk_usd: zkp_cost_chunk.clone(),
+            discount: discount.clone(),
+            pending_block_free_share: self
+                .block_fullness
+                .as_ref()
+                .and_then(|fullness| fullness.free_share())
+                .map(|free_share| ratio_to_big_decimal(&free_share, 4)),
+        };
+        // The prices are not requested for the waived fee, since the token may be delisted.
+        if self.is_waived(&token, fee_type, recipient).await {
+            return Ok(dry_run);
+        }
 
-        let zkp_fee = (zkp_cost_chunk * op_chunks) * token_usd_risk.clone();
-        let gas_fee =
-            (wei_price_usd * gas_tx_amount.clone() * scale_gas_price.clone()) * token_usd_risk;
+        let scale_gas_price = Self::risk_gas_price_estimate(&gas_price_wei);
+        let (eth_quote, token_quote) = self.eth_and_token_quotes(token.id).await?;
+        let wei_price_usd = Self::wei_price_usd(&eth_quote);
+        let token_price_usd = token_quote.usd_price.clone();
+        let risk_factor = self.token_risk_factor(&token);
+        let token_usd_risk = Self::usd_risk(&token, &token_price_usd, &risk_factor);
 
-        Ok(Fee::new(
+        let subsidized_gas = self.subsidized_gas(&token, fee_type, &gas_tx_amount);
+        let zkp_cost_usd = zkp_cost_chunk * op_chunks;
+        let gas_cost_usd = wei_price_usd.clone() * gas_tx_amount.clone() * scale_gas_price.clone();
+        let subsidy_usd = wei_price_usd * subsidized_gas * scale_gas_price.clone();
+        let payable_share = Ratio::one() - discount;
+        let zkp_fee = zkp_cost_usd.clone() * token_usd_risk.clone() * payable_share.clone();
+        let gas_fee = gas_cost_usd.clone() * token_usd_risk.clone() * payable_share.clone();
+        let subsidy = subsidy_usd * token_usd_risk * payable_share;
+
+        let rounding = self.fee_rounding(&token);
+        let mut fee = Fee::new(
             fee_type,
             zkp_fee,
             gas_fee,
             gas_tx_amount,
-            gas_price_wei,
-        ))
+            gas_price_wei.effective_price(),
+            &rounding,
+        )
+        .with_breakdown(FeeBreakdown {
+            zkp_cost_usd,
+            gas_cost_usd,
+            token_price_usd: token_price_usd.clone(),
+            gas_price_wei: scale_gas_price,
+            risk_factor,
+        });
+        fee.subsidy = subsidy.to_integer();
+        if let Some(minimum_fee) = self.minimum_fee(&token, &token_price_usd) {
+            if fee.total_fee < minimum_fee {
+                fee.total_fee = rounding.round(&minimum_fee);
+                fee.exact_total_fee = minimum_fee;
+            }
+        }
+        if let Some(maximum_fee) = self.maximum_fee(&token, &token_price_usd, vec![fee_type]) {
+            if fee.total_fee > maximum_fee {
+                Self::report_capped_fee(&token, &fee.total_fee, &maximum_fee);
+                fee.cap(maximum_fee);
+            }
+        }
+
+        dry_run.fee = fee;
+        dry_run.eth_quote = Some(eth_quote);
+        dry_run.token_quote = Some(token_quote);
+        Ok(dry_run)
     }
 
     async fn get_batch_from_ticker_in_wei(
@@ -437,58 +1339,235 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
         token: TokenLike,
         txs: Vec<(TxFeeTypes, Address)>,
     ) -> anyhow::Result<BatchFee> {
+        let start = Instant::now();
         let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
-        let token = self.api.get_token(token).await?;
-
-        let gas_price_wei = self.api.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
-        let is_token_subsidized = self.is_token_subsidized(&token);
-        let wei_price_usd = self.wei_price_usd().await?;
-        let token_usd_risk = self.token_usd_risk(&token).await?;
+        let (token, gas_price_wei) = self.token_and_gas_price(token).await?;
 
-        let mut total_gas_tx_amount = BigUint::zero();
+        let mut gas_costs = Vec::with_capacity(txs.len());
         let mut total_op_chunks = BigUint::zero();
 
+        let mut fee_types = Vec::with_capacity(txs.len());
+        let mut subsidized_gas = BigUint::zero();
+        let txs_count = txs.len();
         for (tx_type, recipient) in txs {
-            let (_, gas_tx_amount, op_chunks) = self
-                .gas_tx_amount(is_token_subsidized, tx_type, recipient)
-                .await;
-            total_gas_tx_amount += gas_tx_amount;
+            let (fee_type, gas_tx_amount, op_chunks) =
+                self.gas_tx_amount(&token, tx_type, recipient).await;
+            // Waived transactions don't contribute to the batch fee.
+            if self.is_waived(&token, fee_type, recipient).await {
+                continue;
+            }
+            let overhead = self
+                .gas_overhead(&token, fee_type, &op_chunks)
+                .min(gas_tx_amount.clone());
+            subsidized_gas += self.subsidized_gas(&token, fee_type, &gas_tx_amount);
+            fee_types.push(fee_type);
+            gas_costs.push(TxGasCost {
+                total: gas_tx_amount,
+                overhead,
+            });
             total_op_chunks += op_chunks;
         }
+        if txs_count > 0 && fee_types.is_empty() {
+            Self::report_batch_fee_quote(&token, start.elapsed());
+            return Ok(BatchFee::waived());
+        }
+        let total_gas_tx_amount = self.config.batch_fee_model.gas_amount(&gas_costs);
+
+        let scale_gas_price = Self::risk_gas_price_estimate(&gas_price_wei);
+        let (eth_quote, token_quote) = self.eth_and_token_quotes(token.id).await?;
+        let wei_price_usd = Self::wei_price_usd(&eth_quote);
+        let token_price_usd = token_quote.usd_price;
+        let token_usd_risk =
+            Self::usd_risk(&token, &token_price_usd, &self.token_risk_factor(&token));
 
         let total_zkp_fee = (zkp_cost_chunk * total_op_chunks) * token_usd_risk.clone();
-        let total_gas_fee =
-            (wei_price_usd * total_gas_tx_amount * scale_gas_price) * token_usd_risk;
-        let total_fee = BatchFee::new(&total_zkp_fee, &total_gas_fee);
+        let total_gas_fee = (wei_price_usd.clone() * total_gas_tx_amount * scale_gas_price.clone())
+            * token_usd_risk.clone();
+        let subsidy = (wei_price_usd * subsidized_gas * scale_gas_price) * token_usd_risk;
+        let rounding = self.fee_rounding(&token);
+        let mut total_fee = BatchFee::new(&total_zkp_fee, &total_gas_fee, &rounding);
+        total_fee.subsidy = subsidy.to_integer();
+        if let Some(minimum_fee) = self.minimum_fee(&token, &token_price_usd) {
+            if total_fee.total_fee < minimum_fee {
+                total_fee.total_fee = rounding.round(&minimum_fee);
+                total_fee.exact_total_fee = minimum_fee;
+            }
+        }
+        if let Some(maximum_fee) = self.maximum_fee(&token, &token_price_usd, fee_types) {
+            if total_fee.total_fee > maximum_fee {
+                Self::report_capped_fee(&token, &total_fee.total_fee, &maximum_fee);
+                total_fee.cap(maximum_fee);
+            }
+        }
 
+        Self::report_batch_fee_quote(&token, start.elapsed());
         Ok(total_fee)
     }
 
-    async fn wei_price_usd(&mut self) -> anyhow::Result<Ratio<BigUint>> {
-        Ok(self
-            .api
-            .get_last_quote(TokenLike::Id(TokenId(0)))
-            .await?
-            .usd_price
-            / BigUint::from(10u32).pow(18u32))
+    /// Returns the minimum fee paid in the token in the smallest token units, if it's set.
+    /// The minimum fee set in USD is not applied if the token price is unknown.
+    fn minimum_fee(&self, token: &Token, token_price_usd: &Ratio<BigUint>) -> Option<BigUint> {
+        match self.config.minimum_fees.get(&token.id)? {
+            MinimumFee::Token(amount) => Some(amount.clone()),
+            MinimumFee::Usd(amount) => Self::usd_to_token_units(token, token_price_usd, amount),
+        }
     }
 
-    async fn token_usd_risk(&mut self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
-        let token_risk_factor = self
+    /// Returns the maximum fee of the transactions paid in the token in the smallest token units.
+    /// The fee of the batch is capped only if every transaction type in it has the maximum fee.
+    fn maximum_fee(
+        &self,
+        token: &Token,
+        token_price_usd: &Ratio<BigUint>,
+        fee_types: Vec<OutputFeeType>,
+    ) -> Option<BigUint> {
+        let mut max_fee_usd = Ratio::zero();
+        for fee_type in fee_types {
+            max_fee_usd += self.config.maximum_fees.get(&fee_type)?;
+        }
+        Self::usd_to_token_units(token, token_price_usd, &max_fee_usd)
+    }
+
+    fn report_capped_fee(token: &Token, fee: &BigUint, maximum_fee: &BigUint) {
+        vlog::error!(
+            "Fee {} {} exceeds the maximum fee {} {}, it was clamped. \
+             Check the token price and the gas price",
+            fee,
+            token.symbol,
+            maximum_fee,
+            token.symbol
+        );
+        metrics::counter!("ticker.capped_fees", 1);
+    }
+
+    /// Reports the latency of the fee quote broken down by the token, the fee type and whether
+    /// the fee is subsidized. The number of the samples is the number of the quotes.
+    fn report_fee_quote(
+        token: &Token,
+        fee_type: OutputFeeType,
+        subsidized: bool,
+        latency: std::time::Duration,
+    ) {
+        metrics::histogram!(
+            "ticker.tx_fee_quote",
+            latency,
+            "token" => token.symbol.clone(),
+            "fee_type" => Self::fee_type_label(fee_type),
+            "subsidized" => if subsidized { "yes" } else { "no" }
+        );
+    }
+
+    /// Reports the latency of the batch fee quote broken down by the token.
+    fn report_batch_fee_quote(token: &Token, latency: std::time::Duration) {
+        metrics::histogram!(
+            "ticker.batch_fee_quote",
+            latency,
+            "token" => token.symbol.clone()
+        );
+    }
+
+    /// Returns the fee type name used as the metrics label. `ChangePubKey` variants share
+    /// the label, so the number of the series doesn't grow with the signature types.
+    fn fee_type_label(fee_type: OutputFeeType) -> &'static str {
+        match fee_type {
+            OutputFeeType::Transfer => "Transfer",
+            OutputFeeType::TransferToNew => "TransferToNew",
+            OutputFeeType::Withdraw => "Withdraw",
+            OutputFeeType::FastWithdraw => "FastWithdraw",
+            OutputFeeType::ForcedExit => "ForcedExit",
+            OutputFeeType::ChangePubKey(_) => "ChangePubKey",
+            OutputFeeType::PermitDeposit => "PermitDeposit",
+            OutputFeeType::MintNFT => "MintNFT",
+            OutputFeeType::WithdrawNFT => "WithdrawNFT",
+            OutputFeeType::FastWithdrawNFT => "FastWithdrawNFT",
+            OutputFeeType::Swap => "Swap",
+        }
+    }
+
+    /// Converts the USD amount to the smallest token units, rounding up.
+    /// Returns `None` if the token price is unknown.
+    fn usd_to_token_units(
+        token: &Token,
+        token_price_usd: &Ratio<BigUint>,
+        amount_usd: &Ratio<BigUint>,
+    ) -> Option<BigUint> {
+        if token_price_usd.is_zero() {
+            return None;
+        }
+        let token_units = BigUint::from(10u32).pow(u32::from(token.decimals));
+        Some(
+            (amount_usd * token_units / token_price_usd)
+                .ceil()
+                .to_integer(),
+        )
+    }
+
+    /// Returns the rounding of the fee paid in the token. By default, the fee is only
+    /// rounded up to the closest packable amount.
+    fn fee_rounding(&self, token: &Token) -> FeeRoundingPolicy {
+        self.config
+            .fee_rounding
+            .get(&token.id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the fee of the given type paid in the token is waived for the account.
+    async fn is_waived(
+        &mut self,
+        token: &Token,
+        fee_type: OutputFeeType,
+        address: Address,
+    ) -> bool {
+        let now = chrono::Utc::now();
+        let unset_signing_key_only: Vec<bool> = self
             .config
+            .fee_waivers
+            .iter()
+            .filter(|waiver| waiver.applies_to(fee_type, token.id, now))
+            .map(|waiver| waiver.unset_signing_key_only)
+            .collect();
+        if unset_signing_key_only.contains(&false) {
+            return true;
+        }
+        !unset_signing_key_only.is_empty() && !self.info.is_signing_key_set(address).await
+    }
+
+    /// Returns the share of the fee the sender doesn't pay. If several discounts apply,
+    /// the largest one is used.
+    fn discount(&self, sender: Address, fee_type: OutputFeeType) -> Ratio<BigUint> {
+        self.config
+            .fee_discounts
+            .get(&sender)
+            .into_iter()
+            .flatten()
+            .filter(|discount| discount.applies_to(fee_type))
+            .map(|discount| discount.discount.clone())
+            .max()
+            .unwrap_or_else(Ratio::zero)
+    }
+
+    /// Returns the price of one wei in USD.
+    fn wei_price_usd(eth_quote: &TokenPrice) -> Ratio<BigUint> {
+        eth_quote.usd_price.clone() / BigUint::from(10u32).pow(18u32)
+    }
+
+    fn token_risk_factor(&self, token: &Token) -> Ratio<BigUint> {
+        self.config
             .tokens_risk_factors
             .get(&token.id)
+            .or_else(|| self.config.config_risk_factors.get(&token.address))
             .cloned()
-            .unwrap_or_else(|| Ratio::from_integer(1u32.into()));
+            .unwrap_or_else(|| Ratio::from_integer(1u32.into()))
+    }
 
-        let token_price_usd = self
-            .api
-            .get_last_quote(TokenLike::Id(token.id))
-            .await?
-            .usd_price
-            / BigUint::from(10u32).pow(u32::from(token.decimals));
-        Ok(token_risk_factor / token_price_usd)
+    /// Returns the amount of the smallest token units paid for one USD, multiplied by the risk factor.
+    fn usd_risk(
+        token: &Token,
+        token_price_usd: &Ratio<BigUint>,
+        risk_factor: &Ratio<BigUint>,
+    ) -> Ratio<BigUint> {
+        risk_factor / (token_price_usd / BigUint::from(10u32).pow(u32::from(token.decimals)))
     }
 
     /// Returns `true` if account does not yet exist in the zkSync network.
@@ -498,13 +1577,14 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
 
     async fn gas_tx_amount(
         &mut self,
-        is_token_subsidized: bool,
+        token: &Token,
         tx_type: TxFeeTypes,
         recipient: Address,
     ) -> (OutputFeeType, BigUint, BigUint) {
         let (fee_type, op_chunks) = match tx_type {
             TxFeeTypes::Withdraw => (OutputFeeType::Withdraw, WithdrawOp::CHUNKS),
             TxFeeTypes::FastWithdraw => (OutputFeeType::FastWithdraw, WithdrawOp::CHUNKS),
+            TxFeeTypes::ForcedExit => (OutputFeeType::ForcedExit, ForcedExitOp::CHUNKS),
             TxFeeTypes::Transfer => {
                 if self.is_account_new(recipient).await {
                     (OutputFeeType::TransferToNew, TransferToNewOp::CHUNKS)
@@ -515,27 +1595,101 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
             TxFeeTypes::ChangePubKey(arg) => {
                 (OutputFeeType::ChangePubKey(arg), ChangePubKeyOp::CHUNKS)
             }
+            // Deposit is a priority operation, so the user only pays for the L1 transaction
+            // sent by the relayer and no chunks are charged.
+            TxFeeTypes::PermitDeposit => (OutputFeeType::PermitDeposit, 0),
+            TxFeeTypes::MintNFT => (OutputFeeType::MintNFT, constants::MINT_NFT_CHUNKS),
+            TxFeeTypes::WithdrawNFT => (OutputFeeType::WithdrawNFT, constants::WITHDRAW_NFT_CHUNKS),
+            TxFeeTypes::FastWithdrawNFT => (
+                OutputFeeType::FastWithdrawNFT,
+                constants::WITHDRAW_NFT_CHUNKS,
+            ),
+            TxFeeTypes::Swap => (OutputFeeType::Swap, constants::SWAP_CHUNKS),
         };
         // Convert chunks amount to `BigUint`.
         let op_chunks = BigUint::from(op_chunks);
 
-        let gas_tx_amount = {
-            if is_token_subsidized {
-                self.config
-                    .gas_cost_tx
-                    .subsidize_cost
-                    .get(&fee_type)
-                    .cloned()
-                    .unwrap()
-            } else {
-                self.config
-                    .gas_cost_tx
-                    .standard_cost
-                    .get(&fee_type)
-                    .cloned()
-                    .unwrap()
-            }
+        if let Some(gas_tx_amount) = self.token_gas_cost(token.id, fee_type) {
+            return (fee_type, gas_tx_amount.clone(), op_chunks);
+        }
+        let gas_cost = if self.is_subsidized(token, fee_type) {
+            &self.config.gas_cost_tx.subsidize_cost
+        } else {
+            &self.config.gas_cost_tx.standard_cost
         };
+        let gas_tx_amount = gas_cost.get(&fee_type).cloned().unwrap();
+        let gas_tx_amount = self.scale_fast_processing_cost(fee_type, gas_tx_amount, gas_cost);
         (fee_type, gas_tx_amount, op_chunks)
     }
+
+    /// Returns the gas cost of the operation set for the token, if any.
+    fn token_gas_cost(&self, token: TokenId, fee_type: OutputFeeType) -> Option<&BigUint> {
+        self.config
+            .token_gas_costs
+            .get(&token)
+            .and_then(|gas_costs| gas_costs.get(&fee_type))
+    }
+
+    /// Returns the amount of gas covered by the subsidy, i.e. the difference between the
+    /// standard gas cost of the operation and the charged one.
+    fn subsidized_gas(
+        &self,
+        token: &Token,
+        fee_type: OutputFeeType,
+        gas_tx_amount: &BigUint,
+    ) -> BigUint {
+        if !self.is_subsidized(token, fee_type) || self.token_gas_cost(token.id, fee_type).is_some()
+        {
+            return BigUint::zero();
+        }
+
+        let standard_cost = &self.config.gas_cost_tx.standard_cost;
+        let standard_gas = standard_cost.get(&fee_type).cloned().unwrap();
+        let standard_gas = self.scale_fast_processing_cost(fee_type, standard_gas, standard_cost);
+        if standard_gas > *gas_tx_amount {
+            standard_gas - gas_tx_amount
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    /// Returns the part of the gas cost covering the fixed overhead of the block commitment,
+    /// execution and proof. Subsidized costs have no such part.
+    fn gas_overhead(&self, token: &Token, fee_type: OutputFeeType, op_chunks: &BigUint) -> BigUint {
+        if self.is_subsidized(token, fee_type) {
+            return BigUint::zero();
+        }
+        BigUint::from(constants::AMORTIZED_COST_PER_CHUNK) * op_chunks
+    }
+
+    /// Scales the fast processing surcharge by the share of the free chunks in the pending block,
+    /// since the fast operation seals the block. Fast operation added to the almost full block
+    /// costs almost the same as the regular one.
+    fn scale_fast_processing_cost(
+        &self,
+        fee_type: OutputFeeType,
+        gas_tx_amount: BigUint,
+        gas_cost: &HashMap<OutputFeeType, BigUint>,
+    ) -> BigUint {
+        let regular_fee_type = match fee_type {
+            OutputFeeType::FastWithdraw => OutputFeeType::Withdraw,
+            OutputFeeType::FastWithdrawNFT => OutputFeeType::WithdrawNFT,
+            _ => return gas_tx_amount,
+        };
+        let free_share = match self
+            .block_fullness
+            .as_ref()
+            .and_then(|fullness| fullness.free_share())
+        {
+            Some(free_share) => free_share,
+            None => return gas_tx_amount,
+        };
+
+        let regular_cost = gas_cost.get(&regular_fee_type).cloned().unwrap();
+        if gas_tx_amount <= regular_cost {
+            return gas_tx_amount;
+        }
+        let surcharge = Ratio::from_integer(&gas_tx_amount - &regular_cost) * free_share;
+        regular_cost + surcharge.ceil().to_integer()
+    }
 }
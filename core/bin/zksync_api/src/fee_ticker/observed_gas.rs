@@ -0,0 +1,198 @@
+//! Gas costs of the operations observed on L1.
+//!
+//! The standard gas costs the fees are calculated from are estimated once and drift away from
+//! the actual ones, e.g. after the contract upgrade. `eth_sender` records the gas actually used
+//! by the operations of every confirmed block, and the ticker may periodically average it over
+//! the configured window. The observed cost of the operation is the sum of its average costs in
+//! the commit, proof and execute transactions, increased by the safety margin. It replaces the
+//! standard cost once enough operations of the type are observed in every transaction.
+//! Subsidized costs and the costs overridden for the tokens are not affected.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use zksync_storage::{ethereum::records::OpsGasUsage, ConnectionPool};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg},
+    OutputFeeType,
+};
+// Local deps
+use super::settings::TickerConfigHandle;
+
+/// Sleep time between the updates of the observed gas costs.
+const OBSERVED_GAS_UPDATE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// L1 transactions every operation goes through.
+const L1_ACTIONS: [AggregatedActionType; 3] = [
+    AggregatedActionType::CommitBlocks,
+    AggregatedActionType::PublishProofBlocksOnchain,
+    AggregatedActionType::ExecuteBlocks,
+];
+
+/// Returns the fee type the observed gas cost of the operation type is used for.
+/// Fast withdrawals are charged the observed cost of the regular ones scaled as usual.
+fn fee_type(op_type: &str) -> Option<OutputFeeType> {
+    let change_pubkey = |auth_type| {
+        OutputFeeType::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(auth_type))
+    };
+    let fee_type = match op_type {
+        "Transfer" => OutputFeeType::Transfer,
+        "TransferToNew" => OutputFeeType::TransferToNew,
+        "Withdraw" => OutputFeeType::Withdraw,
+        "ForcedExit" => OutputFeeType::ForcedExit,
+        "ChangePubKeyECDSA" => change_pubkey(ChangePubKeyFeeType::ECDSA),
+        "ChangePubKeyCREATE2" => change_pubkey(ChangePubKeyFeeType::CREATE2),
+        "ChangePubKeyOnchain" => change_pubkey(ChangePubKeyFeeType::Onchain),
+        // Priority operations are paid on L1.
+        _ => return None,
+    };
+    Some(fee_type)
+}
+
+/// Calculates the observed gas costs of the operation types with at least `min_ops` operations
+/// observed in every L1 transaction. The costs are multiplied by `multiplier` and rounded up.
+pub fn observed_gas_costs(
+    usage: &[OpsGasUsage],
+    min_ops: u64,
+    multiplier: &Ratio<BigUint>,
+) -> HashMap<OutputFeeType, BigUint> {
+    let mut usage_by_op: HashMap<&str, HashMap<&str, &OpsGasUsage>> = HashMap::new();
+    for ops_usage in usage {
+        usage_by_op
+            .entry(ops_usage.op_type.as_str())
+            .or_default()
+            .insert(ops_usage.action_type.as_str(), ops_usage);
+    }
+
+    let mut gas_costs = HashMap::new();
+    for (op_type, usage_by_action) in usage_by_op {
+        let fee_type = match fee_type(op_type) {
+            Some(fee_type) => fee_type,
+            None => continue,
+        };
+
+        let mut gas_cost = Ratio::from_integer(BigUint::from(0u32));
+        let mut observed = true;
+        for action in L1_ACTIONS.iter() {
+            match usage_by_action.get(action.to_string().as_str()) {
+                Some(ops_usage)
+                    if ops_usage.ops_count > 0 && ops_usage.ops_count as u64 >= min_ops =>
+                {
+                    gas_cost += Ratio::new(
+                        BigUint::from(ops_usage.gas_used.max(0) as u64),
+                        BigUint::from(ops_usage.ops_count as u64),
+                    );
+                }
+                _ => {
+                    observed = false;
+                    break;
+                }
+            }
+        }
+        if observed {
+            let gas_cost = (gas_cost * multiplier).ceil().to_integer();
+            gas_costs.insert(fee_type, gas_cost);
+        }
+    }
+    gas_costs
+}
+
+async fn load_observed_gas_costs(
+    db_pool: &ConnectionPool,
+    window: chrono::Duration,
+    min_ops: u64,
+    multiplier: &Ratio<BigUint>,
+) -> anyhow::Result<HashMap<OutputFeeType, BigUint>> {
+    let mut storage = db_pool.access_storage().await?;
+    let usage = storage
+        .ethereum_schema()
+        .load_ops_gas_usage(Utc::now() - window)
+        .await?;
+    Ok(observed_gas_costs(&usage, min_ops, multiplier))
+}
+
+/// Periodically averages the gas used by the operations over the window and replaces
+/// the standard gas costs of the ticker with the observed ones.
+pub async fn run_observed_gas_updater(
+    db_pool: ConnectionPool,
+    ticker_config: TickerConfigHandle,
+    window: chrono::Duration,
+    min_ops: u64,
+    multiplier: Ratio<BigUint>,
+) {
+    let mut timer = tokio::time::interval(OBSERVED_GAS_UPDATE_INTERVAL);
+    loop {
+        timer.tick().await;
+
+        match load_observed_gas_costs(&db_pool, window, min_ops, &multiplier).await {
+            Ok(gas_costs) => {
+                vlog::debug!("Observed gas costs of the operations: {:?}", gas_costs);
+                ticker_config.set_observed_gas_costs(gas_costs);
+            }
+            Err(e) => vlog::error!("Failed to update the observed gas costs: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(
+        action: AggregatedActionType,
+        op_type: &str,
+        ops_count: i64,
+        gas_used: i64,
+    ) -> OpsGasUsage {
+        OpsGasUsage {
+            action_type: action.to_string(),
+            op_type: op_type.to_string(),
+            ops_count,
+            gas_used,
+        }
+    }
+
+    #[test]
+    fn observed_costs() {
+        let commit = AggregatedActionType::CommitBlocks;
+        let prove = AggregatedActionType::PublishProofBlocksOnchain;
+        let execute = AggregatedActionType::ExecuteBlocks;
+        let usage = vec![
+            usage(commit, "Transfer", 100, 50_000),
+            usage(prove, "Transfer", 100, 30_000),
+            usage(execute, "Transfer", 100, 20_001),
+            // Withdrawals are not executed yet.
+            usage(commit, "Withdraw", 100, 500_000),
+            usage(prove, "Withdraw", 100, 30_000),
+            // Not enough forced exits are observed.
+            usage(commit, "ForcedExit", 100, 50_000),
+            usage(prove, "ForcedExit", 100, 30_000),
+            usage(execute, "ForcedExit", 5, 1_000),
+            // Deposits are not charged.
+            usage(commit, "Deposit", 100, 50_000),
+            usage(prove, "Deposit", 100, 30_000),
+            usage(execute, "Deposit", 100, 20_000),
+        ];
+        let multiplier = Ratio::new(BigUint::from(6u32), BigUint::from(5u32));
+
+        let gas_costs = observed_gas_costs(&usage, 10, &multiplier);
+        // (500 + 300 + 200.01) * 1.2 = 1200.012, rounded up.
+        let expected: HashMap<_, _> = vec![(OutputFeeType::Transfer, BigUint::from(1201u32))]
+            .into_iter()
+            .collect();
+        assert_eq!(gas_costs, expected);
+
+        let gas_costs = observed_gas_costs(&usage, 1, &multiplier);
+        assert_eq!(gas_costs.len(), 2);
+        // (500 + 300 + 200) * 1.2
+        assert_eq!(
+            gas_costs[&OutputFeeType::ForcedExit],
+            BigUint::from(1200u32)
+        );
+    }
+}
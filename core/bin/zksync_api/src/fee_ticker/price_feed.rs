@@ -0,0 +1,309 @@
+//! Feed of the token price updates pushed to the API subscribers.
+//!
+//! Wallets display the fees in the tokens chosen by their users, and used to poll the token
+//! prices to keep them up to date. Instead, the API may watch the tokens its clients are
+//! subscribed to: the feed periodically requests the quotes of the watched tokens from the ticker,
+//! and once the quote of the token changes, it broadcasts the new price together with the
+//! indicative fees of the common transactions recalculated by the ticker.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External deps
+use anyhow::format_err;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use futures::{
+    channel::{mpsc::Sender, oneshot},
+    SinkExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+// Workspace deps
+use zksync_types::{
+    tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg, PriceConfidence},
+    Address, Fee, TokenLike, TxFeeTypes,
+};
+// Local deps
+use super::{PriceQuote, TickerRequest, TokenPriceRequestType};
+
+/// Number of the updates a slow subscriber may lag behind before it starts missing them.
+const PRICE_FEED_CAPACITY: usize = 1024;
+
+/// Sleep time between the checks of the watched token quotes.
+const PRICE_FEED_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Transactions the indicative fees are published for.
+const INDICATIVE_FEE_TYPES: [TxFeeTypes; 4] = [
+    TxFeeTypes::Transfer,
+    TxFeeTypes::Withdraw,
+    TxFeeTypes::FastWithdraw,
+    TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
+        ChangePubKeyFeeType::ECDSA,
+    )),
+];
+
+/// New quote of the token and the fees calculated with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceUpdate {
+    /// Token as it's watched by the subscribers.
+    pub token: TokenLike,
+    /// Price of one token in USD.
+    pub price: BigDecimal,
+    pub last_updated: DateTime<Utc>,
+    pub confidence: PriceConfidence,
+    /// Fees of the common transactions paid in the token, as they are quoted to an unknown
+    /// sender. The fees the ticker failed to calculate are omitted.
+    pub fees: Vec<Fee>,
+}
+
+/// Broadcast channel of the token price updates together with the set of the watched tokens.
+#[derive(Debug, Clone)]
+pub struct PriceFeed {
+    updates: broadcast::Sender<TokenPriceUpdate>,
+    /// Watched tokens with the number of their watchers.
+    watched: Arc<Mutex<HashMap<TokenLike, usize>>>,
+}
+
+impl Default for PriceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PriceFeed {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(PRICE_FEED_CAPACITY);
+        Self {
+            updates,
+            watched: Arc::default(),
+        }
+    }
+
+    /// Returns the receiver of the updates of all the watched tokens.
+    pub fn subscribe(&self) -> broadcast::Receiver<TokenPriceUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Starts publishing the updates of the token, if it's not watched yet.
+    pub fn watch(&self, token: TokenLike) {
+        *self.watched.lock().unwrap().entry(token).or_default() += 1;
+    }
+
+    /// Stops publishing the updates of the token once all its watchers are gone.
+    pub fn unwatch(&self, token: &TokenLike) {
+        let mut watched = self.watched.lock().unwrap();
+        if let Some(watchers) = watched.get_mut(token) {
+            *watchers -= 1;
+            if *watchers == 0 {
+                watched.remove(token);
+            }
+        }
+    }
+
+    fn watched_tokens(&self) -> Vec<TokenLike> {
+        self.watched.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn publish(&self, update: TokenPriceUpdate) {
+        // Sending fails only if there are no receivers, which is fine.
+        self.updates.send(update).unwrap_or_default();
+    }
+}
+
+/// Sends the request to the ticker and waits for the response.
+async fn ticker_request<T>(
+    mut ticker_requests: Sender<TickerRequest>,
+    request: impl FnOnce(oneshot::Sender<anyhow::Result<T>>) -> TickerRequest,
+) -> anyhow::Result<T> {
+    let (response, receiver) = oneshot::channel();
+    ticker_requests.send(request(response)).await?;
+    receiver
+        .await
+        .map_err(|_| format_err!("Ticker dropped the request"))?
+}
+
+/// Requests the quotes of the watched tokens and publishes the changed ones.
+struct PricePoller {
+    feed: PriceFeed,
+    ticker_requests: Sender<TickerRequest>,
+    /// Last published quotes of the watched tokens.
+    published: HashMap<TokenLike, PriceQuote>,
+}
+
+impl PricePoller {
+    fn new(feed: PriceFeed, ticker_requests: Sender<TickerRequest>) -> Self {
+        Self {
+            feed,
+            ticker_requests,
+            published: HashMap::new(),
+        }
+    }
+
+    async fn poll(&mut self) {
+        let watched = self.feed.watched_tokens();
+        self.published.retain(|token, _| watched.contains(token));
+
+        for token in watched {
+            let quote = match self.token_price(token.clone()).await {
+                Ok(quote) => quote,
+                Err(e) => {
+                    vlog::debug!("Failed to get the price of {:?} for the feed: {}", token, e);
+                    continue;
+                }
+            };
+            let changed = self.published.get(&token).map_or(true, |published| {
+                published.price != quote.price || published.last_updated != quote.last_updated
+            });
+            if !changed {
+                continue;
+            }
+
+            let fees = self.indicative_fees(&token).await;
+            self.feed.publish(TokenPriceUpdate {
+                token: token.clone(),
+                price: quote.price.clone(),
+                last_updated: quote.last_updated,
+                confidence: quote.confidence,
+                fees,
+            });
+            metrics::counter!("ticker.price_feed.updates", 1);
+            self.published.insert(token, quote);
+        }
+    }
+
+    async fn token_price(&self, token: TokenLike) -> anyhow::Result<PriceQuote> {
+        ticker_request(self.ticker_requests.clone(), |response| {
+            TickerRequest::GetTokenPrice {
+                token,
+                response,
+                req_type: TokenPriceRequestType::USDForOneToken,
+                span: tracing::Span::current(),
+            }
+        })
+        .await
+    }
+
+    async fn indicative_fees(&self, token: &TokenLike) -> Vec<Fee> {
+        let mut fees = Vec::with_capacity(INDICATIVE_FEE_TYPES.len());
+        for &tx_type in INDICATIVE_FEE_TYPES.iter() {
+            let fee = ticker_request(self.ticker_requests.clone(), |response| {
+                TickerRequest::GetTxFee {
+                    tx_type,
+                    address: Address::zero(),
+                    sender: None,
+                    token: token.clone(),
+                    response,
+                    span: tracing::Span::current(),
+                }
+            })
+            .await;
+            match fee {
+                Ok(fee) => fees.push(fee),
+                Err(e) => vlog::debug!(
+                    "Failed to get the {:?} fee in {:?} for the feed: {}",
+                    tx_type,
+                    token,
+                    e
+                ),
+            }
+        }
+        fees
+    }
+}
+
+/// Periodically checks the quotes of the watched tokens and publishes the changed ones
+/// to the feed.
+pub async fn run_price_feed(feed: PriceFeed, ticker_requests: Sender<TickerRequest>) {
+    let mut poller = PricePoller::new(feed, ticker_requests);
+    let mut timer = tokio::time::interval(PRICE_FEED_POLL_INTERVAL);
+    loop {
+        timer.tick().await;
+        poller.poll().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::mpsc, StreamExt};
+    use std::str::FromStr;
+    use zksync_types::{OutputFeeType, TokenId};
+
+    /// Answers the price and fee requests like the ticker does, with the price set by the test.
+    fn spawn_ticker(price: Arc<Mutex<BigDecimal>>) -> Sender<TickerRequest> {
+        let (sender, mut requests) = mpsc::channel(16);
+        tokio::spawn(async move {
+            while let Some(request) = requests.next().await {
+                match request {
+                    TickerRequest::GetTokenPrice { response, .. } => {
+                        let quote = PriceQuote {
+                            price: price.lock().unwrap().clone(),
+                            source: "test".to_string(),
+                            last_updated: DateTime::from(std::time::SystemTime::UNIX_EPOCH),
+                            confidence: PriceConfidence::Fresh,
+                        };
+                        response.send(Ok(quote)).unwrap_or_default();
+                    }
+                    TickerRequest::GetTxFee {
+                        tx_type: TxFeeTypes::FastWithdraw,
+                        response,
+                        ..
+                    } => response
+                        .send(Err(format_err!("Fast withdrawals are disabled")))
+                        .unwrap_or_default(),
+                    TickerRequest::GetTxFee { response, .. } => response
+                        .send(Ok(Fee::waived(OutputFeeType::Transfer, 1u32.into())))
+                        .unwrap_or_default(),
+                    _ => unreachable!("Unexpected request"),
+                }
+            }
+        });
+        sender
+    }
+
+    #[tokio::test]
+    async fn changed_quotes_published() {
+        let price = Arc::new(Mutex::new(BigDecimal::from_str("1.5").unwrap()));
+        let feed = PriceFeed::new();
+        let mut updates = feed.subscribe();
+        let mut poller = PricePoller::new(feed.clone(), spawn_ticker(price.clone()));
+        let token = TokenLike::Id(TokenId(1));
+
+        // Nothing is published for the tokens nobody watches.
+        poller.poll().await;
+        assert!(updates.try_recv().is_err());
+
+        // The first quote of the watched token is published with the calculated fees.
+        feed.watch(token.clone());
+        feed.watch(token.clone());
+        poller.poll().await;
+        let update = updates.try_recv().unwrap();
+        assert_eq!(update.token, token);
+        assert_eq!(update.price, BigDecimal::from_str("1.5").unwrap());
+        assert_eq!(update.fees.len(), INDICATIVE_FEE_TYPES.len() - 1);
+
+        // The same quote is not published again.
+        poller.poll().await;
+        assert!(updates.try_recv().is_err());
+
+        *price.lock().unwrap() = BigDecimal::from_str("1.6").unwrap();
+        poller.poll().await;
+        let update = updates.try_recv().unwrap();
+        assert_eq!(update.price, BigDecimal::from_str("1.6").unwrap());
+
+        // The token is watched until all its watchers are gone.
+        *price.lock().unwrap() = BigDecimal::from_str("1.7").unwrap();
+        feed.unwatch(&token);
+        poller.poll().await;
+        assert!(updates.try_recv().is_ok());
+
+        *price.lock().unwrap() = BigDecimal::from_str("1.8").unwrap();
+        feed.unwatch(&token);
+        poller.poll().await;
+        assert!(updates.try_recv().is_err());
+        assert!(poller.published.is_empty());
+    }
+}
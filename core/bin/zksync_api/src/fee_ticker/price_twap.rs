@@ -0,0 +1,131 @@
+//! Time-weighted average prices of the tokens.
+//!
+//! Fees calculated from the spot token price jump around the sharp price moves, so the fee
+//! quoted to the user may be rejected on submission, and the fee may be gamed by timing the
+//! transaction. Instead, the fee formula may use the price averaged over the last minutes.
+//! Every quote received by the tickers is added to the shared window of the token, and the price
+//! is weighted by the time it was actual for. Token price requests still return the spot price.
+
+// Built-in deps
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+// External deps
+use chrono::{DateTime, Duration, Utc};
+use num::{rational::Ratio, BigUint, Zero};
+// Workspace deps
+use zksync_types::{TokenId, TokenPrice};
+
+/// Quotes of the tokens within the averaging window, shared between the tickers.
+#[derive(Debug, Clone)]
+pub struct PriceTwap {
+    window: Duration,
+    quotes: Arc<Mutex<HashMap<TokenId, VecDeque<TokenPrice>>>>,
+}
+
+impl PriceTwap {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            quotes: Default::default(),
+        }
+    }
+
+    /// Adds the quote to the window of the token and returns the price averaged over the window.
+    /// The average is reported as updated at the same time as the quote.
+    pub fn average(&self, token: TokenId, quote: TokenPrice) -> TokenPrice {
+        self.average_at(token, quote, Utc::now())
+    }
+
+    fn average_at(&self, token: TokenId, quote: TokenPrice, now: DateTime<Utc>) -> TokenPrice {
+        let mut quotes = self.quotes.lock().expect("price twap lock poisoned");
+        let quotes = quotes.entry(token).or_default();
+        // The same quote is returned until the cached price expires.
+        let is_new_quote = quotes
+            .back()
+            .map_or(true, |last| last.last_updated < quote.last_updated);
+        if is_new_quote {
+            quotes.push_back(quote.clone());
+        }
+
+        // The latest quote received before the window sets the price at the window start.
+        let window_start = now - self.window;
+        while quotes.len() > 1 && quotes[1].last_updated <= window_start {
+            quotes.pop_front();
+        }
+
+        let mut weighted_sum = Ratio::zero();
+        let mut total_weight = BigUint::zero();
+        for (i, price) in quotes.iter().enumerate() {
+            let from = price.last_updated.max(window_start);
+            let to = quotes.get(i + 1).map_or(now, |next| next.last_updated);
+            let weight = (to - from).num_milliseconds();
+            if weight <= 0 {
+                continue;
+            }
+            let weight = BigUint::from(weight as u64);
+            weighted_sum += &price.usd_price * &weight;
+            total_weight += weight;
+        }
+        if total_weight.is_zero() {
+            return quote;
+        }
+
+        TokenPrice {
+            usd_price: weighted_sum / total_weight,
+            last_updated: quote.last_updated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(usd_price: u32, last_updated: DateTime<Utc>) -> TokenPrice {
+        TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(usd_price)),
+            last_updated,
+        }
+    }
+
+    #[test]
+    fn time_weighted_average() {
+        let twap = PriceTwap::new(Duration::minutes(10));
+        let start = Utc::now();
+        let token = TokenId(1);
+
+        // The only quote is returned as is.
+        let average = twap.average_at(token, quote(100, start), start);
+        assert_eq!(average.usd_price, Ratio::from_integer(100u32.into()));
+        assert_eq!(average.last_updated, start);
+
+        // The new price is weighted by the time it's actual for.
+        let average = twap.average_at(
+            token,
+            quote(200, start + Duration::minutes(6)),
+            start + Duration::minutes(8),
+        );
+        assert_eq!(average.usd_price, Ratio::from_integer(125u32.into()));
+        assert_eq!(average.last_updated, start + Duration::minutes(6));
+
+        // The repeated quote is not added twice.
+        let average = twap.average_at(
+            token,
+            quote(200, start + Duration::minutes(6)),
+            start + Duration::minutes(10),
+        );
+        assert_eq!(average.usd_price, Ratio::from_integer(140u32.into()));
+
+        // Only the part of the quote within the window is taken into account.
+        let average = twap.average_at(
+            token,
+            quote(300, start + Duration::minutes(12)),
+            start + Duration::minutes(14),
+        );
+        assert_eq!(average.usd_price, Ratio::from_integer(200u32.into()));
+
+        // Windows of the tokens are independent.
+        let average = twap.average_at(TokenId(2), quote(5, start), start);
+        assert_eq!(average.usd_price, Ratio::from_integer(5u32.into()));
+    }
+}
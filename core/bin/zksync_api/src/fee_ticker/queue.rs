@@ -0,0 +1,438 @@
+//! Queue of the requests to the fee ticker.
+//!
+//! Requests come through two lanes: the priority lane is used by the API internals
+//! (e.g. the fee checks of the submitted transactions), while the public lane serves
+//! the fee quotes requested by the users. Priority requests are always handled first.
+//! The number of the pending public requests is bounded: once the ticker can't keep up,
+//! the oldest public requests are rejected with the `TickerOverloaded` error, so a flood
+//! of the fee quotes doesn't increase the latency for everyone.
+//!
+//! The queue also watches the ticker it forwards the requests to: if the ticker stops
+//! (e.g. because of a panic) or doesn't take the requests for too long, the queue fails
+//! over to the standby ticker, if there is one.
+//!
+//! Once the shutdown is requested, the queue stops accepting the requests and rejects the ones
+//! which are not forwarded yet with the `TickerShuttingDown` error, so the callers don't wait
+//! for their timeouts. The requests already taken by the tickers are handled as usual.
+
+// Built-in deps
+use std::collections::VecDeque;
+use std::time::Duration;
+// External deps
+use anyhow::format_err;
+use futures::{
+    channel::{
+        mpsc::{self, Receiver, Sender},
+        oneshot,
+    },
+    future::{self, BoxFuture, Fuse},
+    stream::FusedStream,
+    FutureExt, StreamExt,
+};
+use thiserror::Error;
+// Local deps
+use super::{health::TickerHealthHandle, TickerRequest};
+
+/// Capacity of the channel between the queue and the ticker. It's kept small, so the
+/// backlog is held by the queue, where the public requests can be shed.
+const TICKER_CHANNEL_SIZE: usize = 16;
+
+/// Creates the channel of the requests forwarded by the queue to the ticker.
+pub(super) fn ticker_channel() -> (Sender<TickerRequest>, Receiver<TickerRequest>) {
+    mpsc::channel(TICKER_CHANNEL_SIZE)
+}
+
+/// Error returned for the public requests shed because of the ticker overload.
+#[derive(Debug, Error)]
+#[error("Fee ticker is overloaded, try again later")]
+pub struct TickerOverloaded;
+
+/// Error returned for the requests left pending when the fee ticker shuts down.
+#[derive(Debug, Error)]
+#[error("Fee ticker is shutting down")]
+pub struct TickerShuttingDown;
+
+pub(super) struct TickerRequestQueue {
+    priority_requests: Receiver<TickerRequest>,
+    public_requests: Receiver<TickerRequest>,
+    /// Channels of the primary ticker and the standby ones, in the order of the failover.
+    tickers: Vec<Sender<TickerRequest>>,
+    /// Index of the ticker the requests are currently forwarded to.
+    active_ticker: usize,
+    /// The ticker is considered hung if it doesn't take the requests for this long.
+    hang_timeout: Duration,
+    pending_priority: VecDeque<TickerRequest>,
+    pending_public: VecDeque<TickerRequest>,
+    /// Maximum number of the public requests waiting to be handled.
+    max_pending_public: usize,
+    /// The queue depth is reported here.
+    health: TickerHealthHandle,
+    /// Resolves once the queue should shut down.
+    shutdown: Fuse<BoxFuture<'static, ()>>,
+    is_shutting_down: bool,
+}
+
+impl TickerRequestQueue {
+    pub fn new(
+        priority_requests: Receiver<TickerRequest>,
+        public_requests: Receiver<TickerRequest>,
+        tickers: Vec<Sender<TickerRequest>>,
+        max_pending_public: usize,
+        hang_timeout: Duration,
+    ) -> Self {
+        Self {
+            priority_requests,
+            public_requests,
+            tickers,
+            active_ticker: 0,
+            hang_timeout,
+            pending_priority: VecDeque::new(),
+            pending_public: VecDeque::with_capacity(max_pending_public),
+            max_pending_public,
+            health: TickerHealthHandle::default(),
+            shutdown: future::pending().boxed().fuse(),
+            is_shutting_down: false,
+        }
+    }
+
+    /// Sets the health the depth of the queue is reported to.
+    pub fn with_health(self, health: TickerHealthHandle) -> Self {
+        Self { health, ..self }
+    }
+
+    /// Sets the signal the queue shuts down on. The queue also shuts down if the sender
+    /// of the signal is dropped.
+    pub fn with_shutdown(self, shutdown: oneshot::Receiver<()>) -> Self {
+        Self {
+            shutdown: shutdown.map(drop).boxed().fuse(),
+            ..self
+        }
+    }
+
+    fn push_public(&mut self, request: TickerRequest) {
+        self.pending_public.push_back(request);
+        if self.pending_public.len() > self.max_pending_public {
+            if let Some(shed) = self.pending_public.pop_front() {
+                metrics::counter!("ticker.queue.shed_requests", 1);
+                shed.reject(TickerOverloaded.into());
+            }
+        }
+    }
+
+    /// Moves all the requests received so far to the queue without waiting for the new ones.
+    fn receive_ready(&mut self) {
+        while let Ok(Some(request)) = self.priority_requests.try_next() {
+            self.pending_priority.push_back(request);
+        }
+        while let Ok(Some(request)) = self.public_requests.try_next() {
+            self.push_public(request);
+        }
+    }
+
+    /// Checks whether the shutdown is requested, without waiting for it.
+    fn is_shutdown_requested(&mut self) -> bool {
+        if !self.is_shutting_down && (&mut self.shutdown).now_or_never().is_some() {
+            self.is_shutting_down = true;
+        }
+        self.is_shutting_down
+    }
+
+    fn next_request(&mut self) -> Option<TickerRequest> {
+        self.pending_priority
+            .pop_front()
+            .or_else(|| self.pending_public.pop_front())
+    }
+
+    /// Sends the request to the active ticker, failing over to the standby one if the active
+    /// ticker has stopped or hung. Returns `false` if there are no tickers left.
+    /// The request is rejected if the shutdown is requested while waiting for the ticker.
+    async fn forward(&mut self, mut request: TickerRequest) -> bool {
+        let hang_timeout = self.hang_timeout;
+        while let Some(ticker) = self.tickers.get_mut(self.active_ticker) {
+            let ready =
+                tokio::time::timeout(hang_timeout, future::poll_fn(|cx| ticker.poll_ready(cx)))
+                    .fuse();
+            futures::pin_mut!(ready);
+            let ready = futures::select_biased! {
+                () = self.shutdown => {
+                    self.is_shutting_down = true;
+                    request.reject(TickerShuttingDown.into());
+                    return true;
+                },
+                ready = ready => ready,
+            };
+            let failure = match ready {
+                Ok(Ok(())) => match ticker.try_send(request) {
+                    Ok(()) => return true,
+                    Err(err) => {
+                        request = err.into_inner();
+                        "stopped"
+                    }
+                },
+                Ok(Err(_)) => "stopped",
+                Err(_) => "hung",
+            };
+
+            vlog::error!(
+                "Fee ticker #{} has {}, failing over to the standby ticker",
+                self.active_ticker,
+                failure
+            );
+            metrics::counter!("ticker.queue.failover", 1);
+            self.active_ticker += 1;
+        }
+
+        request.reject(format_err!("Fee ticker is not available"));
+        false
+    }
+
+    /// Stops accepting the requests and rejects all the requests which are not forwarded yet.
+    fn shut_down(&mut self) {
+        self.priority_requests.close();
+        self.public_requests.close();
+        while let Ok(Some(request)) = self.priority_requests.try_next() {
+            self.pending_priority.push_back(request);
+        }
+        while let Ok(Some(request)) = self.public_requests.try_next() {
+            self.pending_public.push_back(request);
+        }
+
+        let rejected = self.pending_priority.len() + self.pending_public.len();
+        while let Some(request) = self.next_request() {
+            request.reject(TickerShuttingDown.into());
+        }
+        self.health.set_pending_requests(0, 0);
+        vlog::info!(
+            "Fee ticker request queue is shut down, {} pending requests rejected",
+            rejected
+        );
+    }
+
+    pub async fn run(mut self) {
+        loop {
+            if self.is_shutdown_requested() {
+                break;
+            }
+            self.receive_ready();
+            self.health
+                .set_pending_requests(self.pending_priority.len(), self.pending_public.len());
+            metrics::gauge!(
+                "ticker.queue.pending_requests",
+                (self.pending_priority.len() + self.pending_public.len()) as f64
+            );
+
+            if let Some(request) = self.next_request() {
+                if !self.forward(request).await {
+                    vlog::error!("No fee ticker is running, request queue is shutting down");
+                    return;
+                }
+                continue;
+            }
+
+            // Nothing to handle, wait for the new requests.
+            if self.priority_requests.is_terminated() && self.public_requests.is_terminated() {
+                return;
+            }
+            let request = futures::select_biased! {
+                () = self.shutdown => break,
+                request = self.priority_requests.next() => request.map(|request| (request, true)),
+                request = self.public_requests.next() => request.map(|request| (request, false)),
+                complete => return,
+            };
+            match request {
+                Some((request, true)) => self.pending_priority.push_back(request),
+                Some((request, false)) => self.push_public(request),
+                // One of the lanes is closed, keep serving the other one.
+                None => {}
+            }
+        }
+        self.shut_down();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::oneshot, SinkExt};
+    use zksync_types::TokenId;
+
+    const HANG_TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn token_allowed_request(
+        token_id: u32,
+    ) -> (TickerRequest, oneshot::Receiver<anyhow::Result<bool>>) {
+        let (response, receiver) = oneshot::channel();
+        let request = TickerRequest::IsTokenAllowed {
+            token: TokenId(token_id).into(),
+            response,
+            span: tracing::Span::current(),
+        };
+        (request, receiver)
+    }
+
+    fn requested_token(request: TickerRequest) -> u32 {
+        match request {
+            TickerRequest::IsTokenAllowed { token, .. } => match token {
+                zksync_types::TokenLike::Id(id) => *id,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn priority_requests_go_first() {
+        let (mut priority, priority_receiver) = mpsc::channel(10);
+        let (mut public, public_receiver) = mpsc::channel(10);
+        let (ticker, mut ticker_requests) = ticker_channel();
+        let queue = TickerRequestQueue::new(
+            priority_receiver,
+            public_receiver,
+            vec![ticker],
+            10,
+            HANG_TIMEOUT,
+        );
+
+        let mut responses = Vec::new();
+        for token_id in 0..3 {
+            let (request, response) = token_allowed_request(token_id);
+            public.send(request).await.unwrap();
+            responses.push(response);
+        }
+        let (request, response) = token_allowed_request(100);
+        priority.send(request).await.unwrap();
+        responses.push(response);
+
+        tokio::spawn(queue.run());
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            order.push(requested_token(ticker_requests.next().await.unwrap()));
+        }
+        assert_eq!(order, vec![100, 0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn oldest_public_requests_are_shed() {
+        let (_priority, priority_receiver) = mpsc::channel(10);
+        let (mut public, public_receiver) = mpsc::channel(10);
+        let (ticker, _ticker_requests) = ticker_channel();
+        let mut queue = TickerRequestQueue::new(
+            priority_receiver,
+            public_receiver,
+            vec![ticker],
+            2,
+            HANG_TIMEOUT,
+        );
+
+        let mut responses = Vec::new();
+        for token_id in 0..4 {
+            let (request, response) = token_allowed_request(token_id);
+            public.send(request).await.unwrap();
+            responses.push(response);
+        }
+        queue.receive_ready();
+
+        assert_eq!(queue.pending_public.len(), 2);
+        for response in responses.drain(..2) {
+            let error = response.await.unwrap().unwrap_err();
+            assert!(error.is::<TickerOverloaded>());
+        }
+        let pending: Vec<_> = queue
+            .pending_public
+            .drain(..)
+            .map(requested_token)
+            .collect();
+        assert_eq!(pending, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn failover_to_standby_ticker() {
+        let (_priority, priority_receiver) = mpsc::channel(10);
+        let (_public, public_receiver) = mpsc::channel(10);
+        // The primary ticker has stopped, the first standby one hangs.
+        let (stopped_ticker, _) = ticker_channel();
+        let (hung_ticker, _hung_ticker_requests) = mpsc::channel(0);
+        let (standby_ticker, mut standby_ticker_requests) = ticker_channel();
+        let mut queue = TickerRequestQueue::new(
+            priority_receiver,
+            public_receiver,
+            vec![stopped_ticker, hung_ticker, standby_ticker],
+            10,
+            HANG_TIMEOUT,
+        );
+
+        for token_id in 0..3 {
+            let (request, _) = token_allowed_request(token_id);
+            assert!(queue.forward(request).await);
+        }
+        assert_eq!(queue.active_ticker, 2);
+
+        // The hung ticker only takes the first request, the rest go to the standby ticker.
+        let forwarded = vec![
+            requested_token(standby_ticker_requests.next().await.unwrap()),
+            requested_token(standby_ticker_requests.next().await.unwrap()),
+        ];
+        assert_eq!(forwarded, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn pending_requests_are_rejected_on_shutdown() {
+        let (mut priority, priority_receiver) = mpsc::channel(10);
+        let (mut public, public_receiver) = mpsc::channel(10);
+        // The ticker doesn't take the requests, so they stay in the queue.
+        let (ticker, _ticker_requests) = mpsc::channel(0);
+        let (shutdown_sender, shutdown) = oneshot::channel();
+        let queue = TickerRequestQueue::new(
+            priority_receiver,
+            public_receiver,
+            vec![ticker],
+            10,
+            Duration::from_secs(60),
+        )
+        .with_shutdown(shutdown);
+
+        let mut responses = Vec::new();
+        for token_id in 0..3 {
+            let (request, response) = token_allowed_request(token_id);
+            public.send(request).await.unwrap();
+            responses.push(response);
+        }
+        let queue = tokio::spawn(queue.run());
+        tokio::time::delay_for(HANG_TIMEOUT).await;
+
+        let (request, response) = token_allowed_request(100);
+        priority.send(request).await.unwrap();
+        responses.push(response);
+        shutdown_sender.send(()).unwrap();
+        queue.await.unwrap();
+
+        // The first request is taken by the ticker, the rest are rejected, including the one
+        // the queue has been waiting to forward.
+        for response in responses.drain(1..) {
+            let error = response.await.unwrap().unwrap_err();
+            assert!(error.is::<TickerShuttingDown>());
+        }
+        // No new requests are accepted.
+        let (request, _) = token_allowed_request(200);
+        assert!(public.send(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn request_is_rejected_without_tickers() {
+        let (_priority, priority_receiver) = mpsc::channel(10);
+        let (_public, public_receiver) = mpsc::channel(10);
+        let (stopped_ticker, _) = ticker_channel();
+        let mut queue = TickerRequestQueue::new(
+            priority_receiver,
+            public_receiver,
+            vec![stopped_ticker],
+            10,
+            HANG_TIMEOUT,
+        );
+
+        let (request, response) = token_allowed_request(0);
+        assert!(!queue.forward(request).await);
+        assert!(response.await.unwrap().is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Signed fee quotes.
+//!
+//! Gas and token prices may change between the moment the fee is quoted and the moment
+//! the transaction is submitted, so the fee which was sufficient a second ago may become
+//! too low. To avoid this, the ticker may sign the quote: the transaction paying exactly
+//! the quoted fee is then accepted until the quote expires.
+//!
+//! The signature is a keyed keccak256 hash of the quoted values, so it can only be produced
+//! and checked by the servers sharing the signing key. No state is stored for the quotes.
+//!
+//! Fee quoted for the known sender may include the sender's discount, so such a quote is bound
+//! to the sender and can't be used by other accounts.
+
+// Built-in deps
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+// External deps
+use chrono::Utc;
+use num::BigUint;
+// Workspace deps
+use zksync_config::configs::ticker::TickerConfig;
+use zksync_types::{Address, FeeQuote, TokenId, TxFeeTypes, H256};
+
+/// Signs the fee quotes and verifies them upon the transaction submission.
+#[derive(Debug, Clone)]
+pub struct FeeQuoteSigner {
+    key: H256,
+    validity: chrono::Duration,
+    next_id: Arc<AtomicU64>,
+}
+
+impl FeeQuoteSigner {
+    pub fn new(key: H256, validity: chrono::Duration) -> Self {
+        // Identifiers start from the current time, so they aren't repeated after the restart.
+        let first_id = Utc::now().timestamp_nanos() as u64;
+        Self {
+            key,
+            validity,
+            next_id: Arc::new(AtomicU64::new(first_id)),
+        }
+    }
+
+    /// Returns the signer if the signed quotes are enabled in the config.
+    pub fn from_config(config: &TickerConfig) -> Option<Self> {
+        if config.signed_quotes_enabled {
+            Some(Self::new(config.quote_signing_key, config.quote_validity()))
+        } else {
+            None
+        }
+    }
+
+    /// Signs the fee quoted for the transaction of the given type, recipient, sender
+    /// (if it was known to the ticker) and fee token, along with the subsidy included into it.
+    pub fn sign(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        sender: Option<Address>,
+        token: TokenId,
+        total_fee: &BigUint,
+        subsidy: &BigUint,
+    ) -> FeeQuote {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let expires_at = (Utc::now() + self.validity).timestamp();
+        let signature = self.signature(
+            id, expires_at, tx_type, address, sender, token, total_fee, subsidy,
+        );
+        FeeQuote {
+            id,
+            expires_at,
+            subsidy: subsidy.clone(),
+            signature,
+        }
+    }
+
+    /// Checks that the quote is not expired and was issued for exactly the provided fee.
+    /// Quotes issued without the sender are accepted from any sender.
+    pub fn verify(
+        &self,
+        quote: &FeeQuote,
+        tx_type: TxFeeTypes,
+        address: Address,
+        sender: Address,
+        token: TokenId,
+        provided_fee: &BigUint,
+    ) -> bool {
+        if quote.expires_at < Utc::now().timestamp() {
+            return false;
+        }
+
+        [Some(sender), None].iter().any(|&quoted_sender| {
+            let expected = self.signature(
+                quote.id,
+                quote.expires_at,
+                tx_type,
+                address,
+                quoted_sender,
+                token,
+                provided_fee,
+                &quote.subsidy,
+            );
+            // Compare in constant time, so the signature can't be guessed byte by byte.
+            expected
+                .as_bytes()
+                .iter()
+                .zip(quote.signature.as_bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn signature(
+        &self,
+        id: u64,
+        expires_at: i64,
+        tx_type: TxFeeTypes,
+        address: Address,
+        sender: Option<Address>,
+        token: TokenId,
+        total_fee: &BigUint,
+        subsidy: &BigUint,
+    ) -> H256 {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.key.as_bytes());
+        bytes.extend_from_slice(&id.to_be_bytes());
+        bytes.extend_from_slice(&expires_at.to_be_bytes());
+        bytes.extend_from_slice(address.as_bytes());
+        match sender {
+            Some(sender) => {
+                bytes.push(1);
+                bytes.extend_from_slice(sender.as_bytes());
+            }
+            None => bytes.push(0),
+        }
+        bytes.extend_from_slice(&token.0.to_be_bytes());
+        // Amounts are prefixed with their length, so the variable-length fields can't be shifted.
+        for amount in &[total_fee, subsidy] {
+            let amount = amount.to_bytes_be();
+            bytes.extend_from_slice(&(amount.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&amount);
+        }
+        bytes.extend_from_slice(
+            &serde_json::to_vec(&tx_type).expect("Fee type serialization failed"),
+        );
+
+        H256(tiny_keccak::keccak256(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the quote is only accepted for the exact quoted values and until it expires.
+    #[test]
+    fn verify_quote() {
+        let signer = FeeQuoteSigner::new(H256::repeat_byte(1), chrono::Duration::seconds(60));
+        let address = Address::repeat_byte(2);
+        let sender = Address::repeat_byte(5);
+        let fee = BigUint::from(1000u32);
+        let subsidy = BigUint::from(100u32);
+
+        let quote = signer.sign(
+            TxFeeTypes::Transfer,
+            address,
+            None,
+            TokenId(1),
+            &fee,
+            &subsidy,
+        );
+        assert!(signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+
+        let next_quote = signer.sign(
+            TxFeeTypes::Transfer,
+            address,
+            None,
+            TokenId(1),
+            &fee,
+            &subsidy,
+        );
+        assert_ne!(quote.id, next_quote.id);
+
+        // Different fee, token, recipient or transaction type.
+        let lower_fee = BigUint::from(999u32);
+        assert!(!signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &lower_fee
+        ));
+        assert!(!signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(2),
+            &fee
+        ));
+        assert!(!signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            Address::repeat_byte(3),
+            sender,
+            TokenId(1),
+            &fee
+        ));
+        assert!(!signer.verify(
+            &quote,
+            TxFeeTypes::Withdraw,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+
+        // Quote with the changed expiration time.
+        let mut extended_quote = quote.clone();
+        extended_quote.expires_at += 60;
+        assert!(!signer.verify(
+            &extended_quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+
+        // Quote with the changed subsidy.
+        let mut forged_subsidy_quote = quote.clone();
+        forged_subsidy_quote.subsidy = BigUint::from(1000u32);
+        assert!(!signer.verify(
+            &forged_subsidy_quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+
+        // Quote signed by another key.
+        let other_signer = FeeQuoteSigner::new(H256::repeat_byte(4), chrono::Duration::seconds(60));
+        assert!(!other_signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+
+        // Expired quote.
+        let expired_signer =
+            FeeQuoteSigner::new(H256::repeat_byte(1), chrono::Duration::seconds(-1));
+        let expired_quote = expired_signer.sign(
+            TxFeeTypes::Transfer,
+            address,
+            None,
+            TokenId(1),
+            &fee,
+            &subsidy,
+        );
+        assert!(!signer.verify(
+            &expired_quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+    }
+
+    /// Checks that the quote issued for the known sender can't be used by other accounts.
+    #[test]
+    fn verify_sender_quote() {
+        let signer = FeeQuoteSigner::new(H256::repeat_byte(1), chrono::Duration::seconds(60));
+        let address = Address::repeat_byte(2);
+        let sender = Address::repeat_byte(5);
+        let fee = BigUint::from(500u32);
+
+        let quote = signer.sign(
+            TxFeeTypes::Transfer,
+            address,
+            Some(sender),
+            TokenId(1),
+            &fee,
+            &BigUint::default(),
+        );
+        assert!(signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            sender,
+            TokenId(1),
+            &fee
+        ));
+        assert!(!signer.verify(
+            &quote,
+            TxFeeTypes::Transfer,
+            address,
+            Address::repeat_byte(6),
+            TokenId(1),
+            &fee
+        ));
+    }
+}
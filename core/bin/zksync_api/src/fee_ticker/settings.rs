@@ -0,0 +1,418 @@
+//! Ticker settings adjustable at runtime.
+//!
+//! The ticker config is shared by all the ticker actors through the `TickerConfigHandle`.
+//! Upon the update the config is replaced as a whole, and every actor takes the fresh copy
+//! before processing the next request, so no restart is required.
+//!
+//! Token risk factors and gas costs, fee subsidies, discounts and waivers are stored in the database
+//! and periodically reloaded from it, so they are the same for all the API servers and survive the restart.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+// External deps
+use anyhow::ensure;
+use bigdecimal::BigDecimal;
+use num::{rational::Ratio, BigUint, Zero};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_config::configs::ticker::TokenRiskFactorConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{Address, FeeRoundingPolicy, OutputFeeType, TokenId};
+use zksync_utils::{
+    big_decimal_to_ratio, BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal,
+};
+// Local deps
+use super::{
+    discounts::FeeDiscount,
+    freeze::{FeeFreeze, FeeFreezeReport},
+    gas_costs::TokenGasCost,
+    subsidies::FeeSubsidy,
+    waivers::FeeWaiver,
+    GasOperationsCost, TickerConfig,
+};
+
+/// Sleep time between the reloads of the stored settings from the database.
+const SETTINGS_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Risk factor the fee paid in the token is multiplied by.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenRiskFactor {
+    pub token: TokenId,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub risk_factor: Ratio<BigUint>,
+}
+
+/// Rounding of the fees paid in the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenFeeRounding {
+    pub token: TokenId,
+    pub rounding: FeeRoundingPolicy,
+}
+
+/// Minimum fee paid in the token, applied after the fee is calculated and rounded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MinimumFee {
+    /// Amount in the smallest token units.
+    Token(#[serde(with = "BigUintSerdeAsRadix10Str")] BigUint),
+    /// Amount in USD, converted to the token at its current price.
+    Usd(#[serde(with = "UnsignedRatioSerializeAsDecimal")] Ratio<BigUint>),
+}
+
+impl MinimumFee {
+    fn is_zero(&self) -> bool {
+        match self {
+            MinimumFee::Token(amount) => amount.is_zero(),
+            MinimumFee::Usd(amount) => amount.is_zero(),
+        }
+    }
+}
+
+/// Minimum fee paid in the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenMinimumFee {
+    pub token: TokenId,
+    pub minimum_fee: MinimumFee,
+}
+
+/// Maximum fee of the transaction type. Fees exceeding it are usually caused by a wrong
+/// token price or a gas price spike, so they are clamped and reported.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MaximumFee {
+    pub fee_type: OutputFeeType,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub max_fee_usd: Ratio<BigUint>,
+}
+
+/// Gas cost of the operation observed on L1, including the safety margin.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ObservedGasCost {
+    pub fee_type: OutputFeeType,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_cost: BigUint,
+}
+
+/// Ticker settings which can be adjusted by the operator.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TickerSettings {
+    /// Coefficient for the fee price for fast withdrawal requests.
+    pub fast_processing_coeff: f64,
+    /// Cost of the proof generation for one chunk of the block, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Tokens for which subsidies are disabled.
+    pub not_subsidized_tokens: Vec<Address>,
+    /// Tokens with the risk factor other than 1.
+    pub tokens_risk_factors: Vec<TokenRiskFactor>,
+    /// Risk factors set in the config, used for the tokens without the risk factor above.
+    pub config_risk_factors: Vec<TokenRiskFactorConfig>,
+    /// Gas costs of the operations overridden for the tokens.
+    pub token_gas_costs: Vec<TokenGasCost>,
+    /// Gas costs of the operations observed on L1 and used instead of the standard ones.
+    pub observed_gas_costs: Vec<ObservedGasCost>,
+    /// All the fee subsidies, including the disabled and expired ones.
+    pub subsidies: Vec<FeeSubsidy>,
+    /// Fee discounts granted to the accounts.
+    pub fee_discounts: Vec<FeeDiscount>,
+    /// All the fee waivers, including the expired ones.
+    pub fee_waivers: Vec<FeeWaiver>,
+    /// Tokens with the fee rounding other than the default one.
+    pub fee_rounding: Vec<TokenFeeRounding>,
+    /// Tokens with the minimum fee.
+    pub minimum_fees: Vec<TokenMinimumFee>,
+    /// Transaction types with the maximum fee.
+    pub maximum_fees: Vec<MaximumFee>,
+    /// Fee inputs fixed so far, if the fees are frozen.
+    pub fee_freeze: Option<FeeFreezeReport>,
+}
+
+/// Update of the ticker settings. Every provided field replaces the current value as a whole.
+/// Token risk factors and gas costs, subsidies, discounts and waivers are stored in the database
+/// and can't be updated this way.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TickerSettingsUpdate {
+    pub fast_processing_coeff: Option<f64>,
+    pub zkp_cost_chunk_usd: Option<BigDecimal>,
+    pub not_subsidized_tokens: Option<Vec<Address>>,
+    pub fee_rounding: Option<Vec<TokenFeeRounding>>,
+    pub minimum_fees: Option<Vec<TokenMinimumFee>>,
+    pub maximum_fees: Option<Vec<MaximumFee>>,
+}
+
+/// Handle to the ticker config shared between the ticker actors and the admin server.
+#[derive(Debug, Clone)]
+pub struct TickerConfigHandle {
+    config: Arc<RwLock<Arc<TickerConfig>>>,
+}
+
+impl TickerConfigHandle {
+    pub fn new(config: TickerConfig) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(Arc::new(config))),
+        }
+    }
+
+    /// Returns the current config.
+    pub fn get(&self) -> Arc<TickerConfig> {
+        self.config
+            .read()
+            .expect("ticker config lock poisoned")
+            .clone()
+    }
+
+    /// Returns the current adjustable settings.
+    pub fn settings(&self) -> TickerSettings {
+        let config = self.get();
+
+        let mut not_subsidized_tokens: Vec<_> =
+            config.not_subsidized_tokens.iter().copied().collect();
+        not_subsidized_tokens.sort();
+        let mut tokens_risk_factors: Vec<_> = config
+            .tokens_risk_factors
+            .iter()
+            .map(|(&token, risk_factor)| TokenRiskFactor {
+                token,
+                risk_factor: risk_factor.clone(),
+            })
+            .collect();
+        tokens_risk_factors.sort_by_key(|factor| factor.token);
+        let mut config_risk_factors: Vec<_> = config
+            .config_risk_factors
+            .iter()
+            .map(|(&token, risk_factor)| TokenRiskFactorConfig {
+                token,
+                risk_factor: risk_factor.clone(),
+            })
+            .collect();
+        config_risk_factors.sort_by_key(|factor| factor.token);
+        let mut token_gas_costs: Vec<_> = config
+            .token_gas_costs
+            .iter()
+            .flat_map(|(&token, gas_costs)| {
+                gas_costs
+                    .iter()
+                    .map(move |(&fee_type, gas_cost)| TokenGasCost {
+                        token,
+                        fee_type,
+                        gas_cost: gas_cost.clone(),
+                    })
+            })
+            .collect();
+        token_gas_costs
+            .sort_by_key(|gas_cost| (gas_cost.token, format!("{:?}", gas_cost.fee_type)));
+        let mut observed_gas_costs: Vec<_> = config
+            .observed_gas_costs
+            .iter()
+            .map(|(&fee_type, gas_cost)| ObservedGasCost {
+                fee_type,
+                gas_cost: gas_cost.clone(),
+            })
+            .collect();
+        observed_gas_costs.sort_by_key(|gas_cost| format!("{:?}", gas_cost.fee_type));
+        let mut fee_discounts: Vec<_> = config.fee_discounts.values().flatten().cloned().collect();
+        fee_discounts.sort_by_key(|discount| discount.id);
+        let mut fee_rounding: Vec<_> = config
+            .fee_rounding
+            .iter()
+            .map(|(&token, rounding)| TokenFeeRounding {
+                token,
+                rounding: rounding.clone(),
+            })
+            .collect();
+        fee_rounding.sort_by_key(|rounding| rounding.token);
+        let mut minimum_fees: Vec<_> = config
+            .minimum_fees
+            .iter()
+            .map(|(&token, minimum_fee)| TokenMinimumFee {
+                token,
+                minimum_fee: minimum_fee.clone(),
+            })
+            .collect();
+        minimum_fees.sort_by_key(|minimum_fee| minimum_fee.token);
+        let mut maximum_fees: Vec<_> = config
+            .maximum_fees
+            .iter()
+            .map(|(&fee_type, max_fee_usd)| MaximumFee {
+                fee_type,
+                max_fee_usd: max_fee_usd.clone(),
+            })
+            .collect();
+        maximum_fees.sort_by_key(|maximum_fee| format!("{:?}", maximum_fee.fee_type));
+
+        TickerSettings {
+            fast_processing_coeff: config.fast_processing_coeff,
+            zkp_cost_chunk_usd: config.zkp_cost_chunk_usd.clone(),
+            not_subsidized_tokens,
+            tokens_risk_factors,
+            config_risk_factors,
+            token_gas_costs,
+            observed_gas_costs,
+            subsidies: config.subsidies.clone(),
+            fee_discounts,
+            fee_waivers: config.fee_waivers.clone(),
+            fee_rounding,
+            minimum_fees,
+            maximum_fees,
+            fee_freeze: config.fee_freeze.as_ref().map(|freeze| freeze.report()),
+        }
+    }
+
+    /// Freezes the fees, replacing the current freeze if any.
+    pub fn freeze(&self, freeze: FeeFreeze) -> TickerSettings {
+        self.modify(|config| config.fee_freeze = Some(Arc::new(freeze)));
+        self.settings()
+    }
+
+    /// Lifts the fee freeze. Returns `false` if the fees are not frozen.
+    pub fn unfreeze(&self) -> bool {
+        let mut frozen = false;
+        self.modify(|config| frozen = config.fee_freeze.take().is_some());
+        frozen
+    }
+
+    /// Applies the update and returns the resulting settings.
+    /// The config is left intact if the update contains invalid values.
+    pub fn update(&self, update: TickerSettingsUpdate) -> anyhow::Result<TickerSettings> {
+        if let Some(coeff) = update.fast_processing_coeff {
+            ensure!(
+                coeff >= 1.0,
+                "fast processing can't be cheaper than the usual one"
+            );
+        }
+        let zkp_cost_chunk_usd = update
+            .zkp_cost_chunk_usd
+            .as_ref()
+            .map(big_decimal_to_ratio)
+            .transpose()?;
+        if let Some(cost) = &zkp_cost_chunk_usd {
+            ensure!(!cost.is_zero(), "zkp chunk cost must be positive");
+        }
+        for token_rounding in update.fee_rounding.iter().flatten() {
+            ensure!(
+                token_rounding.rounding.significant_digits != Some(0),
+                "fee must have at least one significant digit"
+            );
+        }
+        for token_minimum_fee in update.minimum_fees.iter().flatten() {
+            ensure!(
+                !token_minimum_fee.minimum_fee.is_zero(),
+                "minimum fee must be positive"
+            );
+        }
+        for maximum_fee in update.maximum_fees.iter().flatten() {
+            ensure!(
+                !maximum_fee.max_fee_usd.is_zero(),
+                "maximum fee must be positive"
+            );
+        }
+
+        self.modify(|config| {
+            if let Some(coeff) = update.fast_processing_coeff {
+                config.fast_processing_coeff = coeff;
+                config.gas_cost_tx = GasOperationsCost::from_constants(coeff)
+                    .with_observed_costs(&config.observed_gas_costs, coeff);
+            }
+            if let Some(cost) = zkp_cost_chunk_usd {
+                config.zkp_cost_chunk_usd = cost;
+            }
+            if let Some(tokens) = update.not_subsidized_tokens {
+                config.not_subsidized_tokens = tokens.into_iter().collect();
+            }
+            if let Some(fee_rounding) = update.fee_rounding {
+                config.fee_rounding = fee_rounding
+                    .into_iter()
+                    .map(|rounding| (rounding.token, rounding.rounding))
+                    .collect();
+            }
+            if let Some(minimum_fees) = update.minimum_fees {
+                config.minimum_fees = minimum_fees
+                    .into_iter()
+                    .map(|minimum_fee| (minimum_fee.token, minimum_fee.minimum_fee))
+                    .collect();
+            }
+            if let Some(maximum_fees) = update.maximum_fees {
+                config.maximum_fees = maximum_fees
+                    .into_iter()
+                    .map(|maximum_fee| (maximum_fee.fee_type, maximum_fee.max_fee_usd))
+                    .collect();
+            }
+        });
+        Ok(self.settings())
+    }
+
+    /// Replaces the observed gas costs of the operations, so they are used instead of the standard ones.
+    pub fn set_observed_gas_costs(&self, observed_gas_costs: HashMap<OutputFeeType, BigUint>) {
+        self.modify(|config| {
+            config.gas_cost_tx = GasOperationsCost::from_constants(config.fast_processing_coeff)
+                .with_observed_costs(&observed_gas_costs, config.fast_processing_coeff);
+            config.observed_gas_costs = observed_gas_costs;
+        });
+    }
+
+    /// Replaces the token risk factors and gas costs, subsidies, discounts and waivers with the ones
+    /// loaded from the database.
+    pub async fn reload(&self, db_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = db_pool.access_storage().await?;
+        let risk_factors = storage.tokens_schema().load_risk_factors().await?;
+        let mut token_gas_costs: HashMap<TokenId, HashMap<OutputFeeType, BigUint>> = HashMap::new();
+        for gas_cost in storage.token_gas_costs_schema().load_gas_costs().await? {
+            let gas_cost = TokenGasCost::from_stored(gas_cost)?;
+            token_gas_costs
+                .entry(gas_cost.token)
+                .or_default()
+                .insert(gas_cost.fee_type, gas_cost.gas_cost);
+        }
+        let subsidies = storage
+            .fee_subsidies_schema()
+            .load_subsidies()
+            .await?
+            .into_iter()
+            .map(FeeSubsidy::from_stored)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut fee_discounts: HashMap<Address, Vec<FeeDiscount>> = HashMap::new();
+        for discount in storage.fee_discounts_schema().load_discounts().await? {
+            let discount = FeeDiscount::from_stored(discount)?;
+            fee_discounts
+                .entry(discount.address)
+                .or_default()
+                .push(discount);
+        }
+        let fee_waivers = storage
+            .fee_waivers_schema()
+            .load_waivers()
+            .await?
+            .into_iter()
+            .map(FeeWaiver::from_stored)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        self.modify(|config| {
+            config.tokens_risk_factors = risk_factors;
+            config.token_gas_costs = token_gas_costs;
+            config.subsidies = subsidies;
+            config.fee_discounts = fee_discounts;
+            config.fee_waivers = fee_waivers;
+        });
+        Ok(())
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut TickerConfig)) {
+        let mut current = self.config.write().expect("ticker config lock poisoned");
+        let mut config = TickerConfig::clone(&current);
+        f(&mut config);
+        *current = Arc::new(config);
+    }
+}
+
+/// Periodically reloads the stored settings, so the changes made by other servers are picked up.
+pub async fn run_settings_updater(db_pool: ConnectionPool, ticker_config: TickerConfigHandle) {
+    let mut timer = tokio::time::interval(SETTINGS_UPDATE_INTERVAL);
+    loop {
+        timer.tick().await;
+
+        if let Err(e) = ticker_config.reload(&db_pool).await {
+            vlog::error!("Failed to reload the ticker settings: {}", e);
+        }
+    }
+}
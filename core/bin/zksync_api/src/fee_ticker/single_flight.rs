@@ -0,0 +1,161 @@
+//! Deduplication of the identical fee requests.
+//!
+//! Wallets of many users ask for the fee of the same transaction at once, e.g. the fee of the ETH
+//! transfer when the price changes. Every request used to be calculated by the ticker on its own,
+//! so the balancer now dispatches only the first of the identical requests, and the ones received
+//! while it's being handled wait for its response. The requests are identical if they have the
+//! same transaction type, token, recipient and sender, since the fee quote depends on all of them.
+//! The fee is recorded in the audit log once for all the requests sharing it.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+// External deps
+use anyhow::format_err;
+use futures::channel::oneshot;
+// Workspace deps
+use zksync_types::{Address, Fee, TokenLike, TxFeeTypes};
+// Local deps
+use super::TickerRequest;
+
+type FeeResponder = oneshot::Sender<anyhow::Result<Fee>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FeeRequestKey {
+    tx_type: TxFeeTypes,
+    token: TokenLike,
+    address: Address,
+    sender: Option<Address>,
+}
+
+/// Fee requests dispatched to the ticker with the requests waiting for their responses.
+#[derive(Debug, Clone, Default)]
+pub(super) struct InflightFees {
+    requests: Arc<Mutex<HashMap<FeeRequestKey, Vec<FeeResponder>>>>,
+}
+
+impl InflightFees {
+    /// Returns the request to dispatch to the ticker, or `None` if the identical request is
+    /// already dispatched and the request will get its response.
+    pub fn dedup(&self, request: TickerRequest) -> Option<TickerRequest> {
+        let (tx_type, address, sender, token, response, span) = match request {
+            TickerRequest::GetTxFee {
+                tx_type,
+                address,
+                sender,
+                token,
+                response,
+                span,
+            } => (tx_type, address, sender, token, response, span),
+            request => return Some(request),
+        };
+        let key = FeeRequestKey {
+            tx_type,
+            token: token.clone(),
+            address,
+            sender,
+        };
+
+        let mut requests = self.requests.lock().unwrap();
+        if let Some(waiting) = requests.get_mut(&key) {
+            waiting.push(response);
+            metrics::counter!("ticker.single_flight.joined", 1);
+            return None;
+        }
+        requests.insert(key.clone(), vec![response]);
+
+        let (dispatched_response, dispatched_receiver) = oneshot::channel();
+        let inflight = self.requests.clone();
+        tokio::spawn(async move {
+            let fee = dispatched_receiver
+                .await
+                .unwrap_or_else(|_| Err(format_err!("Fee ticker dropped the request")));
+            let waiting = inflight.lock().unwrap().remove(&key).unwrap_or_default();
+            share_fee(fee, waiting);
+        });
+        Some(TickerRequest::GetTxFee {
+            tx_type,
+            address,
+            sender,
+            token,
+            response: dispatched_response,
+            span,
+        })
+    }
+}
+
+/// Sends the fee to all the requests waiting for it. Errors are not cloneable, so the requests
+/// other than the first one get the error message.
+fn share_fee(fee: anyhow::Result<Fee>, waiting: Vec<FeeResponder>) {
+    let mut waiting = waiting.into_iter();
+    let first = match waiting.next() {
+        Some(first) => first,
+        None => return,
+    };
+    for response in waiting {
+        let fee = match &fee {
+            Ok(fee) => Ok(fee.clone()),
+            Err(e) => Err(format_err!("{:#}", e)),
+        };
+        response.send(fee).unwrap_or_default();
+    }
+    first.send(fee).unwrap_or_default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{OutputFeeType, TokenId};
+
+    fn fee_request(address: Address) -> (TickerRequest, oneshot::Receiver<anyhow::Result<Fee>>) {
+        let (response, receiver) = oneshot::channel();
+        let request = TickerRequest::GetTxFee {
+            tx_type: TxFeeTypes::Transfer,
+            address,
+            sender: None,
+            token: TokenLike::Id(TokenId(0)),
+            response,
+            span: tracing::Span::current(),
+        };
+        (request, receiver)
+    }
+
+    fn respond(request: TickerRequest, fee: anyhow::Result<Fee>) {
+        match request {
+            TickerRequest::GetTxFee { response, .. } => response.send(fee).unwrap(),
+            _ => panic!("Wrong type"),
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_requests_share_response() {
+        let inflight = InflightFees::default();
+        let (first, first_receiver) = fee_request(Address::zero());
+        let (identical, identical_receiver) = fee_request(Address::zero());
+        let (other, other_receiver) = fee_request(Address::repeat_byte(1));
+
+        let dispatched = inflight.dedup(first).unwrap();
+        assert!(inflight.dedup(identical).is_none());
+        let other_dispatched = inflight.dedup(other).unwrap();
+
+        let fee = Fee::waived(OutputFeeType::Transfer, 1u32.into());
+        respond(dispatched, Ok(fee.clone()));
+        assert_eq!(
+            first_receiver.await.unwrap().unwrap().total_fee,
+            fee.total_fee
+        );
+        assert_eq!(
+            identical_receiver.await.unwrap().unwrap().total_fee,
+            fee.total_fee
+        );
+
+        // The request is dispatched again once the response is received.
+        let (repeated, repeated_receiver) = fee_request(Address::zero());
+        let repeated_dispatched = inflight.dedup(repeated).unwrap();
+
+        respond(other_dispatched, Err(format_err!("Token is not allowed")));
+        assert!(other_receiver.await.unwrap().is_err());
+        drop(repeated_dispatched);
+        assert!(repeated_receiver.await.unwrap().is_err());
+    }
+}
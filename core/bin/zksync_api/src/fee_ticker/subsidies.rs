@@ -0,0 +1,122 @@
+//! Fee subsidies.
+//!
+//! While the subsidy is active, the gas part of the fee is calculated using the subsidized
+//! operation costs. Subsidy may be limited to the token the fee is paid in and to the fee
+//! type, and is only active within its time window. Subsidies are stored in the database
+//! and reloaded by the ticker together with the other settings.
+//!
+//! The subsidy covering the fee of the accepted transaction is recorded, so the total spent
+//! on the subsidies can be reported. Only the transactions which fee is checked against
+//! the ticker are recorded, i.e. the batches and the transactions paying the signed quote
+//! are not accounted.
+
+// External deps
+use chrono::{DateTime, Utc};
+use num::{bigint::ToBigInt, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_storage::fee_subsidies::records::{
+    NewFeeSubsidy, StoredFeeSubsidy, StoredSubsidySpendTotal,
+};
+use zksync_types::{OutputFeeType, TokenId};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeSubsidy {
+    pub id: i32,
+    /// Token the fee is paid in, the subsidy applies to all the tokens if not set.
+    pub token: Option<TokenId>,
+    /// Fee type, the subsidy applies to all the fee types if not set.
+    pub fee_type: Option<OutputFeeType>,
+    pub starts_at: DateTime<Utc>,
+    /// The subsidy doesn't expire if not set.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub enabled: bool,
+}
+
+impl FeeSubsidy {
+    pub fn from_stored(subsidy: StoredFeeSubsidy) -> anyhow::Result<Self> {
+        let fee_type = subsidy.fee_type.map(serde_json::from_value).transpose()?;
+        Ok(Self {
+            id: subsidy.id,
+            token: subsidy.token_id.map(|id| TokenId(id as u16)),
+            fee_type,
+            starts_at: subsidy.starts_at,
+            ends_at: subsidy.ends_at,
+            enabled: subsidy.enabled,
+        })
+    }
+
+    /// Returns `true` if the subsidy applies to the fee of the given type paid in the token
+    /// at the given moment.
+    pub fn is_active(&self, token: TokenId, fee_type: OutputFeeType, now: DateTime<Utc>) -> bool {
+        self.enabled
+            && self
+                .token
+                .map_or(true, |subsidy_token| subsidy_token == token)
+            && self
+                .fee_type
+                .map_or(true, |subsidy_fee_type| subsidy_fee_type == fee_type)
+            && self.starts_at <= now
+            && self.ends_at.map_or(true, |ends_at| now < ends_at)
+    }
+}
+
+/// Request to add the subsidy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewSubsidyRequest {
+    pub token: Option<TokenId>,
+    pub fee_type: Option<OutputFeeType>,
+    /// The subsidy starts right away if not set.
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl NewSubsidyRequest {
+    pub fn into_record(self) -> anyhow::Result<NewFeeSubsidy> {
+        let starts_at = self.starts_at.unwrap_or_else(Utc::now);
+        if let Some(ends_at) = self.ends_at {
+            anyhow::ensure!(starts_at < ends_at, "subsidy must end after it starts");
+        }
+
+        Ok(NewFeeSubsidy {
+            token_id: self.token.map(|token| i32::from(*token)),
+            fee_type: self.fee_type.map(serde_json::to_value).transpose()?,
+            starts_at,
+            ends_at: self.ends_at,
+        })
+    }
+}
+
+/// Request to report the subsidies spent on the transactions accepted within the time range.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubsidyReportRequest {
+    pub from: DateTime<Utc>,
+    /// The report covers the transactions up to now if not set.
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Total subsidy spent on the executed transactions paying the fee in the token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubsidySpendTotal {
+    pub token: TokenId,
+    pub txs_count: i64,
+    /// Amount in the smallest units of the token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_subsidy: BigUint,
+}
+
+impl SubsidySpendTotal {
+    pub fn from_stored(total: StoredSubsidySpendTotal) -> anyhow::Result<Self> {
+        let total_subsidy = total
+            .total_subsidy
+            .to_bigint()
+            .and_then(|total_subsidy| total_subsidy.to_biguint())
+            .ok_or_else(|| anyhow::anyhow!("total subsidy must be a non-negative integer"))?;
+        Ok(Self {
+            token: TokenId(total.token_id as u16),
+            txs_count: total.txs_count,
+            total_subsidy,
+        })
+    }
+}
@@ -4,14 +4,20 @@ use async_trait::async_trait;
 use bigdecimal::BigDecimal;
 use chrono::Utc;
 use futures::future::{AbortHandle, Abortable};
-use futures::{channel::mpsc, executor::block_on};
+use futures::{channel::mpsc, executor::block_on, SinkExt};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::thread::sleep;
 use tokio::time::Duration;
+use zksync_config::configs::ticker::TokenPriceAggregation;
 use zksync_types::{Address, Token, TokenId, TokenPrice};
-use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
+use zksync_utils::{
+    big_decimal_to_ratio, ratio_to_big_decimal, round_precision, UnsignedRatioSerializeAsDecimal,
+};
 
 use crate::fee_ticker::{
+    block_fullness::PendingBlockFullness,
+    freeze::FeeFreezeRequest,
     ticker_api::{
         coingecko::{CoinGeckoTokenInfo, CoinGeckoTokenList},
         TokenPriceAPI,
@@ -88,11 +94,14 @@ impl TestToken {
     }
 }
 
-fn get_test_ticker_config() -> TickerConfig {
-    TickerConfig {
+fn get_test_ticker_config() -> TickerConfigHandle {
+    TickerConfigHandle::new(TickerConfig {
         zkp_cost_chunk_usd: UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("0.001")
             .unwrap(),
+        fast_processing_coeff: TEST_FAST_WITHDRAW_COEFF,
         gas_cost_tx: GasOperationsCost::from_constants(TEST_FAST_WITHDRAW_COEFF),
+        observed_gas_costs: HashMap::new(),
+        token_gas_costs: HashMap::new(),
         tokens_risk_factors: TestToken::all_tokens()
             .into_iter()
             .filter_map(|t| {
@@ -100,12 +109,45 @@ fn get_test_ticker_config() -> TickerConfig {
                 t.risk_factor.map(|risk| (id, risk))
             })
             .collect(),
+        config_risk_factors: HashMap::new(),
         not_subsidized_tokens: vec![
             Address::from_str("34083bbd70d394110487feaa087da875a54624ec").unwrap(),
         ]
         .into_iter()
         .collect(),
-    }
+        subsidies: Vec::new(),
+        fee_discounts: HashMap::new(),
+        fee_waivers: Vec::new(),
+        fee_rounding: HashMap::new(),
+        minimum_fees: HashMap::new(),
+        maximum_fees: HashMap::new(),
+        batch_fee_model: BatchFeeModel::standalone(),
+        fee_freeze: None,
+    })
+}
+
+/// Creates the token validator with the in-memory tokens cache.
+fn test_validator() -> FeeTokenValidator<FakeTokenWatcher> {
+    FeeTokenValidator::new(
+        TokenInMemoryCache::new(),
+        chrono::Duration::seconds(100),
+        BigDecimal::from(100),
+        Default::default(),
+        FakeTokenWatcher,
+    )
+}
+
+/// Creates the ticker with the mocked price API and the test token validator.
+fn test_ticker(
+    config: TickerConfigHandle,
+) -> FeeTicker<MockApiProvider, MockTickerInfo, FakeTokenWatcher> {
+    FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo,
+        mpsc::channel(1).1,
+        config,
+        test_validator(),
+    )
 }
 
 struct MockApiProvider;
@@ -125,8 +167,8 @@ impl FeeTickerAPI for MockApiProvider {
     }
 
     /// Get current gas price in ETH
-    async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error> {
-        Ok(BigUint::from(10u32).pow(7u32)) // 10 GWei
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error> {
+        Ok(GasPriceWei::legacy(BigUint::from(10u32).pow(7u32))) // 10 GWei
     }
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
@@ -154,6 +196,68 @@ impl FeeTickerAPI for MockApiProvider {
     }
 }
 
+/// Counts the price requests and allows to change the gas price, so the fee cache usage is observable.
+#[derive(Debug, Clone)]
+struct CountingApiProvider {
+    quotes: Arc<AtomicUsize>,
+    gas_price: Arc<AtomicU64>,
+}
+
+impl CountingApiProvider {
+    fn new() -> Self {
+        Self {
+            quotes: Default::default(),
+            gas_price: Arc::new(AtomicU64::new(10u64.pow(7))),
+        }
+    }
+
+    fn quotes(&self) -> usize {
+        self.quotes.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl FeeTickerAPI for CountingApiProvider {
+    async fn get_last_quote(&self, token: TokenLike) -> Result<TokenPrice, anyhow::Error> {
+        self.quotes.fetch_add(1, Ordering::SeqCst);
+        MockApiProvider.get_last_quote(token).await
+    }
+
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error> {
+        Ok(GasPriceWei::legacy(
+            self.gas_price.load(Ordering::SeqCst).into(),
+        ))
+    }
+
+    async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
+        MockApiProvider.get_token(token).await
+    }
+}
+
+/// Hangs on the gas price requests while `hang` is set, like the unresponsive API does.
+#[derive(Debug, Clone, Default)]
+struct HangingApiProvider {
+    hang: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl FeeTickerAPI for HangingApiProvider {
+    async fn get_last_quote(&self, token: TokenLike) -> Result<TokenPrice, anyhow::Error> {
+        MockApiProvider.get_last_quote(token).await
+    }
+
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error> {
+        if self.hang.load(Ordering::SeqCst) {
+            futures::future::pending::<()>().await;
+        }
+        MockApiProvider.get_gas_price_wei().await
+    }
+
+    async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
+        MockApiProvider.get_token(token).await
+    }
+}
+
 struct MockTickerInfo;
 
 #[async_trait]
@@ -162,6 +266,24 @@ impl FeeTickerInfo for MockTickerInfo {
         // Always false for simplicity.
         false
     }
+
+    async fn is_signing_key_set(&mut self, _address: Address) -> bool {
+        false
+    }
+}
+
+/// Ticker info treating the only given address as the new account.
+struct NewRecipientTickerInfo(Address);
+
+#[async_trait]
+impl FeeTickerInfo for NewRecipientTickerInfo {
+    async fn is_account_new(&mut self, address: Address) -> bool {
+        address == self.0
+    }
+
+    async fn is_signing_key_set(&mut self, _address: Address) -> bool {
+        false
+    }
 }
 
 fn format_with_dot(num: &Ratio<BigUint>, precision: usize) -> String {
@@ -187,6 +309,75 @@ impl TokenPriceAPI for ErrorTickerApi {
     }
 }
 
+#[derive(Debug, Clone)]
+struct FixedPriceTickerApi;
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for FixedPriceTickerApi {
+    async fn get_price(&self, _token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        Ok(TokenPrice {
+            usd_price: Ratio::from_integer(10u32.into()),
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+/// Returns the price far away from the historical one.
+#[derive(Debug, Clone)]
+struct GlitchedPriceTickerApi;
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for GlitchedPriceTickerApi {
+    async fn get_price(&self, _token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        Ok(TokenPrice {
+            usd_price: Ratio::from_integer(1000u32.into()),
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+/// Returns the price last updated the given time ago, or fails if the time is not set.
+#[derive(Debug, Clone)]
+struct AgedPriceTickerApi {
+    usd_price: u32,
+    age: Option<chrono::Duration>,
+}
+
+#[async_trait::async_trait]
+impl TokenPriceAPI for AgedPriceTickerApi {
+    async fn get_price(&self, _token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        let age = self
+            .age
+            .ok_or_else(|| anyhow::format_err!("API is not available"))?;
+        Ok(TokenPrice {
+            usd_price: Ratio::from_integer(self.usd_price.into()),
+            last_updated: Utc::now() - age,
+        })
+    }
+}
+
+/// Creates an in-memory ticker storage which contains ETH and one ERC20 token
+/// with the historical prices stored for both of them.
+async fn ticker_storage_with_historical_prices() -> TickerInMemoryStorage {
+    let storage = TickerInMemoryStorage::new().with_tokens(vec![
+        Token::new(TokenId(0), Address::default(), "ETH", 18),
+        Token::new(TokenId(1), Address::from_low_u64_be(1), "DAI", 18),
+    ]);
+    for token_id in &[TokenId(0), TokenId(1)] {
+        storage
+            .update_historical_ticker_price(
+                *token_id,
+                TokenPrice {
+                    usd_price: big_decimal_to_ratio(&BigDecimal::from(10)).unwrap(),
+                    last_updated: chrono::offset::Utc::now(),
+                },
+            )
+            .await
+            .unwrap();
+    }
+    storage
+}
+
 fn run_server() -> (String, AbortHandle) {
     let mut url = None;
     let mut server = None;
@@ -226,27 +417,12 @@ fn run_server() -> (String, AbortHandle) {
 
 #[test]
 fn test_ticker_formula() {
-    let validator = FeeTokenValidator::new(
-        TokenInMemoryCache::new(),
-        chrono::Duration::seconds(100),
-        BigDecimal::from(100),
-        Default::default(),
-        FakeTokenWatcher,
-    );
-
-    let config = get_test_ticker_config();
-    let mut ticker = FeeTicker::new(
-        MockApiProvider,
-        MockTickerInfo,
-        mpsc::channel(1).1,
-        config,
-        validator,
-    );
+    let mut ticker = test_ticker(get_test_ticker_config());
 
     let mut get_token_fee_in_usd =
         |tx_type: TxFeeTypes, token: TokenLike, address: Address| -> Ratio<BigUint> {
             let fee_in_token =
-                block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.clone(), address))
+                block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.clone(), address, None))
                     .expect("failed to get fee in token");
             let token_precision = block_on(MockApiProvider.get_token(token.clone()))
                 .unwrap()
@@ -334,112 +510,1546 @@ fn test_ticker_formula() {
     }
 }
 
-#[actix_rt::test]
-#[ignore]
-// It's ignore because we can't initialize coingecko in current way with block
-async fn test_error_coingecko_api() {
-    let (address, handler) = run_server();
-    let client = reqwest::ClientBuilder::new()
-        .timeout(CONNECTION_TIMEOUT)
-        .connect_timeout(CONNECTION_TIMEOUT)
-        .build()
-        .expect("Failed to build reqwest::Client");
-    let coingecko = CoinGeckoAPI::new(client, address.parse().unwrap()).unwrap();
-    let validator = FeeTokenValidator::new(
-        TokenInMemoryCache::new(),
-        chrono::Duration::seconds(100),
-        BigDecimal::from(100),
-        Default::default(),
-        FakeTokenWatcher,
+/// Checks that the ticker quotes the fees for the NFT and swap operations.
+/// Checks that the fee breakdown contains the values the fee is calculated from.
+#[test]
+fn test_fee_breakdown() {
+    let mut ticker = test_ticker(get_test_ticker_config());
+
+    let token = TestToken::hex();
+    let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Withdraw,
+        token.id.into(),
+        Address::default(),
+        None,
+    ))
+    .unwrap();
+    let breakdown = fee.breakdown.expect("fee breakdown is not set");
+
+    assert_eq!(breakdown.token_price_usd, token.price_usd);
+    assert_eq!(breakdown.risk_factor, token.risk_factor());
+    // Mocked gas price scaled by 1.3.
+    assert_eq!(breakdown.gas_price_wei, BigUint::from(13_000_000u32));
+
+    let token_usd_risk = token.risk_factor()
+        / (token.price_usd / BigUint::from(10u32).pow(u32::from(token.precision)));
+    let zkp_fee = round_precision(&(breakdown.zkp_cost_usd * token_usd_risk.clone()), 18)
+        .ceil()
+        .to_integer();
+    let gas_fee = round_precision(&(breakdown.gas_cost_usd * token_usd_risk), 18)
+        .ceil()
+        .to_integer();
+    assert_eq!(fee.zkp_fee, zkp_fee);
+    assert_eq!(fee.gas_fee, gas_fee);
+}
+
+#[test]
+fn test_nft_and_swap_fees() {
+    let mut ticker = test_ticker(get_test_ticker_config());
+
+    let mut get_fee = |tx_type: TxFeeTypes| -> Fee {
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            tx_type,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        let batch_fee =
+            block_on(ticker.get_batch_from_ticker_in_wei(
+                TokenId(0).into(),
+                vec![(tx_type, Address::default())],
+            ))
+            .expect("failed to get batched fee for token");
+        assert_eq!(fee.total_fee, batch_fee.total_fee);
+        fee
+    };
+
+    let mint_nft_fee = get_fee(TxFeeTypes::MintNFT);
+    let withdraw_nft_fee = get_fee(TxFeeTypes::WithdrawNFT);
+    let fast_withdraw_nft_fee = get_fee(TxFeeTypes::FastWithdrawNFT);
+    let swap_fee = get_fee(TxFeeTypes::Swap);
+    let transfer_fee = get_fee(TxFeeTypes::Transfer);
+
+    assert_eq!(mint_nft_fee.fee_type, OutputFeeType::MintNFT);
+    assert_eq!(withdraw_nft_fee.fee_type, OutputFeeType::WithdrawNFT);
+    assert_eq!(
+        fast_withdraw_nft_fee.fee_type,
+        OutputFeeType::FastWithdrawNFT
     );
-    let connection_pool = ConnectionPool::new(Some(1));
-    connection_pool
-        .access_storage()
-        .await
-        .unwrap()
-        .tokens_schema()
-        .update_historical_ticker_price(
-            TokenId(1),
-            TokenPrice {
-                usd_price: big_decimal_to_ratio(&BigDecimal::from(10)).unwrap(),
-                last_updated: chrono::offset::Utc::now(),
-            },
-        )
-        .await
-        .unwrap();
-    let ticker_api = TickerApi::new(connection_pool, coingecko);
+    assert_eq!(swap_fee.fee_type, OutputFeeType::Swap);
+
+    // Operations taking more chunks are more expensive to prove than the transfer.
+    assert!(mint_nft_fee.zkp_fee > transfer_fee.zkp_fee);
+    assert!(swap_fee.zkp_fee > transfer_fee.zkp_fee);
+    // NFT withdrawal pays for the L1 token minting.
+    assert!(withdraw_nft_fee.gas_fee > mint_nft_fee.gas_fee);
+    assert!(fast_withdraw_nft_fee.total_fee > withdraw_nft_fee.total_fee);
+}
 
+/// Checks that the forced exit is quoted with its own fee type rather than as a withdrawal.
+#[test]
+fn test_forced_exit_fee() {
+    let mut ticker = test_ticker(get_test_ticker_config());
+
+    let token = TestToken::eth();
+    let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::ForcedExit,
+        token.id.into(),
+        Address::default(),
+        None,
+    ))
+    .expect("failed to get fee in token");
+    assert_eq!(fee.fee_type, OutputFeeType::ForcedExit);
+
+    assert_eq!(
+        fee.gas_tx_amount,
+        BigUint::from(constants::BASE_FORCED_EXIT_COST)
+    );
+    let gas_cost = &get_test_ticker_config().get().gas_cost_tx;
+    assert_eq!(
+        gas_cost.subsidize_cost[&OutputFeeType::ForcedExit],
+        BigUint::from(constants::SUBSIDY_FORCED_EXIT_COST)
+    );
+
+    let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+        token.id.into(),
+        vec![(TxFeeTypes::ForcedExit, Address::default())],
+    ))
+    .expect("failed to get batched fee for token");
+    assert_eq!(fee.total_fee, batch_fee.total_fee);
+}
+
+/// Checks that the forced exits are quoted with their own gas costs while the global
+/// subsidy is active.
+#[test]
+fn test_forced_exit_fee_with_global_subsidy() {
     let config = get_test_ticker_config();
-    let mut ticker = FeeTicker::new(
-        ticker_api,
-        MockTickerInfo,
-        mpsc::channel(1).1,
-        config,
-        validator,
+    let mut ticker = test_ticker(config.clone());
+
+    let mut get_gas_tx_amount = |subsidies: Vec<FeeSubsidy>| -> BigUint {
+        ticker.config = Arc::new(TickerConfig {
+            subsidies,
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::ForcedExit,
+            TestToken::eth().id.into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        assert_eq!(fee.fee_type, OutputFeeType::ForcedExit);
+        fee.gas_tx_amount
+    };
+    let global_subsidy = FeeSubsidy {
+        id: 1,
+        token: None,
+        fee_type: None,
+        starts_at: Utc::now() - chrono::Duration::hours(1),
+        ends_at: Some(Utc::now() + chrono::Duration::hours(1)),
+        enabled: true,
+    };
+
+    assert_eq!(
+        get_gas_tx_amount(Vec::new()),
+        BigUint::from(constants::BASE_FORCED_EXIT_COST)
+    );
+    assert_eq!(
+        get_gas_tx_amount(vec![global_subsidy]),
+        BigUint::from(constants::SUBSIDY_FORCED_EXIT_COST)
     );
-    for _ in 0..1000 {
-        ticker
-            .get_fee_from_ticker_in_wei(
-                TxFeeTypes::FastWithdraw,
-                TokenId(1).into(),
-                Address::default(),
-            )
-            .await
-            .unwrap();
-        ticker
-            .get_token_price(TokenId(1).into(), TokenPriceRequestType::USDForOneWei)
-            .await
-            .unwrap();
-    }
-    handler.abort();
 }
 
-#[tokio::test]
-#[ignore]
-async fn test_error_api() {
-    let validator = FeeTokenValidator::new(
-        TokenInMemoryCache::new(),
-        chrono::Duration::seconds(100),
-        BigDecimal::from(100),
-        Default::default(),
-        FakeTokenWatcher,
+/// Checks that the gas cost is subsidized only while the matching subsidy is active.
+#[test]
+fn test_fee_subsidies() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let mut get_gas_fee = |subsidies: Vec<FeeSubsidy>, token: TokenId| -> BigUint {
+        ticker.config = Arc::new(TickerConfig {
+            subsidies,
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Withdraw,
+            token.into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        fee.gas_fee
+    };
+    let subsidy = |token: Option<TokenId>, fee_type: Option<OutputFeeType>| FeeSubsidy {
+        id: 1,
+        token,
+        fee_type,
+        starts_at: Utc::now() - chrono::Duration::hours(1),
+        ends_at: Some(Utc::now() + chrono::Duration::hours(1)),
+        enabled: true,
+    };
+
+    let standard_fee = get_gas_fee(Vec::new(), TokenId(0));
+    let subsidized_fee = get_gas_fee(vec![subsidy(None, None)], TokenId(0));
+    assert!(subsidized_fee < standard_fee);
+    assert_eq!(
+        get_gas_fee(
+            vec![subsidy(Some(TokenId(0)), Some(OutputFeeType::Withdraw))],
+            TokenId(0)
+        ),
+        subsidized_fee
     );
-    let connection_pool = ConnectionPool::new(Some(1));
-    let second_connection_pool = connection_pool.clone();
-    let ticker_api = TickerApi::new(second_connection_pool, ErrorTickerApi);
-    connection_pool
-        .access_storage()
-        .await
-        .unwrap()
-        .tokens_schema()
-        .update_historical_ticker_price(
-            TokenId(1),
-            TokenPrice {
-                usd_price: big_decimal_to_ratio(&BigDecimal::from(10)).unwrap(),
-                last_updated: chrono::offset::Utc::now(),
-            },
-        )
-        .await
-        .unwrap();
+
+    // Subsidies for other tokens and fee types don't apply.
+    let other_token = subsidy(Some(TokenId(2)), None);
+    let other_fee_type = subsidy(None, Some(OutputFeeType::Transfer));
+    assert_eq!(
+        get_gas_fee(vec![other_token, other_fee_type], TokenId(0)),
+        standard_fee
+    );
+
+    // Disabled, expired and not yet started subsidies don't apply.
+    let disabled = FeeSubsidy {
+        enabled: false,
+        ..subsidy(None, None)
+    };
+    let expired = FeeSubsidy {
+        ends_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+        ..subsidy(None, None)
+    };
+    let not_started = FeeSubsidy {
+        starts_at: Utc::now() + chrono::Duration::minutes(1),
+        ..subsidy(None, None)
+    };
+    assert_eq!(
+        get_gas_fee(vec![disabled, expired, not_started], TokenId(0)),
+        standard_fee
+    );
+
+    // Tokens excluded from the subsidies pay the standard fee.
+    let not_subsidized_fee = get_gas_fee(Vec::new(), TokenId(1));
+    assert_eq!(
+        get_gas_fee(vec![subsidy(None, None)], TokenId(1)),
+        not_subsidized_fee
+    );
+}
+
+/// Checks that the risk factors set in the config are used unless set in the database.
+#[test]
+fn test_config_risk_factors() {
     let config = get_test_ticker_config();
-    let mut ticker = FeeTicker::new(
-        ticker_api,
-        MockTickerInfo,
-        mpsc::channel(1).1,
-        config,
-        validator,
+    let mut ticker = test_ticker(config.clone());
+
+    let hex = TestToken::hex();
+    let hex_address = Address::from_str("34083bbd70d394110487feaa087da875a54624ec").unwrap();
+    let config_risk_factor = Ratio::from_integer(BigUint::from(5u32));
+    let mut get_risk_factor = |tokens_risk_factors: HashMap<TokenId, Ratio<BigUint>>| {
+        ticker.config = Arc::new(TickerConfig {
+            tokens_risk_factors,
+            config_risk_factors: vec![(hex_address, config_risk_factor.clone())]
+                .into_iter()
+                .collect(),
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Withdraw,
+            hex.id.into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        fee.breakdown.expect("fee breakdown is missing").risk_factor
+    };
+
+    // The risk factor stored in the database takes precedence.
+    let stored_risk_factors = config.get().tokens_risk_factors.clone();
+    assert_eq!(get_risk_factor(stored_risk_factors), hex.risk_factor());
+    assert_eq!(get_risk_factor(HashMap::new()), config_risk_factor);
+}
+
+/// Checks that the subsidized fee reports the part of the gas cost covered by the subsidy.
+#[test]
+fn test_fee_subsidy_amount() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let mut get_fee = |subsidies: Vec<FeeSubsidy>, tx_type: TxFeeTypes| -> Fee {
+        ticker.config = Arc::new(TickerConfig {
+            subsidies,
+            ..TickerConfig::clone(&config.get())
+        });
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            tx_type,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token")
+    };
+    let subsidy = FeeSubsidy {
+        id: 1,
+        token: None,
+        fee_type: Some(OutputFeeType::Withdraw),
+        starts_at: Utc::now() - chrono::Duration::hours(1),
+        ends_at: None,
+        enabled: true,
+    };
+
+    let standard_fee = get_fee(Vec::new(), TxFeeTypes::Withdraw);
+    assert_eq!(standard_fee.subsidy, BigUint::zero());
+    let subsidized_fee = get_fee(vec![subsidy.clone()], TxFeeTypes::Withdraw);
+    assert!(subsidized_fee.subsidy > BigUint::zero());
+
+    // The subsidy covers the difference between the standard and the subsidized gas cost.
+    let covered_gas_fee = &subsidized_fee.gas_fee + &subsidized_fee.subsidy;
+    assert!(covered_gas_fee <= standard_fee.gas_fee);
+    assert!(standard_fee.gas_fee - covered_gas_fee <= BigUint::from(1u32));
+
+    // Operations the subsidy doesn't apply to report no subsidy.
+    let transfer_fee = get_fee(vec![subsidy], TxFeeTypes::Transfer);
+    assert_eq!(transfer_fee.subsidy, BigUint::zero());
+}
+
+/// Checks that the batch fee reports the subsidies of the subsidized transactions of the batch.
+#[test]
+fn test_batch_fee_subsidy_amount() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let mut get_batch_fee = |subsidies: Vec<FeeSubsidy>, txs: Vec<TxFeeTypes>| -> BatchFee {
+        ticker.config = Arc::new(TickerConfig {
+            subsidies,
+            ..TickerConfig::clone(&config.get())
+        });
+        let txs = txs
+            .into_iter()
+            .map(|tx_type| (tx_type, Address::default()))
+            .collect();
+        block_on(ticker.get_batch_from_ticker_in_wei(TokenId(0).into(), txs))
+            .expect("failed to get batch fee in token")
+    };
+    let subsidy = FeeSubsidy {
+        id: 1,
+        token: None,
+        fee_type: Some(OutputFeeType::Withdraw),
+        starts_at: Utc::now() - chrono::Duration::hours(1),
+        ends_at: None,
+        enabled: true,
+    };
+    let batch = vec![TxFeeTypes::Withdraw, TxFeeTypes::Transfer];
+
+    assert_eq!(
+        get_batch_fee(Vec::new(), batch.clone()).subsidy,
+        BigUint::zero()
     );
+    let subsidized_fee = get_batch_fee(vec![subsidy.clone()], batch);
+    assert!(subsidized_fee.subsidy > BigUint::zero());
 
-    ticker
-        .get_fee_from_ticker_in_wei(
-            TxFeeTypes::FastWithdraw,
-            TokenId(1).into(),
+    // Batches without the subsidized operations report no subsidy.
+    let transfers_fee = get_batch_fee(vec![subsidy], vec![TxFeeTypes::Transfer; 2]);
+    assert_eq!(transfers_fee.subsidy, BigUint::zero());
+}
+
+/// Checks that the fee discounts are applied only to the fees quoted for their accounts.
+#[test]
+fn test_fee_discounts() {
+    let market_maker = Address::repeat_byte(1);
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+    let discounts = vec![
+        FeeDiscount {
+            id: 1,
+            address: market_maker,
+            fee_type: Some(OutputFeeType::Withdraw),
+            discount: Ratio::new(BigUint::from(1u32), BigUint::from(2u32)),
+        },
+        FeeDiscount {
+            id: 2,
+            address: market_maker,
+            fee_type: None,
+            discount: Ratio::new(BigUint::from(1u32), BigUint::from(10u32)),
+        },
+    ];
+    ticker.config = Arc::new(TickerConfig {
+        fee_discounts: vec![(market_maker, discounts)].into_iter().collect(),
+        ..TickerConfig::clone(&config.get())
+    });
+
+    let mut get_fee = |tx_type: TxFeeTypes, sender: Option<Address>| -> BigUint {
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            tx_type,
+            TokenId(0).into(),
             Address::default(),
-        )
-        .await
+            sender,
+        ))
+        .expect("failed to get fee in token")
+        .exact_total_fee
+    };
+
+    let withdraw_fee = get_fee(TxFeeTypes::Withdraw, None);
+    let fast_withdraw_fee = get_fee(TxFeeTypes::FastWithdraw, None);
+    assert_eq!(
+        get_fee(TxFeeTypes::Withdraw, Some(Address::repeat_byte(2))),
+        withdraw_fee
+    );
+
+    // The largest of the matching discounts is applied.
+    let discounted_withdraw_fee = get_fee(TxFeeTypes::Withdraw, Some(market_maker));
+    assert!(discounted_withdraw_fee <= withdraw_fee / 2u32 + 1u32);
+    let discounted_fast_withdraw_fee = get_fee(TxFeeTypes::FastWithdraw, Some(market_maker));
+    assert!(discounted_fast_withdraw_fee < fast_withdraw_fee);
+    assert!(discounted_fast_withdraw_fee > fast_withdraw_fee / 2u32);
+}
+
+/// Checks that the waived fees are zero and the waived transactions don't contribute to the batch fee.
+#[test]
+fn test_fee_waivers() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+    let change_pubkey_type = OutputFeeType::ChangePubKey(
+        ChangePubKeyFeeTypeArg::ContractsV4Version(ChangePubKeyFeeType::ECDSA),
+    );
+    let change_pubkey = TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
+        ChangePubKeyFeeType::ECDSA,
+    ));
+    let waivers = vec![
+        // Signing key is not set for any account in the mock, so the waiver applies.
+        FeeWaiver {
+            id: 1,
+            fee_type: change_pubkey_type,
+            token: None,
+            unset_signing_key_only: true,
+            expires_at: None,
+        },
+        FeeWaiver {
+            id: 2,
+            fee_type: OutputFeeType::Withdraw,
+            token: Some(TokenId(2)),
+            unset_signing_key_only: false,
+            expires_at: None,
+        },
+        FeeWaiver {
+            id: 3,
+            fee_type: OutputFeeType::Transfer,
+            token: None,
+            unset_signing_key_only: false,
+            expires_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+        },
+    ];
+    ticker.config = Arc::new(TickerConfig {
+        fee_waivers: waivers,
+        ..TickerConfig::clone(&config.get())
+    });
+
+    let mut get_fee = |tx_type: TxFeeTypes, token: TokenId| -> Fee {
+        block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.into(), Address::default(), None))
+            .expect("failed to get fee in token")
+    };
+
+    let change_pubkey_fee = get_fee(change_pubkey, TokenId(0));
+    assert!(change_pubkey_fee.waived);
+    assert!(change_pubkey_fee.total_fee.is_zero());
+
+    // The waiver is limited to the token.
+    let waived_withdraw_fee = get_fee(TxFeeTypes::Withdraw, TokenId(2));
+    assert!(waived_withdraw_fee.waived);
+    assert!(waived_withdraw_fee.total_fee.is_zero());
+    let withdraw_fee = get_fee(TxFeeTypes::Withdraw, TokenId(0));
+    assert!(!withdraw_fee.waived);
+    assert!(!withdraw_fee.total_fee.is_zero());
+
+    // Expired waiver is not applied.
+    let transfer_fee = get_fee(TxFeeTypes::Transfer, TokenId(0));
+    assert!(!transfer_fee.waived);
+
+    let mut get_batch_fee = |txs: Vec<TxFeeTypes>| -> BatchFee {
+        let txs = txs
+            .into_iter()
+            .map(|tx_type| (tx_type, Address::default()))
+            .collect();
+        block_on(ticker.get_batch_from_ticker_in_wei(TokenId(0).into(), txs))
+            .expect("failed to get batch fee")
+    };
+
+    let waived_batch_fee = get_batch_fee(vec![change_pubkey, change_pubkey]);
+    assert!(waived_batch_fee.waived);
+    assert!(waived_batch_fee.total_fee.is_zero());
+
+    let batch_fee = get_batch_fee(vec![change_pubkey, TxFeeTypes::Transfer]);
+    assert!(!batch_fee.waived);
+    assert_eq!(batch_fee.total_fee, transfer_fee.total_fee);
+}
+
+/// Checks that the transfer to the new account is charged for the `TransferToNew` operation,
+/// while the transfer to the existing one is charged for the cheaper `Transfer`.
+#[test]
+fn test_transfer_fee_by_recipient() {
+    let new_account = Address::repeat_byte(0x11);
+    let existing_account = Address::repeat_byte(0x22);
+    let mut ticker = FeeTicker::new(
+        MockApiProvider,
+        NewRecipientTickerInfo(new_account),
+        mpsc::channel(1).1,
+        get_test_ticker_config(),
+        test_validator(),
+    );
+
+    let mut get_fee = |recipient: Address| -> Fee {
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Transfer,
+            TokenId(0).into(),
+            recipient,
+            None,
+        ))
+        .expect("failed to get fee in token")
+    };
+
+    let transfer_to_new_fee = get_fee(new_account);
+    assert_eq!(transfer_to_new_fee.fee_type, OutputFeeType::TransferToNew);
+    let transfer_fee = get_fee(existing_account);
+    assert_eq!(transfer_fee.fee_type, OutputFeeType::Transfer);
+    assert!(transfer_fee.total_fee < transfer_to_new_fee.total_fee);
+
+    // The batch fee depends on the recipients of the transfers as well.
+    let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+        TokenId(0).into(),
+        vec![(TxFeeTypes::Transfer, existing_account)],
+    ))
+    .expect("failed to get batch fee");
+    assert_eq!(batch_fee.total_fee, transfer_fee.total_fee);
+}
+
+/// Checks that the gas cost overridden for the token is used instead of the standard one.
+#[test]
+fn test_token_gas_costs() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+    let standard_withdraw_cost =
+        config.get().gas_cost_tx.standard_cost[&OutputFeeType::Withdraw].clone();
+    let overridden_withdraw_cost = &standard_withdraw_cost * 3u32;
+    let token_gas_costs = vec![(
+        TokenId(2),
+        vec![(OutputFeeType::Withdraw, overridden_withdraw_cost.clone())]
+            .into_iter()
+            .collect(),
+    )]
+    .into_iter()
+    .collect();
+    ticker.config = Arc::new(TickerConfig {
+        token_gas_costs,
+        ..TickerConfig::clone(&config.get())
+    });
+
+    let mut get_fee = |tx_type: TxFeeTypes, token: TokenId| -> Fee {
+        block_on(ticker.get_fee_from_ticker_in_wei(tx_type, token.into(), Address::default(), None))
+            .expect("failed to get fee in token")
+    };
+
+    let withdraw_fee = get_fee(TxFeeTypes::Withdraw, TokenId(2));
+    assert_eq!(withdraw_fee.gas_tx_amount, overridden_withdraw_cost);
+
+    // The override is limited to the token and the fee type.
+    let withdraw_fee = get_fee(TxFeeTypes::Withdraw, TokenId(0));
+    assert_eq!(withdraw_fee.gas_tx_amount, standard_withdraw_cost);
+    let transfer_fee = get_fee(TxFeeTypes::Transfer, TokenId(2));
+    assert_eq!(
+        transfer_fee.gas_tx_amount,
+        config.get().gas_cost_tx.standard_cost[&OutputFeeType::Transfer]
+    );
+}
+
+/// Checks that the observed gas costs are used instead of the standard ones.
+#[test]
+fn test_observed_gas_costs() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+    let standard_transfer_cost =
+        config.get().gas_cost_tx.standard_cost[&OutputFeeType::Transfer].clone();
+    let observed_withdraw_cost = BigUint::from(70_000u32);
+    config.set_observed_gas_costs(
+        vec![(OutputFeeType::Withdraw, observed_withdraw_cost.clone())]
+            .into_iter()
+            .collect(),
+    );
+    ticker.config = config.get();
+
+    let mut get_fee = |tx_type: TxFeeTypes| -> Fee {
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            tx_type,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token")
+    };
+
+    let withdraw_fee = get_fee(TxFeeTypes::Withdraw);
+    assert_eq!(withdraw_fee.gas_tx_amount, observed_withdraw_cost);
+    // Fast withdrawals are scaled from the observed cost of the regular ones.
+    let fast_withdraw_fee = get_fee(TxFeeTypes::FastWithdraw);
+    assert_eq!(
+        fast_withdraw_fee.gas_tx_amount,
+        BigUint::from((70_000f64 * TEST_FAST_WITHDRAW_COEFF) as u64)
+    );
+    // Operations which are not observed keep the standard cost.
+    let transfer_fee = get_fee(TxFeeTypes::Transfer);
+    assert_eq!(transfer_fee.gas_tx_amount, standard_transfer_cost);
+
+    // Observed costs survive the update of the fast processing coefficient.
+    config
+        .update(TickerSettingsUpdate {
+            fast_processing_coeff: Some(TEST_FAST_WITHDRAW_COEFF * 2.0),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(
+        config.get().gas_cost_tx.standard_cost[&OutputFeeType::Withdraw],
+        observed_withdraw_cost
+    );
+}
+
+/// Checks that the dry run calculates the same fee as the quote and reports its inputs.
+#[test]
+fn test_fee_dry_run() {
+    let mut ticker = test_ticker(get_test_ticker_config());
+
+    let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Withdraw,
+        TokenId(0).into(),
+        Address::default(),
+        None,
+    ))
+    .expect("failed to get fee in token");
+    let dry_run = block_on(ticker.fee_dry_run(
+        TxFeeTypes::Withdraw,
+        TokenId(0).into(),
+        Address::default(),
+        None,
+    ))
+    .expect("failed to run the fee calculation");
+
+    assert_eq!(dry_run.fee.total_fee, fee.total_fee);
+    assert_eq!(dry_run.fee.breakdown, fee.breakdown);
+    assert_eq!(dry_run.token.decimals, 18);
+    assert_eq!(dry_run.op_chunks, BigUint::from(WithdrawOp::CHUNKS));
+    assert_eq!(dry_run.base_fee_wei, BigUint::from(10u32).pow(7u32));
+    assert!(dry_run.eth_quote.is_some());
+    assert_eq!(
+        dry_run
+            .token_quote
+            .expect("token quote is not reported")
+            .usd_price,
+        fee.breakdown.unwrap().token_price_usd
+    );
+    assert!(!dry_run.subsidized);
+    assert!(dry_run.discount.is_zero());
+    assert!(dry_run.pending_block_free_share.is_none());
+}
+
+#[test]
+fn test_minimum_fee() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let mut get_fees = |minimum_fees: Vec<(TokenId, MinimumFee)>, token: TokenId| {
+        ticker.config = Arc::new(TickerConfig {
+            minimum_fees: minimum_fees.into_iter().collect(),
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Transfer,
+            token.into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+            token.into(),
+            vec![(TxFeeTypes::Transfer, Address::default())],
+        ))
+        .expect("failed to get batch fee in token");
+        (fee.total_fee, batch_fee.total_fee)
+    };
+
+    let (fee, batch_fee) = get_fees(Vec::new(), TokenId(2));
+    assert_eq!(fee, batch_fee);
+
+    // The minimum fee below the calculated one has no effect.
+    let minimum_fee = MinimumFee::Token(BigUint::from(1u32));
+    assert_eq!(
+        get_fees(vec![(TokenId(2), minimum_fee)], TokenId(2)),
+        (fee.clone(), batch_fee)
+    );
+
+    let minimum_fee = MinimumFee::Token(&fee * 10u32);
+    let (raised_fee, raised_batch_fee) = get_fees(vec![(TokenId(2), minimum_fee)], TokenId(2));
+    assert_eq!(raised_fee, &fee * 10u32);
+    assert_eq!(raised_batch_fee, &fee * 10u32);
+    // The minimum fee of the other token is not applied.
+    let minimum_fee = MinimumFee::Token(&fee * 10u32);
+    assert_eq!(get_fees(vec![(TokenId(1), minimum_fee)], TokenId(2)).0, fee);
+
+    // The token costs 1 USD and has 6 decimals.
+    let minimum_fee = MinimumFee::Usd(Ratio::from_integer(BigUint::from(100u32)));
+    let (raised_fee, raised_batch_fee) = get_fees(vec![(TokenId(2), minimum_fee)], TokenId(2));
+    assert_eq!(raised_fee, BigUint::from(100_000_000u32));
+    assert_eq!(raised_batch_fee, BigUint::from(100_000_000u32));
+}
+
+#[test]
+fn test_maximum_fee() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let usd = |amount: &str| {
+        UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(amount).unwrap()
+    };
+    let mut get_fees = |maximum_fees: Vec<(OutputFeeType, Ratio<BigUint>)>| {
+        ticker.config = Arc::new(TickerConfig {
+            maximum_fees: maximum_fees.into_iter().collect(),
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Withdraw,
+            TokenId(2).into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+            TokenId(2).into(),
+            vec![
+                (TxFeeTypes::Withdraw, Address::default()),
+                (TxFeeTypes::Transfer, Address::default()),
+            ],
+        ))
+        .expect("failed to get batch fee in token");
+        (fee, batch_fee)
+    };
+
+    let (fee, batch_fee) = get_fees(Vec::new());
+    assert!(!fee.capped);
+    assert!(!batch_fee.capped);
+
+    // The maximum fee above the calculated one has no effect.
+    let (high_cap_fee, _) = get_fees(vec![(OutputFeeType::Withdraw, usd("1000000"))]);
+    assert!(!high_cap_fee.capped);
+    assert_eq!(high_cap_fee.total_fee, fee.total_fee);
+
+    // The token costs 1 USD and has 6 decimals.
+    let (capped_fee, uncapped_batch_fee) =
+        get_fees(vec![(OutputFeeType::Withdraw, usd("0.000001"))]);
+    assert!(capped_fee.capped);
+    assert_eq!(capped_fee.total_fee, BigUint::from(1u32));
+    // Transfer has no maximum fee, so the batch is not capped.
+    assert!(!uncapped_batch_fee.capped);
+    assert_eq!(uncapped_batch_fee.total_fee, batch_fee.total_fee);
+
+    let (_, capped_batch_fee) = get_fees(vec![
+        (OutputFeeType::Withdraw, usd("0.000001")),
+        (OutputFeeType::Transfer, usd("0.000002")),
+    ]);
+    assert!(capped_batch_fee.capped);
+    assert_eq!(capped_batch_fee.total_fee, BigUint::from(3u32));
+}
+
+/// Checks that the breakdown and the subsidy of the capped fee are scaled down along with the fee.
+#[test]
+fn test_capped_fee_breakdown() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let token = TestToken::cheap();
+    let subsidy = FeeSubsidy {
+        id: 1,
+        token: None,
+        fee_type: Some(OutputFeeType::Withdraw),
+        starts_at: Utc::now() - chrono::Duration::hours(1),
+        ends_at: None,
+        enabled: true,
+    };
+    let mut get_fees = |maximum_fee: Option<Ratio<BigUint>>| -> (Fee, BatchFee) {
+        ticker.config = Arc::new(TickerConfig {
+            subsidies: vec![subsidy.clone()],
+            maximum_fees: maximum_fee
+                .into_iter()
+                .map(|maximum_fee| (OutputFeeType::Withdraw, maximum_fee))
+                .collect(),
+            ..TickerConfig::clone(&config.get())
+        });
+        let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+            TxFeeTypes::Withdraw,
+            token.id.into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token");
+        let batch_fee = block_on(ticker.get_batch_from_ticker_in_wei(
+            token.id.into(),
+            vec![(TxFeeTypes::Withdraw, Address::default())],
+        ))
+        .expect("failed to get batch fee in token");
+        (fee, batch_fee)
+    };
+
+    let (fee, batch_fee) = get_fees(None);
+    assert!(!fee.capped);
+    assert!(fee.subsidy > BigUint::zero());
+    assert!(batch_fee.subsidy > BigUint::zero());
+
+    // The token costs 1 USD and has 6 decimals, so the fee is capped at a half of it.
+    let maximum_fee = &fee.exact_total_fee / BigUint::from(2u32);
+    let (capped_fee, capped_batch_fee) = get_fees(Some(Ratio::new(
+        maximum_fee.clone(),
+        BigUint::from(1_000_000u32),
+    )));
+    assert!(capped_fee.capped);
+    assert_eq!(capped_fee.exact_total_fee, maximum_fee);
+    assert_eq!(
+        &capped_fee.zkp_fee + &capped_fee.gas_fee,
+        capped_fee.exact_total_fee
+    );
+
+    let share = Ratio::new(maximum_fee, fee.exact_total_fee.clone());
+    let breakdown = fee.breakdown.expect("fee breakdown is missing");
+    let capped_breakdown = capped_fee.breakdown.expect("fee breakdown is missing");
+    assert_eq!(
+        capped_breakdown.zkp_cost_usd,
+        breakdown.zkp_cost_usd * &share
+    );
+    assert_eq!(
+        capped_breakdown.gas_cost_usd,
+        breakdown.gas_cost_usd * &share
+    );
+    assert_eq!(
+        capped_fee.subsidy,
+        (Ratio::from_integer(fee.subsidy) * &share)
+            .floor()
+            .to_integer()
+    );
+
+    assert!(capped_batch_fee.capped);
+    assert!(capped_batch_fee.subsidy < batch_fee.subsidy);
+}
+
+#[test]
+fn test_dynamic_fast_processing_coeff() {
+    let block_fullness = PendingBlockFullness::new(Duration::from_secs(60));
+    let mut ticker =
+        test_ticker(get_test_ticker_config()).with_block_fullness(Some(block_fullness.clone()));
+
+    let mut get_gas_tx_amount = |tx_type: TxFeeTypes| -> BigUint {
+        block_on(ticker.get_fee_from_ticker_in_wei(
+            tx_type,
+            TokenId(0).into(),
+            Address::default(),
+            None,
+        ))
+        .expect("failed to get fee in token")
+        .gas_tx_amount
+    };
+
+    // The static surcharge is applied until the fullness is sampled.
+    let withdraw_amount = get_gas_tx_amount(TxFeeTypes::Withdraw);
+    let fast_withdraw_amount = get_gas_tx_amount(TxFeeTypes::FastWithdraw);
+    assert!(fast_withdraw_amount > withdraw_amount);
+
+    block_fullness.set_chunks_left(30, 30);
+    assert_eq!(
+        get_gas_tx_amount(TxFeeTypes::FastWithdraw),
+        fast_withdraw_amount
+    );
+
+    block_fullness.set_chunks_left(15, 30);
+    let surcharge = &fast_withdraw_amount - &withdraw_amount;
+    assert_eq!(
+        get_gas_tx_amount(TxFeeTypes::FastWithdraw),
+        &withdraw_amount + (surcharge + 1u32) / 2u32
+    );
+
+    block_fullness.set_chunks_left(0, 30);
+    assert_eq!(get_gas_tx_amount(TxFeeTypes::FastWithdraw), withdraw_amount);
+    // Regular operations are not affected.
+    assert_eq!(get_gas_tx_amount(TxFeeTypes::Withdraw), withdraw_amount);
+}
+
+/// Checks that the batch transactions share the fixed block overhead.
+#[test]
+fn test_batch_fee_amortization() {
+    let config = get_test_ticker_config();
+    let mut ticker = test_ticker(config.clone());
+
+    let transfers = vec![(TxFeeTypes::Transfer, Address::default()); 10];
+    let mut get_batch_fee = |overhead_share: Ratio<BigUint>| -> BigUint {
+        ticker.config = Arc::new(TickerConfig {
+            batch_fee_model: BatchFeeModel::new(overhead_share),
+            ..TickerConfig::clone(&config.get())
+        });
+        block_on(ticker.get_batch_from_ticker_in_wei(TokenId(0).into(), transfers.clone()))
+            .expect("failed to get batched fee for token")
+            .exact_total_fee
+    };
+
+    let standalone_fee = get_batch_fee(Ratio::one());
+    let amortized_fee = get_batch_fee(Ratio::new(BigUint::from(1u32), BigUint::from(2u32)));
+    let zero_overhead_fee = get_batch_fee(Ratio::zero());
+    assert!(amortized_fee < standalone_fee);
+    assert!(zero_overhead_fee < amortized_fee);
+}
+
+/// Checks that the settings adjusted at runtime are picked up by the running ticker.
+/// Requests the fee of the transaction paid in ETH through the ticker requests channel.
+async fn request_fee(sender: &mut mpsc::Sender<TickerRequest>, tx_type: TxFeeTypes) -> Fee {
+    let (response, receiver) = oneshot::channel();
+    sender
+        .send(TickerRequest::GetTxFee {
+            tx_type,
+            address: Address::default(),
+            sender: None,
+            token: TokenId(0).into(),
+            response,
+            span: tracing::Span::current(),
+        })
+        .await
+        .unwrap();
+    receiver.await.unwrap().expect("failed to get fee in token")
+}
+
+#[tokio::test]
+async fn test_runtime_settings_update() {
+    let config = get_test_ticker_config();
+    let (mut sender, receiver) = mpsc::channel(1);
+    let ticker = FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo,
+        receiver,
+        config.clone(),
+        test_validator(),
+    );
+
+    let requests = async move {
+        let fee = request_fee(&mut sender, TxFeeTypes::FastWithdraw).await;
+
+        let settings = config
+            .update(TickerSettingsUpdate {
+                fast_processing_coeff: Some(TEST_FAST_WITHDRAW_COEFF * 2.0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            settings.fast_processing_coeff,
+            TEST_FAST_WITHDRAW_COEFF * 2.0
+        );
+        let updated_fee = request_fee(&mut sender, TxFeeTypes::FastWithdraw).await;
+        assert!(updated_fee.gas_fee > fee.gas_fee);
+
+        let fee_rounding = vec![TokenFeeRounding {
+            token: TokenId(0),
+            rounding: FeeRoundingPolicy {
+                significant_digits: Some(1),
+                ..Default::default()
+            },
+        }];
+        let settings = config
+            .update(TickerSettingsUpdate {
+                fee_rounding: Some(fee_rounding.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(settings.fee_rounding, fee_rounding);
+        let rounded_fee = request_fee(&mut sender, TxFeeTypes::FastWithdraw).await;
+        assert!(rounded_fee.total_fee >= updated_fee.total_fee);
+        // The only significant digit is left.
+        let total_fee = rounded_fee.total_fee.to_str_radix(10);
+        assert!(total_fee[1..].chars().all(|digit| digit == '0'));
+
+        // Invalid update is rejected as a whole.
+        let invalid_update = TickerSettingsUpdate {
+            fast_processing_coeff: Some(0.5),
+            not_subsidized_tokens: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert!(config.update(invalid_update).is_err());
+        let invalid_update = TickerSettingsUpdate {
+            fee_rounding: Some(vec![TokenFeeRounding {
+                token: TokenId(0),
+                rounding: FeeRoundingPolicy {
+                    significant_digits: Some(0),
+                    ..Default::default()
+                },
+            }]),
+            ..Default::default()
+        };
+        assert!(config.update(invalid_update).is_err());
+        let invalid_update = TickerSettingsUpdate {
+            minimum_fees: Some(vec![TokenMinimumFee {
+                token: TokenId(0),
+                minimum_fee: MinimumFee::Token(BigUint::zero()),
+            }]),
+            ..Default::default()
+        };
+        assert!(config.update(invalid_update).is_err());
+        assert_eq!(config.settings(), settings);
+
+        let minimum_fees = vec![TokenMinimumFee {
+            token: TokenId(0),
+            minimum_fee: MinimumFee::Token(&rounded_fee.total_fee * 10u32),
+        }];
+        let settings = config
+            .update(TickerSettingsUpdate {
+                minimum_fees: Some(minimum_fees.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(settings.minimum_fees, minimum_fees);
+        let minimum_fee = request_fee(&mut sender, TxFeeTypes::FastWithdraw).await;
+        assert_eq!(minimum_fee.total_fee, &rounded_fee.total_fee * 10u32);
+
+        let fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        let settings = config
+            .update(TickerSettingsUpdate {
+                zkp_cost_chunk_usd: Some(BigDecimal::from_str("0.002").unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            settings.zkp_cost_chunk_usd,
+            Ratio::new(BigUint::from(1u32), BigUint::from(500u32))
+        );
+        let updated_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        // Fees are rounded up, so the doubled cost may differ from the doubled fee by one unit.
+        assert!(updated_fee.zkp_fee > &fee.zkp_fee * 2u32 - 2u32);
+        assert!(updated_fee.zkp_fee <= &fee.zkp_fee * 2u32);
+        let invalid_update = TickerSettingsUpdate {
+            zkp_cost_chunk_usd: Some(BigDecimal::from(0)),
+            ..Default::default()
+        };
+        assert!(config.update(invalid_update).is_err());
+    };
+    // The ticker stops once the requests sender is dropped.
+    futures::join!(ticker.run(), requests);
+}
+
+#[tokio::test]
+async fn test_fee_cache() {
+    let api = CountingApiProvider::new();
+    let config = get_test_ticker_config();
+    let (mut sender, receiver) = mpsc::channel(1);
+    let ticker = FeeTicker::new(
+        api.clone(),
+        MockTickerInfo,
+        receiver,
+        config.clone(),
+        test_validator(),
+    )
+    .with_fee_cache_ttl(Some(std::time::Duration::from_secs(60)));
+
+    let requests = async move {
+        let fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        let quotes = api.quotes();
+        assert!(quotes > 0);
+
+        // The identical request is served from the cache.
+        let cached_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert_eq!(cached_fee.total_fee, fee.total_fee);
+        assert_eq!(api.quotes(), quotes);
+
+        // The fee of the other operation is calculated.
+        request_fee(&mut sender, TxFeeTypes::Withdraw).await;
+        assert!(api.quotes() > quotes);
+
+        // The gas price change invalidates the cache.
+        let quotes = api.quotes();
+        api.gas_price.store(2 * 10u64.pow(7), Ordering::SeqCst);
+        let updated_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert!(api.quotes() > quotes);
+        assert!(updated_fee.gas_fee > fee.gas_fee);
+
+        // So does the settings update.
+        let quotes = api.quotes();
+        config
+            .update(TickerSettingsUpdate {
+                zkp_cost_chunk_usd: Some(BigDecimal::from_str("0.002").unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+        let updated_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert!(api.quotes() > quotes);
+        assert!(updated_fee.zkp_fee > fee.zkp_fee);
+    };
+    // The ticker stops once the requests sender is dropped.
+    futures::join!(ticker.run(), requests);
+}
+
+/// Checks that the frozen fees are calculated from the fixed inputs until the freeze is lifted.
+#[tokio::test]
+async fn test_fee_freeze() {
+    let api = CountingApiProvider::new();
+    let config = get_test_ticker_config();
+    let (mut sender, receiver) = mpsc::channel(1);
+    let ticker = FeeTicker::new(
+        api.clone(),
+        MockTickerInfo,
+        receiver,
+        config.clone(),
+        test_validator(),
+    );
+
+    let requests = async move {
+        let fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+
+        let settings = config.freeze(FeeFreezeRequest::default().into_freeze().unwrap());
+        assert!(settings.fee_freeze.is_some());
+        let frozen_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert_eq!(frozen_fee.total_fee, fee.total_fee);
+
+        // Neither the prices nor the gas price are requested again.
+        let quotes = api.quotes();
+        api.gas_price.store(2 * 10u64.pow(7), Ordering::SeqCst);
+        let frozen_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert_eq!(frozen_fee.total_fee, fee.total_fee);
+        assert_eq!(api.quotes(), quotes);
+        let report = config.settings().fee_freeze.unwrap();
+        assert_eq!(
+            report.base_fee_wei,
+            Some(BigUint::from(10u64.pow(7)).into())
+        );
+
+        // The pinned gas price is used instead of the current one.
+        let request = FeeFreezeRequest {
+            base_fee_wei: Some(BigUint::from(3 * 10u64.pow(7)).into()),
+            ..Default::default()
+        };
+        config.freeze(request.into_freeze().unwrap());
+        let pinned_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert!(pinned_fee.gas_fee > fee.gas_fee);
+
+        assert!(config.unfreeze());
+        assert!(!config.unfreeze());
+        let updated_fee = request_fee(&mut sender, TxFeeTypes::Transfer).await;
+        assert!(updated_fee.gas_fee > fee.gas_fee);
+        assert!(updated_fee.gas_fee < pinned_fee.gas_fee);
+    };
+    // The ticker stops once the requests sender is dropped.
+    futures::join!(ticker.run(), requests);
+}
+
+/// Checks that the EIP-1559 fees are preferred over the legacy gas price,
+/// and only the base fee is scaled for the risk.
+#[tokio::test]
+async fn test_eip1559_gas_price() {
+    let legacy_storage = TickerInMemoryStorage::new().with_average_gas_price(50.into());
+    let ticker_api = TickerApi::with_storage(legacy_storage.clone(), ErrorTickerApi);
+    let gas_price = ticker_api.get_gas_price_wei().await.unwrap();
+    assert_eq!(gas_price, GasPriceWei::legacy(BigUint::from(50u32)));
+
+    let storage = legacy_storage.with_eip1559_fees(100.into(), 2.into());
+    let ticker_api = TickerApi::with_storage(storage, ErrorTickerApi);
+    let gas_price = ticker_api.get_gas_price_wei().await.unwrap();
+    assert_eq!(gas_price.base_fee, BigUint::from(100u32));
+    assert_eq!(gas_price.priority_fee, BigUint::from(2u32));
+    assert_eq!(gas_price.effective_price(), BigUint::from(102u32));
+
+    let scaled_price =
+        FeeTicker::<MockApiProvider, MockTickerInfo, FakeTokenWatcher>::risk_gas_price_estimate(
+            &gas_price,
+        );
+    assert_eq!(scaled_price, BigUint::from(100u32 * 130 / 100 + 2));
+}
+
+/// Checks that the median of the sampled gas prices is preferred over the instantaneous one.
+#[tokio::test]
+async fn test_gas_price_window() {
+    let storage = TickerInMemoryStorage::new().with_average_gas_price(1000.into());
+    let gas_price_window = Arc::new(tokio::sync::Mutex::new(GasPriceWindow::new(
+        3,
+        std::time::Duration::from_secs(300),
+    )));
+    let ticker_api = TickerApi::with_storage(storage, ErrorTickerApi)
+        .with_gas_price_window(gas_price_window.clone());
+
+    // The window is empty, so the instantaneous gas price is used.
+    let gas_price = ticker_api.get_gas_price_wei().await.unwrap();
+    assert_eq!(gas_price, GasPriceWei::legacy(BigUint::from(1000u32)));
+
+    for sample in &[100u32, 10_000, 200] {
+        gas_price_window
+            .lock()
+            .await
+            .add_sample(GasPriceWei::legacy(BigUint::from(*sample)));
+    }
+    let gas_price = ticker_api.get_gas_price_wei().await.unwrap();
+    assert_eq!(gas_price, GasPriceWei::legacy(BigUint::from(200u32)));
+}
+
+#[actix_rt::test]
+#[ignore]
+// It's ignore because we can't initialize coingecko in current way with block
+async fn test_error_coingecko_api() {
+    let (address, handler) = run_server();
+    let client = reqwest::ClientBuilder::new()
+        .timeout(CONNECTION_TIMEOUT)
+        .connect_timeout(CONNECTION_TIMEOUT)
+        .build()
+        .expect("Failed to build reqwest::Client");
+    let coingecko = CoinGeckoAPI::new(client, address.parse().unwrap(), None).unwrap();
+    let ticker_api =
+        TickerApi::with_storage(ticker_storage_with_historical_prices().await, coingecko);
+
+    let config = get_test_ticker_config();
+    let mut ticker = FeeTicker::new(
+        ticker_api,
+        MockTickerInfo,
+        mpsc::channel(1).1,
+        config,
+        test_validator(),
+    );
+    for _ in 0..1000 {
+        ticker
+            .get_fee_from_ticker_in_wei(
+                TxFeeTypes::FastWithdraw,
+                TokenId(1).into(),
+                Address::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        ticker
+            .get_token_price(TokenId(1).into(), TokenPriceRequestType::USDForOneWei)
+            .await
+            .unwrap();
+    }
+    handler.abort();
+}
+
+#[tokio::test]
+async fn test_error_api() {
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        ErrorTickerApi,
+    );
+    let config = get_test_ticker_config();
+    let mut ticker = FeeTicker::new(
+        ticker_api,
+        MockTickerInfo,
+        mpsc::channel(1).1,
+        config,
+        test_validator(),
+    );
+
+    ticker
+        .get_fee_from_ticker_in_wei(
+            TxFeeTypes::FastWithdraw,
+            TokenId(1).into(),
+            Address::default(),
+            None,
+        )
+        .await
         .unwrap();
     ticker
         .get_token_price(TokenId(1).into(), TokenPriceRequestType::USDForOneWei)
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn test_price_observations_stored() {
+    let storage = ticker_storage_with_historical_prices().await;
+    let ticker_api =
+        TickerApi::with_storage(storage.clone(), FixedPriceTickerApi).with_price_source("test");
+
+    ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    // The second request is served from the cache, so no observation is stored.
+    ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+
+    let history = storage.price_history().await;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, TokenId(1));
+    assert_eq!(history[0].1, "test");
+    assert_eq!(history[0].2.usd_price, Ratio::from_integer(10u32.into()));
+}
+
+#[tokio::test]
+async fn test_price_deviation_check() {
+    let max_price_deviation = Some(Ratio::new(1u32.into(), 2u32.into()));
+
+    // The price within the allowed deviation is accepted.
+    let storage = ticker_storage_with_historical_prices().await;
+    let ticker_api = TickerApi::with_storage(storage.clone(), FixedPriceTickerApi)
+        .with_max_price_deviation(max_price_deviation.clone());
+    let price = ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    assert_eq!(price.usd_price, Ratio::from_integer(10u32.into()));
+    assert_eq!(storage.price_history().await.len(), 1);
+
+    // The glitched price is rejected in favor of the accepted one, and isn't stored.
+    let storage = ticker_storage_with_historical_prices().await;
+    let ticker_api = TickerApi::with_storage(storage.clone(), GlitchedPriceTickerApi)
+        .with_max_price_deviation(max_price_deviation);
+    let price = ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    assert_eq!(price.usd_price, Ratio::from_integer(10u32.into()));
+    assert!(storage.price_history().await.is_empty());
+    let historical_price = storage
+        .get_historical_ticker_price(TokenId(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        historical_price.usd_price,
+        Ratio::from_integer(10u32.into())
+    );
+
+    // The check is disabled.
+    let storage = ticker_storage_with_historical_prices().await;
+    let ticker_api = TickerApi::with_storage(storage, GlitchedPriceTickerApi);
+    let price = ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    assert_eq!(price.usd_price, Ratio::from_integer(1000u32.into()));
+}
+
+#[tokio::test]
+async fn test_sourced_price_quote() {
+    // The price received from the API is fresh, both right away and from the cache.
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        FixedPriceTickerApi,
+    )
+    .with_price_source("test");
+    for _ in 0..2 {
+        let quote = ticker_api
+            .get_sourced_quote(TokenId(1).into())
+            .await
+            .unwrap();
+        assert_eq!(quote.source, "test");
+        assert_eq!(quote.confidence, PriceConfidence::Fresh);
+        assert_eq!(quote.price.usd_price, Ratio::from_integer(10u32.into()));
+    }
+
+    // The last known price is reported as the fallback while the API is not available.
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        ErrorTickerApi,
+    )
+    .with_price_source("test");
+    let quote = ticker_api
+        .get_sourced_quote(TokenId(1).into())
+        .await
+        .unwrap();
+    assert_eq!(quote.source, "test");
+    assert_eq!(quote.confidence, PriceConfidence::Fallback);
+}
+
+#[tokio::test]
+async fn test_stale_price_fallback() {
+    let max_price_staleness = Some(chrono::Duration::hours(1));
+
+    // The fresh historical price is used while the API is not available.
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        ErrorTickerApi,
+    )
+    .with_max_price_staleness(max_price_staleness);
+    let price = ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    assert_eq!(price.usd_price, Ratio::from_integer(10u32.into()));
+
+    let storage = ticker_storage_with_historical_prices().await;
+    storage
+        .update_historical_ticker_price(
+            TokenId(1),
+            TokenPrice {
+                usd_price: Ratio::from_integer(10u32.into()),
+                last_updated: Utc::now() - chrono::Duration::hours(2),
+            },
+        )
+        .await
+        .unwrap();
+
+    // The stale price is rejected.
+    let ticker_api = TickerApi::with_storage(storage.clone(), ErrorTickerApi)
+        .with_max_price_staleness(max_price_staleness);
+    ticker_api
+        .get_last_quote(TokenId(1).into())
+        .await
+        .unwrap_err();
+
+    // The staleness check is disabled.
+    let ticker_api = TickerApi::with_storage(storage, ErrorTickerApi);
+    let price = ticker_api.get_last_quote(TokenId(1).into()).await.unwrap();
+    assert_eq!(price.usd_price, Ratio::from_integer(10u32.into()));
+}
+
+#[tokio::test]
+async fn test_fallback_price_sources() {
+    let failed = AgedPriceTickerApi {
+        usd_price: 10,
+        age: None,
+    };
+    let stale = AgedPriceTickerApi {
+        usd_price: 11,
+        age: Some(chrono::Duration::hours(2)),
+    };
+    let fresh = AgedPriceTickerApi {
+        usd_price: 12,
+        age: Some(chrono::Duration::zero()),
+    };
+
+    // The sources are tried in order until the fresh price is received.
+    let storage = ticker_storage_with_historical_prices().await;
+    let ticker_api = TickerApi::with_storage(storage.clone(), failed.clone())
+        .with_price_source("failed")
+        .with_fallback_price_sources(vec![
+            ("stale".to_string(), stale.clone()),
+            ("fresh".to_string(), fresh),
+            ("unused".to_string(), failed.clone()),
+        ])
+        .with_max_price_staleness(Some(chrono::Duration::hours(1)));
+    for _ in 0..2 {
+        let quote = ticker_api
+            .get_sourced_quote(TokenId(1).into())
+            .await
+            .unwrap();
+        assert_eq!(quote.source, "fresh");
+        assert_eq!(quote.confidence, PriceConfidence::Fresh);
+        assert_eq!(quote.price.usd_price, Ratio::from_integer(12u32.into()));
+    }
+    let history = storage.price_history().await;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].1, "fresh");
+
+    // The last known price is used if all the sources fail.
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        failed.clone(),
+    )
+    .with_price_source("failed")
+    .with_fallback_price_sources(vec![("stale".to_string(), stale.clone())])
+    .with_max_price_staleness(Some(chrono::Duration::hours(1)));
+    let quote = ticker_api
+        .get_sourced_quote(TokenId(1).into())
+        .await
+        .unwrap();
+    assert_eq!(quote.source, "failed");
+    assert_eq!(quote.confidence, PriceConfidence::Fallback);
+    assert_eq!(quote.price.usd_price, Ratio::from_integer(10u32.into()));
+
+    // Stale prices are accepted if the staleness check is disabled.
+    let ticker_api = TickerApi::with_storage(ticker_storage_with_historical_prices().await, failed)
+        .with_price_source("failed")
+        .with_fallback_price_sources(vec![("stale".to_string(), stale)]);
+    let quote = ticker_api
+        .get_sourced_quote(TokenId(1).into())
+        .await
+        .unwrap();
+    assert_eq!(quote.source, "stale");
+    assert_eq!(quote.price.usd_price, Ratio::from_integer(11u32.into()));
+}
+
+#[tokio::test]
+async fn test_median_price() {
+    let price_api = |usd_price| AgedPriceTickerApi {
+        usd_price,
+        age: Some(chrono::Duration::zero()),
+    };
+    let ticker_api = |storage, max_source_price_deviation| {
+        TickerApi::with_storage(storage, price_api(10))
+            .with_price_source("first")
+            .with_fallback_price_sources(vec![
+                ("second".to_string(), price_api(12)),
+                ("glitched".to_string(), price_api(1000)),
+                (
+                    "failed".to_string(),
+                    AgedPriceTickerApi {
+                        usd_price: 1,
+                        age: None,
+                    },
+                ),
+            ])
+            .with_price_aggregation(TokenPriceAggregation::Median)
+            .with_max_source_price_deviation(max_source_price_deviation)
+    };
+
+    // The glitched price deviates from the median of 12 too much, so the median
+    // of the rest is used.
+    let storage = ticker_storage_with_historical_prices().await;
+    let quote = ticker_api(storage, Some(Ratio::new(1u32.into(), 10u32.into())))
+        .get_sourced_quote(TokenId(1).into())
+        .await
+        .unwrap();
+    assert_eq!(quote.source, "median");
+    assert_eq!(quote.confidence, PriceConfidence::Fresh);
+    assert_eq!(quote.price.usd_price, Ratio::from_integer(11u32.into()));
+
+    // The deviation check is disabled.
+    let storage = ticker_storage_with_historical_prices().await;
+    let quote = ticker_api(storage, None)
+        .get_sourced_quote(TokenId(1).into())
+        .await
+        .unwrap();
+    assert_eq!(quote.price.usd_price, Ratio::from_integer(12u32.into()));
+}
+
+#[tokio::test]
+async fn test_token_price_batch() {
+    let ticker_api = TickerApi::with_storage(
+        ticker_storage_with_historical_prices().await,
+        ErrorTickerApi,
+    );
+    let ticker = FeeTicker::new(
+        ticker_api,
+        MockTickerInfo,
+        mpsc::channel(1).1,
+        get_test_ticker_config(),
+        test_validator(),
+    );
+
+    let prices = ticker
+        .get_token_prices(
+            vec![
+                TokenId(0).into(),
+                TokenLike::Symbol("DAI".to_string()),
+                TokenId(5).into(),
+                TokenId(0).into(),
+            ],
+            TokenPriceRequestType::USDForOneToken,
+        )
+        .await;
+    let price = Some(BigDecimal::from(10));
+    assert_eq!(prices, vec![price.clone(), price.clone(), None, price]);
+}
+
+#[tokio::test]
+async fn test_request_timeout() {
+    let api = HangingApiProvider::default();
+    api.hang.store(true, Ordering::SeqCst);
+    let (mut sender, receiver) = mpsc::channel(1);
+    let ticker = FeeTicker::new(
+        api.clone(),
+        MockTickerInfo,
+        receiver,
+        get_test_ticker_config(),
+        test_validator(),
+    )
+    .with_request_timeout(Some(Duration::from_millis(100)));
+
+    let requests = async move {
+        let (response, receiver) = oneshot::channel();
+        sender
+            .send(TickerRequest::GetTxFee {
+                tx_type: TxFeeTypes::Transfer,
+                address: Address::default(),
+                sender: None,
+                token: TokenId(0).into(),
+                response,
+                span: tracing::Span::current(),
+            })
+            .await
+            .unwrap();
+        let error = receiver.await.unwrap().unwrap_err();
+        assert!(error.is::<TickerRequestTimeout>());
+
+        // The ticker keeps handling the following requests.
+        api.hang.store(false, Ordering::SeqCst);
+        request_fee(&mut sender, TxFeeTypes::Transfer).await;
+    };
+    // The ticker stops once the requests sender is dropped.
+    futures::join!(ticker.run(), requests);
+}
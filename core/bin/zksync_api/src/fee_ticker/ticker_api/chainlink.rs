@@ -0,0 +1,177 @@
+//! On-chain Chainlink token price feeds.
+//!
+//! Prices of the major tokens may be read from the Chainlink USD price feeds instead of the
+//! third-party HTTP APIs, so they don't depend on the availability of the latter and are hard
+//! to manipulate. Every token is priced by its own aggregator contract, the tokens without
+//! the feeds are priced by the fallback API. Stale answers are rejected, so the last accepted
+//! price is used until the feed is updated.
+//!
+//! The feed is updated only once the price deviates from the reported one by the threshold
+//! or the heartbeat passes, so the answer not older than `max_age` is the price as of the moment
+//! it's read, and it's reported so.
+
+// Built-in deps
+use std::collections::HashMap;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use num::{rational::Ratio, traits::Pow, BigUint};
+use web3::contract::{tokens::Detokenize, Options};
+// Workspace deps
+use zksync_config::configs::ticker::ChainlinkFeedConfig;
+use zksync_contracts::chainlink_aggregator_contract;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, TokenPrice, U256};
+// Local deps
+use super::TokenPriceAPI;
+
+/// Chainlink price feeds with the API pricing the tokens without the feeds.
+#[derive(Debug, Clone)]
+pub struct ChainlinkAPI<F> {
+    client: EthereumGateway,
+    contract: ethabi::Contract,
+    /// Feed addresses by the uppercase token symbols.
+    feeds: HashMap<String, Address>,
+    /// Answers updated earlier than this are considered stale.
+    max_age: chrono::Duration,
+    fallback: F,
+}
+
+impl<F: TokenPriceAPI> ChainlinkAPI<F> {
+    pub fn new(
+        client: EthereumGateway,
+        feeds: &[ChainlinkFeedConfig],
+        max_age: chrono::Duration,
+        fallback: F,
+    ) -> Self {
+        let feeds = feeds
+            .iter()
+            .map(|feed| (feed.token.to_uppercase(), feed.feed))
+            .collect();
+        Self {
+            client,
+            contract: chainlink_aggregator_contract(),
+            feeds,
+            max_age,
+            fallback,
+        }
+    }
+
+    async fn call<R: Detokenize + Unpin>(
+        &self,
+        feed: Address,
+        function: &str,
+    ) -> anyhow::Result<R> {
+        self.client
+            .call_contract_function(
+                function,
+                (),
+                None,
+                Options::default(),
+                None,
+                feed,
+                self.contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query the Chainlink feed {:?}: {}", feed, e))
+    }
+
+    /// Reads the latest price from the feed.
+    async fn feed_price(&self, feed: Address) -> anyhow::Result<TokenPrice> {
+        let (decimals, round_data) = futures::join!(
+            self.call::<U256>(feed, "decimals"),
+            self.call::<(U256, U256, U256, U256, U256)>(feed, "latestRoundData")
+        );
+        let (_round_id, answer, _started_at, updated_at, _answered_in_round) = round_data?;
+
+        parse_answer(answer, decimals?, updated_at, Utc::now(), self.max_age)
+    }
+}
+
+#[async_trait]
+impl<F: TokenPriceAPI + Send + Sync> TokenPriceAPI for ChainlinkAPI<F> {
+    async fn get_price(&self, token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        match self.feeds.get(&token_symbol.to_uppercase()) {
+            Some(&feed) => self.feed_price(feed).await,
+            None => self.fallback.get_price(token_symbol).await,
+        }
+    }
+}
+
+/// Converts the feed answer to the USD price, checking that it's positive and fresh.
+fn parse_answer(
+    answer: U256,
+    decimals: U256,
+    updated_at: U256,
+    now: DateTime<Utc>,
+    max_age: chrono::Duration,
+) -> anyhow::Result<TokenPrice> {
+    // The answer is `int256`, so the negative values have the highest bit set.
+    anyhow::ensure!(
+        !answer.is_zero() && !answer.bit(255),
+        "Chainlink answer {} is not positive",
+        answer
+    );
+    anyhow::ensure!(
+        decimals <= U256::from(u8::MAX),
+        "Chainlink answer decimals {} are invalid",
+        decimals
+    );
+    anyhow::ensure!(
+        updated_at <= U256::from(i64::MAX as u64),
+        "Chainlink update time {} is invalid",
+        updated_at
+    );
+    let updated_at = Utc.timestamp(updated_at.as_u64() as i64, 0);
+    anyhow::ensure!(
+        now - updated_at <= max_age,
+        "Chainlink answer is stale, last updated at {}",
+        updated_at
+    );
+
+    let mut answer_bytes = [0u8; 32];
+    answer.to_big_endian(&mut answer_bytes);
+    Ok(TokenPrice {
+        usd_price: Ratio::new(
+            BigUint::from_bytes_be(&answer_bytes),
+            BigUint::from(10u32).pow(decimals.as_u32()),
+        ),
+        last_updated: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+    #[test]
+    fn feed_answer() {
+        let max_age = chrono::Duration::hours(1);
+        let now = Utc::now();
+        let updated_at = U256::from((now - chrono::Duration::minutes(30)).timestamp() as u64);
+        let decimals = U256::from(8u32);
+
+        let price = parse_answer(
+            U256::from(251_234_000_000u64),
+            decimals,
+            updated_at,
+            now,
+            max_age,
+        )
+        .expect("fresh answer is rejected");
+        assert_eq!(
+            price.usd_price,
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("2512.34").unwrap()
+        );
+        assert_eq!(price.last_updated, now);
+
+        // Zero and negative answers are rejected.
+        assert!(parse_answer(U256::zero(), decimals, updated_at, now, max_age).is_err());
+        assert!(parse_answer(U256::max_value(), decimals, updated_at, now, max_age).is_err());
+
+        // Stale answers are rejected.
+        let outdated = U256::from((now - chrono::Duration::minutes(61)).timestamp() as u64);
+        assert!(parse_answer(U256::from(100u32), decimals, outdated, now, max_age).is_err());
+    }
+}
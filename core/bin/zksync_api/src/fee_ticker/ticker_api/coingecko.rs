@@ -1,4 +1,14 @@
-use super::{TokenPriceAPI, REQUEST_TIMEOUT};
+//! Token prices of CoinGecko.
+//!
+//! The list of the tokens listed on CoinGecko is loaded when the client is created and refreshed
+//! periodically, so the new listings become available. The market charts are cached with
+//! the validators CoinGecko sends, so the documents not modified since the last request are
+//! not downloaded again and cost less of the rate limit.
+
+use super::{
+    rate_limit::{Conditional, RateLimitedClient, Validators},
+    TokenPriceAPI,
+};
 use anyhow::Error;
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -7,69 +17,168 @@ use num::BigUint;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use zksync_types::TokenPrice;
 use zksync_utils::UnsignedRatioSerializeAsDecimal;
 
+/// Header authenticating the requests to CoinGecko Pro API.
+const PRO_API_KEY_HEADER: &str = "x-cg-pro-api-key";
+/// Interval of the token list refreshes.
+const TOKEN_LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Token IDs by the symbols with the validators of the token list they are taken from.
+#[derive(Debug)]
+struct TokenIds {
+    ids: HashMap<String, String>,
+    validators: Validators,
+    refreshed_at: Instant,
+}
+
 #[derive(Debug, Clone)]
 pub struct CoinGeckoAPI {
     base_url: Url,
-    client: reqwest::Client,
-    token_ids: HashMap<String, String>,
+    client: RateLimitedClient,
+    token_ids: Arc<Mutex<TokenIds>>,
+    /// Latest market charts with their validators by the token IDs.
+    market_charts: Arc<Mutex<HashMap<String, (CoinGeckoMarketChart, Validators)>>>,
 }
 
 impl CoinGeckoAPI {
-    pub fn new(client: reqwest::Client, base_url: Url) -> anyhow::Result<Self> {
-        let token_list_url = base_url
-            .join("api/v3/coins/list")
-            .expect("failed to join URL path");
-
-        let token_list = reqwest::blocking::get(token_list_url)
-            .map_err(|err| anyhow::format_err!("CoinGecko API request failed: {}", err))?
-            .json::<CoinGeckoTokenList>()?;
-
-        let mut token_ids = HashMap::new();
-        for token in token_list.0 {
-            token_ids.insert(token.symbol, token.id);
+    /// Creates the client of the API, which is the Pro one if the API key is given.
+    pub fn new(
+        client: reqwest::Client,
+        base_url: Url,
+        api_key: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut client = RateLimitedClient::new(client, "CoinGecko");
+        if let Some(api_key) = api_key {
+            client = client.with_auth_header(PRO_API_KEY_HEADER, api_key);
         }
+
+        let (token_list, validators) =
+            client.get_json_blocking::<CoinGeckoTokenList>(token_list_url(&base_url))?;
+        let token_ids = TokenIds {
+            ids: token_list.token_ids(),
+            validators,
+            refreshed_at: Instant::now(),
+        };
         Ok(Self {
             base_url,
             client,
-            token_ids,
+            token_ids: Arc::new(Mutex::new(token_ids)),
+            market_charts: Arc::default(),
         })
     }
-}
 
-#[async_trait]
-impl TokenPriceAPI for CoinGeckoAPI {
-    async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, Error> {
-        let start = Instant::now();
-        let token_id = self
-            .token_ids
+    async fn token_id(&self, token_symbol: &str) -> anyhow::Result<String> {
+        let mut token_ids = self.token_ids.lock().await;
+        if token_ids.refreshed_at.elapsed() >= TOKEN_LIST_REFRESH_INTERVAL {
+            // The failed refresh is not repeated until the next interval, the loaded list is used.
+            token_ids.refreshed_at = Instant::now();
+            if let Err(err) = self.refresh_token_ids(&mut token_ids).await {
+                vlog::warn!("Failed to refresh the CoinGecko token list: {}", err);
+            }
+        }
+        token_ids
+            .ids
             .get(&token_symbol.to_lowercase())
-            .or_else(|| self.token_ids.get(token_symbol))
+            .or_else(|| token_ids.ids.get(token_symbol))
+            .cloned()
             .ok_or_else(|| {
                 anyhow::format_err!("Token '{}' is not listed on CoinGecko", token_symbol)
-            })?;
+            })
+    }
+
+    async fn refresh_token_ids(&self, token_ids: &mut TokenIds) -> anyhow::Result<()> {
+        let token_list = self
+            .client
+            .get_json_if_modified::<CoinGeckoTokenList>(
+                token_list_url(&self.base_url),
+                &[],
+                &token_ids.validators,
+            )
+            .await?;
+        match token_list {
+            Conditional::Modified(token_list, validators) => {
+                token_ids.ids = token_list.token_ids();
+                token_ids.validators = validators;
+            }
+            Conditional::NotModified => {
+                metrics::counter!(
+                    "ticker.coingecko.not_modified", 1, "document" => "token_list"
+                );
+            }
+        }
+        Ok(())
+    }
 
+    /// Requests the market chart of the token, reusing the cached one if it's not modified.
+    async fn market_chart(&self, token_id: &str) -> anyhow::Result<CoinGeckoMarketChart> {
         let market_chart_url = self
             .base_url
             .join(format!("api/v3/coins/{}/market_chart", token_id).as_str())
             .expect("failed to join URL path");
+        let validators = self
+            .market_charts
+            .lock()
+            .await
+            .get(token_id)
+            .map(|(_, validators)| validators.clone())
+            .unwrap_or_default();
 
         // If we use 2 day interval we will get hourly prices and not minute by minute which makes
         // response faster and smaller
         let market_chart = self
             .client
-            .get(market_chart_url)
-            .timeout(REQUEST_TIMEOUT)
-            .query(&[("vs_currency", "usd"), ("days", "2")])
-            .send()
-            .await
-            .map_err(|err| anyhow::format_err!("CoinGecko API request failed: {}", err))?
-            .json::<CoinGeckoMarketChart>()
+            .get_json_if_modified::<CoinGeckoMarketChart>(
+                market_chart_url,
+                &[("vs_currency", "usd"), ("days", "2")],
+                &validators,
+            )
             .await?;
 
+        let mut market_charts = self.market_charts.lock().await;
+        match market_chart {
+            Conditional::Modified(market_chart, validators) => {
+                // The documents without the validators can't be requested conditionally.
+                if validators != Validators::default() {
+                    market_charts.insert(token_id.to_string(), (market_chart.clone(), validators));
+                }
+                Ok(market_chart)
+            }
+            Conditional::NotModified => {
+                metrics::counter!(
+                    "ticker.coingecko.not_modified", 1, "document" => "market_chart"
+                );
+                market_charts
+                    .get(token_id)
+                    .map(|(market_chart, _)| market_chart.clone())
+                    .ok_or_else(|| {
+                        anyhow::format_err!(
+                            "CoinGecko market chart of '{}' is not modified, but not cached",
+                            token_id
+                        )
+                    })
+            }
+        }
+    }
+}
+
+fn token_list_url(base_url: &Url) -> Url {
+    base_url
+        .join("api/v3/coins/list")
+        .expect("failed to join URL path")
+}
+
+#[async_trait]
+impl TokenPriceAPI for CoinGeckoAPI {
+    async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, Error> {
+        let start = Instant::now();
+        let token_id = self.token_id(token_symbol).await?;
+        let market_chart = self.market_chart(&token_id).await?;
+
         let last_updated_timestamp_ms = market_chart
             .prices
             .last()
@@ -116,6 +225,15 @@ pub struct CoinGeckoTokenInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinGeckoTokenList(pub Vec<CoinGeckoTokenInfo>);
 
+impl CoinGeckoTokenList {
+    fn token_ids(self) -> HashMap<String, String> {
+        self.0
+            .into_iter()
+            .map(|token| (token.symbol, token.id))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinGeckoTokenPrice(
     pub i64, // timestamp (milliseconds)
@@ -136,7 +254,7 @@ mod tests {
     async fn test_coingecko_api() {
         let ticker_url = parse_env("FEE_TICKER_COINGECKO_BASE_URL");
         let client = reqwest::Client::new();
-        let api = CoinGeckoAPI::new(client, ticker_url).unwrap();
+        let api = CoinGeckoAPI::new(client, ticker_url, None).unwrap();
         api.get_price("ETH")
             .await
             .expect("Failed to get data from ticker");
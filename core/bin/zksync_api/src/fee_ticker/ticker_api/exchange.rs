@@ -0,0 +1,225 @@
+//! Spot prices of the centralized exchanges.
+//!
+//! Deployments that can't rely on the rate limits of the public aggregators may price the liquid
+//! tokens by the exchange markets instead. The token is priced by the last trade on its market
+//! quoted in USDT on Binance or in USD on Kraken, unless a different market is configured for it,
+//! e.g. WBTC is priced by the BTC market. The exchanges don't report the time of the last trade,
+//! so the price is considered up to date.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::time::Instant;
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_config::configs::ticker::ExchangeSymbolConfig;
+use zksync_types::TokenPrice;
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+// Local deps
+use super::{TokenPriceAPI, REQUEST_TIMEOUT};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exchange {
+    Binance,
+    Kraken,
+}
+
+impl Exchange {
+    fn default_market(self, token_symbol: &str) -> String {
+        match self {
+            Exchange::Binance => format!("{}USDT", token_symbol),
+            Exchange::Kraken => format!("{}USD", token_symbol),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExchangeAPI {
+    client: reqwest::Client,
+    base_url: Url,
+    exchange: Exchange,
+    /// Configured markets by the uppercase token symbols.
+    markets: HashMap<String, String>,
+}
+
+impl ExchangeAPI {
+    pub fn new(
+        client: reqwest::Client,
+        base_url: Url,
+        exchange: Exchange,
+        markets: &[ExchangeSymbolConfig],
+    ) -> Self {
+        let markets = markets
+            .iter()
+            .map(|market| (market.token.to_uppercase(), market.market.clone()))
+            .collect();
+        Self {
+            client,
+            base_url,
+            exchange,
+            markets,
+        }
+    }
+
+    fn market(&self, token_symbol: &str) -> String {
+        let token_symbol = token_symbol.to_uppercase();
+        self.markets
+            .get(&token_symbol)
+            .cloned()
+            .unwrap_or_else(|| self.exchange.default_market(&token_symbol))
+    }
+
+    async fn binance_price(&self, market: &str) -> anyhow::Result<Ratio<BigUint>> {
+        let request_url = self
+            .base_url
+            .join("api/v3/ticker/price")
+            .expect("failed to join URL path");
+        let response = self
+            .client
+            .get(request_url)
+            .timeout(REQUEST_TIMEOUT)
+            .query(&[("symbol", market)])
+            .send()
+            .await
+            .map_err(|err| format_err!("Binance API request failed: {}", err))?
+            .json::<BinanceTickerPrice>()
+            .await?;
+        Ok(response.price)
+    }
+
+    async fn kraken_price(&self, market: &str) -> anyhow::Result<Ratio<BigUint>> {
+        let request_url = self
+            .base_url
+            .join("0/public/Ticker")
+            .expect("failed to join URL path");
+        let response = self
+            .client
+            .get(request_url)
+            .timeout(REQUEST_TIMEOUT)
+            .query(&[("pair", market)])
+            .send()
+            .await
+            .map_err(|err| format_err!("Kraken API request failed: {}", err))?
+            .json::<KrakenTickerResponse>()
+            .await?;
+        response.last_trade_price()
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for ExchangeAPI {
+    async fn get_price(&self, token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        let start = Instant::now();
+        let market = self.market(token_symbol);
+        let usd_price = match self.exchange {
+            Exchange::Binance => self.binance_price(&market).await?,
+            Exchange::Kraken => self.kraken_price(&market).await?,
+        };
+        metrics::histogram!("ticker.exchange.request", start.elapsed());
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct BinanceTickerPrice {
+    pub symbol: String,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub price: Ratio<BigUint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct KrakenTicker {
+    /// Price and volume of the last trade.
+    pub c: (String, String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct KrakenTickerResponse {
+    pub error: Vec<String>,
+    #[serde(default)]
+    pub result: HashMap<String, KrakenTicker>,
+}
+
+impl KrakenTickerResponse {
+    /// Kraken names the markets in the responses its own way, e.g. `ETHUSD` is `XETHZUSD`,
+    /// so the only market of the response is the requested one.
+    fn last_trade_price(self) -> anyhow::Result<Ratio<BigUint>> {
+        if !self.error.is_empty() {
+            anyhow::bail!("Kraken API returned errors: {}", self.error.join(", "));
+        }
+        let ticker = self
+            .result
+            .into_iter()
+            .next()
+            .ok_or_else(|| format_err!("Kraken returned empty price data"))?
+            .1;
+        UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(&ticker.c.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_exchange_responses() {
+        let binance = r#"{"symbol":"ETHUSDT","price":"3012.45000000"}"#;
+        let binance = serde_json::from_str::<BinanceTickerPrice>(binance).unwrap();
+        assert_eq!(
+            binance.price,
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("3012.45").unwrap()
+        );
+
+        let kraken = r#"{
+    "error": [],
+    "result": {
+        "XETHZUSD": {
+            "a": ["3012.60000", "1", "1.000"],
+            "b": ["3012.59000", "3", "3.000"],
+            "c": ["3012.59000", "0.01250000"],
+            "v": ["1851.14375460", "24105.21738397"],
+            "p": ["3014.44586", "2998.19934"],
+            "t": [4573, 48810],
+            "l": ["2995.00000", "2950.41000"],
+            "h": ["3030.00000", "3044.70000"],
+            "o": "3016.51000"
+        }
+    }
+}"#;
+        let kraken = serde_json::from_str::<KrakenTickerResponse>(kraken).unwrap();
+        assert_eq!(
+            kraken.last_trade_price().unwrap(),
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("3012.59").unwrap()
+        );
+
+        let error = r#"{"error":["EQuery:Unknown asset pair"]}"#;
+        let error = serde_json::from_str::<KrakenTickerResponse>(error).unwrap();
+        assert!(error.last_trade_price().is_err());
+    }
+
+    #[test]
+    fn market_symbols() {
+        let markets = [ExchangeSymbolConfig {
+            token: "WBTC".into(),
+            market: "BTCUSDT".into(),
+        }];
+        let api = ExchangeAPI::new(
+            reqwest::Client::new(),
+            "http://127.0.0.1".parse().unwrap(),
+            Exchange::Binance,
+            &markets,
+        );
+        assert_eq!(api.market("wBTC"), "BTCUSDT");
+        assert_eq!(api.market("eth"), "ETHUSDT");
+
+        assert_eq!(Exchange::Kraken.default_market("ETH"), "ETHUSD");
+    }
+}
@@ -0,0 +1,135 @@
+//! On-chain gas price oracle.
+//!
+//! The gas price observed by `eth_sender` follows the network with a delay, so the fees may
+//! be calculated with the gas price which differs from the one actually paid for the L1
+//! operations. Instead, the gas price may be read from the Chainlink-compatible feed contract,
+//! e.g. Fast Gas / Gwei. The feed answer is used as the legacy gas price in wei. Stale answers
+//! are rejected, so the gas price observed by `eth_sender` is used until the feed is updated.
+
+// External deps
+use chrono::{DateTime, TimeZone, Utc};
+use num::BigUint;
+use web3::contract::Options;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_contracts::gas_price_oracle_contract;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, U256};
+// Local deps
+use super::GasPriceWei;
+
+#[derive(Debug, Clone)]
+pub struct GasPriceOracle {
+    client: EthereumGateway,
+    contract_address: Address,
+    contract: ethabi::Contract,
+    /// Answers updated earlier than this are considered stale.
+    max_age: chrono::Duration,
+}
+
+impl GasPriceOracle {
+    pub fn new(
+        client: EthereumGateway,
+        contract_address: Address,
+        max_age: chrono::Duration,
+    ) -> Self {
+        Self {
+            client,
+            contract_address,
+            contract: gas_price_oracle_contract(),
+            max_age,
+        }
+    }
+
+    /// Creates the oracle if it's configured as the gas price source.
+    pub fn from_config(config: &ZkSyncConfig) -> Option<Self> {
+        let contract_address = config.ticker.gas_oracle()?;
+        Some(Self::new(
+            EthereumGateway::from_config(config),
+            contract_address,
+            config.ticker.gas_oracle_max_age(),
+        ))
+    }
+
+    /// Reads the latest gas price from the feed.
+    pub async fn gas_price(&self) -> anyhow::Result<GasPriceWei> {
+        let (_round_id, answer, _started_at, updated_at, _answered_in_round): (
+            U256,
+            U256,
+            U256,
+            U256,
+            U256,
+        ) = self
+            .client
+            .call_contract_function(
+                "latestRoundData",
+                (),
+                None,
+                Options::default(),
+                None,
+                self.contract_address,
+                self.contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to query the gas price oracle: {}", e))?;
+
+        parse_answer(answer, updated_at, Utc::now(), self.max_age)
+    }
+}
+
+/// Converts the feed answer to the gas price, checking that it's positive and fresh.
+fn parse_answer(
+    answer: U256,
+    updated_at: U256,
+    now: DateTime<Utc>,
+    max_age: chrono::Duration,
+) -> anyhow::Result<GasPriceWei> {
+    // The answer is `int256`, so the negative values have the highest bit set.
+    anyhow::ensure!(
+        !answer.is_zero() && !answer.bit(255),
+        "gas price oracle answer {} is not positive",
+        answer
+    );
+    anyhow::ensure!(
+        updated_at <= U256::from(i64::MAX as u64),
+        "gas price oracle update time {} is invalid",
+        updated_at
+    );
+    let updated_at = Utc.timestamp(updated_at.as_u64() as i64, 0);
+    anyhow::ensure!(
+        now - updated_at <= max_age,
+        "gas price oracle answer is stale, last updated at {}",
+        updated_at
+    );
+
+    let mut answer_bytes = [0u8; 32];
+    answer.to_big_endian(&mut answer_bytes);
+    Ok(GasPriceWei::legacy(BigUint::from_bytes_be(&answer_bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oracle_answer() {
+        let max_age = chrono::Duration::minutes(10);
+        let now = Utc::now();
+        let updated_at = U256::from((now - chrono::Duration::minutes(1)).timestamp() as u64);
+
+        let gas_price = parse_answer(U256::from(150_000_000_000u64), updated_at, now, max_age)
+            .expect("fresh answer is rejected");
+        assert_eq!(
+            gas_price,
+            GasPriceWei::legacy(BigUint::from(150_000_000_000u64))
+        );
+
+        // Zero and negative answers are rejected.
+        assert!(parse_answer(U256::zero(), updated_at, now, max_age).is_err());
+        assert!(parse_answer(U256::max_value(), updated_at, now, max_age).is_err());
+
+        // Stale answers are rejected.
+        let outdated = U256::from((now - chrono::Duration::minutes(11)).timestamp() as u64);
+        assert!(parse_answer(U256::from(100u32), outdated, now, max_age).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Sliding window of the observed gas prices.
+//!
+//! Using the instantaneous gas price makes the fees jump between the consecutive quotes,
+//! so the gas price is periodically sampled by a background task, and the ticker uses
+//! the median of the samples within the window instead.
+
+// Built-in deps
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External deps
+use num::BigUint;
+use tokio::sync::Mutex;
+// Local deps
+use super::{gas_oracle::GasPriceOracle, load_gas_price, storage::TickerStorage, GasPriceWei};
+
+#[derive(Debug, Clone)]
+pub struct GasPriceWindow {
+    samples: VecDeque<(GasPriceWei, Instant)>,
+    /// Maximum number of the samples kept in the window.
+    max_samples: usize,
+    /// Samples older than this are not taken into account.
+    max_age: Duration,
+}
+
+impl GasPriceWindow {
+    pub fn new(max_samples: usize, max_age: Duration) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(max_samples),
+            max_samples,
+            max_age,
+        }
+    }
+
+    pub fn add_sample(&mut self, gas_price: GasPriceWei) {
+        self.add_sample_at(gas_price, Instant::now());
+    }
+
+    fn add_sample_at(&mut self, gas_price: GasPriceWei, observed_at: Instant) {
+        self.samples.push_back((gas_price, observed_at));
+        while self.samples.len() > self.max_samples {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the median of the base fees and the median of the priority fees within the window,
+    /// or `None` if there are no fresh samples.
+    pub fn median(&self) -> Option<GasPriceWei> {
+        let fresh_samples: Vec<_> = self
+            .samples
+            .iter()
+            .filter(|(_, observed_at)| observed_at.elapsed() <= self.max_age)
+            .map(|(gas_price, _)| gas_price)
+            .collect();
+        if fresh_samples.is_empty() {
+            return None;
+        }
+
+        Some(GasPriceWei {
+            base_fee: median(fresh_samples.iter().map(|price| &price.base_fee)),
+            priority_fee: median(fresh_samples.iter().map(|price| &price.priority_fee)),
+        })
+    }
+}
+
+/// Median of the non-empty set of values, the mean of the middle values is taken for the even count.
+fn median<'a>(values: impl Iterator<Item = &'a BigUint>) -> BigUint {
+    let mut values: Vec<_> = values.collect();
+    values.sort();
+
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / BigUint::from(2u32)
+    } else {
+        values[middle].clone()
+    }
+}
+
+/// Periodically adds the current gas price to the window.
+pub async fn run_gas_price_sampler<S: TickerStorage + Send + Sync>(
+    storage: S,
+    gas_price_oracle: Option<GasPriceOracle>,
+    window: Arc<Mutex<GasPriceWindow>>,
+    sampling_interval: Duration,
+) {
+    let mut timer = tokio::time::interval(sampling_interval);
+    loop {
+        timer.tick().await;
+
+        match load_gas_price(&storage, gas_price_oracle.as_ref()).await {
+            Ok(gas_price) => window.lock().await.add_sample(gas_price),
+            Err(e) => vlog::warn!("Failed to sample the gas price: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas_price(base_fee: u32, priority_fee: u32) -> GasPriceWei {
+        GasPriceWei {
+            base_fee: base_fee.into(),
+            priority_fee: priority_fee.into(),
+        }
+    }
+
+    #[test]
+    fn median_of_samples() {
+        let mut window = GasPriceWindow::new(4, Duration::from_secs(600));
+        assert_eq!(window.median(), None);
+
+        window.add_sample(gas_price(100, 2));
+        assert_eq!(window.median(), Some(gas_price(100, 2)));
+
+        window.add_sample(gas_price(300, 1));
+        window.add_sample(gas_price(10_000, 5));
+        assert_eq!(window.median(), Some(gas_price(300, 2)));
+
+        // The even number of samples.
+        window.add_sample(gas_price(200, 3));
+        assert_eq!(window.median(), Some(gas_price(250, 2)));
+
+        // The oldest sample is evicted.
+        window.add_sample(gas_price(400, 4));
+        assert_eq!(window.median(), Some(gas_price(350, 3)));
+    }
+
+    #[test]
+    fn outdated_samples_are_ignored() {
+        let max_age = Duration::from_secs(600);
+        let mut window = GasPriceWindow::new(10, max_age);
+
+        let outdated = Instant::now() - max_age - Duration::from_secs(1);
+        window.add_sample_at(gas_price(10_000, 10), outdated);
+        assert_eq!(window.median(), None);
+
+        window.add_sample(gas_price(100, 1));
+        assert_eq!(window.median(), Some(gas_price(100, 1)));
+    }
+}
@@ -2,20 +2,51 @@ use crate::utils::token_db_cache::TokenDBCache;
 use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
+use futures::future::join_all;
 use num::rational::Ratio;
-use num::BigUint;
+use num::{BigUint, Zero};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use tracing::Instrument;
+use zksync_config::configs::ticker::TokenPriceAggregation;
 use zksync_storage::ConnectionPool;
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{tokens::PriceConfidence, Token, TokenId, TokenLike, TokenPrice};
+use zksync_utils::ratio_to_big_decimal;
 
+pub mod chainlink;
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod exchange;
+pub mod gas_oracle;
+pub mod gas_price_window;
+pub mod rate_limit;
+pub mod registry;
+pub mod replay;
+pub mod static_prices;
+pub mod storage;
+pub mod uniswap_v3;
+
+use self::gas_oracle::GasPriceOracle;
+use self::gas_price_window::GasPriceWindow;
+use self::storage::{TickerDBStorage, TickerStorage};
+use super::health::TickerHealthHandle;
+
+/// Price source name used for the price history when the source is not specified.
+const UNKNOWN_PRICE_SOURCE: &str = "unknown";
+/// Price source name reported for the tokens with the hardcoded price.
+const FIXED_PRICE_SOURCE: &str = "fixed";
+/// Price source name reported for the median of the price API prices.
+const MEDIAN_PRICE_SOURCE: &str = "median";
 
 const API_PRICE_EXPIRATION_TIME_SECS: i64 = 300; // 5 mins
 const HISTORICAL_PRICE_EXPIRATION_TIME: Duration = Duration::from_secs(60);
+/// The new price isn't compared against the accepted one if the latter is older than this,
+/// so the actual price movement is eventually accepted.
+const PRICE_DEVIATION_REFERENCE_AGE_SECS: i64 = 60 * 60; // 1 hour
+/// Precision of the prices written to the log.
+const PRICE_DEVIATION_LOG_PRECISION: usize = 6;
 
 /// The limit of time we are willing to wait for response.
 pub const REQUEST_TIMEOUT: Duration = Duration::from_millis(700);
@@ -27,14 +58,104 @@ pub trait TokenPriceAPI {
     async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, anyhow::Error>;
 }
 
+/// Price APIs created from the registry are shared by the tickers.
+#[async_trait]
+impl<T: TokenPriceAPI + Send + Sync + ?Sized> TokenPriceAPI for Arc<T> {
+    async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, anyhow::Error> {
+        (**self).get_price(token_symbol).await
+    }
+}
+
+/// Gas price of the L1 transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPriceWei {
+    /// Base fee of the block, or the legacy gas price if the network doesn't support EIP-1559.
+    pub base_fee: BigUint,
+    /// Priority fee (tip) for the miner, zero if the network doesn't support EIP-1559.
+    pub priority_fee: BigUint,
+}
+
+impl GasPriceWei {
+    /// Gas price of the network which doesn't support EIP-1559.
+    pub fn legacy(gas_price: BigUint) -> Self {
+        Self {
+            base_fee: gas_price,
+            priority_fee: BigUint::from(0u32),
+        }
+    }
+
+    /// Price actually paid per unit of gas.
+    pub fn effective_price(&self) -> BigUint {
+        &self.base_fee + &self.priority_fee
+    }
+}
+
+/// Loads the current gas price from the oracle if it's set, or from the storage otherwise.
+/// The gas price observed by `eth_sender` is also used while the oracle is not available.
+pub(crate) async fn load_gas_price<S: TickerStorage>(
+    storage: &S,
+    gas_price_oracle: Option<&GasPriceOracle>,
+) -> anyhow::Result<GasPriceWei> {
+    if let Some(gas_price_oracle) = gas_price_oracle {
+        match gas_price_oracle.gas_price().await {
+            Ok(gas_price) => return Ok(gas_price),
+            Err(e) => {
+                vlog::warn!("Failed to get the gas price from the oracle: {}", e);
+                metrics::counter!("ticker.gas_price_oracle.failures", 1);
+            }
+        }
+    }
+
+    // Fees observed by `eth_sender` are only stored once the network supports EIP-1559,
+    // until then the average legacy gas price is used.
+    let gas_price = match storage.load_eip1559_fees().await? {
+        Some((base_fee, priority_fee)) => GasPriceWei {
+            base_fee: BigUint::from(base_fee.as_u64()),
+            priority_fee: BigUint::from(priority_fee.as_u64()),
+        },
+        None => {
+            let average_gas_price = storage
+                .load_average_gas_price()
+                .await?
+                .unwrap_or_default()
+                .as_u64();
+            GasPriceWei::legacy(BigUint::from(average_gas_price))
+        }
+    };
+    Ok(gas_price)
+}
+
+/// Token price together with its origin.
+#[derive(Debug, Clone)]
+pub struct SourcedTokenPrice {
+    pub price: TokenPrice,
+    /// Name of the price API the price is received from.
+    pub source: String,
+    pub confidence: PriceConfidence,
+}
+
 /// Api responsible for querying for TokenPrices
 #[async_trait]
 pub trait FeeTickerAPI {
     /// Get last price from ticker
     async fn get_last_quote(&self, token: TokenLike) -> Result<TokenPrice, anyhow::Error>;
 
-    /// Get current gas price in ETH
-    async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error>;
+    /// Get last price from ticker together with its origin. The price is reported as fresh
+    /// and of unknown source by the implementations that don't track it.
+    async fn get_sourced_quote(
+        &self,
+        token: TokenLike,
+    ) -> Result<SourcedTokenPrice, anyhow::Error> {
+        let price = self.get_last_quote(token).await?;
+        Ok(SourcedTokenPrice {
+            price,
+            source: UNKNOWN_PRICE_SOURCE.to_string(),
+            confidence: PriceConfidence::Fresh,
+        })
+    }
+
+    /// Get current base fee and suggested priority fee in wei
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error>;
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
 }
@@ -42,14 +163,22 @@ pub trait FeeTickerAPI {
 #[derive(Debug, Clone)]
 pub(crate) struct TokenCacheEntry {
     price: TokenPrice,
+    /// Price source the price is attributed to.
+    source: String,
     creation_time: Instant,
     is_price_historical: bool,
 }
 
 impl TokenCacheEntry {
-    fn new(price: TokenPrice, creation_time: Instant, is_price_historical: bool) -> Self {
+    fn new(
+        price: TokenPrice,
+        source: String,
+        creation_time: Instant,
+        is_price_historical: bool,
+    ) -> Self {
         Self {
             price,
+            source,
             creation_time,
             is_price_historical,
         }
@@ -72,149 +201,473 @@ impl TokenCacheEntry {
     }
 }
 
-#[derive(Debug)]
-pub(super) struct TickerApi<T: TokenPriceAPI> {
-    db_pool: ConnectionPool,
+/// Clones of the API share the price and gas price caches.
+#[derive(Debug, Clone)]
+pub(super) struct TickerApi<T: TokenPriceAPI, S: TickerStorage = TickerDBStorage> {
+    storage: S,
+    price_source: String,
+    /// Outcomes of the price API requests are recorded here.
+    health: TickerHealthHandle,
 
-    token_db_cache: TokenDBCache,
     price_cache: Arc<Mutex<HashMap<TokenId, TokenCacheEntry>>>,
-    gas_price_cache: Arc<Mutex<Option<(BigUint, Instant)>>>,
+    gas_price_cache: Arc<Mutex<Option<(GasPriceWei, Instant)>>>,
+    gas_price_window: Option<Arc<Mutex<GasPriceWindow>>>,
+    gas_price_oracle: Option<GasPriceOracle>,
+    /// Maximum relative difference between the new price and the last accepted one.
+    max_price_deviation: Option<Ratio<BigUint>>,
+    /// Last known prices older than this are not used when the price API is not available.
+    max_price_staleness: Option<chrono::Duration>,
 
     token_price_api: T,
+    /// Price APIs tried in order if the main one fails or returns a stale price,
+    /// with their names.
+    fallback_price_apis: Vec<(String, T)>,
+    /// How the prices of the main and the fallback price APIs are combined.
+    price_aggregation: TokenPriceAggregation,
+    /// Maximum relative difference between the price of the API and the median of the APIs.
+    max_source_price_deviation: Option<Ratio<BigUint>>,
 }
 
 impl<T: TokenPriceAPI> TickerApi<T> {
     pub fn new(db_pool: ConnectionPool, token_price_api: T) -> Self {
-        let token_db_cache = TokenDBCache::new();
+        Self::with_storage(TickerDBStorage::new(db_pool), token_price_api)
+    }
+
+    pub fn with_token_db_cache(self, token_db_cache: TokenDBCache) -> Self {
+        Self {
+            storage: self.storage.with_token_db_cache(token_db_cache),
+            ..self
+        }
+    }
+}
+
+impl<T: TokenPriceAPI, S: TickerStorage> TickerApi<T, S> {
+    /// Creates the ticker API on top of an arbitrary storage backend.
+    pub fn with_storage(storage: S, token_price_api: T) -> Self {
         Self {
-            db_pool,
-            token_db_cache,
+            storage,
+            price_source: UNKNOWN_PRICE_SOURCE.to_string(),
+            health: TickerHealthHandle::default(),
             price_cache: Default::default(),
             gas_price_cache: Default::default(),
+            gas_price_window: None,
+            gas_price_oracle: None,
+            max_price_deviation: None,
+            max_price_staleness: None,
             token_price_api,
+            fallback_price_apis: Vec::new(),
+            price_aggregation: TokenPriceAggregation::Fallback,
+            max_source_price_deviation: None,
         }
     }
-    pub fn with_token_db_cache(self, token_db_cache: TokenDBCache) -> Self {
+
+    /// Sets the name of the price API used in the stored price history.
+    pub fn with_price_source(self, price_source: impl Into<String>) -> Self {
         Self {
-            token_db_cache,
+            price_source: price_source.into(),
             ..self
         }
     }
 
-    pub fn with_gas_price_cache(
-        self,
-        gas_price_cache: Arc<Mutex<Option<(BigUint, Instant)>>>,
-    ) -> Self {
+    /// Sets the health the outcomes of the price API requests are recorded to.
+    pub fn with_health(self, health: TickerHealthHandle) -> Self {
+        Self { health, ..self }
+    }
+
+    /// Sets the window of the sampled gas prices. If set, the median of the window is used
+    /// instead of the instantaneous gas price, while the window has fresh samples.
+    pub fn with_gas_price_window(self, gas_price_window: Arc<Mutex<GasPriceWindow>>) -> Self {
         Self {
-            gas_price_cache,
+            gas_price_window: Some(gas_price_window),
             ..self
         }
     }
 
-    pub fn with_price_cache(
-        self,
-        price_cache: Arc<Mutex<HashMap<TokenId, TokenCacheEntry>>>,
-    ) -> Self {
+    /// Sets the gas price feed contract used instead of the gas price observed by `eth_sender`.
+    pub fn with_gas_price_oracle(self, gas_price_oracle: Option<GasPriceOracle>) -> Self {
         Self {
-            price_cache,
+            gas_price_oracle,
             ..self
         }
     }
 
-    // Version of `update_stored_value` which returns a `Result` for convenient error handling.
-    async fn _update_stored_value(
-        &self,
-        token_id: TokenId,
-        price: TokenPrice,
-    ) -> Result<(), anyhow::Error> {
-        let mut storage = self
-            .db_pool
-            .access_storage()
-            .await
-            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+    /// Sets the maximum relative difference between the price received from the API
+    /// and the last accepted one. Prices deviating further are rejected.
+    pub fn with_max_price_deviation(self, max_price_deviation: Option<Ratio<BigUint>>) -> Self {
+        Self {
+            max_price_deviation,
+            ..self
+        }
+    }
 
-        storage
-            .tokens_schema()
-            .update_historical_ticker_price(token_id, price)
-            .await
-            .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e))?;
+    /// Sets the maximum age of the last known price used when the price API is not available.
+    pub fn with_max_price_staleness(self, max_price_staleness: Option<chrono::Duration>) -> Self {
+        Self {
+            max_price_staleness,
+            ..self
+        }
+    }
+
+    /// Sets the price APIs tried in order if the main one fails or returns a price older than
+    /// the maximum staleness. Every API is given with its name.
+    pub fn with_fallback_price_sources(self, fallback_price_apis: Vec<(String, T)>) -> Self {
+        Self {
+            fallback_price_apis,
+            ..self
+        }
+    }
 
-        Ok(())
+    /// Sets how the prices of the main and the fallback price APIs are combined.
+    pub fn with_price_aggregation(self, price_aggregation: TokenPriceAggregation) -> Self {
+        Self {
+            price_aggregation,
+            ..self
+        }
+    }
+
+    /// Sets the maximum relative difference between the price received from the API and
+    /// the median of the APIs. Prices deviating further are not used for the median price.
+    pub fn with_max_source_price_deviation(
+        self,
+        max_source_price_deviation: Option<Ratio<BigUint>>,
+    ) -> Self {
+        Self {
+            max_source_price_deviation,
+            ..self
+        }
     }
 
     async fn update_stored_value(
         &self,
         token_id: TokenId,
+        source: &str,
         price: TokenPrice,
         is_price_historical: bool,
     ) {
         self.price_cache.lock().await.insert(
             token_id,
-            TokenCacheEntry::new(price.clone(), Instant::now(), is_price_historical),
+            TokenCacheEntry::new(
+                price.clone(),
+                source.to_string(),
+                Instant::now(),
+                is_price_historical,
+            ),
         );
 
         if !is_price_historical {
-            self._update_stored_value(token_id, price)
+            self.storage
+                .store_price_observation(token_id, source, &price)
+                .await
+                .map_err(|e| vlog::warn!("Failed to store price observation: {}", e))
+                .unwrap_or_default();
+            self.storage
+                .update_historical_ticker_price(token_id, price)
                 .await
                 .map_err(|e| vlog::warn!("Failed to update historical ticker price: {}", e))
                 .unwrap_or_default();
         }
     }
 
-    async fn get_stored_value(&self, token_id: TokenId) -> Option<TokenPrice> {
-        let mut price_cache = self.price_cache.lock().await;
+    async fn get_stored_value(&self, token_id: TokenId) -> Option<SourcedTokenPrice> {
+        let price_cache = self.price_cache.lock().await;
 
-        if let Some(cached_entry) = price_cache.remove(&token_id) {
-            if !cached_entry.is_cache_entry_expired() {
-                price_cache.insert(token_id, cached_entry.clone());
-                if cached_entry.is_price_historical {
-                    vlog::warn!("Using historical price for token_id: {}", token_id);
-                }
-                return Some(cached_entry.price);
+        // Expired entries are kept in the cache, so they can be used as the last known prices.
+        let cached_entry = price_cache
+            .get(&token_id)
+            .filter(|entry| !entry.is_cache_entry_expired())?;
+        let confidence = if cached_entry.is_price_historical {
+            vlog::warn!("Using historical price for token_id: {}", token_id);
+            PriceConfidence::Fallback
+        } else {
+            PriceConfidence::Fresh
+        };
+        Some(SourcedTokenPrice {
+            price: cached_entry.price.clone(),
+            source: cached_entry.source.clone(),
+            confidence,
+        })
+    }
+
+    /// Attributes the price to the main price API of this ticker.
+    fn sourced(&self, price: TokenPrice, confidence: PriceConfidence) -> SourcedTokenPrice {
+        SourcedTokenPrice {
+            price,
+            source: self.price_source.clone(),
+            confidence,
+        }
+    }
+
+    /// Main and fallback price APIs in order, with their names.
+    fn price_apis(&self) -> impl Iterator<Item = (&str, &T)> {
+        std::iter::once((self.price_source.as_str(), &self.token_price_api)).chain(
+            self.fallback_price_apis
+                .iter()
+                .map(|(source, api)| (source.as_str(), api)),
+        )
+    }
+
+    /// Requests the price from the price API. Returns `None` if the request fails or the price
+    /// is stale.
+    async fn request_source_price(
+        &self,
+        source: &str,
+        api: &T,
+        token_symbol: &str,
+    ) -> Option<TokenPrice> {
+        let api_price = api
+            .get_price(token_symbol)
+            .instrument(tracing::info_span!(
+                "price_api_request",
+                source = %source,
+                token = %token_symbol
+            ))
+            .await;
+        match api_price {
+            Ok(api_price) if self.is_price_stale(&api_price) => {
+                vlog::warn!(
+                    "Price of {} from {} is stale, last updated at {}",
+                    token_symbol,
+                    source,
+                    api_price.last_updated
+                );
+                let error = format_err!("Price is stale");
+                self.health.record_error(source, token_symbol, &error);
+                metrics::counter!(
+                    "ticker.price_source.stale", 1, "source" => source.to_string()
+                );
+                None
+            }
+            Ok(api_price) => {
+                self.health.record_quote(source, token_symbol);
+                Some(api_price)
+            }
+            Err(e) => {
+                vlog::warn!("Failed to get price from {}: {}", source, e);
+                self.health.record_error(source, token_symbol, &e);
+                metrics::counter!(
+                    "ticker.price_source.failed", 1, "source" => source.to_string()
+                );
+                None
+            }
+        }
+    }
+
+    /// Requests the price from the price APIs as configured by the aggregation mode.
+    /// Returns the price with the name of the API or the aggregation.
+    async fn request_api_price(&self, token_symbol: &str) -> Option<(&str, TokenPrice)> {
+        match self.price_aggregation {
+            TokenPriceAggregation::Fallback => self.request_first_price(token_symbol).await,
+            TokenPriceAggregation::Median => self.request_median_price(token_symbol).await,
+        }
+    }
+
+    /// Requests the price from the price APIs in order until one of them returns a price
+    /// that is not stale.
+    async fn request_first_price(&self, token_symbol: &str) -> Option<(&str, TokenPrice)> {
+        for (source, api) in self.price_apis() {
+            if let Some(api_price) = self.request_source_price(source, api, token_symbol).await {
+                metrics::counter!(
+                    "ticker.price_source.served", 1, "source" => source.to_string()
+                );
+                return Some((source, api_price));
             }
         }
         None
     }
 
+    /// Requests the price from all the price APIs at once and takes the median of the prices
+    /// that are not stale. The prices deviating from the median too much are rejected and
+    /// the median of the rest is used, so a single mispriced API doesn't affect the fees.
+    async fn request_median_price(&self, token_symbol: &str) -> Option<(&str, TokenPrice)> {
+        let prices = join_all(self.price_apis().map(|(source, api)| async move {
+            self.request_source_price(source, api, token_symbol)
+                .await
+                .map(|price| (source, price))
+        }))
+        .await;
+        let prices: Vec<_> = prices.into_iter().flatten().collect();
+        let median = median_price(prices.iter().map(|(_, price)| &price.usd_price))?;
+
+        let accepted: Vec<_> = prices
+            .into_iter()
+            .filter(|(source, price)| {
+                let max_deviation = match &self.max_source_price_deviation {
+                    Some(max_deviation) if !median.is_zero() => max_deviation,
+                    _ => return true,
+                };
+                let difference = if price.usd_price > median {
+                    &price.usd_price - &median
+                } else {
+                    &median - &price.usd_price
+                };
+                if difference / &median <= *max_deviation {
+                    return true;
+                }
+                vlog::warn!(
+                    "Price of {} from {} deviates from the median too much: {} (median: {})",
+                    token_symbol,
+                    source,
+                    ratio_to_big_decimal(&price.usd_price, PRICE_DEVIATION_LOG_PRECISION),
+                    ratio_to_big_decimal(&median, PRICE_DEVIATION_LOG_PRECISION),
+                );
+                let error = format_err!("Price deviates from the median of the sources");
+                self.health.record_error(source, token_symbol, &error);
+                metrics::counter!(
+                    "ticker.price_source.outlier", 1, "source" => source.to_string()
+                );
+                false
+            })
+            .collect();
+        for (source, _) in &accepted {
+            metrics::counter!(
+                "ticker.price_source.served", 1, "source" => source.to_string()
+            );
+        }
+
+        let usd_price = median_price(accepted.iter().map(|(_, price)| &price.usd_price))?;
+        let last_updated = accepted.iter().map(|(_, price)| price.last_updated).min()?;
+        Some((
+            MEDIAN_PRICE_SOURCE,
+            TokenPrice {
+                usd_price,
+                last_updated,
+            },
+        ))
+    }
+
+    /// Returns the most recent of the cached and the stored historical prices of the token.
+    async fn get_last_known_price(&self, token_id: TokenId) -> Option<TokenPrice> {
+        let cached_price = self
+            .price_cache
+            .lock()
+            .await
+            .get(&token_id)
+            .map(|entry| entry.price.clone());
+        let historical_price = self
+            .get_historical_ticker_price(token_id)
+            .await
+            .map_err(|e| vlog::warn!("Failed to get historical ticker price: {}", e))
+            .ok()
+            .flatten();
+
+        match (cached_price, historical_price) {
+            (Some(cached_price), Some(historical_price)) => {
+                if cached_price.last_updated >= historical_price.last_updated {
+                    Some(cached_price)
+                } else {
+                    Some(historical_price)
+                }
+            }
+            (cached_price, historical_price) => cached_price.or(historical_price),
+        }
+    }
+
+    fn is_price_stale(&self, price: &TokenPrice) -> bool {
+        self.max_price_staleness
+            .map_or(false, |max_price_staleness| {
+                Utc::now().signed_duration_since(price.last_updated) > max_price_staleness
+            })
+    }
+
+    /// Compares the price received from the API against the last accepted one.
+    /// Returns the accepted price if the new one deviates too much, so it should be used instead.
+    async fn check_price_deviation(
+        &self,
+        token_id: TokenId,
+        price: &TokenPrice,
+    ) -> Option<TokenPrice> {
+        let max_price_deviation = self.max_price_deviation.as_ref()?;
+        let accepted_price = self
+            .get_historical_ticker_price(token_id)
+            .await
+            .map_err(|e| vlog::warn!("Failed to get historical ticker price: {}", e))
+            .ok()??;
+        let accepted_price_age = Utc::now()
+            .signed_duration_since(accepted_price.last_updated)
+            .num_seconds();
+        if accepted_price_age > PRICE_DEVIATION_REFERENCE_AGE_SECS
+            || accepted_price.usd_price.is_zero()
+        {
+            return None;
+        }
+
+        let difference = if price.usd_price > accepted_price.usd_price {
+            &price.usd_price - &accepted_price.usd_price
+        } else {
+            &accepted_price.usd_price - &price.usd_price
+        };
+        if difference / &accepted_price.usd_price <= *max_price_deviation {
+            return None;
+        }
+
+        vlog::warn!(
+            "Price of the token {} received from the API deviates too much: {} (accepted price: {})",
+            token_id,
+            ratio_to_big_decimal(&price.usd_price, PRICE_DEVIATION_LOG_PRECISION),
+            ratio_to_big_decimal(&accepted_price.usd_price, PRICE_DEVIATION_LOG_PRECISION),
+        );
+        metrics::counter!("ticker.price_deviation_rejected", 1);
+        Some(accepted_price)
+    }
+
     async fn get_historical_ticker_price(
         &self,
         token_id: TokenId,
     ) -> Result<Option<TokenPrice>, anyhow::Error> {
         let start = Instant::now();
-        let mut storage = self
-            .db_pool
-            .access_storage()
-            .await
-            .map_err(|e| format_err!("Can't access storage: {}", e))?;
-
-        let result = storage
-            .tokens_schema()
-            .get_historical_ticker_price(token_id)
-            .await
-            .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e));
+        let result = self.storage.get_historical_ticker_price(token_id).await;
 
         metrics::histogram!("ticker.get_historical_ticker_price", start.elapsed());
         result
     }
 }
 
+/// Returns the median of the prices, or the mean of the two middle ones if their number is even.
+fn median_price<'a>(prices: impl Iterator<Item = &'a Ratio<BigUint>>) -> Option<Ratio<BigUint>> {
+    let mut prices: Vec<_> = prices.collect();
+    prices.sort();
+    let middle = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(prices[middle].clone()),
+        _ => Some((prices[middle - 1] + prices[middle]) / Ratio::from_integer(BigUint::from(2u32))),
+    }
+}
+
 #[async_trait]
-impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
+impl<T, S> FeeTickerAPI for TickerApi<T, S>
+where
+    T: TokenPriceAPI + Send + Sync,
+    S: TickerStorage + Send + Sync,
+{
     /// Get last price from ticker
     async fn get_last_quote(&self, token: TokenLike) -> Result<TokenPrice, anyhow::Error> {
+        self.get_sourced_quote(token)
+            .await
+            .map(|sourced_price| sourced_price.price)
+    }
+
+    async fn get_sourced_quote(
+        &self,
+        token: TokenLike,
+    ) -> Result<SourcedTokenPrice, anyhow::Error> {
         let start = Instant::now();
         let token = self
-            .token_db_cache
-            .get_token(&mut self.db_pool.access_storage().await?, token.clone())
+            .storage
+            .get_token(token.clone())
             .await?
             .ok_or_else(|| format_err!("Token not found: {:?}", token))?;
 
         // TODO: remove hardcode for Matter Labs Trial Token (ZKS-63).
         if token.symbol == "MLTT" {
             metrics::histogram!("ticker.get_last_quote", start.elapsed());
-            return Ok(TokenPrice {
-                usd_price: Ratio::from_integer(1u32.into()),
-                last_updated: Utc::now(),
+            return Ok(SourcedTokenPrice {
+                price: TokenPrice {
+                    usd_price: Ratio::from_integer(1u32.into()),
+                    last_updated: Utc::now(),
+                },
+                source: FIXED_PRICE_SOURCE.to_string(),
+                confidence: PriceConfidence::Fresh,
             });
         }
 
@@ -223,36 +676,58 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
             return Ok(cached_value);
         }
 
-        let api_price = self
-            .token_price_api
-            .get_price(&token.symbol)
-            .await
-            .map_err(|e| vlog::warn!("Failed to get price: {}", e));
-        if let Ok(api_price) = api_price {
-            self.update_stored_value(token.id, api_price.clone(), false)
+        if let Some((source, api_price)) = self.request_api_price(&token.symbol).await {
+            if let Some(accepted_price) = self.check_price_deviation(token.id, &api_price).await {
+                // The accepted price is cached as the historical one, so the API is queried
+                // again soon.
+                self.update_stored_value(
+                    token.id,
+                    &self.price_source,
+                    accepted_price.clone(),
+                    true,
+                )
+                .await;
+                metrics::histogram!("ticker.get_last_quote", start.elapsed());
+                return Ok(self.sourced(accepted_price, PriceConfidence::Fallback));
+            }
+
+            self.update_stored_value(token.id, source, api_price.clone(), false)
                 .await;
             metrics::histogram!("ticker.get_last_quote", start.elapsed());
-            return Ok(api_price);
+            return Ok(SourcedTokenPrice {
+                price: api_price,
+                source: source.to_string(),
+                confidence: PriceConfidence::Fresh,
+            });
         }
 
-        let historical_price = self
-            .get_historical_ticker_price(token.id)
-            .await
-            .map_err(|e| vlog::warn!("Failed to get historical ticker price: {}", e));
+        if let Some(last_known_price) = self.get_last_known_price(token.id).await {
+            if self.is_price_stale(&last_known_price) {
+                metrics::counter!("ticker.stale_price_rejected", 1);
+                anyhow::bail!(
+                    "Token price api is not available right now and the last known price is stale."
+                );
+            }
 
-        if let Ok(Some(historical_price)) = historical_price {
-            self.update_stored_value(token.id, historical_price.clone(), true)
+            self.update_stored_value(token.id, &self.price_source, last_known_price.clone(), true)
                 .await;
             metrics::histogram!("ticker.get_last_quote", start.elapsed());
-            return Ok(historical_price);
+            return Ok(self.sourced(last_known_price, PriceConfidence::Fallback));
         }
 
         anyhow::bail!("Token price api is not available right now.")
     }
 
-    /// Get current gas price in ETH
-    async fn get_gas_price_wei(&self) -> Result<BigUint, anyhow::Error> {
+    /// Get current base fee and suggested priority fee in wei
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error> {
         let start = Instant::now();
+        if let Some(gas_price_window) = &self.gas_price_window {
+            if let Some(gas_price) = gas_price_window.lock().await.median() {
+                metrics::histogram!("ticker.get_gas_price_wei", start.elapsed());
+                return Ok(gas_price);
+            }
+        }
+
         let mut cached_value = self.gas_price_cache.lock().await;
 
         if let Some((cached_gas_price, cache_time)) = cached_value.take() {
@@ -263,29 +738,18 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
         }
         drop(cached_value);
 
-        let mut storage = self
-            .db_pool
-            .access_storage()
-            .await
-            .map_err(|e| format_err!("Can't access storage: {}", e))?;
-        let average_gas_price = storage
-            .ethereum_schema()
-            .load_average_gas_price()
-            .await?
-            .unwrap_or_default()
-            .as_u64();
-        let average_gas_price = BigUint::from(average_gas_price);
+        let gas_price = load_gas_price(&self.storage, self.gas_price_oracle.as_ref()).await?;
 
-        *self.gas_price_cache.lock().await = Some((average_gas_price.clone(), Instant::now()));
+        *self.gas_price_cache.lock().await = Some((gas_price.clone(), Instant::now()));
         metrics::histogram!("ticker.get_gas_price_wei", start.elapsed());
-        Ok(average_gas_price)
+        Ok(gas_price)
     }
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
         let start = Instant::now();
         let result = self
-            .token_db_cache
-            .get_token(&mut self.db_pool.access_storage().await?, token.clone())
+            .storage
+            .get_token(token.clone())
             .await?
             .ok_or_else(|| format_err!("Token not found: {:?}", token));
         metrics::histogram!("ticker.get_token", start.elapsed());
@@ -0,0 +1,258 @@
+//! HTTP client respecting the rate limits of the price APIs.
+//!
+//! The APIs throttle the clients exceeding the limits of their plans by responding with
+//! `429 Too Many Requests`, which made the fee requests fail at random under load. The throttled
+//! requests are repeated after the delay the API asks for in `Retry-After` or, if it doesn't ask
+//! for any, after the exponential backoff. The delays are jittered, so the requests throttled
+//! together are not repeated together.
+//!
+//! The documents which rarely change may be requested conditionally: the API responds with
+//! `304 Not Modified` instead of the document if the validators of the cached one still match.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use anyhow::format_err;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER,
+    },
+    StatusCode, Url,
+};
+use serde::de::DeserializeOwned;
+// Workspace deps
+use zksync_crypto::rand::{thread_rng, Rng};
+// Local deps
+use super::REQUEST_TIMEOUT;
+
+/// Maximum amount of attempts to send the request.
+const MAX_ATTEMPTS: u32 = 3;
+/// Delay before the first retry. Every next retry waits twice as long as the previous one.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+/// The requests asked to wait longer fail at once, so the ticker moves on to the fallback
+/// price sources instead of holding the fee requests.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Validators of the cached document, see `RateLimitedClient::get_json_if_modified`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+/// Response to the conditional request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conditional<T> {
+    Modified(T, Validators),
+    NotModified,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitedClient {
+    client: reqwest::Client,
+    /// Name of the API for the errors and metrics.
+    api_name: &'static str,
+    /// Header authenticating the requests, e.g. the API key.
+    auth_header: Option<(&'static str, String)>,
+}
+
+impl RateLimitedClient {
+    pub fn new(client: reqwest::Client, api_name: &'static str) -> Self {
+        Self {
+            client,
+            api_name,
+            auth_header: None,
+        }
+    }
+
+    pub fn with_auth_header(self, name: &'static str, value: impl Into<String>) -> Self {
+        Self {
+            auth_header: Some((name, value.into())),
+            ..self
+        }
+    }
+
+    /// Requests the JSON document unless it's not modified since the cached one was received,
+    /// repeating the request while it's throttled. The request is unconditional if the cached
+    /// document has no validators.
+    pub async fn get_json_if_modified<T: DeserializeOwned>(
+        &self,
+        url: Url,
+        query: &[(&str, &str)],
+        validators: &Validators,
+    ) -> anyhow::Result<Conditional<T>> {
+        let mut attempt = 1;
+        loop {
+            let mut request = self
+                .client
+                .get(url.clone())
+                .timeout(REQUEST_TIMEOUT)
+                .query(query);
+            if let Some((name, value)) = &self.auth_header {
+                request = request.header(*name, value.as_str());
+            }
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag.as_str());
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|err| format_err!("{} API request failed: {}", self.api_name, err))?;
+
+            let status = response.status();
+            if status == StatusCode::NOT_MODIFIED {
+                return Ok(Conditional::NotModified);
+            }
+            if status.is_success() {
+                let validators = Validators::from_headers(response.headers());
+                return Ok(Conditional::Modified(response.json().await?, validators));
+            }
+            let delay = self.next_delay(status, response.headers(), attempt)?;
+            tokio::time::delay_for(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Requests the JSON document unconditionally, blocking the thread. Used to load the data
+    /// the API client is created with, so the validators are returned for its later updates.
+    pub fn get_json_blocking<T: DeserializeOwned>(
+        &self,
+        url: Url,
+    ) -> anyhow::Result<(T, Validators)> {
+        let client = reqwest::blocking::Client::new();
+        let mut attempt = 1;
+        loop {
+            let mut request = client.get(url.clone());
+            if let Some((name, value)) = &self.auth_header {
+                request = request.header(*name, value.as_str());
+            }
+            let response = request
+                .send()
+                .map_err(|err| format_err!("{} API request failed: {}", self.api_name, err))?;
+
+            let status = response.status();
+            if status.is_success() {
+                let validators = Validators::from_headers(response.headers());
+                return Ok((response.json()?, validators));
+            }
+            let delay = self.next_delay(status, response.headers(), attempt)?;
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Returns the delay before the unsuccessful request is repeated, failing if it shouldn't be.
+    fn next_delay(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        attempt: u32,
+    ) -> anyhow::Result<Duration> {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            metrics::counter!("ticker.api.throttled", 1, "api" => self.api_name);
+        }
+        let retry_after = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok());
+        let delay = retry_delay(status, retry_after, attempt)
+            .ok_or_else(|| format_err!("{} API responded with {}", self.api_name, status))?;
+        vlog::debug!(
+            "{} API responded with {}, retrying in {:?}",
+            self.api_name,
+            status,
+            delay
+        );
+        Ok(jittered(delay))
+    }
+}
+
+/// Returns the delay before the request is repeated, or `None` if it shouldn't be.
+/// Only the throttled requests and the ones the API is temporarily unavailable for are repeated.
+fn retry_delay(status: StatusCode, retry_after: Option<&str>, attempt: u32) -> Option<Duration> {
+    let retriable =
+        status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE;
+    if !retriable || attempt >= MAX_ATTEMPTS {
+        return None;
+    }
+    // `Retry-After` may also be an HTTP date, which the price APIs don't use.
+    let delay = retry_after
+        .and_then(|seconds| seconds.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| INITIAL_BACKOFF * 2u32.pow(attempt - 1));
+    Some(delay).filter(|delay| *delay <= MAX_RETRY_DELAY)
+}
+
+/// Extends the delay by up to a half, so the delay the API asks for is still respected.
+fn jittered(delay: Duration) -> Duration {
+    delay.mul_f64(thread_rng().gen_range(1.0, 1.5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delays() {
+        let throttled = StatusCode::TOO_MANY_REQUESTS;
+        assert_eq!(retry_delay(throttled, None, 1), Some(INITIAL_BACKOFF));
+        assert_eq!(retry_delay(throttled, None, 2), Some(INITIAL_BACKOFF * 2));
+        assert_eq!(retry_delay(throttled, None, MAX_ATTEMPTS), None);
+
+        // The delay the API asks for is respected, unless it's too long.
+        assert_eq!(
+            retry_delay(throttled, Some("1"), 1),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(retry_delay(throttled, Some("60"), 1), None);
+        assert_eq!(
+            retry_delay(throttled, Some("soon"), 1),
+            Some(INITIAL_BACKOFF)
+        );
+
+        assert_eq!(
+            retry_delay(StatusCode::SERVICE_UNAVAILABLE, None, 1),
+            Some(INITIAL_BACKOFF)
+        );
+        assert_eq!(retry_delay(StatusCode::NOT_FOUND, None, 1), None);
+
+        let delay = jittered(Duration::from_secs(1));
+        assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn response_validators() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Validators::from_headers(&headers), Validators::default());
+
+        headers.insert(ETAG, "W/\"5f3a\"".parse().unwrap());
+        headers.insert(
+            LAST_MODIFIED,
+            "Wed, 21 Oct 2026 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(
+            Validators::from_headers(&headers),
+            Validators {
+                etag: Some("W/\"5f3a\"".into()),
+                last_modified: Some("Wed, 21 Oct 2026 07:28:00 GMT".into()),
+            }
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Registry of the token price APIs.
+//!
+//! The ticker creates the APIs of the main and the fallback price sources by their names
+//! from the registry, which has the built-in sources registered. The server built on top of
+//! this crate may register its own sources, e.g. the internal oracle service of the operator,
+//! before the ticker is started and refer to them in the config by the same names.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+// External deps
+use anyhow::format_err;
+use once_cell::sync::Lazy;
+// Workspace deps
+use zksync_config::{configs::ticker::TokenPriceSource, ZkSyncConfig};
+use zksync_eth_client::EthereumGateway;
+// Local deps
+use super::{
+    chainlink::ChainlinkAPI,
+    coingecko::CoinGeckoAPI,
+    coinmarkercap::CoinMarketCapAPI,
+    exchange::{Exchange, ExchangeAPI},
+    static_prices::StaticPriceAPI,
+    uniswap_v3::UniswapV3API,
+    TokenPriceAPI,
+};
+
+/// Price API shared by the tickers.
+pub type SharedTokenPriceAPI = Arc<dyn TokenPriceAPI + Send + Sync>;
+
+/// Creates the price API of the source.
+pub type PriceSourceConstructor =
+    Arc<dyn Fn(&PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> + Send + Sync>;
+
+/// Everything the price API is created from.
+pub struct PriceSourceContext<'a> {
+    pub config: &'a ZkSyncConfig,
+    /// API URL of the source. Empty for the custom sources, which are configured by the server.
+    pub base_url: &'a str,
+    /// HTTP client shared by the price APIs.
+    pub client: &'a reqwest::Client,
+}
+
+static PRICE_SOURCES: Lazy<RwLock<HashMap<String, PriceSourceConstructor>>> =
+    Lazy::new(|| RwLock::new(builtin_sources()));
+
+/// Registers the price source under the name, replacing the source registered before.
+/// The sources registered after the ticker is started are not used by it.
+pub fn register_price_source<F>(name: impl Into<String>, constructor: F)
+where
+    F: Fn(&PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> + Send + Sync + 'static,
+{
+    PRICE_SOURCES
+        .write()
+        .unwrap()
+        .insert(name.into(), Arc::new(constructor));
+}
+
+/// Creates the API of the registered price source.
+pub fn create_price_source(
+    source: &TokenPriceSource,
+    context: &PriceSourceContext<'_>,
+) -> anyhow::Result<SharedTokenPriceAPI> {
+    let constructor = PRICE_SOURCES
+        .read()
+        .unwrap()
+        .get(source.name())
+        .cloned()
+        .ok_or_else(|| format_err!("Token price source {} is not registered", source.name()))?;
+    constructor(context)
+}
+
+fn builtin_sources() -> HashMap<String, PriceSourceConstructor> {
+    let sources: [(TokenPriceSource, PriceSourceConstructor); 7] = [
+        (TokenPriceSource::CoinGecko, Arc::new(coingecko)),
+        (TokenPriceSource::CoinMarketCap, Arc::new(coinmarketcap)),
+        (TokenPriceSource::Chainlink, Arc::new(chainlink)),
+        (TokenPriceSource::UniswapV3, Arc::new(uniswap_v3)),
+        (TokenPriceSource::Binance, Arc::new(binance)),
+        (TokenPriceSource::Kraken, Arc::new(kraken)),
+        (TokenPriceSource::Static, Arc::new(static_prices)),
+    ];
+    sources
+        .iter()
+        .map(|(source, constructor)| (source.name().to_string(), constructor.clone()))
+        .collect()
+}
+
+fn coingecko_api(context: &PriceSourceContext<'_>) -> anyhow::Result<CoinGeckoAPI> {
+    CoinGeckoAPI::new(
+        context.client.clone(),
+        context.base_url.parse()?,
+        context.config.ticker.coingecko_api_key.clone(),
+    )
+}
+
+fn coingecko(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(coingecko_api(context)?))
+}
+
+fn coinmarketcap(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(CoinMarketCapAPI::new(
+        context.client.clone(),
+        context.base_url.parse()?,
+    )))
+}
+
+fn chainlink(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(ChainlinkAPI::new(
+        EthereumGateway::from_config(context.config),
+        &context.config.ticker.chainlink_feeds,
+        context.config.ticker.chainlink_max_age(),
+        coingecko_api(context)?,
+    )))
+}
+
+fn uniswap_v3(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(UniswapV3API::new(
+        EthereumGateway::from_config(context.config),
+        &context.config.ticker.uniswap_v3_pools,
+        context.config.ticker.uniswap_v3_twap_window(),
+        coingecko_api(context)?,
+    )))
+}
+
+fn exchange_api(
+    context: &PriceSourceContext<'_>,
+    exchange: Exchange,
+) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(ExchangeAPI::new(
+        context.client.clone(),
+        context.base_url.parse()?,
+        exchange,
+        &context.config.ticker.exchange_symbols,
+    )))
+}
+
+fn binance(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    exchange_api(context, Exchange::Binance)
+}
+
+fn kraken(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    exchange_api(context, Exchange::Kraken)
+}
+
+fn static_prices(context: &PriceSourceContext<'_>) -> anyhow::Result<SharedTokenPriceAPI> {
+    Ok(Arc::new(StaticPriceAPI::new(
+        &context.config.ticker.static_token_prices,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use num::{rational::Ratio, BigUint};
+    use zksync_types::TokenPrice;
+
+    struct OracleServiceAPI;
+
+    #[async_trait::async_trait]
+    impl TokenPriceAPI for OracleServiceAPI {
+        async fn get_price(&self, _token_symbol: &str) -> anyhow::Result<TokenPrice> {
+            Ok(TokenPrice {
+                usd_price: Ratio::from_integer(BigUint::from(42u32)),
+                last_updated: Utc::now(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_price_source() {
+        let config = ZkSyncConfig::from_env();
+        let client = reqwest::Client::new();
+        let context = PriceSourceContext {
+            config: &config,
+            base_url: "",
+            client: &client,
+        };
+        let source = TokenPriceSource::from_name("OracleService");
+
+        assert!(create_price_source(&source, &context).is_err());
+        register_price_source("OracleService", |_| {
+            Ok(Arc::new(OracleServiceAPI) as SharedTokenPriceAPI)
+        });
+        let api = create_price_source(&source, &context).unwrap();
+        assert_eq!(
+            api.get_price("ETH").await.unwrap().usd_price,
+            Ratio::from_integer(BigUint::from(42u32))
+        );
+
+        // The built-in sources are registered.
+        let api = create_price_source(&TokenPriceSource::Static, &context).unwrap();
+        assert!(api.get_price("UNKNOWN").await.is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Replay of the recorded fee inputs.
+//!
+//! To reproduce the fees calculated during an incident, or to check how a change of the fee
+//! formula affects the past fees, the ticker may be run on the recorded inputs instead of the
+//! live ones. The fees are calculated as of the replay moment: the token prices are the last ones
+//! recorded before it, and so is the gas price. Samples are read from the JSON file, the token
+//! prices missing in the file are read from the price history, so the file may contain only
+//! the gas prices. The prices are reported as fresh, so the fallbacks of the live ticker API
+//! (cache, historical prices, deviation checks) don't interfere with the replay.
+
+// Built-in deps
+use std::{fs, path::Path, sync::Arc};
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_types::{tokens::PriceConfidence, Token, TokenId, TokenLike, TokenPrice};
+use zksync_utils::{BigUintSerdeWrapper, UnsignedRatioSerializeAsDecimal};
+// Local deps
+use super::{
+    storage::{TickerDBStorage, TickerStorage},
+    FeeTickerAPI, GasPriceWei, SourcedTokenPrice,
+};
+
+/// Price source name reported for the replayed prices.
+const REPLAY_PRICE_SOURCE: &str = "replay";
+
+/// USD price of the token recorded at the given moment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordedPrice {
+    pub token: TokenId,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub usd_price: Ratio<BigUint>,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Gas price recorded at the given moment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordedGasPrice {
+    /// Base fee of the block, or the legacy gas price.
+    pub base_fee_wei: BigUintSerdeWrapper,
+    #[serde(default)]
+    pub priority_fee_wei: BigUintSerdeWrapper,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Fee inputs recorded over some period, in any order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecordedSamples {
+    #[serde(default)]
+    pub prices: Vec<RecordedPrice>,
+    #[serde(default)]
+    pub gas_prices: Vec<RecordedGasPrice>,
+}
+
+impl RecordedSamples {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let samples = fs::read_to_string(path)
+            .map_err(|e| format_err!("Can't read the samples file {}: {}", path.display(), e))?;
+        serde_json::from_str(&samples)
+            .map_err(|e| format_err!("Invalid samples file {}: {}", path.display(), e))
+    }
+
+    /// Returns the moment of the latest sample.
+    pub fn last_observed_at(&self) -> Option<DateTime<Utc>> {
+        let prices = self.prices.iter().map(|price| price.observed_at);
+        let gas_prices = self
+            .gas_prices
+            .iter()
+            .map(|gas_price| gas_price.observed_at);
+        prices.chain(gas_prices).max()
+    }
+
+    /// Returns the last price of the token recorded not later than `at`.
+    fn price_at(&self, token: TokenId, at: DateTime<Utc>) -> Option<TokenPrice> {
+        self.prices
+            .iter()
+            .filter(|price| price.token == token && price.observed_at <= at)
+            .max_by_key(|price| price.observed_at)
+            .map(|price| TokenPrice {
+                usd_price: price.usd_price.clone(),
+                last_updated: price.observed_at,
+            })
+    }
+
+    /// Returns the last gas price recorded not later than `at`.
+    fn gas_price_at(&self, at: DateTime<Utc>) -> Option<GasPriceWei> {
+        self.gas_prices
+            .iter()
+            .filter(|gas_price| gas_price.observed_at <= at)
+            .max_by_key(|gas_price| gas_price.observed_at)
+            .map(|gas_price| GasPriceWei {
+                base_fee: gas_price.base_fee_wei.0.clone(),
+                priority_fee: gas_price.priority_fee_wei.0.clone(),
+            })
+    }
+}
+
+/// Ticker API serving the fee inputs recorded before the replay moment.
+#[derive(Debug, Clone)]
+pub struct ReplayTickerApi<S: TickerStorage = TickerDBStorage> {
+    storage: S,
+    samples: Arc<RecordedSamples>,
+    at: DateTime<Utc>,
+}
+
+impl<S: TickerStorage> ReplayTickerApi<S> {
+    /// Creates the API replaying the samples as of `at`, or as of the latest sample if not set.
+    pub fn new(
+        storage: S,
+        samples: RecordedSamples,
+        at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Self> {
+        let at = at
+            .or_else(|| samples.last_observed_at())
+            .ok_or_else(|| format_err!("Replay moment is not set and there are no samples"))?;
+        Ok(Self {
+            storage,
+            samples: Arc::new(samples),
+            at,
+        })
+    }
+}
+
+#[async_trait]
+impl<S: TickerStorage + Send + Sync> FeeTickerAPI for ReplayTickerApi<S> {
+    async fn get_last_quote(&self, token: TokenLike) -> Result<TokenPrice, anyhow::Error> {
+        self.get_sourced_quote(token)
+            .await
+            .map(|sourced_price| sourced_price.price)
+    }
+
+    async fn get_sourced_quote(
+        &self,
+        token: TokenLike,
+    ) -> Result<SourcedTokenPrice, anyhow::Error> {
+        let token = self.get_token(token).await?;
+        let price = match self.samples.price_at(token.id, self.at) {
+            Some(price) => price,
+            None => self
+                .storage
+                .load_price_at(token.id, self.at)
+                .await?
+                .ok_or_else(|| {
+                    format_err!(
+                        "No price of the token {} recorded before {}",
+                        token.id,
+                        self.at
+                    )
+                })?,
+        };
+        Ok(SourcedTokenPrice {
+            price,
+            source: REPLAY_PRICE_SOURCE.to_string(),
+            confidence: PriceConfidence::Fresh,
+        })
+    }
+
+    async fn get_gas_price_wei(&self) -> Result<GasPriceWei, anyhow::Error> {
+        self.samples
+            .gas_price_at(self.at)
+            .ok_or_else(|| format_err!("No gas price recorded before {}", self.at))
+    }
+
+    async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error> {
+        self.storage
+            .get_token(token.clone())
+            .await?
+            .ok_or_else(|| format_err!("Token not found: {:?}", token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fee_ticker::ticker_api::storage::TickerInMemoryStorage;
+    use chrono::Duration;
+    use zksync_types::Address;
+
+    fn price(token: u32, usd_price: u32, observed_at: DateTime<Utc>) -> RecordedPrice {
+        RecordedPrice {
+            token: TokenId(token),
+            usd_price: Ratio::from_integer(BigUint::from(usd_price)),
+            observed_at,
+        }
+    }
+
+    fn gas_price(base_fee: u32, observed_at: DateTime<Utc>) -> RecordedGasPrice {
+        RecordedGasPrice {
+            base_fee_wei: BigUint::from(base_fee).into(),
+            priority_fee_wei: BigUint::from(1u32).into(),
+            observed_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn replayed_inputs() {
+        let start = Utc::now() - Duration::days(1);
+        let tokens = vec![
+            Token::new(TokenId(0), Address::zero(), "ETH", 18),
+            Token::new(TokenId(1), Address::repeat_byte(1), "DAI", 18),
+        ];
+        let storage = TickerInMemoryStorage::new().with_tokens(tokens);
+        storage
+            .store_price_observation(
+                TokenId(1),
+                "coingecko",
+                &TokenPrice {
+                    usd_price: Ratio::from_integer(BigUint::from(1u32)),
+                    last_updated: start,
+                },
+            )
+            .await
+            .unwrap();
+
+        let samples = RecordedSamples {
+            prices: vec![
+                price(0, 2000, start),
+                price(0, 2100, start + Duration::minutes(10)),
+                price(0, 2200, start + Duration::minutes(30)),
+            ],
+            gas_prices: vec![
+                gas_price(100, start),
+                gas_price(120, start + Duration::minutes(20)),
+            ],
+        };
+        let at = start + Duration::minutes(25);
+        let api = ReplayTickerApi::new(storage.clone(), samples.clone(), Some(at)).unwrap();
+
+        // Prices and the gas price are the last ones recorded before the replay moment.
+        let eth_price = api.get_last_quote(TokenLike::Id(TokenId(0))).await.unwrap();
+        assert_eq!(eth_price.usd_price, Ratio::from_integer(2100u32.into()));
+        assert_eq!(eth_price.last_updated, start + Duration::minutes(10));
+        assert_eq!(
+            api.get_gas_price_wei().await.unwrap(),
+            GasPriceWei {
+                base_fee: BigUint::from(120u32),
+                priority_fee: BigUint::from(1u32),
+            }
+        );
+
+        // Prices missing in the samples are taken from the price history.
+        let dai_price = api.get_last_quote(TokenLike::Id(TokenId(1))).await.unwrap();
+        assert_eq!(dai_price.usd_price, Ratio::from_integer(1u32.into()));
+
+        // Nothing is recorded before the replay moment.
+        let api =
+            ReplayTickerApi::new(storage, samples, Some(start - Duration::minutes(1))).unwrap();
+        assert!(api.get_last_quote(TokenLike::Id(TokenId(0))).await.is_err());
+        assert!(api.get_gas_price_wei().await.is_err());
+    }
+
+    #[test]
+    fn replay_moment_defaults_to_latest_sample() {
+        let start = Utc::now();
+        let samples = RecordedSamples {
+            prices: vec![price(0, 2000, start)],
+            gas_prices: vec![gas_price(100, start + Duration::minutes(5))],
+        };
+        let api = ReplayTickerApi::new(TickerInMemoryStorage::new(), samples, None).unwrap();
+        assert_eq!(api.at, start + Duration::minutes(5));
+
+        assert!(
+            ReplayTickerApi::new(TickerInMemoryStorage::new(), Default::default(), None).is_err()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Fixed token prices for the local development.
+//!
+//! Devnets and CI may run the whole API without the access to the price APIs and without
+//! the mock server: the prices of the tokens are set in the config and are always up to date.
+
+// Built-in deps
+use std::collections::HashMap;
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use zksync_config::configs::ticker::StaticTokenPriceConfig;
+use zksync_types::TokenPrice;
+// Local deps
+use super::TokenPriceAPI;
+
+#[derive(Debug, Clone)]
+pub struct StaticPriceAPI {
+    /// Prices by the uppercase token symbols.
+    prices: HashMap<String, Ratio<BigUint>>,
+}
+
+impl StaticPriceAPI {
+    pub fn new(prices: &[StaticTokenPriceConfig]) -> Self {
+        let prices = prices
+            .iter()
+            .map(|price| (price.token.to_uppercase(), price.usd_price.clone()))
+            .collect();
+        Self { prices }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for StaticPriceAPI {
+    async fn get_price(&self, token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        let usd_price = self
+            .prices
+            .get(&token_symbol.to_uppercase())
+            .cloned()
+            .ok_or_else(|| format_err!("Static price of '{}' is not set", token_symbol))?;
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn static_prices() {
+        let api = StaticPriceAPI::new(&[StaticTokenPriceConfig {
+            token: "ETH".into(),
+            usd_price: Ratio::from_integer(BigUint::from(3000u32)),
+        }]);
+        assert_eq!(
+            api.get_price("eth").await.unwrap().usd_price,
+            Ratio::from_integer(BigUint::from(3000u32))
+        );
+        assert!(api.get_price("DAI").await.is_err());
+    }
+}
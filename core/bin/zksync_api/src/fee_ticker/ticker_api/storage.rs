@@ -0,0 +1,310 @@
+//! Storage backends used by the ticker API.
+//!
+//! `TickerApi` only needs a handful of storage interactions (token lookup, historical
+//! prices, price history and the gas prices), so they are gathered in the `TickerStorage` trait.
+//! The same trait backs the replay of the recorded fee inputs.
+//! The production backend is the Postgres database, while the in-memory backend allows
+//! to run the ticker in tests and local setups without a provisioned database.
+
+// Built-in deps
+use std::{collections::HashMap, sync::Arc};
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+// Workspace deps
+use zksync_storage::ConnectionPool;
+use zksync_types::{Token, TokenId, TokenLike, TokenPrice, U256};
+use zksync_utils::big_decimal_to_ratio;
+// Local deps
+use crate::utils::token_db_cache::TokenDBCache;
+
+/// Storage interactions required by the ticker API.
+#[async_trait]
+pub trait TickerStorage {
+    /// Loads the token by its ID, address or symbol.
+    async fn get_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>>;
+
+    /// Loads the last price of the token successfully received from the price API.
+    async fn get_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<TokenPrice>>;
+
+    /// Stores the price received from the price API so it can be used as a fallback.
+    async fn update_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+        price: TokenPrice,
+    ) -> anyhow::Result<()>;
+
+    /// Stores the price received from the price API to the price history.
+    async fn store_price_observation(
+        &self,
+        token_id: TokenId,
+        source: &str,
+        price: &TokenPrice,
+    ) -> anyhow::Result<()>;
+
+    /// Loads the last price of the token from the price history observed not later than `at`.
+    async fn load_price_at(
+        &self,
+        token_id: TokenId,
+        at: DateTime<Utc>,
+    ) -> anyhow::Result<Option<TokenPrice>>;
+
+    /// Loads the average gas price used for the L1 transactions.
+    async fn load_average_gas_price(&self) -> anyhow::Result<Option<U256>>;
+
+    /// Loads the base fee and the priority fee observed in the network,
+    /// if it supports EIP-1559.
+    async fn load_eip1559_fees(&self) -> anyhow::Result<Option<(U256, U256)>>;
+}
+
+/// Ticker storage backed by the Postgres database.
+#[derive(Debug, Clone)]
+pub struct TickerDBStorage {
+    pool: ConnectionPool,
+    token_db_cache: TokenDBCache,
+}
+
+impl TickerDBStorage {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self {
+            pool,
+            token_db_cache: TokenDBCache::new(),
+        }
+    }
+
+    pub fn with_token_db_cache(self, token_db_cache: TokenDBCache) -> Self {
+        Self {
+            token_db_cache,
+            ..self
+        }
+    }
+}
+
+#[async_trait]
+impl TickerStorage for TickerDBStorage {
+    async fn get_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
+        self.token_db_cache
+            .get_token(&mut self.pool.access_storage().await?, token)
+            .await
+    }
+
+    async fn get_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<TokenPrice>> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        storage
+            .tokens_schema()
+            .get_historical_ticker_price(token_id)
+            .await
+            .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e))
+    }
+
+    async fn update_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+        price: TokenPrice,
+    ) -> anyhow::Result<()> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(token_id, price)
+            .await
+            .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e))
+    }
+
+    async fn store_price_observation(
+        &self,
+        token_id: TokenId,
+        source: &str,
+        price: &TokenPrice,
+    ) -> anyhow::Result<()> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        storage
+            .tokens_schema()
+            .store_price_observation(token_id, source, price)
+            .await
+            .map_err(|e| format_err!("Can't store price observation: {}", e))
+    }
+
+    async fn load_price_at(
+        &self,
+        token_id: TokenId,
+        at: DateTime<Utc>,
+    ) -> anyhow::Result<Option<TokenPrice>> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        let observation = storage
+            .tokens_schema()
+            .load_price_at(token_id, at)
+            .await
+            .map_err(|e| format_err!("Can't load price history: {}", e))?;
+        observation
+            .map(|observation| {
+                Ok(TokenPrice {
+                    usd_price: big_decimal_to_ratio(&observation.usd_price)?,
+                    last_updated: observation.observed_at,
+                })
+            })
+            .transpose()
+    }
+
+    async fn load_average_gas_price(&self) -> anyhow::Result<Option<U256>> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        storage.ethereum_schema().load_average_gas_price().await
+    }
+
+    async fn load_eip1559_fees(&self) -> anyhow::Result<Option<(U256, U256)>> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+
+        storage.ethereum_schema().load_eip1559_fees().await
+    }
+}
+
+/// Ticker storage which keeps all the data in memory.
+#[derive(Debug, Clone, Default)]
+pub struct TickerInMemoryStorage {
+    tokens: Arc<Mutex<HashMap<TokenId, Token>>>,
+    historical_prices: Arc<Mutex<HashMap<TokenId, TokenPrice>>>,
+    price_history: Arc<Mutex<Vec<(TokenId, String, TokenPrice)>>>,
+    average_gas_price: Arc<Mutex<Option<U256>>>,
+    eip1559_fees: Arc<Mutex<Option<(U256, U256)>>>,
+}
+
+impl TickerInMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tokens(self, tokens: impl IntoIterator<Item = Token>) -> Self {
+        let tokens = tokens.into_iter().map(|token| (token.id, token)).collect();
+        Self {
+            tokens: Arc::new(Mutex::new(tokens)),
+            ..self
+        }
+    }
+
+    pub fn with_average_gas_price(self, average_gas_price: U256) -> Self {
+        Self {
+            average_gas_price: Arc::new(Mutex::new(Some(average_gas_price))),
+            ..self
+        }
+    }
+
+    pub fn with_eip1559_fees(self, base_fee: U256, priority_fee: U256) -> Self {
+        Self {
+            eip1559_fees: Arc::new(Mutex::new(Some((base_fee, priority_fee)))),
+            ..self
+        }
+    }
+
+    /// Returns all the stored price observations in the order they were received.
+    pub async fn price_history(&self) -> Vec<(TokenId, String, TokenPrice)> {
+        self.price_history.lock().await.clone()
+    }
+}
+
+#[async_trait]
+impl TickerStorage for TickerInMemoryStorage {
+    async fn get_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
+        let tokens = self.tokens.lock().await;
+        let token = match token {
+            TokenLike::Id(id) => tokens.get(&id).cloned(),
+            TokenLike::Address(address) => tokens
+                .values()
+                .find(|token| token.address == address)
+                .cloned(),
+            TokenLike::Symbol(symbol) => tokens
+                .values()
+                .find(|token| token.symbol == symbol)
+                .cloned(),
+        };
+        Ok(token)
+    }
+
+    async fn get_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+    ) -> anyhow::Result<Option<TokenPrice>> {
+        Ok(self.historical_prices.lock().await.get(&token_id).cloned())
+    }
+
+    async fn update_historical_ticker_price(
+        &self,
+        token_id: TokenId,
+        price: TokenPrice,
+    ) -> anyhow::Result<()> {
+        self.historical_prices.lock().await.insert(token_id, price);
+        Ok(())
+    }
+
+    async fn store_price_observation(
+        &self,
+        token_id: TokenId,
+        source: &str,
+        price: &TokenPrice,
+    ) -> anyhow::Result<()> {
+        self.price_history
+            .lock()
+            .await
+            .push((token_id, source.to_string(), price.clone()));
+        Ok(())
+    }
+
+    async fn load_price_at(
+        &self,
+        token_id: TokenId,
+        at: DateTime<Utc>,
+    ) -> anyhow::Result<Option<TokenPrice>> {
+        let price = self
+            .price_history
+            .lock()
+            .await
+            .iter()
+            .filter(|(id, _, price)| *id == token_id && price.last_updated <= at)
+            .max_by_key(|(_, _, price)| price.last_updated)
+            .map(|(_, _, price)| price.clone());
+        Ok(price)
+    }
+
+    async fn load_average_gas_price(&self) -> anyhow::Result<Option<U256>> {
+        Ok(*self.average_gas_price.lock().await)
+    }
+
+    async fn load_eip1559_fees(&self) -> anyhow::Result<Option<(U256, U256)>> {
+        Ok(*self.eip1559_fees.lock().await)
+    }
+}
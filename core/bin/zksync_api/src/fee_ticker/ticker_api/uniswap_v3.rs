@@ -0,0 +1,273 @@
+//! Time-weighted average prices of the Uniswap V3 pools.
+//!
+//! Newly listed tokens are usually traded on Uniswap long before CoinGecko starts tracking them,
+//! so the fees may still be quoted in them. Every configured token is priced by its pool against
+//! the other pool token, e.g. WETH, which is priced by the fallback API, as are the tokens without
+//! the pools. The pool price is averaged over the window from the tick observations the pool keeps,
+//! so it can't be moved for the moment of the request by a single large swap.
+
+// Built-in deps
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+// External deps
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use num::{rational::Ratio, traits::Pow, BigUint, FromPrimitive};
+use web3::contract::{
+    tokens::{Detokenize, Tokenize},
+    Options,
+};
+// Workspace deps
+use zksync_config::configs::ticker::UniswapV3PoolConfig;
+use zksync_contracts::{erc20_metadata_contract, uniswap_v3_pool_contract};
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, TokenPrice, U256};
+use zksync_utils::big_decimal_to_ratio;
+// Local deps
+use super::TokenPriceAPI;
+
+/// Tick bounds of the Uniswap V3 pools.
+const MAX_TICK: i64 = 887_272;
+
+/// Token priced by the pool and the other pool token it's priced against.
+#[derive(Debug, Clone)]
+struct PoolTokens {
+    token_is_token0: bool,
+    decimals: u8,
+    quote_symbol: String,
+    quote_decimals: u8,
+}
+
+/// Uniswap V3 pools with the API pricing their quote tokens and the tokens without the pools.
+#[derive(Debug, Clone)]
+pub struct UniswapV3API<F> {
+    client: EthereumGateway,
+    pool_contract: ethabi::Contract,
+    erc20_contract: ethabi::Contract,
+    /// Pool addresses by the uppercase token symbols.
+    pools: HashMap<String, Address>,
+    /// Pool tokens never change, so they are loaded once.
+    pool_tokens: Arc<Mutex<HashMap<Address, PoolTokens>>>,
+    window: u32,
+    fallback: F,
+}
+
+impl<F: TokenPriceAPI> UniswapV3API<F> {
+    pub fn new(
+        client: EthereumGateway,
+        pools: &[UniswapV3PoolConfig],
+        window: Duration,
+        fallback: F,
+    ) -> Self {
+        let pools = pools
+            .iter()
+            .map(|pool| (pool.token.to_uppercase(), pool.pool))
+            .collect();
+        Self {
+            client,
+            pool_contract: uniswap_v3_pool_contract(),
+            erc20_contract: erc20_metadata_contract(),
+            pools,
+            pool_tokens: Arc::default(),
+            window: window.as_secs() as u32,
+            fallback,
+        }
+    }
+
+    async fn call<R, P>(
+        &self,
+        address: Address,
+        contract: &ethabi::Contract,
+        function: &str,
+        params: P,
+    ) -> anyhow::Result<R>
+    where
+        R: Detokenize + Unpin,
+        P: Tokenize + Clone,
+    {
+        self.client
+            .call_contract_function(
+                function,
+                params,
+                None,
+                Options::default(),
+                None,
+                address,
+                contract.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to call `{}` of {:?}: {}", function, address, e))
+    }
+
+    async fn erc20_metadata(&self, token: Address) -> anyhow::Result<(String, u8)> {
+        let symbol: String = self.call(token, &self.erc20_contract, "symbol", ()).await?;
+        let decimals: U256 = self
+            .call(token, &self.erc20_contract, "decimals", ())
+            .await?;
+        anyhow::ensure!(
+            decimals <= U256::from(u8::MAX),
+            "Decimals {} of {:?} are invalid",
+            decimals,
+            token
+        );
+        Ok((symbol, decimals.as_u32() as u8))
+    }
+
+    async fn pool_tokens(&self, token_symbol: &str, pool: Address) -> anyhow::Result<PoolTokens> {
+        if let Some(tokens) = self.pool_tokens.lock().unwrap().get(&pool) {
+            return Ok(tokens.clone());
+        }
+
+        let token0: Address = self.call(pool, &self.pool_contract, "token0", ()).await?;
+        let token1: Address = self.call(pool, &self.pool_contract, "token1", ()).await?;
+        let (symbol0, decimals0) = self.erc20_metadata(token0).await?;
+        let (symbol1, decimals1) = self.erc20_metadata(token1).await?;
+        let tokens = if symbol0.eq_ignore_ascii_case(token_symbol) {
+            PoolTokens {
+                token_is_token0: true,
+                decimals: decimals0,
+                quote_symbol: symbol1,
+                quote_decimals: decimals1,
+            }
+        } else if symbol1.eq_ignore_ascii_case(token_symbol) {
+            PoolTokens {
+                token_is_token0: false,
+                decimals: decimals1,
+                quote_symbol: symbol0,
+                quote_decimals: decimals0,
+            }
+        } else {
+            anyhow::bail!(
+                "Uniswap V3 pool {:?} of {} trades {} and {}",
+                pool,
+                token_symbol,
+                symbol0,
+                symbol1
+            );
+        };
+
+        self.pool_tokens
+            .lock()
+            .unwrap()
+            .insert(pool, tokens.clone());
+        Ok(tokens)
+    }
+
+    /// Prices the token by the pool average price and the price of the other pool token.
+    async fn pool_price(&self, token_symbol: &str, pool: Address) -> anyhow::Result<TokenPrice> {
+        let tokens = self.pool_tokens(token_symbol, pool).await?;
+        let seconds_agos = vec![U256::from(self.window), U256::zero()];
+        let (tick_cumulatives, _): (Vec<U256>, Vec<U256>) = self
+            .call(pool, &self.pool_contract, "observe", (seconds_agos,))
+            .await?;
+        let tick = average_tick(&tick_cumulatives, self.window)?;
+        let pool_price = pool_price(
+            tick,
+            tokens.token_is_token0,
+            tokens.decimals,
+            tokens.quote_decimals,
+        )?;
+
+        let quote_price = self.fallback.get_price(&tokens.quote_symbol).await?;
+        Ok(TokenPrice {
+            usd_price: pool_price * quote_price.usd_price,
+            last_updated: quote_price.last_updated,
+        })
+    }
+}
+
+#[async_trait]
+impl<F: TokenPriceAPI + Send + Sync> TokenPriceAPI for UniswapV3API<F> {
+    async fn get_price(&self, token_symbol: &str) -> anyhow::Result<TokenPrice> {
+        match self.pools.get(&token_symbol.to_uppercase()) {
+            Some(&pool) => self.pool_price(token_symbol, pool).await,
+            None => self.fallback.get_price(token_symbol).await,
+        }
+    }
+}
+
+/// Calculates the average tick over the window from the cumulative ticks observed at its start
+/// and end, rounding to negative infinity like the Uniswap oracle library does.
+fn average_tick(tick_cumulatives: &[U256], window: u32) -> anyhow::Result<i64> {
+    let (start, end) = match tick_cumulatives {
+        [start, end] => (*start, *end),
+        _ => anyhow::bail!(
+            "Expected 2 tick observations, got {}",
+            tick_cumulatives.len()
+        ),
+    };
+    // The cumulative ticks are `int56`, so their difference is taken modulo 2^256 and
+    // its low bits are the signed difference.
+    let delta = end.overflowing_sub(start).0.low_u64() as i64;
+    let window = i64::from(window);
+    let mut tick = delta / window;
+    if delta < 0 && delta % window != 0 {
+        tick -= 1;
+    }
+    anyhow::ensure!(
+        (-MAX_TICK..=MAX_TICK).contains(&tick),
+        "Average tick {} is out of bounds",
+        tick
+    );
+    Ok(tick)
+}
+
+/// Converts the tick to the price of one token in the quote tokens. The tick is the power
+/// of 1.0001 equal to the price of the smallest unit of `token0` in the units of `token1`.
+fn pool_price(
+    tick: i64,
+    token_is_token0: bool,
+    decimals: u8,
+    quote_decimals: u8,
+) -> anyhow::Result<Ratio<BigUint>> {
+    let tick = if token_is_token0 { tick } else { -tick };
+    let unit_price = 1.0001f64.powi(tick as i32);
+    let unit_price = BigDecimal::from_f64(unit_price)
+        .ok_or_else(|| anyhow::format_err!("Invalid pool price {}", unit_price))?;
+    let unit_price = big_decimal_to_ratio(&unit_price)?;
+
+    let scale = |decimals: u8| Ratio::from_integer(BigUint::from(10u32).pow(u32::from(decimals)));
+    Ok(unit_price * scale(decimals) / scale(quote_decimals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes the signed value the way `int56` is returned by the contract.
+    fn int56(value: i64) -> U256 {
+        if value < 0 {
+            U256::max_value() - U256::from((-value) as u64) + 1
+        } else {
+            U256::from(value)
+        }
+    }
+
+    #[test]
+    fn twap_price() {
+        // The tick is rounded to negative infinity.
+        let observations = [int56(-1_000), int56(-1_000 + 1_800 * 10 + 1)];
+        assert_eq!(average_tick(&observations, 1_800).unwrap(), 10);
+        let observations = [int56(1_000), int56(1_000 - 1_800 * 10 - 1)];
+        assert_eq!(average_tick(&observations, 1_800).unwrap(), -11);
+        let observations = [int56(0), int56(1_800 * (MAX_TICK + 1))];
+        assert!(average_tick(&observations, 1_800).is_err());
+
+        assert_eq!(
+            pool_price(0, true, 18, 18).unwrap(),
+            Ratio::from_integer(BigUint::from(1u32))
+        );
+
+        // USDC/WETH pool prices WETH (token1, 18 decimals) in USDC (token0, 6 decimals)
+        // at about 2000 USDC, since 1.0001^(-200_311) * 10^12 = 2000.04.
+        let price = pool_price(200_311, false, 18, 6).unwrap();
+        assert!(price > Ratio::from_integer(BigUint::from(1999u32)));
+        assert!(price < Ratio::from_integer(BigUint::from(2001u32)));
+        // And the other way round.
+        let price = pool_price(200_311, true, 6, 18).unwrap();
+        let inverse = Ratio::new(BigUint::from(1u32), BigUint::from(2000u32));
+        assert!(price > inverse.clone() * Ratio::new(BigUint::from(99u32), BigUint::from(100u32)));
+        assert!(price < inverse * Ratio::new(BigUint::from(101u32), BigUint::from(100u32)));
+    }
+}
@@ -5,7 +5,7 @@
 use async_trait::async_trait;
 // Workspace deps
 use zksync_storage::ConnectionPool;
-use zksync_types::Address;
+use zksync_types::{Address, PubKeyHash};
 // Local deps
 
 /// Api responsible for querying for TokenPrices
@@ -14,6 +14,9 @@ pub trait FeeTickerInfo {
     /// Check whether account exists in the zkSync network or not.
     /// Returns `true` if account does not yet exist in the zkSync network.
     async fn is_account_new(&mut self, address: Address) -> bool;
+
+    /// Returns `true` if the account exists and its signing key is set.
+    async fn is_signing_key_set(&mut self, address: Address) -> bool;
 }
 
 #[derive(Clone)]
@@ -46,4 +49,23 @@ impl FeeTickerInfo for TickerInfo {
         // If account is `Some(_)` then it's not new.
         account_state.committed.is_none()
     }
+
+    async fn is_signing_key_set(&mut self, address: Address) -> bool {
+        let mut storage = self
+            .db
+            .access_storage()
+            .await
+            .expect("Unable to establish connection to db");
+
+        let account_state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(address)
+            .await
+            .expect("Unable to query account state from the database");
+
+        account_state.committed.map_or(false, |(_, account)| {
+            account.pub_key_hash != PubKeyHash::default()
+        })
+    }
 }
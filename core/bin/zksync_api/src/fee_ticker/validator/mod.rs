@@ -15,7 +15,7 @@ use chrono::Utc;
 
 // Workspace uses
 use zksync_types::{
-    tokens::{Token, TokenLike, TokenMarketVolume},
+    tokens::{FeeTokenStatus, Token, TokenLike, TokenLiquidity, TokenMarketVolume},
     Address,
 };
 
@@ -29,7 +29,7 @@ const CRITICAL_NUMBER_OF_ERRORS: u32 = 500;
 #[derive(Clone, Debug)]
 struct AcceptanceData {
     last_refresh: Instant,
-    allowed: bool,
+    status: FeeTokenStatus,
 }
 
 /// We don't want to send requests to the Internet for every request from users.
@@ -132,30 +132,81 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
 
     /// Returns `true` if token can be used to pay fees.
     pub(crate) async fn token_allowed(&mut self, token: TokenLike) -> anyhow::Result<bool> {
+        Ok(self.token_status(token).await?.allowed)
+    }
+
+    /// Returns whether the token can be used to pay fees along with the reason of the decision.
+    pub(crate) async fn token_status(
+        &mut self,
+        token: TokenLike,
+    ) -> anyhow::Result<FeeTokenStatus> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
             if self.unconditionally_valid.contains(&token.address) {
-                return Ok(true);
+                return Ok(FeeTokenStatus {
+                    allowed: true,
+                    reason: "Token is unconditionally allowed".to_string(),
+                });
+            }
+            let status = self.check_token(token.clone()).await?;
+            if !status.allowed {
+                metrics::counter!(
+                    "ticker.validator.rejected_tokens",
+                    1,
+                    "token" => token.symbol,
+                    "reason" => "low_liquidity"
+                );
             }
-            self.check_token(token).await
+            Ok(status)
         } else {
             // Unknown tokens aren't suitable for our needs, obviously.
-            Ok(false)
+            metrics::counter!(
+                "ticker.validator.rejected_tokens",
+                1,
+                "token" => "unknown",
+                "reason" => "unknown_token"
+            );
+            Ok(FeeTokenStatus {
+                allowed: false,
+                reason: "Token is unknown".to_string(),
+            })
         }
     }
 
+    /// Returns the last measured liquidity of the token, or `None` if it's not measured yet.
+    pub(crate) async fn token_liquidity(
+        &mut self,
+        token: TokenLike,
+    ) -> anyhow::Result<Option<TokenLiquidity>> {
+        let token = self
+            .resolve_token(token)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Token is unknown"))?;
+        let volume = match self.get_token_market_volume(&token).await? {
+            Some(volume) => volume,
+            None => return Ok(None),
+        };
+
+        Ok(Some(TokenLiquidity {
+            volume: volume.market_volume,
+            last_updated: volume.last_updated,
+            required_volume: big_decimal_to_ratio(&self.liquidity_volume)?,
+            unconditionally_allowed: self.unconditionally_valid.contains(&token.address),
+        }))
+    }
+
     async fn resolve_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
         self.tokens_cache.get_token(token).await
     }
 
-    async fn check_token(&mut self, token: Token) -> anyhow::Result<bool> {
+    async fn check_token(&mut self, token: Token) -> anyhow::Result<FeeTokenStatus> {
         let start = Instant::now();
         if let Some(acceptance_data) = self.tokens.get(&token.address) {
             if chrono::Duration::from_std(acceptance_data.last_refresh.elapsed())
                 .expect("Correct duration")
                 < self.available_time
             {
-                return Ok(acceptance_data.allowed);
+                return Ok(acceptance_data.status.clone());
             }
         }
 
@@ -167,16 +218,24 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
         if Utc::now() - volume.last_updated > self.available_time {
             vlog::warn!("Token market amount for {} is not relevant", &token.symbol)
         }
-        let allowed = ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume;
+        let market_volume = ratio_to_big_decimal(&volume.market_volume, 2);
+        let allowed = market_volume >= self.liquidity_volume;
+        let reason = format!(
+            "Market volume {} is {} the required liquidity volume {}",
+            market_volume,
+            if allowed { "not below" } else { "below" },
+            self.liquidity_volume
+        );
+        let status = FeeTokenStatus { allowed, reason };
         self.tokens.insert(
             token.address,
             AcceptanceData {
                 last_refresh: Instant::now(),
-                allowed,
+                status: status.clone(),
             },
         );
         metrics::histogram!("ticker.validator.check_token", start.elapsed());
-        Ok(allowed)
+        Ok(status)
     }
     // I think, it's redundant method and we could remove watcher from validator and store it only in updater
     async fn get_remote_token_market(
@@ -340,7 +399,54 @@ mod tests {
         assert_eq!(dai_allowed, true);
         assert_eq!(phnx_allowed, false);
         assert_eq!(eth_allowed, true);
-        assert!(validator.tokens.get(&dai_token_address).unwrap().allowed);
-        assert!(!validator.tokens.get(&phnx_token_address).unwrap().allowed);
+        assert!(
+            validator
+                .tokens
+                .get(&dai_token_address)
+                .unwrap()
+                .status
+                .allowed
+        );
+        assert!(
+            !validator
+                .tokens
+                .get(&phnx_token_address)
+                .unwrap()
+                .status
+                .allowed
+        );
+
+        let phnx_status = validator
+            .token_status(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap();
+        assert!(phnx_status.reason.contains("below"));
+
+        let phnx_liquidity = validator
+            .token_liquidity(TokenLike::Address(phnx_token_address))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(phnx_liquidity.volume, new_phnx_token_market.market_volume);
+        assert_eq!(
+            phnx_liquidity.required_volume,
+            big_decimal_to_ratio(&BigDecimal::from(100)).unwrap()
+        );
+        assert!(!phnx_liquidity.unconditionally_allowed);
+        // The liquidity of ETH is not measured.
+        let eth_liquidity = validator
+            .token_liquidity(TokenLike::Address(eth_address))
+            .await
+            .unwrap();
+        assert!(eth_liquidity.is_none());
+        assert!(validator
+            .token_liquidity(TokenLike::Symbol("UNKNOWN".to_string()))
+            .await
+            .is_err());
+        let unknown_status = validator
+            .token_status(TokenLike::Symbol("UNKNOWN".to_string()))
+            .await
+            .unwrap();
+        assert!(!unknown_status.allowed);
     }
 }
@@ -0,0 +1,74 @@
+//! Fee waivers.
+//!
+//! Some operations are not charged at all, e.g. the first `ChangePubKey` of the account
+//! or the withdrawals of the token being delisted. Waiver applies to the fee type, may be
+//! limited to the token the fee is paid in and to the accounts which signing key is not set yet.
+//! The ticker returns zero fee flagged as waived for such operations, and the transaction
+//! is accepted with any fee. Waivers are stored in the database and reloaded by the ticker
+//! together with the other settings.
+
+// External deps
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_storage::fee_waivers::records::{NewFeeWaiver, StoredFeeWaiver};
+use zksync_types::{OutputFeeType, TokenId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeWaiver {
+    pub id: i32,
+    pub fee_type: OutputFeeType,
+    /// Token the fee is paid in, the waiver applies to all the tokens if not set.
+    pub token: Option<TokenId>,
+    /// The waiver only applies to the accounts which signing key is not set yet.
+    pub unset_signing_key_only: bool,
+    /// The waiver doesn't expire if not set.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl FeeWaiver {
+    pub fn from_stored(waiver: StoredFeeWaiver) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: waiver.id,
+            fee_type: serde_json::from_value(waiver.fee_type)?,
+            token: waiver.token_id.map(|id| TokenId(id as u16)),
+            unset_signing_key_only: waiver.unset_signing_key_only,
+            expires_at: waiver.expires_at,
+        })
+    }
+
+    /// Returns `true` if the waiver applies to the fee of the given type paid in the token
+    /// at the given moment. The account condition is checked separately.
+    pub fn applies_to(&self, fee_type: OutputFeeType, token: TokenId, now: DateTime<Utc>) -> bool {
+        self.fee_type == fee_type
+            && self
+                .token
+                .map_or(true, |waiver_token| waiver_token == token)
+            && self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+}
+
+/// Request to add the waiver.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewWaiverRequest {
+    pub fee_type: OutputFeeType,
+    pub token: Option<TokenId>,
+    #[serde(default)]
+    pub unset_signing_key_only: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl NewWaiverRequest {
+    pub fn into_record(self) -> anyhow::Result<NewFeeWaiver> {
+        if let Some(expires_at) = self.expires_at {
+            anyhow::ensure!(Utc::now() < expires_at, "waiver must expire in the future");
+        }
+
+        Ok(NewFeeWaiver {
+            fee_type: serde_json::to_value(self.fee_type)?,
+            token_id: self.token.map(|token| i32::from(*token)),
+            unset_signing_key_only: self.unset_signing_key_only,
+            expires_at: self.expires_at,
+        })
+    }
+}
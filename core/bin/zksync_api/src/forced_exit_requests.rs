@@ -0,0 +1,240 @@
+//! Service fulfilling the paid forced exit requests.
+//!
+//! Requests accepted by the API are paid by the L2 transfers of ETH from the requester to the
+//! funding account. This task matches the executed transfers with the pending requests and,
+//! once the request is paid, sends the `ForcedExit` transactions initiated by the funding account
+//! for every requested token the target account has funds in. Since the operator is already paid
+//! in ETH, the transactions are sent directly to the mempool with zero fee.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+use num::Zero;
+use tokio::{task::JoinHandle, time};
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_crypto::{
+    franklin_crypto::{
+        alt_babyjubjub::fs::FsRepr,
+        bellman::pairing::ff::{PrimeField, PrimeFieldRepr},
+    },
+    priv_key_from_fs, Fs, PrivateKey,
+};
+use zksync_storage::{
+    forced_exit_requests::{records::StoredForcedExitRequest, FORCED_EXIT_REQUEST_PENDING},
+    ConnectionPool,
+};
+use zksync_types::{
+    tx::TimeRange, AccountId, Address, ForcedExit, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx, H256,
+};
+// Local deps
+use crate::core_api_client::CoreApiClient;
+
+/// Maximum amount of requests processed within one iteration.
+const PROCESSING_BATCH_SIZE: i64 = 10;
+
+struct ForcedExitRequestsService {
+    pool: ConnectionPool,
+    core_api_client: CoreApiClient,
+    funding_account: Address,
+    private_key: PrivateKey,
+    poll_interval: Duration,
+    /// ID and the next nonce of the funding account. Loaded from the storage on the first use
+    /// and after the failures, and tracked locally otherwise, since the sent transactions
+    /// may not be executed yet.
+    funding_account_state: Option<(AccountId, Nonce)>,
+}
+
+impl ForcedExitRequestsService {
+    async fn run(mut self) {
+        let mut timer = time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(e) = self.process_requests().await {
+                vlog::error!("Failed to process the forced exit requests: {}", e);
+                self.funding_account_state = None;
+            }
+        }
+    }
+
+    async fn process_requests(&mut self) -> anyhow::Result<()> {
+        let requests = self
+            .pool
+            .access_storage()
+            .await?
+            .forced_exit_requests_schema()
+            .load_unfulfilled(PROCESSING_BATCH_SIZE)
+            .await?;
+
+        for request in requests {
+            if request.status == FORCED_EXIT_REQUEST_PENDING
+                && !self.check_payment(&request).await?
+            {
+                continue;
+            }
+            self.fulfill_request(&request).await?;
+        }
+
+        // Requests are expired only after the payments are checked, so the request paid
+        // in time is not expired even if the payment is noticed after the deadline.
+        let expired = self
+            .pool
+            .access_storage()
+            .await?
+            .forced_exit_requests_schema()
+            .expire_unpaid(Utc::now())
+            .await?;
+        if expired > 0 {
+            metrics::counter!("forced_exit_requests.expired_requests", expired);
+        }
+
+        Ok(())
+    }
+
+    /// Looks for the transfer paying for the request and marks the request as paid if it's found.
+    async fn check_payment(&self, request: &StoredForcedExitRequest) -> anyhow::Result<bool> {
+        let mut storage = self.pool.access_storage().await?;
+        let payments = storage
+            .forced_exit_requests_schema()
+            .find_payments(
+                &request.requester,
+                self.funding_account.as_bytes(),
+                request.created_at,
+            )
+            .await?;
+
+        let payment = payments.into_iter().find(|payment| {
+            payment.token == 0
+                && payment.amount >= request.price_in_wei
+                && payment.created_at <= request.valid_until
+        });
+        match payment {
+            Some(payment) => {
+                storage
+                    .forced_exit_requests_schema()
+                    .mark_paid(request.id, &payment.tx_hash)
+                    .await?;
+                vlog::info!("Forced exit request #{} is paid", request.id);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn fulfill_request(&mut self, request: &StoredForcedExitRequest) -> anyhow::Result<()> {
+        let target = Address::from_slice(&request.target);
+        let target_account = self
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .account_state_by_address(target)
+            .await?
+            .committed
+            .map(|(_, account)| account)
+            .unwrap_or_default();
+
+        let mut tx_hashes = Vec::new();
+        for &token in &request.tokens {
+            let token = TokenId(token as u16);
+            if target_account.get_balance(token).is_zero() {
+                continue;
+            }
+
+            let (account_id, nonce) = self.funding_account_state().await?;
+            let tx = ForcedExit::new_signed(
+                account_id,
+                target,
+                token,
+                Zero::zero(),
+                nonce,
+                TimeRange::default(),
+                &self.private_key,
+            )?;
+            let tx = ZkSyncTx::from(tx);
+            let tx_hash = tx.hash();
+
+            self.core_api_client
+                .send_tx(SignedZkSyncTx {
+                    tx,
+                    eth_sign_data: None,
+                })
+                .await?
+                .map_err(|e| anyhow::anyhow!("ForcedExit was rejected: {}", e))?;
+            self.funding_account_state = Some((account_id, nonce + 1));
+            tx_hashes.push(tx_hash.to_string());
+        }
+
+        self.pool
+            .access_storage()
+            .await?
+            .forced_exit_requests_schema()
+            .mark_fulfilled(request.id, &tx_hashes)
+            .await?;
+        vlog::info!(
+            "Forced exit request #{} is fulfilled by {} transactions",
+            request.id,
+            tx_hashes.len()
+        );
+        metrics::counter!("forced_exit_requests.fulfilled_requests", 1);
+
+        Ok(())
+    }
+
+    async fn funding_account_state(&mut self) -> anyhow::Result<(AccountId, Nonce)> {
+        if let Some(state) = self.funding_account_state {
+            return Ok(state);
+        }
+
+        let (account_id, account) = self
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .account_schema()
+            .account_state_by_address(self.funding_account)
+            .await?
+            .committed
+            .ok_or_else(|| anyhow::anyhow!("Funding account does not exist"))?;
+        let state = (account_id, account.nonce);
+        self.funding_account_state = Some(state);
+        Ok(state)
+    }
+}
+
+/// Reads the zkSync private key from its big-endian representation.
+fn read_private_key(key: H256) -> anyhow::Result<PrivateKey> {
+    let mut fs_repr = FsRepr::default();
+    fs_repr.read_be(key.as_bytes())?;
+    let fs = Fs::from_repr(fs_repr)?;
+    Ok(priv_key_from_fs(fs))
+}
+
+/// Runs the service fulfilling the forced exit requests if they are enabled.
+pub fn run_forced_exit_requests(
+    pool: ConnectionPool,
+    config: &ZkSyncConfig,
+) -> Option<JoinHandle<()>> {
+    let requests_config = &config.api.forced_exit_requests;
+    if !requests_config.enabled {
+        return None;
+    }
+
+    let private_key = read_private_key(requests_config.funding_account_private_key)
+        .expect("Invalid private key of the forced exit funding account");
+    let service = ForcedExitRequestsService {
+        pool,
+        core_api_client: CoreApiClient::new(config.api.private.url.clone()),
+        funding_account: requests_config.funding_account_addr,
+        private_key,
+        poll_interval: requests_config.poll_interval(),
+        funding_account_state: None,
+    };
+    Some(tokio::spawn(vlog::supervised(
+        "forced_exit_requests",
+        service.run(),
+    )))
+}
@@ -1,30 +1,65 @@
 #![recursion_limit = "256"]
 
-use crate::{api_server::start_api_server, fee_ticker::run_ticker_task};
-use futures::channel::mpsc;
+use crate::{
+    api_server::start_api_server,
+    event_forwarder::run_event_forwarder,
+    fee_ticker::{health::TickerHealthHandle, run_ticker_task, TickerConfig, TickerConfigHandle},
+    forced_exit_requests::run_forced_exit_requests,
+    permit_relayer::run_permit_relayer,
+};
+use futures::channel::{mpsc, oneshot};
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 
 pub mod api_server;
 pub mod core_api_client;
 pub mod eth_checker;
+pub mod event_forwarder;
 pub mod fee_ticker;
+pub mod forced_exit_requests;
+pub mod permit_relayer;
 pub mod signature_checker;
 pub mod tx_error;
 pub mod utils;
 
-/// Runs the application actors.
+/// Runs the application actors. Returns the handle of the fee ticker, which shuts down
+/// once the `ticker_shutdown` signal is sent.
 pub fn run_api(
     connection_pool: ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
+    ticker_shutdown: oneshot::Receiver<()>,
     config: &ZkSyncConfig,
 ) -> tokio::task::JoinHandle<()> {
     let channel_size = 32768;
     let (ticker_request_sender, ticker_request_receiver) = mpsc::channel(channel_size);
+    let (ticker_priority_request_sender, ticker_priority_request_receiver) =
+        mpsc::channel(channel_size);
 
-    let ticker_task = run_ticker_task(connection_pool.clone(), ticker_request_receiver, config);
+    let ticker_config = TickerConfigHandle::new(TickerConfig::from_config(config));
+    let ticker_health = TickerHealthHandle::new();
 
-    start_api_server(connection_pool, panic_notify, ticker_request_sender, config);
+    let ticker_task = run_ticker_task(
+        connection_pool.clone(),
+        ticker_priority_request_receiver,
+        ticker_request_receiver,
+        ticker_config.clone(),
+        ticker_health.clone(),
+        ticker_shutdown,
+        config,
+    );
+    run_permit_relayer(connection_pool.clone(), config);
+    run_forced_exit_requests(connection_pool.clone(), config);
+    run_event_forwarder(connection_pool.clone(), config);
+
+    start_api_server(
+        connection_pool,
+        panic_notify,
+        ticker_request_sender,
+        ticker_priority_request_sender,
+        ticker_config,
+        ticker_health,
+        config,
+    );
 
     ticker_task
 }
@@ -1,10 +1,18 @@
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    executor::block_on,
+    SinkExt, StreamExt,
+};
 use std::cell::RefCell;
+use std::time::Duration;
 use zksync_api::run_api;
 use zksync_config::ZkSyncConfig;
 use zksync_prometheus_exporter::run_prometheus_exporter;
 use zksync_storage::ConnectionPool;
 
+/// Time given to the fee ticker to answer the accepted requests on shutdown.
+const TICKER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     vlog::init();
@@ -25,10 +33,16 @@ async fn main() -> anyhow::Result<()> {
     let (prometheus_task_handle, _) =
         run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, false);
 
-    let task_handle = run_api(connection_pool, stop_signal_sender, &config);
+    let (ticker_shutdown_sender, ticker_shutdown) = oneshot::channel();
+    let mut task_handle = run_api(
+        connection_pool,
+        stop_signal_sender,
+        ticker_shutdown,
+        &config,
+    );
 
     tokio::select! {
-        _ = async { task_handle.await } => {
+        _ = async { (&mut task_handle).await } => {
             panic!("API server actors aren't supposed to finish their execution")
         },
         _ = async { prometheus_task_handle.await } => {
@@ -39,5 +53,14 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    // Let the fee ticker answer the accepted requests before exiting.
+    ticker_shutdown_sender.send(()).unwrap_or_default();
+    if tokio::time::timeout(TICKER_SHUTDOWN_TIMEOUT, task_handle)
+        .await
+        .is_err()
+    {
+        vlog::warn!("Fee ticker didn't shut down in time");
+    }
+
     Ok(())
 }
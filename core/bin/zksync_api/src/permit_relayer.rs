@@ -0,0 +1,158 @@
+//! Relayer of the deposits authorized by the EIP-2612 permit signatures.
+//!
+//! Deposit requests accepted by the API are stored to the database, and this task sends them
+//! to the zkSync contract on behalf of the tokens owner using the dedicated relayer account.
+//! The relayer pays for the L1 gas and receives the fee in the deposited token.
+//! Once the transaction is sent, the deposit is processed as a usual priority operation.
+
+// Built-in deps
+use std::time::Duration;
+// External deps
+use bigdecimal::BigDecimal;
+use ethabi::Token;
+use tokio::{task::JoinHandle, time};
+use web3::contract::Options;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::{permit_deposits::records::StoredPermitDeposit, ConnectionPool};
+use zksync_types::{tx::PackedEthSignature, Address, TokenId, TokenLike, U256};
+
+/// Maximum amount of deposits relayed within one iteration.
+const RELAY_BATCH_SIZE: i64 = 10;
+/// Gas limit of the `depositERC20WithPermit` call.
+const PERMIT_DEPOSIT_GAS_LIMIT: u64 = 250_000;
+
+struct PermitRelayer {
+    pool: ConnectionPool,
+    ethereum: EthereumGateway,
+    poll_interval: Duration,
+}
+
+impl PermitRelayer {
+    async fn run(self) {
+        let mut timer = time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(e) = self.relay_pending_deposits().await {
+                vlog::error!("Failed to relay the permit deposits: {}", e);
+            }
+        }
+    }
+
+    async fn relay_pending_deposits(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let requests = storage
+            .permit_deposits_schema()
+            .load_pending(RELAY_BATCH_SIZE)
+            .await?;
+
+        for request in requests {
+            if request.deadline < chrono::Utc::now().timestamp() {
+                storage
+                    .permit_deposits_schema()
+                    .mark_failed(request.id, "Permit deadline has passed")
+                    .await?;
+                metrics::counter!("permit_relayer.expired_deposits", 1);
+                continue;
+            }
+
+            let token = storage
+                .tokens_schema()
+                .get_token(TokenLike::Id(TokenId(request.token_id as u16)))
+                .await?;
+            let token = match token {
+                Some(token) => token,
+                None => {
+                    storage
+                        .permit_deposits_schema()
+                        .mark_failed(request.id, "Token not found")
+                        .await?;
+                    continue;
+                }
+            };
+
+            match self.send_deposit(&request, token.address).await {
+                Ok(eth_tx_hash) => {
+                    vlog::info!(
+                        "Permit deposit #{} was sent to L1, tx hash: {:#x}",
+                        request.id,
+                        eth_tx_hash
+                    );
+                    storage
+                        .permit_deposits_schema()
+                        .mark_sent(request.id, eth_tx_hash.as_bytes())
+                        .await?;
+                    metrics::counter!("permit_relayer.sent_deposits", 1);
+                }
+                // The request stays pending and is retried on the next iteration.
+                // Should the transaction actually be sent, the retry will be reverted since
+                // the permit nonce is already used.
+                Err(e) => {
+                    vlog::warn!("Failed to send permit deposit #{}: {}", request.id, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn send_deposit(
+        &self,
+        request: &StoredPermitDeposit,
+        token_address: Address,
+    ) -> anyhow::Result<zksync_types::H256> {
+        let signature = PackedEthSignature::deserialize_packed(&request.signature)?;
+        let signature = signature.serialize_packed();
+
+        let args = [
+            Token::Address(token_address),
+            Token::Address(Address::from_slice(&request.owner)),
+            Token::Uint(big_decimal_to_u256(&request.amount)?),
+            Token::Uint(big_decimal_to_u256(&request.fee)?),
+            Token::Uint(U256::from(request.deadline)),
+            Token::Uint(U256::from(signature[64])),
+            Token::FixedBytes(signature[0..32].to_vec()),
+            Token::FixedBytes(signature[32..64].to_vec()),
+        ];
+        let data = self
+            .ethereum
+            .encode_tx_data("depositERC20WithPermit", args.as_ref());
+        let options = Options {
+            gas: Some(PERMIT_DEPOSIT_GAS_LIMIT.into()),
+            ..Default::default()
+        };
+
+        let signed_tx = self.ethereum.sign_prepared_tx(data, options).await?;
+        self.ethereum.send_raw_tx(signed_tx.raw_tx).await
+    }
+}
+
+fn big_decimal_to_u256(value: &BigDecimal) -> anyhow::Result<U256> {
+    let (value, _) = value.with_scale(0).into_bigint_and_exponent();
+    U256::from_dec_str(&value.to_string()).map_err(|e| anyhow::anyhow!("{:?}", e))
+}
+
+/// Runs the relayer task if the permit-based deposits are enabled.
+pub fn run_permit_relayer(pool: ConnectionPool, config: &ZkSyncConfig) -> Option<JoinHandle<()>> {
+    let relayer_config = &config.api.permit_relayer;
+    if !relayer_config.enabled {
+        return None;
+    }
+
+    let ethereum = EthereumGateway::from_config_with_sender(
+        config,
+        relayer_config.relayer_eth_addr,
+        relayer_config.relayer_private_key,
+    );
+    let relayer = PermitRelayer {
+        pool,
+        ethereum,
+        poll_interval: relayer_config.poll_interval(),
+    };
+    Some(tokio::spawn(vlog::supervised(
+        "permit_relayer",
+        relayer.run(),
+    )))
+}
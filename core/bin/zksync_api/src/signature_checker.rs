@@ -16,7 +16,7 @@ use futures::{
 use tokio::runtime::{Builder, Handle};
 // Workspace uses
 use zksync_types::{
-    tx::{EthBatchSignData, TxEthSignature},
+    tx::{EthBatchSignData, PackedEthSignature, TxEthSignature},
     Address, SignedZkSyncTx, Token, ZkSyncTx,
 };
 // Local uses
@@ -101,19 +101,27 @@ async fn verify_eth_signature(
             if accounts.len() != 1 || tokens.len() != 1 {
                 return Err(TxAddError::Other);
             }
-            verify_eth_signature_single_tx(tx, accounts[0], tokens[0].clone(), eth_checker).await?;
+            verify_eth_signature_single_tx(
+                tx,
+                accounts[0],
+                tokens[0].clone(),
+                eth_checker,
+                network,
+            )
+            .await?;
         }
         TxVariant::Batch(txs, batch_sign_data) => {
             if accounts.len() != txs.len() {
                 return Err(TxAddError::Other);
             }
-            verify_eth_signature_txs_batch(txs, accounts, batch_sign_data, eth_checker).await?;
+            verify_eth_signature_txs_batch(txs, accounts, batch_sign_data, eth_checker, network)
+                .await?;
             // In case there're signatures provided for some of transactions
             // we still verify them.
             for ((tx, &account), token) in
                 txs.iter().zip(accounts.iter()).zip(tokens.iter().cloned())
             {
-                verify_eth_signature_single_tx(tx, account, token, eth_checker).await?;
+                verify_eth_signature_single_tx(tx, account, token, eth_checker, network).await?;
             }
         }
     }
@@ -139,6 +147,9 @@ async fn verify_ethereum_signature(
                 .await
                 .expect("Unable to check EIP1271 signature")
         }
+        // Typed data signatures don't sign the text message and are checked
+        // against the transaction itself in `verify_eip712_signature`.
+        TxEthSignature::EIP712Signature(_) => return false,
     };
     match signer_account {
         Ok(address) => address == sender_address,
@@ -146,11 +157,34 @@ async fn verify_ethereum_signature(
     }
 }
 
+/// Checks that the EIP-712 typed data of the transaction was signed by an expected address.
+fn verify_eip712_signature(
+    signature: &PackedEthSignature,
+    tx: &ZkSyncTx,
+    token: &Token,
+    sender_address: Address,
+    network: Network,
+) -> bool {
+    // Typed data is bound to the chain ID, which is not defined for the test networks.
+    if matches!(network, Network::Test | Network::Unknown) {
+        return false;
+    }
+    let signing_hash = match tx.get_eip712_signing_hash(token, network.chain_id()) {
+        Some(signing_hash) => signing_hash,
+        None => return false,
+    };
+    match signature.signature_recover_signer_from_hash(&signing_hash) {
+        Ok(address) => address == sender_address,
+        Err(_) => false,
+    }
+}
+
 async fn verify_eth_signature_single_tx(
     tx: &SignedZkSyncTx,
     sender_address: Address,
     token: Token,
     eth_checker: &EthereumChecker,
+    network: Network,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
@@ -175,6 +209,17 @@ async fn verify_eth_signature_single_tx(
     // Check the signature.
     if let Some(sign_data) = &tx.eth_sign_data {
         let signature = &sign_data.signature;
+        if let TxEthSignature::EIP712Signature(signature) = signature {
+            if !verify_eip712_signature(signature, &tx.tx, &token, sender_address, network) {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+            metrics::histogram!(
+                "signature_checker.verify_eth_signature_single_tx",
+                start.elapsed()
+            );
+            return Ok(());
+        }
+
         let mut signature_correct =
             verify_ethereum_signature(signature, &sign_data.message, sender_address, eth_checker)
                 .await;
@@ -207,11 +252,21 @@ async fn verify_eth_signature_txs_batch(
     senders: &[Address],
     batch_sign_data: &EthBatchSignData,
     eth_checker: &EthereumChecker,
+    network: Network,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Cache for verified senders.
     let mut signers = HashSet::with_capacity(senders.len());
     let old_message = EthBatchSignData::get_old_ethereum_batch_message(txs.iter().map(|tx| &tx.tx));
+    // Typed data is bound to the chain ID, which is not defined for the test networks.
+    let eip712_signing_hash = if matches!(network, Network::Test | Network::Unknown) {
+        None
+    } else {
+        Some(EthBatchSignData::get_eip712_batch_signing_hash(
+            txs.iter().map(|tx| &tx.tx),
+            network.chain_id(),
+        ))
+    };
     // For every sender check whether there exists at least one signature that matches it.
     for sender in senders {
         if signers.contains(sender) {
@@ -224,6 +279,21 @@ async fn verify_eth_signature_txs_batch(
         // This block will set the `sender_correct` variable to `true` at the first match.
         let mut sender_correct = false;
         for signature in &batch_sign_data.signatures {
+            // The typed data signature covers the batch hash rather than the batch message.
+            if let TxEthSignature::EIP712Signature(signature) = signature {
+                let signature_correct = eip712_signing_hash
+                    .map(|hash| signature.signature_recover_signer_from_hash(&hash))
+                    .and_then(Result::ok)
+                    .map(|address| address == *sender)
+                    .unwrap_or(false);
+                if signature_correct {
+                    signers.insert(sender);
+                    sender_correct = true;
+                    break;
+                }
+                continue;
+            }
+
             let mut signature_correct = verify_ethereum_signature(
                 signature,
                 &batch_sign_data.message,
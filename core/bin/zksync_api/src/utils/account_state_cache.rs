@@ -0,0 +1,130 @@
+//! Cache-aside layer for the account states requested by the API.
+//!
+//! Account state requests are the most frequent ones, while the state itself changes only
+//! when the server commits or verifies a block. The cache watches the stored state version
+//! (see `StateVersion`) and drops all the cached entries once the state changes, so the
+//! staleness of the cached data is bounded by the polling interval.
+
+// Built-in uses
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_storage::{chain::account::StoredAccountState, ConnectionPool, StorageProcessor};
+use zksync_types::Address;
+// Local uses
+use super::shared_lru_cache::SharedLruCache;
+
+#[derive(Debug, Clone)]
+pub struct AccountStateCache {
+    states: SharedLruCache<Address, StoredAccountState>,
+    /// Incremented on every invalidation. Used to prevent storing the state loaded
+    /// before the invalidation, since it could be already outdated.
+    generation: Arc<AtomicU64>,
+}
+
+impl AccountStateCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            states: SharedLruCache::new(capacity),
+            generation: Arc::default(),
+        }
+    }
+
+    /// Returns the state of the account with the given address, loading it from the
+    /// database if it's not cached.
+    pub async fn account_state_by_address(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        address: Address,
+    ) -> anyhow::Result<StoredAccountState> {
+        if let Some(state) = self.states.get(&address) {
+            metrics::counter!("api.account_state_cache.hit", 1);
+            return Ok(state);
+        }
+        metrics::counter!("api.account_state_cache.miss", 1);
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(address)
+            .await?;
+        if generation == self.generation.load(Ordering::SeqCst) {
+            self.states.insert(address, state.clone());
+        }
+
+        Ok(state)
+    }
+
+    /// Drops all the cached account states.
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.states.clear();
+    }
+
+    /// Spawns the task which invalidates the cache every time the stored accounts state changes.
+    pub fn spawn_invalidator(
+        &self,
+        runtime: &tokio::runtime::Handle,
+        pool: ConnectionPool,
+        poll_interval: Duration,
+    ) -> JoinHandle<()> {
+        let cache = self.clone();
+        runtime.spawn(async move {
+            let mut timer = time::interval(poll_interval);
+            let mut last_version = None;
+            loop {
+                timer.tick().await;
+
+                let version = match pool.access_storage().await {
+                    Ok(mut storage) => storage.chain().state_schema().load_state_version().await,
+                    Err(e) => Err(e),
+                };
+                match version {
+                    Ok(version) => {
+                        if last_version != Some(version) {
+                            cache.invalidate();
+                            last_version = Some(version);
+                        }
+                    }
+                    Err(e) => {
+                        // The state may change while we're unable to check it.
+                        cache.invalidate();
+                        last_version = None;
+                        vlog::warn!("Unable to load the accounts state version: {}", e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidate() {
+        let cache = AccountStateCache::new(10);
+        let address = Address::repeat_byte(1);
+        cache.states.insert(
+            address,
+            StoredAccountState {
+                committed: None,
+                verified: None,
+            },
+        );
+        assert!(cache.states.get(&address).is_some());
+
+        cache.invalidate();
+        assert!(cache.states.get(&address).is_none());
+        assert_eq!(cache.generation.load(Ordering::SeqCst), 1);
+    }
+}
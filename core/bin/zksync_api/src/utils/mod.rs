@@ -1,2 +1,3 @@
+pub mod account_state_cache;
 pub mod shared_lru_cache;
 pub mod token_db_cache;
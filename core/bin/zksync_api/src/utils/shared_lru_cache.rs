@@ -24,6 +24,10 @@ impl<K: Eq + Hash, V: Clone> SharedLruCache<K, V> {
     pub fn get(&self, key: &K) -> Option<V> {
         self.0.lock().unwrap().get_mut(&key).cloned()
     }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
 }
 
 /// `AsyncLruCache` is an thread-safe alternative of the `LruCache`.
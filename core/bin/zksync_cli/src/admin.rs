@@ -0,0 +1,311 @@
+//! Client for the admin API of the server.
+//!
+//! Every request is authorized by the JSON web token signed with the admin secret
+//! (`API_ADMIN_SECRET_AUTH` on the server side).
+
+// Built-in uses
+use std::time::{Duration, UNIX_EPOCH};
+// External uses
+use anyhow::Context;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+// Workspace uses
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, Address, TokenId, TokenLike, TxFeeTypes,
+};
+
+/// Lifetime of the generated authorization tokens.
+const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    inner: reqwest::Client,
+    url: String,
+    secret: String,
+}
+
+impl AdminClient {
+    pub fn new(url: String, secret: String) -> Self {
+        Self {
+            inner: reqwest::Client::new(),
+            url,
+            secret,
+        }
+    }
+
+    pub async fn add_token(
+        &self,
+        id: Option<TokenId>,
+        address: Address,
+        symbol: String,
+        decimals: u8,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "tokens",
+            json!({
+                "id": id,
+                "address": address,
+                "symbol": symbol,
+                "decimals": decimals,
+            }),
+        )
+        .await
+    }
+
+    pub async fn pause_eth_sender(
+        &self,
+        action_type: AggregatedActionType,
+    ) -> anyhow::Result<Value> {
+        self.post("eth_sender/pause", json!({ "action_type": action_type }))
+            .await
+    }
+
+    pub async fn resume_eth_sender(
+        &self,
+        action_type: AggregatedActionType,
+    ) -> anyhow::Result<Value> {
+        self.post("eth_sender/resume", json!({ "action_type": action_type }))
+            .await
+    }
+
+    pub async fn paused_eth_sender_actions(&self) -> anyhow::Result<Value> {
+        self.get("eth_sender/paused").await
+    }
+
+    pub async fn prune(&self, retention_blocks: u32) -> anyhow::Result<Value> {
+        self.post("pruning", json!({ "retention_blocks": retention_blocks }))
+            .await
+    }
+
+    pub async fn log_filter(&self) -> anyhow::Result<Value> {
+        self.get("log_filter").await
+    }
+
+    pub async fn set_log_filter(&self, filter: String) -> anyhow::Result<Value> {
+        self.post("log_filter", json!({ "filter": filter })).await
+    }
+
+    pub async fn ticker_settings(&self) -> anyhow::Result<Value> {
+        self.get("fee_ticker/settings").await
+    }
+
+    pub async fn set_ticker_settings(&self, update: Value) -> anyhow::Result<Value> {
+        self.post("fee_ticker/settings", update).await
+    }
+
+    pub async fn fee_dry_run(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenLike,
+        sender: Option<Address>,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/dry_run",
+            json!({
+                "tx_type": tx_type,
+                "address": address,
+                "token": token,
+                "sender": sender,
+            }),
+        )
+        .await
+    }
+
+    pub async fn token_liquidity(&self, token: TokenLike) -> anyhow::Result<Value> {
+        self.post("fee_ticker/liquidity", json!({ "token": token }))
+            .await
+    }
+
+    pub async fn freeze_fees(
+        &self,
+        base_fee_wei: Option<String>,
+        priority_fee_wei: Option<String>,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/freeze",
+            json!({ "base_fee_wei": base_fee_wei, "priority_fee_wei": priority_fee_wei }),
+        )
+        .await
+    }
+
+    pub async fn unfreeze_fees(&self) -> anyhow::Result<Value> {
+        self.post("fee_ticker/unfreeze", json!({})).await
+    }
+
+    pub async fn set_risk_factor(
+        &self,
+        token: TokenId,
+        risk_factor: String,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/risk_factors",
+            json!({ "token": token, "risk_factor": risk_factor }),
+        )
+        .await
+    }
+
+    pub async fn remove_risk_factor(&self, token: TokenId) -> anyhow::Result<Value> {
+        self.post("fee_ticker/risk_factors/remove", json!({ "token": token }))
+            .await
+    }
+
+    pub async fn set_gas_cost(
+        &self,
+        token: TokenId,
+        fee_type: Value,
+        gas_cost: u64,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/gas_costs",
+            json!({ "token": token, "fee_type": fee_type, "gas_cost": gas_cost.to_string() }),
+        )
+        .await
+    }
+
+    pub async fn remove_gas_cost(&self, token: TokenId, fee_type: Value) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/gas_costs/remove",
+            json!({ "token": token, "fee_type": fee_type }),
+        )
+        .await
+    }
+
+    pub async fn add_subsidy(
+        &self,
+        token: Option<TokenId>,
+        fee_type: Option<Value>,
+        starts_at: Option<String>,
+        ends_at: Option<String>,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/subsidies",
+            json!({
+                "token": token,
+                "fee_type": fee_type,
+                "starts_at": starts_at,
+                "ends_at": ends_at,
+            }),
+        )
+        .await
+    }
+
+    pub async fn set_subsidy_enabled(&self, id: i32, enabled: bool) -> anyhow::Result<Value> {
+        let method = if enabled {
+            "fee_ticker/subsidies/enable"
+        } else {
+            "fee_ticker/subsidies/disable"
+        };
+        self.post(method, json!({ "id": id })).await
+    }
+
+    pub async fn subsidy_report(&self, from: String, to: Option<String>) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/subsidies/report",
+            json!({ "from": from, "to": to }),
+        )
+        .await
+    }
+
+    pub async fn add_discount(
+        &self,
+        address: Address,
+        fee_type: Option<Value>,
+        discount: String,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/discounts",
+            json!({ "address": address, "fee_type": fee_type, "discount": discount }),
+        )
+        .await
+    }
+
+    pub async fn remove_discount(&self, id: i32) -> anyhow::Result<Value> {
+        self.post("fee_ticker/discounts/remove", json!({ "id": id }))
+            .await
+    }
+
+    pub async fn add_waiver(
+        &self,
+        fee_type: Value,
+        token: Option<TokenId>,
+        unset_signing_key_only: bool,
+        expires_at: Option<String>,
+    ) -> anyhow::Result<Value> {
+        self.post(
+            "fee_ticker/waivers",
+            json!({
+                "fee_type": fee_type,
+                "token": token,
+                "unset_signing_key_only": unset_signing_key_only,
+                "expires_at": expires_at,
+            }),
+        )
+        .await
+    }
+
+    pub async fn remove_waiver(&self, id: i32) -> anyhow::Result<Value> {
+        self.post("fee_ticker/waivers/remove", json!({ "id": id }))
+            .await
+    }
+
+    async fn get(&self, method: &str) -> anyhow::Result<Value> {
+        let response = self
+            .inner
+            .get(&self.endpoint(method))
+            .bearer_auth(self.auth_token()?)
+            .send()
+            .await?;
+        Self::parse_response(response).await
+    }
+
+    async fn post(&self, method: &str, body: Value) -> anyhow::Result<Value> {
+        let response = self
+            .inner
+            .post(&self.endpoint(method))
+            .bearer_auth(self.auth_token()?)
+            .json(&body)
+            .send()
+            .await?;
+        Self::parse_response(response).await
+    }
+
+    async fn parse_response(response: reqwest::Response) -> anyhow::Result<Value> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Admin API request failed: {} {}", status, body);
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse the admin API response")
+    }
+
+    fn endpoint(&self, method: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), method)
+    }
+
+    fn auth_token(&self) -> anyhow::Result<String> {
+        let exp = UNIX_EPOCH.elapsed()? + AUTH_TOKEN_LIFETIME;
+        let payload = PayloadAuthToken {
+            sub: "Authorization".to_string(),
+            exp: exp.as_secs() as usize,
+        };
+        encode(
+            &Header::default(),
+            &payload,
+            &EncodingKey::from_secret(self.secret.as_ref()),
+        )
+        .context("Failed to generate the authorization token")
+    }
+}
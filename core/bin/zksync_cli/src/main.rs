@@ -0,0 +1,457 @@
+//! Command-line client for the zkSync server.
+//!
+//! Covers the routine operations of the operator and power users: fee queries, token
+//! inspection, transaction submission and the admin API calls. All the results are
+//! printed as JSON, so they can be processed further by the usual tools.
+
+// Built-in uses
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+// External uses
+use anyhow::Context;
+use serde::Serialize;
+use structopt::StructOpt;
+// Workspace uses
+use zksync_api_client::rest::v1::{Client, IncomingTx, IncomingTxBatch, TokenPriceKind};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, network::Network, tx::TxHash, Address, TokenId,
+    TokenLike, TxFeeTypes,
+};
+// Local uses
+use crate::admin::AdminClient;
+
+mod admin;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zkcli", author = "Matter Labs", rename_all = "kebab-case")]
+struct Opt {
+    /// URL of the REST API server
+    #[structopt(long, env = "ZKSYNC_API_URL", default_value = "http://127.0.0.1:3001")]
+    api_url: String,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+enum Command {
+    /// Gets the fee for a single transaction
+    Fee {
+        /// Transaction type, e.g. `Transfer` or `{"ChangePubKey":"ECDSA"}`
+        #[structopt(long, parse(try_from_str = parse_fee_type))]
+        tx_type: TxFeeTypes,
+        /// Recipient of the transaction
+        #[structopt(long)]
+        address: Address,
+        /// Token to pay the fee in (ID, symbol or address)
+        #[structopt(long, parse(from_str = TokenLike::parse))]
+        token: TokenLike,
+        /// Sender of the transaction, its fee discounts are applied if set
+        #[structopt(long)]
+        sender: Option<Address>,
+    },
+    /// Gets the fee for a batch of transactions
+    BatchFee {
+        /// Types of the transactions in the batch
+        #[structopt(long = "tx-type", parse(try_from_str = parse_fee_type), required = true)]
+        tx_types: Vec<TxFeeTypes>,
+        /// Senders of the transactions in the batch, in the same order as the types
+        #[structopt(long = "address", required = true)]
+        addresses: Vec<Address>,
+        /// Token to pay the fee in (ID, symbol or address)
+        #[structopt(long, parse(from_str = TokenLike::parse))]
+        token: TokenLike,
+    },
+    /// Shows the token, its price and whether it can be used to pay fees
+    Token {
+        /// Token ID, symbol or address
+        #[structopt(parse(from_str = TokenLike::parse))]
+        token: TokenLike,
+    },
+    /// Submits the transaction from the JSON file with `tx` and `signature` fields
+    Submit {
+        tx_file: PathBuf,
+        /// Requests the fast processing of the withdrawal
+        #[structopt(long)]
+        fast: bool,
+    },
+    /// Submits the batch from the JSON file with `txs` and `signature` fields
+    SubmitBatch { batch_file: PathBuf },
+    /// Shows the transaction receipt
+    TxStatus { tx_hash: TxHash },
+    /// Calls the admin API
+    Admin {
+        /// URL of the admin API server
+        #[structopt(
+            long,
+            env = "ZKSYNC_ADMIN_URL",
+            default_value = "http://127.0.0.1:8080"
+        )]
+        admin_url: String,
+        /// Secret used to sign the authorization tokens
+        #[structopt(long, env = "ZKSYNC_ADMIN_SECRET", hide_env_values = true)]
+        secret: String,
+
+        #[structopt(subcommand)]
+        command: AdminCommand,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+enum AdminCommand {
+    /// Adds the token to the server database
+    AddToken {
+        /// Token ID, the next free ID is used if not set
+        #[structopt(long)]
+        id: Option<u16>,
+        #[structopt(long)]
+        address: Address,
+        #[structopt(long)]
+        symbol: String,
+        #[structopt(long)]
+        decimals: u8,
+    },
+    /// Pauses sending of the L1 operations of the given type
+    /// (`CommitBlocks`, `PublishProofBlocksOnchain` or `ExecuteBlocks`)
+    Pause { action_type: AggregatedActionType },
+    /// Resumes sending of the L1 operations of the given type
+    Resume { action_type: AggregatedActionType },
+    /// Shows the types of the L1 operations which sending is paused
+    Paused,
+    /// Prunes the data of the old executed blocks
+    Prune {
+        /// Amount of the latest executed blocks which data must be kept
+        #[structopt(long)]
+        retention_blocks: u32,
+    },
+    /// Shows the log filter of the API server, or replaces it if the new one is provided
+    LogFilter {
+        /// New filter in the `RUST_LOG` format, e.g. `info,zksync_api=debug`
+        #[structopt(long)]
+        set: Option<String>,
+    },
+    /// Shows the adjustable fee ticker settings, or updates them if the update is provided
+    TickerSettings {
+        /// Update in JSON, e.g. `{"fast_processing_coeff":12.0}`; omitted fields are kept intact
+        #[structopt(long)]
+        set: Option<String>,
+    },
+    /// Calculates the fee of the transaction and shows every input of the calculation
+    FeeDryRun {
+        /// Transaction type, e.g. `Transfer` or `{"ChangePubKey":"ECDSA"}`
+        #[structopt(long, parse(try_from_str = parse_fee_type))]
+        tx_type: TxFeeTypes,
+        /// Recipient of the transaction
+        #[structopt(long)]
+        address: Address,
+        /// Token to pay the fee in (ID, symbol or address)
+        #[structopt(long, parse(from_str = TokenLike::parse))]
+        token: TokenLike,
+        /// Sender of the transaction, its fee discounts are applied if set
+        #[structopt(long)]
+        sender: Option<Address>,
+    },
+    /// Shows the last measured liquidity of the token the fee token validator decides on
+    TokenLiquidity {
+        /// Token ID, symbol or address
+        #[structopt(parse(from_str = TokenLike::parse))]
+        token: TokenLike,
+    },
+    /// Freezes the fees, so they are calculated from the fixed gas price and token prices
+    FreezeFees {
+        /// Base fee to pin, in wei; the current gas price is fixed if not set
+        #[structopt(long)]
+        base_fee_wei: Option<String>,
+        /// Priority fee to pin together with the base fee, in wei
+        #[structopt(long)]
+        priority_fee_wei: Option<String>,
+    },
+    /// Lifts the fee freeze
+    UnfreezeFees,
+    /// Sets the risk factor the fee paid in the token is multiplied by
+    SetRiskFactor {
+        token: u16,
+        /// Decimal risk factor, e.g. `1.5`
+        risk_factor: String,
+    },
+    /// Removes the risk factor of the token, so the default one is used
+    RemoveRiskFactor { token: u16 },
+    /// Overrides the gas cost of the operation paid in the token, e.g. for the fee-on-transfer tokens
+    SetGasCost {
+        token: u16,
+        /// Fee type, e.g. `Withdraw` or `FastWithdraw`
+        fee_type: String,
+        gas_cost: u64,
+    },
+    /// Removes the gas cost override, so the standard gas cost is used
+    RemoveGasCost { token: u16, fee_type: String },
+    /// Adds the fee subsidy; it applies to all the tokens and fee types unless limited
+    AddSubsidy {
+        #[structopt(long)]
+        token: Option<u16>,
+        /// Fee type, e.g. `TransferToNew` or `{"ChangePubKey":"ECDSA"}`
+        #[structopt(long)]
+        fee_type: Option<String>,
+        /// Start time in RFC 3339, e.g. `2021-03-20T12:00:00Z`; starts right away if not set
+        #[structopt(long)]
+        starts_at: Option<String>,
+        /// End time in RFC 3339; the subsidy doesn't expire if not set
+        #[structopt(long)]
+        ends_at: Option<String>,
+    },
+    /// Enables the fee subsidy
+    EnableSubsidy { id: i32 },
+    /// Disables the fee subsidy
+    DisableSubsidy { id: i32 },
+    /// Reports the subsidies spent on the executed transactions per token
+    SubsidyReport {
+        /// Start time in RFC 3339, e.g. `2021-03-20T12:00:00Z`
+        from: String,
+        /// End time in RFC 3339; the report covers the transactions up to now if not set
+        #[structopt(long)]
+        to: Option<String>,
+    },
+    /// Grants the fee discount to the account
+    AddDiscount {
+        address: Address,
+        /// Share of the fee the account doesn't pay, e.g. `0.5`
+        discount: String,
+        /// Fee type, e.g. `Transfer`; the discount applies to all the fee types if not set
+        #[structopt(long)]
+        fee_type: Option<String>,
+    },
+    /// Removes the fee discount
+    RemoveDiscount { id: i32 },
+    /// Waives the fee of the operation, e.g. the first `ChangePubKey` of the account
+    AddWaiver {
+        /// Fee type, e.g. `Withdraw` or `{"ChangePubKey":"ECDSA"}`
+        fee_type: String,
+        /// Token the fee is paid in; the waiver applies to all the tokens if not set
+        #[structopt(long)]
+        token: Option<u16>,
+        /// Waive the fee only for the accounts which signing key is not set yet
+        #[structopt(long)]
+        unset_signing_key_only: bool,
+        /// Expiration time in RFC 3339; the waiver doesn't expire if not set
+        #[structopt(long)]
+        expires_at: Option<String>,
+    },
+    /// Removes the fee waiver
+    RemoveWaiver { id: i32 },
+}
+
+/// Parses the fee type either from its JSON representation or from the plain variant name.
+fn parse_fee_type(value: &str) -> Result<TxFeeTypes, serde_json::Error> {
+    serde_json::from_str(value).or_else(|_| serde_json::from_str(&format!("\"{}\"", value)))
+}
+
+/// Converts the fee type to JSON, plain variant names are accepted without the quotes.
+fn output_fee_type_value(value: String) -> serde_json::Value {
+    serde_json::from_str(&value).unwrap_or(serde_json::Value::String(value))
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Checks that the transactions intended for the specific network are sent to the server
+/// of this network.
+async fn check_network(client: &Client, network: Option<Network>) -> anyhow::Result<()> {
+    if let Some(network) = network {
+        let server_network = client.network().await?;
+        anyhow::ensure!(
+            server_network == network.to_string(),
+            "Transactions are intended for the {} network, while the server runs on {}",
+            network,
+            server_network
+        );
+    }
+    Ok(())
+}
+
+async fn run_admin_command(client: AdminClient, command: AdminCommand) -> anyhow::Result<()> {
+    let response = match command {
+        AdminCommand::AddToken {
+            id,
+            address,
+            symbol,
+            decimals,
+        } => {
+            client
+                .add_token(id.map(TokenId), address, symbol, decimals)
+                .await?
+        }
+        AdminCommand::Pause { action_type } => client.pause_eth_sender(action_type).await?,
+        AdminCommand::Resume { action_type } => client.resume_eth_sender(action_type).await?,
+        AdminCommand::Paused => client.paused_eth_sender_actions().await?,
+        AdminCommand::Prune { retention_blocks } => client.prune(retention_blocks).await?,
+        AdminCommand::LogFilter { set: Some(filter) } => client.set_log_filter(filter).await?,
+        AdminCommand::LogFilter { set: None } => client.log_filter().await?,
+        AdminCommand::TickerSettings { set: Some(update) } => {
+            client
+                .set_ticker_settings(serde_json::from_str(&update)?)
+                .await?
+        }
+        AdminCommand::TickerSettings { set: None } => client.ticker_settings().await?,
+        AdminCommand::FeeDryRun {
+            tx_type,
+            address,
+            token,
+            sender,
+        } => client.fee_dry_run(tx_type, address, token, sender).await?,
+        AdminCommand::TokenLiquidity { token } => client.token_liquidity(token).await?,
+        AdminCommand::FreezeFees {
+            base_fee_wei,
+            priority_fee_wei,
+        } => client.freeze_fees(base_fee_wei, priority_fee_wei).await?,
+        AdminCommand::UnfreezeFees => client.unfreeze_fees().await?,
+        AdminCommand::SetRiskFactor { token, risk_factor } => {
+            client.set_risk_factor(TokenId(token), risk_factor).await?
+        }
+        AdminCommand::RemoveRiskFactor { token } => {
+            client.remove_risk_factor(TokenId(token)).await?
+        }
+        AdminCommand::SetGasCost {
+            token,
+            fee_type,
+            gas_cost,
+        } => {
+            client
+                .set_gas_cost(TokenId(token), output_fee_type_value(fee_type), gas_cost)
+                .await?
+        }
+        AdminCommand::RemoveGasCost { token, fee_type } => {
+            client
+                .remove_gas_cost(TokenId(token), output_fee_type_value(fee_type))
+                .await?
+        }
+        AdminCommand::AddSubsidy {
+            token,
+            fee_type,
+            starts_at,
+            ends_at,
+        } => {
+            client
+                .add_subsidy(
+                    token.map(TokenId),
+                    fee_type.map(output_fee_type_value),
+                    starts_at,
+                    ends_at,
+                )
+                .await?
+        }
+        AdminCommand::EnableSubsidy { id } => client.set_subsidy_enabled(id, true).await?,
+        AdminCommand::DisableSubsidy { id } => client.set_subsidy_enabled(id, false).await?,
+        AdminCommand::SubsidyReport { from, to } => client.subsidy_report(from, to).await?,
+        AdminCommand::AddDiscount {
+            address,
+            discount,
+            fee_type,
+        } => {
+            client
+                .add_discount(address, fee_type.map(output_fee_type_value), discount)
+                .await?
+        }
+        AdminCommand::RemoveDiscount { id } => client.remove_discount(id).await?,
+        AdminCommand::AddWaiver {
+            fee_type,
+            token,
+            unset_signing_key_only,
+            expires_at,
+        } => {
+            client
+                .add_waiver(
+                    output_fee_type_value(fee_type),
+                    token.map(TokenId),
+                    unset_signing_key_only,
+                    expires_at,
+                )
+                .await?
+        }
+        AdminCommand::RemoveWaiver { id } => client.remove_waiver(id).await?,
+    };
+    print_json(&response)
+}
+
+async fn run_command(client: Client, command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Fee {
+            tx_type,
+            address,
+            token,
+            sender,
+        } => print_json(&client.get_txs_fee(tx_type, address, token, sender).await?),
+        Command::BatchFee {
+            tx_types,
+            addresses,
+            token,
+        } => {
+            anyhow::ensure!(
+                tx_types.len() == addresses.len(),
+                "Amount of transaction types must match the amount of addresses"
+            );
+            print_json(
+                &client
+                    .get_batched_txs_fee(tx_types, addresses, token)
+                    .await?,
+            )
+        }
+        Command::Token { token } => {
+            let info = client
+                .token_by_id(&token)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Token {} not found", token))?;
+            let usd_price = client.token_price(&token, TokenPriceKind::Currency).await?;
+            let fee_status = client.token_fee_status(&token).await?;
+            print_json(&serde_json::json!({
+                "token": info,
+                "usdPrice": usd_price,
+                "feeStatus": fee_status,
+            }))
+        }
+        Command::Submit { tx_file, fast } => {
+            let IncomingTx {
+                tx,
+                signature,
+                network,
+                ..
+            } = read_json(&tx_file)?;
+            check_network(&client, network).await?;
+            print_json(&client.submit_tx(tx, signature, Some(fast)).await?)
+        }
+        Command::SubmitBatch { batch_file } => {
+            let IncomingTxBatch {
+                txs,
+                signature,
+                network,
+            } = read_json(&batch_file)?;
+            check_network(&client, network).await?;
+            print_json(&client.submit_tx_batch(txs, signature).await?)
+        }
+        Command::TxStatus { tx_hash } => print_json(&client.tx_status(tx_hash).await?),
+        Command::Admin {
+            admin_url,
+            secret,
+            command,
+        } => run_admin_command(AdminClient::new(admin_url, secret), command).await,
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let client = Client::new(opt.api_url);
+
+    run_command(client, opt.command).await
+}
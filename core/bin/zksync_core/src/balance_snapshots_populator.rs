@@ -0,0 +1,67 @@
+//! The populator is responsible for filling the per-block balance snapshots
+//! (see `BalanceSnapshotsSchema` for details) which are used for the analytical queries.
+//!
+//! Only the blocks executed on Ethereum are processed, since the committed blocks can still be reverted.
+
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+/// Maximum amount of blocks processed within one iteration.
+const MAX_BLOCKS_PER_ITERATION: u32 = 100;
+
+async fn populate_snapshots(db_pool: &ConnectionPool) -> anyhow::Result<Option<BlockNumber>> {
+    let mut storage = db_pool.access_storage().await?;
+    let last_executed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+    let last_populated_block = storage
+        .chain()
+        .balance_snapshots_schema()
+        .last_populated_block()
+        .await?;
+
+    let last_block = std::cmp::min(
+        *last_executed_block,
+        *last_populated_block + MAX_BLOCKS_PER_ITERATION,
+    );
+    if last_block <= *last_populated_block {
+        return Ok(None);
+    }
+
+    for block_number in (*last_populated_block + 1)..=last_block {
+        storage
+            .chain()
+            .balance_snapshots_schema()
+            .populate_block(BlockNumber(block_number))
+            .await?;
+    }
+    Ok(Some(BlockNumber(last_block)))
+}
+
+#[must_use]
+pub fn run_balance_snapshots_populator(
+    config: &ZkSyncConfig,
+    db_pool: ConnectionPool,
+) -> JoinHandle<()> {
+    let mut timer = time::interval(config.db.balance_snapshots_interval());
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            match populate_snapshots(&db_pool).await {
+                Ok(Some(last_block)) => {
+                    vlog::info!("Populated balance snapshots up to block {}", *last_block)
+                }
+                Ok(None) => {}
+                Err(e) => vlog::error!("Failed to populate balance snapshots: {}", e),
+            }
+        }
+    })
+}
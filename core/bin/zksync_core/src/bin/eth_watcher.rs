@@ -18,8 +18,16 @@ fn main() {
 
     let (eth_req_sender, eth_req_receiver) = mpsc::channel(256);
 
-    let eth_client = EthHttpClient::new(client, config.contracts.contract_addr);
-    let watcher = EthWatch::new(eth_client, 0);
+    let eth_client = EthHttpClient::new(
+        client,
+        config.contracts.contract_addr,
+        config.contracts.governance_addr,
+    );
+    let watcher = EthWatch::new(
+        eth_client,
+        0,
+        config.eth_watch.priority_op_expiration_alert_blocks,
+    );
 
     main_runtime.spawn(watcher.run(eth_req_receiver));
     main_runtime.block_on(async move {
@@ -8,25 +8,39 @@ use web3::{
     types::{BlockNumber, FilterBuilder, Log},
 };
 
-use zksync_contracts::zksync_contract;
+use zksync_contracts::{governance_contract, zksync_contract};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
-use zksync_types::{Address, Nonce, PriorityOp, H160, U256};
+use zksync_types::{Address, NewTokenEvent, Nonce, PriorityOp, SerialId, H160, U256};
 
 struct ContractTopics {
     new_priority_request: Hash,
+    new_token: Hash,
 }
 
 impl ContractTopics {
-    fn new(zksync_contract: &ethabi::Contract) -> Self {
+    fn new(zksync_contract: &ethabi::Contract, governance_contract: &ethabi::Contract) -> Self {
         Self {
             new_priority_request: zksync_contract
                 .event("NewPriorityRequest")
                 .expect("main contract abi error")
                 .signature(),
+            new_token: governance_contract
+                .event("NewToken")
+                .expect("governance contract abi error")
+                .signature(),
         }
     }
 }
 
+/// Range of the priority operations that are not yet executed by the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OpenPriorityRequests {
+    /// Serial ID of the first open priority request.
+    pub first_serial_id: SerialId,
+    /// Total number of open priority requests.
+    pub total: u64,
+}
+
 #[async_trait::async_trait]
 pub trait EthClient {
     async fn get_priority_op_events(
@@ -34,30 +48,43 @@ pub trait EthClient {
         from: BlockNumber,
         to: BlockNumber,
     ) -> anyhow::Result<Vec<PriorityOp>>;
+    async fn get_new_tokens_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<NewTokenEvent>>;
     async fn block_number(&self) -> anyhow::Result<u64>;
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>>;
     async fn get_auth_fact_reset_time(&self, address: Address, nonce: Nonce)
         -> anyhow::Result<u64>;
+    async fn get_open_priority_requests(&self) -> anyhow::Result<OpenPriorityRequests>;
 }
 
 pub struct EthHttpClient {
     client: EthereumGateway,
     topics: ContractTopics,
     zksync_contract_addr: H160,
+    governance_contract_addr: H160,
 }
 
 impl EthHttpClient {
-    pub fn new(client: EthereumGateway, zksync_contract_addr: H160) -> Self {
-        let topics = ContractTopics::new(&zksync_contract());
+    pub fn new(
+        client: EthereumGateway,
+        zksync_contract_addr: H160,
+        governance_contract_addr: H160,
+    ) -> Self {
+        let topics = ContractTopics::new(&zksync_contract(), &governance_contract());
         Self {
             client,
             topics,
             zksync_contract_addr,
+            governance_contract_addr,
         }
     }
 
     async fn get_events<T>(
         &self,
+        contract_addr: H160,
         from: BlockNumber,
         to: BlockNumber,
         topics: Vec<Hash>,
@@ -67,7 +94,7 @@ impl EthHttpClient {
         T::Error: Debug,
     {
         let filter = FilterBuilder::default()
-            .address(vec![self.zksync_contract_addr])
+            .address(vec![contract_addr])
             .from_block(from)
             .to_block(to)
             .topics(Some(topics), None, None, None)
@@ -100,12 +127,36 @@ impl EthClient for EthHttpClient {
         let start = Instant::now();
 
         let result = self
-            .get_events(from, to, vec![self.topics.new_priority_request])
+            .get_events(
+                self.zksync_contract_addr,
+                from,
+                to,
+                vec![self.topics.new_priority_request],
+            )
             .await;
         metrics::histogram!("eth_watcher.get_priority_op_events", start.elapsed());
         result
     }
 
+    async fn get_new_tokens_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<NewTokenEvent>> {
+        let start = Instant::now();
+
+        let result = self
+            .get_events(
+                self.governance_contract_addr,
+                from,
+                to,
+                vec![self.topics.new_token],
+            )
+            .await;
+        metrics::histogram!("eth_watcher.get_new_tokens_events", start.elapsed());
+        result
+    }
+
     async fn block_number(&self) -> anyhow::Result<u64> {
         Ok(self.client.block_number().await?.as_u64())
     }
@@ -140,4 +191,36 @@ impl EthClient for EthHttpClient {
             .map_err(|e| format_err!("Failed to query contract authFacts: {}", e))
             .map(|res: U256| res.as_u64())
     }
+
+    async fn get_open_priority_requests(&self) -> anyhow::Result<OpenPriorityRequests> {
+        let first_serial_id: U256 = self
+            .client
+            .call_main_contract_function(
+                "firstPriorityRequestId",
+                (),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map_err(|e| format_err!("Failed to query contract firstPriorityRequestId: {}", e))?;
+        let total: U256 = self
+            .client
+            .call_main_contract_function(
+                "totalOpenPriorityRequests",
+                (),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map_err(|e| {
+                format_err!("Failed to query contract totalOpenPriorityRequests: {}", e)
+            })?;
+
+        Ok(OpenPriorityRequests {
+            first_serial_id: first_serial_id.as_u64(),
+            total: total.as_u64(),
+        })
+    }
 }
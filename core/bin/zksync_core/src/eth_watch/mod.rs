@@ -22,7 +22,7 @@ use web3::types::{Address, BlockNumber};
 
 // Workspace deps
 use zksync_crypto::params::PRIORITY_EXPIRATION;
-use zksync_types::{Nonce, PriorityOp, PubKeyHash, ZkSyncPriorityOp};
+use zksync_types::{NewTokenEvent, Nonce, PriorityOp, PubKeyHash, ZkSyncPriorityOp};
 
 // Local deps
 use self::{
@@ -88,6 +88,12 @@ pub enum EthWatchRequest {
         eth_hash: Vec<u8>,
         resp: oneshot::Sender<Option<PriorityOp>>,
     },
+    /// Requests the confirmed `NewToken` events emitted after the given Ethereum block,
+    /// or all the known events if the block is not set.
+    GetNewTokens {
+        last_eth_block: Option<u64>,
+        resp: oneshot::Sender<Vec<NewTokenEvent>>,
+    },
 }
 
 pub struct EthWatch<W: EthClient> {
@@ -95,16 +101,23 @@ pub struct EthWatch<W: EthClient> {
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
+    /// Amount of Ethereum blocks before the priority operation expiration at which the alert is raised.
+    expiration_alert_blocks: u64,
     mode: WatcherMode,
 }
 
 impl<W: EthClient> EthWatch<W> {
-    pub fn new(client: W, number_of_confirmations_for_event: u64) -> Self {
+    pub fn new(
+        client: W,
+        number_of_confirmations_for_event: u64,
+        expiration_alert_blocks: u64,
+    ) -> Self {
         Self {
             client,
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            expiration_alert_blocks,
         }
     }
 
@@ -143,7 +156,7 @@ impl<W: EthClient> EthWatch<W> {
         let block_difference =
             last_ethereum_block.saturating_sub(self.eth_state.last_ethereum_block());
 
-        let (unconfirmed_queue, received_priority_queue) = self
+        let (unconfirmed_queue, received_priority_queue, received_new_tokens) = self
             .update_eth_state(last_ethereum_block, block_difference)
             .await?;
 
@@ -153,17 +166,36 @@ impl<W: EthClient> EthWatch<W> {
             priority_queue.insert(serial_id, op);
         }
 
-        let new_state = ETHState::new(last_ethereum_block, unconfirmed_queue, priority_queue);
+        // Extend the existing token list with the new ones. The boundary block is requested
+        // twice, so the events that are already known are skipped.
+        let mut new_tokens = self.eth_state.new_tokens().to_vec();
+        for token in received_new_tokens {
+            if !new_tokens.iter().any(|known| known.id == token.id) {
+                new_tokens.push(token);
+            }
+        }
+
+        let new_state = ETHState::new(
+            last_ethereum_block,
+            unconfirmed_queue,
+            priority_queue,
+            new_tokens,
+        );
         self.set_new_state(new_state);
         Ok(())
     }
 
     async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
-        let (unconfirmed_queue, priority_queue) = self
+        let (unconfirmed_queue, priority_queue, new_tokens) = self
             .update_eth_state(last_ethereum_block, PRIORITY_EXPIRATION)
             .await?;
 
-        let new_state = ETHState::new(last_ethereum_block, unconfirmed_queue, priority_queue);
+        let new_state = ETHState::new(
+            last_ethereum_block,
+            unconfirmed_queue,
+            priority_queue,
+            new_tokens,
+        );
 
         self.set_new_state(new_state);
         vlog::debug!("ETH state: {:#?}", self.eth_state);
@@ -174,7 +206,11 @@ impl<W: EthClient> EthWatch<W> {
         &mut self,
         current_ethereum_block: u64,
         unprocessed_blocks_amount: u64,
-    ) -> anyhow::Result<(Vec<PriorityOp>, HashMap<u64, ReceivedPriorityOp>)> {
+    ) -> anyhow::Result<(
+        Vec<PriorityOp>,
+        HashMap<u64, ReceivedPriorityOp>,
+        Vec<NewTokenEvent>,
+    )> {
         let new_block_with_accepted_events =
             current_ethereum_block.saturating_sub(self.number_of_confirmations_for_event);
         let previous_block_with_accepted_events =
@@ -191,8 +227,15 @@ impl<W: EthClient> EthWatch<W> {
             .into_iter()
             .map(|priority_op| (priority_op.serial_id, priority_op.into()))
             .collect();
+        let new_tokens = self
+            .client
+            .get_new_tokens_events(
+                BlockNumber::Number(previous_block_with_accepted_events.into()),
+                BlockNumber::Number(new_block_with_accepted_events.into()),
+            )
+            .await?;
 
-        Ok((unconfirmed_queue, priority_queue))
+        Ok((unconfirmed_queue, priority_queue, new_tokens))
     }
 
     fn get_priority_requests(&self, first_serial_id: u64, max_chunks: usize) -> Vec<PriorityOp> {
@@ -228,6 +271,18 @@ impl<W: EthClient> EthWatch<W> {
         Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
     }
 
+    fn get_new_tokens(&self, last_eth_block: Option<u64>) -> Vec<NewTokenEvent> {
+        self.eth_state
+            .new_tokens()
+            .iter()
+            .filter(|token| match last_eth_block {
+                Some(block) => token.eth_block_number > block,
+                None => true,
+            })
+            .cloned()
+            .collect()
+    }
+
     fn find_ongoing_op_by_hash(&self, eth_hash: &[u8]) -> Option<PriorityOp> {
         self.eth_state
             .unconfirmed_queue()
@@ -266,12 +321,74 @@ impl<W: EthClient> EthWatch<W> {
             .collect()
     }
 
+    /// Checks the queue of the priority operations that are not yet executed by the contract
+    /// and reports its depth and the age of the oldest operation.
+    ///
+    /// If the oldest operation is close to its expiration block (after which the contract
+    /// will enter the exodus mode), an alert is raised. Returns `true` if the alert was raised.
+    async fn monitor_priority_queue(&self, current_ethereum_block: u64) -> anyhow::Result<bool> {
+        let open_requests = self.client.get_open_priority_requests().await?;
+        metrics::gauge!(
+            "eth_watcher.priority_queue.depth",
+            open_requests.total as f64
+        );
+
+        if open_requests.total == 0 {
+            metrics::gauge!("eth_watcher.priority_queue.oldest_op_age", 0f64);
+            return Ok(false);
+        }
+
+        // Operations that don't have enough confirmations yet are not in the priority queue,
+        // but they are too young to expire anyway.
+        let oldest_op = match self
+            .eth_state
+            .priority_queue()
+            .get(&open_requests.first_serial_id)
+        {
+            Some(op) => op.as_ref(),
+            None => return Ok(false),
+        };
+
+        let age = current_ethereum_block.saturating_sub(oldest_op.eth_block);
+        let blocks_to_expiration = oldest_op
+            .deadline_block
+            .saturating_sub(current_ethereum_block);
+        metrics::gauge!("eth_watcher.priority_queue.oldest_op_age", age as f64);
+        metrics::gauge!(
+            "eth_watcher.priority_queue.blocks_to_expiration",
+            blocks_to_expiration as f64
+        );
+
+        let expires_soon = blocks_to_expiration <= self.expiration_alert_blocks;
+        if expires_soon {
+            // This metric is needed to trigger grafana alerts.
+            metrics::counter!("eth_watcher.priority_queue.expiration_alert", 1);
+            vlog::error!(
+                "Priority operation #{} is about to expire: {} Ethereum blocks left until the exodus mode \
+                (open priority operations: {}, received in block {}, deadline block {})",
+                oldest_op.serial_id,
+                blocks_to_expiration,
+                open_requests.total,
+                oldest_op.eth_block,
+                oldest_op.deadline_block,
+            );
+        }
+
+        Ok(expires_soon)
+    }
+
     async fn poll_eth_node(&mut self) -> anyhow::Result<()> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
 
         if last_block_number > self.eth_state.last_ethereum_block() {
             self.process_new_blocks(last_block_number).await?;
+
+            // Failure to check the priority queue state should not affect the processing
+            // of the new operations, so the error is only reported.
+            if let Err(error) = self.monitor_priority_queue(last_block_number).await {
+                vlog::warn!("Unable to check the priority queue state: {}", error);
+            }
         }
 
         metrics::histogram!("eth_watcher.poll_eth_node", start.elapsed());
@@ -382,6 +499,13 @@ impl<W: EthClient> EthWatch<W> {
                     let unconfirmed_op = self.find_ongoing_op_by_hash(&eth_hash);
                     resp.send(unconfirmed_op).unwrap_or_default();
                 }
+                EthWatchRequest::GetNewTokens {
+                    last_eth_block,
+                    resp,
+                } => {
+                    resp.send(self.get_new_tokens(last_eth_block))
+                        .unwrap_or_default();
+                }
                 EthWatchRequest::IsPubkeyChangeAuthorized {
                     address,
                     nonce,
@@ -406,11 +530,16 @@ pub fn start_eth_watch(
     eth_req_receiver: mpsc::Receiver<EthWatchRequest>,
 ) -> JoinHandle<()> {
     let client = EthereumGateway::from_config(&config_options);
-    let eth_client = EthHttpClient::new(client, config_options.contracts.contract_addr);
+    let eth_client = EthHttpClient::new(
+        client,
+        config_options.contracts.contract_addr,
+        config_options.contracts.governance_addr,
+    );
 
     let eth_watch = EthWatch::new(
         eth_client,
         config_options.eth_watch.confirmations_for_eth_event,
+        config_options.eth_watch.priority_op_expiration_alert_blocks,
     );
 
     tokio::spawn(eth_watch.run(eth_req_receiver));
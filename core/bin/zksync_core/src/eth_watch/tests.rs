@@ -3,14 +3,21 @@ use std::collections::HashMap;
 
 use web3::types::{Address, BlockNumber};
 
-use zksync_types::{AccountId, Deposit, FullExit, Nonce, PriorityOp, TokenId, ZkSyncPriorityOp};
+use zksync_types::{
+    AccountId, Deposit, FullExit, NewTokenEvent, Nonce, PriorityOp, TokenId, ZkSyncPriorityOp,
+};
 
-use crate::eth_watch::{client::EthClient, EthWatch};
+use crate::eth_watch::{
+    client::{EthClient, OpenPriorityRequests},
+    EthWatch,
+};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
+    new_tokens: Vec<NewTokenEvent>,
+    open_priority_requests: OpenPriorityRequests,
     last_block_number: u64,
 }
 
@@ -18,6 +25,8 @@ impl FakeEthClientData {
     fn new() -> Self {
         Self {
             priority_ops: Default::default(),
+            new_tokens: Default::default(),
+            open_priority_requests: Default::default(),
             last_block_number: 0,
         }
     }
@@ -31,6 +40,13 @@ impl FakeEthClientData {
                 .push(op.clone());
         }
     }
+
+    fn add_new_tokens(&mut self, tokens: &[NewTokenEvent]) {
+        for token in tokens {
+            self.last_block_number = max(token.eth_block_number, self.last_block_number);
+            self.new_tokens.push(token.clone());
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -49,6 +65,14 @@ impl FakeEthClient {
         self.inner.write().await.add_operations(ops);
     }
 
+    async fn add_new_tokens(&mut self, tokens: &[NewTokenEvent]) {
+        self.inner.write().await.add_new_tokens(tokens);
+    }
+
+    async fn set_open_priority_requests(&mut self, open_priority_requests: OpenPriorityRequests) {
+        self.inner.write().await.open_priority_requests = open_priority_requests;
+    }
+
     async fn block_to_number(&self, block: &BlockNumber) -> u64 {
         match block {
             BlockNumber::Latest => self.inner.read().await.last_block_number,
@@ -77,6 +101,24 @@ impl EthClient for FakeEthClient {
         Ok(operations)
     }
 
+    async fn get_new_tokens_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<NewTokenEvent>, anyhow::Error> {
+        let from = self.block_to_number(&from).await;
+        let to = self.block_to_number(&to).await;
+        Ok(self
+            .inner
+            .read()
+            .await
+            .new_tokens
+            .iter()
+            .filter(|token| (from..=to).contains(&token.eth_block_number))
+            .cloned()
+            .collect())
+    }
+
     async fn block_number(&self) -> Result<u64, anyhow::Error> {
         Ok(self.inner.read().await.last_block_number)
     }
@@ -96,10 +138,14 @@ impl EthClient for FakeEthClient {
     ) -> Result<u64, anyhow::Error> {
         unreachable!()
     }
+
+    async fn get_open_priority_requests(&self) -> Result<OpenPriorityRequests, anyhow::Error> {
+        Ok(self.inner.read().await.open_priority_requests)
+    }
 }
 
 fn create_watcher<T: EthClient>(client: T) -> EthWatch<T> {
-    EthWatch::new(client, 1)
+    EthWatch::new(client, 1, 10)
 }
 
 #[tokio::test]
@@ -364,3 +410,95 @@ async fn test_restore_and_poll_time_lag() {
     priority_queues.get(&0).unwrap();
     priority_queues.get(&1).unwrap();
 }
+
+/// Checks that the confirmed `NewToken` events are collected once and can be requested
+/// starting from the given Ethereum block.
+#[tokio::test]
+async fn test_new_tokens() {
+    let mut client = FakeEthClient::new();
+    let new_token = |id: u16, eth_block_number: u64| NewTokenEvent {
+        eth_block_number,
+        address: [id as u8; 20].into(),
+        id: TokenId(id),
+    };
+    client
+        .add_new_tokens(&[new_token(1, 1), new_token(2, 3), new_token(3, 4)])
+        .await;
+
+    let mut watcher = create_watcher(client.clone());
+    watcher.restore_state_from_eth(4).await.unwrap();
+    // The last token doesn't have enough confirmations yet.
+    assert_eq!(
+        watcher.get_new_tokens(None),
+        vec![new_token(1, 1), new_token(2, 3)]
+    );
+
+    // The boundary block is requested again, but its events must not be duplicated.
+    client.add_new_tokens(&[new_token(4, 5)]).await;
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(
+        watcher.get_new_tokens(None),
+        vec![new_token(1, 1), new_token(2, 3), new_token(3, 4)]
+    );
+    assert_eq!(watcher.get_new_tokens(Some(3)), vec![new_token(3, 4)]);
+}
+
+fn deposit_op(serial_id: u64, eth_block: u64, deadline_block: u64) -> PriorityOp {
+    PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Default::default(),
+            token: TokenId(0),
+            amount: Default::default(),
+            to: Default::default(),
+        }),
+        deadline_block,
+        eth_hash: [serial_id as u8; 32].into(),
+        eth_block,
+    }
+}
+
+/// Checks that the alert is raised once the oldest open priority operation approaches
+/// its expiration block.
+#[tokio::test]
+async fn test_priority_queue_expiration_alert() {
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[deposit_op(0, 1, 20), deposit_op(1, 5, 1000)])
+        .await;
+    let mut watcher = create_watcher(client.clone());
+    watcher.poll_eth_node().await.unwrap();
+
+    // Nothing to monitor while the contract has no open priority requests.
+    assert!(!watcher.monitor_priority_queue(15).await.unwrap());
+
+    client
+        .set_open_priority_requests(OpenPriorityRequests {
+            first_serial_id: 0,
+            total: 2,
+        })
+        .await;
+    // The oldest operation is far from the expiration yet.
+    assert!(!watcher.monitor_priority_queue(5).await.unwrap());
+    // The oldest operation expires in 5 blocks, which is within the alert threshold.
+    assert!(watcher.monitor_priority_queue(15).await.unwrap());
+}
+
+/// Checks that the fresh open priority operations don't trigger the alert.
+#[tokio::test]
+async fn test_priority_queue_no_alert_for_fresh_ops() {
+    let mut client = FakeEthClient::new();
+    client
+        .add_operations(&[deposit_op(0, 1, 1000), deposit_op(1, 5, 1000)])
+        .await;
+    client
+        .set_open_priority_requests(OpenPriorityRequests {
+            first_serial_id: 0,
+            total: 2,
+        })
+        .await;
+    let mut watcher = create_watcher(client);
+    watcher.poll_eth_node().await.unwrap();
+
+    assert!(!watcher.monitor_priority_queue(15).await.unwrap());
+}
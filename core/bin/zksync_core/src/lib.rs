@@ -2,29 +2,36 @@ use zksync_types::{tokens::get_genesis_token_list, Token, TokenId};
 
 use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
+    balance_snapshots_populator::run_balance_snapshots_populator,
     block_proposer::run_block_proposer_task,
     committer::run_committer,
     eth_watch::start_eth_watch,
     mempool::run_mempool_tasks,
     private_api::start_private_core_api,
+    pruner::run_pruner,
     rejected_tx_cleaner::run_rejected_tx_cleaner,
-    state_keeper::{start_state_keeper, ZkSyncStateKeeper},
+    state_keeper::{start_gas_price_updater, start_state_keeper, SealCostLimit, ZkSyncStateKeeper},
+    token_handler::run_token_handler,
 };
 use futures::{channel::mpsc, future};
 use tokio::task::JoinHandle;
 use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
+pub mod balance_snapshots_populator;
 pub mod balancer;
 pub mod block_proposer;
 pub mod committer;
 pub mod eth_watch;
 pub mod mempool;
 pub mod private_api;
+pub mod pruner;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
+pub mod token_handler;
 
 /// Waits for *any* of the tokio tasks to be finished.
 /// Since the main tokio tasks are used as actors which should live as long
@@ -86,6 +93,7 @@ pub async fn genesis_init(config: &ZkSyncConfig) {
 /// Starts the core application, which has the following sub-modules:
 ///
 /// - Ethereum Watcher, module to monitor on-chain operations.
+/// - token handler, module to store the tokens added on-chain into the database.
 /// - zkSync state keeper, module to execute and seal blocks.
 /// - mempool, module to organize incoming transactions.
 /// - block proposer, module to create block proposals for state keeper.
@@ -116,13 +124,26 @@ pub async fn run_core(
     // Insert pending withdrawals into database (if required)
     let mut storage_processor = connection_pool.access_storage().await?;
 
+    // Make sure the server isn't started against the database of another network.
+    let network = config.chain.eth.network.to_string();
+    let db_network = storage_processor
+        .config_schema()
+        .bind_network(&network)
+        .await?;
+    anyhow::ensure!(
+        db_network == network,
+        "Database belongs to the {} network, while the server is configured for {}",
+        db_network,
+        network
+    );
+
     // Start State Keeper.
     let state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage_processor).await?;
     let pending_block = state_keeper_init
         .get_pending_block(&mut storage_processor)
         .await;
 
-    let state_keeper = ZkSyncStateKeeper::new(
+    let mut state_keeper = ZkSyncStateKeeper::new(
         state_keeper_init,
         config.chain.state_keeper.fee_account_addr,
         state_keeper_req_receiver,
@@ -132,6 +153,18 @@ pub async fn run_core(
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
         config.chain.state_keeper.last_tx_signer_data(),
     );
+    let mut gas_price_updater_task = None;
+    if config.chain.state_keeper.seal_max_cost_per_operation > 0 {
+        state_keeper = state_keeper.with_seal_cost_limit(SealCostLimit {
+            max_cost_per_operation: config.chain.state_keeper.seal_max_cost_per_operation.into(),
+            max_delay_iterations: config.chain.state_keeper.seal_max_delay_iterations as usize,
+        });
+        gas_price_updater_task = Some(start_gas_price_updater(
+            EthereumGateway::from_config(&config),
+            state_keeper_req_sender.clone(),
+            config.eth_sender.gas_price_limit.sample_interval(),
+        ));
+    }
     let state_keeper_task = start_state_keeper(state_keeper, pending_block);
 
     // Start committer.
@@ -156,6 +189,23 @@ pub async fn run_core(
     // Start rejected transactions cleaner task.
     let rejected_tx_cleaner_task = run_rejected_tx_cleaner(&config, connection_pool.clone());
 
+    // Start the old data pruner (if required).
+    let pruner_task = if config.db.pruning_enabled {
+        Some(run_pruner(&config, connection_pool.clone()))
+    } else {
+        None
+    };
+
+    // Start the balance snapshots populator (if required).
+    let balance_snapshots_task = if config.db.balance_snapshots_enabled {
+        Some(run_balance_snapshots_populator(
+            &config,
+            connection_pool.clone(),
+        ))
+    } else {
+        None
+    };
+
     // Start block proposer.
     let proposer_task = run_block_proposer_task(
         &config,
@@ -163,22 +213,34 @@ pub async fn run_core(
         state_keeper_req_sender.clone(),
     );
 
+    // Start token handler.
+    let token_handler_task = run_token_handler(
+        &config,
+        connection_pool.clone(),
+        eth_watch_req_sender.clone(),
+    );
+
     // Start private API.
     start_private_core_api(
         panic_notify.clone(),
         mempool_tx_request_sender,
         eth_watch_req_sender,
+        state_keeper_req_sender,
         config.api.private.clone(),
     );
 
-    let task_futures = vec![
+    let mut task_futures = vec![
         eth_watch_task,
         state_keeper_task,
         committer_task,
         mempool_task,
         proposer_task,
         rejected_tx_cleaner_task,
+        token_handler_task,
     ];
+    task_futures.extend(gas_price_updater_task);
+    task_futures.extend(pruner_task);
+    task_futures.extend(balance_snapshots_task);
 
     Ok(task_futures)
 }
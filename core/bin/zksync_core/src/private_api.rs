@@ -7,7 +7,10 @@
 //! All the incoming data is assumed to be correct and not double-checked
 //! for correctness.
 
-use crate::{eth_watch::EthWatchRequest, mempool::MempoolTransactionRequest};
+use crate::{
+    eth_watch::EthWatchRequest, mempool::MempoolTransactionRequest,
+    state_keeper::StateKeeperRequest,
+};
 use actix_web::{web, App, HttpResponse, HttpServer};
 use futures::{
     channel::{mpsc, oneshot},
@@ -22,6 +25,7 @@ use zksync_utils::panic_notify::ThreadPanicNotify;
 struct AppState {
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
 }
 
 /// Adds a new transaction into the mempool.
@@ -142,11 +146,30 @@ async fn unconfirmed_op(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Estimates the L1 cost of committing and executing the pending block if it was sealed right now.
+#[actix_web::get("/pending_block_cost")]
+async fn pending_block_cost(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = StateKeeperRequest::EstimatePendingBlockCost(sender);
+    let mut state_keeper_sender = data.state_keeper_req_sender.clone();
+    state_keeper_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn start_private_core_api(
     panic_notify: mpsc::Sender<bool>,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
     config: PrivateApi,
 ) {
     thread::Builder::new()
@@ -161,6 +184,7 @@ pub fn start_private_core_api(
                     let app_state = AppState {
                         mempool_tx_sender: mempool_tx_sender.clone(),
                         eth_watch_req_sender: eth_watch_req_sender.clone(),
+                        state_keeper_req_sender: state_keeper_req_sender.clone(),
                     };
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -173,6 +197,7 @@ pub fn start_private_core_api(
                         .service(unconfirmed_op)
                         .service(unconfirmed_ops)
                         .service(unconfirmed_deposits)
+                        .service(pending_block_cost)
                 })
                 .bind(&config.bind_addr())
                 .expect("failed to bind")
@@ -182,3 +207,53 @@ pub fn start_private_core_api(
         })
         .expect("failed to start prover server");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_keeper::PendingBlockCost;
+    use actix_web::test;
+    use futures::StreamExt;
+    use zksync_types::U256;
+
+    /// Checks that the pending block cost is requested from the state keeper.
+    #[actix_rt::test]
+    async fn pending_block_cost_estimation() {
+        let (mempool_tx_sender, _mempool_tx_receiver) = mpsc::channel(1);
+        let (eth_watch_req_sender, _eth_watch_req_receiver) = mpsc::channel(1);
+        let (state_keeper_req_sender, mut state_keeper_req_receiver) = mpsc::channel(1);
+
+        let expected_cost = PendingBlockCost {
+            commit_gas: U256::from(100_000),
+            execute_gas: U256::from(50_000),
+            operations: 3,
+            gas_price: Some(U256::from(10)),
+        };
+        let cost = expected_cost.clone();
+        actix_rt::spawn(async move {
+            while let Some(request) = state_keeper_req_receiver.next().await {
+                if let StateKeeperRequest::EstimatePendingBlockCost(sender) = request {
+                    sender.send(cost.clone()).unwrap_or_default();
+                }
+            }
+        });
+
+        let app_state = AppState {
+            mempool_tx_sender,
+            eth_watch_req_sender,
+            state_keeper_req_sender,
+        };
+        let mut app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state))
+                .service(pending_block_cost),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/pending_block_cost")
+            .to_request();
+        let cost: PendingBlockCost = test::read_response_json(&mut app, request).await;
+        assert_eq!(cost, expected_cost);
+    }
+}
@@ -0,0 +1,38 @@
+//! The pruner is responsible for moving the data of the old executed blocks out of the main tables
+//! (see `PruningSchema` for details on which data is pruned).
+//!
+//! Details of the executed transactions and the proofs are not needed for the server operation
+//! once the block is executed on Ethereum, but they make the database grow unboundedly.
+//! The amount of the latest blocks which data is kept is configurable as well as the actor's sleep time.
+
+// External uses
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+
+#[must_use]
+pub fn run_pruner(config: &ZkSyncConfig, db_pool: ConnectionPool) -> JoinHandle<()> {
+    let retention_blocks = config.db.pruning_retention_blocks;
+    let interval = config.db.pruning_interval();
+    let mut timer = time::interval(interval);
+
+    tokio::spawn(async move {
+        loop {
+            timer.tick().await;
+            let mut storage = db_pool
+                .access_storage()
+                .await
+                .expect("pruner couldn't access the database");
+            match storage
+                .pruning_schema()
+                .prune_with_retention(retention_blocks)
+                .await
+            {
+                Ok(stats) => vlog::info!("Pruned the data of the old blocks: {:?}", stats),
+                Err(e) => vlog::error!("Failed to prune the data of the old blocks: {}", e),
+            }
+        }
+    })
+}
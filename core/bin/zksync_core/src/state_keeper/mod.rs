@@ -1,6 +1,6 @@
 use anyhow::{ensure, Result};
 use std::collections::{HashMap, VecDeque};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
@@ -8,7 +8,8 @@ use futures::{
     SinkExt,
 };
 use itertools::Itertools;
-use tokio::task::JoinHandle;
+use serde::{Deserialize, Serialize};
+use tokio::{task::JoinHandle, time};
 // Workspace uses
 use zksync_crypto::{
     convert::FeConvert,
@@ -16,6 +17,7 @@ use zksync_crypto::{
     params::ETH_TOKEN_ID,
     PrivateKey,
 };
+use zksync_eth_client::EthereumGateway;
 use zksync_state::state::{CollectedFee, OpSuccess, ZkSyncState};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
@@ -28,7 +30,7 @@ use zksync_types::{
     mempool::SignedTxVariant,
     tx::{TxHash, ZkSyncTx},
     Account, AccountId, AccountTree, AccountUpdate, AccountUpdates, Address, BlockNumber,
-    PriorityOp, SignedZkSyncTx, Transfer, TransferOp, H256,
+    PriorityOp, SignedZkSyncTx, Transfer, TransferOp, H256, U256,
 };
 // Local uses
 use crate::{
@@ -52,6 +54,50 @@ pub enum StateKeeperRequest {
     ExecuteMiniBlock(ProposedBlock),
     SealBlock,
     GetCurrentState(oneshot::Sender<ZkSyncStateInitParams>),
+    /// Estimates the L1 cost of the pending block as if it was sealed right now.
+    EstimatePendingBlockCost(oneshot::Sender<PendingBlockCost>),
+    /// Updates the L1 gas price used to decide whether sealing the pending block is economical.
+    UpdateGasPrice(U256),
+}
+
+/// Estimated L1 cost of the pending block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingBlockCost {
+    /// Estimated gas for the block commitment.
+    pub commit_gas: U256,
+    /// Estimated gas for the block execution.
+    pub execute_gas: U256,
+    /// Amount of successfully executed operations in the block.
+    pub operations: usize,
+    /// Last known L1 gas price, if any.
+    pub gas_price: Option<U256>,
+}
+
+impl PendingBlockCost {
+    /// Total estimated gas to commit and execute the block.
+    pub fn total_gas(&self) -> U256 {
+        self.commit_gas + self.execute_gas
+    }
+
+    /// Estimated cost of the block in wei per executed operation.
+    /// Returns `None` if the gas price is not known or the block is empty.
+    pub fn cost_per_operation(&self) -> Option<U256> {
+        if self.operations == 0 {
+            return None;
+        }
+        self.gas_price
+            .map(|gas_price| self.total_gas() * gas_price / U256::from(self.operations))
+    }
+}
+
+/// Limits on the cost of sealing the block, used to postpone sealing of blocks
+/// that are too expensive to publish on L1 at the current gas prices.
+#[derive(Debug, Clone, Copy)]
+pub struct SealCostLimit {
+    /// Maximum L1 cost of the block in wei per executed operation.
+    pub max_cost_per_operation: U256,
+    /// Maximum amount of miniblock iterations for which sealing can be postponed.
+    pub max_delay_iterations: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -141,6 +187,11 @@ pub struct ZkSyncStateKeeper {
 
     /// ZK sync account that is used to create last transfer before sealing block (e.g. to change block hash)
     tx_signer: Option<(Address, PrivateKey)>,
+
+    /// Last known L1 gas price.
+    gas_price: Option<U256>,
+    /// If set, sealing of the blocks by timeout is postponed while the block is uneconomical.
+    seal_cost_limit: Option<SealCostLimit>,
 }
 
 #[derive(Debug, Clone)]
@@ -424,6 +475,9 @@ impl ZkSyncStateKeeper {
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
             tx_signer,
+
+            gas_price: None,
+            seal_cost_limit: None,
         };
 
         let root = keeper.state.root_hash();
@@ -432,6 +486,12 @@ impl ZkSyncStateKeeper {
         keeper
     }
 
+    /// Enables postponing of the block sealing while the block is too expensive to publish on L1.
+    pub fn with_seal_cost_limit(mut self, seal_cost_limit: SealCostLimit) -> Self {
+        self.seal_cost_limit = Some(seal_cost_limit);
+        self
+    }
+
     pub async fn initialize(&mut self, pending_block: Option<SendablePendingBlock>) {
         let start = Instant::now();
         if let Some(pending_block) = pending_block {
@@ -562,6 +622,14 @@ impl ZkSyncStateKeeper {
                 StateKeeperRequest::GetCurrentState(sender) => {
                     sender.send(self.get_current_state()).unwrap_or_default();
                 }
+                StateKeeperRequest::EstimatePendingBlockCost(sender) => {
+                    sender
+                        .send(self.estimate_pending_block_cost())
+                        .unwrap_or_default();
+                }
+                StateKeeperRequest::UpdateGasPrice(gas_price) => {
+                    self.gas_price = Some(gas_price);
+                }
             }
         }
     }
@@ -641,9 +709,10 @@ impl ZkSyncStateKeeper {
         } else {
             self.max_miniblock_iterations
         };
-        if self.pending_block.chunks_left == 0
-            || self.pending_block.pending_block_iteration > max_miniblock_iterations
-        {
+        let deadline_reached = self.pending_block.pending_block_iteration
+            > max_miniblock_iterations
+            && !self.should_postpone_sealing(max_miniblock_iterations);
+        if self.pending_block.chunks_left == 0 || deadline_reached {
             self.seal_pending_block().await;
         } else {
             // We've already incremented the pending block iteration, so this iteration will count towards
@@ -657,6 +726,49 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.execute_proposed_block", start.elapsed());
     }
 
+    /// Estimates the L1 gas required to commit and execute the pending block.
+    fn estimate_pending_block_cost(&self) -> PendingBlockCost {
+        PendingBlockCost {
+            commit_gas: self.pending_block.gas_counter.commit_gas_limit(),
+            execute_gas: self.pending_block.gas_counter.verify_gas_limit(),
+            operations: self.pending_block.success_operations.len(),
+            gas_price: self.gas_price,
+        }
+    }
+
+    /// Checks whether the sealing of the pending block by timeout should be postponed,
+    /// because the block is too expensive to publish at the current gas price.
+    /// Blocks that require fast processing are never postponed.
+    fn should_postpone_sealing(&self, max_miniblock_iterations: usize) -> bool {
+        let seal_cost_limit = match self.seal_cost_limit {
+            Some(seal_cost_limit) => seal_cost_limit,
+            None => return false,
+        };
+        if self.pending_block.fast_processing_required
+            || self.pending_block.pending_block_iteration
+                > max_miniblock_iterations + seal_cost_limit.max_delay_iterations
+        {
+            return false;
+        }
+
+        let cost = self.estimate_pending_block_cost();
+        match cost.cost_per_operation() {
+            Some(cost_per_operation)
+                if cost_per_operation > seal_cost_limit.max_cost_per_operation =>
+            {
+                vlog::debug!(
+                    "Postponing the block sealing: cost per operation is {} wei, gas: {}, operations: {}",
+                    cost_per_operation,
+                    cost.total_gas(),
+                    cost.operations
+                );
+                metrics::counter!("state_keeper.postponed_block_sealing", 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
     // Err if there is no space in current block
     fn apply_priority_op(
         &mut self,
@@ -776,7 +888,7 @@ impl ZkSyncStateKeeper {
         txs: &[SignedZkSyncTx],
         batch_id: i64,
     ) -> Result<Vec<ExecutedOperations>, ()> {
-        metrics::gauge!("tx_batch_size", txs.len() as f64);
+        metrics::gauge!("state_keeper.tx_batch_size", txs.len() as f64);
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_batch(txs);
 
@@ -1204,3 +1316,29 @@ pub fn start_state_keeper(
 ) -> JoinHandle<()> {
     tokio::spawn(sk.run(pending_block))
 }
+
+/// Periodically loads the L1 gas price and sends it to the state keeper,
+/// so it can estimate the cost of publishing the pending block.
+pub fn start_gas_price_updater(
+    eth_gateway: EthereumGateway,
+    mut state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
+    update_interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(update_interval);
+
+        loop {
+            timer.tick().await;
+
+            match eth_gateway.get_gas_price().await {
+                Ok(gas_price) => {
+                    state_keeper_req_sender
+                        .send(StateKeeperRequest::UpdateGasPrice(gas_price))
+                        .await
+                        .expect("state keeper receiver dropped");
+                }
+                Err(err) => vlog::warn!("Failed to load the L1 gas price: {}", err),
+            }
+        }
+    })
+}
@@ -1,4 +1,4 @@
-use super::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
+use super::{CommitRequest, SealCostLimit, ZkSyncStateInitParams, ZkSyncStateKeeper};
 use crate::mempool::ProposedBlock;
 use futures::{channel::mpsc, stream::StreamExt};
 use num::BigUint;
@@ -1151,4 +1151,56 @@ mod execute_proposed_block {
             panic!("Block not stored");
         }
     }
+
+    /// Checks that sealing of the block by timeout is postponed while the block is too expensive
+    /// to publish at the current gas price, but no longer than the configured amount of iterations.
+    #[tokio::test]
+    async fn uneconomical_block_sealing_postponed() {
+        let mut tester = StateKeeperTester::new(20, 1, 1);
+        tester.state_keeper.seal_cost_limit = Some(SealCostLimit {
+            max_cost_per_operation: U256::from(1),
+            max_delay_iterations: 2,
+        });
+        tester.state_keeper.gas_price = Some(U256::from(1_000_000_000u64));
+        let initial_block_number = tester.state_keeper.state.block_number;
+
+        // First iteration doesn't reach the deadline, the next two are postponed.
+        for iteration in 1..=3 {
+            apply_single_transfer(&mut tester).await;
+            assert_eq!(
+                tester.state_keeper.pending_block.pending_block_iteration,
+                iteration
+            );
+            assert_eq!(tester.state_keeper.state.block_number, initial_block_number);
+        }
+
+        let cost = tester.state_keeper.estimate_pending_block_cost();
+        assert_eq!(cost.operations, 3);
+        assert!(cost.cost_per_operation().unwrap() > U256::from(1));
+
+        // Delay limit is reached, block must be sealed.
+        apply_single_transfer(&mut tester).await;
+        assert_eq!(
+            tester.state_keeper.state.block_number,
+            initial_block_number + 1
+        );
+    }
+
+    /// Checks that sealing is not postponed if the gas price is not known.
+    #[tokio::test]
+    async fn block_sealing_without_gas_price() {
+        let mut tester = StateKeeperTester::new(20, 1, 1);
+        tester.state_keeper.seal_cost_limit = Some(SealCostLimit {
+            max_cost_per_operation: U256::from(1),
+            max_delay_iterations: 2,
+        });
+        let initial_block_number = tester.state_keeper.state.block_number;
+
+        apply_single_transfer(&mut tester).await;
+        apply_single_transfer(&mut tester).await;
+        assert_eq!(
+            tester.state_keeper.state.block_number,
+            initial_block_number + 1
+        );
+    }
 }
@@ -0,0 +1,236 @@
+//! Token handler stores the tokens added to the network on-chain into the database.
+//!
+//! Tokens can be added either by the network governor or by anyone paying the listing fee to the
+//! token governance contract, so no operator action is required to make the token tradable.
+//! Once the `NewToken` event gets enough confirmations, the handler fetches the token metadata
+//! from its contract and vets it: the symbol must be unique and human-readable, otherwise the
+//! placeholder symbol is used. Whether the token can be used to pay fees is decided separately
+//! by the fee ticker based on the token liquidity.
+
+// Built-in deps
+use std::time::Duration;
+// External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::{task::JoinHandle, time};
+use web3::contract::Options;
+// Workspace deps
+use zksync_config::ZkSyncConfig;
+use zksync_contracts::erc20_metadata_contract;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{NewTokenEvent, Token, TokenId, TokenLike, U256};
+// Local deps
+use crate::eth_watch::EthWatchRequest;
+
+/// Maximum length of the token symbol accepted from the token contract.
+const MAX_SYMBOL_LENGTH: usize = 10;
+/// Decimals used if the token contract doesn't provide them.
+const DEFAULT_DECIMALS: u8 = 18;
+
+/// Token metadata as reported by the token contract.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TokenMetadata {
+    symbol: Option<String>,
+    decimals: Option<u8>,
+}
+
+struct TokenHandler {
+    pool: ConnectionPool,
+    eth_client: EthereumGateway,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    poll_interval: Duration,
+    /// The last Ethereum block with the already stored tokens.
+    last_eth_block: Option<u64>,
+}
+
+impl TokenHandler {
+    async fn run(mut self) {
+        let mut timer = time::interval(self.poll_interval);
+        loop {
+            timer.tick().await;
+
+            if let Err(e) = self.store_new_tokens().await {
+                vlog::error!("Failed to store the new tokens: {}", e);
+            }
+        }
+    }
+
+    async fn store_new_tokens(&mut self) -> anyhow::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.eth_watch_req
+            .send(EthWatchRequest::GetNewTokens {
+                last_eth_block: self.last_eth_block,
+                resp: sender,
+            })
+            .await?;
+        let new_tokens = receiver.await?;
+
+        for event in new_tokens {
+            self.store_token(&event).await?;
+            self.last_eth_block = Some(event.eth_block_number);
+        }
+        Ok(())
+    }
+
+    async fn store_token(&self, event: &NewTokenEvent) -> anyhow::Result<()> {
+        // Tokens added by the operator keep the metadata they were added with.
+        let mut storage = self.pool.access_storage().await?;
+        if storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(event.id))
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+        drop(storage);
+
+        let metadata = self.fetch_metadata(event).await;
+        let mut storage = self.pool.access_storage().await?;
+        let symbol_taken = match &metadata.symbol {
+            Some(symbol) => storage
+                .tokens_schema()
+                .get_token(TokenLike::Symbol(symbol.clone()))
+                .await?
+                .is_some(),
+            None => false,
+        };
+        let token = vet_token(event, metadata, symbol_taken);
+
+        storage.tokens_schema().store_token(token.clone()).await?;
+        vlog::info!(
+            "Listed token: {}, id: {}, address: {:?}, decimals: {}",
+            token.symbol,
+            *token.id,
+            token.address,
+            token.decimals
+        );
+        metrics::counter!("token_handler.listed_tokens", 1);
+        Ok(())
+    }
+
+    /// Fetches the optional ERC20 metadata. Errors are not propagated, since the token contract
+    /// is not obliged to implement these methods.
+    async fn fetch_metadata(&self, event: &NewTokenEvent) -> TokenMetadata {
+        let symbol = self
+            .eth_client
+            .call_contract_function(
+                "symbol",
+                (),
+                None,
+                Options::default(),
+                None,
+                event.address,
+                erc20_metadata_contract(),
+            )
+            .await
+            .map_err(|e| {
+                vlog::warn!(
+                    "Failed to get the symbol of token {:?}: {}",
+                    event.address,
+                    e
+                )
+            })
+            .ok();
+        let decimals = self
+            .eth_client
+            .call_contract_function(
+                "decimals",
+                (),
+                None,
+                Options::default(),
+                None,
+                event.address,
+                erc20_metadata_contract(),
+            )
+            .await
+            .map_err(|e| {
+                vlog::warn!(
+                    "Failed to get the decimals of token {:?}: {}",
+                    event.address,
+                    e
+                )
+            })
+            .ok()
+            .filter(|decimals: &U256| *decimals <= U256::from(u8::MAX))
+            .map(|decimals| decimals.as_u32() as u8);
+
+        TokenMetadata { symbol, decimals }
+    }
+}
+
+/// Builds the token from its metadata, replacing the values which can't be used as is.
+fn vet_token(event: &NewTokenEvent, metadata: TokenMetadata, symbol_taken: bool) -> Token {
+    let symbol = metadata
+        .symbol
+        .filter(|symbol| {
+            !symbol_taken
+                && !symbol.is_empty()
+                && symbol.len() <= MAX_SYMBOL_LENGTH
+                && symbol.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or_else(|| placeholder_symbol(event.id));
+    let decimals = metadata.decimals.unwrap_or(DEFAULT_DECIMALS);
+
+    Token::new(event.id, event.address, &symbol, decimals)
+}
+
+fn placeholder_symbol(id: TokenId) -> String {
+    format!("ERC20-{}", *id)
+}
+
+#[must_use]
+pub fn run_token_handler(
+    config: &ZkSyncConfig,
+    pool: ConnectionPool,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+) -> JoinHandle<()> {
+    let handler = TokenHandler {
+        pool,
+        eth_client: EthereumGateway::from_config(config),
+        eth_watch_req,
+        poll_interval: config.eth_watch.poll_interval(),
+        last_eth_block: None,
+    };
+
+    tokio::spawn(vlog::supervised("token_handler", handler.run()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vet_token_metadata() {
+        let event = NewTokenEvent {
+            eth_block_number: 1,
+            address: [1u8; 20].into(),
+            id: TokenId(17),
+        };
+        let metadata = |symbol: Option<&str>, decimals: Option<u8>| TokenMetadata {
+            symbol: symbol.map(str::to_owned),
+            decimals,
+        };
+
+        let token = vet_token(&event, metadata(Some("DAI"), Some(6)), false);
+        assert_eq!(token, Token::new(TokenId(17), event.address, "DAI", 6));
+
+        // Missing metadata is replaced with the defaults.
+        let token = vet_token(&event, metadata(None, None), false);
+        assert_eq!(
+            token,
+            Token::new(TokenId(17), event.address, "ERC20-17", DEFAULT_DECIMALS)
+        );
+
+        // Symbols that can be confused with other tokens are not accepted.
+        for symbol in &["DAI", "", "VERYLONGSYMBOL", "D A I", "DAI\u{200b}"] {
+            let symbol_taken = *symbol == "DAI";
+            let token = vet_token(&event, metadata(Some(symbol), Some(6)), symbol_taken);
+            assert_eq!(token.symbol, "ERC20-17");
+            assert_eq!(token.decimals, 6);
+        }
+    }
+}
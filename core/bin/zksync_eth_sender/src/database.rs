@@ -5,10 +5,11 @@ use std::str::FromStr;
 use num::BigUint;
 use zksync_basic_types::{H256, U256};
 // Workspace uses
+use zksync_eth_client::Eip1559Fees;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
 // Local uses
-use super::transactions::ETHStats;
+use super::{gas_usage::ops_gas_usage, transactions::ETHStats};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 
 /// Abstract database access trait, optimized for the needs of `ETHSender`.
@@ -57,6 +58,24 @@ pub(super) trait DatabaseInterface {
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
+    /// Moves the stored nonce forward if the on-chain nonce of the operator account is greater.
+    /// Returns `true` if the stored nonce was updated.
+    async fn sync_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<bool>;
+
+    /// Assigns new nonces to the Ethereum operation which nonce was consumed by another transaction
+    /// and to every pending operation created after it, so they are still mined in order.
+    /// Returns the IDs of the re-sequenced operations with their new nonces.
+    async fn reassign_nonces(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<Vec<(EthOpId, U256)>>;
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -74,17 +93,24 @@ pub(super) trait DatabaseInterface {
         new_gas_value: U256,
     ) -> anyhow::Result<()>;
 
-    /// Marks an operation as completed in the database.
+    /// Marks an operation as completed in the database and stores the gas used by its operations.
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
     async fn load_stats(&self, connection: &mut StorageProcessor<'_>) -> anyhow::Result<ETHStats>;
 
+    /// Loads the types of operations which must not be sent to Ethereum at the moment.
+    async fn load_paused_actions(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<AggregatedActionType>>;
+
     /// Loads the stored gas price limit.
     async fn load_gas_price_limit(
         &self,
@@ -99,6 +125,13 @@ pub(super) trait DatabaseInterface {
         average_gas_price: U256,
     ) -> anyhow::Result<()>;
 
+    /// Updates the stored EIP-1559 fees observed in the network.
+    async fn update_eip1559_fees(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        fees: Eip1559Fees,
+    ) -> anyhow::Result<()>;
+
     async fn is_previous_operation_confirmed(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -199,6 +232,36 @@ impl DatabaseInterface for Database {
         Ok(result)
     }
 
+    async fn sync_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<bool> {
+        let updated = connection
+            .ethereum_schema()
+            .sync_nonce(on_chain_nonce.as_u64() as i64)
+            .await?;
+
+        Ok(updated)
+    }
+
+    async fn reassign_nonces(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<Vec<(EthOpId, U256)>> {
+        let nonces = connection
+            .ethereum_schema()
+            .reassign_nonces(eth_op_id, on_chain_nonce.as_u64() as i64)
+            .await?
+            .into_iter()
+            .map(|(eth_op_id, nonce)| (eth_op_id, nonce.into()))
+            .collect();
+
+        Ok(nonces)
+    }
+
     async fn add_hash_entry(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -255,6 +318,7 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -306,6 +370,15 @@ impl DatabaseInterface for Database {
             _ => {}
         }
 
+        if let (Some((_, aggregated_op)), Some(gas_used)) = (&op.op, gas_used) {
+            let usage = ops_gas_usage(aggregated_op, gas_used);
+            if !usage.is_empty() {
+                transaction
+                    .ethereum_schema()
+                    .store_ops_gas_usage(&usage)
+                    .await?;
+            }
+        }
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
         transaction.commit().await?;
 
@@ -317,6 +390,14 @@ impl DatabaseInterface for Database {
         Ok(stats.into())
     }
 
+    async fn load_paused_actions(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<AggregatedActionType>> {
+        let paused_actions = connection.ethereum_schema().load_paused_actions().await?;
+        Ok(paused_actions)
+    }
+
     async fn load_gas_price_limit(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -337,4 +418,16 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn update_eip1559_fees(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        fees: Eip1559Fees,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .update_eip1559_fees(fees.base_fee_per_gas, fees.max_priority_fee_per_gas)
+            .await?;
+        Ok(())
+    }
 }
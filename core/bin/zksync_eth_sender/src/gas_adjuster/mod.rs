@@ -109,6 +109,8 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
                     vlog::warn!("Cannot add the sample gas price: {}", err);
                 }
             }
+            // Fee ticker calculates the fees using the EIP-1559 prices if they are known.
+            self.store_eip1559_fees(ethereum, db).await;
         }
 
         if self.last_price_renewal.elapsed() >= parameters::limit_update_interval() {
@@ -141,6 +143,26 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         }
     }
 
+    /// Stores the current EIP-1559 fees, unless the network doesn't support them yet.
+    async fn store_eip1559_fees(&self, ethereum: &EthereumGateway, db: &DB) {
+        let fees = match ethereum.get_eip1559_fees().await {
+            Ok(Some(fees)) => fees,
+            Ok(None) => return,
+            Err(err) => {
+                vlog::warn!("Cannot get the EIP-1559 fees: {}", err);
+                return;
+            }
+        };
+
+        let result = match db.acquire_connection().await {
+            Ok(mut connection) => db.update_eip1559_fees(&mut connection, fees).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            vlog::warn!("Cannot update the EIP-1559 fees in the database: {}", err);
+        }
+    }
+
     fn scale_up(&self, price_to_scale: U256, current_network_price: U256) -> U256 {
         let replacement_price = (price_to_scale * U256::from(115)) / U256::from(100);
         std::cmp::max(current_network_price, replacement_price)
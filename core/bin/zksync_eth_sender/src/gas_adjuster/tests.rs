@@ -8,7 +8,7 @@ use crate::{
     DatabaseInterface, GasAdjuster,
 };
 
-use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_eth_client::ethereum_gateway::{Eip1559Fees, EthereumGateway};
 
 /// Creates `Ethereum` and `Database` instances for the `GasAdjuster` tests.
 async fn eth_and_db_clients() -> (EthereumGateway, MockDatabase) {
@@ -292,3 +292,21 @@ async fn gas_price_limit_preservation() {
         assert_eq!(new_limit, price_limit.into());
     }
 }
+
+/// Checks that the EIP-1559 fees are stored once the network supports them.
+#[tokio::test]
+async fn eip1559_fees_update() {
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    // Pre-London network has no base fee.
+    gas_adjuster.keep_updated(&ethereum, &db).await;
+    assert_eq!(db.eip1559_fees().await, (None, None));
+
+    ethereum.get_mut_mock().unwrap().eip1559_fees = Some(Eip1559Fees {
+        base_fee_per_gas: 100.into(),
+        max_priority_fee_per_gas: 2.into(),
+    });
+    gas_adjuster.keep_updated(&ethereum, &db).await;
+    assert_eq!(db.eip1559_fees().await, (Some(100), Some(2)));
+}
@@ -0,0 +1,244 @@
+//! Gas actually used by the zkSync operations.
+//!
+//! The fee ticker calculates the fees from the estimated gas costs of the operations, see
+//! `GasCounter`. To learn the actual costs, the gas used by every confirmed L1 transaction is
+//! attributed to the operations of its blocks: every operation gets the share proportional to its
+//! estimated cost, while the fixed overhead of the transaction and the blocks is spread over the
+//! operations by their chunks. The attributed gas is stored per block and operation type.
+
+// Built-in deps
+use std::collections::BTreeMap;
+// Workspace uses
+use zksync_basic_types::U256;
+use zksync_storage::ethereum::records::NewOpsGasUsage;
+use zksync_types::{
+    aggregated_operations::AggregatedOperation,
+    block::Block,
+    gas_counter::{CommitCost, GasCounter, VerifyCost},
+    BlockNumber, ZkSyncOp,
+};
+
+/// Estimated gas costs of the L1 transaction performing the aggregated operation.
+struct CostModel {
+    /// Fixed cost of the transaction.
+    tx_cost: u64,
+    /// Fixed cost of every block in the transaction.
+    block_cost: u64,
+    /// Cost of the operation in the block.
+    op_cost: fn(&ZkSyncOp) -> U256,
+}
+
+impl CostModel {
+    fn new(op: &AggregatedOperation) -> Option<(Self, &[Block])> {
+        let (model, blocks) = match op {
+            AggregatedOperation::CommitBlocks(op) => (
+                Self {
+                    tx_cost: GasCounter::BASE_COMMIT_BLOCKS_TX_COST as u64,
+                    block_cost: CommitCost::BASE_COST,
+                    op_cost: CommitCost::op_cost,
+                },
+                &op.blocks,
+            ),
+            // Proof verification cost doesn't depend on the operations.
+            AggregatedOperation::PublishProofBlocksOnchain(op) => (
+                Self {
+                    tx_cost: GasCounter::BASE_PROOF_BLOCKS_TX_COST as u64,
+                    block_cost: 0,
+                    op_cost: |_| U256::zero(),
+                },
+                &op.blocks,
+            ),
+            AggregatedOperation::ExecuteBlocks(op) => (
+                Self {
+                    tx_cost: GasCounter::BASE_EXECUTE_BLOCKS_TX_COST as u64,
+                    block_cost: VerifyCost::BASE_COST,
+                    op_cost: VerifyCost::op_cost,
+                },
+                &op.blocks,
+            ),
+            // Proofs are created off-chain.
+            AggregatedOperation::CreateProofBlocks(_) => return None,
+        };
+        Some((model, blocks.as_slice()))
+    }
+}
+
+/// Returns the name of the operation type the gas usage is stored for.
+/// Block padding is not a real operation, so its gas is attributed to the other ones.
+fn op_type(op: &ZkSyncOp) -> Option<&'static str> {
+    let op_type = match op {
+        ZkSyncOp::Deposit(_) => "Deposit",
+        ZkSyncOp::Transfer(_) => "Transfer",
+        ZkSyncOp::TransferToNew(_) => "TransferToNew",
+        ZkSyncOp::Withdraw(_) => "Withdraw",
+        ZkSyncOp::FullExit(_) => "FullExit",
+        ZkSyncOp::ChangePubKeyOffchain(op) => {
+            if op.tx.is_ecdsa() {
+                "ChangePubKeyECDSA"
+            } else if op.tx.is_onchain() {
+                "ChangePubKeyOnchain"
+            } else {
+                "ChangePubKeyCREATE2"
+            }
+        }
+        ZkSyncOp::ForcedExit(_) => "ForcedExit",
+        ZkSyncOp::Noop(_) | ZkSyncOp::Close(_) => return None,
+    };
+    Some(op_type)
+}
+
+/// Attributes the gas used by the L1 transaction performing the aggregated operation
+/// to the operations of its blocks.
+pub(super) fn ops_gas_usage(op: &AggregatedOperation, gas_used: U256) -> Vec<NewOpsGasUsage> {
+    let (model, blocks) = match CostModel::new(op) {
+        Some(model) => model,
+        None => return Vec::new(),
+    };
+
+    let ops: Vec<_> = blocks
+        .iter()
+        .flat_map(|block| {
+            block
+                .block_transactions
+                .iter()
+                .filter_map(|tx| tx.get_executed_op())
+                .filter_map(move |op| Some((block.block_number, op_type(op)?, op)))
+        })
+        .collect();
+    let overhead = u128::from(model.tx_cost) + u128::from(model.block_cost) * blocks.len() as u128;
+    let total_chunks: u128 = ops.iter().map(|(_, _, op)| op.chunks() as u128).sum();
+    let estimated_cost = overhead
+        + ops
+            .iter()
+            .map(|(_, _, op)| (model.op_cost)(op).as_u128())
+            .sum::<u128>();
+    if total_chunks == 0 {
+        return Vec::new();
+    }
+
+    let gas_used = gas_used.as_u128();
+    let mut usage: BTreeMap<(BlockNumber, &str), (i64, u128)> = BTreeMap::new();
+    for (block_number, op_type, op) in ops {
+        let weight = (model.op_cost)(op).as_u128() * total_chunks + overhead * op.chunks() as u128;
+        let op_gas = gas_used * weight / (estimated_cost * total_chunks);
+
+        let entry = usage.entry((block_number, op_type)).or_default();
+        entry.0 += 1;
+        entry.1 += op_gas;
+    }
+
+    let action_type = op.get_action_type().to_string();
+    usage
+        .into_iter()
+        .map(
+            |((block_number, op_type), (ops_count, gas_used))| NewOpsGasUsage {
+                block_number: i64::from(*block_number),
+                action_type: action_type.clone(),
+                op_type: op_type.to_string(),
+                ops_count,
+                gas_used: gas_used as i64,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+    use zksync_basic_types::H256;
+    use zksync_types::{
+        aggregated_operations::{AggregatedActionType, BlocksExecuteOperation},
+        operations::NoopOp,
+        AccountId, Address, Deposit, DepositOp, ExecutedOperations, ExecutedPriorityOp, Fr,
+        FullExit, FullExitOp, PriorityOp, TokenId, ZkSyncPriorityOp,
+    };
+
+    fn executed_op(op: ZkSyncOp, data: ZkSyncPriorityOp) -> ExecutedOperations {
+        ExecutedOperations::PriorityOp(Box::new(ExecutedPriorityOp {
+            priority_op: PriorityOp {
+                serial_id: 0,
+                data,
+                deadline_block: 0,
+                eth_hash: H256::zero(),
+                eth_block: 0,
+            },
+            op,
+            block_index: 0,
+            created_at: SystemTime::UNIX_EPOCH.into(),
+        }))
+    }
+
+    fn block(block_number: u32) -> Block {
+        let deposit = Deposit {
+            from: Address::zero(),
+            token: TokenId(0),
+            amount: 1u32.into(),
+            to: Address::zero(),
+        };
+        let full_exit = FullExit {
+            account_id: AccountId(0),
+            eth_address: Address::zero(),
+            token: TokenId(0),
+        };
+        let deposit_op = ZkSyncOp::Deposit(Box::new(DepositOp {
+            priority_op: deposit.clone(),
+            account_id: AccountId(0),
+        }));
+        let full_exit_op = ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: full_exit.clone(),
+            withdraw_amount: None,
+        }));
+        // Block padding is not taken into account.
+        let noop = executed_op(
+            ZkSyncOp::Noop(NoopOp {}),
+            ZkSyncPriorityOp::Deposit(deposit.clone()),
+        );
+
+        Block::new(
+            BlockNumber(block_number),
+            Fr::default(),
+            AccountId(0),
+            vec![
+                executed_op(deposit_op, ZkSyncPriorityOp::Deposit(deposit)),
+                executed_op(full_exit_op, ZkSyncPriorityOp::FullExit(full_exit)),
+                noop,
+            ],
+            (0, 0),
+            50,
+            1_000_000.into(),
+            1_500_000.into(),
+            H256::default(),
+            0,
+        )
+    }
+
+    fn usage(op_type: &str, gas_used: i64) -> NewOpsGasUsage {
+        NewOpsGasUsage {
+            block_number: 1,
+            action_type: AggregatedActionType::ExecuteBlocks.to_string(),
+            op_type: op_type.to_string(),
+            ops_count: 1,
+            gas_used,
+        }
+    }
+
+    #[test]
+    fn gas_attributed_to_operations() {
+        let op = AggregatedOperation::ExecuteBlocks(BlocksExecuteOperation {
+            blocks: vec![block(1)],
+        });
+        // Transaction and block overhead is 460_000, deposit costs 50 and full exit 30_000.
+        // Both operations have 6 chunks, so the overhead is split evenly.
+        let estimated_cost = 490_050u64;
+
+        assert_eq!(
+            ops_gas_usage(&op, estimated_cost.into()),
+            vec![usage("Deposit", 230_050), usage("FullExit", 260_000)]
+        );
+        assert_eq!(
+            ops_gas_usage(&op, (2 * estimated_cost).into()),
+            vec![usage("Deposit", 460_100), usage("FullExit", 520_000)]
+        );
+    }
+}
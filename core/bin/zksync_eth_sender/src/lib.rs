@@ -32,6 +32,7 @@ use zksync_types::{
 
 mod database;
 mod gas_adjuster;
+mod gas_usage;
 mod transactions;
 mod tx_queue;
 
@@ -88,6 +89,11 @@ enum TxCheckMode {
 /// Note: make sure to save signed tx to db before sending it to ETH, this way we can be sure
 /// that state is always recoverable.
 ///
+/// Operator account may be used to send transactions bypassing `ETHSender` (e.g. for manual
+/// interventions). Stored nonce is synchronized with the on-chain one before sending every
+/// new operation, and if the nonce of the stuck operation turns out to be consumed by such
+/// transaction, the operation is assigned a new nonce.
+///
 /// # Concurrent transaction sending
 ///
 /// `ETHSender` supports sending multiple transaction to the Ethereum at the same time.
@@ -196,6 +202,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             }
 
             if self.options.sender.is_enabled {
+                // Check which operations were paused by the operator...
+                if let Err(e) = self.load_paused_actions().await {
+                    vlog::warn!("Failed to load paused operation types: {}", e);
+                }
                 // ...and proceed them.
                 self.proceed_next_operations().await;
                 // Update the gas adjuster to maintain the up-to-date max gas price limit.
@@ -233,6 +243,34 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         Ok(())
     }
 
+    /// Loads the types of operations paused by the operator (e.g. during the contract upgrade),
+    /// so that the new transactions of these types are not sent until they are resumed.
+    /// Transactions that were already sent are still being tracked and supplemented if stuck.
+    async fn load_paused_actions(&mut self) -> anyhow::Result<()> {
+        let mut connection = self.db.acquire_connection().await?;
+        let paused_actions = self.db.load_paused_actions(&mut connection).await?;
+        drop(connection);
+
+        for action_type in &[
+            AggregatedActionType::CommitBlocks,
+            AggregatedActionType::PublishProofBlocksOnchain,
+            AggregatedActionType::ExecuteBlocks,
+        ] {
+            let paused = paused_actions.contains(action_type);
+            if paused != self.tx_queue.is_paused(*action_type) {
+                vlog::info!(
+                    "Sending of {} operations is {}",
+                    action_type.to_string(),
+                    if paused { "paused" } else { "resumed" }
+                );
+            }
+        }
+        self.tx_queue
+            .set_paused_actions(paused_actions.into_iter().collect());
+
+        Ok(())
+    }
+
     /// This method does two main things:
     ///
     /// 1. Pops all the available transactions from the `TxQueue` and sends them.
@@ -316,9 +354,21 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .get_gas_price(&self.ethereum, None)
             .await?;
 
+        let on_chain_nonce = self.ethereum.current_nonce().await?;
+
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
 
+        // The operator account may have been used to send transactions bypassing `ETHSender`
+        // (e.g. manual interventions). In that case stored nonce is outdated and must be
+        // synchronized with the network, otherwise the new transaction will be rejected.
+        if self.db.sync_nonce(&mut transaction, on_chain_nonce).await? {
+            vlog::warn!(
+                "Stored nonce was behind the operator account nonce, moved it to {}",
+                on_chain_nonce
+            );
+        }
+
         // let (new_op, signed_tx) = self.db.transaction(|| {
         let (new_op, signed_tx) = {
             // First, we should store the operation in the database and obtain the assigned
@@ -446,7 +496,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed(gas_used) => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -480,7 +530,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, gas_used)
                         .await?;
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
@@ -505,14 +555,46 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
         // Reaching this point will mean that the latest transaction got stuck.
         // We should create another tx based on it, and send it.
+        let mut connection = self.db.acquire_connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+
+        // None of the sent transactions was mined, but if the nonce of the operation is already
+        // used on chain, it was consumed by a transaction sent bypassing `ETHSender`.
+        // Replacement transactions with the same nonce will never be accepted, so the operation
+        // has to get a new nonce.
+        let on_chain_nonce = self.ethereum.current_nonce().await?;
+        if on_chain_nonce > op.nonce {
+            // The pending operations created after this one are re-sequenced as well,
+            // since none of them may be mined before it.
+            let new_nonces = self
+                .db
+                .reassign_nonces(&mut transaction, op.id, on_chain_nonce)
+                .await?;
+            for (op_id, new_nonce) in new_nonces {
+                let pending_op = if op_id == op.id {
+                    Some(&mut *op)
+                } else {
+                    self.ongoing_ops
+                        .iter_mut()
+                        .find(|pending_op| pending_op.id == op_id)
+                };
+                if let Some(pending_op) = pending_op {
+                    vlog::warn!(
+                        "Nonce {} of ETH Operation <id: {}> was consumed by an external transaction, new nonce: {}",
+                        pending_op.nonce,
+                        op_id,
+                        new_nonce
+                    );
+                    pending_op.nonce = new_nonce;
+                }
+            }
+        }
+
         let deadline_block = self.get_deadline_block(current_block.as_u64());
         // Raw tx contents are the same for every transaction, so we just
         // create a new one from the old one with updated parameters.
         let new_tx = self.create_supplement_tx(deadline_block, op).await?;
         // New transaction should be persisted in the DB *before* sending it.
-
-        let mut connection = self.db.acquire_connection().await?;
-        let mut transaction = connection.start_transaction().await?;
         self.db
             .update_eth_tx(
                 &mut transaction,
@@ -573,7 +655,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed(status.gas_used)
                 } else {
                     TxCheckOutcome::Pending
                 }
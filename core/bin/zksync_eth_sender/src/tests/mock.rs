@@ -9,7 +9,7 @@ use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
 use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
-use zksync_eth_client::EthereumGateway;
+use zksync_eth_client::{Eip1559Fees, EthereumGateway};
 use zksync_storage::{ethereum::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::ethereum::{ETHOperation, EthOpId, InsertedOperationResponse};
@@ -26,6 +26,7 @@ pub(in crate) struct MockDatabase {
     aggregated_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     unprocessed_operations: RwLock<Vec<(i64, AggregatedOperation)>>,
     eth_parameters: RwLock<ETHParams>,
+    paused_actions: RwLock<Vec<AggregatedActionType>>,
 }
 
 impl MockDatabase {
@@ -41,6 +42,16 @@ impl MockDatabase {
             aggregated_operations: RwLock::new(aggregated_operations),
             unprocessed_operations: RwLock::new(unprocessed_operations),
             eth_parameters: RwLock::new(eth_parameters),
+            paused_actions: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Simulates the admin request to pause or resume sending of the operations of the given type.
+    pub async fn set_paused(&self, action_type: AggregatedActionType, paused: bool) {
+        let mut paused_actions = self.paused_actions.write().await;
+        paused_actions.retain(|paused_action| *paused_action != action_type);
+        if paused {
+            paused_actions.push(action_type);
         }
     }
 
@@ -51,6 +62,15 @@ impl MockDatabase {
         Ok(())
     }
 
+    /// Returns the stored base fee and priority fee.
+    pub async fn eip1559_fees(&self) -> (Option<i64>, Option<i64>) {
+        let eth_parameters = self.eth_parameters.read().await;
+        (
+            eth_parameters.base_fee_per_gas,
+            eth_parameters.max_priority_fee_per_gas,
+        )
+    }
+
     /// Simulates the operation of OperationsSchema, creates a new operation in the database.
     pub async fn send_aggregated_operation(
         &mut self,
@@ -147,6 +167,20 @@ impl DatabaseInterface for MockDatabase {
         Ok(())
     }
 
+    async fn update_eip1559_fees(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        fees: Eip1559Fees,
+    ) -> anyhow::Result<()> {
+        let mut eth_parameters = self.eth_parameters.write().await;
+        eth_parameters.base_fee_per_gas =
+            Some(i64::try_from(fees.base_fee_per_gas).expect("Can't convert U256 to i64"));
+        eth_parameters.max_priority_fee_per_gas =
+            Some(i64::try_from(fees.max_priority_fee_per_gas).expect("Can't convert U256 to i64"));
+
+        Ok(())
+    }
+
     async fn restore_unprocessed_operations(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -200,8 +234,11 @@ impl DatabaseInterface for MockDatabase {
         encoded_tx_data: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
+        let mut eth_parameters = self.eth_parameters.write().await;
         let id = eth_operations.len() as i64;
-        let nonce = eth_operations.len();
+        // Nonce is bound to the operation ID unless it was moved forward by the nonce sync.
+        let nonce = std::cmp::max(eth_operations.len() as i64, eth_parameters.nonce);
+        eth_parameters.nonce = nonce + 1;
 
         // Store with the assigned ID.
         let eth_operation = ETHOperation {
@@ -227,6 +264,61 @@ impl DatabaseInterface for MockDatabase {
         Ok(response)
     }
 
+    async fn sync_nonce(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<bool> {
+        let mut eth_parameters = self.eth_parameters.write().await;
+        let on_chain_nonce = on_chain_nonce.as_u64() as i64;
+        if eth_parameters.nonce < on_chain_nonce {
+            eth_parameters.nonce = on_chain_nonce;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn reassign_nonces(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        eth_op_id: EthOpId,
+        on_chain_nonce: U256,
+    ) -> anyhow::Result<Vec<(EthOpId, U256)>> {
+        let mut eth_operations = self.eth_operations.write().await;
+        let mut eth_parameters = self.eth_parameters.write().await;
+        assert!(
+            eth_operations
+                .iter()
+                .any(|eth_op| eth_op.id == eth_op_id && !eth_op.confirmed),
+            "Attempt to update tx that is not unconfirmed"
+        );
+
+        // The pending operations created before the stuck one keep their nonces.
+        let mut nonce = eth_operations
+            .iter()
+            .filter(|eth_op| eth_op.id < eth_op_id && !eth_op.confirmed)
+            .map(|eth_op| eth_op.nonce + 1)
+            .max()
+            .map_or(on_chain_nonce, |next_nonce| next_nonce.max(on_chain_nonce));
+
+        let mut pending_ops: Vec<_> = eth_operations
+            .iter_mut()
+            .filter(|eth_op| eth_op.id >= eth_op_id && !eth_op.confirmed)
+            .collect();
+        pending_ops.sort_by_key(|eth_op| eth_op.id);
+
+        let mut nonces = Vec::with_capacity(pending_ops.len());
+        for eth_op in pending_ops {
+            eth_op.nonce = nonce;
+            nonces.push((eth_op.id, nonce));
+            nonce = nonce + 1;
+        }
+        eth_parameters.nonce = nonce.as_u64() as i64;
+
+        Ok(nonces)
+    }
+
     /// Adds a tx hash entry associated with some Ethereum operation to the database.
     async fn add_hash_entry(
         &self,
@@ -275,6 +367,7 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
         _op: &ETHOperation,
+        _gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let mut op_idx: Option<i64> = None;
@@ -316,6 +409,13 @@ impl DatabaseInterface for MockDatabase {
         Ok(eth_stats)
     }
 
+    async fn load_paused_actions(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Vec<AggregatedActionType>> {
+        Ok(self.paused_actions.read().await.clone())
+    }
+
     async fn is_previous_operation_confirmed(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -360,6 +460,8 @@ pub(in crate) fn default_eth_parameters() -> ETHParams {
         last_committed_block: 0,
         last_verified_block: 0,
         last_executed_block: 0,
+        base_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
     }
 }
 
@@ -5,6 +5,7 @@ use self::mock::{
 };
 use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
+use zksync_types::{aggregated_operations::AggregatedActionType, U256};
 
 const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
 const WAIT_CONFIRMATIONS: u64 = 3;
@@ -70,6 +71,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: Some(U256::from(500_000)),
     };
     eth_sender
         .ethereum
@@ -83,6 +85,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -96,6 +99,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -109,6 +113,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -131,7 +136,7 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed(Some(U256::from(500_000)))
     );
 
     // Pending operation (no enough confirmations).
@@ -360,6 +365,116 @@ async fn stuck_transaction() {
     eth_sender.db.assert_confirmed(&stuck_tx).await;
 }
 
+/// Checks that `ETHSender` recovers after the operator account nonces were consumed by
+/// transactions sent bypassing it:
+/// - Stored nonce is moved forward before sending a new operation.
+/// - Stuck operation which nonce was consumed gets a new nonce.
+/// - Pending operations sent after the stuck one are re-sequenced to follow it.
+#[tokio::test]
+async fn external_nonce_consumption() {
+    let mut eth_sender = concurrent_eth_sender(2).await;
+
+    // Two transactions were sent from the operator account by someone else.
+    eth_sender.ethereum.get_mut_mock().unwrap().current_nonce = 2.into();
+
+    let operations = vec![
+        test_data::commit_blocks_operation(0),
+        test_data::commit_blocks_operation(1),
+    ];
+    for operation in &operations {
+        eth_sender
+            .db
+            .send_aggregated_operation(operation.clone())
+            .await
+            .unwrap();
+    }
+
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    let deadline_block =
+        eth_sender.get_deadline_block(eth_sender.ethereum.get_mock().unwrap().block_number);
+    for (eth_op_id, operation) in operations.into_iter().enumerate() {
+        let nonce = eth_op_id as i64 + 2;
+        let expected_tx = create_signed_tx(
+            eth_op_id as i64,
+            &eth_sender,
+            operation,
+            deadline_block,
+            nonce,
+        )
+        .await;
+
+        eth_sender.db.assert_stored(&expected_tx).await;
+        eth_sender
+            .ethereum
+            .get_mut_mock()
+            .unwrap()
+            .assert_sent(&expected_tx.used_tx_hashes[0].as_bytes().to_vec())
+            .await;
+    }
+
+    // Nonce of the first sent operation is consumed by another external transaction, and
+    // both operations become stuck.
+    eth_sender.ethereum.get_mut_mock().unwrap().current_nonce = 3.into();
+    eth_sender.ethereum.get_mut_mock().unwrap().block_number += EXPECTED_WAIT_TIME_BLOCKS;
+    eth_sender.proceed_next_operations().await;
+
+    // Check that the replacement transactions are sent with the new nonces, and the second
+    // operation still follows the first one.
+    assert_eq!(eth_sender.ongoing_ops.len(), 2);
+    let ongoing_ops = eth_sender.ongoing_ops.clone();
+    for (ongoing_op, nonce) in ongoing_ops.iter().zip(3u64..) {
+        assert_eq!(ongoing_op.nonce, nonce.into());
+        assert_eq!(ongoing_op.used_tx_hashes.len(), 2);
+        eth_sender
+            .ethereum
+            .get_mut_mock()
+            .unwrap()
+            .assert_sent(&ongoing_op.used_tx_hashes[1].as_bytes().to_vec())
+            .await;
+    }
+}
+
+/// Checks that no transactions are sent for the paused operation types,
+/// and the sending continues once the operations are resumed.
+#[tokio::test]
+async fn paused_operations() {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender
+        .db
+        .set_paused(AggregatedActionType::CommitBlocks, true)
+        .await;
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation.clone())
+        .await
+        .unwrap();
+
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.load_paused_actions().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    // Operation is paused, so nothing should be sent.
+    assert!(eth_sender.ongoing_ops.is_empty());
+
+    eth_sender
+        .db
+        .set_paused(AggregatedActionType::CommitBlocks, false)
+        .await;
+    eth_sender.load_paused_actions().await.unwrap();
+    eth_sender.proceed_next_operations().await;
+
+    let deadline_block =
+        eth_sender.get_deadline_block(eth_sender.ethereum.get_mock().unwrap().block_number);
+    let expected_tx =
+        create_signed_tx(0, &eth_sender, aggregated_operation, deadline_block, 0).await;
+    eth_sender.db.assert_stored(&expected_tx).await;
+    assert_eq!(eth_sender.ongoing_ops.len(), 1);
+}
+
 /// This test verifies that with multiple operations received all-together,
 /// their order is respected and no processing of the next operation is started until
 /// the previous one is committed.
@@ -5,7 +5,7 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
 
@@ -36,8 +36,9 @@ impl From<StorageETHStats> for ETHStats {
 /// The result of the check for the Ethereum transaction commitment.
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
-    /// Transaction was committed and confirmed.
-    Committed,
+    /// Transaction was committed and confirmed. Contains the gas used by the transaction,
+    /// if reported by the node.
+    Committed(Option<U256>),
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
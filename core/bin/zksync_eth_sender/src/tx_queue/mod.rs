@@ -1,3 +1,5 @@
+// Built-in deps
+use std::collections::HashSet;
 // Workspace imports
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
@@ -119,6 +121,8 @@ impl TxQueueBuilder {
             execute_operations: OperationQueue::new(BlockNumber(
                 self.execute_operations_count as u32,
             )),
+
+            paused_actions: HashSet::new(),
         }
     }
 }
@@ -144,9 +148,23 @@ pub struct TxQueue {
     commit_operations: OperationQueue,
     verify_operations: OperationQueue,
     execute_operations: OperationQueue,
+
+    /// Types of operations that must not be yielded until they are resumed.
+    paused_actions: HashSet<AggregatedActionType>,
 }
 
 impl TxQueue {
+    /// Replaces the set of paused operation types.
+    /// Operations of the paused types stay in the queue, but are not yielded.
+    pub fn set_paused_actions(&mut self, paused_actions: HashSet<AggregatedActionType>) {
+        self.paused_actions = paused_actions;
+    }
+
+    /// Checks whether the operations of the given type are paused.
+    pub fn is_paused(&self, action_type: AggregatedActionType) -> bool {
+        self.paused_actions.contains(&action_type)
+    }
+
     /// Adds the `commit` operation to the queue.
     pub fn add_commit_operation(&mut self, commit_operation: TxData) -> anyhow::Result<()> {
         self.commit_operations.push_back(commit_operation)?;
@@ -246,22 +264,29 @@ impl TxQueue {
     /// This method does not use/affect `sent_pending_tx` counter.
     fn get_next_operation(&mut self) -> Option<TxData> {
         // 1. Highest priority: execute operations.
-        if let Some(next_execute_block) = self.execute_operations.get_next_last_block_number() {
-            let current_verify_block = self.verify_operations.get_last_block_number();
-            if *next_execute_block <= *current_verify_block {
-                return Some(self.execute_operations.pop_front().unwrap());
+        if !self.is_paused(AggregatedActionType::ExecuteBlocks) {
+            if let Some(next_execute_block) = self.execute_operations.get_next_last_block_number() {
+                let current_verify_block = self.verify_operations.get_last_block_number();
+                if *next_execute_block <= *current_verify_block {
+                    return Some(self.execute_operations.pop_front().unwrap());
+                }
             }
         }
 
         // 2. After execute operations we should process verify operation.
-        if let Some(next_verify_block) = self.verify_operations.get_next_last_block_number() {
-            let current_commit_block = self.commit_operations.get_last_block_number();
-            if *next_verify_block <= *current_commit_block {
-                return Some(self.verify_operations.pop_front().unwrap());
+        if !self.is_paused(AggregatedActionType::PublishProofBlocksOnchain) {
+            if let Some(next_verify_block) = self.verify_operations.get_next_last_block_number() {
+                let current_commit_block = self.commit_operations.get_last_block_number();
+                if *next_verify_block <= *current_commit_block {
+                    return Some(self.verify_operations.pop_front().unwrap());
+                }
             }
         }
 
         // 3. Finally, check the commit queue.
+        if self.is_paused(AggregatedActionType::CommitBlocks) {
+            return None;
+        }
         self.commit_operations.pop_front()
     }
 
@@ -424,4 +449,57 @@ mod tests {
             ))
             .unwrap();
     }
+
+    /// Checks that operations of the paused types are not yielded until they are resumed,
+    /// while the operations of other types are processed as usual.
+    #[test]
+    fn paused_operations() {
+        const MAX_IN_FLY: usize = 3;
+        const COMMIT_MARK: u8 = 0;
+        const VERIFY_MARK: u8 = 1;
+
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY).build();
+        queue
+            .add_commit_operation(get_tx_data(
+                AggregatedActionType::CommitBlocks,
+                BlockNumber(1),
+                vec![COMMIT_MARK, 0],
+            ))
+            .unwrap();
+        queue
+            .add_commit_operation(get_tx_data(
+                AggregatedActionType::CommitBlocks,
+                BlockNumber(2),
+                vec![COMMIT_MARK, 1],
+            ))
+            .unwrap();
+
+        // Commit the first block, then pause commits.
+        let op = queue.pop_front().unwrap();
+        assert_eq!(op.raw, vec![COMMIT_MARK, 0]);
+        queue.set_paused_actions(
+            vec![AggregatedActionType::CommitBlocks]
+                .into_iter()
+                .collect(),
+        );
+        assert!(queue.is_paused(AggregatedActionType::CommitBlocks));
+        assert!(queue.pop_front().is_none());
+
+        // Verify operation for the already committed block is not affected by the pause.
+        queue
+            .add_verify_operation(get_tx_data(
+                AggregatedActionType::PublishProofBlocksOnchain,
+                BlockNumber(1),
+                vec![VERIFY_MARK, 0],
+            ))
+            .unwrap();
+        let op = queue.pop_front().unwrap();
+        assert_eq!(op.raw, vec![VERIFY_MARK, 0]);
+        assert!(queue.pop_front().is_none());
+
+        // After resuming the commits, the second block is committed.
+        queue.set_paused_actions(HashSet::new());
+        let op = queue.pop_front().unwrap();
+        assert_eq!(op.raw, vec![COMMIT_MARK, 1]);
+    }
 }
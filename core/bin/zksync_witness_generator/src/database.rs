@@ -5,6 +5,7 @@
 
 // Built-in
 use std::clone::Clone;
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::{ConnectionPool, StorageProcessor};
@@ -107,10 +108,14 @@ impl DatabaseInterface for Database {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()> {
-        connection.prover_schema().mark_stale_jobs_as_idle().await?;
+        lease_timeout: Duration,
+    ) -> anyhow::Result<u64> {
+        let reassigned_jobs = connection
+            .prover_schema()
+            .mark_stale_jobs_as_idle(lease_timeout)
+            .await?;
 
-        Ok(())
+        Ok(reassigned_jobs)
     }
 
     async fn load_last_verified_block(
@@ -152,10 +157,11 @@ impl DatabaseInterface for Database {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
     ) -> anyhow::Result<Option<ProverJob>> {
         let proof = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .get_idle_prover_job_from_job_queue(prover_name)
             .await?;
 
         Ok(proof)
@@ -166,13 +172,13 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
-        connection
+    ) -> anyhow::Result<bool> {
+        let is_leased = connection
             .prover_schema()
             .record_prover_is_working(job_id, prover_name)
             .await?;
 
-        Ok(())
+        Ok(is_leased)
     }
 
     async fn store_proof(
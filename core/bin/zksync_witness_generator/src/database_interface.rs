@@ -6,6 +6,7 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+use std::time::Duration;
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::StorageProcessor;
@@ -62,7 +63,8 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
-    ) -> anyhow::Result<()>;
+        lease_timeout: Duration,
+    ) -> anyhow::Result<u64>;
 
     async fn load_last_verified_block(
         &self,
@@ -83,6 +85,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
     ) -> anyhow::Result<Option<ProverJob>>;
 
     async fn record_prover_is_working(
@@ -90,7 +93,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()>;
+    ) -> anyhow::Result<bool>;
 
     async fn store_proof(
         &self,
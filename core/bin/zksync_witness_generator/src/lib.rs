@@ -10,6 +10,7 @@ use actix_web_httpauth::extractors::{
     AuthenticationError,
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
+use chrono::{DateTime, Utc};
 use futures::channel::mpsc;
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
@@ -30,8 +31,9 @@ use zksync_types::aggregated_operations::{
 };
 use zksync_types::prover::{
     ProverJobType, AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
+    URGENT_SINGLE_PROOF_JOB_PRIORITY,
 };
-use zksync_types::BlockNumber;
+use zksync_types::{block::Block, BlockNumber, ExecutedOperations};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 #[cfg(test)]
@@ -127,7 +129,7 @@ async fn get_job<DB: DatabaseInterface>(
     let mut storage = data.access_storage().await?;
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .load_idle_prover_job_from_job_queue(&mut storage, &r.prover_name)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
@@ -165,13 +167,22 @@ async fn working_on<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
-    data.database
+    let is_leased = data
+        .database
         .record_prover_is_working(&mut storage, r.job_id, &r.prover_name)
         .await
         .map_err(|e| {
             vlog::warn!("failed to record prover work in progress request: {}", e);
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
+    if !is_leased {
+        vlog::info!(
+            "Prover '{}' lost the lease of the job {}",
+            r.prover_name,
+            r.job_id
+        );
+        return Err(actix_web::error::ErrorConflict("job lease expired"));
+    }
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -282,20 +293,81 @@ async fn required_replicas<DB: DatabaseInterface>(
     Ok(HttpResponse::Ok().json(response))
 }
 
-async fn update_prover_job_queue_loop<DB: DatabaseInterface>(database: DB) {
+/// Average time between the Ethereum blocks, used to estimate the priority operations deadlines.
+const ETH_BLOCK_TIME: Duration = Duration::from_secs(15);
+
+/// Returns the priority of the single proof job for the block. Blocks containing priority
+/// operations that expire in less than `deadline_margin` are proven first, since the network
+/// enters the exodus mode if a priority operation is not verified in time.
+fn single_proof_job_priority(block: &Block, deadline_margin: Duration, now: DateTime<Utc>) -> i32 {
+    let is_urgent = block.block_transactions.iter().any(|op| match op {
+        ExecutedOperations::PriorityOp(op) => {
+            let blocks_left = op
+                .priority_op
+                .deadline_block
+                .saturating_sub(op.priority_op.eth_block);
+            let expires_in = ETH_BLOCK_TIME.as_secs() as i64 * blocks_left as i64
+                - (now - op.created_at).num_seconds();
+            expires_in < deadline_margin.as_secs() as i64
+        }
+        ExecutedOperations::Tx(_) => false,
+    });
+
+    if is_urgent {
+        URGENT_SINGLE_PROOF_JOB_PRIORITY
+    } else {
+        SINGLE_PROOF_JOB_PRIORITY
+    }
+}
+
+async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
+    database: DB,
+    priority_op_deadline_margin: Duration,
+    lease_timeout: Duration,
+) {
     let mut interval = tokio::time::interval(Duration::from_secs(5));
     loop {
         interval.tick().await;
 
-        update_prover_job_queue(database.clone())
+        update_prover_job_queue(database.clone(), priority_op_deadline_margin)
             .await
             .unwrap_or_else(|e| {
                 vlog::warn!("Failed to update prover job queue: {}", e);
             });
+        // Done separately, so the jobs of the crashed provers are reassigned even
+        // if the new jobs can't be added to the queue.
+        reassign_stale_jobs(database.clone(), lease_timeout)
+            .await
+            .unwrap_or_else(|e| {
+                vlog::warn!("Failed to reassign stale prover jobs: {}", e);
+            });
     }
 }
 
-async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow::Result<()> {
+/// Returns the jobs of the provers that stopped sending heartbeats back to the queue.
+async fn reassign_stale_jobs<DB: DatabaseInterface>(
+    database: DB,
+    lease_timeout: Duration,
+) -> anyhow::Result<()> {
+    let mut connection = database.acquire_connection().await?;
+    let reassigned_jobs = database
+        .mark_stale_jobs_as_idle(&mut connection, lease_timeout)
+        .await?;
+    if reassigned_jobs > 0 {
+        vlog::warn!(
+            "{} prover jobs were not renewed for {}s and returned to the queue",
+            reassigned_jobs,
+            lease_timeout.as_secs()
+        );
+    }
+
+    Ok(())
+}
+
+async fn update_prover_job_queue<DB: DatabaseInterface>(
+    database: DB,
+    priority_op_deadline_margin: Duration,
+) -> anyhow::Result<()> {
     let mut connection = database.acquire_connection().await?;
     {
         let next_single_block_to_add = database
@@ -312,13 +384,27 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
             let job_data =
                 serde_json::to_value(JobRequestData::BlockProof(prover_data, block_size))
                     .expect("Failed to serialize single proof job data");
+            let block = database
+                .load_block(&mut connection, next_single_block_to_add)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::format_err!("Block {} is not found", next_single_block_to_add)
+                })?;
+            let job_priority =
+                single_proof_job_priority(&block, priority_op_deadline_margin, Utc::now());
+            if job_priority == URGENT_SINGLE_PROOF_JOB_PRIORITY {
+                vlog::info!(
+                    "Block {} contains expiring priority operations, proving it first",
+                    next_single_block_to_add
+                );
+            }
             database
                 .add_prover_job_to_job_queue(
                     &mut connection,
                     next_single_block_to_add,
                     next_single_block_to_add,
                     job_data,
-                    SINGLE_PROOF_JOB_PRIORITY,
+                    job_priority,
                     ProverJobType::SingleProof,
                 )
                 .await?;
@@ -373,7 +459,6 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                 .await?;
         }
     }
-    database.mark_stale_jobs_as_idle(&mut connection).await?;
 
     Ok(())
 }
@@ -394,7 +479,11 @@ pub fn run_prover_server<DB: DatabaseInterface>(
             let mut actix_runtime = actix_rt::System::new("prover-server");
 
             actix_runtime.block_on(async move {
-                tokio::spawn(update_prover_job_queue_loop(database.clone()));
+                tokio::spawn(update_prover_job_queue_loop(
+                    database.clone(),
+                    witness_generator_opts.priority_op_deadline_margin(),
+                    core_opts.gone_timeout(),
+                ));
 
                 let last_verified_block = {
                     let mut storage = database
@@ -0,0 +1,48 @@
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::Utc;
+// Workspace deps
+use zksync_types::{
+    prover::{SINGLE_PROOF_JOB_PRIORITY, URGENT_SINGLE_PROOF_JOB_PRIORITY},
+    ExecutedOperations,
+};
+// Local deps
+use super::prover_server::get_test_block;
+use crate::single_proof_job_priority;
+
+const DEADLINE_MARGIN: Duration = Duration::from_secs(24 * 3600);
+
+/// Checks that the block with an expiring priority operation is proven first.
+#[tokio::test]
+async fn expiring_priority_op_is_urgent() {
+    // Deadline of the test block priority operation has already passed.
+    let block = get_test_block().await;
+
+    assert_eq!(
+        single_proof_job_priority(&block, DEADLINE_MARGIN, Utc::now()),
+        URGENT_SINGLE_PROOF_JOB_PRIORITY
+    );
+}
+
+/// Checks that the block with priority operations far from the deadline has the regular priority.
+#[tokio::test]
+async fn fresh_priority_op_is_not_urgent() {
+    let mut block = get_test_block().await;
+    for op in block.block_transactions.iter_mut() {
+        if let ExecutedOperations::PriorityOp(op) = op {
+            op.priority_op.deadline_block = op.priority_op.eth_block + 35000;
+        }
+    }
+
+    assert_eq!(
+        single_proof_job_priority(&block, DEADLINE_MARGIN, Utc::now()),
+        SINGLE_PROOF_JOB_PRIORITY
+    );
+    // The same block becomes urgent closer to the deadline.
+    let later = Utc::now() + chrono::Duration::days(6);
+    assert_eq!(
+        single_proof_job_priority(&block, DEADLINE_MARGIN, later),
+        URGENT_SINGLE_PROOF_JOB_PRIORITY
+    );
+}
@@ -170,19 +170,28 @@ impl DatabaseInterface for MockDatabase {
         Ok(single_proof)
     }
 
-    async fn mark_stale_jobs_as_idle(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    async fn mark_stale_jobs_as_idle(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        lease_timeout: Duration,
+    ) -> anyhow::Result<u64> {
         let now = Utc::now();
+        let lease_timeout = chrono::Duration::from_std(lease_timeout)?;
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
+        let mut reassigned_jobs = 0;
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
+            if job.job_status == ProverJobStatus::InProgress.to_number()
+                && now - job.updated_at >= lease_timeout
+            {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = now;
                 job.updated_by = "server_clean_idle".to_string();
+                reassigned_jobs += 1;
             }
         }
 
-        Ok(())
+        Ok(reassigned_jobs)
     }
 
     async fn load_last_verified_block(
@@ -224,6 +233,7 @@ impl DatabaseInterface for MockDatabase {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         _: &mut StorageProcessor<'_>,
+        prover_name: &str,
     ) -> anyhow::Result<Option<ProverJob>> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
         let idle_prover_job = prover_job_queue
@@ -234,7 +244,7 @@ impl DatabaseInterface for MockDatabase {
         let prover_job = if let Some(job) = idle_prover_job {
             job.job_status = ProverJobStatus::InProgress.to_number();
             job.updated_at = Utc::now();
-            job.updated_by = "server_give_job".to_string();
+            job.updated_by = prover_name.to_string();
 
             Some(ProverJob::new(
                 job.id,
@@ -254,16 +264,20 @@ impl DatabaseInterface for MockDatabase {
         _: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<bool> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
-        let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
+        let prover_job = prover_job_queue.iter_mut().find(|job| {
+            job.id == job_id
+                && job.updated_by == prover_name
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+        });
 
         if let Some(job) = prover_job {
             job.updated_at = Utc::now();
-            job.updated_by = prover_name.to_string();
+            Ok(true)
+        } else {
+            Ok(false)
         }
-
-        Ok(())
     }
 
     async fn store_proof(
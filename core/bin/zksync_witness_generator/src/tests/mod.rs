@@ -1,2 +1,3 @@
+mod job_priority;
 mod mock;
 mod prover_server;
@@ -262,6 +262,22 @@ impl Client {
         self.get(&format!("accounts/{}", account)).send().await
     }
 
+    /// Gets the account state as of the given block.
+    pub async fn account_state_at_block(
+        &self,
+        account: impl Into<AccountQuery>,
+        block_number: BlockNumber,
+    ) -> Result<Option<AccountState>, ClientError> {
+        let account = account.into();
+
+        self.get(&format!(
+            "accounts/{}/blocks/{}/state",
+            account, *block_number
+        ))
+        .send()
+        .await
+    }
+
     pub async fn account_tx_receipts(
         &self,
         account: impl Into<AccountQuery>,
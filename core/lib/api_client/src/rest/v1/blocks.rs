@@ -43,6 +43,14 @@ pub struct TransactionInfo {
     pub created_at: DateTime<Utc>,
 }
 
+/// Inclusive range of blocks requested by the block data export.
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockExportQuery {
+    pub from: BlockNumber,
+    pub to: BlockNumber,
+}
+
 /// Blocks API part.
 impl Client {
     /// Returns information about block with the specified number or null if block doesn't exist.
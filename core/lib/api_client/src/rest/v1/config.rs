@@ -3,10 +3,12 @@
 // Built-in uses
 
 // External uses
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_types::Address;
+use zksync_utils::BigUintSerdeAsRadix10Str;
 
 // Local uses
 use super::client::{self, Client};
@@ -19,6 +21,21 @@ pub struct Contracts {
     pub contract: Address,
 }
 
+/// Parameters of the permissionless token listing.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenListing {
+    /// Token governance contract, tokens are listed by calling its `addToken` method.
+    pub contract: Address,
+    /// Token to pay the listing fee in.
+    pub fee_token: Address,
+    /// Listing fee in the smallest units of the fee token.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    /// Maximum total amount of the tokens after which the listing is no longer possible.
+    pub cap: u16,
+}
+
 /// Configuration API part.
 impl Client {
     pub async fn contracts(&self) -> client::Result<Contracts> {
@@ -32,4 +49,9 @@ impl Client {
     pub async fn network(&self) -> client::Result<String> {
         self.get("config/network").send().await
     }
+
+    /// Returns `None` if the permissionless token listing is not enabled in the network.
+    pub async fn token_listing(&self) -> client::Result<Option<TokenListing>> {
+        self.get("config/token_listing").send().await
+    }
 }
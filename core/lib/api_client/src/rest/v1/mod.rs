@@ -8,16 +8,20 @@ use zksync_types::BlockNumber;
 
 // Public uses
 pub use self::{
-    blocks::{BlockInfo, TransactionInfo},
+    blocks::{BlockExportQuery, BlockInfo, TransactionInfo},
     client::{Client, ClientError},
-    config::Contracts,
+    config::{Contracts, TokenListing},
     error::ErrorBody,
     operations::{PriorityOpData, PriorityOpQuery, PriorityOpQueryError, PriorityOpReceipt},
     search::BlockSearchQuery,
-    tokens::{TokenPriceKind, TokenPriceQuery},
+    tokens::{
+        PriceAtQuery, PriceHistoryQuery, PriceObservation, TokenPriceKind, TokenPriceQuery,
+        TokenPriceQuote, TokenPricesRequest,
+    },
     transactions::{
-        FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-        Receipt, TxData,
+        FastProcessingQuery, ForcedExitRequestInfo, ForcedExitRequestStatus,
+        IncomingForcedExitRequest, IncomingPermitDeposit, IncomingTx, IncomingTxBatch,
+        IncomingTxBatchForFee, IncomingTxForFee, PermitDepositStatus, Receipt, TxData,
     },
 };
 
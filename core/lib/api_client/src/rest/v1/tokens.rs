@@ -4,10 +4,14 @@
 
 // External uses
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{Token, TokenLike};
+use zksync_types::{
+    tokens::{FeeTokenStatus, PriceConfidence},
+    Token, TokenLike,
+};
 
 // Local uses
 use super::client::{self, Client};
@@ -28,6 +32,53 @@ pub struct TokenPriceQuery {
     pub kind: TokenPriceKind,
 }
 
+/// Request of the prices of several tokens at once.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPricesRequest {
+    pub tokens: Vec<TokenLike>,
+    #[serde(rename = "in")]
+    pub kind: TokenPriceKind,
+}
+
+/// Time range of the token price history request.
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceHistoryQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Timestamp of the token price request.
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAtQuery {
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Token price observed by the fee ticker.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceObservation {
+    /// Name of the API the price was received from.
+    pub source: String,
+    pub usd_price: BigDecimal,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Current token price together with its origin, so the users can see how fresh it is.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceQuote {
+    pub price: BigDecimal,
+    /// Name of the API the price was received from.
+    pub source: String,
+    pub last_updated: DateTime<Utc>,
+    /// Age of the price at the moment of the response, in seconds.
+    pub age_secs: i64,
+    pub confidence: PriceConfidence,
+}
+
 /// Tokens API part.
 impl Client {
     pub async fn tokens(&self) -> client::Result<Vec<Token>> {
@@ -48,4 +99,60 @@ impl Client {
             .send()
             .await
     }
+
+    /// Gets the token price together with its source, time and confidence.
+    pub async fn token_price_quote(
+        &self,
+        token: &TokenLike,
+        kind: TokenPriceKind,
+    ) -> client::Result<Option<TokenPriceQuote>> {
+        self.get(&format!("tokens/{}/price_quote", token))
+            .query(&TokenPriceQuery { kind })
+            .send()
+            .await
+    }
+
+    /// Gets the prices of several tokens at once. The price is `None` if the token
+    /// is not found or its price is not available.
+    pub async fn token_prices(
+        &self,
+        tokens: Vec<TokenLike>,
+        kind: TokenPriceKind,
+    ) -> client::Result<Vec<Option<BigDecimal>>> {
+        self.post("tokens/prices")
+            .body(&TokenPricesRequest { tokens, kind })
+            .send()
+            .await
+    }
+
+    /// Gets whether the token can be used to pay fees, and why.
+    pub async fn token_fee_status(&self, token: &TokenLike) -> client::Result<FeeTokenStatus> {
+        self.get(&format!("tokens/{}/fee_status", token))
+            .send()
+            .await
+    }
+
+    pub async fn token_price_history(
+        &self,
+        token: &TokenLike,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> client::Result<Vec<PriceObservation>> {
+        self.get(&format!("tokens/{}/price_history", token))
+            .query(&PriceHistoryQuery { from, to })
+            .send()
+            .await
+    }
+
+    /// Gets the last token price observed not later than the timestamp.
+    pub async fn token_price_at(
+        &self,
+        token: &TokenLike,
+        timestamp: DateTime<Utc>,
+    ) -> client::Result<Option<PriceObservation>> {
+        self.get(&format!("tokens/{}/price_at", token))
+            .query(&PriceAtQuery { timestamp })
+            .send()
+            .await
+    }
 }
@@ -3,13 +3,18 @@
 // Built-in uses
 
 // External uses
+use chrono::{DateTime, Utc};
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_types::{
-    tx::{EthBatchSignatures, EthSignData, TxEthSignature, TxHash},
-    Address, BatchFee, BlockNumber, Fee, SignedZkSyncTx, TokenLike, TxFeeTypes, ZkSyncTx,
+    network::Network,
+    tx::{EthBatchSignatures, EthSignData, PackedEthSignature, TxEthSignature, TxHash},
+    Address, BatchFee, BlockNumber, Fee, FeeQuote, SignedZkSyncTx, TokenId, TokenLike, TxFeeTypes,
+    ZkSyncTx, H256,
 };
+use zksync_utils::BigUintSerdeAsRadix10Str;
 
 // Local uses
 use super::{client::Client, client::ClientError, Pagination};
@@ -44,6 +49,13 @@ pub struct TxData {
 pub struct IncomingTx {
     pub tx: ZkSyncTx,
     pub signature: Option<TxEthSignature>,
+    /// Network the transaction is intended for, the transaction is rejected
+    /// if the server runs on another network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
+    /// Signed quote of the fee paid by the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_quote: Option<FeeQuote>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -52,6 +64,9 @@ pub struct IncomingTxForFee {
     pub tx_type: TxFeeTypes,
     pub address: Address,
     pub token_like: TokenLike,
+    /// Sender of the transaction, its fee discounts are applied if provided.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -67,6 +82,88 @@ pub struct IncomingTxBatchForFee {
 pub struct IncomingTxBatch {
     pub txs: Vec<ZkSyncTx>,
     pub signature: EthBatchSignatures,
+    /// Network the batch is intended for, the batch is rejected
+    /// if the server runs on another network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
+}
+
+/// Deposit authorized by the EIP-2612 permit signature, which should be sent to L1
+/// by the relayer. The relayer pays for the L1 gas and receives `fee` in the deposited token.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingPermitDeposit {
+    pub token_like: TokenLike,
+    /// Owner of the tokens and the recipient of the deposit.
+    pub owner: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    /// Deadline of the permit (unix timestamp in seconds).
+    pub deadline: u64,
+    /// Permit signature of the `amount + fee` allowance for the zkSync contract.
+    pub signature: PackedEthSignature,
+}
+
+/// State of the deposit authorized by the permit signature.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum PermitDepositStatus {
+    /// The deposit is awaiting to be sent to L1.
+    Pending,
+    /// The deposit has been sent to L1, its further state can be tracked as the state
+    /// of the priority operation.
+    Sent { eth_tx_hash: H256 },
+    /// The deposit could not be sent to L1.
+    Failed { reason: Option<String> },
+}
+
+/// Request to withdraw the funds of the account which signing key is not set.
+/// The request is paid by the L2 transfer of ETH from `requester` to the funding account
+/// of the server, which then initiates the `ForcedExit` transactions for the requested tokens.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomingForcedExitRequest {
+    pub target: Address,
+    pub tokens: Vec<TokenId>,
+    /// Account which will pay for the request.
+    pub requester: Address,
+}
+
+/// Accepted forced exit request along with its current state.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRequestInfo {
+    pub id: i64,
+    pub target: Address,
+    pub tokens: Vec<TokenId>,
+    pub requester: Address,
+    /// Amount of ETH to be transferred to `funding_account` to pay for the request.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub price_in_wei: BigUint,
+    pub funding_account: Address,
+    /// The request is expired if it's not paid until this moment.
+    pub valid_until: DateTime<Utc>,
+    pub status: ForcedExitRequestStatus,
+}
+
+/// State of the forced exit request.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum ForcedExitRequestStatus {
+    /// The request is awaiting the payment.
+    Pending,
+    /// The request has been paid, its `ForcedExit` transactions are about to be sent.
+    Paid { payment_tx_hash: TxHash },
+    /// The `ForcedExit` transactions of the request have been sent, their further state
+    /// can be tracked as the state of the usual transactions.
+    Fulfilled {
+        payment_tx_hash: TxHash,
+        fulfilled_by: Vec<TxHash>,
+    },
+    /// The request was not paid in time.
+    Expired,
 }
 
 /// Transaction (or priority operation) receipt.
@@ -115,7 +212,12 @@ impl Client {
     ) -> Result<TxHash, ClientError> {
         self.post("transactions/submit")
             .query(&FastProcessingQuery { fast_processing })
-            .body(&IncomingTx { tx, signature })
+            .body(&IncomingTx {
+                tx,
+                signature,
+                network: None,
+                fee_quote: None,
+            })
             .send()
             .await
     }
@@ -126,12 +228,14 @@ impl Client {
         tx_type: TxFeeTypes,
         address: Address,
         token_like: TokenLike,
+        sender: Option<Address>,
     ) -> Result<Fee, ClientError> {
         self.post("transactions/fee")
             .body(&IncomingTxForFee {
                 tx_type,
                 address,
                 token_like,
+                sender,
             })
             .send()
             .await
@@ -161,7 +265,54 @@ impl Client {
         signature: EthBatchSignatures,
     ) -> Result<Vec<TxHash>, ClientError> {
         self.post("transactions/submit/batch")
-            .body(&IncomingTxBatch { txs, signature })
+            .body(&IncomingTxBatch {
+                txs,
+                signature,
+                network: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Sends a new deposit authorized by the permit signature to the relayer.
+    /// Returns the ID of the deposit request.
+    pub async fn submit_permit_deposit(
+        &self,
+        deposit: IncomingPermitDeposit,
+    ) -> Result<i64, ClientError> {
+        self.post("transactions/submit/permit_deposit")
+            .body(&deposit)
+            .send()
+            .await
+    }
+
+    /// Gets the state of the deposit authorized by the permit signature.
+    pub async fn permit_deposit_status(
+        &self,
+        request_id: i64,
+    ) -> Result<Option<PermitDepositStatus>, ClientError> {
+        self.get(&format!("transactions/permit_deposits/{}", request_id))
+            .send()
+            .await
+    }
+
+    /// Sends a new forced exit request. Returns the accepted request along with its price.
+    pub async fn submit_forced_exit_request(
+        &self,
+        request: IncomingForcedExitRequest,
+    ) -> Result<ForcedExitRequestInfo, ClientError> {
+        self.post("transactions/submit/forced_exit_request")
+            .body(&request)
+            .send()
+            .await
+    }
+
+    /// Gets the forced exit request along with its current state.
+    pub async fn forced_exit_request(
+        &self,
+        request_id: i64,
+    ) -> Result<Option<ForcedExitRequestInfo>, ClientError> {
+        self.get(&format!("transactions/forced_exit_requests/{}", request_id))
             .send()
             .await
     }
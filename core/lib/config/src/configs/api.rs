@@ -1,12 +1,14 @@
 /// External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 /// Built-in uses
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
+// Workspace uses
+use zksync_types::{Address, H256};
 // Local uses
 use crate::envy_load;
 
 /// API configuration.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ApiConfig {
     /// Common configuration options for the API.
     pub common: Common,
@@ -22,6 +24,14 @@ pub struct ApiConfig {
     pub prover: ProverApi,
     /// Configuration options for the Prometheus exporter.
     pub prometheus: Prometheus,
+    /// Configuration options for the relayer of the permit-based deposits.
+    pub permit_relayer: PermitRelayer,
+    /// Configuration options for the service of the paid forced exit requests.
+    pub forced_exit_requests: ForcedExitRequests,
+    /// Configuration options for forwarding the block events to the message broker.
+    pub event_forwarder: EventForwarder,
+    /// Configuration options for the internal gRPC API of the fee ticker.
+    pub fee_ticker_grpc: FeeTickerGrpc,
 }
 
 impl ApiConfig {
@@ -34,12 +44,16 @@ impl ApiConfig {
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
             prometheus: envy_load!("prometheus", "API_PROMETHEUS_"),
+            permit_relayer: envy_load!("permit_relayer", "API_PERMIT_RELAYER_"),
+            forced_exit_requests: envy_load!("forced_exit_requests", "API_FORCED_EXIT_REQUESTS_"),
+            event_forwarder: envy_load!("event_forwarder", "API_EVENT_FORWARDER_"),
+            fee_ticker_grpc: envy_load!("fee_ticker_grpc", "API_FEE_TICKER_GRPC_"),
         }
     }
 }
 
 // Common configuration options for the API
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Common {
     // Size of LRU caches for requests
     pub caches_size: usize,
@@ -50,9 +64,20 @@ pub struct Common {
 
     pub max_number_of_transactions_per_batch: u64,
     pub max_number_of_authors_per_batch: u64,
+
+    // Whether the account states requested via JSON RPC should be cached.
+    pub account_state_cache_enabled: bool,
+    // Interval (in milliseconds) of checking the stored state for changes to invalidate the account states cache.
+    pub account_state_cache_poll_interval: u64,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl Common {
+    pub fn account_state_cache_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.account_state_cache_poll_interval)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AdminApi {
     /// Port to which the API server is listening.
     pub port: u16,
@@ -68,7 +93,7 @@ impl AdminApi {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ProverApi {
     /// Port to which the API server is listening.
     pub port: u16,
@@ -84,7 +109,7 @@ impl ProverApi {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct PrivateApi {
     /// Port to which the API server is listening.
     pub port: u16,
@@ -98,7 +123,7 @@ impl PrivateApi {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RestApi {
     /// Port to which the API server is listening.
     pub port: u16,
@@ -112,7 +137,7 @@ impl RestApi {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct JsonRpc {
     /// Port to which the HTTP RPC server is listening.
     pub http_port: u16,
@@ -134,16 +159,98 @@ impl JsonRpc {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Prometheus {
     /// Port to which the Prometheus exporter server is listening.
     pub port: u16,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PermitRelayer {
+    /// Whether the permit-based deposits are accepted and relayed to L1.
+    pub enabled: bool,
+    /// Address of the account sending the deposits to L1 and receiving the deposit fees.
+    /// Must not be the operator account, since the Ethereum sender relies on its nonces.
+    pub relayer_eth_addr: Address,
+    /// Private key of the relayer account.
+    pub relayer_private_key: H256,
+    /// Interval (in milliseconds) of checking the storage for the new deposits to relay.
+    pub poll_interval: u64,
+}
+
+impl PermitRelayer {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ForcedExitRequests {
+    /// Whether the paid forced exit requests are accepted.
+    pub enabled: bool,
+    /// Maximum number of tokens withdrawn by a single request.
+    pub max_tokens_per_request: u8,
+    /// Multiplier applied to the fees of the `ForcedExit` transactions to get the request price.
+    pub price_scaling_factor: f64,
+    /// Time (in seconds) during which the request can be paid.
+    pub request_lifetime_secs: u64,
+    /// Interval (in milliseconds) of checking the funding account for the new payments.
+    pub poll_interval: u64,
+    /// Address of the zkSync account receiving the payments and initiating the forced exits.
+    pub funding_account_addr: Address,
+    /// zkSync private key of the funding account, used to sign the `ForcedExit` transactions.
+    pub funding_account_private_key: H256,
+}
+
+impl ForcedExitRequests {
+    pub fn request_lifetime(&self) -> Duration {
+        Duration::from_secs(self.request_lifetime_secs)
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EventForwarder {
+    /// Whether the block events are published to the message broker.
+    pub enabled: bool,
+    /// URL of the NATS server, e.g. `nats://127.0.0.1:4222`.
+    pub nats_url: String,
+    /// Prefix of the subjects the events are published to.
+    pub subject_prefix: String,
+    /// Interval (in milliseconds) of checking the storage for the new verified blocks.
+    pub poll_interval: u64,
+    /// Block to start forwarding the events from, regardless of the stored progress.
+    /// Used to replay the events once they were lost by the consumers.
+    pub replay_from_block: Option<u32>,
+}
+
+impl EventForwarder {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeeTickerGrpc {
+    /// Whether the internal gRPC API of the fee ticker is started.
+    pub enabled: bool,
+    /// Port to which the gRPC server is listening.
+    pub port: u16,
+}
+
+impl FeeTickerGrpc {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
+    use crate::configs::test_utils::{addr, hash, set_env};
     use std::net::IpAddr;
 
     fn expected_config() -> ApiConfig {
@@ -154,6 +261,8 @@ mod tests {
                 enforce_pubkey_change_fee: true,
                 max_number_of_transactions_per_batch: 200,
                 max_number_of_authors_per_batch: 10,
+                account_state_cache_enabled: false,
+                account_state_cache_poll_interval: 500,
             },
             admin: AdminApi {
                 port: 8080,
@@ -180,6 +289,36 @@ mod tests {
                 secret_auth: "sample".into(),
             },
             prometheus: Prometheus { port: 3312 },
+            permit_relayer: PermitRelayer {
+                enabled: false,
+                relayer_eth_addr: addr("36615cf349d7f6344891b1e7ca7c72883f5dc049"),
+                relayer_private_key: hash(
+                    "03c807e375d9a70fb5f21984496e018baed148dad00829b58d7ca9e557f2998c",
+                ),
+                poll_interval: 1000,
+            },
+            forced_exit_requests: ForcedExitRequests {
+                enabled: false,
+                max_tokens_per_request: 10,
+                price_scaling_factor: 1.5,
+                request_lifetime_secs: 3600,
+                poll_interval: 1000,
+                funding_account_addr: addr("de03a0b5963f75f1c8485b355ff6d30f3093bde7"),
+                funding_account_private_key: hash(
+                    "0559b9f000b4e4bbb7fe02e1374cef9623c2ab7c3791204b490e1f229191d104",
+                ),
+            },
+            event_forwarder: EventForwarder {
+                enabled: false,
+                nats_url: "nats://127.0.0.1:4222".into(),
+                subject_prefix: "zksync".into(),
+                poll_interval: 1000,
+                replay_from_block: Some(10),
+            },
+            fee_ticker_grpc: FeeTickerGrpc {
+                enabled: false,
+                port: 3040,
+            },
         }
     }
 
@@ -191,6 +330,8 @@ API_COMMON_FORCED_EXIT_MINIMUM_ACCOUNT_AGE_SECS="0"
 API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_ACCOUNT_STATE_CACHE_ENABLED=false
+API_COMMON_ACCOUNT_STATE_CACHE_POLL_INTERVAL=500
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
@@ -206,6 +347,24 @@ API_PROVER_PORT="8088"
 API_PROVER_URL="http://127.0.0.1:8088"
 API_PROVER_SECRET_AUTH="sample"
 API_PROMETHEUS_PORT="3312"
+API_PERMIT_RELAYER_ENABLED=false
+API_PERMIT_RELAYER_RELAYER_ETH_ADDR="0x36615cf349d7f6344891b1e7ca7c72883f5dc049"
+API_PERMIT_RELAYER_RELAYER_PRIVATE_KEY="0x03c807e375d9a70fb5f21984496e018baed148dad00829b58d7ca9e557f2998c"
+API_PERMIT_RELAYER_POLL_INTERVAL="1000"
+API_FORCED_EXIT_REQUESTS_ENABLED=false
+API_FORCED_EXIT_REQUESTS_MAX_TOKENS_PER_REQUEST=10
+API_FORCED_EXIT_REQUESTS_PRICE_SCALING_FACTOR=1.5
+API_FORCED_EXIT_REQUESTS_REQUEST_LIFETIME_SECS=3600
+API_FORCED_EXIT_REQUESTS_POLL_INTERVAL="1000"
+API_FORCED_EXIT_REQUESTS_FUNDING_ACCOUNT_ADDR="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+API_FORCED_EXIT_REQUESTS_FUNDING_ACCOUNT_PRIVATE_KEY="0x0559b9f000b4e4bbb7fe02e1374cef9623c2ab7c3791204b490e1f229191d104"
+API_EVENT_FORWARDER_ENABLED=false
+API_EVENT_FORWARDER_NATS_URL="nats://127.0.0.1:4222"
+API_EVENT_FORWARDER_SUBJECT_PREFIX="zksync"
+API_EVENT_FORWARDER_POLL_INTERVAL="1000"
+API_EVENT_FORWARDER_REPLAY_FROM_BLOCK="10"
+API_FEE_TICKER_GRPC_ENABLED=false
+API_FEE_TICKER_GRPC_PORT="3040"
         "#;
         set_env(config);
 
@@ -239,5 +398,9 @@ API_PROMETHEUS_PORT="3312"
             config.json_rpc.http_bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.json_rpc.http_port)
         );
+        assert_eq!(
+            config.fee_ticker_grpc.bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.fee_ticker_grpc.port)
+        );
     }
 }
@@ -1,5 +1,5 @@
 /// External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 /// Built-in uses
 use std::time::Duration;
 // Local uses
@@ -9,7 +9,7 @@ use zksync_types::Address;
 
 use crate::envy_load;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ChainConfig {
     /// Proving / circuit data configuration.
     pub circuit: Circuit,
@@ -29,7 +29,7 @@ impl ChainConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Circuit {
     /// Path to the directory with the cryptographical keys. Relative to `$ZKSYNC_HOME`.
     pub key_dir: String,
@@ -63,13 +63,13 @@ impl Circuit {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Eth {
     /// Name of the used Ethereum network, e.g. `localhost` or `rinkeby`.
     pub network: Network,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct StateKeeper {
     /// Block sizes to be generated by server. Has to contain only values set in the `supported_block_chunks_sizes`,
     /// otherwise block will never be proven. This list can contain not all the values though: e.g. for local
@@ -92,6 +92,11 @@ pub struct StateKeeper {
     pub last_tx_signer_used: bool,
     pub last_tx_signer_address: Address,
     pub last_tx_signer_private_key: String,
+    /// Maximum estimated L1 cost (in wei) of the block per executed operation. Sealing of the block by
+    /// timeout is postponed while the cost is higher. `0` disables the check.
+    pub seal_max_cost_per_operation: u64,
+    /// Maximum amount of miniblock iterations for which the block sealing can be postponed.
+    pub seal_max_delay_iterations: u64,
 }
 
 impl StateKeeper {
@@ -159,6 +164,8 @@ mod tests {
                 last_tx_signer_used: false,
                 last_tx_signer_private_key: "0xaabbeecc".into(),
                 last_tx_signer_address: addr("da03a0b5963f75f1c8485b355ff6d30f3093bde7"),
+                seal_max_cost_per_operation: 0,
+                seal_max_delay_iterations: 50,
             },
         }
     }
@@ -190,6 +197,8 @@ CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
 CHAIN_STATE_KEEPER_LAST_TX_SIGNER_USED="false"
 CHAIN_STATE_KEEPER_LAST_TX_SIGNER_ADDRESS="0xda03a0b5963f75f1c8485b355ff6d30f3093bde7"
 CHAIN_STATE_KEEPER_LAST_TX_SIGNER_PRIVATE_KEY="0xaabbeecc"
+CHAIN_STATE_KEEPER_SEAL_MAX_COST_PER_OPERATION="0"
+CHAIN_STATE_KEEPER_SEAL_MAX_DELAY_ITERATIONS="50"
         "#;
         set_env(config);
 
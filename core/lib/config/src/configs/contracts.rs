@@ -1,12 +1,12 @@
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_types::{Address, H256};
 // Local uses
 use crate::envy_load;
 
 /// Data about deployed contracts.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ContractsConfig {
     pub upgrade_gatekeeper_addr: Address,
     pub governance_target_addr: Address,
@@ -14,6 +14,8 @@ pub struct ContractsConfig {
     pub contract_target_addr: Address,
     pub contract_addr: Address,
     pub governance_addr: Address,
+    /// Address of the contract allowing anyone to list the tokens for a fee.
+    pub token_governance_addr: Address,
     pub verifier_addr: Address,
     pub deploy_factory_addr: Address,
     pub genesis_tx_hash: H256,
@@ -38,6 +40,7 @@ mod tests {
             contract_target_addr: addr("5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"),
             contract_addr: addr("70a0F165d6f8054d0d0CF8dFd4DD2005f0AF6B55"),
             governance_addr: addr("5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"),
+            token_governance_addr: addr("7c3F3a6d3Ef53E35B44f2E5aC7c0A2d0Da3f2E4C"),
             verifier_addr: addr("DAbb67b676F5b01FcC8997Cc8439846D0d8078ca"),
             deploy_factory_addr: addr("FC073319977e314F251EAE6ae6bE76B0B3BAeeCF"),
             genesis_tx_hash: hash(
@@ -55,6 +58,7 @@ CONTRACTS_VERIFIER_TARGET_ADDR="0x5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"
 CONTRACTS_CONTRACT_TARGET_ADDR="0x5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"
 CONTRACTS_CONTRACT_ADDR="0x70a0F165d6f8054d0d0CF8dFd4DD2005f0AF6B55"
 CONTRACTS_GOVERNANCE_ADDR="0x5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9"
+CONTRACTS_TOKEN_GOVERNANCE_ADDR="0x7c3F3a6d3Ef53E35B44f2E5aC7c0A2d0Da3f2E4C"
 CONTRACTS_VERIFIER_ADDR="0xDAbb67b676F5b01FcC8997Cc8439846D0d8078ca"
 CONTRACTS_DEPLOY_FACTORY_ADDR="0xFC073319977e314F251EAE6ae6bE76B0B3BAeeCF"
 CONTRACTS_GENESIS_TX_HASH="0xb99ebfea46cbe05a21cd80fe5597d97b204befc52a16303f579c607dc1ac2e2e"
@@ -2,22 +2,38 @@
 use std::time;
 
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 // Local uses
 use crate::envy_load;
 
 /// Used database configuration.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct DBConfig {
     /// Amount of open connections to the database held by server in the pool.
     pub pool_size: usize,
     /// Database URL.
     pub url: String,
+    /// URL of the read replica of the database. If set, read-only API queries are served by the replica.
+    pub replica_url: Option<String>,
+    /// Queries executed for longer than this amount of milliseconds are logged.
+    pub slow_query_threshold: u64,
+    /// Queries executed for longer than this amount of milliseconds are aborted. Zero value disables the timeout.
+    pub statement_timeout: u64,
     /// Rejected transactions will be stored in the database for this amount of hours.
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Whether the data of the old executed blocks should be moved to the archive tables.
+    pub pruning_enabled: bool,
+    /// Amount of the latest executed blocks which data is never pruned.
+    pub pruning_retention_blocks: u32,
+    /// Sleep time (in hours) of the actor responsible for the data pruning.
+    pub pruning_interval: u64,
+    /// Whether the per-block balance snapshots should be populated.
+    pub balance_snapshots_enabled: bool,
+    /// Sleep time (in seconds) of the actor responsible for populating the balance snapshots.
+    pub balance_snapshots_interval: u64,
 }
 
 impl DBConfig {
@@ -34,6 +50,14 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval * Self::SECS_PER_HOUR)
     }
+
+    pub fn pruning_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.pruning_interval * Self::SECS_PER_HOUR)
+    }
+
+    pub fn balance_snapshots_interval(&self) -> time::Duration {
+        time::Duration::from_secs(self.balance_snapshots_interval)
+    }
 }
 
 #[cfg(test)]
@@ -45,8 +69,16 @@ mod tests {
         DBConfig {
             pool_size: 10,
             url: "postgres://postgres@localhost/plasma".into(),
+            replica_url: Some("postgres://postgres@replica/plasma".into()),
+            slow_query_threshold: 1000,
+            statement_timeout: 60000,
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            pruning_enabled: false,
+            pruning_retention_blocks: 100000,
+            pruning_interval: 24,
+            balance_snapshots_enabled: false,
+            balance_snapshots_interval: 60,
         }
     }
 
@@ -55,8 +87,16 @@ mod tests {
         let config = r#"
 DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
+DATABASE_REPLICA_URL="postgres://postgres@replica/plasma"
+DATABASE_SLOW_QUERY_THRESHOLD="1000"
+DATABASE_STATEMENT_TIMEOUT="60000"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_PRUNING_ENABLED="false"
+DATABASE_PRUNING_RETENTION_BLOCKS="100000"
+DATABASE_PRUNING_INTERVAL="24"
+DATABASE_BALANCE_SNAPSHOTS_ENABLED="false"
+DATABASE_BALANCE_SNAPSHOTS_INTERVAL="60"
         "#;
         set_env(config);
 
@@ -1,10 +1,12 @@
+// Built-in uses
+use std::time::Duration;
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Local uses
 use crate::envy_load;
 
 /// Configuration for the Ethereum gateways.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ETHClientConfig {
     /// Numeric identifier of the L1 network (e.g. `9` for localhost).
     pub chain_id: u8,
@@ -14,6 +16,11 @@ pub struct ETHClientConfig {
     pub gas_price_factor: f64,
     /// Address of the Ethereum node API.
     pub web3_url: Vec<String>,
+    /// Maximum response time (in ms) of the Ethereum node after which it is considered degraded
+    /// and the next node from the `web3_url` list is preferred.
+    pub provider_max_latency: u64,
+    /// Time (in seconds) during which the degraded Ethereum node is not preferred.
+    pub provider_cooldown: u64,
 }
 
 impl ETHClientConfig {
@@ -28,6 +35,16 @@ impl ETHClientConfig {
             .cloned()
             .expect("Should be at least one")
     }
+
+    /// Converts `self.provider_max_latency` into `Duration`.
+    pub fn provider_max_latency(&self) -> Duration {
+        Duration::from_millis(self.provider_max_latency)
+    }
+
+    /// Converts `self.provider_cooldown` into `Duration`.
+    pub fn provider_cooldown(&self) -> Duration {
+        Duration::from_secs(self.provider_cooldown)
+    }
 }
 
 #[cfg(test)]
@@ -43,6 +60,8 @@ mod tests {
                 "http://127.0.0.1:8545".into(),
                 "http://127.0.0.1:8546".into(),
             ],
+            provider_max_latency: 10000,
+            provider_cooldown: 60,
         }
     }
 
@@ -52,6 +71,8 @@ mod tests {
 ETH_CLIENT_CHAIN_ID="9"
 ETH_CLIENT_GAS_PRICE_FACTOR="1"
 ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545,http://127.0.0.1:8546"
+ETH_CLIENT_PROVIDER_MAX_LATENCY="10000"
+ETH_CLIENT_PROVIDER_COOLDOWN="60"
         "#;
         set_env(config);
 
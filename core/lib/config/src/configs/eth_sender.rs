@@ -1,14 +1,14 @@
 // Built-in uses
 use std::time::Duration;
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_types::{Address, H256};
 // Local uses
 use crate::envy_load;
 
 /// Configuration for the Ethereum sender crate.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ETHSenderConfig {
     /// Options related to the Ethereum sender directly.
     pub sender: Sender,
@@ -28,7 +28,7 @@ impl ETHSenderConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Sender {
     /// Private key of the operator account.
     pub operator_private_key: H256,
@@ -53,7 +53,7 @@ impl Sender {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GasLimit {
     /// Gas price limit to be used by GasAdjuster until the statistics data is gathered.
     pub default: u64,
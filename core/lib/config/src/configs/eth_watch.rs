@@ -1,12 +1,12 @@
 // Built-in uses
 use std::time::Duration;
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Local uses
 use crate::envy_load;
 
 /// Configuration for the Ethereum sender crate.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ETHWatchConfig {
     /// Amount of confirmations for the priority operation to be processed.
     /// In production this should be a non-zero value because of block reverts.
@@ -14,6 +14,9 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Amount of Ethereum blocks before the expiration of the oldest unprocessed priority
+    /// operation at which the alert is raised.
+    pub priority_op_expiration_alert_blocks: u64,
 }
 
 impl ETHWatchConfig {
@@ -36,6 +39,7 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: 0,
             eth_node_poll_interval: 300,
+            priority_op_expiration_alert_blocks: 5760,
         }
     }
 
@@ -44,6 +48,7 @@ mod tests {
         let config = r#"
 ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
 ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+ETH_WATCH_PRIORITY_OP_EXPIRATION_ALERT_BLOCKS="5760"
         "#;
         set_env(config);
 
@@ -1,11 +1,11 @@
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_types::H256;
 // Local uses
 use crate::envy_load;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Json,
@@ -16,7 +16,7 @@ pub enum LogFormat {
 ///
 /// While these options may not be used by the server, it's helpful to provide an interface for them too,
 /// so at the very least it will be checked for correctness and parseability within the tests.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MiscConfig {
     /// Download setup files from `prover_setup_network_dir` if `prover_download_setup` == 1
     /// or use local files if `prover_download_setup` == 0.
@@ -35,6 +35,8 @@ pub struct MiscConfig {
     pub fee_account_private_key: H256,
     /// Log format
     pub log_format: LogFormat,
+    /// DSN of the Sentry-compatible project the panics are reported to. Reporting is disabled if not set.
+    pub sentry_dsn: Option<String>,
 }
 
 impl MiscConfig {
@@ -60,6 +62,7 @@ mod tests {
                 "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
             ),
             log_format: LogFormat::Json,
+            sentry_dsn: Some("https://abcdef@sentry.example.com/42".into()),
         }
     }
 
@@ -74,6 +77,7 @@ MISC_ETHERSCAN_API_KEY="unset"
 MISC_MAX_LIQUIDATION_FEE_PERCENT="5"
 MISC_FEE_ACCOUNT_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 MISC_LOG_FORMAT="json"
+MISC_SENTRY_DSN="https://abcdef@sentry.example.com/42"
         "#;
         set_env(config);
 
@@ -1,12 +1,12 @@
 // Built-in uses
 use std::time::Duration;
 // External uses
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 // Local uses
 use crate::envy_load;
 
 /// Configuration for the prover application and part of the server that interact with it.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ProverConfig {
     pub prover: Prover,
     pub core: Core,
@@ -24,7 +24,7 @@ impl ProverConfig {
 }
 
 /// Actual prover application settings.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Prover {
     /// Interval of notifying about an ongoing job in ms.
     pub heartbeat_interval: u64,
@@ -52,9 +52,10 @@ impl Prover {
 }
 
 /// Core settings related to the prover applications interacting with it.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Core {
-    /// Timeout to consider prover gone in ms.
+    /// Timeout to consider prover gone in ms. Jobs not renewed by the prover heartbeats
+    /// for this time are given to other provers.
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
@@ -67,12 +68,15 @@ impl Core {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct WitnessGenerator {
     /// Interval to check whether a new witness generation job should be started in ms.
     pub prepare_data_interval: u64,
     /// Amount of witness generator threads.
     pub witness_generators: usize,
+    /// Blocks with priority operations expiring sooner than this margin (in seconds)
+    /// are proven before the other blocks.
+    pub priority_op_deadline_margin: u64,
 }
 
 impl WitnessGenerator {
@@ -80,6 +84,11 @@ impl WitnessGenerator {
     pub fn prepare_data_interval(&self) -> Duration {
         Duration::from_millis(self.prepare_data_interval)
     }
+
+    /// Converts `self.priority_op_deadline_margin` into `Duration`.
+    pub fn priority_op_deadline_margin(&self) -> Duration {
+        Duration::from_secs(self.priority_op_deadline_margin)
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +110,7 @@ mod tests {
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                priority_op_deadline_margin: 86400,
             },
         }
     }
@@ -115,6 +125,7 @@ PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_PRIORITY_OP_DEADLINE_MARGIN="86400"
         "#;
         set_env(config);
 
@@ -149,5 +160,9 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.witness_generator.prepare_data_interval(),
             Duration::from_millis(config.witness_generator.prepare_data_interval)
         );
+        assert_eq!(
+            config.witness_generator.priority_op_deadline_margin(),
+            Duration::from_secs(config.witness_generator.priority_op_deadline_margin)
+        );
     }
 }
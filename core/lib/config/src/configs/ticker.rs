@@ -1,28 +1,338 @@
 // Built-in uses
+use std::str::FromStr;
+use std::time::Duration;
 // External uses
-use serde::Deserialize;
+use chrono::{DateTime, TimeZone, Utc};
+use num::{rational::Ratio, BigUint};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 // Workspace uses
-use zksync_types::Address;
+use zksync_types::{Address, H256};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
 // Local uses
 use crate::envy_load;
 
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+/// Public CoinGecko API, which is rate limited for the clients without the key.
+const COINGECKO_PUBLIC_URL: &str = "https://api.coingecko.com";
+/// CoinGecko API for the clients with the key of the paid plan.
+const COINGECKO_PRO_URL: &str = "https://pro-api.coingecko.com/";
+
+/// Token price API, given by its name, e.g. `CoinGecko`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Chainlink price feed contracts, see `chainlink_feeds`. The prices of the tokens without
+    /// the feeds are requested from CoinGecko.
+    Chainlink,
+    /// Time-weighted average prices of the Uniswap V3 pools, see `uniswap_v3_pools`. Allows
+    /// quoting the fees in the tokens not listed by CoinGecko yet. The other pool tokens and
+    /// the tokens without the pools are priced by CoinGecko.
+    UniswapV3,
+    /// Binance spot prices in USDT, see `exchange_symbols`.
+    Binance,
+    /// Kraken spot prices in USD, see `exchange_symbols`.
+    Kraken,
+    /// Fixed prices set in `static_token_prices`, for the local development without
+    /// the access to the price APIs.
+    Static,
+    /// Source registered by the server under this name, e.g. the internal oracle service
+    /// of the operator.
+    Custom(String),
+}
+
+impl TokenPriceSource {
+    const BUILTIN: [TokenPriceSource; 7] = [
+        TokenPriceSource::CoinGecko,
+        TokenPriceSource::CoinMarketCap,
+        TokenPriceSource::Chainlink,
+        TokenPriceSource::UniswapV3,
+        TokenPriceSource::Binance,
+        TokenPriceSource::Kraken,
+        TokenPriceSource::Static,
+    ];
+
+    /// Returns the name of the source the config refers to it by.
+    pub fn name(&self) -> &str {
+        match self {
+            TokenPriceSource::CoinGecko => "CoinGecko",
+            TokenPriceSource::CoinMarketCap => "CoinMarketCap",
+            TokenPriceSource::Chainlink => "Chainlink",
+            TokenPriceSource::UniswapV3 => "UniswapV3",
+            TokenPriceSource::Binance => "Binance",
+            TokenPriceSource::Kraken => "Kraken",
+            TokenPriceSource::Static => "Static",
+            TokenPriceSource::Custom(name) => name,
+        }
+    }
+
+    /// Returns the source with the name, which is custom unless it's one of the built-in ones.
+    pub fn from_name(name: &str) -> Self {
+        Self::BUILTIN
+            .iter()
+            .find(|source| source.name() == name)
+            .cloned()
+            .unwrap_or_else(|| TokenPriceSource::Custom(name.to_string()))
+    }
+}
+
+impl Serialize for TokenPriceSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenPriceSource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name.trim().is_empty() {
+            return Err(de::Error::custom("token price source name is empty"));
+        }
+        Ok(Self::from_name(name.trim()))
+    }
+}
+
+/// How the prices of the main and the fallback token price sources are combined.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TokenPriceAggregation {
+    /// The sources are requested in order until one of them returns a fresh price.
+    Fallback,
+    /// All the sources are requested at once and the median of their prices is used.
+    Median,
+}
+
+/// Source of the gas price the fees are calculated with.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum GasPriceSource {
+    /// Gas price observed by `eth_sender`.
+    EthSender,
+    /// Chainlink-compatible gas price feed contract.
+    Oracle,
+}
+
+/// Risk factor of the token given as `token_address:factor`, e.g. `0x6b17...1d0f:1.5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRiskFactorConfig {
+    pub token: Address,
+    pub risk_factor: Ratio<BigUint>,
+}
+
+impl Serialize for TokenRiskFactorConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let risk_factor =
+            UnsignedRatioSerializeAsDecimal::serialize_to_str_with_dot(&self.risk_factor, 18);
+        serializer.serialize_str(&format!("{:?}:{}", self.token, risk_factor))
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenRiskFactorConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let mut parts = value.splitn(2, ':');
+        let (token, risk_factor) = match (parts.next(), parts.next()) {
+            (Some(token), Some(risk_factor)) => (token.trim(), risk_factor.trim()),
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "expected `token_address:factor`, got `{}`",
+                    value
+                )))
+            }
+        };
+        let token = Address::from_str(token.trim_start_matches("0x")).map_err(de::Error::custom)?;
+        let risk_factor =
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(risk_factor)
+                .map_err(de::Error::custom)?;
+        Ok(Self { token, risk_factor })
+    }
+}
+
+/// Chainlink USD price feed of the token given as `token_symbol:feed_address`,
+/// e.g. `ETH:0x5f4e...8419`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainlinkFeedConfig {
+    pub token: String,
+    pub feed: Address,
+}
+
+impl Serialize for ChainlinkFeedConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{}:{:?}", self.token, self.feed))
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainlinkFeedConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let mut parts = value.splitn(2, ':');
+        let (token, feed) = match (parts.next(), parts.next()) {
+            (Some(token), Some(feed)) => (token.trim(), feed.trim()),
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "expected `token_symbol:feed_address`, got `{}`",
+                    value
+                )))
+            }
+        };
+        let feed = Address::from_str(feed.trim_start_matches("0x")).map_err(de::Error::custom)?;
+        Ok(Self {
+            token: token.to_string(),
+            feed,
+        })
+    }
+}
+
+/// Uniswap V3 pool the token is priced by, given as `token_symbol:pool_address`,
+/// e.g. `UNI:0x1d42...a801`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniswapV3PoolConfig {
+    pub token: String,
+    pub pool: Address,
+}
+
+impl Serialize for UniswapV3PoolConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{}:{:?}", self.token, self.pool))
+    }
+}
+
+impl<'de> Deserialize<'de> for UniswapV3PoolConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let mut parts = value.splitn(2, ':');
+        let (token, pool) = match (parts.next(), parts.next()) {
+            (Some(token), Some(pool)) => (token.trim(), pool.trim()),
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "expected `token_symbol:pool_address`, got `{}`",
+                    value
+                )))
+            }
+        };
+        let pool = Address::from_str(pool.trim_start_matches("0x")).map_err(de::Error::custom)?;
+        Ok(Self {
+            token: token.to_string(),
+            pool,
+        })
+    }
+}
+
+/// Fixed USD price of the token given as `token_symbol:price`, e.g. `ETH:3000`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticTokenPriceConfig {
+    pub token: String,
+    pub usd_price: Ratio<BigUint>,
+}
+
+impl Serialize for StaticTokenPriceConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let usd_price =
+            UnsignedRatioSerializeAsDecimal::serialize_to_str_with_dot(&self.usd_price, 18);
+        serializer.serialize_str(&format!("{}:{}", self.token, usd_price))
+    }
+}
+
+impl<'de> Deserialize<'de> for StaticTokenPriceConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let mut parts = value.splitn(2, ':');
+        let (token, usd_price) = match (parts.next(), parts.next()) {
+            (Some(token), Some(usd_price)) => (token.trim(), usd_price.trim()),
+            _ => {
+                return Err(de::Error::custom(format!(
+                    "expected `token_symbol:price`, got `{}`",
+                    value
+                )))
+            }
+        };
+        let usd_price = UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(usd_price)
+            .map_err(de::Error::custom)?;
+        Ok(Self {
+            token: token.to_string(),
+            usd_price,
+        })
+    }
+}
+
+/// Exchange market the token is priced by, given as `token_symbol:market_symbol`,
+/// e.g. `WBTC:BTCUSDT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeSymbolConfig {
+    pub token: String,
+    pub market: String,
+}
+
+impl Serialize for ExchangeSymbolConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{}:{}", self.token, self.market))
+    }
+}
+
+impl<'de> Deserialize<'de> for ExchangeSymbolConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let mut parts = value.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(token), Some(market))
+                if !token.trim().is_empty() && !market.trim().is_empty() =>
+            {
+                Ok(Self {
+                    token: token.trim().to_string(),
+                    market: market.trim().to_string(),
+                })
+            }
+            _ => Err(de::Error::custom(format!(
+                "expected `token_symbol:market_symbol`, got `{}`",
+                value
+            ))),
+        }
+    }
 }
 
 /// Configuration for the fee ticker.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct TickerConfig {
     /// Indicator of the API to be used for getting token prices.
     pub token_price_source: TokenPriceSource,
+    /// APIs tried in order if the main one fails or returns a stale price.
+    #[serde(default)]
+    pub token_price_fallback_sources: Vec<TokenPriceSource>,
+    /// How the prices of the main and the fallback sources are combined.
+    pub token_price_aggregation: TokenPriceAggregation,
+    /// Maximum deviation (in percent) of the price received from the source from the median
+    /// of the sources. Used if the aggregation is `Median`. Set to 0 to disable the check.
+    pub max_source_price_deviation_percent: u64,
     /// URL of CoinMarketCap API. Can be set to the mock server for local development.
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
+    /// Key of CoinGecko Pro API. If set, the requests to the public API are sent to the Pro one.
+    #[serde(default)]
+    pub coingecko_api_key: Option<String>,
+    /// USD price feeds of the tokens, used if the token price source is `Chainlink`.
+    #[serde(default)]
+    pub chainlink_feeds: Vec<ChainlinkFeedConfig>,
+    /// Maximum age (in seconds) of the price reported by the Chainlink feed. Stale prices
+    /// are rejected, so the last accepted price is used until the feed is updated.
+    pub chainlink_max_age_secs: u64,
+    /// Pools pricing the tokens, used if the token price source is `UniswapV3`.
+    #[serde(default)]
+    pub uniswap_v3_pools: Vec<UniswapV3PoolConfig>,
+    /// Window (in seconds) the Uniswap V3 pool prices are averaged over. The pools must keep
+    /// enough observations to cover it.
+    pub uniswap_v3_twap_window_secs: u32,
+    /// URL of the exchange REST API, used if the token price source is `Binance` or `Kraken`.
+    pub exchange_base_url: String,
+    /// Exchange markets of the tokens whose market symbols differ from the default ones,
+    /// i.e. `{token}USDT` on Binance and `{token}USD` on Kraken.
+    #[serde(default)]
+    pub exchange_symbols: Vec<ExchangeSymbolConfig>,
+    /// USD prices of the tokens, used if the token price source is `Static`.
+    #[serde(default)]
+    pub static_token_prices: Vec<StaticTokenPriceConfig>,
     /// Coefficient for the fee price for fast withdrawal requests.
     pub fast_processing_coeff: f64,
+    /// Cost of the proof generation for one chunk of the block, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_chunk_usd: Ratio<BigUint>,
     /// Url to uniswap api
     pub uniswap_url: String,
     /// The volume of tokens to confirm their liquidity
@@ -37,6 +347,80 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// List of tokens for which subsidions are disabled.
     pub not_subsidized_tokens: Vec<Address>,
+    /// Risk factors of the tokens the fees are multiplied by. The ones stored in the database
+    /// take precedence, so the factors may be set without the database-based configuration.
+    #[serde(default)]
+    pub tokens_risk_factors: Vec<TokenRiskFactorConfig>,
+    /// Observed token prices are stored in the price history for this amount of days.
+    pub price_history_retention_days: u64,
+    /// Whether the fee quotes and overpayments should be stored for the auditing purposes.
+    pub fee_audit_enabled: bool,
+    /// Fee quotes and overpayment records are stored for this amount of days.
+    pub fee_audit_retention_days: u64,
+    /// Whether the fee quotes should be signed, so the quoted fee is accepted until expiry.
+    pub signed_quotes_enabled: bool,
+    /// Time (in seconds) during which the signed fee quote is valid.
+    pub quote_validity_secs: u64,
+    /// Secret key used to sign the fee quotes. Must be the same for all the API servers.
+    pub quote_signing_key: H256,
+    /// Number of the gas price samples the fee is calculated from.
+    pub gas_price_window_size: usize,
+    /// Time (in minutes) covered by the gas price samples.
+    pub gas_price_window_minutes: u64,
+    /// Indicator of the source to be used for getting the gas price.
+    pub gas_price_source: GasPriceSource,
+    /// Address of the gas price feed contract, used if the gas price source is `Oracle`.
+    pub gas_oracle_addr: Address,
+    /// Maximum age (in seconds) of the gas price reported by the feed. The gas price observed
+    /// by `eth_sender` is used while the feed is stale or not available.
+    pub gas_oracle_max_age_secs: u64,
+    /// Maximum deviation (in percent) of the token price received from the API
+    /// from the last accepted one. Set to 0 to disable the check.
+    pub max_price_deviation_percent: u64,
+    /// Maximum age (in seconds) of the last known token price used when the price API
+    /// is not available. Set to 0 to use the last known price regardless of its age.
+    pub max_price_staleness_secs: u64,
+    /// Maximum number of the public fee requests waiting to be handled.
+    /// Once exceeded, the oldest requests are rejected.
+    pub max_pending_requests: usize,
+    /// Whether the standby ticker taking over the requests once the primary one fails is run.
+    pub standby_ticker_enabled: bool,
+    /// Time (in seconds) after which the ticker not taking the requests is considered hung.
+    pub hang_timeout_secs: u64,
+    /// Time (in seconds) the ticker handles a single request for, so the request stuck waiting
+    /// for the price API doesn't stall the ticker. Set to 0 to disable the timeout.
+    pub request_timeout_secs: u64,
+    /// Whether the fast processing surcharge is scaled by the share of the free chunks
+    /// in the pending block.
+    pub dynamic_fast_processing_enabled: bool,
+    /// Time (in seconds) between the samples of the pending block fullness.
+    pub block_fullness_sampling_interval_secs: u64,
+    /// Share (in percent) of the fixed block overhead paid by every batch transaction
+    /// except for the one with the largest overhead. Set to 100 to charge batch transactions in full.
+    pub batch_overhead_share_percent: u64,
+    /// Time (in seconds) the calculated fee is reused for the identical requests.
+    /// Set to 0 to disable the cache.
+    pub fee_cache_ttl_secs: u64,
+    /// Time (in minutes) the token prices used in the fee formula are averaged over.
+    /// Set to 0 to use the spot prices.
+    pub price_twap_minutes: u64,
+    /// Time (in hours) the gas actually used by the L1 transactions is averaged over, so the fees
+    /// are calculated from the observed gas costs of the operations instead of the estimated ones.
+    /// Set to 0 to use the estimated gas costs.
+    pub observed_gas_window_hours: u64,
+    /// Minimum number of the operations of the type the observed gas cost is calculated from.
+    /// The estimated gas cost is used until enough operations are observed.
+    pub observed_gas_min_ops: u64,
+    /// Safety margin (in percent) the observed gas costs are increased by.
+    pub observed_gas_margin_percent: u64,
+    /// JSON file with the recorded token prices and gas prices. If set, the fees are calculated
+    /// from the recorded inputs instead of the live ones, see `replay_timestamp`.
+    #[serde(default)]
+    pub replay_samples_file: Option<String>,
+    /// Moment (UNIX timestamp in seconds) the recorded inputs are replayed as of.
+    /// The moment of the latest recorded sample is used if not set.
+    #[serde(default)]
+    pub replay_timestamp: Option<i64>,
 }
 
 impl TickerConfig {
@@ -44,28 +428,235 @@ impl TickerConfig {
         envy_load!("fee_ticker", "FEE_TICKER_")
     }
 
-    /// Returns the token price source type and the corresponding API URL.
+    /// Returns the token price source type and the corresponding API URL. The prices of
+    /// the tokens without the Chainlink feeds or Uniswap V3 pools are requested from
+    /// the CoinGecko API.
     pub fn price_source(&self) -> (TokenPriceSource, &str) {
-        let url = match self.token_price_source {
-            TokenPriceSource::CoinGecko => self.coingecko_base_url.as_ref(),
+        (
+            self.token_price_source.clone(),
+            self.price_source_url(&self.token_price_source),
+        )
+    }
+
+    /// Returns the fallback token price sources in order with the corresponding API URLs.
+    pub fn fallback_price_sources(&self) -> Vec<(TokenPriceSource, &str)> {
+        self.token_price_fallback_sources
+            .iter()
+            .map(|source| (source.clone(), self.price_source_url(source)))
+            .collect()
+    }
+
+    /// Returns the main and the fallback token price sources.
+    pub fn all_price_sources(&self) -> impl Iterator<Item = TokenPriceSource> + '_ {
+        std::iter::once(self.token_price_source.clone())
+            .chain(self.token_price_fallback_sources.iter().cloned())
+    }
+
+    /// Returns the URL of CoinGecko API, which is the Pro API instead of the public one
+    /// if the API key is set.
+    pub fn coingecko_url(&self) -> &str {
+        let is_public_api = self.coingecko_base_url.trim_end_matches('/') == COINGECKO_PUBLIC_URL;
+        if is_public_api && self.coingecko_api_key.is_some() {
+            COINGECKO_PRO_URL
+        } else {
+            self.coingecko_base_url.as_ref()
+        }
+    }
+
+    /// Returns the API URL of the source. The custom sources are configured by the server.
+    fn price_source_url(&self, source: &TokenPriceSource) -> &str {
+        match source {
+            TokenPriceSource::CoinGecko
+            | TokenPriceSource::Chainlink
+            | TokenPriceSource::UniswapV3 => self.coingecko_url(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.as_ref(),
-        };
+            TokenPriceSource::Binance | TokenPriceSource::Kraken => self.exchange_base_url.as_ref(),
+            // The static prices are not requested from anywhere.
+            TokenPriceSource::Static | TokenPriceSource::Custom(_) => "",
+        }
+    }
+
+    pub fn price_history_retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.price_history_retention_days as i64)
+    }
+
+    pub fn fee_audit_retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.fee_audit_retention_days as i64)
+    }
+
+    pub fn quote_validity(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.quote_validity_secs as i64)
+    }
+
+    pub fn gas_price_window(&self) -> Duration {
+        Duration::from_secs(self.gas_price_window_minutes * 60)
+    }
+
+    /// Returns the address of the gas price feed contract, if it's the gas price source.
+    pub fn gas_oracle(&self) -> Option<Address> {
+        match self.gas_price_source {
+            GasPriceSource::EthSender => None,
+            GasPriceSource::Oracle => Some(self.gas_oracle_addr),
+        }
+    }
+
+    pub fn chainlink_max_age(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.chainlink_max_age_secs as i64)
+    }
+
+    pub fn uniswap_v3_twap_window(&self) -> Duration {
+        Duration::from_secs(self.uniswap_v3_twap_window_secs as u64)
+    }
+
+    pub fn gas_oracle_max_age(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.gas_oracle_max_age_secs as i64)
+    }
+
+    /// Interval between the gas price samples, so the window is covered by the configured number of them.
+    pub fn gas_price_sampling_interval(&self) -> Duration {
+        self.gas_price_window() / self.gas_price_window_size as u32
+    }
+
+    pub fn max_price_deviation(&self) -> Option<Ratio<BigUint>> {
+        if self.max_price_deviation_percent == 0 {
+            return None;
+        }
+        Some(Ratio::new(
+            self.max_price_deviation_percent.into(),
+            100u32.into(),
+        ))
+    }
+
+    pub fn max_source_price_deviation(&self) -> Option<Ratio<BigUint>> {
+        if self.max_source_price_deviation_percent == 0 {
+            return None;
+        }
+        Some(Ratio::new(
+            self.max_source_price_deviation_percent.into(),
+            100u32.into(),
+        ))
+    }
+
+    pub fn hang_timeout(&self) -> Duration {
+        Duration::from_secs(self.hang_timeout_secs)
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        if self.request_timeout_secs == 0 {
+            return None;
+        }
+        Some(Duration::from_secs(self.request_timeout_secs))
+    }
+
+    pub fn block_fullness_sampling_interval(&self) -> Duration {
+        Duration::from_secs(self.block_fullness_sampling_interval_secs)
+    }
 
-        (self.token_price_source, url)
+    pub fn batch_overhead_share(&self) -> Ratio<BigUint> {
+        Ratio::new(self.batch_overhead_share_percent.into(), 100u32.into())
+    }
+
+    pub fn fee_cache_ttl(&self) -> Option<Duration> {
+        if self.fee_cache_ttl_secs == 0 {
+            return None;
+        }
+        Some(Duration::from_secs(self.fee_cache_ttl_secs))
+    }
+
+    pub fn price_twap_window(&self) -> Option<chrono::Duration> {
+        if self.price_twap_minutes == 0 {
+            return None;
+        }
+        Some(chrono::Duration::minutes(self.price_twap_minutes as i64))
+    }
+
+    pub fn observed_gas_window(&self) -> Option<chrono::Duration> {
+        if self.observed_gas_window_hours == 0 {
+            return None;
+        }
+        Some(chrono::Duration::hours(
+            self.observed_gas_window_hours as i64,
+        ))
+    }
+
+    /// Returns the coefficient the observed gas costs are multiplied by to include the safety margin.
+    pub fn observed_gas_multiplier(&self) -> Ratio<BigUint> {
+        Ratio::new(
+            (100 + self.observed_gas_margin_percent).into(),
+            100u32.into(),
+        )
+    }
+
+    /// Returns the file with the recorded fee inputs and the moment they are replayed as of,
+    /// if the replay mode is enabled.
+    pub fn replay(&self) -> Option<(&str, Option<DateTime<Utc>>)> {
+        let samples_file = self.replay_samples_file.as_deref()?;
+        let at = self
+            .replay_timestamp
+            .map(|timestamp| Utc.timestamp(timestamp, 0));
+        Some((samples_file, at))
+    }
+
+    pub fn max_price_staleness(&self) -> Option<chrono::Duration> {
+        if self.max_price_staleness_secs == 0 {
+            return None;
+        }
+        Some(chrono::Duration::seconds(
+            self.max_price_staleness_secs as i64,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::{addr, set_env};
+    use crate::configs::test_utils::{addr, hash, set_env};
 
     fn expected_config() -> TickerConfig {
         TickerConfig {
             token_price_source: TokenPriceSource::CoinGecko,
+            token_price_fallback_sources: vec![
+                TokenPriceSource::CoinMarketCap,
+                TokenPriceSource::Chainlink,
+            ],
+            token_price_aggregation: TokenPriceAggregation::Median,
+            max_source_price_deviation_percent: 10,
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
+            coingecko_api_key: Some("CG-test-key".into()),
+            chainlink_feeds: vec![
+                ChainlinkFeedConfig {
+                    token: "ETH".into(),
+                    feed: addr("5f4ec3df9cbd43714fe2740f5e3616155c5b8419"),
+                },
+                ChainlinkFeedConfig {
+                    token: "DAI".into(),
+                    feed: addr("aed0c38402a5d19df6e4c03f4e2dced6e29c1ee9"),
+                },
+            ],
+            chainlink_max_age_secs: 3600,
+            uniswap_v3_pools: vec![UniswapV3PoolConfig {
+                token: "UNI".into(),
+                pool: addr("1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"),
+            }],
+            uniswap_v3_twap_window_secs: 1800,
+            exchange_base_url: "http://127.0.0.1:9877".into(),
+            exchange_symbols: vec![ExchangeSymbolConfig {
+                token: "WBTC".into(),
+                market: "BTCUSDT".into(),
+            }],
+            static_token_prices: vec![
+                StaticTokenPriceConfig {
+                    token: "ETH".into(),
+                    usd_price: Ratio::from_integer(BigUint::from(3000u32)),
+                },
+                StaticTokenPriceConfig {
+                    token: "DAI".into(),
+                    usd_price: Ratio::new(BigUint::from(1001u32), BigUint::from(1000u32)),
+                },
+            ],
             fast_processing_coeff: 10.0f64,
+            zkp_cost_chunk_usd: Ratio::new(BigUint::from(1u32), BigUint::from(1000u32)),
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
             liquidity_volume: 100.0,
             available_liquidity_seconds: 1000,
@@ -76,6 +667,45 @@ mod tests {
                 addr("2b591e99afe9f32eaa6214f7b7629768c40eeb39"),
                 addr("34083bbd70d394110487feaa087da875a54624ec"),
             ],
+            tokens_risk_factors: vec![
+                TokenRiskFactorConfig {
+                    token: addr("2b591e99afe9f32eaa6214f7b7629768c40eeb39"),
+                    risk_factor: Ratio::new(BigUint::from(3u32), BigUint::from(2u32)),
+                },
+                TokenRiskFactorConfig {
+                    token: addr("34083bbd70d394110487feaa087da875a54624ec"),
+                    risk_factor: Ratio::from_integer(BigUint::from(2u32)),
+                },
+            ],
+            price_history_retention_days: 30,
+            fee_audit_enabled: false,
+            fee_audit_retention_days: 90,
+            signed_quotes_enabled: true,
+            quote_validity_secs: 60,
+            quote_signing_key: hash(
+                "c1783a9a8222e47778911c58bb5aac1343eb425159ff140799e0a283bfb8fa16",
+            ),
+            gas_price_window_size: 10,
+            gas_price_window_minutes: 5,
+            gas_price_source: GasPriceSource::Oracle,
+            gas_oracle_addr: addr("169e633a2d1e6c10dd91238ba11c4a708dfef37c"),
+            gas_oracle_max_age_secs: 600,
+            max_price_deviation_percent: 50,
+            max_price_staleness_secs: 3600,
+            max_pending_requests: 1000,
+            standby_ticker_enabled: true,
+            hang_timeout_secs: 30,
+            request_timeout_secs: 10,
+            dynamic_fast_processing_enabled: true,
+            block_fullness_sampling_interval_secs: 5,
+            batch_overhead_share_percent: 50,
+            fee_cache_ttl_secs: 2,
+            price_twap_minutes: 15,
+            observed_gas_window_hours: 24,
+            observed_gas_min_ops: 100,
+            observed_gas_margin_percent: 20,
+            replay_samples_file: Some("/var/lib/zksync/fee_samples.json".into()),
+            replay_timestamp: Some(1616313600),
         }
     }
 
@@ -83,16 +713,56 @@ mod tests {
     fn from_env() {
         let config = r#"
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
+FEE_TICKER_TOKEN_PRICE_FALLBACK_SOURCES="CoinMarketCap,Chainlink"
+FEE_TICKER_TOKEN_PRICE_AGGREGATION="Median"
+FEE_TICKER_MAX_SOURCE_PRICE_DEVIATION_PERCENT="10"
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
+FEE_TICKER_COINGECKO_API_KEY="CG-test-key"
+FEE_TICKER_CHAINLINK_FEEDS="ETH:0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419,DAI:0xaed0c38402a5d19df6e4c03f4e2dced6e29c1ee9"
+FEE_TICKER_CHAINLINK_MAX_AGE_SECS="3600"
+FEE_TICKER_UNISWAP_V3_POOLS="UNI:0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"
+FEE_TICKER_UNISWAP_V3_TWAP_WINDOW_SECS="1800"
+FEE_TICKER_EXCHANGE_BASE_URL="http://127.0.0.1:9877"
+FEE_TICKER_EXCHANGE_SYMBOLS="WBTC:BTCUSDT"
+FEE_TICKER_STATIC_TOKEN_PRICES="ETH:3000,DAI:1.001"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
+FEE_TICKER_ZKP_COST_CHUNK_USD="0.001"
 FEE_TICKER_UNISWAP_URL=http://127.0.0.1:9975/graphql
 FEE_TICKER_NOT_SUBSIDIZED_TOKENS="0x2b591e99afe9f32eaa6214f7b7629768c40eeb39,0x34083bbd70d394110487feaa087da875a54624ec"
+FEE_TICKER_TOKENS_RISK_FACTORS="0x2b591e99afe9f32eaa6214f7b7629768c40eeb39:1.5,0x34083bbd70d394110487feaa087da875a54624ec:2"
 FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
 FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
 FEE_TICKER_LIQUIDITY_VOLUME=100
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
+FEE_TICKER_PRICE_HISTORY_RETENTION_DAYS="30"
+FEE_TICKER_FEE_AUDIT_ENABLED="false"
+FEE_TICKER_FEE_AUDIT_RETENTION_DAYS="90"
+FEE_TICKER_SIGNED_QUOTES_ENABLED="true"
+FEE_TICKER_QUOTE_VALIDITY_SECS="60"
+FEE_TICKER_QUOTE_SIGNING_KEY="0xc1783a9a8222e47778911c58bb5aac1343eb425159ff140799e0a283bfb8fa16"
+FEE_TICKER_GAS_PRICE_WINDOW_SIZE="10"
+FEE_TICKER_GAS_PRICE_WINDOW_MINUTES="5"
+FEE_TICKER_GAS_PRICE_SOURCE="Oracle"
+FEE_TICKER_GAS_ORACLE_ADDR="0x169e633a2d1e6c10dd91238ba11c4a708dfef37c"
+FEE_TICKER_GAS_ORACLE_MAX_AGE_SECS="600"
+FEE_TICKER_MAX_PRICE_DEVIATION_PERCENT="50"
+FEE_TICKER_MAX_PRICE_STALENESS_SECS="3600"
+FEE_TICKER_MAX_PENDING_REQUESTS="1000"
+FEE_TICKER_STANDBY_TICKER_ENABLED="true"
+FEE_TICKER_HANG_TIMEOUT_SECS="30"
+FEE_TICKER_REQUEST_TIMEOUT_SECS="10"
+FEE_TICKER_DYNAMIC_FAST_PROCESSING_ENABLED="true"
+FEE_TICKER_BLOCK_FULLNESS_SAMPLING_INTERVAL_SECS="5"
+FEE_TICKER_BATCH_OVERHEAD_SHARE_PERCENT="50"
+FEE_TICKER_FEE_CACHE_TTL_SECS="2"
+FEE_TICKER_PRICE_TWAP_MINUTES="15"
+FEE_TICKER_OBSERVED_GAS_WINDOW_HOURS="24"
+FEE_TICKER_OBSERVED_GAS_MIN_OPS="100"
+FEE_TICKER_OBSERVED_GAS_MARGIN_PERCENT="20"
+FEE_TICKER_REPLAY_SAMPLES_FILE="/var/lib/zksync/fee_samples.json"
+FEE_TICKER_REPLAY_TIMESTAMP="1616313600"
         "#;
         set_env(config);
 
@@ -105,11 +775,13 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
     fn methods() {
         const COINGECKO_URL: &str = "http://coingecko";
         const COINMARKETCAP_URL: &str = "http://coinmarketcap";
+        const EXCHANGE_URL: &str = "http://exchange";
 
         let mut config = expected_config();
 
         config.coingecko_base_url = COINGECKO_URL.into();
         config.coinmarketcap_base_url = COINMARKETCAP_URL.into();
+        config.exchange_base_url = EXCHANGE_URL.into();
 
         config.token_price_source = TokenPriceSource::CoinGecko;
         assert_eq!(
@@ -117,10 +789,124 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
             (TokenPriceSource::CoinGecko, COINGECKO_URL)
         );
 
+        // The key switches the public CoinGecko API to the Pro one, but not the mock server.
+        config.coingecko_base_url = "https://api.coingecko.com/".into();
+        assert_eq!(config.coingecko_url(), "https://pro-api.coingecko.com/");
+        config.coingecko_api_key = None;
+        assert_eq!(config.coingecko_url(), "https://api.coingecko.com/");
+        config.coingecko_base_url = COINGECKO_URL.into();
+
         config.token_price_source = TokenPriceSource::CoinMarketCap;
         assert_eq!(
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL)
         );
+
+        config.token_price_source = TokenPriceSource::Chainlink;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::Chainlink, COINGECKO_URL)
+        );
+        assert_eq!(config.chainlink_max_age(), chrono::Duration::hours(1));
+
+        config.token_price_source = TokenPriceSource::UniswapV3;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::UniswapV3, COINGECKO_URL)
+        );
+        assert_eq!(config.uniswap_v3_twap_window(), Duration::from_secs(1800));
+
+        config.token_price_source = TokenPriceSource::Kraken;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::Kraken, EXCHANGE_URL)
+        );
+        config.token_price_source = TokenPriceSource::Static;
+        assert_eq!(config.price_source(), (TokenPriceSource::Static, ""));
+
+        let custom = TokenPriceSource::Custom("OracleService".into());
+        config.token_price_source = custom.clone();
+        assert_eq!(config.price_source(), (custom.clone(), ""));
+        assert_eq!(TokenPriceSource::from_name("OracleService"), custom);
+        assert_eq!(
+            TokenPriceSource::from_name("Chainlink"),
+            TokenPriceSource::Chainlink
+        );
+        assert_eq!(
+            serde_json::to_string(&custom).unwrap(),
+            r#""OracleService""#.to_string()
+        );
+        config.token_price_source = TokenPriceSource::Static;
+
+        assert_eq!(
+            config.fallback_price_sources(),
+            vec![
+                (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL),
+                (TokenPriceSource::Chainlink, COINGECKO_URL)
+            ]
+        );
+        assert_eq!(
+            config.all_price_sources().collect::<Vec<_>>(),
+            vec![
+                TokenPriceSource::Static,
+                TokenPriceSource::CoinMarketCap,
+                TokenPriceSource::Chainlink
+            ]
+        );
+
+        assert_eq!(
+            config.max_source_price_deviation(),
+            Some(Ratio::new(BigUint::from(1u32), BigUint::from(10u32)))
+        );
+
+        assert_eq!(config.gas_price_window(), Duration::from_secs(300));
+        assert_eq!(config.hang_timeout(), Duration::from_secs(30));
+        assert_eq!(config.request_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(
+            config.block_fullness_sampling_interval(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            config.batch_overhead_share(),
+            Ratio::new(BigUint::from(1u32), BigUint::from(2u32))
+        );
+        assert_eq!(
+            config.gas_price_sampling_interval(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            config.gas_oracle(),
+            Some(addr("169e633a2d1e6c10dd91238ba11c4a708dfef37c"))
+        );
+        config.gas_price_source = GasPriceSource::EthSender;
+        assert_eq!(config.gas_oracle(), None);
+        assert_eq!(config.fee_cache_ttl(), Some(Duration::from_secs(2)));
+        config.fee_cache_ttl_secs = 0;
+        assert_eq!(config.fee_cache_ttl(), None);
+        assert_eq!(
+            config.price_twap_window(),
+            Some(chrono::Duration::minutes(15))
+        );
+        config.price_twap_minutes = 0;
+        assert_eq!(config.price_twap_window(), None);
+        assert_eq!(
+            config.observed_gas_window(),
+            Some(chrono::Duration::hours(24))
+        );
+        assert_eq!(
+            config.observed_gas_multiplier(),
+            Ratio::new(BigUint::from(6u32), BigUint::from(5u32))
+        );
+        config.observed_gas_window_hours = 0;
+        assert_eq!(config.observed_gas_window(), None);
+        assert_eq!(
+            config.replay(),
+            Some((
+                "/var/lib/zksync/fee_samples.json",
+                Some(Utc.timestamp(1616313600, 0))
+            ))
+        );
+        config.replay_samples_file = None;
+        assert_eq!(config.replay(), None);
     }
 }
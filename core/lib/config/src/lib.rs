@@ -1,4 +1,13 @@
-use serde::Deserialize;
+// Built-in uses
+use std::{collections::HashSet, path::Path};
+// External uses
+use num::Zero;
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_types::network::Network;
+// Local uses
+use crate::configs::ticker::{TokenPriceAggregation, TokenPriceSource};
+use crate::loader::{apply_config_files, ConfigError};
 
 pub use crate::configs::{
     ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevLiquidityTokenWatcherConfig,
@@ -6,9 +15,10 @@ pub use crate::configs::{
 };
 
 pub mod configs;
+pub mod loader;
 pub mod test_config;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ZkSyncConfig {
     pub api: ApiConfig,
     pub chain: ChainConfig,
@@ -35,4 +45,231 @@ impl ZkSyncConfig {
             ticker: TickerConfig::from_env(),
         }
     }
+
+    /// Loads the configuration from the given TOML files overridden by the environment
+    /// variables, and checks that it's consistent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required value is neither in the files nor in the environment.
+    pub fn load<P: AsRef<Path>>(config_files: &[P]) -> Result<Self, ConfigError> {
+        apply_config_files(config_files)?;
+        let config = Self::from_env();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the values which can be parsed correctly, but make no sense together.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let api = &self.api;
+        let ports = [
+            ("api.admin.port", api.admin.port),
+            ("api.rest.port", api.rest.port),
+            ("api.json_rpc.http_port", api.json_rpc.http_port),
+            ("api.json_rpc.ws_port", api.json_rpc.ws_port),
+            ("api.private.port", api.private.port),
+            ("api.prover.port", api.prover.port),
+            ("api.prometheus.port", api.prometheus.port),
+        ];
+        let mut used_ports = HashSet::new();
+        for &(name, port) in ports.iter() {
+            if !used_ports.insert(port) {
+                return Err(ConfigError::invalid(
+                    name,
+                    format!("port {} is already used by another server", port),
+                ));
+            }
+        }
+
+        if api.common.max_number_of_transactions_per_batch == 0 {
+            return Err(ConfigError::invalid(
+                "api.common.max_number_of_transactions_per_batch",
+                "must be positive",
+            ));
+        }
+        if api.permit_relayer.enabled
+            && api.permit_relayer.relayer_eth_addr
+                == self.eth_sender.sender.operator_commit_eth_addr
+        {
+            return Err(ConfigError::invalid(
+                "api.permit_relayer.relayer_eth_addr",
+                "the operator account can't be used by the relayer",
+            ));
+        }
+
+        let forced_exit_requests = &api.forced_exit_requests;
+        if forced_exit_requests.enabled && forced_exit_requests.max_tokens_per_request == 0 {
+            return Err(ConfigError::invalid(
+                "api.forced_exit_requests.max_tokens_per_request",
+                "must be positive",
+            ));
+        }
+        if forced_exit_requests.enabled && forced_exit_requests.price_scaling_factor < 1.0 {
+            return Err(ConfigError::invalid(
+                "api.forced_exit_requests.price_scaling_factor",
+                "requests can't be cheaper than the forced exits they are fulfilled by",
+            ));
+        }
+
+        // Chain ID is only defined for the actual Ethereum networks.
+        let network = self.chain.eth.network;
+        if !matches!(network, Network::Test | Network::Unknown)
+            && self.eth_client.chain_id != network.chain_id()
+        {
+            return Err(ConfigError::invalid(
+                "eth_client.chain_id",
+                format!(
+                    "chain ID of the {} network is {}",
+                    network,
+                    network.chain_id()
+                ),
+            ));
+        }
+
+        let circuit = &self.chain.circuit;
+        if circuit.supported_block_chunks_sizes.len()
+            != circuit.supported_block_chunks_sizes_setup_powers.len()
+        {
+            return Err(ConfigError::invalid(
+                "chain.circuit.supported_block_chunks_sizes_setup_powers",
+                "must have the same length as `supported_block_chunks_sizes`",
+            ));
+        }
+        let state_keeper = &self.chain.state_keeper;
+        if state_keeper.block_chunk_sizes.is_empty() {
+            return Err(ConfigError::invalid(
+                "chain.state_keeper.block_chunk_sizes",
+                "at least one block size is required",
+            ));
+        }
+        if let Some(size) = state_keeper
+            .block_chunk_sizes
+            .iter()
+            .find(|size| !circuit.supported_block_chunks_sizes.contains(size))
+        {
+            return Err(ConfigError::invalid(
+                "chain.state_keeper.block_chunk_sizes",
+                format!("block size {} is not supported by the circuit", size),
+            ));
+        }
+
+        if self.db.pool_size == 0 {
+            return Err(ConfigError::invalid("db.pool_size", "must be positive"));
+        }
+        if self.ticker.number_of_ticker_actors == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.number_of_ticker_actors",
+                "at least one ticker actor is required",
+            ));
+        }
+        if self.ticker.fast_processing_coeff < 1.0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.fast_processing_coeff",
+                "fast processing can't be cheaper than the usual one",
+            ));
+        }
+        if self.ticker.zkp_cost_chunk_usd.is_zero() {
+            return Err(ConfigError::invalid(
+                "fee_ticker.zkp_cost_chunk_usd",
+                "must be positive",
+            ));
+        }
+        if self.ticker.signed_quotes_enabled && self.ticker.quote_validity_secs == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.quote_validity_secs",
+                "must be positive",
+            ));
+        }
+        let price_sources: Vec<_> = self.ticker.all_price_sources().collect();
+        for (i, source) in price_sources.iter().enumerate() {
+            if price_sources[..i].contains(source) {
+                return Err(ConfigError::invalid(
+                    "fee_ticker.token_price_fallback_sources",
+                    "price sources must not repeat",
+                ));
+            }
+        }
+        if self.ticker.token_price_aggregation == TokenPriceAggregation::Median
+            && price_sources.len() < 3
+        {
+            return Err(ConfigError::invalid(
+                "fee_ticker.token_price_fallback_sources",
+                "at least three price sources are required for the median price",
+            ));
+        }
+        if price_sources.contains(&TokenPriceSource::Chainlink)
+            && self.ticker.chainlink_feeds.is_empty()
+        {
+            return Err(ConfigError::invalid(
+                "fee_ticker.chainlink_feeds",
+                "at least one feed is required for the Chainlink price source",
+            ));
+        }
+        if price_sources.contains(&TokenPriceSource::Static)
+            && self.ticker.static_token_prices.is_empty()
+        {
+            return Err(ConfigError::invalid(
+                "fee_ticker.static_token_prices",
+                "at least one price is required for the Static price source",
+            ));
+        }
+        if price_sources.contains(&TokenPriceSource::UniswapV3) {
+            if self.ticker.uniswap_v3_pools.is_empty() {
+                return Err(ConfigError::invalid(
+                    "fee_ticker.uniswap_v3_pools",
+                    "at least one pool is required for the Uniswap V3 price source",
+                ));
+            }
+            if self.ticker.uniswap_v3_twap_window_secs == 0 {
+                return Err(ConfigError::invalid(
+                    "fee_ticker.uniswap_v3_twap_window_secs",
+                    "must be positive",
+                ));
+            }
+        }
+        if self.ticker.gas_price_window_size == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.gas_price_window_size",
+                "at least one gas price sample is required",
+            ));
+        }
+        if self.ticker.gas_price_window_minutes == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.gas_price_window_minutes",
+                "must be positive",
+            ));
+        }
+        if self.ticker.max_pending_requests == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.max_pending_requests",
+                "at least one pending request is required",
+            ));
+        }
+        if self.ticker.hang_timeout_secs == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.hang_timeout_secs",
+                "must be positive",
+            ));
+        }
+        if self.ticker.request_timeout_secs >= self.ticker.hang_timeout_secs {
+            return Err(ConfigError::invalid(
+                "fee_ticker.request_timeout_secs",
+                "must be less than the hang timeout",
+            ));
+        }
+        if self.ticker.block_fullness_sampling_interval_secs == 0 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.block_fullness_sampling_interval_secs",
+                "must be positive",
+            ));
+        }
+        if self.ticker.batch_overhead_share_percent > 100 {
+            return Err(ConfigError::invalid(
+                "fee_ticker.batch_overhead_share_percent",
+                "can't exceed 100",
+            ));
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,133 @@
+//! Layered loading of the configuration.
+//!
+//! The configuration can be provided by the TOML files in the same format as the files in
+//! `etc/env/base`, and by the environment variables, which take precedence over the files.
+//! Every file value is mapped to the environment variable the same way as `zk config compile`
+//! does, e.g. `port` in the `[api.rest]` section becomes `API_REST_PORT`. Later files override
+//! the values of the earlier ones.
+
+// Built-in uses
+use std::{collections::HashMap, env, fs, io, path::Path};
+// External uses
+use thiserror::Error;
+use toml::{value::Table, Value};
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Cannot read config file {path}: {source}")]
+    Read { path: String, source: io::Error },
+    #[error("Cannot parse config file {path}: {source}")]
+    Parse {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error("Invalid value of `{name}`: {reason}")]
+    InvalidValue { name: &'static str, reason: String },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid(name: &'static str, reason: impl Into<String>) -> Self {
+        Self::InvalidValue {
+            name,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Loads the config file and returns the environment variables it corresponds to.
+pub fn file_variables(path: &Path) -> Result<HashMap<String, String>, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let table: Table = toml::from_str(&contents).map_err(|source| ConfigError::Parse {
+        path: path.display().to_string(),
+        source,
+    })?;
+
+    let mut variables = HashMap::new();
+    collect_variables("", &table, &mut variables);
+    Ok(variables)
+}
+
+/// Sets the environment variables from the config files unless they're already set,
+/// so the actual environment always takes precedence over the files.
+pub fn apply_config_files<P: AsRef<Path>>(paths: &[P]) -> Result<(), ConfigError> {
+    let mut variables = HashMap::new();
+    for path in paths {
+        variables.extend(file_variables(path.as_ref())?);
+    }
+
+    for (name, value) in variables {
+        if env::var_os(&name).is_none() {
+            env::set_var(name, value);
+        }
+    }
+    Ok(())
+}
+
+fn collect_variables(prefix: &str, table: &Table, variables: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let name = format!("{}{}", prefix, key.to_uppercase());
+        match value {
+            Value::Table(table) => collect_variables(&format!("{}_", name), table, variables),
+            value => {
+                variables.insert(name, value_to_string(value));
+            }
+        }
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_nested_variables() {
+        let config: Table = toml::from_str(
+            r#"
+            [api.rest]
+            port=3001
+            url="http://127.0.0.1:3001"
+
+            [chain.state_keeper]
+            block_chunk_sizes=[6,30]
+            fee_account_addr="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+
+            [db]
+            pruning_enabled=false
+        "#,
+        )
+        .unwrap();
+
+        let mut variables = HashMap::new();
+        collect_variables("", &config, &mut variables);
+
+        let expected: HashMap<String, String> = vec![
+            ("API_REST_PORT", "3001"),
+            ("API_REST_URL", "http://127.0.0.1:3001"),
+            ("CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES", "6,30"),
+            (
+                "CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR",
+                "0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7",
+            ),
+            ("DB_PRUNING_ENABLED", "false"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+        assert_eq!(variables, expected);
+    }
+}
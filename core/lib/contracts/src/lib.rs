@@ -11,8 +11,18 @@ const ZKSYNC_CONTRACT_FILE_V4: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/ZkSync.sol/ZkSync.json";
 const GOVERNANCE_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/Governance.sol/Governance.json";
+const TOKEN_GOVERNANCE_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/TokenGovernance.sol/TokenGovernance.json";
 const IERC20_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/IERC20.sol/IERC20.json";
+const IERC20_METADATA_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IERC20Metadata.sol/IERC20Metadata.json";
+const GAS_PRICE_ORACLE_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IGasPriceOracle.sol/IGasPriceOracle.json";
+const CHAINLINK_AGGREGATOR_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IChainlinkAggregator.sol/IChainlinkAggregator.json";
+const UNISWAP_V3_POOL_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IUniswapV3Pool.sol/IUniswapV3Pool.json";
 const IEIP1271_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/dev-contracts/IEIP1271.sol/IEIP1271.json";
 const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
@@ -80,6 +90,15 @@ pub fn governance_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("governance contract abi")
 }
 
+pub fn token_governance_contract() -> Contract {
+    let abi_string = read_file_to_json_value(TOKEN_GOVERNANCE_CONTRACT_FILE)
+        .expect("couldn't read TOKEN_GOVERNANCE_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from TOKEN_GOVERNANCE_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("token governance contract abi")
+}
+
 pub fn erc20_contract() -> Contract {
     let abi_string = read_file_to_json_value(IERC20_CONTRACT_FILE)
         .expect("couldn't read IERC20_CONTRACT_FILE")
@@ -89,6 +108,42 @@ pub fn erc20_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("erc20 contract abi")
 }
 
+pub fn erc20_metadata_contract() -> Contract {
+    let abi_string = read_file_to_json_value(IERC20_METADATA_CONTRACT_FILE)
+        .expect("couldn't read IERC20_METADATA_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from IERC20_METADATA_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("erc20 metadata contract abi")
+}
+
+pub fn gas_price_oracle_contract() -> Contract {
+    let abi_string = read_file_to_json_value(GAS_PRICE_ORACLE_CONTRACT_FILE)
+        .expect("couldn't read GAS_PRICE_ORACLE_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from GAS_PRICE_ORACLE_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("gas price oracle contract abi")
+}
+
+pub fn chainlink_aggregator_contract() -> Contract {
+    let abi_string = read_file_to_json_value(CHAINLINK_AGGREGATOR_CONTRACT_FILE)
+        .expect("couldn't read CHAINLINK_AGGREGATOR_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from CHAINLINK_AGGREGATOR_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("chainlink aggregator contract abi")
+}
+
+pub fn uniswap_v3_pool_contract() -> Contract {
+    let abi_string = read_file_to_json_value(UNISWAP_V3_POOL_CONTRACT_FILE)
+        .expect("couldn't read UNISWAP_V3_POOL_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from UNISWAP_V3_POOL_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("uniswap v3 pool contract abi")
+}
+
 pub fn eip1271_contract() -> Contract {
     let abi_string = read_file_to_json_value(IEIP1271_CONTRACT_FILE)
         .expect("couldn't read IEIP1271_CONTRACT_FILE")
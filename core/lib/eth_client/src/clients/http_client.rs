@@ -12,13 +12,13 @@ use web3::{
         Address, BlockId, BlockNumber, Bytes, Filter, Log, TransactionReceipt, H160, H256, U256,
         U64,
     },
-    Web3,
+    Transport, Web3,
 };
 
 // Workspace uses
 use zksync_eth_signer::{raw_ethereum_tx::RawTransaction, EthereumSigner};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
+use crate::ethereum_gateway::{Eip1559Fees, ExecutedTxStatus, FailureInfo, SignedCallResult};
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -118,6 +118,37 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    /// Returns the base fee of the latest block and the priority fee suggested by the node,
+    /// or `None` if the latest block has no base fee (network doesn't support EIP-1559 yet).
+    ///
+    /// Requests are sent as is, since the `web3` types don't know about EIP-1559 fields.
+    pub async fn get_eip1559_fees(&self) -> Result<Option<Eip1559Fees>, anyhow::Error> {
+        let start = Instant::now();
+        let transport = self.web3.transport();
+        let latest_block = transport
+            .execute(
+                "eth_getBlockByNumber",
+                vec![serde_json::json!("latest"), serde_json::json!(false)],
+            )
+            .await?;
+        let base_fee_per_gas = match latest_block.get("baseFeePerGas") {
+            Some(base_fee) if !base_fee.is_null() => {
+                serde_json::from_value::<U256>(base_fee.clone())?
+            }
+            _ => return Ok(None),
+        };
+        let max_priority_fee_per_gas = transport
+            .execute("eth_maxPriorityFeePerGas", Vec::new())
+            .await?;
+        let max_priority_fee_per_gas = serde_json::from_value::<U256>(max_priority_fee_per_gas)?;
+
+        metrics::histogram!("eth_client.direct.get_eip1559_fees", start.elapsed());
+        Ok(Some(Eip1559Fees {
+            base_fee_per_gas,
+            max_priority_fee_per_gas,
+        }))
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -353,6 +384,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     .saturating_sub(tx_block_number)
                     .as_u64();
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -365,6 +397,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
                 }))
             }
             _ => Ok(None),
@@ -11,7 +11,7 @@ use web3::types::{BlockId, Filter, Log, U64};
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
 use crate::{
-    ethereum_gateway::{ExecutedTxStatus, FailureInfo},
+    ethereum_gateway::{Eip1559Fees, ExecutedTxStatus, FailureInfo},
     SignedCallResult,
 };
 
@@ -20,6 +20,10 @@ use crate::{
 pub struct MockEthereum {
     pub block_number: u64,
     pub gas_price: U256,
+    /// EIP-1559 fees, the network is pre-London if not set.
+    pub eip1559_fees: Option<Eip1559Fees>,
+    /// Nonce of the sender account based on the last mined block.
+    pub current_nonce: U256,
     pub tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     pub sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
 }
@@ -29,6 +33,8 @@ impl Default for MockEthereum {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            eip1559_fees: None,
+            current_nonce: 0.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
         }
@@ -72,6 +78,7 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
         };
         self.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -84,6 +91,7 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
         };
         self.tx_statuses.write().await.insert(*hash, status);
     }
@@ -99,6 +107,10 @@ impl MockEthereum {
         Ok(self.gas_price)
     }
 
+    pub async fn get_eip1559_fees(&self) -> anyhow::Result<Option<Eip1559Fees>> {
+        Ok(self.eip1559_fees)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -144,7 +156,7 @@ impl MockEthereum {
     }
 
     pub async fn current_nonce(&self) -> Result<U256, Error> {
-        unreachable!()
+        Ok(self.current_nonce)
     }
 
     pub async fn sender_eth_balance(&self) -> Result<U256, Error> {
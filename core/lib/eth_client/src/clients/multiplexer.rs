@@ -1,3 +1,8 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
 use ethabi::Contract;
 use web3::{
     contract::tokens::{Detokenize, Tokenize},
@@ -8,12 +13,48 @@ use web3::{
 use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
 
-use crate::ethereum_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
+use crate::ethereum_gateway::{Eip1559Fees, ExecutedTxStatus, FailureInfo, SignedCallResult};
 use crate::ETHDirectClient;
 
+/// Default maximum response time after which the provider is considered degraded.
+const DEFAULT_MAX_LATENCY: Duration = Duration::from_secs(10);
+/// Default amount of time during which the degraded provider is not used if there are healthy ones.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Health state of the single Ethereum node provider.
+#[derive(Debug, Default)]
+struct ProviderHealth {
+    /// If set, provider is considered degraded until this moment.
+    degraded_until: Option<Instant>,
+}
+
+impl ProviderHealth {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.degraded_until
+            .map(|degraded_until| now >= degraded_until)
+            .unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Provider {
+    name: String,
+    client: ETHDirectClient<PrivateKeySigner>,
+    health: Arc<RwLock<ProviderHealth>>,
+}
+
+/// Ethereum client that interacts with an ordered list of Ethereum node providers.
+///
+/// Every request is sent to the first healthy provider in the list. If the provider
+/// can't be reached or its response takes too long, it is considered degraded and
+/// is moved to the end of the list for the cooldown period, so the next request will
+/// be served by the next provider. Degraded providers are still used if there are
+/// no healthy ones left.
 #[derive(Debug, Clone)]
 pub struct MultiplexerEthereumClient {
-    clients: Vec<(String, ETHDirectClient<PrivateKeySigner>)>,
+    clients: Vec<Provider>,
+    max_latency: Duration,
+    cooldown: Duration,
 }
 
 impl Default for MultiplexerEthereumClient {
@@ -24,20 +65,26 @@ impl Default for MultiplexerEthereumClient {
 
 macro_rules! multiple_call {
     ($self:expr, $func:ident($($attr:expr),+)) => {
-        for (name, client) in $self.clients.iter() {
-            match client.$func($($attr.clone()),+).await {
+        for provider in $self.ordered_providers() {
+            let start = Instant::now();
+            let result = provider.client.$func($($attr.clone()),+).await;
+            $self.report_response(provider, start.elapsed(), result.as_ref().err());
+            match result {
                 Ok(res) => return Ok(res),
-                Err(err) => vlog::error!("Error in interface: {}, {} ", name, err),
+                Err(err) => vlog::error!("Error in interface: {}, {} ", provider.name, err),
             }
         }
         anyhow::bail!("All interfaces was wrong please try again")
     };
 
     ($self:expr, $func:ident()) => {
-        for (name, client) in $self.clients.iter() {
-            match client.$func().await {
+        for provider in $self.ordered_providers() {
+            let start = Instant::now();
+            let result = provider.client.$func().await;
+            $self.report_response(provider, start.elapsed(), result.as_ref().err());
+            match result {
                 Ok(res) => return Ok(res),
-                Err(err) => vlog::error!("Error in interface: {}, {} ", name, err),
+                Err(err) => vlog::error!("Error in interface: {}, {} ", provider.name, err),
             }
         }
         anyhow::bail!("All interfaces was wrong please try again")
@@ -46,14 +93,80 @@ macro_rules! multiple_call {
 
 impl MultiplexerEthereumClient {
     pub fn new() -> Self {
-        Self { clients: vec![] }
+        Self {
+            clients: vec![],
+            max_latency: DEFAULT_MAX_LATENCY,
+            cooldown: DEFAULT_COOLDOWN,
+        }
     }
 
     pub fn add_client(mut self, name: String, client: ETHDirectClient<PrivateKeySigner>) -> Self {
-        self.clients.push((name, client));
+        self.clients.push(Provider {
+            name,
+            client,
+            health: Default::default(),
+        });
+        self
+    }
+
+    /// Sets the failover policy: maximum response time after which the provider is considered
+    /// degraded, and the time during which the degraded provider is not preferred.
+    pub fn with_failover_policy(mut self, max_latency: Duration, cooldown: Duration) -> Self {
+        self.max_latency = max_latency;
+        self.cooldown = cooldown;
         self
     }
 
+    /// Returns providers in the order they should be requested: healthy providers first,
+    /// then the degraded ones. The configured order is preserved within both groups.
+    fn ordered_providers(&self) -> Vec<&Provider> {
+        let now = Instant::now();
+        let (healthy, degraded): (Vec<_>, Vec<_>) = self
+            .clients
+            .iter()
+            .partition(|provider| provider.health.read().unwrap().is_healthy(now));
+
+        healthy.into_iter().chain(degraded).collect()
+    }
+
+    /// Updates the health of the provider according to the response and reports the metrics.
+    ///
+    /// Only the transport failures and slow responses degrade the provider: the logical errors
+    /// (e.g. reverted calls) would be returned by any other provider as well.
+    fn report_response(
+        &self,
+        provider: &Provider,
+        latency: Duration,
+        error: Option<&anyhow::Error>,
+    ) {
+        metrics::histogram!("eth_client.multiplexer.response_time", latency, "provider" => provider.name.clone());
+        if error.is_some() {
+            metrics::counter!("eth_client.multiplexer.errors", 1, "provider" => provider.name.clone());
+        }
+
+        let unreachable = error.map_or(false, is_transport_error);
+        let degraded = unreachable || latency > self.max_latency;
+        let mut health = provider.health.write().unwrap();
+        if degraded {
+            if health.is_healthy(Instant::now()) {
+                vlog::warn!(
+                    "Ethereum node provider {} is degraded (unreachable: {}, response time: {}ms), switching to the next one",
+                    provider.name,
+                    unreachable,
+                    latency.as_millis()
+                );
+            }
+            health.degraded_until = Some(Instant::now() + self.cooldown);
+        } else {
+            health.degraded_until = None;
+        }
+        metrics::gauge!(
+            "eth_client.multiplexer.provider_healthy",
+            if degraded { 0.0 } else { 1.0 },
+            "provider" => provider.name.clone()
+        );
+    }
+
     pub async fn pending_nonce(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, pending_nonce());
     }
@@ -70,6 +183,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_eip1559_fees(&self) -> Result<Option<Eip1559Fees>, anyhow::Error> {
+        multiple_call!(self, get_eip1559_fees());
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_eth_balance());
     }
@@ -179,7 +296,56 @@ impl MultiplexerEthereumClient {
     }
 
     pub fn encode_tx_data<P: Tokenize + Clone>(&self, func: &str, params: P) -> Vec<u8> {
-        let (_, client) = self.clients.first().expect("Should be exactly one client");
-        client.encode_tx_data(func, params)
+        let provider = self.clients.first().expect("Should be exactly one client");
+        provider.client.encode_tx_data(func, params)
+    }
+}
+
+/// Checks whether the request failed because the provider couldn't be reached or didn't respond
+/// properly, rather than because of the request itself.
+fn is_transport_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<web3::Error>(),
+            Some(web3::Error::Unreachable)
+                | Some(web3::Error::Transport(_))
+                | Some(web3::Error::Io(_))
+                | Some(web3::Error::InvalidResponse(_))
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_health() {
+        let now = Instant::now();
+        let mut health = ProviderHealth::default();
+        assert!(health.is_healthy(now));
+
+        health.degraded_until = Some(now + Duration::from_secs(1));
+        assert!(!health.is_healthy(now));
+        assert!(health.is_healthy(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn transport_errors() {
+        let transport_error = || web3::Error::Transport("connection refused".into());
+        assert!(is_transport_error(&transport_error().into()));
+        assert!(is_transport_error(&web3::Error::Unreachable.into()));
+        // Contract calls wrap the transport errors.
+        let contract_error = web3::contract::Error::Api(transport_error());
+        assert!(is_transport_error(&contract_error.into()));
+
+        // The logical errors are returned by any provider, so they don't degrade it.
+        let decoder_error = web3::Error::Decoder("invalid output".into());
+        assert!(!is_transport_error(&decoder_error.into()));
+        let abi_error = web3::contract::Error::InvalidOutputType("reverted".into());
+        assert!(!is_transport_error(&abi_error.into()));
+        assert!(!is_transport_error(&anyhow::format_err!(
+            "execution reverted"
+        )));
     }
 }
@@ -20,6 +20,15 @@ pub struct SignedCallResult {
     pub hash: H256,
 }
 
+/// Gas fees of the networks supporting EIP-1559 (London hard fork and later).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eip1559Fees {
+    /// Base fee of the latest block, which is burned.
+    pub base_fee_per_gas: U256,
+    /// Tip for the miner suggested by the node.
+    pub max_priority_fee_per_gas: U256,
+}
+
 /// State of the executed Ethereum transaction.
 #[derive(Debug, Clone)]
 pub struct ExecutedTxStatus {
@@ -30,6 +39,8 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Gas used by the transaction, if reported by the node.
+    pub gas_used: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -49,20 +60,36 @@ pub enum EthereumGateway {
 
 impl EthereumGateway {
     pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self::from_config_with_sender(
+            config,
+            config.eth_sender.sender.operator_commit_eth_addr,
+            config.eth_sender.sender.operator_private_key,
+        )
+    }
+
+    /// Creates the gateway sending transactions on behalf of the given account rather than the operator.
+    pub fn from_config_with_sender(
+        config: &ZkSyncConfig,
+        sender_address: Address,
+        sender_private_key: H256,
+    ) -> Self {
         if config.eth_client.web3_url.len() == 1 {
             let transport = web3::transports::Http::new(&config.eth_client.web3_url()).unwrap();
 
             EthereumGateway::Direct(ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                config.eth_sender.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                sender_address,
+                PrivateKeySigner::new(sender_private_key),
                 config.contracts.contract_addr,
                 config.eth_client.chain_id,
                 config.eth_client.gas_price_factor,
             ))
         } else {
-            let mut client = MultiplexerEthereumClient::new();
+            let mut client = MultiplexerEthereumClient::new().with_failover_policy(
+                config.eth_client.provider_max_latency(),
+                config.eth_client.provider_cooldown(),
+            );
 
             let contract = zksync_contract();
             for web3_url in config.eth_client.web3_url.iter() {
@@ -72,8 +99,8 @@ impl EthereumGateway {
                     ETHDirectClient::new(
                         transport,
                         contract.clone(),
-                        config.eth_sender.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                        sender_address,
+                        PrivateKeySigner::new(sender_private_key),
                         config.contracts.contract_addr,
                         config.eth_client.chain_id,
                         config.eth_client.gas_price_factor,
@@ -129,6 +156,12 @@ impl EthereumGateway {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the current base fee and the suggested priority fee,
+    /// or `None` if the network doesn't support EIP-1559 yet.
+    pub async fn get_eip1559_fees(&self) -> Result<Option<Eip1559Fees>, anyhow::Error> {
+        delegate_call!(self.get_eip1559_fees())
+    }
     /// Returns the account balance.
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_eth_balance())
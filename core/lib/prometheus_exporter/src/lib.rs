@@ -1,4 +1,13 @@
-//! This module handles metric export to the Prometheus server
+//! This module handles metric export to the Prometheus server.
+//!
+//! Every process of the server runs a single exporter, which serves all the metrics recorded
+//! via the `metrics` macros at `http://<host>:<API_PROMETHEUS_PORT>/metrics`.
+//!
+//! Metric names are `<component>.<name>`, where `<component>` is one of the following:
+//! `api`, `signature_checker`, `eth_checker`, `ticker`, `permit_relayer`, `forced_exit_requests`,
+//! `state_keeper`, `state`, `committer`, `mempool`, `eth_watcher`, `token_handler`, `eth_sender`,
+//! `eth_client`, `witness_generator` or `sql`. The storage metrics are named `sql.<schema>.<method>`. The exporter replaces dots
+//! with underscores, so e.g. `sql.chain.block.get_block` is exported as `sql_chain_block_get_block`.
 
 use metrics_exporter_prometheus::PrometheusBuilder;
 use std::time::Duration;
@@ -20,6 +29,10 @@ pub fn run_prometheus_exporter(
         .build_with_exporter()
         .expect("failed to install Prometheus recorder");
     metrics::set_boxed_recorder(Box::new(recorder)).expect("failed to set metrics recorder");
+    vlog::info!(
+        "Prometheus metrics are exported at http://0.0.0.0:{}/metrics",
+        port
+    );
 
     let prometheus_handle = tokio::spawn(async move {
         tokio::pin!(exporter);
@@ -51,7 +64,7 @@ pub fn run_prometheus_exporter(
                             .await
                             .expect("");
                         metrics::gauge!(
-                            "count_operations",
+                            "eth_sender.aggregated_operations",
                             result as f64,
                             "action" => action.to_string(),
                             "confirmed" => is_confirmed.to_string()
@@ -94,7 +94,7 @@ impl ZkSyncState {
     pub fn root_hash(&self) -> Fr {
         let start = std::time::Instant::now();
         let hash = self.balance_tree.root_hash();
-        metrics::histogram!("root_hash", start.elapsed());
+        metrics::histogram!("state.root_hash", start.elapsed());
         hash
     }
 
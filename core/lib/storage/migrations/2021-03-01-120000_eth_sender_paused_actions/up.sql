@@ -0,0 +1,6 @@
+-- Types of the L1 operations (e.g. `CommitBlocks`) which must not be sent by the `eth_sender`.
+CREATE TABLE eth_sender_paused_actions
+(
+    action_type TEXT                     NOT NULL PRIMARY KEY,
+    paused_at   TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS executed_transactions_archive;
+DROP TABLE IF EXISTS executed_priority_operations_archive;
+DROP TABLE IF EXISTS proofs_archive;
+DROP TABLE IF EXISTS aggregated_proofs_archive;
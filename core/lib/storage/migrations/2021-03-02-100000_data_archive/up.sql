@@ -0,0 +1,19 @@
+-- Archive tables for the data moved out of the main tables by the pruning job.
+-- Archived transactions and priority operations are still served by the API lookups,
+-- so the archive tables are indexed the same way as the main ones.
+CREATE TABLE executed_transactions_archive (LIKE executed_transactions);
+CREATE INDEX executed_transactions_archive_block_number_idx ON executed_transactions_archive (block_number);
+CREATE INDEX executed_transactions_archive_tx_hash_idx ON executed_transactions_archive USING hash (tx_hash);
+CREATE INDEX executed_transactions_archive_from_account_idx ON executed_transactions_archive (from_account);
+CREATE INDEX executed_transactions_archive_to_account_idx ON executed_transactions_archive (to_account);
+CREATE INDEX executed_transactions_archive_primary_account_address_idx ON executed_transactions_archive (primary_account_address);
+
+CREATE TABLE executed_priority_operations_archive (LIKE executed_priority_operations);
+CREATE INDEX executed_priority_operations_archive_block_number_idx ON executed_priority_operations_archive (block_number);
+CREATE INDEX executed_priority_operations_archive_serialid_idx ON executed_priority_operations_archive (priority_op_serialid);
+CREATE INDEX executed_priority_operations_archive_eth_hash_idx ON executed_priority_operations_archive USING hash (eth_hash);
+CREATE INDEX executed_priority_operations_archive_from_account_idx ON executed_priority_operations_archive (from_account);
+CREATE INDEX executed_priority_operations_archive_to_account_idx ON executed_priority_operations_archive (to_account);
+
+CREATE TABLE proofs_archive (LIKE proofs);
+CREATE TABLE aggregated_proofs_archive (LIKE aggregated_proofs);
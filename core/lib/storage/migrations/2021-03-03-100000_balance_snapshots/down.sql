@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS balance_snapshots;
+DROP TABLE IF EXISTS total_balance_snapshots;
+DROP TABLE IF EXISTS balance_snapshots_progress;
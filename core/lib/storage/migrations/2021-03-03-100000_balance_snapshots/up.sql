@@ -0,0 +1,28 @@
+-- Per-block snapshots of the account balances.
+-- Only the balances changed in the block are stored, so the balance of the account at the
+-- certain block is the latest snapshot with the block number not greater than the requested one.
+CREATE TABLE balance_snapshots (
+    block_number BIGINT NOT NULL,
+    account_id BIGINT NOT NULL,
+    coin_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    balance NUMERIC NOT NULL,
+    PRIMARY KEY (account_id, coin_id, block_number)
+);
+CREATE INDEX balance_snapshots_block_number_idx ON balance_snapshots (block_number);
+
+-- Per-block snapshots of the total amount of tokens locked in the network.
+-- Similarly to `balance_snapshots`, only the tokens which total balance changed in the block are stored.
+CREATE TABLE total_balance_snapshots (
+    block_number BIGINT NOT NULL,
+    coin_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    total_balance NUMERIC NOT NULL,
+    PRIMARY KEY (coin_id, block_number)
+);
+
+-- Last block for which the snapshots were populated.
+CREATE TABLE balance_snapshots_progress (
+    -- enforce single record
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    last_block BIGINT NOT NULL
+);
+INSERT INTO balance_snapshots_progress (last_block) VALUES (0);
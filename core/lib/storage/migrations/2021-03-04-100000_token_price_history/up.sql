@@ -0,0 +1,13 @@
+-- Token prices observed by the fee ticker.
+-- Unlike `ticker_price` which only holds the latest price, every observation is kept
+-- until it's removed according to the retention policy.
+CREATE TABLE token_price_history (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- Name of the API the price was received from.
+    source TEXT NOT NULL,
+    usd_price NUMERIC NOT NULL,
+    observed_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX token_price_history_token_id_observed_at_idx ON token_price_history (token_id, observed_at);
+CREATE INDEX token_price_history_observed_at_idx ON token_price_history (observed_at);
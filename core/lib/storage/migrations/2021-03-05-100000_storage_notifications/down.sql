@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS mempool_changed ON mempool_txs;
+DROP FUNCTION IF EXISTS notify_mempool_changed;
+DROP TRIGGER IF EXISTS pending_block_updated ON pending_block;
+DROP FUNCTION IF EXISTS notify_pending_block_updated;
+DROP TRIGGER IF EXISTS aggregate_operation_confirmed ON aggregate_operations;
+DROP FUNCTION IF EXISTS notify_aggregate_operation_confirmed;
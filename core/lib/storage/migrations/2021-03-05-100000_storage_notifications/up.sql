@@ -0,0 +1,47 @@
+-- Notifies listeners about the aggregated operations confirmed on Ethereum.
+CREATE OR REPLACE FUNCTION notify_aggregate_operation_confirmed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify(
+        'zksync_blocks_confirmed',
+        json_build_object(
+            'action_type', NEW.action_type,
+            'from_block', NEW.from_block,
+            'to_block', NEW.to_block
+        )::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER aggregate_operation_confirmed
+    AFTER UPDATE OF confirmed ON aggregate_operations
+    FOR EACH ROW
+    WHEN (NEW.confirmed AND NOT OLD.confirmed)
+    EXECUTE PROCEDURE notify_aggregate_operation_confirmed();
+
+-- Notifies listeners that the pending block has changed.
+-- Identical notifications within one transaction are delivered only once.
+CREATE OR REPLACE FUNCTION notify_pending_block_updated() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('zksync_pending_block_updated', '');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER pending_block_updated
+    AFTER INSERT OR UPDATE ON pending_block
+    FOR EACH STATEMENT
+    EXECUTE PROCEDURE notify_pending_block_updated();
+
+-- Notifies listeners that transactions were added to or removed from the mempool.
+CREATE OR REPLACE FUNCTION notify_mempool_changed() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('zksync_mempool_changed', '');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER mempool_changed
+    AFTER INSERT OR DELETE ON mempool_txs
+    FOR EACH STATEMENT
+    EXECUTE PROCEDURE notify_mempool_changed();
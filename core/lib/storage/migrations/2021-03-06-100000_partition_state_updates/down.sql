@@ -0,0 +1,70 @@
+ALTER TABLE account_balance_updates RENAME TO account_balance_updates_partitioned;
+ALTER INDEX account_balance_updates_block_index RENAME TO account_balance_updates_partitioned_block_index;
+CREATE TABLE account_balance_updates (
+    balance_update_id INTEGER NOT NULL DEFAULT nextval('account_balance_updates_balance_update_id_seq'),
+    account_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    coin_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    old_balance NUMERIC NOT NULL,
+    new_balance NUMERIC NOT NULL,
+    old_nonce BIGINT NOT NULL,
+    new_nonce BIGINT NOT NULL,
+    update_order_id INTEGER NOT NULL,
+    PRIMARY KEY (balance_update_id)
+);
+CREATE INDEX account_balance_updates_block_index ON account_balance_updates (block_number);
+ALTER SEQUENCE account_balance_updates_balance_update_id_seq OWNED BY account_balance_updates.balance_update_id;
+INSERT INTO account_balance_updates SELECT * FROM account_balance_updates_partitioned;
+DROP TABLE account_balance_updates_partitioned;
+
+ALTER TABLE account_pubkey_updates RENAME TO account_pubkey_updates_partitioned;
+ALTER INDEX account_pubkey_updates_block_index RENAME TO account_pubkey_updates_partitioned_block_index;
+CREATE TABLE account_pubkey_updates (
+    pubkey_update_id INTEGER NOT NULL DEFAULT nextval('account_pubkey_updates_pubkey_update_id_seq'),
+    update_order_id INTEGER NOT NULL,
+    account_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    old_pubkey_hash bytea NOT NULL,
+    new_pubkey_hash bytea NOT NULL,
+    old_nonce BIGINT NOT NULL,
+    new_nonce BIGINT NOT NULL,
+    PRIMARY KEY (pubkey_update_id)
+);
+CREATE INDEX account_pubkey_updates_block_index ON account_pubkey_updates (block_number);
+ALTER SEQUENCE account_pubkey_updates_pubkey_update_id_seq OWNED BY account_pubkey_updates.pubkey_update_id;
+INSERT INTO account_pubkey_updates SELECT * FROM account_pubkey_updates_partitioned;
+DROP TABLE account_pubkey_updates_partitioned;
+
+ALTER TABLE executed_transactions RENAME TO executed_transactions_partitioned;
+ALTER INDEX executed_transactions_pkey RENAME TO executed_transactions_partitioned_pkey;
+ALTER INDEX executed_transactions_block_number_index RENAME TO executed_transactions_partitioned_block_number_index;
+ALTER INDEX executed_transactions_hash_index RENAME TO executed_transactions_partitioned_hash_index;
+ALTER INDEX executed_transactions_from_account_idx RENAME TO executed_transactions_partitioned_from_account_idx;
+ALTER INDEX executed_transactions_to_account_idx RENAME TO executed_transactions_partitioned_to_account_idx;
+ALTER INDEX executed_transactions_primary_account_address_idx RENAME TO executed_transactions_partitioned_primary_account_address_idx;
+CREATE TABLE executed_transactions (
+    block_number BIGINT NOT NULL,
+    block_index INT,
+    tx jsonb NOT NULL,
+    operation jsonb NOT NULL,
+    tx_hash bytea NOT NULL,
+    from_account bytea NOT NULL,
+    to_account bytea,
+    success bool NOT NULL,
+    fail_reason TEXT,
+    primary_account_address bytea NOT NULL,
+    nonce BIGINT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL,
+    eth_sign_data JSONB,
+    batch_id BIGINT,
+    PRIMARY KEY (tx_hash)
+);
+CREATE INDEX executed_transactions_block_number_index ON executed_transactions (block_number);
+CREATE INDEX executed_transactions_hash_index ON executed_transactions (tx_hash);
+CREATE INDEX executed_transactions_from_account_idx ON executed_transactions USING hash (from_account);
+CREATE INDEX executed_transactions_to_account_idx ON executed_transactions USING hash (to_account);
+CREATE INDEX executed_transactions_primary_account_address_idx ON executed_transactions USING hash (primary_account_address);
+INSERT INTO executed_transactions SELECT * FROM executed_transactions_partitioned;
+DROP TABLE executed_transactions_partitioned;
+
+DROP FUNCTION IF EXISTS create_block_partitions;
@@ -0,0 +1,121 @@
+-- Account state updates and executed transactions are partitioned by the block number ranges,
+-- so the indices of the recent (most queried) blocks stay small regardless of the chain length.
+--
+-- The partition key has to be a part of the primary key, so the transaction hashes are not
+-- unique by the primary key anymore: the storage replaces the stored transaction with the same
+-- hash explicitly. `executed_priority_operations` is not partitioned, since its primary key
+-- (the serial ID) must stay unique across all the blocks.
+
+-- Creates the partitions of the block data tables for the blocks range containing the given
+-- block, if they don't exist yet. Returns the index of the partition.
+CREATE OR REPLACE FUNCTION create_block_partitions(block BIGINT) RETURNS BIGINT AS $$
+DECLARE
+    partition_size CONSTANT BIGINT := 100000;
+    partition_idx BIGINT := block / partition_size;
+    range_start BIGINT := partition_idx * partition_size;
+    range_end BIGINT := range_start + partition_size;
+    parent TEXT;
+BEGIN
+    FOREACH parent IN ARRAY ARRAY['account_balance_updates', 'account_pubkey_updates', 'executed_transactions'] LOOP
+        IF to_regclass(parent || '_p' || partition_idx) IS NULL THEN
+            EXECUTE format(
+                'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%s) TO (%s)',
+                parent || '_p' || partition_idx, parent, range_start, range_end
+            );
+        END IF;
+    END LOOP;
+    RETURN partition_idx;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE account_balance_updates RENAME TO account_balance_updates_unpartitioned;
+ALTER INDEX account_balance_updates_block_index RENAME TO account_balance_updates_unpartitioned_block_index;
+CREATE TABLE account_balance_updates (
+    balance_update_id INTEGER NOT NULL DEFAULT nextval('account_balance_updates_balance_update_id_seq'),
+    account_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    coin_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    old_balance NUMERIC NOT NULL,
+    new_balance NUMERIC NOT NULL,
+    old_nonce BIGINT NOT NULL,
+    new_nonce BIGINT NOT NULL,
+    update_order_id INTEGER NOT NULL,
+    PRIMARY KEY (balance_update_id, block_number)
+) PARTITION BY RANGE (block_number);
+CREATE INDEX account_balance_updates_block_index ON account_balance_updates (block_number);
+ALTER SEQUENCE account_balance_updates_balance_update_id_seq OWNED BY account_balance_updates.balance_update_id;
+
+ALTER TABLE account_pubkey_updates RENAME TO account_pubkey_updates_unpartitioned;
+ALTER INDEX account_pubkey_updates_block_index RENAME TO account_pubkey_updates_unpartitioned_block_index;
+CREATE TABLE account_pubkey_updates (
+    pubkey_update_id INTEGER NOT NULL DEFAULT nextval('account_pubkey_updates_pubkey_update_id_seq'),
+    update_order_id INTEGER NOT NULL,
+    account_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    old_pubkey_hash bytea NOT NULL,
+    new_pubkey_hash bytea NOT NULL,
+    old_nonce BIGINT NOT NULL,
+    new_nonce BIGINT NOT NULL,
+    PRIMARY KEY (pubkey_update_id, block_number)
+) PARTITION BY RANGE (block_number);
+CREATE INDEX account_pubkey_updates_block_index ON account_pubkey_updates (block_number);
+ALTER SEQUENCE account_pubkey_updates_pubkey_update_id_seq OWNED BY account_pubkey_updates.pubkey_update_id;
+
+ALTER TABLE executed_transactions RENAME TO executed_transactions_unpartitioned;
+ALTER INDEX executed_transactions_pkey RENAME TO executed_transactions_unpartitioned_pkey;
+ALTER INDEX executed_transactions_block_number_index RENAME TO executed_transactions_unpartitioned_block_number_index;
+ALTER INDEX executed_transactions_hash_index RENAME TO executed_transactions_unpartitioned_hash_index;
+ALTER INDEX executed_transactions_from_account_idx RENAME TO executed_transactions_unpartitioned_from_account_idx;
+ALTER INDEX executed_transactions_to_account_idx RENAME TO executed_transactions_unpartitioned_to_account_idx;
+ALTER INDEX executed_transactions_primary_account_address_idx RENAME TO executed_transactions_unpartitioned_primary_account_address_idx;
+CREATE TABLE executed_transactions (
+    block_number BIGINT NOT NULL,
+    block_index INT,
+    tx jsonb NOT NULL,
+    operation jsonb NOT NULL,
+    tx_hash bytea NOT NULL,
+    from_account bytea NOT NULL,
+    to_account bytea,
+    success bool NOT NULL,
+    fail_reason TEXT,
+    primary_account_address bytea NOT NULL,
+    nonce BIGINT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL,
+    eth_sign_data JSONB,
+    batch_id BIGINT,
+    PRIMARY KEY (tx_hash, block_number)
+) PARTITION BY RANGE (block_number);
+CREATE INDEX executed_transactions_block_number_index ON executed_transactions (block_number);
+CREATE INDEX executed_transactions_hash_index ON executed_transactions (tx_hash);
+CREATE INDEX executed_transactions_from_account_idx ON executed_transactions USING hash (from_account);
+CREATE INDEX executed_transactions_to_account_idx ON executed_transactions USING hash (to_account);
+CREATE INDEX executed_transactions_primary_account_address_idx ON executed_transactions USING hash (primary_account_address);
+
+-- Create partitions for the already stored blocks and move the data.
+DO $$
+DECLARE
+    last_block BIGINT := GREATEST(
+        (SELECT COALESCE(max(block_number), 0) FROM account_balance_updates_unpartitioned),
+        (SELECT COALESCE(max(block_number), 0) FROM account_pubkey_updates_unpartitioned),
+        (SELECT COALESCE(max(block_number), 0) FROM executed_transactions_unpartitioned)
+    );
+    block BIGINT := 0;
+BEGIN
+    WHILE block <= last_block LOOP
+        PERFORM create_block_partitions(block);
+        block := block + 100000;
+    END LOOP;
+END;
+$$;
+
+INSERT INTO account_balance_updates (balance_update_id, account_id, block_number, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id)
+    SELECT balance_update_id, account_id, block_number, coin_id, old_balance, new_balance, old_nonce, new_nonce, update_order_id
+    FROM account_balance_updates_unpartitioned;
+INSERT INTO account_pubkey_updates (pubkey_update_id, update_order_id, account_id, block_number, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce)
+    SELECT pubkey_update_id, update_order_id, account_id, block_number, old_pubkey_hash, new_pubkey_hash, old_nonce, new_nonce
+    FROM account_pubkey_updates_unpartitioned;
+INSERT INTO executed_transactions SELECT * FROM executed_transactions_unpartitioned;
+
+DROP TABLE account_balance_updates_unpartitioned;
+DROP TABLE account_pubkey_updates_unpartitioned;
+DROP TABLE executed_transactions_unpartitioned;
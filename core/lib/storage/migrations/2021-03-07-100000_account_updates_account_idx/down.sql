@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS account_balance_updates_account_id_block_number_idx;
+DROP INDEX IF EXISTS account_pubkey_updates_account_id_block_number_idx;
@@ -0,0 +1,3 @@
+-- Allows to load the whole history of the account updates without scanning all the blocks.
+CREATE INDEX account_balance_updates_account_id_block_number_idx ON account_balance_updates (account_id, block_number);
+CREATE INDEX account_pubkey_updates_account_id_block_number_idx ON account_pubkey_updates (account_id, block_number);
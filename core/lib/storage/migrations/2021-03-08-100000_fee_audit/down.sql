@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_overpayments;
+DROP TABLE IF EXISTS fee_quotes;
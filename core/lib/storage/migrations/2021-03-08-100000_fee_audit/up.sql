@@ -0,0 +1,30 @@
+-- Fee quotes returned by the fee ticker. Kept for the auditing purposes
+-- until they're removed according to the retention policy.
+CREATE TABLE fee_quotes (
+    id BIGSERIAL PRIMARY KEY,
+    -- Serialized `OutputFeeType`.
+    fee_type jsonb NOT NULL,
+    -- Address the fee was requested for.
+    address bytea NOT NULL,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    gas_tx_amount NUMERIC NOT NULL,
+    gas_price_wei NUMERIC NOT NULL,
+    gas_fee NUMERIC NOT NULL,
+    zkp_fee NUMERIC NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    quoted_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX fee_quotes_quoted_at_idx ON fee_quotes (quoted_at);
+CREATE INDEX fee_quotes_address_idx ON fee_quotes USING hash (address);
+
+-- Transactions which paid more fee than it was quoted.
+CREATE TABLE fee_overpayments (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash bytea NOT NULL,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    quoted_fee NUMERIC NOT NULL,
+    paid_fee NUMERIC NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX fee_overpayments_recorded_at_idx ON fee_overpayments (recorded_at);
+CREATE INDEX fee_overpayments_tx_hash_idx ON fee_overpayments USING hash (tx_hash);
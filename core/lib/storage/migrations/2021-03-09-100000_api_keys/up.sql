@@ -0,0 +1,30 @@
+-- Keys used to authenticate the API clients.
+-- Only the hash of the key is stored, so the key itself can't be recovered from the database.
+CREATE TABLE api_keys (
+    id BIGSERIAL PRIMARY KEY,
+    key_hash bytea NOT NULL UNIQUE,
+    -- Human-readable name of the key owner.
+    name TEXT NOT NULL,
+    -- Maximum amount of requests per quota period, `NULL` for unlimited keys.
+    requests_quota BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    revoked_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Amount of requests performed with the key within the quota period.
+CREATE TABLE api_key_usage (
+    api_key_id BIGINT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    period_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    requests_count BIGINT NOT NULL,
+    PRIMARY KEY (api_key_id, period_start)
+);
+
+-- Endpoints notified about the events the key owner is subscribed to.
+CREATE TABLE webhooks (
+    id BIGSERIAL PRIMARY KEY,
+    api_key_id BIGINT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    event_types TEXT[] NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX webhooks_api_key_id_idx ON webhooks (api_key_id);
@@ -0,0 +1,19 @@
+-- Deposits authorized by the EIP-2612 permit signature, which are sent to L1 by the relayer.
+CREATE TABLE permit_deposits (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL,
+    -- Owner of the deposited tokens, which is also the recipient of the deposit on L2.
+    owner bytea NOT NULL,
+    amount NUMERIC NOT NULL,
+    -- Fee paid to the relayer in the deposited token.
+    fee NUMERIC NOT NULL,
+    -- Deadline of the permit signature (unix timestamp in seconds).
+    deadline BIGINT NOT NULL,
+    signature bytea NOT NULL,
+    -- One of `pending`, `sent` or `failed`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    eth_tx_hash bytea,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX permit_deposits_status_idx ON permit_deposits (status);
@@ -0,0 +1,20 @@
+-- Paid requests to withdraw the funds of the account which signing key is not set.
+CREATE TABLE forced_exit_requests (
+    id BIGSERIAL PRIMARY KEY,
+    -- Account which funds are withdrawn to L1.
+    target bytea NOT NULL,
+    tokens INTEGER[] NOT NULL,
+    -- Account expected to pay for the request by the L2 transfer to the funding account.
+    requester bytea NOT NULL,
+    -- Price of the request in ETH.
+    price_in_wei NUMERIC NOT NULL,
+    -- One of `pending`, `paid`, `fulfilled` or `expired`.
+    status TEXT NOT NULL DEFAULT 'pending',
+    valid_until TIMESTAMP WITH TIME ZONE NOT NULL,
+    payment_tx_hash bytea UNIQUE,
+    -- Hashes of the `ForcedExit` transactions submitted for the request.
+    fulfilled_by TEXT[],
+    fulfilled_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX forced_exit_requests_status_idx ON forced_exit_requests (status);
@@ -0,0 +1,8 @@
+-- Progress of forwarding the block events to the external message broker.
+CREATE TABLE event_forwarder_state (
+    -- enforce single record
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    CONSTRAINT single_event_forwarder_state CHECK (id),
+    -- Last block which events were acknowledged by the broker.
+    last_forwarded_block BIGINT NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Name of the Ethereum network the database belongs to, e.g. `mainnet` or `rinkeby`.
+-- Set by the server on the first start, and checked on every next one.
+ALTER TABLE server_config ADD COLUMN network TEXT;
@@ -0,0 +1,7 @@
+-- Risk factors the fee paid in the token is multiplied by.
+-- Tokens without the record have the risk factor of 1.
+CREATE TABLE token_risk_factors (
+    token_id INTEGER PRIMARY KEY NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    risk_factor NUMERIC NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
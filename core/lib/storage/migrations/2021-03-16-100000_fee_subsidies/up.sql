@@ -0,0 +1,13 @@
+-- Subsidies of the fee ticker: while the subsidy is active, the gas part of the fee
+-- is calculated using the subsidized operation costs.
+CREATE TABLE fee_subsidies (
+    id SERIAL PRIMARY KEY,
+    -- Token the fee is paid in, the subsidy applies to all the tokens if not set.
+    token_id INTEGER REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- Fee type (`OutputFeeType` in JSON), the subsidy applies to all the fee types if not set.
+    fee_type jsonb,
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- The subsidy doesn't expire if not set.
+    ends_at TIMESTAMP WITH TIME ZONE,
+    enabled BOOLEAN NOT NULL DEFAULT true
+);
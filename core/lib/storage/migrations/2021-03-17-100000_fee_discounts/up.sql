@@ -0,0 +1,13 @@
+-- Fee discounts granted to the specific accounts, e.g. to the market makers.
+CREATE TABLE fee_discounts (
+    id SERIAL PRIMARY KEY,
+    -- Account sending the transaction.
+    address bytea NOT NULL,
+    -- Fee type (`OutputFeeType` in JSON), the discount applies to all the fee types if not set.
+    fee_type jsonb,
+    -- Share of the fee the account doesn't pay, from 0 to 1.
+    discount NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX fee_discounts_address_idx ON fee_discounts (address);
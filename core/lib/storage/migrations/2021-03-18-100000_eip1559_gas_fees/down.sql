@@ -0,0 +1,2 @@
+ALTER TABLE eth_parameters DROP COLUMN max_priority_fee_per_gas;
+ALTER TABLE eth_parameters DROP COLUMN base_fee_per_gas;
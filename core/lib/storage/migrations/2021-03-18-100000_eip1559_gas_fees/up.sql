@@ -0,0 +1,2 @@
+ALTER TABLE eth_parameters ADD base_fee_per_gas BIGINT;
+ALTER TABLE eth_parameters ADD max_priority_fee_per_gas BIGINT;
@@ -0,0 +1,14 @@
+-- Fee waivers for the specific operations, e.g. the first `ChangePubKey` of the account
+-- or the withdrawals of the delisted token.
+CREATE TABLE fee_waivers (
+    id SERIAL PRIMARY KEY,
+    -- Fee type (`OutputFeeType` in JSON).
+    fee_type jsonb NOT NULL,
+    -- Token the fee is paid in, the waiver applies to all the tokens if not set.
+    token_id INTEGER,
+    -- The waiver only applies to the accounts which signing key is not set yet.
+    unset_signing_key_only BOOLEAN NOT NULL DEFAULT false,
+    -- The waiver doesn't expire if not set.
+    expires_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
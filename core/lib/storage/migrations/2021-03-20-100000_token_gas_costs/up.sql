@@ -0,0 +1,10 @@
+-- Gas costs of the operations paid in the specific tokens, e.g. the fee-on-transfer tokens
+-- which cost more gas to withdraw. Override both the standard and the subsidized gas costs.
+CREATE TABLE token_gas_costs (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- Fee type (`OutputFeeType` in JSON).
+    fee_type jsonb NOT NULL,
+    gas_cost NUMERIC NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (token_id, fee_type)
+);
@@ -0,0 +1,14 @@
+-- Subsidies spent on the transactions: the difference between the standard fee
+-- and the subsidized fee the transaction was accepted with, in the token the fee is paid in.
+-- The subsidy of the batch is paid in ETH and is recorded for the first transaction of the batch.
+CREATE TABLE fee_subsidy_spends (
+    tx_hash bytea PRIMARY KEY,
+    token_id INTEGER NOT NULL,
+    -- Fee type (`TxFeeTypes` in JSON, or the array of them for the batch).
+    fee_type jsonb NOT NULL,
+    -- Subsidized fee required from the transaction.
+    paid_fee NUMERIC NOT NULL,
+    subsidy NUMERIC NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX fee_subsidy_spends_recorded_at_idx ON fee_subsidy_spends (recorded_at);
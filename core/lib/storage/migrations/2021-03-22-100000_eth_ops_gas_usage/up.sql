@@ -0,0 +1,14 @@
+-- Gas actually used by the confirmed L1 transactions, attributed to the operations of the blocks
+-- in proportion to their estimated gas costs. Used to learn the gas costs of the operations.
+CREATE TABLE eth_ops_gas_usage (
+    block_number BIGINT NOT NULL,
+    -- Aggregated action the gas was used by (`AggregatedActionType`).
+    action_type TEXT NOT NULL,
+    -- Type of the zkSync operations, e.g. `Transfer` or `ChangePubKeyECDSA`.
+    op_type TEXT NOT NULL,
+    ops_count BIGINT NOT NULL,
+    gas_used BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (block_number, action_type, op_type)
+);
+CREATE INDEX eth_ops_gas_usage_created_at_idx ON eth_ops_gas_usage (created_at);
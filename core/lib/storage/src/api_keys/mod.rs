@@ -0,0 +1,214 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{StoredApiKey, StoredWebhook};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// API keys schema stores the keys used to authenticate the API clients,
+/// the amount of requests performed with every key and the webhooks registered by the key owners.
+///
+/// Keys themselves are never stored, the schema works with the key hashes only.
+#[derive(Debug)]
+pub struct ApiKeysSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ApiKeysSchema<'a, 'c> {
+    /// Stores the new API key. `requests_quota` set to `None` means that the key is not limited.
+    pub async fn store_api_key(
+        &mut self,
+        key_hash: &[u8],
+        name: &str,
+        requests_quota: Option<i64>,
+    ) -> QueryResult<StoredApiKey> {
+        let start = Instant::now();
+        let api_key = sqlx::query_as!(
+            StoredApiKey,
+            "INSERT INTO api_keys (key_hash, name, requests_quota)
+            VALUES ($1, $2, $3)
+            RETURNING *",
+            key_hash,
+            name,
+            requests_quota
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.store_api_key", start.elapsed());
+        Ok(api_key)
+    }
+
+    /// Loads the API key by its hash. Revoked keys are not returned.
+    pub async fn get_api_key(&mut self, key_hash: &[u8]) -> QueryResult<Option<StoredApiKey>> {
+        let start = Instant::now();
+        let api_key = sqlx::query_as!(
+            StoredApiKey,
+            "SELECT * FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+            key_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.get_api_key", start.elapsed());
+        Ok(api_key)
+    }
+
+    /// Revokes the API key. Returns `false` if there is no such active key.
+    pub async fn revoke_api_key(&mut self, api_key_id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let revoked = sqlx::query!(
+            "UPDATE api_keys SET revoked_at = now() WHERE id = $1 AND revoked_at IS NULL",
+            api_key_id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.api_keys.revoke_api_key", start.elapsed());
+        Ok(revoked > 0)
+    }
+
+    /// Increases the amount of requests performed with the key within the quota period
+    /// starting at `period_start`. Returns the updated amount of requests.
+    pub async fn increment_usage(
+        &mut self,
+        api_key_id: i64,
+        period_start: DateTime<Utc>,
+        requests: i64,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let requests_count = sqlx::query!(
+            "INSERT INTO api_key_usage (api_key_id, period_start, requests_count)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (api_key_id, period_start)
+            DO UPDATE SET requests_count = api_key_usage.requests_count + EXCLUDED.requests_count
+            RETURNING requests_count",
+            api_key_id,
+            period_start,
+            requests
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .requests_count;
+
+        metrics::histogram!("sql.api_keys.increment_usage", start.elapsed());
+        Ok(requests_count)
+    }
+
+    /// Loads the amount of requests performed with the key within the quota period
+    /// starting at `period_start`.
+    pub async fn get_usage(
+        &mut self,
+        api_key_id: i64,
+        period_start: DateTime<Utc>,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let requests_count = sqlx::query!(
+            "SELECT requests_count FROM api_key_usage
+            WHERE api_key_id = $1 AND period_start = $2",
+            api_key_id,
+            period_start
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| row.requests_count)
+        .unwrap_or_default();
+
+        metrics::histogram!("sql.api_keys.get_usage", start.elapsed());
+        Ok(requests_count)
+    }
+
+    /// Removes the usage counters of the quota periods started before the given timestamp.
+    /// Returns the amount of removed counters.
+    pub async fn remove_usage_before(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM api_key_usage WHERE period_start < $1", before)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        metrics::histogram!("sql.api_keys.remove_usage_before", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Registers the webhook which should be notified about the events of the given types.
+    pub async fn store_webhook(
+        &mut self,
+        api_key_id: i64,
+        url: &str,
+        event_types: &[String],
+    ) -> QueryResult<StoredWebhook> {
+        let start = Instant::now();
+        let webhook = sqlx::query_as!(
+            StoredWebhook,
+            "INSERT INTO webhooks (api_key_id, url, event_types)
+            VALUES ($1, $2, $3)
+            RETURNING *",
+            api_key_id,
+            url,
+            event_types
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.store_webhook", start.elapsed());
+        Ok(webhook)
+    }
+
+    /// Loads all the webhooks registered with the API key.
+    pub async fn load_webhooks(&mut self, api_key_id: i64) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT * FROM webhooks WHERE api_key_id = $1 ORDER BY id",
+            api_key_id
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.load_webhooks", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Loads the webhooks subscribed to the given event type.
+    /// Webhooks registered with the revoked keys are not returned.
+    pub async fn load_event_webhooks(
+        &mut self,
+        event_type: &str,
+    ) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+        let webhooks = sqlx::query_as!(
+            StoredWebhook,
+            "SELECT webhooks.* FROM webhooks
+            INNER JOIN api_keys ON api_keys.id = webhooks.api_key_id
+            WHERE $1 = ANY(webhooks.event_types) AND api_keys.revoked_at IS NULL
+            ORDER BY webhooks.id",
+            event_type
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.api_keys.load_event_webhooks", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Removes the webhook registered with the API key.
+    /// Returns `false` if the key has no such webhook.
+    pub async fn remove_webhook(&mut self, api_key_id: i64, webhook_id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM webhooks WHERE id = $1 AND api_key_id = $2",
+            webhook_id,
+            api_key_id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.api_keys.remove_webhook", start.elapsed());
+        Ok(removed > 0)
+    }
+}
@@ -3,7 +3,7 @@ use std::time::Instant;
 // External imports
 use sqlx::Acquire;
 // Workspace imports
-use zksync_types::{Account, AccountId, AccountUpdates, Address};
+use zksync_types::{Account, AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber};
 // Local imports
 use self::records::*;
 use crate::diff::StorageAccountDiff;
@@ -209,6 +209,89 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(account_state)
     }
 
+    /// Loads the state of the account as of the given block, i.e. the state obtained once all
+    /// the updates from this block are applied.
+    ///
+    /// The state is restored by replaying all the updates of the account up to the given block,
+    /// thus it's available for any block for which the state updates are committed.
+    /// Returns `None` if the account didn't exist at that moment.
+    pub async fn account_state_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<Account>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = $1 AND block_number <= $2
+            ",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        let mut account_diff: Vec<StorageAccountDiff> = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        // Replay the whole history of the account starting from its creation.
+        let account_state = account_diff
+            .into_iter()
+            .map(|diff| {
+                let (_, update): (AccountId, AccountUpdate) = diff.into();
+                update
+            })
+            .fold(None, Account::apply_update);
+
+        metrics::histogram!("sql.chain.account.account_state_at_block", start.elapsed());
+        Ok(account_state)
+    }
+
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
     /// which was both committed and verified).
     pub async fn last_verified_state_for_account(
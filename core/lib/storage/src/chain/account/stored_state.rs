@@ -1,6 +1,6 @@
 use zksync_types::{Account, AccountId};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StoredAccountState {
     pub committed: Option<(AccountId, Account)>,
     pub verified: Option<(AccountId, Account)>,
@@ -0,0 +1,181 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{AccountId, BlockNumber, TokenId};
+// Local imports
+use self::records::{StorageBalanceSnapshot, StorageTotalBalanceSnapshot};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Balance snapshots schema stores the per-block balances of the accounts as well as the
+/// total amount of every token locked in the network (TVL).
+///
+/// Snapshots are derived from the balance updates stored on the block commit, and are
+/// populated strictly in the order of blocks, since the total balances are calculated
+/// incrementally. Only the balances changed in a block are stored, so the state as of the
+/// certain block is represented by the latest snapshots not newer than this block.
+#[derive(Debug)]
+pub struct BalanceSnapshotsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> BalanceSnapshotsSchema<'a, 'c> {
+    /// Returns the number of the last block for which the snapshots were populated.
+    pub async fn last_populated_block(&mut self) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let last_block = sqlx::query!("SELECT last_block FROM balance_snapshots_progress")
+            .fetch_one(self.0.conn())
+            .await?
+            .last_block;
+
+        metrics::histogram!(
+            "sql.chain.balance_snapshots.last_populated_block",
+            start.elapsed()
+        );
+        Ok(BlockNumber(last_block as u32))
+    }
+
+    /// Stores the snapshots of the balances changed in the given block.
+    /// The snapshots of the previous block must be already populated.
+    pub async fn populate_block(&mut self, block_number: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let last_populated_block = BalanceSnapshotsSchema(&mut transaction)
+            .last_populated_block()
+            .await?;
+        anyhow::ensure!(
+            *block_number == *last_populated_block + 1,
+            "Balance snapshots must be populated in order: last populated block is {}, got {}",
+            *last_populated_block,
+            *block_number
+        );
+
+        // The latest update of every balance within the block contains its resulting value.
+        sqlx::query!(
+            "INSERT INTO balance_snapshots (block_number, account_id, coin_id, balance)
+            SELECT DISTINCT ON (account_id, coin_id) block_number, account_id, coin_id, new_balance
+            FROM account_balance_updates
+            WHERE block_number = $1
+            ORDER BY account_id, coin_id, update_order_id DESC",
+            i64::from(*block_number)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO total_balance_snapshots (block_number, coin_id, total_balance)
+            SELECT $1, diff.coin_id, COALESCE(prev.total_balance, 0) + diff.change
+            FROM (
+                SELECT coin_id, SUM(new_balance - old_balance) AS change
+                FROM account_balance_updates
+                WHERE block_number = $1
+                GROUP BY coin_id
+            ) diff
+            LEFT JOIN LATERAL (
+                SELECT total_balance FROM total_balance_snapshots
+                WHERE total_balance_snapshots.coin_id = diff.coin_id AND block_number < $1
+                ORDER BY block_number DESC
+                LIMIT 1
+            ) prev ON true",
+            i64::from(*block_number)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "UPDATE balance_snapshots_progress SET last_block = $1",
+            i64::from(*block_number)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.balance_snapshots.populate_block",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Loads the balances of the account in all the tokens as of the given block.
+    pub async fn account_balances_at_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<StorageBalanceSnapshot>> {
+        let start = Instant::now();
+        let balances = sqlx::query_as!(
+            StorageBalanceSnapshot,
+            "SELECT DISTINCT ON (coin_id) block_number, account_id, coin_id, balance
+            FROM balance_snapshots
+            WHERE account_id = $1 AND block_number <= $2
+            ORDER BY coin_id, block_number DESC",
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.balance_snapshots.account_balances_at_block",
+            start.elapsed()
+        );
+        Ok(balances)
+    }
+
+    /// Loads the total amount of every token locked in the network as of the given block.
+    pub async fn total_balances_at_block(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<StorageTotalBalanceSnapshot>> {
+        let start = Instant::now();
+        let balances = sqlx::query_as!(
+            StorageTotalBalanceSnapshot,
+            "SELECT DISTINCT ON (coin_id) block_number, coin_id, total_balance
+            FROM total_balance_snapshots
+            WHERE block_number <= $1
+            ORDER BY coin_id, block_number DESC",
+            i64::from(*block_number)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.balance_snapshots.total_balances_at_block",
+            start.elapsed()
+        );
+        Ok(balances)
+    }
+
+    /// Loads the history of the total amount of the token locked in the network
+    /// for the blocks in the range `[from_block, to_block]`.
+    /// Only the blocks where the total amount has changed are returned.
+    pub async fn total_balance_history(
+        &mut self,
+        token_id: TokenId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<StorageTotalBalanceSnapshot>> {
+        let start = Instant::now();
+        let history = sqlx::query_as!(
+            StorageTotalBalanceSnapshot,
+            "SELECT block_number, coin_id, total_balance
+            FROM total_balance_snapshots
+            WHERE coin_id = $1 AND block_number >= $2 AND block_number <= $3
+            ORDER BY block_number",
+            i32::from(*token_id),
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.balance_snapshots.total_balance_history",
+            start.elapsed()
+        );
+        Ok(history)
+    }
+}
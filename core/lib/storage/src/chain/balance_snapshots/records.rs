@@ -0,0 +1,21 @@
+// External imports
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Balance of the account in a certain token as of the certain block.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StorageBalanceSnapshot {
+    pub block_number: i64,
+    pub account_id: i64,
+    pub coin_id: i32,
+    pub balance: BigDecimal,
+}
+
+/// Total amount of a certain token locked in the network as of the certain block.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StorageTotalBalanceSnapshot {
+    pub block_number: i64,
+    pub coin_id: i32,
+    pub total_balance: BigDecimal,
+}
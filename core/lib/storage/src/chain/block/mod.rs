@@ -11,16 +11,21 @@ use zksync_types::{
 };
 // Local imports
 use self::records::{
-    AccountTreeCache, BlockDetails, BlockTransactionItem, StorageBlock, StoragePendingBlock,
+    AccountTreeCache, BlockDetails, BlockExport, BlockTransactionItem, StorageBlock,
+    StoragePendingBlock,
 };
 use crate::{
-    chain::operations::{
-        records::{
-            NewExecutedPriorityOperation, NewExecutedTransaction, StoredExecutedPriorityOperation,
-            StoredExecutedTransaction,
+    chain::{
+        operations::{
+            records::{
+                NewExecutedPriorityOperation, NewExecutedTransaction,
+                StoredExecutedPriorityOperation, StoredExecutedTransaction,
+            },
+            OperationsSchema,
         },
-        OperationsSchema,
+        state::StateSchema,
     },
+    prover::ProverSchema,
     QueryResult, StorageProcessor,
 };
 
@@ -42,25 +47,36 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         operations: Vec<ExecutedOperations>,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let mut executed_txs = Vec::new();
+        let mut executed_priority_ops = Vec::new();
         for block_tx in operations.into_iter() {
             match block_tx {
                 ExecutedOperations::Tx(tx) => {
-                    // Store the executed operation in the corresponding schema.
-                    let new_tx = NewExecutedTransaction::prepare_stored_tx(*tx, block_number);
-                    OperationsSchema(self.0).store_executed_tx(new_tx).await?;
+                    executed_txs.push(NewExecutedTransaction::prepare_stored_tx(*tx, block_number));
                 }
                 ExecutedOperations::PriorityOp(prior_op) => {
-                    // For priority operation we should only store it in the Operations schema.
-                    let new_priority_op = NewExecutedPriorityOperation::prepare_stored_priority_op(
-                        *prior_op,
-                        block_number,
+                    executed_priority_ops.push(
+                        NewExecutedPriorityOperation::prepare_stored_priority_op(
+                            *prior_op,
+                            block_number,
+                        ),
                     );
-                    OperationsSchema(self.0)
-                        .store_executed_priority_op(new_priority_op)
-                        .await?;
                 }
             }
         }
+
+        // Operations are stored in bulk, since the row-by-row insertion takes
+        // a noticeable time for the large blocks.
+        OperationsSchema(&mut transaction)
+            .store_executed_txs(executed_txs)
+            .await?;
+        OperationsSchema(&mut transaction)
+            .store_executed_priority_ops(executed_priority_ops)
+            .await?;
+
+        transaction.commit().await?;
         metrics::histogram!("sql.chain.block.save_block_transactions", start.elapsed());
         Ok(())
     }
@@ -81,6 +97,39 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block)
     }
 
+    /// Loads the complete data of the block (operations, state diff and proof) for the export.
+    /// Returns `None` if the block with provided number does not exist yet.
+    pub async fn load_block_export(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<BlockExport>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let block = match BlockSchema(&mut transaction)
+            .get_block(block_number)
+            .await?
+        {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let state_diff = StateSchema(&mut transaction)
+            .load_state_diff_for_block(block_number)
+            .await?;
+        let proof = ProverSchema(&mut transaction)
+            .load_proof(block_number)
+            .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.block.load_block_export", start.elapsed());
+        Ok(Some(BlockExport {
+            block,
+            state_diff,
+            proof,
+        }))
+    }
+
     /// Given the block number, attempts to retrieve it from the database.
     /// Returns `None` if the block with provided number does not exist yet.
     pub async fn get_block(&mut self, block: BlockNumber) -> QueryResult<Option<Block>> {
@@ -157,6 +206,16 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                         created_at
                     FROM executed_transactions
                     WHERE block_number = $1
+                    UNION ALL
+                    SELECT
+                        '0x' || encode(tx_hash, 'hex') as tx_hash,
+                        tx as op,
+                        block_number,
+                        success,
+                        fail_reason,
+                        created_at
+                    FROM executed_transactions_archive
+                    WHERE block_number = $1
                 ), priority_ops AS (
                     SELECT
                         '0x' || encode(eth_hash, 'hex') as tx_hash,
@@ -167,6 +226,16 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                         created_at
                     FROM executed_priority_operations
                     WHERE block_number = $1
+                    UNION ALL
+                    SELECT
+                        '0x' || encode(eth_hash, 'hex') as tx_hash,
+                        operation as op,
+                        block_number,
+                        true as success,
+                        Null as fail_reason,
+                        created_at
+                    FROM executed_priority_operations_archive
+                    WHERE block_number = $1
                 ), everything AS (
                     SELECT * FROM transactions
                     UNION ALL
@@ -192,6 +261,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
     }
 
     /// Given the block number, loads all the operations that were executed in that block.
+    /// Operations of the pruned blocks are loaded from the archive.
     pub async fn get_block_executed_ops(
         &mut self,
         block: BlockNumber,
@@ -202,7 +272,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         // Load both executed transactions and executed priority operations
         // from the database.
         let (executed_ops, executed_priority_ops) = {
-            let executed_ops = sqlx::query_as!(
+            let mut executed_ops = sqlx::query_as!(
                 StoredExecutedTransaction,
                 "SELECT * FROM executed_transactions WHERE block_number = $1",
                 i64::from(*block)
@@ -210,7 +280,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .fetch_all(self.0.conn())
             .await?;
 
-            let executed_priority_ops = sqlx::query_as!(
+            let mut executed_priority_ops = sqlx::query_as!(
                 StoredExecutedPriorityOperation,
                 "SELECT * FROM executed_priority_operations WHERE block_number = $1",
                 i64::from(*block)
@@ -218,6 +288,26 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .fetch_all(self.0.conn())
             .await?;
 
+            // Pruning moves all the operations of the block at once, so the archive
+            // has to be checked only if the block has none in the main tables.
+            if executed_ops.is_empty() && executed_priority_ops.is_empty() {
+                executed_ops = sqlx::query_as!(
+                    StoredExecutedTransaction,
+                    "SELECT * FROM executed_transactions_archive WHERE block_number = $1",
+                    i64::from(*block)
+                )
+                .fetch_all(self.0.conn())
+                .await?;
+
+                executed_priority_ops = sqlx::query_as!(
+                    StoredExecutedPriorityOperation,
+                    "SELECT * FROM executed_priority_operations_archive WHERE block_number = $1",
+                    i64::from(*block)
+                )
+                .fetch_all(self.0.conn())
+                .await?;
+            }
+
             (executed_ops, executed_priority_ops)
         };
 
@@ -545,6 +635,21 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(Some(result))
     }
 
+    /// Retrieves the amount of chunks left in the latest pending block, if such is present.
+    pub async fn load_pending_block_chunks_left(&mut self) -> QueryResult<Option<usize>> {
+        let start = Instant::now();
+        let result = self
+            .load_storage_pending_block()
+            .await?
+            .map(|block| block.chunks_left as usize);
+
+        metrics::histogram!(
+            "sql.chain.block.load_pending_block_chunks_left",
+            start.elapsed()
+        );
+        Ok(result)
+    }
+
     /// Returns `true` if there is a stored pending block in the database.
     pub async fn pending_block_exists(&mut self) -> QueryResult<bool> {
         let start = Instant::now();
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
+use zksync_crypto::proof::SingleProof;
+use zksync_types::{block::Block, AccountUpdates};
 use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, SyncBlockPrefix, ZeroxPrefix};
 // Local imports
 
@@ -77,3 +79,14 @@ impl BlockDetails {
         self.verified_at.is_some() && self.verify_tx_hash.is_some()
     }
 }
+
+/// Complete data of the block, used for the block data export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockExport {
+    /// Block header and the executed operations.
+    pub block: Block,
+    /// Account updates performed in the block.
+    pub state_diff: AccountUpdates,
+    /// Proof of the block, if the block was proven individually and the proof was not pruned.
+    pub proof: Option<SingleProof>,
+}
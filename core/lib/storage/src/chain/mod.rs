@@ -1,4 +1,5 @@
 pub mod account;
+pub mod balance_snapshots;
 pub mod block;
 pub mod mempool;
 pub mod operations;
@@ -38,6 +39,10 @@ impl<'a, 'c> ChainIntermediator<'a, 'c> {
         stats::StatsSchema(self.0)
     }
 
+    pub fn balance_snapshots_schema(self) -> balance_snapshots::BalanceSnapshotsSchema<'a, 'c> {
+        balance_snapshots::BalanceSnapshotsSchema(self.0)
+    }
+
     pub fn mempool_schema(self) -> mempool::MempoolSchema<'a, 'c> {
         mempool::MempoolSchema(self.0)
     }
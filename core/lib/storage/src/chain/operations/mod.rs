@@ -1,16 +1,21 @@
 // Built-in deps
-use std::time::Instant;
+use std::{
+    collections::{BTreeSet, HashSet},
+    time::Instant,
+};
 // External imports
 use chrono::{Duration, Utc};
 // Workspace imports
 use zksync_types::{tx::TxHash, BlockNumber};
 // Local imports
 use self::records::{
-    NewExecutedPriorityOperation, NewExecutedTransaction, StoredAggregatedOperation,
-    StoredCompleteWithdrawalsTransaction, StoredExecutedPriorityOperation, StoredPendingWithdrawal,
+    ExecutedPriorityOperationColumns, ExecutedTransactionColumns, NewExecutedPriorityOperation,
+    NewExecutedTransaction, StoredAggregatedOperation, StoredCompleteWithdrawalsTransaction,
+    StoredExecutedPriorityOperation, StoredPendingWithdrawal,
 };
 use crate::chain::operations::records::StoredExecutedTransaction;
 use crate::chain::operations_ext::OperationsExtSchema;
+use crate::chain::state::StateSchema;
 use crate::ethereum::EthereumSchema;
 use crate::{chain::mempool::MempoolSchema, QueryResult, StorageProcessor};
 use zksync_basic_types::H256;
@@ -79,13 +84,23 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         op_hash: &[u8],
     ) -> QueryResult<Option<StoredExecutedTransaction>> {
         let start = Instant::now();
-        let op = sqlx::query_as!(
+        let mut op = sqlx::query_as!(
             StoredExecutedTransaction,
             "SELECT * FROM executed_transactions WHERE tx_hash = $1",
             op_hash
         )
         .fetch_optional(self.0.conn())
         .await?;
+        // Transactions of the pruned blocks are moved to the archive.
+        if op.is_none() {
+            op = sqlx::query_as!(
+                StoredExecutedTransaction,
+                "SELECT * FROM executed_transactions_archive WHERE tx_hash = $1",
+                op_hash
+            )
+            .fetch_optional(self.0.conn())
+            .await?;
+        }
 
         metrics::histogram!(
             "sql.chain.operations.get_executed_operation",
@@ -100,13 +115,23 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         priority_op_id: u32,
     ) -> QueryResult<Option<StoredExecutedPriorityOperation>> {
         let start = Instant::now();
-        let op = sqlx::query_as!(
+        let mut op = sqlx::query_as!(
             StoredExecutedPriorityOperation,
             "SELECT * FROM executed_priority_operations WHERE priority_op_serialid = $1",
             i64::from(priority_op_id)
         )
         .fetch_optional(self.0.conn())
         .await?;
+        // Priority operations of the pruned blocks are moved to the archive.
+        if op.is_none() {
+            op = sqlx::query_as!(
+                StoredExecutedPriorityOperation,
+                "SELECT * FROM executed_priority_operations_archive WHERE priority_op_serialid = $1",
+                i64::from(priority_op_id)
+            )
+            .fetch_optional(self.0.conn())
+            .await?;
+        }
 
         metrics::histogram!(
             "sql.chain.operations.get_executed_priority_operation",
@@ -121,13 +146,23 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         eth_hash: &[u8],
     ) -> QueryResult<Option<StoredExecutedPriorityOperation>> {
         let start = Instant::now();
-        let op = sqlx::query_as!(
+        let mut op = sqlx::query_as!(
             StoredExecutedPriorityOperation,
             "SELECT * FROM executed_priority_operations WHERE eth_hash = $1",
             eth_hash
         )
         .fetch_optional(self.0.conn())
         .await?;
+        // Priority operations of the pruned blocks are moved to the archive.
+        if op.is_none() {
+            op = sqlx::query_as!(
+                StoredExecutedPriorityOperation,
+                "SELECT * FROM executed_priority_operations_archive WHERE eth_hash = $1",
+                eth_hash
+            )
+            .fetch_optional(self.0.conn())
+            .await?;
+        }
 
         metrics::histogram!(
             "sql.chain.operations.get_executed_priority_operation_by_hash",
@@ -172,20 +207,28 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         MempoolSchema(&mut transaction)
             .remove_tx(&operation.tx_hash)
             .await?;
+        StateSchema(&mut transaction)
+            .create_block_partitions(BlockNumber(operation.block_number as u32))
+            .await?;
 
+        // Transactions are partitioned by the block number, so the uniqueness of their hashes
+        // is not enforced by the primary key and the stored duplicates are handled explicitly.
         if operation.success {
             // If transaction succeed, it should replace the stored tx with the same hash.
             // The situation when a duplicate tx is stored in the database may exist only if has
             // failed previously.
             // Possible scenario: user had no enough funds for transfer, then deposited some and
             // sent the same transfer again.
+            sqlx::query!(
+                "DELETE FROM executed_transactions WHERE tx_hash = $1",
+                operation.tx_hash
+            )
+            .execute(transaction.conn())
+            .await?;
 
             sqlx::query!(
                 "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
-                ON CONFLICT (tx_hash)
-                DO UPDATE
-                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14",
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
                 operation.block_number,
                 operation.block_index,
                 operation.tx,
@@ -204,12 +247,11 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             .execute(transaction.conn())
             .await?;
         } else {
-            // If transaction failed, we do nothing on conflict.
+            // If transaction failed, we do nothing if the tx with the same hash is stored.
             sqlx::query!(
                 "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
-                ON CONFLICT (tx_hash)
-                DO NOTHING",
+                SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                WHERE NOT EXISTS (SELECT 1 FROM executed_transactions WHERE tx_hash = $5)",
                 operation.block_number,
                 operation.block_index,
                 operation.tx,
@@ -234,6 +276,121 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the executed transactions in the database using a couple of statements per
+    /// conflict policy instead of a statement per transaction.
+    ///
+    /// Semantics are the same as for the sequential `store_executed_tx` calls: successful
+    /// transactions replace stored ones with the same hash, while failed transactions
+    /// are ignored if a transaction with the same hash already exists.
+    pub(crate) async fn store_executed_txs(
+        &mut self,
+        operations: Vec<NewExecutedTransaction>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if operations.is_empty() {
+            return Ok(());
+        }
+        let mut transaction = self.0.start_transaction().await?;
+
+        let tx_hashes: Vec<_> = operations
+            .iter()
+            .filter_map(|operation| TxHash::from_slice(&operation.tx_hash))
+            .collect();
+        MempoolSchema(&mut transaction)
+            .remove_txs(&tx_hashes)
+            .await?;
+
+        let block_numbers: BTreeSet<_> = operations
+            .iter()
+            .map(|operation| operation.block_number)
+            .collect();
+        for block_number in block_numbers {
+            StateSchema(&mut transaction)
+                .create_block_partitions(BlockNumber(block_number as u32))
+                .await?;
+        }
+
+        let (succeeded, failed): (Vec<_>, Vec<_>) = operations
+            .into_iter()
+            .partition(|operation| operation.success);
+
+        // Only the latest successful transaction with the given hash is kept, and only
+        // the first failed one is stored if there is no transaction with the same hash yet.
+        let mut succeeded_hashes = HashSet::new();
+        let mut succeeded: Vec<_> = succeeded
+            .into_iter()
+            .rev()
+            .filter(|operation| succeeded_hashes.insert(operation.tx_hash.clone()))
+            .collect();
+        succeeded.reverse();
+        let mut failed_hashes = HashSet::new();
+        let failed: Vec<_> = failed
+            .into_iter()
+            .filter(|operation| failed_hashes.insert(operation.tx_hash.clone()))
+            .collect();
+
+        if !succeeded.is_empty() {
+            let columns = ExecutedTransactionColumns::from(succeeded);
+            sqlx::query!(
+                "DELETE FROM executed_transactions WHERE tx_hash = ANY($1)",
+                &columns.tx_hash
+            )
+            .execute(transaction.conn())
+            .await?;
+
+            sqlx::query!(
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
+                SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::jsonb[], $5::bytea[], $6::bytea[], $7::bytea[], $8::boolean[], $9::text[], $10::bytea[], $11::bigint[], $12::timestamptz[], $13::jsonb[], $14::bigint[])",
+                &columns.block_number,
+                &columns.block_index,
+                &columns.tx,
+                &columns.operation,
+                &columns.tx_hash,
+                &columns.from_account,
+                &columns.to_account,
+                &columns.success,
+                &columns.fail_reason,
+                &columns.primary_account_address,
+                &columns.nonce,
+                &columns.created_at,
+                &columns.eth_sign_data,
+                &columns.batch_id,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        if !failed.is_empty() {
+            let columns = ExecutedTransactionColumns::from(failed);
+            sqlx::query!(
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
+                SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::jsonb[], $5::bytea[], $6::bytea[], $7::bytea[], $8::boolean[], $9::text[], $10::bytea[], $11::bigint[], $12::timestamptz[], $13::jsonb[], $14::bigint[])
+                    AS new_txs (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
+                WHERE NOT EXISTS (SELECT 1 FROM executed_transactions WHERE executed_transactions.tx_hash = new_txs.tx_hash)",
+                &columns.block_number,
+                &columns.block_index,
+                &columns.tx,
+                &columns.operation,
+                &columns.tx_hash,
+                &columns.from_account,
+                &columns.to_account,
+                &columns.success,
+                &columns.fail_reason,
+                &columns.primary_account_address,
+                &columns.nonce,
+                &columns.created_at,
+                &columns.eth_sign_data,
+                &columns.batch_id,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.operations.store_executed_txs", start.elapsed());
+        Ok(())
+    }
+
     /// Removes all rejected transactions with an age greater than `max_age` from the database.
     pub async fn remove_rejected_transactions(&mut self, max_age: Duration) -> QueryResult<()> {
         let start = Instant::now();
@@ -289,6 +446,43 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores executed priority operations in the database using a single statement.
+    pub(crate) async fn store_executed_priority_ops(
+        &mut self,
+        operations: Vec<NewExecutedPriorityOperation>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if operations.is_empty() {
+            return Ok(());
+        }
+
+        let columns = ExecutedPriorityOperationColumns::from(operations);
+        sqlx::query!(
+            "INSERT INTO executed_priority_operations (block_number, block_index, operation, from_account, to_account, priority_op_serialid, deadline_block, eth_hash, eth_block, created_at)
+            SELECT * FROM UNNEST ($1::bigint[], $2::integer[], $3::jsonb[], $4::bytea[], $5::bytea[], $6::bigint[], $7::bigint[], $8::bytea[], $9::bigint[], $10::timestamptz[])
+            ON CONFLICT (priority_op_serialid)
+            DO NOTHING",
+            &columns.block_number,
+            &columns.block_index,
+            &columns.operation,
+            &columns.from_account,
+            &columns.to_account,
+            &columns.priority_op_serialid,
+            &columns.deadline_block,
+            &columns.eth_hash,
+            &columns.eth_block,
+            &columns.created_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations.store_executed_priority_ops",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
     /// On old contracts, a separate operation was used to withdraw - `CompleteWithdrawals`.
     ///
     /// NOTE: Currently `CompleteWithdrawals` is deprecated but the information is still stored
@@ -85,6 +85,88 @@ pub struct NewExecutedTransaction {
     pub batch_id: Option<i64>,
 }
 
+/// Column-wise representation of the executed transactions, used to insert
+/// many rows at once via `UNNEST`.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutedTransactionColumns {
+    pub block_number: Vec<i64>,
+    pub block_index: Vec<Option<i32>>,
+    pub tx: Vec<Value>,
+    pub operation: Vec<Value>,
+    pub tx_hash: Vec<Vec<u8>>,
+    pub from_account: Vec<Vec<u8>>,
+    pub to_account: Vec<Option<Vec<u8>>>,
+    pub success: Vec<bool>,
+    pub fail_reason: Vec<Option<String>>,
+    pub primary_account_address: Vec<Vec<u8>>,
+    pub nonce: Vec<i64>,
+    pub created_at: Vec<DateTime<Utc>>,
+    pub eth_sign_data: Vec<Option<serde_json::Value>>,
+    pub batch_id: Vec<Option<i64>>,
+}
+
+impl From<Vec<NewExecutedTransaction>> for ExecutedTransactionColumns {
+    fn from(operations: Vec<NewExecutedTransaction>) -> Self {
+        let mut columns = Self::default();
+        for operation in operations {
+            columns.block_number.push(operation.block_number);
+            columns.block_index.push(operation.block_index);
+            columns.tx.push(operation.tx);
+            columns.operation.push(operation.operation);
+            columns.tx_hash.push(operation.tx_hash);
+            columns.from_account.push(operation.from_account);
+            columns.to_account.push(operation.to_account);
+            columns.success.push(operation.success);
+            columns.fail_reason.push(operation.fail_reason);
+            columns
+                .primary_account_address
+                .push(operation.primary_account_address);
+            columns.nonce.push(operation.nonce);
+            columns.created_at.push(operation.created_at);
+            columns.eth_sign_data.push(operation.eth_sign_data);
+            columns.batch_id.push(operation.batch_id);
+        }
+        columns
+    }
+}
+
+/// Column-wise representation of the executed priority operations, used to insert
+/// many rows at once via `UNNEST`.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutedPriorityOperationColumns {
+    pub block_number: Vec<i64>,
+    pub block_index: Vec<i32>,
+    pub operation: Vec<Value>,
+    pub from_account: Vec<Vec<u8>>,
+    pub to_account: Vec<Vec<u8>>,
+    pub priority_op_serialid: Vec<i64>,
+    pub deadline_block: Vec<i64>,
+    pub eth_hash: Vec<Vec<u8>>,
+    pub eth_block: Vec<i64>,
+    pub created_at: Vec<DateTime<Utc>>,
+}
+
+impl From<Vec<NewExecutedPriorityOperation>> for ExecutedPriorityOperationColumns {
+    fn from(operations: Vec<NewExecutedPriorityOperation>) -> Self {
+        let mut columns = Self::default();
+        for operation in operations {
+            columns.block_number.push(operation.block_number);
+            columns.block_index.push(operation.block_index);
+            columns.operation.push(operation.operation);
+            columns.from_account.push(operation.from_account);
+            columns.to_account.push(operation.to_account);
+            columns
+                .priority_op_serialid
+                .push(operation.priority_op_serialid);
+            columns.deadline_block.push(operation.deadline_block);
+            columns.eth_hash.push(operation.eth_hash);
+            columns.eth_block.push(operation.eth_block);
+            columns.created_at.push(operation.created_at);
+        }
+        columns
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StoredPendingWithdrawal {
     pub id: i64,
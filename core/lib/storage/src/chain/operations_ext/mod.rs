@@ -314,7 +314,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
     ) -> QueryResult<Option<DateTime<Utc>>> {
         let start = Instant::now();
         // This query loads the `committed_at` field from both `executed_transactions` and
-        // `executed_priority_operations` tables and returns the oldest result.
+        // `executed_priority_operations` tables (including their archives) and returns the oldest result.
         let first_history_entry = sqlx::query_as!(
             AccountCreatedAt,
             r#"
@@ -340,6 +340,26 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         from_account = $1
                         or
                         to_account = $1
+                    union all
+                    select
+                        created_at
+                    from
+                        executed_transactions_archive
+                    where
+                        from_account = $1
+                        or
+                        to_account = $1
+                        or
+                        primary_account_address = $1
+                    union all
+                    select
+                        created_at
+                    from
+                        executed_priority_operations_archive
+                    where
+                        from_account = $1
+                        or
+                        to_account = $1
             ) t
             order by
                 created_at asc
@@ -369,6 +389,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         let start = Instant::now();
         // This query does the following:
         // - creates a union of `executed_transactions` and the `executed_priority_operations`
+        //   (including their archives)
         // - unifies the information to match the `TransactionsHistoryItem`
         //   structure layout
         // - returns the obtained results.
@@ -425,6 +446,42 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     from 
                         executed_priority_operations
                     where 
+                        from_account = $1
+                        or
+                        to_account = $1
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        tx,
+                        'sync-tx:' || encode(tx_hash, 'hex') as hash,
+                        null as pq_id,
+                        null as eth_block,
+                        success,
+                        fail_reason,
+                        block_number,
+                        created_at
+                    from
+                        executed_transactions_archive
+                    where
+                        from_account = $1
+                        or
+                        to_account = $1
+                        or
+                        primary_account_address = $1
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        operation as tx,
+                        '0x' || encode(eth_hash, 'hex') as hash,
+                        priority_op_serialid as pq_id,
+                        eth_block,
+                        true as success,
+                        null as fail_reason,
+                        block_number,
+                        created_at
+                    from
+                        executed_priority_operations_archive
+                    where
                         from_account = $1
                         or
                         to_account = $1) t
@@ -527,6 +584,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
 
         // This query does the following:
         // - creates a union of `executed_transactions` and the `executed_priority_operations`
+        //   (including their archives)
         // - unifies the information to match the `TransactionsHistoryItem`
         //   structure layout
         // - returns the obtained results.
@@ -600,6 +658,50 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         )
                         and
                         (block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6))
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        tx,
+                        'sync-tx:' || encode(tx_hash, 'hex') as hash,
+                        null as pq_id,
+                        null as eth_block,
+                        success,
+                        fail_reason,
+                        block_number,
+                        created_at
+                    from
+                        executed_transactions_archive
+                    where
+                        (
+                            from_account = $1
+                            or
+                            to_account = $1
+                            or
+                            primary_account_address = $1
+                        )
+                        and
+                        (block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6))
+                    union all
+                    select
+                        concat_ws(',', block_number, block_index) as tx_id,
+                        operation as tx,
+                        '0x' || encode(eth_hash, 'hex') as hash,
+                        priority_op_serialid as pq_id,
+                        eth_block,
+                        true as success,
+                        null as fail_reason,
+                        block_number,
+                        created_at
+                    from
+                        executed_priority_operations_archive
+                    where
+                        (
+                            from_account = $1
+                            or
+                            to_account = $1
+                        )
+                        and
+                        (block_number BETWEEN $3 AND $4 or (block_number = $2 and block_index BETWEEN $5 AND $6))
                     ) t
                 order by
                     block_number desc, created_at desc
@@ -729,14 +831,18 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                                 LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
                         )
                     SELECT
-                        block_number, 
+                        block_number as "block_number!",
                         block_index as "block_index?",
-                        tx_hash,
-                        success,
+                        tx_hash as "tx_hash!",
+                        success as "success!",
                         fail_reason as "fail_reason?",
                         details.commit_tx_hash as "commit_tx_hash?",
                         details.verify_tx_hash as "verify_tx_hash?"
-                    FROM executed_transactions
+                    FROM (
+                        SELECT * FROM executed_transactions
+                        UNION ALL
+                        SELECT * FROM executed_transactions_archive
+                    ) executed_transactions
                     LEFT JOIN block_details details ON details.details_block_number = executed_transactions.block_number
                     WHERE (
                         (primary_account_address = $1 OR from_account = $1 OR to_account = $1)
@@ -795,14 +901,18 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                                 LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
                     )
                     SELECT
-                        block_number, 
+                        block_number as "block_number!",
                         block_index as "block_index?",
-                        tx_hash,
-                        success,
+                        tx_hash as "tx_hash!",
+                        success as "success!",
                         fail_reason as "fail_reason?",
                         details.commit_tx_hash as "commit_tx_hash?",
                         details.verify_tx_hash as "verify_tx_hash?"
-                    FROM executed_transactions
+                    FROM (
+                        SELECT * FROM executed_transactions
+                        UNION ALL
+                        SELECT * FROM executed_transactions_archive
+                    ) executed_transactions
                     LEFT JOIN block_details details ON details.details_block_number = executed_transactions.block_number
                     WHERE (
                         (primary_account_address = $1 OR from_account = $1 OR to_account = $1)
@@ -890,12 +1000,16 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                                 LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
                     )
                     SELECT
-                        block_number, 
-                        block_index,
-                        eth_hash,
+                        block_number as "block_number!",
+                        block_index as "block_index!",
+                        eth_hash as "eth_hash!",
                         details.commit_tx_hash as "commit_tx_hash?",
                         details.verify_tx_hash as "verify_tx_hash?"
-                    FROM executed_priority_operations
+                    FROM (
+                        SELECT * FROM executed_priority_operations
+                        UNION ALL
+                        SELECT * FROM executed_priority_operations_archive
+                    ) executed_priority_operations
                     LEFT JOIN block_details details ON details.details_block_number = executed_priority_operations.block_number
                     WHERE (
                         (from_account = $1 OR to_account = $1)
@@ -954,12 +1068,16 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                                 LEFT JOIN aggr_exec verified ON blocks.number = verified.block_number
                     )
                     SELECT
-                        block_number, 
-                        block_index,
-                        eth_hash,
+                        block_number as "block_number!",
+                        block_index as "block_index!",
+                        eth_hash as "eth_hash!",
                         details.commit_tx_hash as "commit_tx_hash?",
                         details.verify_tx_hash as "verify_tx_hash?"
-                    FROM executed_priority_operations
+                    FROM (
+                        SELECT * FROM executed_priority_operations
+                        UNION ALL
+                        SELECT * FROM executed_priority_operations_archive
+                    ) executed_priority_operations
                     LEFT JOIN block_details details ON details.details_block_number = executed_priority_operations.block_number
                     WHERE (
                         (from_account = $1 OR to_account = $1)
@@ -9,6 +9,7 @@ use zksync_types::{
     AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, PubKeyHash,
 };
 // Local imports
+use self::records::StateVersion;
 use crate::chain::{
     account::{records::*, restore_account},
     block::BlockSchema,
@@ -17,6 +18,8 @@ use crate::diff::StorageAccountDiff;
 // use crate::schema::*;
 use crate::{QueryResult, StorageProcessor};
 
+pub mod records;
+
 /// State schema is capable of managing... well, the state of the chain.
 ///
 /// This roughly includes the two main topics:
@@ -49,6 +52,10 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
+        StateSchema(&mut transaction)
+            .create_block_partitions(block_number)
+            .await?;
+
         // Simply go through the every account update, and update the corresponding table.
         // This may look scary, but every match arm is very simple by its nature.
 
@@ -159,6 +166,24 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Creates the partitions of the account state updates and executed transactions tables
+    /// for the blocks range containing the given block, if they don't exist yet.
+    pub(crate) async fn create_block_partitions(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"SELECT create_block_partitions($1) AS "partition_idx!""#,
+            i64::from(*block_number)
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.state.create_block_partitions", start.elapsed());
+        Ok(())
+    }
+
     /// Applies the previously stored list of account changes to the stored state.
     ///
     /// This method is invoked from the `zksync_eth_sender` after corresponding `Verify` transaction
@@ -549,4 +574,33 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
         result
     }
+
+    /// Loads the identifiers of the latest accounts state changes.
+    /// See `StateVersion` for details.
+    pub async fn load_state_version(&mut self) -> QueryResult<StateVersion> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT MAX(balance_update_id) FROM account_balance_updates) AS last_balance_update_id,
+                (SELECT MAX(pubkey_update_id) FROM account_pubkey_updates) AS last_pubkey_update_id
+            "#
+        )
+        .fetch_one(transaction.conn())
+        .await?;
+        let last_verified_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.state.load_state_version", start.elapsed());
+        Ok(StateVersion {
+            last_balance_update_id: record.last_balance_update_id.unwrap_or_default(),
+            last_pubkey_update_id: record.last_pubkey_update_id.unwrap_or_default(),
+            last_verified_block,
+        })
+    }
 }
@@ -0,0 +1,19 @@
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+
+/// Identifiers of the latest changes of the stored accounts state.
+///
+/// Every state change made by the server (either a commit of the (pending) block or
+/// the verification of the block) changes this value, so it can be used to check whether
+/// the previously loaded accounts state is still up to date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateVersion {
+    /// Identifier of the latest stored balance update.
+    pub last_balance_update_id: i32,
+    /// Identifier of the latest stored public key update.
+    pub last_pubkey_update_id: i32,
+    /// Latest block which state updates were applied to the verified state.
+    pub last_verified_block: BlockNumber,
+}
@@ -28,21 +28,33 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         Ok(count as u32)
     }
 
-    /// Returns the amount of executed transactions (both usual and priority).
+    /// Returns the amount of executed transactions (both usual and priority),
+    /// including the archived ones.
     pub async fn count_total_transactions(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
-        let count_tx =
-            sqlx::query!("SELECT COUNT(*) FROM executed_transactions WHERE success = true",)
-                .fetch_one(self.0.conn())
-                .await?
-                .count
-                .unwrap_or(0);
+        let count_tx = sqlx::query!(
+            "SELECT COUNT(*) FROM (
+                SELECT tx_hash FROM executed_transactions WHERE success = true
+                UNION ALL
+                SELECT tx_hash FROM executed_transactions_archive WHERE success = true
+            ) t"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0);
 
-        let prior_ops = sqlx::query!("SELECT COUNT(*) FROM executed_priority_operations",)
-            .fetch_one(self.0.conn())
-            .await?
-            .count
-            .unwrap_or(0);
+        let prior_ops = sqlx::query!(
+            "SELECT COUNT(*) FROM (
+                SELECT eth_hash FROM executed_priority_operations
+                UNION ALL
+                SELECT eth_hash FROM executed_priority_operations_archive
+            ) t"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count
+        .unwrap_or(0);
 
         metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
         Ok((count_tx + prior_ops) as u32)
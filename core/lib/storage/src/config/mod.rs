@@ -10,8 +10,8 @@ use crate::{QueryResult, StorageProcessor};
 pub mod records;
 
 /// Schema for loading the server config.
-/// Note that there is no setter in this schema, since the config
-/// isn't expected to be writable within application.
+/// Note that the contract addresses can't be set within this schema, since they
+/// aren't expected to be writable within application.
 ///
 /// Currently config is added to ZKSync by the `db-insert-contract.sh` script.
 #[derive(Debug)]
@@ -28,4 +28,21 @@ impl<'a, 'c> ConfigSchema<'a, 'c> {
         metrics::histogram!("sql.load_config", start.elapsed());
         Ok(config)
     }
+
+    /// Binds the database to the network unless it's already bound to some network,
+    /// and returns the network the database belongs to.
+    pub async fn bind_network(&mut self, network: &str) -> QueryResult<String> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            "INSERT INTO server_config (network) VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET network = COALESCE(server_config.network, $1)
+            RETURNING network",
+            network
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.bind_network", start.elapsed());
+        Ok(record.network.unwrap_or_default())
+    }
 }
@@ -1,39 +1,80 @@
 // Built-in deps
-use std::{env, fmt, time::Instant};
+use std::{
+    env, fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
-use sqlx::{Connection, Error as SqlxError, PgConnection};
+use sqlx::{
+    postgres::PgConnectOptions, ConnectOptions, Connection, Error as SqlxError, Executor,
+    PgConnection,
+};
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
 use crate::StorageProcessor;
 use zksync_utils::parse_env;
 
 pub mod holder;
+pub mod retry;
 
 type Pool = deadpool::managed::Pool<PgConnection, SqlxError>;
 
+/// Queries executed for longer than this amount of milliseconds are logged,
+/// unless the threshold is overridden via `DATABASE_SLOW_QUERY_THRESHOLD` environment variable.
+const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 1000;
+
+/// Queries executed for longer than this amount of milliseconds are aborted,
+/// unless the timeout is overridden via `DATABASE_STATEMENT_TIMEOUT` environment variable.
+/// Zero value disables the timeout.
+const DEFAULT_STATEMENT_TIMEOUT_MS: u64 = 0;
+
 pub type PooledConnection = deadpool::managed::Object<PgConnection, SqlxError>;
 
 #[derive(Clone)]
 struct DbPool {
     url: String,
+    slow_query_threshold: Duration,
+    statement_timeout: Option<Duration>,
 }
 
 impl DbPool {
-    fn create(url: impl Into<String>, max_size: usize) -> Pool {
+    fn create(
+        url: impl Into<String>,
+        max_size: usize,
+        slow_query_threshold: Duration,
+        statement_timeout: Option<Duration>,
+    ) -> Pool {
         let pool_config = PoolConfig {
             max_size,
             timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
         };
-        Pool::from_config(DbPool { url: url.into() }, pool_config)
+        let manager = DbPool {
+            url: url.into(),
+            slow_query_threshold,
+            statement_timeout,
+        };
+        Pool::from_config(manager, pool_config)
     }
 }
 
 #[async_trait]
 impl Manager<PgConnection, SqlxError> for DbPool {
     async fn create(&self) -> Result<PgConnection, SqlxError> {
-        PgConnection::connect(&self.url).await
+        let mut options = PgConnectOptions::from_str(&self.url)?;
+        // Regular statements are not logged to not flood the logs, but the slow ones
+        // are reported with the warning level.
+        options
+            .log_statements(log::LevelFilter::Off)
+            .log_slow_statements(log::LevelFilter::Warn, self.slow_query_threshold);
+        let mut connection = PgConnection::connect_with(&options).await?;
+
+        if let Some(statement_timeout) = self.statement_timeout {
+            let query = format!("SET statement_timeout = {}", statement_timeout.as_millis());
+            (&mut connection).execute(query.as_str()).await?;
+        }
+        Ok(connection)
     }
     async fn recycle(&self, obj: &mut PgConnection) -> RecycleResult<SqlxError> {
         Ok(obj.ping().await?)
@@ -45,9 +86,21 @@ impl Manager<PgConnection, SqlxError> for DbPool {
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DATABASE_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// Optionally, the URL of the read replica can be provided via `DATABASE_REPLICA_URL`
+/// environment variable. In that case, connections obtained via `access_read_only_storage`
+/// are served by the replica, so that the heavy read-only load (e.g. explorer API)
+/// doesn't compete with the server for the primary database.
+///
+/// Every connection acquisition reports the pool saturation metrics (amount of the
+/// connections in use, idle connections and tasks waiting for a connection), and the
+/// queries executed for longer than `DATABASE_SLOW_QUERY_THRESHOLD` milliseconds are logged.
+/// Queries executed for longer than `DATABASE_STATEMENT_TIMEOUT` milliseconds are aborted
+/// by the database.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    replica_pool: Option<Pool>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -64,9 +117,25 @@ impl ConnectionPool {
         let database_url = Self::get_database_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        let slow_query_threshold = Self::get_slow_query_threshold();
+        let statement_timeout = Self::get_statement_timeout();
 
-        Self { pool }
+        let pool = DbPool::create(
+            database_url,
+            max_size as usize,
+            slow_query_threshold,
+            statement_timeout,
+        );
+        let replica_pool = Self::get_replica_database_url().map(|replica_url| {
+            DbPool::create(
+                replica_url,
+                max_size as usize,
+                slow_query_threshold,
+                statement_timeout,
+            )
+        });
+
+        Self { pool, replica_pool }
     }
 
     /// Creates a `StorageProcessor` entity over a recoverable connection.
@@ -81,12 +150,77 @@ impl ConnectionPool {
         let start = Instant::now();
         let connection = self.pool.get().await.unwrap();
         metrics::histogram!("sql.connection_acquire", start.elapsed());
+        Self::report_pool_status(&self.pool, "primary");
+
+        Ok(StorageProcessor::from_pool(connection))
+    }
+
+    /// Creates a `StorageProcessor` entity intended to be used for read-only queries.
+    ///
+    /// If the read replica is configured, connection to the replica is returned.
+    /// Since the replica may lag behind the primary database, this method must not
+    /// be used in contexts where the most recent state is required or if the data
+    /// is going to be modified. Otherwise, this method is equivalent to `access_storage`.
+    pub async fn access_read_only_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
+        let replica_pool = match &self.replica_pool {
+            Some(replica_pool) => replica_pool,
+            None => return self.access_storage().await,
+        };
+
+        let start = Instant::now();
+        let connection = replica_pool.get().await.unwrap();
+        metrics::histogram!("sql.replica_connection_acquire", start.elapsed());
+        Self::report_pool_status(replica_pool, "replica");
 
         Ok(StorageProcessor::from_pool(connection))
     }
 
+    /// Reports the saturation of the given pool.
+    fn report_pool_status(pool: &Pool, pool_name: &'static str) {
+        let status = pool.status();
+        // Negative amount of the available connections means that there are tasks waiting for a connection.
+        let idle = status.available.max(0) as usize;
+        let waiting = (-status.available).max(0);
+        let in_use = status.size.saturating_sub(idle);
+
+        metrics::gauge!("sql.pool.size", status.size as f64, "pool" => pool_name);
+        metrics::gauge!("sql.pool.in_use", in_use as f64, "pool" => pool_name);
+        metrics::gauge!("sql.pool.idle", idle as f64, "pool" => pool_name);
+        metrics::gauge!("sql.pool.waiting", waiting as f64, "pool" => pool_name);
+    }
+
     /// Obtains the database URL from the environment variable.
     fn get_database_url() -> String {
         env::var("DATABASE_URL").expect("DATABASE_URL must be set")
     }
+
+    /// Obtains the read replica database URL from the environment variable, if it's set.
+    fn get_replica_database_url() -> Option<String> {
+        env::var("DATABASE_REPLICA_URL")
+            .ok()
+            .filter(|url| !url.is_empty())
+    }
+
+    /// Obtains the slow query threshold from the environment variable, if it's set.
+    fn get_slow_query_threshold() -> Duration {
+        let threshold_ms = env::var("DATABASE_SLOW_QUERY_THRESHOLD")
+            .ok()
+            .and_then(|threshold| threshold.parse().ok())
+            .unwrap_or(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+        Duration::from_millis(threshold_ms)
+    }
+
+    /// Obtains the statement timeout from the environment variable, if it's set.
+    /// Returns `None` if the timeout is disabled.
+    fn get_statement_timeout() -> Option<Duration> {
+        let timeout_ms = env::var("DATABASE_STATEMENT_TIMEOUT")
+            .ok()
+            .and_then(|timeout| timeout.parse().ok())
+            .unwrap_or(DEFAULT_STATEMENT_TIMEOUT_MS);
+        if timeout_ms == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(timeout_ms))
+        }
+    }
 }
@@ -0,0 +1,81 @@
+//! Retrying of the storage interactions failed due to transient errors.
+//!
+//! Some database errors (e.g. serialization failures of concurrent transactions or
+//! a dropped connection) don't indicate a problem with the query itself and are likely
+//! to disappear if the same interaction is repeated a moment later.
+
+// Built-in deps
+use std::{future::Future, time::Duration};
+// External imports
+use sqlx::Error as SqlxError;
+// Local imports
+use crate::QueryResult;
+
+/// Maximum amount of attempts performed by `retry_on_transient_errors`.
+const MAX_ATTEMPTS: usize = 3;
+/// Delay before the first retry. Every next retry waits twice as long as the previous one.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Postgres error codes which are considered transient.
+/// See https://www.postgresql.org/docs/current/errcodes-appendix.html
+const TRANSIENT_ERROR_CODES: &[&str] = &[
+    "40001", // serialization_failure
+    "40P01", // deadlock_detected
+    "08000", // connection_exception
+    "08003", // connection_does_not_exist
+    "08006", // connection_failure
+    "57P01", // admin_shutdown
+    "57P03", // cannot_connect_now
+];
+
+/// Checks whether the error is caused by a transient database failure,
+/// so the failed interaction may succeed if performed again.
+pub fn is_transient_error(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<SqlxError>() {
+        Some(SqlxError::Io(_)) | Some(SqlxError::PoolTimedOut) => true,
+        Some(SqlxError::Database(error)) => error
+            .code()
+            .map(|code| TRANSIENT_ERROR_CODES.contains(&code.as_ref()))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Performs the storage interaction, repeating it if it fails due to a transient error.
+///
+/// Since the interaction may be performed several times, it must be idempotent,
+/// e.g. it's safe to retry a read-only interaction or the whole database transaction.
+///
+/// ```ignore
+/// let block = retry_on_transient_errors(|| async {
+///     let mut storage = pool.access_storage().await?;
+///     storage.chain().block_schema().get_block(block_number).await
+/// })
+/// .await?;
+/// ```
+pub async fn retry_on_transient_errors<T, F, Fut>(mut action: F) -> QueryResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = QueryResult<T>>,
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match action().await {
+            Err(err) if attempt < MAX_ATTEMPTS && is_transient_error(&err) => {
+                vlog::warn!(
+                    "Transient database error (attempt {} of {}), retrying: {}",
+                    attempt,
+                    MAX_ATTEMPTS,
+                    err
+                );
+                metrics::counter!("sql.transient_error_retries", 1);
+
+                tokio::time::delay_for(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -2,6 +2,7 @@
 use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
 // External imports
 use anyhow::format_err;
+use chrono::{DateTime, Utc};
 use num::{BigInt, BigUint};
 use sqlx::types::BigDecimal;
 use zksync_basic_types::{H256, U256};
@@ -10,7 +11,9 @@ use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperat
 use zksync_types::ethereum::{ETHOperation, InsertedOperationResponse};
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
+use self::records::{
+    ETHParams, ETHStats, ETHTxHash, NewOpsGasUsage, OpsGasUsage, StorageETHOperation,
+};
 use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
 
 pub mod records;
@@ -463,6 +466,51 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(average_gas_price)
     }
 
+    /// Updates the stored EIP-1559 fees observed in the network.
+    pub async fn update_eip1559_fees(
+        &mut self,
+        base_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let base_fee_per_gas: i64 =
+            i64::try_from(base_fee_per_gas).expect("Can't convert U256 to i64");
+        let max_priority_fee_per_gas: i64 =
+            i64::try_from(max_priority_fee_per_gas).expect("Can't convert U256 to i64");
+
+        sqlx::query!(
+            "UPDATE eth_parameters
+            SET base_fee_per_gas = $1, max_priority_fee_per_gas = $2
+            WHERE id = true",
+            base_fee_per_gas,
+            max_priority_fee_per_gas
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.update_eip1559_fees", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the stored base fee and priority fee, if the network supports EIP-1559.
+    pub async fn load_eip1559_fees(&mut self) -> QueryResult<Option<(U256, U256)>> {
+        let start = Instant::now();
+        let params = self.load_eth_params().await?;
+
+        let fees = params
+            .base_fee_per_gas
+            .zip(params.max_priority_fee_per_gas)
+            .map(|(base_fee, priority_fee)| {
+                (
+                    U256::try_from(base_fee).expect("Negative base fee stored in DB"),
+                    U256::try_from(priority_fee).expect("Negative priority fee stored in DB"),
+                )
+            });
+
+        metrics::histogram!("sql.ethereum.load_eip1559_fees", start.elapsed());
+        Ok(fees)
+    }
+
     /// Loads the stored Ethereum operations stats.
     pub async fn load_stats(&mut self) -> QueryResult<ETHStats> {
         let start = Instant::now();
@@ -472,6 +520,59 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(params.into())
     }
 
+    /// Stores the gas used by the operations of the blocks, replacing the previous records
+    /// of the same blocks and actions if any.
+    pub async fn store_ops_gas_usage(&mut self, usage: &[NewOpsGasUsage]) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for ops_usage in usage {
+            sqlx::query!(
+                r#"
+                INSERT INTO eth_ops_gas_usage ( block_number, action_type, op_type, ops_count, gas_used, created_at )
+                VALUES ( $1, $2, $3, $4, $5, now() )
+                ON CONFLICT (block_number, action_type, op_type)
+                DO
+                  UPDATE SET ops_count = $4, gas_used = $5, created_at = now()
+                "#,
+                ops_usage.block_number,
+                ops_usage.action_type,
+                ops_usage.op_type,
+                ops_usage.ops_count,
+                ops_usage.gas_used
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.store_ops_gas_usage", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the gas used by the operations of every type and action stored since the given moment.
+    pub async fn load_ops_gas_usage(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<OpsGasUsage>> {
+        let start = Instant::now();
+        let usage = sqlx::query_as!(
+            OpsGasUsage,
+            r#"
+            SELECT action_type, op_type, SUM(ops_count)::BIGINT as "ops_count!", SUM(gas_used)::BIGINT as "gas_used!"
+            FROM eth_ops_gas_usage
+            WHERE created_at >= $1
+            GROUP BY action_type, op_type
+            ORDER BY action_type, op_type
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.load_ops_gas_usage", start.elapsed());
+        Ok(usage)
+    }
+
     async fn load_eth_params(&mut self) -> QueryResult<ETHParams> {
         let start = Instant::now();
         let params = sqlx::query_as!(ETHParams, "SELECT * FROM eth_parameters WHERE id = true",)
@@ -550,6 +651,144 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(old_nonce_value)
     }
 
+    /// Moves the stored next nonce forward to the provided on-chain nonce, if the latter is greater.
+    /// This is required when the operator account has sent transactions bypassing the `eth_sender`
+    /// (e.g. manual interventions), so the stored nonce became outdated.
+    ///
+    /// Returns `true` if the stored nonce was updated.
+    pub async fn sync_nonce(&mut self, on_chain_nonce: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let updated_rows = sqlx::query!(
+            "UPDATE eth_parameters
+            SET nonce = $1
+            WHERE id = true AND nonce < $1",
+            on_chain_nonce
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.ethereum.sync_nonce", start.elapsed());
+        Ok(updated_rows > 0)
+    }
+
+    /// Assigns new nonces to the existing Ethereum operation and to every pending operation
+    /// created after it. Used when the nonce previously assigned to the operation was consumed
+    /// by some other transaction sent from the operator account. The nonces define the order
+    /// the operations are mined in, so the following operations get the next nonces and
+    /// can't be mined before the stuck one.
+    ///
+    /// Returns the IDs of the re-sequenced operations with their new nonces, ordered by the nonces.
+    pub async fn reassign_nonces(
+        &mut self,
+        eth_op_id: i64,
+        on_chain_nonce: i64,
+    ) -> QueryResult<Vec<(i64, i64)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        // The pending operations created before the stuck one keep their nonces.
+        let first_nonce = sqlx::query!(
+            "SELECT MAX(nonce) + 1 AS next_nonce FROM eth_operations
+            WHERE confirmed = false AND id < $1",
+            eth_op_id
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .next_nonce
+        .map_or(on_chain_nonce, |next_nonce| next_nonce.max(on_chain_nonce));
+
+        let op_ids = sqlx::query!(
+            "SELECT id FROM eth_operations
+            WHERE confirmed = false AND id >= $1
+            ORDER BY id ASC",
+            eth_op_id
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut nonces = Vec::with_capacity(op_ids.len());
+        for (nonce, op) in (first_nonce..).zip(op_ids) {
+            sqlx::query!(
+                "UPDATE eth_operations
+                SET nonce = $1
+                WHERE id = $2",
+                nonce,
+                op.id
+            )
+            .execute(transaction.conn())
+            .await?;
+            nonces.push((op.id, nonce));
+        }
+
+        // The stored nonce follows the re-sequenced operations, so the next operation
+        // is sent without a gap in the nonces.
+        sqlx::query!(
+            "UPDATE eth_parameters
+            SET nonce = $1
+            WHERE id = true",
+            first_nonce + nonces.len() as i64
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.reassign_nonces", start.elapsed());
+        Ok(nonces)
+    }
+
+    /// Marks the L1 operations of the given type as paused, so `eth_sender` won't send them.
+    pub async fn pause_action(&mut self, action_type: AggregatedActionType) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "INSERT INTO eth_sender_paused_actions (action_type)
+            VALUES ($1)
+            ON CONFLICT (action_type) DO NOTHING",
+            action_type.to_string()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.pause_action", start.elapsed());
+        Ok(())
+    }
+
+    /// Allows `eth_sender` to send the L1 operations of the given type again.
+    pub async fn resume_action(&mut self, action_type: AggregatedActionType) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "DELETE FROM eth_sender_paused_actions WHERE action_type = $1",
+            action_type.to_string()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.ethereum.resume_action", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the types of the L1 operations that are currently paused.
+    pub async fn load_paused_actions(&mut self) -> QueryResult<Vec<AggregatedActionType>> {
+        let start = Instant::now();
+
+        let paused_actions = sqlx::query!("SELECT action_type FROM eth_sender_paused_actions")
+            .fetch_all(self.0.conn())
+            .await?
+            .into_iter()
+            .map(|record| {
+                AggregatedActionType::from_str(&record.action_type)
+                    .expect("Stored action type must have a valid value")
+            })
+            .collect();
+
+        metrics::histogram!("sql.ethereum.load_paused_actions", start.elapsed());
+        Ok(paused_actions)
+    }
+
     /// Method that internally initializes the `eth_parameters` table.
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
@@ -31,6 +31,8 @@ pub struct ETHParams {
     pub last_committed_block: i64,
     pub last_verified_block: i64,
     pub last_executed_block: i64,
+    pub base_fee_per_gas: Option<i64>,
+    pub max_priority_fee_per_gas: Option<i64>,
 }
 
 /// A slice of `ETHParams` structure with only stats part in it.
@@ -50,3 +52,22 @@ impl From<ETHParams> for ETHStats {
         }
     }
 }
+
+/// Gas used by the operations of one type in the block, attributed from the L1 transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOpsGasUsage {
+    pub block_number: i64,
+    pub action_type: String,
+    pub op_type: String,
+    pub ops_count: i64,
+    pub gas_used: i64,
+}
+
+/// Gas used by the operations of one type summed over the blocks.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct OpsGasUsage {
+    pub action_type: String,
+    pub op_type: String,
+    pub ops_count: i64,
+    pub gas_used: i64,
+}
@@ -0,0 +1,46 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Event forwarder schema stores the progress of forwarding the block events
+/// to the external message broker, so the forwarding is resumed after a restart.
+#[derive(Debug)]
+pub struct EventForwarderSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> EventForwarderSchema<'a, 'c> {
+    /// Returns the last block which events were acknowledged by the broker,
+    /// or `None` if no events were forwarded yet.
+    pub async fn last_forwarded_block(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let last_forwarded_block =
+            sqlx::query!("SELECT last_forwarded_block FROM event_forwarder_state WHERE id = true")
+                .fetch_optional(self.0.conn())
+                .await?
+                .map(|record| BlockNumber(record.last_forwarded_block as u32));
+
+        metrics::histogram!("sql.event_forwarder.last_forwarded_block", start.elapsed());
+        Ok(last_forwarded_block)
+    }
+
+    /// Stores the last block which events were acknowledged by the broker.
+    pub async fn update_last_forwarded_block(&mut self, block: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO event_forwarder_state (id, last_forwarded_block) VALUES (true, $1)
+            ON CONFLICT (id) DO UPDATE SET last_forwarded_block = $1",
+            i64::from(*block),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.event_forwarder.update_last_forwarded_block",
+            start.elapsed()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{NewFeeOverpayment, NewFeeQuote, StoredFeeOverpayment, StoredFeeQuote};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee audit schema stores the fee quotes returned by the fee ticker and the
+/// records about the transactions which paid more fee than it was quoted.
+///
+/// Records are written in batches, since there may be a lot of them.
+#[derive(Debug)]
+pub struct FeeAuditSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeAuditSchema<'a, 'c> {
+    /// Stores the batch of fee quotes using a single statement.
+    pub async fn store_fee_quotes(&mut self, quotes: Vec<NewFeeQuote>) -> QueryResult<()> {
+        let start = Instant::now();
+        if quotes.is_empty() {
+            return Ok(());
+        }
+
+        let mut fee_types = Vec::with_capacity(quotes.len());
+        let mut addresses = Vec::with_capacity(quotes.len());
+        let mut token_ids = Vec::with_capacity(quotes.len());
+        let mut gas_tx_amounts = Vec::with_capacity(quotes.len());
+        let mut gas_prices_wei = Vec::with_capacity(quotes.len());
+        let mut gas_fees = Vec::with_capacity(quotes.len());
+        let mut zkp_fees = Vec::with_capacity(quotes.len());
+        let mut total_fees = Vec::with_capacity(quotes.len());
+        let mut quoted_at = Vec::with_capacity(quotes.len());
+        for quote in quotes {
+            fee_types.push(quote.fee_type);
+            addresses.push(quote.address);
+            token_ids.push(quote.token_id);
+            gas_tx_amounts.push(quote.gas_tx_amount);
+            gas_prices_wei.push(quote.gas_price_wei);
+            gas_fees.push(quote.gas_fee);
+            zkp_fees.push(quote.zkp_fee);
+            total_fees.push(quote.total_fee);
+            quoted_at.push(quote.quoted_at);
+        }
+
+        sqlx::query!(
+            "INSERT INTO fee_quotes (fee_type, address, token_id, gas_tx_amount, gas_price_wei, gas_fee, zkp_fee, total_fee, quoted_at)
+            SELECT * FROM UNNEST ($1::jsonb[], $2::bytea[], $3::integer[], $4::numeric[], $5::numeric[], $6::numeric[], $7::numeric[], $8::numeric[], $9::timestamptz[])",
+            &fee_types,
+            &addresses,
+            &token_ids,
+            &gas_tx_amounts,
+            &gas_prices_wei,
+            &gas_fees,
+            &zkp_fees,
+            &total_fees,
+            &quoted_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_audit.store_fee_quotes", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fee quotes returned within the `[from, to]` time range,
+    /// ordered by the quote time.
+    pub async fn load_fee_quotes(
+        &mut self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredFeeQuote>> {
+        let start = Instant::now();
+        let quotes = sqlx::query_as!(
+            StoredFeeQuote,
+            "SELECT * FROM fee_quotes
+            WHERE quoted_at >= $1 AND quoted_at <= $2
+            ORDER BY quoted_at, id",
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_audit.load_fee_quotes", start.elapsed());
+        Ok(quotes)
+    }
+
+    /// Stores the batch of fee overpayment records using a single statement.
+    pub async fn store_fee_overpayments(
+        &mut self,
+        overpayments: Vec<NewFeeOverpayment>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        if overpayments.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx_hashes = Vec::with_capacity(overpayments.len());
+        let mut token_ids = Vec::with_capacity(overpayments.len());
+        let mut quoted_fees = Vec::with_capacity(overpayments.len());
+        let mut paid_fees = Vec::with_capacity(overpayments.len());
+        let mut recorded_at = Vec::with_capacity(overpayments.len());
+        for overpayment in overpayments {
+            tx_hashes.push(overpayment.tx_hash);
+            token_ids.push(overpayment.token_id);
+            quoted_fees.push(overpayment.quoted_fee);
+            paid_fees.push(overpayment.paid_fee);
+            recorded_at.push(overpayment.recorded_at);
+        }
+
+        sqlx::query!(
+            "INSERT INTO fee_overpayments (tx_hash, token_id, quoted_fee, paid_fee, recorded_at)
+            SELECT * FROM UNNEST ($1::bytea[], $2::integer[], $3::numeric[], $4::numeric[], $5::timestamptz[])",
+            &tx_hashes,
+            &token_ids,
+            &quoted_fees,
+            &paid_fees,
+            &recorded_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_audit.store_fee_overpayments", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fee overpayment records created within the `[from, to]` time range,
+    /// ordered by the creation time.
+    pub async fn load_fee_overpayments(
+        &mut self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredFeeOverpayment>> {
+        let start = Instant::now();
+        let overpayments = sqlx::query_as!(
+            StoredFeeOverpayment,
+            "SELECT * FROM fee_overpayments
+            WHERE recorded_at >= $1 AND recorded_at <= $2
+            ORDER BY recorded_at, id",
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_audit.load_fee_overpayments", start.elapsed());
+        Ok(overpayments)
+    }
+
+    /// Removes the fee quotes and overpayment records older than the given timestamp.
+    /// Returns the amount of removed records.
+    pub async fn remove_records_before(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let removed_quotes = sqlx::query!("DELETE FROM fee_quotes WHERE quoted_at < $1", before)
+            .execute(transaction.conn())
+            .await?
+            .rows_affected();
+        let removed_overpayments = sqlx::query!(
+            "DELETE FROM fee_overpayments WHERE recorded_at < $1",
+            before
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.fee_audit.remove_records_before", start.elapsed());
+        Ok(removed_quotes + removed_overpayments)
+    }
+}
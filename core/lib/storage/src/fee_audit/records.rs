@@ -0,0 +1,55 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Fee quote returned by the fee ticker, which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeeQuote {
+    pub fee_type: Value,
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub gas_tx_amount: BigDecimal,
+    pub gas_price_wei: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub total_fee: BigDecimal,
+    pub quoted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredFeeQuote {
+    pub id: i64,
+    pub fee_type: Value,
+    pub address: Vec<u8>,
+    pub token_id: i32,
+    pub gas_tx_amount: BigDecimal,
+    pub gas_price_wei: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub total_fee: BigDecimal,
+    pub quoted_at: DateTime<Utc>,
+}
+
+/// Record of the transaction which paid more fee than it was quoted, which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeeOverpayment {
+    pub tx_hash: Vec<u8>,
+    pub token_id: i32,
+    pub quoted_fee: BigDecimal,
+    pub paid_fee: BigDecimal,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredFeeOverpayment {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub token_id: i32,
+    pub quoted_fee: BigDecimal,
+    pub paid_fee: BigDecimal,
+    pub recorded_at: DateTime<Utc>,
+}
@@ -0,0 +1,60 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::{NewFeeDiscount, StoredFeeDiscount};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee discounts schema stores the discounts granted to the specific accounts by the fee ticker.
+#[derive(Debug)]
+pub struct FeeDiscountsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeDiscountsSchema<'a, 'c> {
+    /// Stores the new discount. Returns the discount ID.
+    pub async fn store_discount(&mut self, discount: NewFeeDiscount) -> QueryResult<i32> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO fee_discounts ( address, fee_type, discount )
+            VALUES ( $1, $2, $3 )
+            RETURNING id
+            "#,
+            discount.address,
+            discount.fee_type,
+            discount.discount
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.fee_discounts.store_discount", start.elapsed());
+        Ok(id)
+    }
+
+    /// Loads all the discounts, ordered by ID.
+    pub async fn load_discounts(&mut self) -> QueryResult<Vec<StoredFeeDiscount>> {
+        let start = Instant::now();
+        let discounts =
+            sqlx::query_as!(StoredFeeDiscount, "SELECT * FROM fee_discounts ORDER BY id")
+                .fetch_all(self.0.conn())
+                .await?;
+
+        metrics::histogram!("sql.fee_discounts.load_discounts", start.elapsed());
+        Ok(discounts)
+    }
+
+    /// Removes the discount. Returns `false` if there is no discount with such ID.
+    pub async fn remove_discount(&mut self, id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM fee_discounts WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        metrics::histogram!("sql.fee_discounts.remove_discount", start.elapsed());
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,24 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Fee discount which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeeDiscount {
+    pub address: Vec<u8>,
+    pub fee_type: Option<Value>,
+    pub discount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredFeeDiscount {
+    pub id: i32,
+    pub address: Vec<u8>,
+    pub fee_type: Option<Value>,
+    pub discount: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,134 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{NewFeeSubsidy, NewSubsidySpend, StoredFeeSubsidy, StoredSubsidySpendTotal};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee subsidies schema stores the subsidies applied by the fee ticker and the subsidies
+/// spent on the transactions.
+#[derive(Debug)]
+pub struct FeeSubsidiesSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeSubsidiesSchema<'a, 'c> {
+    /// Stores the new subsidy, which is enabled right away. Returns the subsidy ID.
+    pub async fn store_subsidy(&mut self, subsidy: NewFeeSubsidy) -> QueryResult<i32> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO fee_subsidies ( token_id, fee_type, starts_at, ends_at )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING id
+            "#,
+            subsidy.token_id,
+            subsidy.fee_type,
+            subsidy.starts_at,
+            subsidy.ends_at
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.fee_subsidies.store_subsidy", start.elapsed());
+        Ok(id)
+    }
+
+    /// Loads all the subsidies, ordered by ID.
+    pub async fn load_subsidies(&mut self) -> QueryResult<Vec<StoredFeeSubsidy>> {
+        let start = Instant::now();
+        let subsidies =
+            sqlx::query_as!(StoredFeeSubsidy, "SELECT * FROM fee_subsidies ORDER BY id")
+                .fetch_all(self.0.conn())
+                .await?;
+
+        metrics::histogram!("sql.fee_subsidies.load_subsidies", start.elapsed());
+        Ok(subsidies)
+    }
+
+    /// Enables or disables the subsidy. Returns `false` if there is no subsidy with such ID.
+    pub async fn set_subsidy_enabled(&mut self, id: i32, enabled: bool) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            "UPDATE fee_subsidies SET enabled = $2 WHERE id = $1",
+            id,
+            enabled
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.fee_subsidies.set_subsidy_enabled", start.elapsed());
+        Ok(updated > 0)
+    }
+
+    /// Records the subsidy spent on the transaction. The transaction is recorded only once,
+    /// even if it's submitted again.
+    pub async fn store_subsidy_spend(&mut self, spend: NewSubsidySpend) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_subsidy_spends ( tx_hash, token_id, fee_type, paid_fee, subsidy )
+            VALUES ( $1, $2, $3, $4, $5 )
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            spend.tx_hash,
+            spend.token_id,
+            spend.fee_type,
+            spend.paid_fee,
+            spend.subsidy
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.fee_subsidies.store_subsidy_spend", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the total subsidies spent on the transactions recorded within the `[from, to]`
+    /// time range, per token. The spends are recorded once the transactions are accepted
+    /// into the mempool, so only the ones executed successfully are counted. The batch is
+    /// counted as one transaction.
+    pub async fn load_subsidy_spend_totals(
+        &mut self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<StoredSubsidySpendTotal>> {
+        let start = Instant::now();
+        let totals = sqlx::query_as!(
+            StoredSubsidySpendTotal,
+            r#"
+            SELECT token_id, count(*) as "txs_count!", sum(subsidy) as "total_subsidy!"
+            FROM fee_subsidy_spends
+            WHERE recorded_at >= $1 AND recorded_at <= $2
+                AND (
+                    EXISTS (
+                        SELECT 1 FROM executed_transactions
+                        WHERE executed_transactions.tx_hash = fee_subsidy_spends.tx_hash
+                            AND success = true
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM executed_transactions_archive
+                        WHERE executed_transactions_archive.tx_hash = fee_subsidy_spends.tx_hash
+                            AND success = true
+                    )
+                )
+            GROUP BY token_id
+            ORDER BY token_id
+            "#,
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.fee_subsidies.load_subsidy_spend_totals",
+            start.elapsed()
+        );
+        Ok(totals)
+    }
+}
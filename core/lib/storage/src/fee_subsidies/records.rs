@@ -0,0 +1,44 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Fee subsidy which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeeSubsidy {
+    pub token_id: Option<i32>,
+    pub fee_type: Option<Value>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredFeeSubsidy {
+    pub id: i32,
+    pub token_id: Option<i32>,
+    pub fee_type: Option<Value>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub enabled: bool,
+}
+
+/// Subsidy spent on the transaction, which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewSubsidySpend {
+    pub tx_hash: Vec<u8>,
+    pub token_id: i32,
+    pub fee_type: Value,
+    pub paid_fee: BigDecimal,
+    pub subsidy: BigDecimal,
+}
+
+/// Total subsidy spent on the transactions paying the fee in the token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredSubsidySpendTotal {
+    pub token_id: i32,
+    pub txs_count: i64,
+    pub total_subsidy: BigDecimal,
+}
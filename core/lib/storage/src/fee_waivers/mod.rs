@@ -0,0 +1,60 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::{NewFeeWaiver, StoredFeeWaiver};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Fee waivers schema stores the operations the fee ticker doesn't charge the fee for.
+#[derive(Debug)]
+pub struct FeeWaiversSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> FeeWaiversSchema<'a, 'c> {
+    /// Stores the new waiver. Returns the waiver ID.
+    pub async fn store_waiver(&mut self, waiver: NewFeeWaiver) -> QueryResult<i32> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO fee_waivers ( fee_type, token_id, unset_signing_key_only, expires_at )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING id
+            "#,
+            waiver.fee_type,
+            waiver.token_id,
+            waiver.unset_signing_key_only,
+            waiver.expires_at
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        metrics::histogram!("sql.fee_waivers.store_waiver", start.elapsed());
+        Ok(id)
+    }
+
+    /// Loads all the waivers, including the expired ones, ordered by ID.
+    pub async fn load_waivers(&mut self) -> QueryResult<Vec<StoredFeeWaiver>> {
+        let start = Instant::now();
+        let waivers = sqlx::query_as!(StoredFeeWaiver, "SELECT * FROM fee_waivers ORDER BY id")
+            .fetch_all(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.fee_waivers.load_waivers", start.elapsed());
+        Ok(waivers)
+    }
+
+    /// Removes the waiver. Returns `false` if there is no waiver with such ID.
+    pub async fn remove_waiver(&mut self, id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!("DELETE FROM fee_waivers WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        metrics::histogram!("sql.fee_waivers.remove_waiver", start.elapsed());
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,26 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Fee waiver which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewFeeWaiver {
+    pub fee_type: Value,
+    pub token_id: Option<i32>,
+    pub unset_signing_key_only: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredFeeWaiver {
+    pub id: i32,
+    pub fee_type: Value,
+    pub token_id: Option<i32>,
+    pub unset_signing_key_only: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,173 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::records::{ForcedExitPayment, NewForcedExitRequest, StoredForcedExitRequest};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Status of the request which is awaiting the payment.
+pub const FORCED_EXIT_REQUEST_PENDING: &str = "pending";
+/// Status of the paid request which `ForcedExit` transactions are not submitted yet.
+pub const FORCED_EXIT_REQUEST_PAID: &str = "paid";
+/// Status of the request which `ForcedExit` transactions are submitted.
+pub const FORCED_EXIT_REQUEST_FULFILLED: &str = "fulfilled";
+/// Status of the request which was not paid in time.
+pub const FORCED_EXIT_REQUEST_EXPIRED: &str = "expired";
+
+/// Forced exit requests schema stores the paid requests to withdraw the funds of the accounts
+/// which signing key is not set, along with the payments and the transactions fulfilling them.
+#[derive(Debug)]
+pub struct ForcedExitRequestsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
+    /// Stores the new request with the `pending` status.
+    pub async fn store_request(
+        &mut self,
+        request: NewForcedExitRequest,
+    ) -> QueryResult<StoredForcedExitRequest> {
+        let start = Instant::now();
+        let stored = sqlx::query_as!(
+            StoredForcedExitRequest,
+            "INSERT INTO forced_exit_requests (target, tokens, requester, price_in_wei, valid_until)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *",
+            request.target,
+            &request.tokens,
+            request.requester,
+            request.price_in_wei,
+            request.valid_until
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.store_request", start.elapsed());
+        Ok(stored)
+    }
+
+    /// Loads the request by its ID.
+    pub async fn get_request(&mut self, id: i64) -> QueryResult<Option<StoredForcedExitRequest>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredForcedExitRequest,
+            "SELECT * FROM forced_exit_requests WHERE id = $1",
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.get_request", start.elapsed());
+        Ok(request)
+    }
+
+    /// Loads the oldest requests which are either awaiting the payment or paid but not fulfilled yet.
+    pub async fn load_unfulfilled(
+        &mut self,
+        limit: i64,
+    ) -> QueryResult<Vec<StoredForcedExitRequest>> {
+        let start = Instant::now();
+        let requests = sqlx::query_as!(
+            StoredForcedExitRequest,
+            "SELECT * FROM forced_exit_requests WHERE status = $1 OR status = $2 ORDER BY id LIMIT $3",
+            FORCED_EXIT_REQUEST_PENDING,
+            FORCED_EXIT_REQUEST_PAID,
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.load_unfulfilled", start.elapsed());
+        Ok(requests)
+    }
+
+    /// Loads the successful transfers from `requester` to `recipient` executed since the given
+    /// moment, which were not used to pay for any request yet.
+    pub async fn find_payments(
+        &mut self,
+        requester: &[u8],
+        recipient: &[u8],
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<ForcedExitPayment>> {
+        let start = Instant::now();
+        let payments = sqlx::query_as!(
+            ForcedExitPayment,
+            r#"
+            SELECT
+                tx_hash,
+                (tx->>'token')::INTEGER AS "token!",
+                (tx->>'amount')::NUMERIC AS "amount!",
+                created_at
+            FROM executed_transactions
+            WHERE from_account = $1 AND to_account = $2 AND success = true
+                AND created_at >= $3 AND tx->>'type' = 'Transfer'
+                AND NOT EXISTS (
+                    SELECT 1 FROM forced_exit_requests
+                    WHERE payment_tx_hash = executed_transactions.tx_hash
+                )
+            ORDER BY created_at
+            "#,
+            requester,
+            recipient,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.find_payments", start.elapsed());
+        Ok(payments)
+    }
+
+    /// Marks the request as paid by the transfer with the given hash.
+    pub async fn mark_paid(&mut self, id: i64, payment_tx_hash: &[u8]) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE forced_exit_requests SET status = $1, payment_tx_hash = $2 WHERE id = $3",
+            FORCED_EXIT_REQUEST_PAID,
+            payment_tx_hash,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.mark_paid", start.elapsed());
+        Ok(())
+    }
+
+    /// Marks the request as fulfilled by the `ForcedExit` transactions with the given hashes.
+    pub async fn mark_fulfilled(&mut self, id: i64, fulfilled_by: &[String]) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE forced_exit_requests SET status = $1, fulfilled_by = $2, fulfilled_at = now()
+            WHERE id = $3",
+            FORCED_EXIT_REQUEST_FULFILLED,
+            fulfilled_by,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.mark_fulfilled", start.elapsed());
+        Ok(())
+    }
+
+    /// Marks the requests which were not paid until their deadline as expired.
+    /// Returns the number of the expired requests.
+    pub async fn expire_unpaid(&mut self, now: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let expired = sqlx::query!(
+            "UPDATE forced_exit_requests SET status = $1 WHERE status = $2 AND valid_until < $3",
+            FORCED_EXIT_REQUEST_EXPIRED,
+            FORCED_EXIT_REQUEST_PENDING,
+            now
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.forced_exit_requests.expire_unpaid", start.elapsed());
+        Ok(expired)
+    }
+}
@@ -0,0 +1,39 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredForcedExitRequest {
+    pub id: i64,
+    pub target: Vec<u8>,
+    pub tokens: Vec<i32>,
+    pub requester: Vec<u8>,
+    pub price_in_wei: BigDecimal,
+    pub status: String,
+    pub valid_until: DateTime<Utc>,
+    pub payment_tx_hash: Option<Vec<u8>>,
+    pub fulfilled_by: Option<Vec<String>>,
+    pub fulfilled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewForcedExitRequest {
+    pub target: Vec<u8>,
+    pub tokens: Vec<i32>,
+    pub requester: Vec<u8>,
+    pub price_in_wei: BigDecimal,
+    pub valid_until: DateTime<Utc>,
+}
+
+/// Executed transfer which can pay for the forced exit request.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ForcedExitPayment {
+    pub tx_hash: Vec<u8>,
+    pub token: i32,
+    pub amount: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
@@ -30,16 +30,28 @@
 //!
 //! There are the following sets of schemas:
 //!
+//! - api_keys, for the API keys, their usage and the webhooks registered by the key owners.
 //! - config, for the server config.
 //! - data_restore, for the data_restore crate.
 //! - ethereum, for the data associated with the Ethereum blockchain.
+//! - event_forwarder, for the progress of forwarding the block events to the message broker.
+//! - fee_audit, for the fee quotes and overpayments records used for the fee auditing.
+//! - fee_discounts, for the fee discounts granted to the specific accounts.
+//! - fee_subsidies, for the subsidies applied by the fee ticker.
+//! - fee_waivers, for the operations the fee ticker doesn't charge the fee for.
+//! - forced_exit_requests, for the paid requests to withdraw the funds of the inactive accounts.
+//! - listener, not a schema itself, but a subscriber for the events emitted by the database.
+//! - permit_deposits, for the deposits authorized by the EIP-2612 permit and relayed to L1.
 //! - prover, for the data on prover jobs, proofs, etc.
+//! - pruning, for moving the outdated data out of the main tables.
+//! - token_gas_costs, for the gas costs of the operations overridden for the specific tokens.
 //! - tokens, for storing and loading known tokens.
 //! - chain - the biggest one, which includes several schemas for the ZKSync sidechain itself.
 //!
 //! The chain module includes the following schemas:
 //!
 //! - account, for storing and loading account data.
+//! - balance_snapshots, per-block account balances and total balances of tokens for the analytical queries.
 //! - block, the main one, which implements the logic of the block creation.
 //! - operations, the transactions storage.
 //! - operations_ext, a set of getters for the operations, more specific and convenient to use than operations has.
@@ -86,14 +98,25 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod api_keys;
 pub mod chain;
 pub mod config;
 pub mod connection;
 pub mod data_restore;
 pub mod diff;
 pub mod ethereum;
+pub mod event_forwarder;
+pub mod fee_audit;
+pub mod fee_discounts;
+pub mod fee_subsidies;
+pub mod fee_waivers;
+pub mod forced_exit_requests;
+pub mod listener;
+pub mod permit_deposits;
 pub mod prover;
+pub mod pruning;
 pub mod test_data;
+pub mod token_gas_costs;
 pub mod tokens;
 
 pub use crate::connection::ConnectionPool;
@@ -182,6 +205,11 @@ impl<'a> StorageProcessor<'a> {
         }
     }
 
+    /// Gains access to the `ApiKeys` schema.
+    pub fn api_keys_schema(&mut self) -> api_keys::ApiKeysSchema<'_, 'a> {
+        api_keys::ApiKeysSchema(self)
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -202,11 +230,58 @@ impl<'a> StorageProcessor<'a> {
         ethereum::EthereumSchema(self)
     }
 
+    /// Gains access to the `EventForwarder` schema.
+    pub fn event_forwarder_schema(&mut self) -> event_forwarder::EventForwarderSchema<'_, 'a> {
+        event_forwarder::EventForwarderSchema(self)
+    }
+
+    /// Gains access to the `FeeAudit` schema.
+    pub fn fee_audit_schema(&mut self) -> fee_audit::FeeAuditSchema<'_, 'a> {
+        fee_audit::FeeAuditSchema(self)
+    }
+
+    /// Gains access to the `FeeDiscounts` schema.
+    pub fn fee_discounts_schema(&mut self) -> fee_discounts::FeeDiscountsSchema<'_, 'a> {
+        fee_discounts::FeeDiscountsSchema(self)
+    }
+
+    /// Gains access to the `FeeSubsidies` schema.
+    pub fn fee_subsidies_schema(&mut self) -> fee_subsidies::FeeSubsidiesSchema<'_, 'a> {
+        fee_subsidies::FeeSubsidiesSchema(self)
+    }
+
+    /// Gains access to the `FeeWaivers` schema.
+    pub fn fee_waivers_schema(&mut self) -> fee_waivers::FeeWaiversSchema<'_, 'a> {
+        fee_waivers::FeeWaiversSchema(self)
+    }
+
+    /// Gains access to the `ForcedExitRequests` schema.
+    pub fn forced_exit_requests_schema(
+        &mut self,
+    ) -> forced_exit_requests::ForcedExitRequestsSchema<'_, 'a> {
+        forced_exit_requests::ForcedExitRequestsSchema(self)
+    }
+
+    /// Gains access to the `PermitDeposits` schema.
+    pub fn permit_deposits_schema(&mut self) -> permit_deposits::PermitDepositsSchema<'_, 'a> {
+        permit_deposits::PermitDepositsSchema(self)
+    }
+
     /// Gains access to the `Prover` schema.
     pub fn prover_schema(&mut self) -> prover::ProverSchema<'_, 'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `Pruning` schema.
+    pub fn pruning_schema(&mut self) -> pruning::PruningSchema<'_, 'a> {
+        pruning::PruningSchema(self)
+    }
+
+    /// Gains access to the `TokenGasCosts` schema.
+    pub fn token_gas_costs_schema(&mut self) -> token_gas_costs::TokenGasCostsSchema<'_, 'a> {
+        token_gas_costs::TokenGasCostsSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
@@ -0,0 +1,116 @@
+//! Listener for the notifications emitted by the database.
+//!
+//! Database triggers send `NOTIFY` messages when aggregated operations get confirmed
+//! and when the pending block or the mempool are changed. Components interested in
+//! these events can wait for them instead of polling the database in a tight loop.
+//!
+//! Note that notifications are not stored anywhere: events sent while the listener
+//! was disconnected are lost. Thus listeners are expected to re-check the database
+//! state after reconnecting or from time to time.
+
+// Built-in deps
+use std::{fmt, str::FromStr};
+// External imports
+use anyhow::format_err;
+use serde::Deserialize;
+use sqlx::postgres::{PgListener, PgNotification};
+// Workspace imports
+use zksync_types::{aggregated_operations::AggregatedActionType, BlockNumber};
+// Local imports
+use crate::QueryResult;
+
+/// Channel used to notify about the confirmed aggregated operations.
+pub const BLOCKS_CONFIRMED_CHANNEL: &str = "zksync_blocks_confirmed";
+/// Channel used to notify about the pending block updates.
+pub const PENDING_BLOCK_UPDATED_CHANNEL: &str = "zksync_pending_block_updated";
+/// Channel used to notify about the transactions added to or removed from the mempool.
+pub const MEMPOOL_CHANGED_CHANNEL: &str = "zksync_mempool_changed";
+
+/// Event received from the database.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageEvent {
+    /// Aggregated operation for the blocks range was confirmed on Ethereum.
+    BlocksConfirmed {
+        action_type: AggregatedActionType,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    },
+    /// Pending block was created or updated.
+    PendingBlockUpdated,
+    /// Transactions were added to or removed from the mempool.
+    MempoolChanged,
+}
+
+/// Payload of the `BLOCKS_CONFIRMED_CHANNEL` notification.
+#[derive(Debug, Deserialize)]
+struct BlocksConfirmedPayload {
+    action_type: String,
+    from_block: i64,
+    to_block: i64,
+}
+
+impl StorageEvent {
+    fn from_notification(notification: &PgNotification) -> QueryResult<Self> {
+        match notification.channel() {
+            BLOCKS_CONFIRMED_CHANNEL => {
+                let payload: BlocksConfirmedPayload = serde_json::from_str(notification.payload())?;
+                let action_type =
+                    AggregatedActionType::from_str(&payload.action_type).map_err(|e| {
+                        format_err!("Unexpected action type in the notification: {}", e)
+                    })?;
+
+                Ok(Self::BlocksConfirmed {
+                    action_type,
+                    from_block: BlockNumber(payload.from_block as u32),
+                    to_block: BlockNumber(payload.to_block as u32),
+                })
+            }
+            PENDING_BLOCK_UPDATED_CHANNEL => Ok(Self::PendingBlockUpdated),
+            MEMPOOL_CHANGED_CHANNEL => Ok(Self::MempoolChanged),
+            channel => Err(format_err!(
+                "Notification from the unknown channel: {}",
+                channel
+            )),
+        }
+    }
+}
+
+/// Listener of the database events.
+///
+/// Uses a dedicated connection to the database, which isn't taken from the connection pool.
+pub struct StorageListener {
+    inner: PgListener,
+}
+
+impl fmt::Debug for StorageListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageListener").finish()
+    }
+}
+
+impl StorageListener {
+    /// Connects to the database and subscribes to all the storage events.
+    pub async fn connect() -> QueryResult<Self> {
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let mut inner = PgListener::connect(&database_url).await?;
+        inner
+            .listen_all(vec![
+                BLOCKS_CONFIRMED_CHANNEL,
+                PENDING_BLOCK_UPDATED_CHANNEL,
+                MEMPOOL_CHANGED_CHANNEL,
+            ])
+            .await?;
+
+        Ok(Self { inner })
+    }
+
+    /// Waits for the next event.
+    ///
+    /// If the connection was lost, an error is returned and the listener will
+    /// try to reconnect upon the next call.
+    pub async fn recv(&mut self) -> QueryResult<StorageEvent> {
+        let notification = self.inner.recv().await?;
+        metrics::counter!("sql.listener.notifications", 1);
+        StorageEvent::from_notification(&notification)
+    }
+}
@@ -0,0 +1,111 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::{NewPermitDeposit, StoredPermitDeposit};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Status of the deposit which is not sent to L1 yet.
+pub const PERMIT_DEPOSIT_PENDING: &str = "pending";
+/// Status of the deposit which transaction is sent to L1.
+pub const PERMIT_DEPOSIT_SENT: &str = "sent";
+/// Status of the deposit which could not be sent to L1.
+pub const PERMIT_DEPOSIT_FAILED: &str = "failed";
+
+/// Permit deposits schema stores the deposits authorized by the EIP-2612 permit signatures,
+/// which are sent to L1 by the relayer on behalf of the tokens owner.
+#[derive(Debug)]
+pub struct PermitDepositsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PermitDepositsSchema<'a, 'c> {
+    /// Stores the new deposit request with the `pending` status.
+    pub async fn store_request(
+        &mut self,
+        request: NewPermitDeposit,
+    ) -> QueryResult<StoredPermitDeposit> {
+        let start = Instant::now();
+        let stored = sqlx::query_as!(
+            StoredPermitDeposit,
+            "INSERT INTO permit_deposits (token_id, owner, amount, fee, deadline, signature)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *",
+            request.token_id,
+            request.owner,
+            request.amount,
+            request.fee,
+            request.deadline,
+            request.signature
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.permit_deposits.store_request", start.elapsed());
+        Ok(stored)
+    }
+
+    /// Loads the deposit request by its ID.
+    pub async fn get_request(&mut self, id: i64) -> QueryResult<Option<StoredPermitDeposit>> {
+        let start = Instant::now();
+        let request = sqlx::query_as!(
+            StoredPermitDeposit,
+            "SELECT * FROM permit_deposits WHERE id = $1",
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.permit_deposits.get_request", start.elapsed());
+        Ok(request)
+    }
+
+    /// Loads the oldest requests which are not sent to L1 yet.
+    pub async fn load_pending(&mut self, limit: i64) -> QueryResult<Vec<StoredPermitDeposit>> {
+        let start = Instant::now();
+        let requests = sqlx::query_as!(
+            StoredPermitDeposit,
+            "SELECT * FROM permit_deposits WHERE status = $1 ORDER BY id LIMIT $2",
+            PERMIT_DEPOSIT_PENDING,
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.permit_deposits.load_pending", start.elapsed());
+        Ok(requests)
+    }
+
+    /// Marks the request as sent to L1 within the transaction with the given hash.
+    pub async fn mark_sent(&mut self, id: i64, eth_tx_hash: &[u8]) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE permit_deposits SET status = $1, eth_tx_hash = $2 WHERE id = $3",
+            PERMIT_DEPOSIT_SENT,
+            eth_tx_hash,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.permit_deposits.mark_sent", start.elapsed());
+        Ok(())
+    }
+
+    /// Marks the request as failed, so it won't be relayed anymore.
+    pub async fn mark_failed(&mut self, id: i64, error: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE permit_deposits SET status = $1, error = $2 WHERE id = $3",
+            PERMIT_DEPOSIT_FAILED,
+            error,
+            id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.permit_deposits.mark_failed", start.elapsed());
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredPermitDeposit {
+    pub id: i64,
+    pub token_id: i32,
+    pub owner: Vec<u8>,
+    pub amount: BigDecimal,
+    pub fee: BigDecimal,
+    pub deadline: i64,
+    pub signature: Vec<u8>,
+    pub status: String,
+    pub eth_tx_hash: Option<Vec<u8>>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPermitDeposit {
+    pub token_id: i32,
+    pub owner: Vec<u8>,
+    pub amount: BigDecimal,
+    pub fee: BigDecimal,
+    pub deadline: i64,
+    pub signature: Vec<u8>,
+}
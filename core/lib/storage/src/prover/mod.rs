@@ -1,5 +1,5 @@
 // Built-in deps
-use std::time::Instant;
+use std::time::{Duration, Instant};
 // External imports
 use anyhow::format_err;
 use sqlx::Done;
@@ -34,7 +34,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .count
         .unwrap_or(0) as u32;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "pending_jobs_count");
+        metrics::histogram!("sql.prover.pending_jobs_count", start.elapsed());
         Ok(pending_jobs_count)
     }
 
@@ -65,19 +65,32 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
-        sqlx::query!(
+    /// Returns the jobs whose lease was not renewed by the prover for `lease_timeout`
+    /// back to the queue, so they can be given to another prover.
+    /// Returns the amount of the reassigned jobs.
+    pub async fn mark_stale_jobs_as_idle(&mut self, lease_timeout: Duration) -> QueryResult<u64> {
+        let start = Instant::now();
+        let reassigned_jobs = sqlx::query!(
             "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 and (now() - updated_at) >= interval '120 seconds'",
+            WHERE job_status = $2 and (now() - updated_at) >= make_interval(secs => $3)",
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
+            lease_timeout.as_secs_f64(),
         )
         .execute(self.0.conn())
-        .await?;
-        Ok(())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.prover.mark_stale_jobs_as_idle", start.elapsed());
+        Ok(reassigned_jobs)
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Leases the idle job with the highest priority to the prover. The lease has to be
+    /// renewed with `record_prover_is_working`, otherwise the job is given to another prover.
+    pub async fn get_idle_prover_job_from_job_queue(
+        &mut self,
+        prover_name: &str,
+    ) -> QueryResult<Option<ProverJob>> {
         let start = Instant::now();
         // Select the block to prove.
         let mut transaction = self.0.start_transaction().await?;
@@ -102,10 +115,11 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             sqlx::query!(
                 r#"
                 UPDATE prover_job_queue
-                SET (job_status, updated_at, updated_by) = ($1, now(), 'server_give_job')
-                WHERE id = $2;
+                SET (job_status, updated_at, updated_by) = ($1, now(), $2)
+                WHERE id = $3;
             "#,
                 ProverJobStatus::InProgress.to_number(),
+                prover_name,
                 job.id,
             )
             .execute(transaction.conn())
@@ -121,29 +135,36 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             None
         };
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_job_from_job_queue");
+        metrics::histogram!(
+            "sql.prover.get_idle_prover_job_from_job_queue",
+            start.elapsed()
+        );
         Ok(prover_job)
     }
 
-    /// Updates the state of ongoing prover job.
+    /// Renews the lease of the ongoing prover job.
+    /// Returns `false` if the job is no longer leased to the prover, e.g. it was
+    /// considered stale and given to another prover.
     pub async fn record_prover_is_working(
         &mut self,
         job_id: i32,
         prover_name: &str,
-    ) -> QueryResult<()> {
+    ) -> QueryResult<bool> {
         let start = Instant::now();
-        sqlx::query!(
+        let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, updated_by) = (now(), $1)
-            WHERE id = $2",
-            prover_name.to_string(),
+            SET updated_at = now()
+            WHERE id = $1 and updated_by = $2 and job_status = $3",
             job_id,
+            prover_name,
+            ProverJobStatus::InProgress.to_number(),
         )
         .execute(self.0.conn())
-        .await?;
+        .await?
+        .rows_affected();
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
-        Ok(())
+        metrics::histogram!("sql.prover.record_prover_is_working", start.elapsed());
+        Ok(updated_rows > 0)
     }
 
     /// Marks the prover as stopped.
@@ -160,7 +181,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
+        metrics::histogram!("sql.prover.record_prover_stop", start.elapsed());
         Ok(())
     }
 
@@ -199,7 +220,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_proof");
+        metrics::histogram!("sql.prover.store_proof", start.elapsed());
         Ok(())
     }
 
@@ -240,7 +261,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_aggregated_proof");
+        metrics::histogram!("sql.prover.store_aggregated_proof", start.elapsed());
         Ok(())
     }
 
@@ -259,7 +280,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_proof");
+        metrics::histogram!("sql.prover.load_proof", start.elapsed());
         Ok(proof)
     }
 
@@ -280,7 +301,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof");
+        metrics::histogram!("sql.prover.load_aggregated_proof", start.elapsed());
         Ok(proof)
     }
 
@@ -303,7 +324,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "store_witness");
+        metrics::histogram!("sql.prover.store_witness", start.elapsed());
         Ok(())
     }
 
@@ -321,7 +342,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
+        metrics::histogram!("sql.prover.get_witness", start.elapsed());
         Ok(block_witness
             .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
     }
@@ -0,0 +1,185 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{prover::ProverJobStatus, BlockNumber};
+// Local imports
+use self::records::PruningStats;
+use crate::{chain::block::BlockSchema, QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Pruning schema is capable of moving the outdated data out of the main tables.
+///
+/// Only the data that is not required for the server operation is pruned: executed transactions
+/// and priority operations (the processed L1 events) are moved to the archive tables, proofs of
+/// the executed blocks are moved to the archive tables and the witnesses are removed, since they
+/// can be recalculated if needed. The API lookups fall back to the archive tables, so the archived
+/// operations can still be requested. Account state is never touched, so the exit (and data
+/// restore) capabilities are not affected.
+#[derive(Debug)]
+pub struct PruningSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> PruningSchema<'a, 'c> {
+    /// Prunes the data of all the blocks executed on Ethereum more than `retention_blocks` blocks ago.
+    pub async fn prune_with_retention(
+        &mut self,
+        retention_blocks: u32,
+    ) -> QueryResult<PruningStats> {
+        let start = Instant::now();
+        let last_executed_block = BlockSchema(self.0)
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let stats = if *last_executed_block > retention_blocks {
+            self.prune(last_executed_block - retention_blocks).await?
+        } else {
+            PruningStats::default()
+        };
+
+        metrics::histogram!("sql.pruning.prune_with_retention", start.elapsed());
+        Ok(stats)
+    }
+
+    /// Prunes the data of all the blocks up to `last_block` (inclusive).
+    /// The caller is responsible for checking that these blocks are executed on Ethereum.
+    pub async fn prune(&mut self, last_block: BlockNumber) -> QueryResult<PruningStats> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let archived_transactions = PruningSchema(&mut transaction)
+            .archive_executed_transactions(last_block)
+            .await?;
+        let archived_priority_ops = PruningSchema(&mut transaction)
+            .archive_executed_priority_operations(last_block)
+            .await?;
+        let archived_proofs = PruningSchema(&mut transaction)
+            .archive_proofs(last_block)
+            .await?;
+        let removed_proving_data = PruningSchema(&mut transaction)
+            .remove_proving_data(last_block)
+            .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.pruning.prune", start.elapsed());
+        Ok(PruningStats {
+            last_pruned_block: last_block,
+            archived_transactions,
+            archived_priority_ops,
+            archived_proofs,
+            removed_proving_data,
+        })
+    }
+
+    /// Moves the executed transactions of the blocks up to `last_block` to the archive table.
+    async fn archive_executed_transactions(&mut self, last_block: BlockNumber) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let archived = sqlx::query!(
+            "WITH archived AS (
+                DELETE FROM executed_transactions
+                WHERE block_number <= $1
+                RETURNING *
+            )
+            INSERT INTO executed_transactions_archive
+            SELECT * FROM archived",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.pruning.archive_executed_transactions", start.elapsed());
+        Ok(archived)
+    }
+
+    /// Moves the executed priority operations of the blocks up to `last_block` to the archive table.
+    async fn archive_executed_priority_operations(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let archived = sqlx::query!(
+            "WITH archived AS (
+                DELETE FROM executed_priority_operations
+                WHERE block_number <= $1
+                RETURNING *
+            )
+            INSERT INTO executed_priority_operations_archive
+            SELECT * FROM archived",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.pruning.archive_executed_priority_operations",
+            start.elapsed()
+        );
+        Ok(archived)
+    }
+
+    /// Moves the single and aggregated proofs of the blocks up to `last_block` to the archive tables.
+    async fn archive_proofs(&mut self, last_block: BlockNumber) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let archived_single = sqlx::query!(
+            "WITH archived AS (
+                DELETE FROM proofs
+                WHERE block_number <= $1
+                RETURNING *
+            )
+            INSERT INTO proofs_archive
+            SELECT * FROM archived",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        let archived_aggregated = sqlx::query!(
+            "WITH archived AS (
+                DELETE FROM aggregated_proofs
+                WHERE last_block <= $1
+                RETURNING *
+            )
+            INSERT INTO aggregated_proofs_archive
+            SELECT * FROM archived",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.pruning.archive_proofs", start.elapsed());
+        Ok(archived_single + archived_aggregated)
+    }
+
+    /// Removes the witnesses and finished prover jobs of the blocks up to `last_block`.
+    async fn remove_proving_data(&mut self, last_block: BlockNumber) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let removed_witnesses = sqlx::query!(
+            "DELETE FROM block_witness WHERE block <= $1",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        let removed_jobs = sqlx::query!(
+            "DELETE FROM prover_job_queue WHERE last_block <= $1 AND job_status = $2",
+            i64::from(*last_block),
+            ProverJobStatus::Done.to_number()
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.pruning.remove_proving_data", start.elapsed());
+        Ok(removed_witnesses + removed_jobs)
+    }
+}
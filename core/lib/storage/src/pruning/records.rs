@@ -0,0 +1,20 @@
+// External imports
+use serde::{Deserialize, Serialize};
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+
+/// Summary of the pruning run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PruningStats {
+    /// Last block which data was pruned.
+    pub last_pruned_block: BlockNumber,
+    /// Amount of executed transactions moved to the archive.
+    pub archived_transactions: u64,
+    /// Amount of executed priority operations moved to the archive.
+    pub archived_priority_ops: u64,
+    /// Amount of single and aggregated proofs moved to the archive.
+    pub archived_proofs: u64,
+    /// Amount of removed block witnesses and finished prover jobs.
+    pub removed_proving_data: u64,
+}
@@ -0,0 +1,179 @@
+// External imports
+use chrono::{Duration, DurationRound, Utc};
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks the save&load routine for the API keys, as well as the key revocation.
+#[db_test]
+async fn api_keys(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let key_hash = vec![0xAA; 32];
+    let stored = storage
+        .api_keys_schema()
+        .store_api_key(&key_hash, "explorer", Some(1000))
+        .await?;
+    assert_eq!(stored.name, "explorer");
+    assert_eq!(stored.requests_quota, Some(1000));
+    assert_eq!(stored.revoked_at, None);
+
+    let loaded = storage.api_keys_schema().get_api_key(&key_hash).await?;
+    assert_eq!(loaded, Some(stored.clone()));
+    assert!(storage
+        .api_keys_schema()
+        .get_api_key(&[0xBB; 32])
+        .await?
+        .is_none());
+
+    // Revoked keys are not loaded and can't be revoked twice.
+    assert!(storage.api_keys_schema().revoke_api_key(stored.id).await?);
+    assert!(!storage.api_keys_schema().revoke_api_key(stored.id).await?);
+    assert!(storage
+        .api_keys_schema()
+        .get_api_key(&key_hash)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the usage counters are accumulated per quota period and removed once outdated.
+#[db_test]
+async fn api_key_usage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let api_key = storage
+        .api_keys_schema()
+        .store_api_key(&[0xAA; 32], "wallet", None)
+        .await?;
+    let period = Utc::now().duration_trunc(Duration::days(1)).unwrap();
+    let previous_period = period - Duration::days(1);
+
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .get_usage(api_key.id, period)
+            .await?,
+        0
+    );
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .increment_usage(api_key.id, period, 1)
+            .await?,
+        1
+    );
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .increment_usage(api_key.id, period, 5)
+            .await?,
+        6
+    );
+    storage
+        .api_keys_schema()
+        .increment_usage(api_key.id, previous_period, 10)
+        .await?;
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .get_usage(api_key.id, period)
+            .await?,
+        6
+    );
+
+    // Only the previous period counter is removed.
+    let removed = storage
+        .api_keys_schema()
+        .remove_usage_before(period)
+        .await?;
+    assert_eq!(removed, 1);
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .get_usage(api_key.id, previous_period)
+            .await?,
+        0
+    );
+    assert_eq!(
+        storage
+            .api_keys_schema()
+            .get_usage(api_key.id, period)
+            .await?,
+        6
+    );
+
+    Ok(())
+}
+
+/// Checks the registration, lookup and removal of the webhooks.
+#[db_test]
+async fn webhooks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_key = storage
+        .api_keys_schema()
+        .store_api_key(&[0xAA; 32], "first", None)
+        .await?;
+    let second_key = storage
+        .api_keys_schema()
+        .store_api_key(&[0xBB; 32], "second", None)
+        .await?;
+
+    let first_webhook = storage
+        .api_keys_schema()
+        .store_webhook(
+            first_key.id,
+            "https://first.example/hook",
+            &["block_verified".to_string(), "tx_executed".to_string()],
+        )
+        .await?;
+    let second_webhook = storage
+        .api_keys_schema()
+        .store_webhook(
+            second_key.id,
+            "https://second.example/hook",
+            &["tx_executed".to_string()],
+        )
+        .await?;
+
+    let loaded = storage
+        .api_keys_schema()
+        .load_webhooks(first_key.id)
+        .await?;
+    assert_eq!(loaded, vec![first_webhook.clone()]);
+
+    let loaded = storage
+        .api_keys_schema()
+        .load_event_webhooks("tx_executed")
+        .await?;
+    assert_eq!(loaded, vec![first_webhook.clone(), second_webhook.clone()]);
+
+    // Webhooks of the revoked keys are not notified.
+    storage
+        .api_keys_schema()
+        .revoke_api_key(second_key.id)
+        .await?;
+    let loaded = storage
+        .api_keys_schema()
+        .load_event_webhooks("tx_executed")
+        .await?;
+    assert_eq!(loaded, vec![first_webhook.clone()]);
+
+    // Webhook can only be removed by its owner.
+    assert!(
+        !storage
+            .api_keys_schema()
+            .remove_webhook(second_key.id, first_webhook.id)
+            .await?
+    );
+    assert!(
+        storage
+            .api_keys_schema()
+            .remove_webhook(first_key.id, first_webhook.id)
+            .await?
+    );
+    assert!(storage
+        .api_keys_schema()
+        .load_event_webhooks("block_verified")
+        .await?
+        .is_empty());
+
+    Ok(())
+}
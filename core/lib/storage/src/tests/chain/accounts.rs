@@ -154,3 +154,72 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the account state can be obtained for any committed block.
+#[db_test]
+async fn account_state_at_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let block_size = 100;
+
+    let (accounts_block_1, updates_block_1) = apply_random_updates(AccountMap::default(), &mut rng);
+    let (_, updates_block_2) = apply_random_updates(accounts_block_1.clone(), &mut rng);
+
+    BlockSchema(&mut storage)
+        .save_block(gen_sample_block(
+            BlockNumber(1),
+            block_size,
+            Default::default(),
+        ))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates_block_1, 0)
+        .await?;
+
+    // Remember the account states after the first block.
+    let mut states_block_1 = Vec::new();
+    for account_id in accounts_block_1.keys() {
+        let state = AccountSchema(&mut storage)
+            .last_committed_state_for_account(*account_id)
+            .await?;
+        assert!(state.is_some());
+        states_block_1.push((*account_id, state));
+    }
+
+    BlockSchema(&mut storage)
+        .save_block(gen_sample_block(
+            BlockNumber(2),
+            block_size,
+            Default::default(),
+        ))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates_block_2, 0)
+        .await?;
+
+    for (account_id, state_block_1) in states_block_1 {
+        // Accounts did not exist before the first block.
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .account_state_at_block(account_id, BlockNumber(0))
+                .await?,
+            None
+        );
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .account_state_at_block(account_id, BlockNumber(1))
+                .await?,
+            state_block_1
+        );
+        assert_eq!(
+            AccountSchema(&mut storage)
+                .account_state_at_block(account_id, BlockNumber(2))
+                .await?,
+            AccountSchema(&mut storage)
+                .last_committed_state_for_account(account_id)
+                .await?
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// External imports
+use num::{BigInt, BigUint};
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::{AccountMap, BlockNumber, TokenId};
+// Local imports
+use super::block::apply_random_updates;
+use crate::{
+    chain::{balance_snapshots::BalanceSnapshotsSchema, state::StateSchema},
+    tests::{create_rng, db_test},
+    QueryResult, StorageProcessor,
+};
+
+fn to_decimal(value: BigUint) -> BigDecimal {
+    BigDecimal::from(BigInt::from(value))
+}
+
+/// Checks that the balance snapshots of the committed blocks match the account states
+/// as of these blocks, and that the total balances are accumulated correctly.
+#[db_test]
+async fn balance_snapshots(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let mut accounts = AccountMap::default();
+    let mut states = Vec::new();
+    for block_number in 1..=3 {
+        let (new_accounts, updates) = apply_random_updates(accounts, &mut rng);
+        StateSchema(&mut storage)
+            .commit_state_update(BlockNumber(block_number), &updates, 0)
+            .await?;
+        accounts = new_accounts;
+        states.push(accounts.clone());
+    }
+
+    // Snapshots can't be populated out of order.
+    assert!(BalanceSnapshotsSchema(&mut storage)
+        .populate_block(BlockNumber(2))
+        .await
+        .is_err());
+
+    for block_number in 1..=3 {
+        BalanceSnapshotsSchema(&mut storage)
+            .populate_block(BlockNumber(block_number))
+            .await?;
+    }
+    assert_eq!(
+        BalanceSnapshotsSchema(&mut storage)
+            .last_populated_block()
+            .await?,
+        BlockNumber(3)
+    );
+
+    for (block_number, state) in (1..=3).map(BlockNumber).zip(states) {
+        let mut expected_total = BigUint::from(0u32);
+        for (account_id, account) in state.iter() {
+            let balances = BalanceSnapshotsSchema(&mut storage)
+                .account_balances_at_block(*account_id, block_number)
+                .await?;
+            assert_eq!(balances.len(), 1);
+            assert_eq!(balances[0].coin_id, 0);
+            assert_eq!(
+                balances[0].balance,
+                to_decimal(account.get_balance(TokenId(0)))
+            );
+            expected_total += account.get_balance(TokenId(0));
+        }
+
+        let total_balances = BalanceSnapshotsSchema(&mut storage)
+            .total_balances_at_block(block_number)
+            .await?;
+        assert_eq!(total_balances.len(), 1);
+        assert_eq!(total_balances[0].total_balance, to_decimal(expected_total));
+    }
+
+    let history = BalanceSnapshotsSchema(&mut storage)
+        .total_balance_history(TokenId(0), BlockNumber(2), BlockNumber(3))
+        .await?;
+    assert_eq!(
+        history
+            .iter()
+            .map(|snapshot| snapshot.block_number)
+            .collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+
+    Ok(())
+}
@@ -929,3 +929,36 @@ async fn test_operations_counter(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the block export contains the block itself and its state diff.
+#[db_test]
+async fn test_load_block_export(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+    let (_, updates) = apply_random_updates(AccountMap::default(), &mut rng);
+
+    assert!(BlockSchema(&mut storage)
+        .load_block_export(BlockNumber(1))
+        .await?
+        .is_none());
+
+    BlockSchema(&mut storage)
+        .save_block(gen_sample_block(
+            BlockNumber(1),
+            BLOCK_SIZE_CHUNKS,
+            Default::default(),
+        ))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let export = BlockSchema(&mut storage)
+        .load_block_export(BlockNumber(1))
+        .await?
+        .expect("Block should be exported");
+    assert_eq!(export.block.block_number, BlockNumber(1));
+    assert_eq!(export.state_diff.len(), updates.len());
+    assert!(export.proof.is_none());
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 mod accounts;
+mod balance_snapshots;
 mod block;
 mod mempool;
 mod operations;
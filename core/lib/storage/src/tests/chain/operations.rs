@@ -275,6 +275,165 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that the transaction hashes stay unique when the transaction is resent
+/// in a block belonging to the other partition.
+#[db_test]
+async fn transaction_resent_across_partitions(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    const FAILED_BLOCK_NUMBER: i64 = 1;
+    // Partitions span 100 000 blocks.
+    const RESENT_BLOCK_NUMBER: i64 = 100_001;
+
+    let failed_tx = NewExecutedTransaction {
+        block_number: FAILED_BLOCK_NUMBER,
+        tx_hash: vec![0x12, 0xAD, 0xBE, 0xEF],
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success: false,
+        fail_reason: Some("Not enough balance".to_string()),
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+    };
+    let mut resent_tx = failed_tx.clone();
+    resent_tx.block_number = RESENT_BLOCK_NUMBER;
+    resent_tx.success = true;
+    resent_tx.fail_reason = None;
+    resent_tx.block_index = Some(0);
+    // Failed again after being executed, must be ignored.
+    let mut failed_again = failed_tx.clone();
+    failed_again.block_number = RESENT_BLOCK_NUMBER + 1;
+
+    OperationsSchema(&mut storage)
+        .store_executed_tx(failed_tx)
+        .await?;
+    OperationsSchema(&mut storage)
+        .store_executed_txs(vec![resent_tx.clone()])
+        .await?;
+    OperationsSchema(&mut storage)
+        .store_executed_tx(failed_again)
+        .await?;
+
+    let loaded_tx = OperationsSchema(&mut storage)
+        .get_executed_operation(resent_tx.tx_hash.as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(loaded_tx.block_number, RESENT_BLOCK_NUMBER);
+    assert_eq!(loaded_tx.success, true);
+
+    for (block_number, expected_txs) in &[
+        (FAILED_BLOCK_NUMBER, 0),
+        (RESENT_BLOCK_NUMBER, 1),
+        (RESENT_BLOCK_NUMBER + 1, 0),
+    ] {
+        let block_txs = BlockSchema(&mut storage)
+            .get_block_transactions(BlockNumber(*block_number as u32))
+            .await?;
+        assert_eq!(block_txs.len(), *expected_txs);
+    }
+
+    Ok(())
+}
+
+/// Checks that the bulk insertion of executed operations follows the same rules
+/// as the row-by-row one.
+#[db_test]
+async fn bulk_executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_NUMBER: i64 = 1;
+
+    let executed_tx = NewExecutedTransaction {
+        block_number: BLOCK_NUMBER,
+        tx_hash: vec![0x12, 0xAD, 0xBE, 0xEF],
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success: true,
+        fail_reason: None,
+        block_index: Some(0),
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+    };
+    // Failed transaction which is later resent successfully within the same block.
+    let mut resent_tx = executed_tx.clone();
+    resent_tx.tx_hash = vec![0x13, 0xAD, 0xBE, 0xEF];
+    resent_tx.success = false;
+    resent_tx.fail_reason = Some("Not enough balance".to_string());
+    resent_tx.block_index = None;
+    let mut resent_tx_success = resent_tx.clone();
+    resent_tx_success.success = true;
+    resent_tx_success.fail_reason = None;
+    resent_tx_success.block_index = Some(1);
+    // Failed transaction that can't replace the successful one.
+    let mut failed_duplicate = executed_tx.clone();
+    failed_duplicate.success = false;
+
+    let executed_priority_op = NewExecutedPriorityOperation {
+        block_number: BLOCK_NUMBER,
+        block_index: 2,
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: Default::default(),
+        priority_op_serialid: 0,
+        deadline_block: 100,
+        eth_hash: vec![0xDE, 0xAD, 0xBE, 0xEF],
+        eth_block: 10,
+        created_at: chrono::Utc::now(),
+    };
+
+    OperationsSchema(&mut storage)
+        .store_executed_txs(vec![
+            executed_tx.clone(),
+            resent_tx,
+            resent_tx_success.clone(),
+            failed_duplicate,
+        ])
+        .await?;
+    // Duplicated priority operations are ignored.
+    OperationsSchema(&mut storage)
+        .store_executed_priority_ops(vec![
+            executed_priority_op.clone(),
+            executed_priority_op.clone(),
+        ])
+        .await?;
+    // Empty batches are allowed.
+    OperationsSchema(&mut storage)
+        .store_executed_txs(Vec::new())
+        .await?;
+    OperationsSchema(&mut storage)
+        .store_executed_priority_ops(Vec::new())
+        .await?;
+
+    for tx in &[executed_tx, resent_tx_success] {
+        let loaded_tx = OperationsSchema(&mut storage)
+            .get_executed_operation(tx.tx_hash.as_ref())
+            .await?
+            .unwrap();
+        assert_eq!(loaded_tx.success, true);
+        assert_eq!(loaded_tx.block_index, tx.block_index);
+    }
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_priority_operation(executed_priority_op.priority_op_serialid as u32)
+        .await?
+        .is_some());
+
+    let block_txs = BlockSchema(&mut storage)
+        .get_block_transactions(BlockNumber(BLOCK_NUMBER as u32))
+        .await?;
+    assert_eq!(block_txs.len(), 3);
+
+    Ok(())
+}
+
 /// Checks that rejected transactions are removed correctly depending on the given age limit.
 #[db_test]
 async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
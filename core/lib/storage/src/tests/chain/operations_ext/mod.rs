@@ -9,9 +9,10 @@ use crate::{
     chain::block::BlockSchema,
     chain::operations::OperationsSchema,
     chain::operations_ext::{
-        records::{AccountOpReceiptResponse, AccountTxReceiptResponse},
+        records::{AccountOpReceiptResponse, AccountTxReceiptResponse, TransactionsHistoryItem},
         SearchDirection,
     },
+    pruning::PruningSchema,
     test_data::{
         dummy_ethereum_tx_hash, gen_sample_block, gen_unique_aggregated_operation,
         BLOCK_SIZE_CHUNKS,
@@ -20,7 +21,7 @@ use crate::{
     QueryResult, StorageProcessor,
 };
 use zksync_types::aggregated_operations::AggregatedOperation;
-use zksync_types::BlockNumber;
+use zksync_types::{BlockNumber, ExecutedOperations};
 
 mod setup;
 
@@ -879,3 +880,103 @@ async fn get_account_operations_receipts(mut storage: StorageProcessor<'_>) -> Q
 
     Ok(())
 }
+
+/// Checks that the transactions and priority operations of the pruned blocks are still
+/// served from the archive tables.
+#[db_test]
+async fn archived_operations_lookup(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let address = setup.from_zksync_account.address;
+    let tx_hash = setup.blocks[0]
+        .block_transactions
+        .iter()
+        .find_map(|op| match op {
+            ExecutedOperations::Tx(tx) => Some(tx.signed_tx.tx.hash()),
+            ExecutedOperations::PriorityOp(_) => None,
+        })
+        .expect("Block should contain transactions");
+
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100)
+        .await?;
+    let created_on = storage
+        .chain()
+        .operations_ext_schema()
+        .account_created_on(&address)
+        .await?;
+    let block_txs = storage
+        .chain()
+        .block_schema()
+        .get_block_transactions(BlockNumber(1))
+        .await?;
+    let total_txs = storage
+        .chain()
+        .stats_schema()
+        .count_total_transactions()
+        .await?;
+
+    let stats = PruningSchema(&mut storage).prune(BlockNumber(1)).await?;
+    assert_eq!(stats.archived_transactions, 5);
+    assert_eq!(stats.archived_priority_ops, 2);
+
+    let archived_tx = storage
+        .chain()
+        .operations_ext_schema()
+        .get_tx_by_hash(tx_hash.as_ref())
+        .await?
+        .expect("Archived transaction should be found");
+    assert_eq!(archived_tx.block_number, 1);
+    assert!(storage
+        .chain()
+        .operations_schema()
+        .get_executed_priority_operation(2)
+        .await?
+        .is_some());
+
+    // Account history is not affected by the pruning.
+    let archived_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history(&address, 0, 100)
+        .await?;
+    let tx_ids = |history: &[TransactionsHistoryItem]| {
+        history
+            .iter()
+            .map(|item| item.tx_id.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(tx_ids(&archived_history), tx_ids(&history));
+    assert_eq!(
+        storage
+            .chain()
+            .operations_ext_schema()
+            .account_created_on(&address)
+            .await?,
+        created_on
+    );
+    assert_eq!(
+        storage
+            .chain()
+            .block_schema()
+            .get_block_transactions(BlockNumber(1))
+            .await?
+            .len(),
+        block_txs.len()
+    );
+    assert_eq!(
+        storage
+            .chain()
+            .stats_schema()
+            .count_total_transactions()
+            .await?,
+        total_txs
+    );
+
+    Ok(())
+}
@@ -216,3 +216,60 @@ async fn state_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the state version changes once the state update is committed.
+#[db_test]
+async fn state_version(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let initial_version = StateSchema(&mut storage).load_state_version().await?;
+    assert_eq!(
+        initial_version,
+        StateSchema(&mut storage).load_state_version().await?
+    );
+
+    let (_, updates) = apply_random_updates(AccountMap::default(), &mut rng);
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let version = StateSchema(&mut storage).load_state_version().await?;
+    assert_ne!(version, initial_version);
+    assert_eq!(
+        version.last_verified_block,
+        initial_version.last_verified_block
+    );
+
+    Ok(())
+}
+
+/// Checks that the state updates are stored and loaded regardless of the partition
+/// they belong to.
+#[db_test]
+async fn state_updates_partitions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let (accounts, updates_1) = apply_random_updates(AccountMap::default(), &mut rng);
+    let (_, updates_2) = apply_random_updates(accounts, &mut rng);
+
+    // Blocks belong to the different partitions.
+    let block_1 = BlockNumber(1);
+    let block_2 = BlockNumber(250_001);
+    StateSchema(&mut storage)
+        .commit_state_update(block_1, &updates_1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(block_2, &updates_2, 0)
+        .await?;
+
+    let diff_1 = StateSchema(&mut storage)
+        .load_state_diff_for_block(block_1)
+        .await?;
+    assert_eq!(diff_1.len(), updates_1.len());
+    let diff_2 = StateSchema(&mut storage)
+        .load_state_diff_for_block(block_2)
+        .await?;
+    assert_eq!(diff_2.len(), updates_2.len());
+
+    Ok(())
+}
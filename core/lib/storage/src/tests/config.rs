@@ -17,3 +17,18 @@ async fn test_load_config(mut storage: StorageProcessor<'_>) -> QueryResult<()>
 
     Ok(())
 }
+
+/// Once the database is bound to the network, it can't be rebound to another one.
+#[db_test]
+async fn test_bind_network(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // The database may be already bound by the `zk db insert contract` command.
+    let network = storage.config_schema().bind_network("localhost").await?;
+    assert!(!network.is_empty());
+
+    assert_eq!(
+        storage.config_schema().bind_network("mainnet").await?,
+        network
+    );
+
+    Ok(())
+}
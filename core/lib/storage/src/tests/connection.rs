@@ -0,0 +1,55 @@
+// Built-in deps
+use std::sync::atomic::{AtomicUsize, Ordering};
+// External imports
+use anyhow::format_err;
+// Local imports
+use crate::connection::retry::{is_transient_error, retry_on_transient_errors};
+
+/// Checks that the transient errors are distinguished from the other ones.
+#[test]
+fn transient_errors() {
+    assert!(is_transient_error(&sqlx::Error::PoolTimedOut.into()));
+    assert!(is_transient_error(
+        &sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into()).into()
+    ));
+
+    assert!(!is_transient_error(&sqlx::Error::RowNotFound.into()));
+    assert!(!is_transient_error(&format_err!("Unrelated error")));
+}
+
+/// Checks that only the transient errors are retried, and only a limited amount of times.
+#[tokio::test]
+async fn retry_transient_errors() {
+    // Interaction succeeds after a transient failure.
+    let attempts = &AtomicUsize::new(0);
+    let result = retry_on_transient_errors(|| async move {
+        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            Err(sqlx::Error::PoolTimedOut.into())
+        } else {
+            Ok(42)
+        }
+    })
+    .await;
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    // Non-transient error is returned right away.
+    let attempts = &AtomicUsize::new(0);
+    let result: anyhow::Result<()> = retry_on_transient_errors(|| async move {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(sqlx::Error::RowNotFound.into())
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    // Persistent transient error is returned once the attempts are exhausted.
+    let attempts = &AtomicUsize::new(0);
+    let result: anyhow::Result<()> = retry_on_transient_errors(|| async move {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(sqlx::Error::PoolTimedOut.into())
+    })
+    .await;
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
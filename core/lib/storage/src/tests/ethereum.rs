@@ -1,6 +1,7 @@
 // Built-in deps
 use std::str::FromStr;
 // External imports
+use chrono::{Duration, Utc};
 use zksync_basic_types::{H256, U256};
 // Workspace imports
 use zksync_types::{
@@ -12,7 +13,12 @@ use zksync_types::{
 use crate::test_data::{gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS};
 use crate::tests::db_test;
 use crate::{
-    chain::operations::OperationsSchema, ethereum::EthereumSchema, QueryResult, StorageProcessor,
+    chain::operations::OperationsSchema,
+    ethereum::{
+        records::{NewOpsGasUsage, OpsGasUsage},
+        EthereumSchema,
+    },
+    QueryResult, StorageProcessor,
 };
 use num::BigUint;
 
@@ -205,6 +211,125 @@ async fn eth_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Check that stored nonce is moved forward by the on-chain nonce, but never moved backward.
+#[db_test]
+async fn eth_nonce_sync(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    // On-chain nonce is ahead of the stored one, so it should be updated.
+    assert!(EthereumSchema(&mut storage).sync_nonce(5).await?);
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 5);
+
+    // On-chain nonce is behind the stored one (e.g. there are pending txs), nothing should change.
+    assert!(!EthereumSchema(&mut storage).sync_nonce(3).await?);
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 6);
+
+    Ok(())
+}
+
+/// Checks that the stuck operation and every pending operation created after it get new nonces
+/// following the on-chain one, while the confirmed operations are left intact.
+#[db_test]
+async fn eth_nonce_reassign(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let mut op_ids = Vec::new();
+    for block_number in 1..=3 {
+        let block_number = BlockNumber(block_number);
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                block_number,
+                AggregatedActionType::CommitBlocks,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+        let op = OperationsSchema(&mut storage)
+            .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+            .await?;
+
+        let params = EthereumTxParams::new("CommitBlocks".into(), op);
+        let response = EthereumSchema(&mut storage)
+            .save_new_eth_tx(
+                AggregatedActionType::CommitBlocks,
+                params.op.clone(),
+                params.deadline_block as i64,
+                params.gas_price.clone(),
+                params.raw_tx.clone(),
+            )
+            .await?;
+        EthereumSchema(&mut storage)
+            .add_hash_entry(response.id, &params.hash)
+            .await?;
+        op_ids.push((response.id, params.hash));
+    }
+
+    // The first operation is mined, and the nonce of the second one is consumed by another tx.
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&op_ids[0].1)
+        .await?;
+    let nonces = EthereumSchema(&mut storage)
+        .reassign_nonces(op_ids[1].0, 2)
+        .await?;
+    assert_eq!(nonces, vec![(op_ids[1].0, 2), (op_ids[2].0, 3)]);
+
+    let unconfirmed_nonces: Vec<_> = EthereumSchema(&mut storage)
+        .load_unconfirmed_operations()
+        .await?
+        .into_iter()
+        .map(|op| (op.id, op.nonce.as_u64() as i64))
+        .collect();
+    assert_eq!(unconfirmed_nonces, nonces);
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 4);
+
+    // Operations created before the stuck one keep their nonces, and the stuck one follows them.
+    let nonces = EthereumSchema(&mut storage)
+        .reassign_nonces(op_ids[2].0, 1)
+        .await?;
+    assert_eq!(nonces, vec![(op_ids[2].0, 3)]);
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 4);
+
+    Ok(())
+}
+
+/// Checks that paused action types are persisted and can be resumed.
+#[db_test]
+async fn eth_sender_pause(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(EthereumSchema(&mut storage)
+        .load_paused_actions()
+        .await?
+        .is_empty());
+
+    // Pausing the same action twice should not fail.
+    for _ in 0..2 {
+        EthereumSchema(&mut storage)
+            .pause_action(AggregatedActionType::CommitBlocks)
+            .await?;
+    }
+    EthereumSchema(&mut storage)
+        .pause_action(AggregatedActionType::ExecuteBlocks)
+        .await?;
+
+    let mut paused = EthereumSchema(&mut storage).load_paused_actions().await?;
+    paused.sort_by_key(|action_type| action_type.to_string());
+    assert_eq!(
+        paused,
+        vec![
+            AggregatedActionType::CommitBlocks,
+            AggregatedActionType::ExecuteBlocks
+        ]
+    );
+
+    EthereumSchema(&mut storage)
+        .resume_action(AggregatedActionType::CommitBlocks)
+        .await?;
+    assert_eq!(
+        EthereumSchema(&mut storage).load_paused_actions().await?,
+        vec![AggregatedActionType::ExecuteBlocks]
+    );
+
+    Ok(())
+}
+
 /// Here we check `unprocessed` and `unconfirmed` operations getting.
 /// If there is no `ETHOperation` for `Operation`, it must be returend by `load_unprocessed_operations`.
 /// It must **not** be returned by `load_unconfirmed_operations`.
@@ -389,3 +514,83 @@ async fn ethereum_gas_update(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+/// Checks that the EIP-1559 fees are stored and loaded.
+#[db_test]
+async fn ethereum_eip1559_fees_update(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    storage.ethereum_schema().initialize_eth_data().await?;
+    // Fees are not known until the first update.
+    assert!(storage
+        .ethereum_schema()
+        .load_eip1559_fees()
+        .await?
+        .is_none());
+
+    storage
+        .ethereum_schema()
+        .update_eip1559_fees(100.into(), 2.into())
+        .await?;
+    assert_eq!(
+        storage.ethereum_schema().load_eip1559_fees().await?,
+        Some((100.into(), 2.into()))
+    );
+
+    Ok(())
+}
+
+/// Checks that the gas used by the operations is stored and summed over the blocks.
+#[db_test]
+async fn ethereum_ops_gas_usage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let usage = |block_number, action: AggregatedActionType, ops_count, gas_used| NewOpsGasUsage {
+        block_number,
+        action_type: action.to_string(),
+        op_type: "Transfer".to_string(),
+        ops_count,
+        gas_used,
+    };
+    let since = Utc::now() - Duration::minutes(1);
+
+    storage
+        .ethereum_schema()
+        .store_ops_gas_usage(&[
+            usage(1, AggregatedActionType::CommitBlocks, 10, 5_000),
+            usage(1, AggregatedActionType::ExecuteBlocks, 10, 1_000),
+        ])
+        .await?;
+    // Records of the same block replace the previous ones.
+    storage
+        .ethereum_schema()
+        .store_ops_gas_usage(&[
+            usage(2, AggregatedActionType::CommitBlocks, 5, 2_000),
+            usage(1, AggregatedActionType::CommitBlocks, 10, 4_000),
+        ])
+        .await?;
+
+    let loaded = storage.ethereum_schema().load_ops_gas_usage(since).await?;
+    assert_eq!(
+        loaded,
+        vec![
+            OpsGasUsage {
+                action_type: AggregatedActionType::CommitBlocks.to_string(),
+                op_type: "Transfer".to_string(),
+                ops_count: 15,
+                gas_used: 6_000,
+            },
+            OpsGasUsage {
+                action_type: AggregatedActionType::ExecuteBlocks.to_string(),
+                op_type: "Transfer".to_string(),
+                ops_count: 10,
+                gas_used: 1_000,
+            },
+        ]
+    );
+
+    // Records stored before the moment are not taken into account.
+    let loaded = storage
+        .ethereum_schema()
+        .load_ops_gas_usage(Utc::now() + Duration::minutes(1))
+        .await?;
+    assert!(loaded.is_empty());
+
+    Ok(())
+}
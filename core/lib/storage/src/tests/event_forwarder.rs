@@ -0,0 +1,32 @@
+// External imports
+// Workspace imports
+use zksync_types::BlockNumber;
+// Local imports
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+/// Checks that the last forwarded block is stored and updated.
+#[db_test]
+async fn last_forwarded_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let last_forwarded_block = storage
+        .event_forwarder_schema()
+        .last_forwarded_block()
+        .await?;
+    assert_eq!(last_forwarded_block, None);
+
+    storage
+        .event_forwarder_schema()
+        .update_last_forwarded_block(BlockNumber(1))
+        .await?;
+    storage
+        .event_forwarder_schema()
+        .update_last_forwarded_block(BlockNumber(2))
+        .await?;
+    let last_forwarded_block = storage
+        .event_forwarder_schema()
+        .last_forwarded_block()
+        .await?;
+    assert_eq!(last_forwarded_block, Some(BlockNumber(2)));
+
+    Ok(())
+}
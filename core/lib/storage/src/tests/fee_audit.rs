@@ -0,0 +1,91 @@
+// External imports
+use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{
+    fee_audit::records::{NewFeeOverpayment, NewFeeQuote},
+    QueryResult, StorageProcessor,
+};
+
+/// Checks the save&load routine for the fee quotes and overpayments,
+/// as well as the removal of the outdated records.
+#[db_test]
+async fn fee_audit_records(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+
+    let quotes = (0..3)
+        .map(|hours_ago| NewFeeQuote {
+            fee_type: serde_json::json!("Transfer"),
+            address: vec![hours_ago as u8; 20],
+            token_id: 0,
+            gas_tx_amount: BigDecimal::from(2000),
+            gas_price_wei: BigDecimal::from(100),
+            gas_fee: BigDecimal::from(200_000),
+            zkp_fee: BigDecimal::from(1000 + hours_ago),
+            total_fee: BigDecimal::from(201_000 + hours_ago),
+            quoted_at: now - Duration::hours(hours_ago),
+        })
+        .collect::<Vec<_>>();
+    storage
+        .fee_audit_schema()
+        .store_fee_quotes(quotes.clone())
+        .await?;
+
+    let overpayment = NewFeeOverpayment {
+        tx_hash: vec![0xAB; 32],
+        token_id: 0,
+        quoted_fee: BigDecimal::from(100),
+        paid_fee: BigDecimal::from(150),
+        recorded_at: now - Duration::hours(2),
+    };
+    storage
+        .fee_audit_schema()
+        .store_fee_overpayments(vec![overpayment.clone()])
+        .await?;
+
+    // Only the quotes within the range are loaded, the oldest first.
+    let loaded = storage
+        .fee_audit_schema()
+        .load_fee_quotes(now - Duration::minutes(90), now)
+        .await?;
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].address, quotes[1].address);
+    assert_eq!(loaded[0].total_fee, quotes[1].total_fee);
+    assert_eq!(loaded[1].address, quotes[0].address);
+    assert_eq!(loaded[1].fee_type, quotes[0].fee_type);
+
+    let loaded = storage
+        .fee_audit_schema()
+        .load_fee_overpayments(now - Duration::days(1), now)
+        .await?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].tx_hash, overpayment.tx_hash);
+    assert_eq!(loaded[0].paid_fee, overpayment.paid_fee);
+
+    // Two oldest quotes and the overpayment record are removed.
+    let removed = storage
+        .fee_audit_schema()
+        .remove_records_before(now - Duration::minutes(30))
+        .await?;
+    assert_eq!(removed, 3);
+
+    let loaded = storage
+        .fee_audit_schema()
+        .load_fee_quotes(now - Duration::days(1), now)
+        .await?;
+    assert_eq!(loaded.len(), 1);
+
+    // Empty batches are allowed.
+    storage
+        .fee_audit_schema()
+        .store_fee_quotes(Vec::new())
+        .await?;
+    storage
+        .fee_audit_schema()
+        .store_fee_overpayments(Vec::new())
+        .await?;
+
+    Ok(())
+}
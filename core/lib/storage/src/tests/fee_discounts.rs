@@ -0,0 +1,65 @@
+// Built-in imports
+use std::str::FromStr;
+// External imports
+use sqlx::types::BigDecimal;
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::tests::db_test;
+use crate::{fee_discounts::records::NewFeeDiscount, QueryResult, StorageProcessor};
+
+/// Checks the storing, loading and removal of the fee discounts.
+#[db_test]
+async fn fee_discounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(1);
+    let discounts = vec![
+        NewFeeDiscount {
+            address: address.as_bytes().to_vec(),
+            fee_type: Some(serde_json::json!("Transfer")),
+            discount: BigDecimal::from_str("0.5").unwrap(),
+        },
+        NewFeeDiscount {
+            address: address.as_bytes().to_vec(),
+            fee_type: None,
+            discount: BigDecimal::from_str("0.1").unwrap(),
+        },
+    ];
+    let mut ids = Vec::new();
+    for discount in discounts.clone() {
+        ids.push(
+            storage
+                .fee_discounts_schema()
+                .store_discount(discount)
+                .await?,
+        );
+    }
+
+    let stored = storage.fee_discounts_schema().load_discounts().await?;
+    assert_eq!(stored.len(), 2);
+    for ((stored, discount), id) in stored.iter().zip(&discounts).zip(&ids) {
+        assert_eq!(stored.id, *id);
+        assert_eq!(stored.address, discount.address);
+        assert_eq!(stored.fee_type, discount.fee_type);
+        assert_eq!(stored.discount, discount.discount);
+    }
+
+    assert!(
+        storage
+            .fee_discounts_schema()
+            .remove_discount(ids[0])
+            .await?
+    );
+    let stored = storage.fee_discounts_schema().load_discounts().await?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].id, ids[1]);
+
+    // Already removed discount.
+    assert!(
+        !storage
+            .fee_discounts_schema()
+            .remove_discount(ids[0])
+            .await?
+    );
+
+    Ok(())
+}
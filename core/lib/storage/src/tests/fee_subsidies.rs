@@ -0,0 +1,146 @@
+// External imports
+use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{
+    chain::operations::{records::NewExecutedTransaction, OperationsSchema},
+    fee_subsidies::records::{NewFeeSubsidy, NewSubsidySpend},
+    QueryResult, StorageProcessor,
+};
+
+/// Checks the storing, loading and toggling of the fee subsidies.
+#[db_test]
+async fn fee_subsidies(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now();
+    let subsidies = vec![
+        NewFeeSubsidy {
+            token_id: Some(0),
+            fee_type: Some(serde_json::json!("Transfer")),
+            starts_at: now,
+            ends_at: Some(now + Duration::days(1)),
+        },
+        NewFeeSubsidy {
+            token_id: None,
+            fee_type: None,
+            starts_at: now,
+            ends_at: None,
+        },
+    ];
+    let mut ids = Vec::new();
+    for subsidy in subsidies.clone() {
+        ids.push(
+            storage
+                .fee_subsidies_schema()
+                .store_subsidy(subsidy)
+                .await?,
+        );
+    }
+
+    let stored = storage.fee_subsidies_schema().load_subsidies().await?;
+    assert_eq!(stored.len(), 2);
+    for ((stored, subsidy), id) in stored.iter().zip(&subsidies).zip(&ids) {
+        assert_eq!(stored.id, *id);
+        assert_eq!(stored.token_id, subsidy.token_id);
+        assert_eq!(stored.fee_type, subsidy.fee_type);
+        assert_eq!(stored.ends_at.is_some(), subsidy.ends_at.is_some());
+        assert!(stored.enabled);
+    }
+
+    assert!(
+        storage
+            .fee_subsidies_schema()
+            .set_subsidy_enabled(ids[0], false)
+            .await?
+    );
+    let stored = storage.fee_subsidies_schema().load_subsidies().await?;
+    assert!(!stored[0].enabled);
+    assert!(stored[1].enabled);
+
+    // Unknown subsidy.
+    assert!(
+        !storage
+            .fee_subsidies_schema()
+            .set_subsidy_enabled(ids[1] + 1, false)
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks that only the subsidies spent on the successfully executed transactions are reported.
+#[db_test]
+async fn fee_subsidy_spends(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let spend = |tx_hash: u8, token_id: i32, subsidy: u32| NewSubsidySpend {
+        tx_hash: vec![tx_hash; 32],
+        token_id,
+        fee_type: serde_json::json!("Withdraw"),
+        paid_fee: BigDecimal::from(10),
+        subsidy: BigDecimal::from(subsidy),
+    };
+    let spends = vec![
+        spend(1, 0, 100),
+        spend(2, 0, 200),
+        spend(3, 1, 300),
+        // The transaction is failed.
+        spend(4, 1, 400),
+        // The transaction is not executed yet.
+        spend(5, 1, 500),
+    ];
+    for spend in spends {
+        storage
+            .fee_subsidies_schema()
+            .store_subsidy_spend(spend)
+            .await?;
+    }
+    // The resubmitted transaction is not recorded twice.
+    storage
+        .fee_subsidies_schema()
+        .store_subsidy_spend(spend(1, 0, 100))
+        .await?;
+
+    for (tx_hash, success) in vec![(1, true), (2, true), (3, true), (4, false)] {
+        let executed_tx = NewExecutedTransaction {
+            block_number: 1,
+            tx_hash: vec![tx_hash; 32],
+            tx: Default::default(),
+            operation: Default::default(),
+            from_account: Default::default(),
+            to_account: None,
+            success,
+            fail_reason: None,
+            block_index: None,
+            primary_account_address: Default::default(),
+            nonce: i64::from(tx_hash),
+            created_at: Utc::now(),
+            eth_sign_data: None,
+            batch_id: None,
+        };
+        OperationsSchema(&mut storage)
+            .store_executed_tx(executed_tx)
+            .await?;
+    }
+
+    let now = Utc::now();
+    let totals = storage
+        .fee_subsidies_schema()
+        .load_subsidy_spend_totals(now - Duration::hours(1), now)
+        .await?;
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals[0].token_id, 0);
+    assert_eq!(totals[0].txs_count, 2);
+    assert_eq!(totals[0].total_subsidy, BigDecimal::from(300));
+    assert_eq!(totals[1].token_id, 1);
+    assert_eq!(totals[1].txs_count, 1);
+    assert_eq!(totals[1].total_subsidy, BigDecimal::from(300));
+
+    // No spends are recorded within the period.
+    let totals = storage
+        .fee_subsidies_schema()
+        .load_subsidy_spend_totals(now - Duration::hours(2), now - Duration::hours(1))
+        .await?;
+    assert!(totals.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// External imports
+use chrono::{Duration, Utc};
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{fee_waivers::records::NewFeeWaiver, QueryResult, StorageProcessor};
+
+/// Checks the storing, loading and removal of the fee waivers.
+#[db_test]
+async fn fee_waivers(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let waivers = vec![
+        NewFeeWaiver {
+            fee_type: serde_json::json!({ "ChangePubKey": "ECDSA" }),
+            token_id: None,
+            unset_signing_key_only: true,
+            expires_at: None,
+        },
+        NewFeeWaiver {
+            fee_type: serde_json::json!("Withdraw"),
+            token_id: Some(1),
+            unset_signing_key_only: false,
+            expires_at: Some(Utc::now() + Duration::days(30)),
+        },
+    ];
+    let mut ids = Vec::new();
+    for waiver in waivers.clone() {
+        ids.push(storage.fee_waivers_schema().store_waiver(waiver).await?);
+    }
+
+    let stored = storage.fee_waivers_schema().load_waivers().await?;
+    assert_eq!(stored.len(), 2);
+    for ((stored, waiver), id) in stored.iter().zip(&waivers).zip(&ids) {
+        assert_eq!(stored.id, *id);
+        assert_eq!(stored.fee_type, waiver.fee_type);
+        assert_eq!(stored.token_id, waiver.token_id);
+        assert_eq!(stored.unset_signing_key_only, waiver.unset_signing_key_only);
+        assert_eq!(
+            stored.expires_at.map(|time| time.timestamp()),
+            waiver.expires_at.map(|time| time.timestamp())
+        );
+    }
+
+    assert!(storage.fee_waivers_schema().remove_waiver(ids[0]).await?);
+    let stored = storage.fee_waivers_schema().load_waivers().await?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].id, ids[1]);
+
+    // Already removed waiver.
+    assert!(!storage.fee_waivers_schema().remove_waiver(ids[0]).await?);
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+// External imports
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::chain::operations::{records::NewExecutedTransaction, OperationsSchema};
+use crate::forced_exit_requests::{
+    records::NewForcedExitRequest, FORCED_EXIT_REQUEST_EXPIRED, FORCED_EXIT_REQUEST_FULFILLED,
+    FORCED_EXIT_REQUEST_PAID, FORCED_EXIT_REQUEST_PENDING,
+};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+const REQUESTER: [u8; 20] = [0xAA; 20];
+const FUNDING_ACCOUNT: [u8; 20] = [0xBB; 20];
+
+fn new_request(valid_for: Duration) -> NewForcedExitRequest {
+    NewForcedExitRequest {
+        target: vec![0xCC; 20],
+        tokens: vec![0, 1],
+        requester: REQUESTER.to_vec(),
+        price_in_wei: BigDecimal::from(1000),
+        valid_until: Utc::now() + valid_for,
+    }
+}
+
+fn transfer(tx_hash: u8, from: [u8; 20], success: bool) -> NewExecutedTransaction {
+    NewExecutedTransaction {
+        block_number: 1,
+        block_index: Some(tx_hash as i32),
+        tx: json!({
+            "type": "Transfer",
+            "token": 0,
+            "amount": "1500",
+        }),
+        operation: Default::default(),
+        tx_hash: vec![tx_hash; 32],
+        from_account: from.to_vec(),
+        to_account: Some(FUNDING_ACCOUNT.to_vec()),
+        success,
+        fail_reason: None,
+        primary_account_address: from.to_vec(),
+        nonce: tx_hash as i64,
+        created_at: Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+    }
+}
+
+/// Checks the lifecycle of the request: storing, expiration, payment and fulfillment.
+#[db_test]
+async fn forced_exit_requests(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first = storage
+        .forced_exit_requests_schema()
+        .store_request(new_request(Duration::hours(1)))
+        .await?;
+    let second = storage
+        .forced_exit_requests_schema()
+        .store_request(new_request(Duration::seconds(-1)))
+        .await?;
+    assert_eq!(first.status, FORCED_EXIT_REQUEST_PENDING);
+    assert_eq!(first.tokens, vec![0, 1]);
+
+    let unfulfilled = storage
+        .forced_exit_requests_schema()
+        .load_unfulfilled(10)
+        .await?;
+    assert_eq!(unfulfilled, vec![first.clone(), second.clone()]);
+
+    // The second request has already passed its deadline.
+    let expired = storage
+        .forced_exit_requests_schema()
+        .expire_unpaid(Utc::now())
+        .await?;
+    assert_eq!(expired, 1);
+    let second = storage
+        .forced_exit_requests_schema()
+        .get_request(second.id)
+        .await?
+        .unwrap();
+    assert_eq!(second.status, FORCED_EXIT_REQUEST_EXPIRED);
+
+    storage
+        .forced_exit_requests_schema()
+        .mark_paid(first.id, &[0x01; 32])
+        .await?;
+    storage
+        .forced_exit_requests_schema()
+        .mark_fulfilled(first.id, &["sync-tx:01".to_string()])
+        .await?;
+    assert!(storage
+        .forced_exit_requests_schema()
+        .load_unfulfilled(10)
+        .await?
+        .is_empty());
+
+    let first = storage
+        .forced_exit_requests_schema()
+        .get_request(first.id)
+        .await?
+        .unwrap();
+    assert_eq!(first.status, FORCED_EXIT_REQUEST_FULFILLED);
+    assert_eq!(first.payment_tx_hash, Some(vec![0x01; 32]));
+    assert_eq!(first.fulfilled_by, Some(vec!["sync-tx:01".to_string()]));
+    assert!(first.fulfilled_at.is_some());
+
+    Ok(())
+}
+
+/// Checks that only the successful transfers from the requester which were not used
+/// to pay for other requests are considered to be the payments.
+#[db_test]
+async fn forced_exit_payments(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let request = storage
+        .forced_exit_requests_schema()
+        .store_request(new_request(Duration::hours(1)))
+        .await?;
+    let since = request.created_at - Duration::minutes(1);
+
+    for tx in vec![
+        transfer(0x01, REQUESTER, true),
+        transfer(0x02, REQUESTER, false),
+        transfer(0x03, [0xDD; 20], true),
+        transfer(0x04, REQUESTER, true),
+    ] {
+        OperationsSchema(&mut storage).store_executed_tx(tx).await?;
+    }
+
+    let payments = storage
+        .forced_exit_requests_schema()
+        .find_payments(&REQUESTER, &FUNDING_ACCOUNT, since)
+        .await?;
+    let hashes: Vec<_> = payments.iter().map(|p| p.tx_hash.clone()).collect();
+    assert_eq!(hashes, vec![vec![0x01; 32], vec![0x04; 32]]);
+    assert_eq!(payments[0].token, 0);
+    assert_eq!(payments[0].amount, BigDecimal::from(1500));
+
+    storage
+        .forced_exit_requests_schema()
+        .mark_paid(request.id, &[0x01; 32])
+        .await?;
+    let payments = storage
+        .forced_exit_requests_schema()
+        .find_payments(&REQUESTER, &FUNDING_ACCOUNT, since)
+        .await?;
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments[0].tx_hash, vec![0x04; 32]);
+
+    Ok(())
+}
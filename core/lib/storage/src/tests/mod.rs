@@ -28,11 +28,22 @@
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 // use diesel::Connection;
 
+mod api_keys;
 pub(crate) mod chain;
 mod config;
+mod connection;
 mod data_restore;
 mod ethereum;
+mod event_forwarder;
+mod fee_audit;
+mod fee_discounts;
+mod fee_subsidies;
+mod fee_waivers;
+mod forced_exit_requests;
+mod permit_deposits;
 mod prover;
+mod pruning;
+mod token_gas_costs;
 mod tokens;
 
 pub use db_test_macro::test as db_test;
@@ -0,0 +1,72 @@
+// External imports
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::permit_deposits::{
+    records::NewPermitDeposit, PERMIT_DEPOSIT_FAILED, PERMIT_DEPOSIT_PENDING, PERMIT_DEPOSIT_SENT,
+};
+use crate::tests::db_test;
+use crate::{QueryResult, StorageProcessor};
+
+fn new_request(token_id: i32) -> NewPermitDeposit {
+    NewPermitDeposit {
+        token_id,
+        owner: vec![0xAA; 20],
+        amount: BigDecimal::from(1000),
+        fee: BigDecimal::from(10),
+        deadline: 1_700_000_000,
+        signature: vec![0x11; 65],
+    }
+}
+
+/// Checks that the requests are stored as pending and leave the pending queue once processed.
+#[db_test]
+async fn permit_deposits(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first = storage
+        .permit_deposits_schema()
+        .store_request(new_request(1))
+        .await?;
+    let second = storage
+        .permit_deposits_schema()
+        .store_request(new_request(2))
+        .await?;
+    assert_eq!(first.status, PERMIT_DEPOSIT_PENDING);
+    assert_eq!(first.amount, BigDecimal::from(1000));
+
+    let pending = storage.permit_deposits_schema().load_pending(10).await?;
+    assert_eq!(pending, vec![first.clone(), second.clone()]);
+    let pending = storage.permit_deposits_schema().load_pending(1).await?;
+    assert_eq!(pending, vec![first.clone()]);
+
+    storage
+        .permit_deposits_schema()
+        .mark_sent(first.id, &[0xCC; 32])
+        .await?;
+    storage
+        .permit_deposits_schema()
+        .mark_failed(second.id, "permit expired")
+        .await?;
+    assert!(storage
+        .permit_deposits_schema()
+        .load_pending(10)
+        .await?
+        .is_empty());
+
+    let first = storage
+        .permit_deposits_schema()
+        .get_request(first.id)
+        .await?
+        .unwrap();
+    assert_eq!(first.status, PERMIT_DEPOSIT_SENT);
+    assert_eq!(first.eth_tx_hash, Some(vec![0xCC; 32]));
+
+    let second = storage
+        .permit_deposits_schema()
+        .get_request(second.id)
+        .await?
+        .unwrap();
+    assert_eq!(second.status, PERMIT_DEPOSIT_FAILED);
+    assert_eq!(second.error.as_deref(), Some("permit expired"));
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+// Built-in imports
+use std::time::Duration;
 // External imports
 use anyhow::format_err;
 // Workspace imports
@@ -10,7 +12,7 @@ use zksync_types::BlockNumber;
 
 async fn get_idle_job_from_queue(mut storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(&mut storage)
-        .get_idle_prover_job_from_job_queue()
+        .get_idle_prover_job_from_job_queue("test_prover")
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -22,6 +24,7 @@ async fn get_idle_job_from_queue(mut storage: &mut StorageProcessor<'_>) -> Quer
 async fn test_prover_job_queue(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     test_store_proof(&mut storage).await?;
     pending_jobs_count(&mut storage).await?;
+    job_lease(&mut storage).await?;
 
     Ok(())
 }
@@ -206,6 +209,68 @@ async fn pending_jobs_count(mut storage: &mut StorageProcessor<'_>) -> QueryResu
     Ok(())
 }
 
+/// Checks that the job lease is renewed only by the prover it was given to and
+/// that the stale job is given to another prover.
+async fn job_lease(mut storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
+    // The job with the lowest priority value is given first.
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(4),
+            BlockNumber(4),
+            Default::default(),
+            -1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("first_prover")
+        .await?
+        .expect("expect idle job from job queue");
+    assert_eq!(job.first_block, BlockNumber(4));
+
+    // Only the prover holding the lease can renew it.
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "second_prover")
+            .await?
+    );
+    assert!(
+        ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "first_prover")
+            .await?
+    );
+
+    // The lease is not expired yet.
+    let reassigned_jobs = ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(Duration::from_secs(3600))
+        .await?;
+    assert_eq!(reassigned_jobs, 0);
+
+    // Once the lease is expired, the job is given to another prover.
+    let reassigned_jobs = ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(Duration::from_secs(0))
+        .await?;
+    assert_eq!(reassigned_jobs, 1);
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "first_prover")
+            .await?
+    );
+
+    let reassigned_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("second_prover")
+        .await?
+        .expect("expect idle job from job queue");
+    assert_eq!(reassigned_job.job_id, job.job_id);
+    assert!(
+        ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "second_prover")
+            .await?
+    );
+
+    Ok(())
+}
+
 /// Checks that the witness can be stored and loaded.
 #[db_test]
 async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
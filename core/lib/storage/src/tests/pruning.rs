@@ -0,0 +1,57 @@
+// External imports
+// Workspace imports
+use zksync_types::{prover::ProverJobType, BlockNumber};
+// Local imports
+use crate::test_data::get_sample_single_proof;
+use crate::tests::db_test;
+use crate::{prover::ProverSchema, pruning::PruningSchema, QueryResult, StorageProcessor};
+
+/// Checks that proofs of the pruned blocks are moved out of the main table,
+/// while the proofs of the newer blocks are kept.
+#[db_test]
+async fn prune_proofs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let proof = get_sample_single_proof();
+    for block_number in 1..=3 {
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                serde_json::Value::default(),
+                0,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+        let job = ProverSchema(&mut storage)
+            .get_idle_prover_job_from_job_queue("test_prover")
+            .await?
+            .expect("Prover job should be stored");
+        ProverSchema(&mut storage)
+            .store_proof(job.job_id, BlockNumber(block_number), &proof)
+            .await?;
+    }
+
+    let stats = PruningSchema(&mut storage).prune(BlockNumber(2)).await?;
+    assert_eq!(stats.last_pruned_block, BlockNumber(2));
+    assert_eq!(stats.archived_transactions, 0);
+    assert_eq!(stats.archived_proofs, 2);
+    // Finished prover jobs for the pruned blocks.
+    assert_eq!(stats.removed_proving_data, 2);
+
+    for block_number in 1..=2 {
+        assert!(ProverSchema(&mut storage)
+            .load_proof(BlockNumber(block_number))
+            .await?
+            .is_none());
+    }
+    assert!(ProverSchema(&mut storage)
+        .load_proof(BlockNumber(3))
+        .await?
+        .is_some());
+
+    // Pruning the same range again is a no-op.
+    let stats = PruningSchema(&mut storage).prune(BlockNumber(2)).await?;
+    assert_eq!(stats.archived_proofs, 0);
+    assert_eq!(stats.removed_proving_data, 0);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// External imports
+use sqlx::types::BigDecimal;
+// Workspace imports
+// Local imports
+use crate::tests::db_test;
+use crate::{token_gas_costs::records::NewTokenGasCost, QueryResult, StorageProcessor};
+
+/// Checks the storing, updating and removal of the token gas costs.
+#[db_test]
+async fn token_gas_costs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let withdraw = NewTokenGasCost {
+        token_id: 0,
+        fee_type: serde_json::json!("Withdraw"),
+        gas_cost: BigDecimal::from(20_000),
+    };
+    let fast_withdraw = NewTokenGasCost {
+        token_id: 0,
+        fee_type: serde_json::json!("FastWithdraw"),
+        gas_cost: BigDecimal::from(200_000),
+    };
+    for gas_cost in vec![withdraw.clone(), fast_withdraw.clone()] {
+        storage
+            .token_gas_costs_schema()
+            .store_gas_cost(gas_cost)
+            .await?;
+    }
+    let stored = storage.token_gas_costs_schema().load_gas_costs().await?;
+    assert_eq!(stored.len(), 2);
+
+    // Storing the gas cost again replaces the previous one.
+    let withdraw = NewTokenGasCost {
+        gas_cost: BigDecimal::from(30_000),
+        ..withdraw
+    };
+    storage
+        .token_gas_costs_schema()
+        .store_gas_cost(withdraw.clone())
+        .await?;
+    let stored = storage.token_gas_costs_schema().load_gas_costs().await?;
+    assert_eq!(stored.len(), 2);
+    let stored_withdraw = stored
+        .iter()
+        .find(|stored| stored.fee_type == withdraw.fee_type)
+        .unwrap();
+    assert_eq!(stored_withdraw.gas_cost, withdraw.gas_cost);
+
+    assert!(
+        storage
+            .token_gas_costs_schema()
+            .remove_gas_cost(0, fast_withdraw.fee_type.clone())
+            .await?
+    );
+    let stored = storage.token_gas_costs_schema().load_gas_costs().await?;
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].fee_type, withdraw.fee_type);
+
+    // Already removed gas cost.
+    assert!(
+        !storage
+            .token_gas_costs_schema()
+            .remove_gas_cost(0, fast_withdraw.fee_type)
+            .await?
+    );
+
+    Ok(())
+}
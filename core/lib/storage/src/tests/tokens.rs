@@ -193,3 +193,109 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks the storing, loading and removing of the token price history.
+#[db_test]
+async fn test_price_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    let now = chrono::Utc::now();
+    let prices = (0..3)
+        .map(|hours_ago| TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(100u32 + hours_ago as u32)),
+            last_updated: now - chrono::Duration::hours(hours_ago),
+        })
+        .collect::<Vec<_>>();
+    for price in &prices {
+        storage
+            .tokens_schema()
+            .store_price_observation(TOKEN_ID, "CoinGecko", price)
+            .await?;
+    }
+
+    // Only the observations within the range are loaded, the oldest first.
+    let history = storage
+        .tokens_schema()
+        .load_price_history(TOKEN_ID, now - chrono::Duration::minutes(90), now)
+        .await?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history[0].usd_price,
+        ratio_to_big_decimal(&prices[1].usd_price, 0)
+    );
+    assert_eq!(
+        history[1].usd_price,
+        ratio_to_big_decimal(&prices[0].usd_price, 0)
+    );
+    assert!(history.iter().all(|price| price.source == "CoinGecko"));
+
+    // The last observation before the timestamp is loaded.
+    let price = storage
+        .tokens_schema()
+        .load_price_at(TOKEN_ID, now - chrono::Duration::minutes(90))
+        .await?
+        .expect("no price observed");
+    assert_eq!(
+        price.usd_price,
+        ratio_to_big_decimal(&prices[2].usd_price, 0)
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_price_at(TOKEN_ID, now - chrono::Duration::hours(3))
+        .await?
+        .is_none());
+
+    let removed = storage
+        .tokens_schema()
+        .remove_price_history_before(now - chrono::Duration::minutes(30))
+        .await?;
+    assert_eq!(removed, 2);
+
+    let history = storage
+        .tokens_schema()
+        .load_price_history(TOKEN_ID, now - chrono::Duration::days(1), now)
+        .await?;
+    assert_eq!(history.len(), 1);
+
+    Ok(())
+}
+
+/// Checks the storing, updating and removing of the token risk factors.
+#[db_test]
+async fn test_risk_factors(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+
+    assert!(storage
+        .tokens_schema()
+        .load_risk_factors()
+        .await?
+        .is_empty());
+
+    let risk_factor = Ratio::new(BigUint::from(3u32), BigUint::from(2u32));
+    storage
+        .tokens_schema()
+        .store_risk_factor(TOKEN_ID, &risk_factor)
+        .await?;
+    let risk_factors = storage.tokens_schema().load_risk_factors().await?;
+    assert_eq!(risk_factors.get(&TOKEN_ID), Some(&risk_factor));
+
+    // Storing the risk factor again replaces the previous one.
+    let risk_factor = Ratio::from_integer(BigUint::from(2u32));
+    storage
+        .tokens_schema()
+        .store_risk_factor(TOKEN_ID, &risk_factor)
+        .await?;
+    let risk_factors = storage.tokens_schema().load_risk_factors().await?;
+    assert_eq!(risk_factors.len(), 1);
+    assert_eq!(risk_factors.get(&TOKEN_ID), Some(&risk_factor));
+
+    assert!(storage.tokens_schema().remove_risk_factor(TOKEN_ID).await?);
+    assert!(!storage.tokens_schema().remove_risk_factor(TOKEN_ID).await?);
+    assert!(storage
+        .tokens_schema()
+        .load_risk_factors()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use serde_json::Value;
+// Workspace imports
+// Local imports
+use self::records::{NewTokenGasCost, StoredTokenGasCost};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Token gas costs schema stores the gas costs of the operations overridden for the specific tokens.
+#[derive(Debug)]
+pub struct TokenGasCostsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> TokenGasCostsSchema<'a, 'c> {
+    /// Sets the gas cost of the operation paid in the token, replacing the previous one if any.
+    pub async fn store_gas_cost(&mut self, gas_cost: NewTokenGasCost) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_gas_costs ( token_id, fee_type, gas_cost, updated_at )
+            VALUES ( $1, $2, $3, now() )
+            ON CONFLICT (token_id, fee_type)
+            DO
+              UPDATE SET gas_cost = $3, updated_at = now()
+            "#,
+            gas_cost.token_id,
+            gas_cost.fee_type,
+            gas_cost.gas_cost
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token_gas_costs.store_gas_cost", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads all the gas costs ordered by the token ID.
+    pub async fn load_gas_costs(&mut self) -> QueryResult<Vec<StoredTokenGasCost>> {
+        let start = Instant::now();
+        let gas_costs = sqlx::query_as!(
+            StoredTokenGasCost,
+            "SELECT * FROM token_gas_costs ORDER BY token_id, updated_at"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token_gas_costs.load_gas_costs", start.elapsed());
+        Ok(gas_costs)
+    }
+
+    /// Removes the gas cost of the operation paid in the token, so the standard one is used.
+    /// Returns `false` if there was no such gas cost.
+    pub async fn remove_gas_cost(&mut self, token_id: i32, fee_type: Value) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM token_gas_costs WHERE token_id = $1 AND fee_type = $2",
+            token_id,
+            fee_type
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.token_gas_costs.remove_gas_cost", start.elapsed());
+        Ok(removed > 0)
+    }
+}
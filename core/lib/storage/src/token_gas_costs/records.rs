@@ -0,0 +1,23 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::BigDecimal, FromRow};
+// Workspace imports
+// Local imports
+
+/// Gas cost of the operation paid in the token which is not stored yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTokenGasCost {
+    pub token_id: i32,
+    pub fee_type: Value,
+    pub gas_cost: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredTokenGasCost {
+    pub token_id: i32,
+    pub fee_type: Value,
+    pub gas_cost: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
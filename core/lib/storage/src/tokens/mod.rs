@@ -2,12 +2,13 @@
 use std::collections::HashMap;
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
-use zksync_utils::ratio_to_big_decimal;
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken};
+use self::records::{DBMarketVolume, DbPriceObservation, DbTickerPrice, DbToken};
 use crate::tokens::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
@@ -17,6 +18,8 @@ mod utils;
 
 /// Precision of the USD price per token
 pub(crate) const STORED_USD_PRICE_PRECISION: usize = 6;
+/// Precision of the token risk factor
+pub(crate) const STORED_RISK_FACTOR_PRECISION: usize = 6;
 
 /// Tokens schema handles the `tokens` table, providing methods to
 /// get and store new tokens.
@@ -271,4 +274,167 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
         Ok(())
     }
+
+    /// Stores the token price received from the given source.
+    ///
+    /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
+    /// so the number might get rounded.
+    pub async fn store_price_observation(
+        &mut self,
+        token_id: TokenId,
+        source: &str,
+        price: &TokenPrice,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
+        sqlx::query!(
+            r#"
+            INSERT INTO token_price_history ( token_id, source, usd_price, observed_at )
+            VALUES ( $1, $2, $3, $4 )
+            "#,
+            i32::from(*token_id),
+            source,
+            usd_price_rounded,
+            price.last_updated
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_price_observation", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the prices of the token observed within the `[from, to]` time range,
+    /// ordered by the observation time.
+    pub async fn load_price_history(
+        &mut self,
+        token_id: TokenId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> QueryResult<Vec<DbPriceObservation>> {
+        let start = Instant::now();
+        let history = sqlx::query_as!(
+            DbPriceObservation,
+            r#"
+            SELECT * FROM token_price_history
+            WHERE token_id = $1 AND observed_at >= $2 AND observed_at <= $3
+            ORDER BY observed_at
+            "#,
+            i32::from(*token_id),
+            from,
+            to
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_price_history", start.elapsed());
+        Ok(history)
+    }
+
+    /// Loads the last price of the token observed not later than the given timestamp.
+    pub async fn load_price_at(
+        &mut self,
+        token_id: TokenId,
+        at: DateTime<Utc>,
+    ) -> QueryResult<Option<DbPriceObservation>> {
+        let start = Instant::now();
+        let observation = sqlx::query_as!(
+            DbPriceObservation,
+            r#"
+            SELECT * FROM token_price_history
+            WHERE token_id = $1 AND observed_at <= $2
+            ORDER BY observed_at DESC
+            LIMIT 1
+            "#,
+            i32::from(*token_id),
+            at
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_price_at", start.elapsed());
+        Ok(observation)
+    }
+
+    /// Removes the price observations older than the given timestamp.
+    /// Returns the amount of removed observations.
+    pub async fn remove_price_history_before(&mut self, before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM token_price_history WHERE observed_at < $1",
+            before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.token.remove_price_history_before", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads the risk factors of the tokens. Tokens without the stored
+    /// risk factor are not included.
+    pub async fn load_risk_factors(&mut self) -> QueryResult<HashMap<TokenId, Ratio<BigUint>>> {
+        let start = Instant::now();
+        let records = sqlx::query!("SELECT token_id, risk_factor FROM token_risk_factors")
+            .fetch_all(self.0.conn())
+            .await?;
+
+        let risk_factors = records
+            .into_iter()
+            .map(|record| {
+                let risk_factor = big_decimal_to_ratio(&record.risk_factor)
+                    .expect("Risk factor could not be negative");
+                (TokenId(record.token_id as u16), risk_factor)
+            })
+            .collect();
+
+        metrics::histogram!("sql.token.load_risk_factors", start.elapsed());
+        Ok(risk_factors)
+    }
+
+    /// Sets the risk factor of the token.
+    ///
+    /// Note, that the risk factor precision cannot be greater than `STORED_RISK_FACTOR_PRECISION`,
+    /// so the number might get rounded.
+    pub async fn store_risk_factor(
+        &mut self,
+        token_id: TokenId,
+        risk_factor: &Ratio<BigUint>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let risk_factor_rounded = ratio_to_big_decimal(risk_factor, STORED_RISK_FACTOR_PRECISION);
+        sqlx::query!(
+            r#"
+            INSERT INTO token_risk_factors ( token_id, risk_factor, updated_at )
+            VALUES ( $1, $2, now() )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET risk_factor = $2, updated_at = now()
+            "#,
+            i32::from(*token_id),
+            risk_factor_rounded
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_risk_factor", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the risk factor of the token, so the default one is used.
+    /// Returns `false` if the token had no risk factor.
+    pub async fn remove_risk_factor(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM token_risk_factors WHERE token_id = $1",
+            i32::from(*token_id)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.token.remove_risk_factor", start.elapsed());
+        Ok(removed > 0)
+    }
 }
@@ -55,6 +55,15 @@ impl Into<TokenPrice> for DbTickerPrice {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct DbPriceObservation {
+    pub id: i64,
+    pub token_id: i32,
+    pub source: String,
+    pub usd_price: BigDecimal,
+    pub observed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct DBMarketVolume {
     pub token_id: i32,
@@ -136,7 +136,7 @@ impl BlocksExecuteOperation {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AggregatedActionType {
     CommitBlocks,
     CreateProofBlocks,
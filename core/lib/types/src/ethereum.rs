@@ -56,7 +56,7 @@ pub struct ETHOperation {
     pub op_type: AggregatedActionType,
     /// Optional ZKSync operation associated with Ethereum operation.
     pub op: Option<(i64, AggregatedOperation)>,
-    /// Used nonce (fixed for all the sent transactions).
+    /// Used nonce (fixed for all the sent transactions unless it was consumed by an external transaction).
     pub nonce: U256,
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
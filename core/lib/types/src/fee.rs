@@ -1,10 +1,11 @@
 use num::rational::Ratio;
-use num::BigUint;
+use num::{BigUint, One, Zero};
 use serde::{Deserialize, Serialize};
 
-use crate::helpers::{closest_packable_fee_amount, pack_fee_amount, unpack_fee_amount};
+use crate::helpers::{closest_greater_or_eq_packable_fee_amount, closest_packable_fee_amount};
 use crate::tokens::ChangePubKeyFeeTypeArg;
-use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str};
+use crate::H256;
+use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
 
 /// Type of the fee calculation pattern.
 /// Unlike the `TxFeeTypes`, this enum represents the fee
@@ -16,7 +17,13 @@ pub enum OutputFeeType {
     TransferToNew,
     Withdraw,
     FastWithdraw,
+    ForcedExit,
     ChangePubKey(ChangePubKeyFeeTypeArg),
+    PermitDeposit,
+    MintNFT,
+    WithdrawNFT,
+    FastWithdrawNFT,
+    Swap,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,22 +38,161 @@ pub struct Fee {
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub zkp_fee: BigUint,
+    /// Total fee rounded up to the closest amount representable in the packed fee format,
+    /// so it can be used in the transaction as is.
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Total fee before the rounding to the packable amount.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub exact_total_fee: BigUint,
+    /// Server signature guaranteeing that `total_fee` is accepted until the quote expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
+    /// Intermediate values the fee is calculated from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<FeeBreakdown>,
+    /// `true` if the calculated fee exceeded the maximum fee of the transaction type
+    /// and was clamped to it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub capped: bool,
+    /// `true` if the fee is waived for the operation, so the transaction is accepted
+    /// with any fee, including zero.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub waived: bool,
+    /// Part of the gas cost covered by the subsidy, in the token the fee is paid in.
+    /// Zero if the operation is not subsidized.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub subsidy: BigUint,
+}
+
+/// Intermediate values of the fee calculation, so the fee can be explained to the user.
+/// The costs are given before the discounts granted to the sender. The costs of the fee
+/// clamped to the maximum fee are scaled down along with the fee.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Cost of the proof generation for the transaction, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_usd: Ratio<BigUint>,
+    /// Cost of the gas spent for the transaction on L1, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub gas_cost_usd: Ratio<BigUint>,
+    /// Price of one token the fee is paid in, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub token_price_usd: Ratio<BigUint>,
+    /// Gas price including the margin for its volatility, in wei.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_price_wei: BigUint,
+    /// Risk factor of the token the fee is multiplied by.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub risk_factor: Ratio<BigUint>,
+}
+
+/// Rounding of the total fee, applied before it's rounded up to the closest packable amount.
+/// The fee is always rounded up, so the user never pays less than the calculated fee.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct FeeRoundingPolicy {
+    /// Number of the significant digits kept in the fee. All the digits are kept if not set.
+    #[serde(default)]
+    pub significant_digits: Option<u32>,
+    /// The fee is rounded up to a multiple of this amount (in the smallest token units).
+    /// Zero means no rounding.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub granularity: BigUint,
+}
+
+impl FeeRoundingPolicy {
+    /// Rounds the amount up according to the policy. The result is always packable.
+    pub fn round(&self, amount: &BigUint) -> BigUint {
+        let mut amount = amount.clone();
+        if let Some(significant_digits) = self.significant_digits {
+            let digits = amount.to_str_radix(10).len() as u32;
+            if digits > significant_digits {
+                let unit = BigUint::from(10u32).pow(digits - significant_digits);
+                amount = round_up_to_multiple(&amount, &unit);
+            }
+        }
+        if !self.granularity.is_zero() {
+            amount = round_up_to_multiple(&amount, &self.granularity);
+        }
+        closest_greater_or_eq_packable_fee_amount(&amount)
+    }
+}
+
+fn round_up_to_multiple(amount: &BigUint, unit: &BigUint) -> BigUint {
+    (amount + unit - BigUint::one()) / unit * unit
+}
+
+/// Signed fee quote. Transaction paying exactly the quoted total fee is accepted
+/// until the expiration time, regardless of the gas and token price changes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeQuote {
+    pub id: u64,
+    /// Unix timestamp (in seconds) after which the quote is no longer accepted.
+    pub expires_at: i64,
+    /// Part of the quoted fee covered by the subsidy. The signature covers it, so the subsidy
+    /// spent on the transaction paying the quoted fee is known without repeating the quote.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub subsidy: BigUint,
+    pub signature: H256,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchFee {
+    /// Total fee rounded up to the closest amount representable in the packed fee format.
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Total fee before the rounding to the packable amount.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub exact_total_fee: BigUint,
+    /// `true` if the calculated fee exceeded the maximum fee of the batch and was clamped to it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub capped: bool,
+    /// `true` if the fee is waived for every transaction in the batch.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub waived: bool,
+    /// Part of the gas cost of the batch covered by the subsidies, in the token the fee
+    /// is calculated in. Zero if none of the operations is subsidized.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub subsidy: BigUint,
 }
 
 impl BatchFee {
-    pub fn new(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> BatchFee {
-        let (_, _, mut total_fee) = total_fee(zkp_fee, gas_fee);
-        total_fee = closest_packable_fee_amount(&total_fee);
-        BatchFee { total_fee }
+    pub fn new(
+        zkp_fee: &Ratio<BigUint>,
+        gas_fee: &Ratio<BigUint>,
+        rounding: &FeeRoundingPolicy,
+    ) -> BatchFee {
+        let (_, _, exact_total_fee) = total_fee(zkp_fee, gas_fee);
+        BatchFee {
+            total_fee: rounding.round(&exact_total_fee),
+            exact_total_fee,
+            capped: false,
+            waived: false,
+            subsidy: BigUint::zero(),
+        }
+    }
+
+    /// Zero fee of the batch which fee is waived.
+    pub fn waived() -> BatchFee {
+        BatchFee {
+            total_fee: BigUint::zero(),
+            exact_total_fee: BigUint::zero(),
+            capped: false,
+            waived: true,
+            subsidy: BigUint::zero(),
+        }
+    }
+
+    /// Clamps the fee to the maximum fee. The subsidy is scaled down in the same proportion.
+    pub fn cap(&mut self, maximum_fee: BigUint) {
+        let share = cap_share(&maximum_fee, &self.exact_total_fee);
+        self.subsidy = scale_down(&self.subsidy, &share);
+        self.total_fee = closest_packable_fee_amount(&maximum_fee);
+        self.exact_total_fee = maximum_fee;
+        self.capped = true;
     }
 }
 
@@ -57,27 +203,258 @@ impl Fee {
         gas_fee: Ratio<BigUint>,
         gas_tx_amount: BigUint,
         gas_price_wei: BigUint,
+        rounding: &FeeRoundingPolicy,
     ) -> Self {
-        let (zkp_fee, gas_fee, total_fee) = total_fee(&zkp_fee, &gas_fee);
+        let (zkp_fee, gas_fee, exact_total_fee) = total_fee(&zkp_fee, &gas_fee);
         Self {
             fee_type,
             gas_tx_amount,
             gas_price_wei,
             gas_fee,
             zkp_fee,
-            total_fee,
+            total_fee: rounding.round(&exact_total_fee),
+            exact_total_fee,
+            quote: None,
+            breakdown: None,
+            capped: false,
+            waived: false,
+            subsidy: BigUint::zero(),
+        }
+    }
+
+    /// Zero fee of the operation which fee is waived.
+    pub fn waived(fee_type: OutputFeeType, gas_price_wei: BigUint) -> Self {
+        Self {
+            waived: true,
+            ..Self::new(
+                fee_type,
+                Ratio::zero(),
+                Ratio::zero(),
+                BigUint::zero(),
+                gas_price_wei,
+                &FeeRoundingPolicy::default(),
+            )
+        }
+    }
+
+    pub fn with_breakdown(self, breakdown: FeeBreakdown) -> Self {
+        Self {
+            breakdown: Some(breakdown),
+            ..self
         }
     }
+
+    /// Clamps the fee to the maximum fee. The zkp and gas fees, the costs of the breakdown
+    /// and the subsidy are scaled down in the same proportion, so they still add up
+    /// to the total fee.
+    pub fn cap(&mut self, maximum_fee: BigUint) {
+        let share = cap_share(&maximum_fee, &self.exact_total_fee);
+        self.zkp_fee = scale_down(&self.zkp_fee, &share).min(maximum_fee.clone());
+        self.gas_fee = &maximum_fee - &self.zkp_fee;
+        self.subsidy = scale_down(&self.subsidy, &share);
+        if let Some(breakdown) = self.breakdown.as_mut() {
+            breakdown.zkp_cost_usd = &breakdown.zkp_cost_usd * &share;
+            breakdown.gas_cost_usd = &breakdown.gas_cost_usd * &share;
+        }
+        self.total_fee = closest_packable_fee_amount(&maximum_fee);
+        self.exact_total_fee = maximum_fee;
+        self.capped = true;
+    }
 }
 
+/// Returns the share of the calculated fee left after it's clamped to the maximum fee.
+fn cap_share(maximum_fee: &BigUint, exact_total_fee: &BigUint) -> Ratio<BigUint> {
+    if exact_total_fee.is_zero() {
+        return Ratio::one();
+    }
+    Ratio::new(maximum_fee.clone(), exact_total_fee.clone())
+}
+
+fn scale_down(amount: &BigUint, share: &Ratio<BigUint>) -> BigUint {
+    (Ratio::from_integer(amount.clone()) * share)
+        .floor()
+        .to_integer()
+}
+
+/// Gas cost of the transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxGasCost {
+    /// Total gas cost charged when the transaction is sent alone.
+    pub total: BigUint,
+    /// Part of the total cost covering the fixed overhead of the block commitment,
+    /// execution and proof.
+    pub overhead: BigUint,
+}
+
+/// Gas cost model of the transactions batch.
+///
+/// Transactions of the batch are always included into the same block, so instead of summing
+/// the standalone costs, the fixed overhead is charged in full only for the transaction
+/// with the largest one, while the other transactions pay the share of their overhead.
+/// The operation-specific gas and the zkp cost of the chunks are charged for every transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BatchFeeModel {
+    /// Share of the overhead paid by the transactions other than the one with the largest overhead.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub overhead_share: Ratio<BigUint>,
+}
+
+impl BatchFeeModel {
+    pub fn new(overhead_share: Ratio<BigUint>) -> Self {
+        assert!(
+            overhead_share <= Ratio::one(),
+            "overhead share can't exceed one"
+        );
+        Self { overhead_share }
+    }
+
+    /// Model charging every transaction in full, as if they were sent one by one.
+    pub fn standalone() -> Self {
+        Self::new(Ratio::one())
+    }
+
+    /// Returns the total gas cost of the batch transactions.
+    pub fn gas_amount(&self, costs: &[TxGasCost]) -> BigUint {
+        let total: BigUint = costs.iter().map(|cost| &cost.total).sum();
+        let max_overhead = match costs.iter().map(|cost| &cost.overhead).max() {
+            Some(max_overhead) => max_overhead,
+            None => return total,
+        };
+        let shared_overhead: BigUint =
+            costs.iter().map(|cost| &cost.overhead).sum::<BigUint>() - max_overhead;
+
+        // The discount is rounded down, so the batch is never charged less than the model implies.
+        let discount = Ratio::from_integer(shared_overhead) * (Ratio::one() - &self.overhead_share);
+        total - discount.floor().to_integer()
+    }
+}
+
+/// Returns the zkp and gas fees rounded up to integers, and their exact sum.
 fn total_fee(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> (BigUint, BigUint, BigUint) {
     let zkp_fee = round_precision(zkp_fee, 18).ceil().to_integer();
     let gas_fee = round_precision(gas_fee, 18).ceil().to_integer();
 
-    let total_fee = zkp_fee.clone() + gas_fee.clone();
-    (
-        zkp_fee,
-        gas_fee,
-        unpack_fee_amount(&pack_fee_amount(&total_fee)).expect("Failed to round gas fee amount."),
-    )
+    let total_fee = &zkp_fee + &gas_fee;
+    (zkp_fee, gas_fee, total_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::is_fee_amount_packable;
+
+    /// Checks that the total fee is rounded up to the closest packable amount.
+    #[test]
+    fn total_fee_is_packable() {
+        let zkp_fee = Ratio::from_integer(BigUint::from(1_000_000_001u64));
+        let gas_fee = Ratio::new(BigUint::from(2_345_678_901u64), BigUint::from(2u32));
+
+        let fee = Fee::new(
+            OutputFeeType::Transfer,
+            zkp_fee.clone(),
+            gas_fee.clone(),
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            &FeeRoundingPolicy::default(),
+        );
+        assert_eq!(fee.exact_total_fee, BigUint::from(2_172_839_452u64));
+        assert!(fee.total_fee > fee.exact_total_fee);
+        assert!(is_fee_amount_packable(&fee.total_fee));
+        assert_eq!(
+            fee.total_fee,
+            closest_greater_or_eq_packable_fee_amount(&fee.exact_total_fee)
+        );
+
+        let batch_fee = BatchFee::new(&zkp_fee, &gas_fee, &FeeRoundingPolicy::default());
+        assert_eq!(batch_fee.total_fee, fee.total_fee);
+        assert_eq!(batch_fee.exact_total_fee, fee.exact_total_fee);
+    }
+
+    /// Checks that the breakdown is only serialized when set.
+    #[test]
+    fn breakdown_serialization() {
+        let fee = Fee::new(
+            OutputFeeType::Transfer,
+            Ratio::from_integer(BigUint::from(100u32)),
+            Ratio::from_integer(BigUint::from(200u32)),
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+            &FeeRoundingPolicy::default(),
+        );
+        let value = serde_json::to_value(&fee).unwrap();
+        assert!(value.get("breakdown").is_none());
+
+        let breakdown = FeeBreakdown {
+            zkp_cost_usd: Ratio::new(BigUint::from(1u32), BigUint::from(1000u32)),
+            gas_cost_usd: Ratio::new(BigUint::from(3u32), BigUint::from(2u32)),
+            token_price_usd: Ratio::from_integer(BigUint::from(2000u32)),
+            gas_price_wei: BigUint::from(13_000_000_000u64),
+            risk_factor: Ratio::from_integer(BigUint::from(1u32)),
+        };
+        let value = serde_json::to_value(&fee.with_breakdown(breakdown.clone())).unwrap();
+        assert_eq!(value["breakdown"]["gasPriceWei"], "13000000000");
+        let deserialized: Fee = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.breakdown, Some(breakdown));
+    }
+
+    #[test]
+    fn batch_fee_model() {
+        let cost = |total: u32, overhead: u32| TxGasCost {
+            total: BigUint::from(total),
+            overhead: BigUint::from(overhead),
+        };
+        let costs = vec![cost(1000, 200), cost(3000, 400), cost(500, 0)];
+
+        assert_eq!(
+            BatchFeeModel::standalone().gas_amount(&costs),
+            BigUint::from(4500u32)
+        );
+        // The largest overhead is charged in full, the half of the rest.
+        let model = BatchFeeModel::new(Ratio::new(BigUint::from(1u32), BigUint::from(2u32)));
+        assert_eq!(model.gas_amount(&costs), BigUint::from(4400u32));
+        assert_eq!(model.gas_amount(&costs[1..2]), BigUint::from(3000u32));
+        assert_eq!(model.gas_amount(&[]), BigUint::zero());
+
+        let model = BatchFeeModel::new(Ratio::zero());
+        assert_eq!(model.gas_amount(&costs), BigUint::from(4300u32));
+    }
+
+    #[test]
+    fn rounding_policy() {
+        let amount = BigUint::from(123_456_789u64);
+
+        // Amount is only rounded up to the packable one by default.
+        let rounding = FeeRoundingPolicy::default();
+        assert_eq!(
+            rounding.round(&amount),
+            closest_greater_or_eq_packable_fee_amount(&amount)
+        );
+
+        let rounding = FeeRoundingPolicy {
+            significant_digits: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(rounding.round(&amount), BigUint::from(124_000_000u64));
+        // Amounts with fewer digits are kept intact.
+        assert_eq!(rounding.round(&BigUint::from(12u32)), BigUint::from(12u32));
+
+        let rounding = FeeRoundingPolicy {
+            significant_digits: None,
+            granularity: BigUint::from(1_000_000u32),
+        };
+        assert_eq!(rounding.round(&amount), BigUint::from(124_000_000u64));
+        assert_eq!(
+            rounding.round(&BigUint::from(5_000_000u32)),
+            BigUint::from(5_000_000u32)
+        );
+
+        let rounding = FeeRoundingPolicy {
+            significant_digits: Some(1),
+            granularity: BigUint::from(3u32),
+        };
+        // 123_456_789 -> 200_000_000 -> 200_000_001
+        let rounded = rounding.round(&amount);
+        assert!(rounded >= BigUint::from(200_000_001u64));
+        assert!(is_fee_amount_packable(&rounded));
+    }
 }
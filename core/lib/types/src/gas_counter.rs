@@ -33,6 +33,11 @@ impl CommitCost {
     pub const WITHDRAW_COST: u64 = 3_500;
     pub const FORCED_EXIT_COST: u64 = Self::WITHDRAW_COST; // TODO: Verify value (ZKS-109).
 
+    // Used for the fee quotes only, the circuit doesn't support the NFT operations and swaps yet.
+    pub const MINT_NFT_COST: u64 = 920;
+    pub const WITHDRAW_NFT_COST: u64 = 4_500;
+    pub const SWAP_COST: u64 = 700;
+
     pub fn base_cost() -> U256 {
         U256::from(Self::BASE_COST)
     }
@@ -80,6 +85,12 @@ impl VerifyCost {
     pub const WITHDRAW_COST: u64 = 48_000;
     pub const FORCED_EXIT_COST: u64 = Self::WITHDRAW_COST; // TODO: Verify value (ZKS-109).
 
+    // Used for the fee quotes only, the circuit doesn't support the NFT operations and swaps yet.
+    pub const MINT_NFT_COST: u64 = 0;
+    // Withdrawn NFT is minted as the ERC721 token on L1.
+    pub const WITHDRAW_NFT_COST: u64 = 150_000;
+    pub const SWAP_COST: u64 = 0;
+
     pub fn base_cost() -> U256 {
         U256::from(Self::BASE_COST)
     }
@@ -58,13 +58,18 @@ mod tests;
 
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
-pub use self::fee::{BatchFee, Fee, OutputFeeType};
+pub use self::fee::{
+    BatchFee, BatchFeeModel, Fee, FeeBreakdown, FeeQuote, FeeRoundingPolicy, OutputFeeType,
+    TxGasCost,
+};
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, TransferOp, TransferToNewOp, WithdrawOp,
     ZkSyncOp,
 };
 pub use self::priority_ops::{Deposit, FullExit, PriorityOp, ZkSyncPriorityOp};
-pub use self::tokens::{Token, TokenGenesisListItem, TokenLike, TokenPrice, TxFeeTypes};
+pub use self::tokens::{
+    NewTokenEvent, Token, TokenGenesisListItem, TokenLike, TokenPrice, TxFeeTypes,
+};
 pub use self::tx::{ForcedExit, SignedZkSyncTx, Transfer, Withdraw, ZkSyncTx};
 
 #[doc(hidden)]
@@ -26,6 +26,8 @@ impl ProverJobStatus {
     }
 }
 
+// Jobs with the lower priority value are given to the provers first.
+pub const URGENT_SINGLE_PROOF_JOB_PRIORITY: i32 = -1;
 pub const SINGLE_PROOF_JOB_PRIORITY: i32 = 1;
 pub const AGGREGATED_PROOF_JOB_PRIORITY: i32 = 0;
 
@@ -18,7 +18,7 @@ use crate::{
     },
     priority_ops::{Deposit, FullExit},
     tx::{ChangePubKey, ForcedExit, PackedEthSignature, TimeRange, Transfer, Withdraw},
-    Log, PriorityOp,
+    Log, NewTokenEvent, PriorityOp,
 };
 use lazy_static::lazy_static;
 use zksync_basic_types::{AccountId, Address, Nonce, TokenId, H256};
@@ -428,3 +428,44 @@ fn test_priority_op_from_valid_logs() {
         assert!(op.is_ok());
     }
 }
+
+#[test]
+fn test_new_token_event_from_log() {
+    let log = Log {
+        address: Address::from_str("5E6D086F5eC079ADFF4FB3774CDf3e8D6a34F7E9").unwrap(),
+        topics: vec![
+            H256::from_str("fe74dea79bde70d1990ddb655bac45735b14f495ddc508cfab80b7729aa9d668")
+                .unwrap(),
+            H256::from_str("000000000000000000000000a61464658afeaf65cccaafd3a512b69a83b77618")
+                .unwrap(),
+            H256::from_str("0000000000000000000000000000000000000000000000000000000000000011")
+                .unwrap(),
+        ],
+        data: Bytes(Vec::new()),
+        block_hash: None,
+        block_number: Some(1196475.into()),
+        transaction_hash: None,
+        transaction_index: None,
+        log_index: None,
+        transaction_log_index: None,
+        log_type: None,
+        removed: Some(false),
+    };
+
+    let event = NewTokenEvent::try_from(log.clone()).unwrap();
+    assert_eq!(
+        event,
+        NewTokenEvent {
+            eth_block_number: 1196475,
+            address: Address::from_str("a61464658afeaf65cccaafd3a512b69a83b77618").unwrap(),
+            id: TokenId(17),
+        }
+    );
+
+    // `NewToken` has two indexed fields, so the log without them is rejected.
+    let invalid_log = Log {
+        topics: log.topics[..1].to_vec(),
+        ..log
+    };
+    assert!(NewTokenEvent::try_from(invalid_log).is_err());
+}
@@ -1,8 +1,9 @@
-use crate::{Address, TokenId};
+use crate::{Address, Log, TokenId, U256};
+use anyhow::ensure;
 use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
-use std::{fmt, fs::read_to_string, path::PathBuf, str::FromStr};
+use std::{convert::TryFrom, fmt, fs::read_to_string, path::PathBuf, str::FromStr};
 use zksync_utils::parse_env;
 use zksync_utils::UnsignedRatioSerializeAsDecimal;
 
@@ -95,6 +96,39 @@ pub struct Token {
     pub decimals: u8,
 }
 
+/// `NewToken` event emitted by the governance contract once the token is added to the network,
+/// either by the governor or by paying the listing fee to the token governance contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewTokenEvent {
+    /// Number of the Ethereum block the event was emitted in.
+    pub eth_block_number: u64,
+    pub address: Address,
+    pub id: TokenId,
+}
+
+impl TryFrom<Log> for NewTokenEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: Log) -> Result<Self, Self::Error> {
+        // `address` and `tokenId` are both indexed, so they're stored in the topics.
+        ensure!(
+            event.topics.len() == 3,
+            "Failed to parse NewTokenEvent: {:#?}",
+            event
+        );
+        let eth_block_number = event
+            .block_number
+            .ok_or_else(|| anyhow::format_err!("Event log without the block number"))?
+            .as_u64();
+
+        Ok(Self {
+            eth_block_number,
+            address: Address::from_slice(&event.topics[1].as_fixed_bytes()[12..]),
+            id: TokenId(U256::from_big_endian(event.topics[2].as_fixed_bytes()).as_u32() as u16),
+        })
+    }
+}
+
 /// Tokens that added when deploying contract
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenGenesisListItem {
@@ -144,6 +178,41 @@ pub struct TokenMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Confidence in the token price reported to the users, depending on how it was obtained.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PriceConfidence {
+    /// The price is received from the price source and isn't expired yet.
+    Fresh,
+    /// The price source is not available or its price is rejected,
+    /// so the last accepted price is reported instead.
+    Fallback,
+}
+
+/// Whether the token can be used to pay fees, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenStatus {
+    pub allowed: bool,
+    /// Human-readable explanation of the decision.
+    pub reason: String,
+}
+
+/// Last measured liquidity of the token, which decides whether the token can be used to pay fees.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenLiquidity {
+    /// Market volume of the token in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub volume: Ratio<BigUint>,
+    pub last_updated: DateTime<Utc>,
+    /// Market volume below which the token is not accepted to pay fees.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub required_volume: Ratio<BigUint>,
+    /// The token is accepted regardless of its liquidity.
+    pub unconditionally_allowed: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum ChangePubKeyFeeType {
     Onchain,
@@ -164,14 +233,26 @@ pub enum ChangePubKeyFeeTypeArg {
 /// Type of transaction fees that exist in the zkSync network.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum TxFeeTypes {
-    /// Fee for the `Withdraw` or `ForcedExit` transaction.
+    /// Fee for the `Withdraw` transaction.
     Withdraw,
     /// Fee for the `Withdraw` operation that requires fast processing.
     FastWithdraw,
+    /// Fee for the `ForcedExit` transaction.
+    ForcedExit,
     /// Fee for the `Transfer` operation.
     Transfer,
     /// Fee for the `ChangePubKey` operation.
     ChangePubKey(ChangePubKeyFeeTypeArg),
+    /// Fee for the deposit authorized by the EIP-2612 permit and sent to L1 by the relayer.
+    PermitDeposit,
+    /// Fee for the `MintNFT` transaction.
+    MintNFT,
+    /// Fee for the `WithdrawNFT` transaction.
+    WithdrawNFT,
+    /// Fee for the `WithdrawNFT` operation that requires fast processing.
+    FastWithdrawNFT,
+    /// Fee for the `Swap` transaction.
+    Swap,
 }
 
 #[cfg(test)]
@@ -15,7 +15,7 @@ use zksync_crypto::{
 };
 use zksync_utils::{format_units, BigUintSerdeAsRadix10Str};
 
-use super::{PackedEthSignature, TimeRange, TxSignature, VerifiedSignatureCache};
+use super::{EIP712Struct, PackedEthSignature, TimeRange, TxSignature, VerifiedSignatureCache};
 use crate::tokens::{ChangePubKeyFeeType, ChangePubKeyFeeTypeArg};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -411,6 +411,23 @@ impl ChangePubKey {
         message
     }
 
+    /// Returns the EIP-712 `hashStruct` value of the transaction, which may be signed
+    /// by Ethereum keys of the account instead of the text message.
+    ///
+    /// Note that it doesn't replace the Ethereum authorization data, since the latter
+    /// is verified by the zkSync contract.
+    pub fn get_eip712_struct_hash(&self, token_symbol: &str) -> H256 {
+        EIP712Struct::new(
+            "ChangePubKey(bytes20 pubKeyHash,string feeToken,uint256 fee,uint32 accountId,uint32 nonce)",
+        )
+        .add_fixed_bytes(&self.new_pk_hash.data)
+        .add_string(token_symbol)
+        .add_uint(&self.fee)
+        .add_uint(&BigUint::from(*self.account_id))
+        .add_uint(&BigUint::from(*self.nonce))
+        .hash()
+    }
+
     pub fn get_fee_type(&self) -> TxFeeTypes {
         if let Some(auth_data) = &self.eth_auth_data {
             TxFeeTypes::ChangePubKey(ChangePubKeyFeeTypeArg::ContractsV4Version(
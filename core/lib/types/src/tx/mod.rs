@@ -26,10 +26,16 @@ pub use self::{
 
 // Re-export primitives associated with transactions.
 pub use self::primitives::{
-    eip1271_signature::EIP1271Signature, eth_batch_sign_data::EthBatchSignData,
-    eth_batch_signature::EthBatchSignatures, eth_signature::TxEthSignature,
-    packed_eth_signature::PackedEthSignature, packed_public_key::PackedPublicKey,
-    packed_signature::PackedSignature, signature::TxSignature, time_range::TimeRange,
+    eip1271_signature::EIP1271Signature,
+    eip712_signature::{EIP712Domain, EIP712Struct},
+    eth_batch_sign_data::EthBatchSignData,
+    eth_batch_signature::EthBatchSignatures,
+    eth_signature::TxEthSignature,
+    packed_eth_signature::PackedEthSignature,
+    packed_public_key::PackedPublicKey,
+    packed_signature::PackedSignature,
+    signature::TxSignature,
+    time_range::TimeRange,
     tx_hash::TxHash,
 };
 
@@ -0,0 +1,122 @@
+//! Structured data hashing according to EIP-712.
+//!
+//! Instead of the human-readable text message, the user may sign the typed representation
+//! of the transaction, so the wallet can display the transaction fields instead of asking
+//! for a blind text signature. The signed data is
+//! `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(transaction))`.
+//!
+//! See https://eips.ethereum.org/EIPS/eip-712 for details.
+
+use num::BigUint;
+use zksync_basic_types::{Address, H256};
+
+/// Name of the signing domain shown to the user by the wallet.
+pub const EIP712_DOMAIN_NAME: &str = "zkSync";
+/// Version of the typed data format. Must be increased every time the structures are changed.
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+const EIP712_DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId)";
+
+/// Domain separating the zkSync typed data from the typed data of other applications and networks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EIP712Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: u8,
+}
+
+impl EIP712Domain {
+    /// Creates the zkSync domain for the network with the given chain ID.
+    pub fn zksync(chain_id: u8) -> Self {
+        Self {
+            name: EIP712_DOMAIN_NAME.to_string(),
+            version: EIP712_DOMAIN_VERSION.to_string(),
+            chain_id,
+        }
+    }
+
+    /// Returns the `domainSeparator` value.
+    pub fn separator(&self) -> H256 {
+        EIP712Struct::new(EIP712_DOMAIN_TYPE)
+            .add_string(&self.name)
+            .add_string(&self.version)
+            .add_uint(&BigUint::from(self.chain_id))
+            .hash()
+    }
+
+    /// Returns the hash which is actually signed by the user for the structure with the given hash.
+    pub fn signing_hash(&self, struct_hash: H256) -> H256 {
+        let mut bytes = Vec::with_capacity(2 + 32 + 32);
+        bytes.extend_from_slice(b"\x19\x01");
+        bytes.extend_from_slice(self.separator().as_bytes());
+        bytes.extend_from_slice(struct_hash.as_bytes());
+        H256(tiny_keccak::keccak256(&bytes))
+    }
+}
+
+/// Encoder of the structure fields, used to calculate the `hashStruct` value.
+///
+/// Fields must be added in the same order as they're declared in the type definition.
+#[derive(Debug, Clone)]
+pub struct EIP712Struct {
+    encoded: Vec<u8>,
+}
+
+impl EIP712Struct {
+    /// Creates the encoder for the structure with the given type definition,
+    /// e.g. `Mail(address from,address to,string contents)`.
+    pub fn new(type_definition: &str) -> Self {
+        let mut encoded = Vec::with_capacity(32 * 8);
+        encoded.extend_from_slice(&tiny_keccak::keccak256(type_definition.as_bytes()));
+        Self { encoded }
+    }
+
+    /// Adds the `address` field.
+    pub fn add_address(mut self, value: &Address) -> Self {
+        self.encoded.extend_from_slice(&[0u8; 12]);
+        self.encoded.extend_from_slice(value.as_bytes());
+        self
+    }
+
+    /// Adds the `uint256` (or any shorter unsigned integer type) field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value doesn't fit into 256 bits.
+    pub fn add_uint(mut self, value: &BigUint) -> Self {
+        let bytes = value.to_bytes_be();
+        assert!(bytes.len() <= 32, "Value doesn't fit into uint256");
+        self.encoded
+            .extend(std::iter::repeat(0u8).take(32 - bytes.len()));
+        self.encoded.extend_from_slice(&bytes);
+        self
+    }
+
+    /// Adds the `string` field.
+    pub fn add_string(mut self, value: &str) -> Self {
+        self.encoded
+            .extend_from_slice(&tiny_keccak::keccak256(value.as_bytes()));
+        self
+    }
+
+    /// Adds the fixed-size `bytesN` field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is longer than 32 bytes.
+    pub fn add_fixed_bytes(mut self, value: &[u8]) -> Self {
+        assert!(
+            value.len() <= 32,
+            "Fixed bytes value is longer than 32 bytes"
+        );
+        self.encoded.extend_from_slice(value);
+        self.encoded
+            .extend(std::iter::repeat(0u8).take(32 - value.len()));
+        self
+    }
+
+    /// Returns the `hashStruct` value.
+    pub fn hash(&self) -> H256 {
+        H256(tiny_keccak::keccak256(&self.encoded))
+    }
+}
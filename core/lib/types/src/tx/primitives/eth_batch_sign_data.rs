@@ -2,9 +2,12 @@
 use anyhow::ensure;
 use itertools::Itertools;
 // Workspace uses
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 // Local uses
-use super::eth_signature::TxEthSignature;
+use super::{
+    eip712_signature::{EIP712Domain, EIP712Struct},
+    eth_signature::TxEthSignature,
+};
 use crate::{Token, ZkSyncTx};
 
 /// Encapsulates transactions batch signature data. Should only be created via `new()`
@@ -92,17 +95,37 @@ impl EthBatchSignData {
         }
     }
 
-    /// Returns an old-format message that should be signed by Ethereum account key.
-    /// Needed for backwards compatibility.
-    pub fn get_old_ethereum_batch_message<'a, I>(txs: I) -> Vec<u8>
+    /// Returns the hash of the batch, which commits to the bytes of every transaction in it.
+    pub fn get_batch_hash<'a, I>(txs: I) -> H256
     where
         I: Iterator<Item = &'a ZkSyncTx>,
     {
-        tiny_keccak::keccak256(
+        H256(tiny_keccak::keccak256(
             txs.flat_map(ZkSyncTx::get_bytes)
                 .collect::<Vec<u8>>()
                 .as_slice(),
-        )
-        .to_vec()
+        ))
+    }
+
+    /// Returns an old-format message that should be signed by Ethereum account key.
+    /// Needed for backwards compatibility.
+    pub fn get_old_ethereum_batch_message<'a, I>(txs: I) -> Vec<u8>
+    where
+        I: Iterator<Item = &'a ZkSyncTx>,
+    {
+        Self::get_batch_hash(txs).as_bytes().to_vec()
+    }
+
+    /// Returns the hash which should be signed to authorize the whole batch with
+    /// a single EIP-712 typed data signature. The typed data is `Batch(bytes32 batchHash)`,
+    /// where `batchHash` is the value returned by `get_batch_hash`.
+    pub fn get_eip712_batch_signing_hash<'a, I>(txs: I, chain_id: u8) -> H256
+    where
+        I: Iterator<Item = &'a ZkSyncTx>,
+    {
+        let struct_hash = EIP712Struct::new("Batch(bytes32 batchHash)")
+            .add_fixed_bytes(Self::get_batch_hash(txs).as_bytes())
+            .hash();
+        EIP712Domain::zksync(chain_id).signing_hash(struct_hash)
     }
 }
@@ -130,3 +130,121 @@ fn test_batch_message() {
     let message = EthBatchSignData::get_batch_sign_message(txs);
     assert_eq!(message, expected.into_bytes());
 }
+
+/// Checks that the EIP-712 typed data signature of the transaction can be verified
+/// and is bound to both the transaction data and the network.
+#[test]
+fn test_eip712_signature() {
+    let private_key = "0b43c0f5b5a13a7047408d1f8c8ad32ba5879902ea6212184e0a5d1157281d76"
+        .parse()
+        .unwrap();
+    let signer = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+    let token = Token::new(TokenId(0), Default::default(), "ETH", 18);
+
+    let txs = vec![
+        ZkSyncTx::from(get_transfer()),
+        ZkSyncTx::from(get_withdraw()),
+        ZkSyncTx::from(get_change_pub_key()),
+    ];
+    for tx in txs {
+        let signing_hash = tx.get_eip712_signing_hash(&token, 1).unwrap();
+        let signature = PackedEthSignature::sign_hash(&private_key, &signing_hash).unwrap();
+        assert_eq!(
+            signature
+                .signature_recover_signer_from_hash(&signing_hash)
+                .unwrap(),
+            signer
+        );
+
+        // Signature from another network must not be accepted.
+        let other_network_hash = tx.get_eip712_signing_hash(&token, 4).unwrap();
+        assert_ne!(signing_hash, other_network_hash);
+        assert_ne!(
+            signature
+                .signature_recover_signer_from_hash(&other_network_hash)
+                .unwrap(),
+            signer
+        );
+    }
+
+    // Typed data covers every field shown to the user.
+    let transfer = get_transfer();
+    let mut changed_transfer = transfer.clone();
+    changed_transfer.fee = 1u32.into();
+    assert_ne!(
+        transfer.get_eip712_struct_hash("ETH"),
+        changed_transfer.get_eip712_struct_hash("ETH")
+    );
+    assert_ne!(
+        transfer.get_eip712_struct_hash("ETH"),
+        transfer.get_eip712_struct_hash("DAI")
+    );
+
+    // Transfer and withdraw with the same fields are not interchangeable.
+    let withdraw = get_withdraw();
+    let mut transfer = get_transfer();
+    transfer.from = withdraw.from;
+    transfer.to = withdraw.to;
+    transfer.amount = withdraw.amount.clone();
+    transfer.fee = withdraw.fee.clone();
+    transfer.nonce = withdraw.nonce;
+    assert_ne!(
+        transfer.get_eip712_struct_hash("ETH"),
+        withdraw.get_eip712_struct_hash("ETH")
+    );
+}
+
+/// Checks that the single EIP-712 signature over the batch hash is bound to the batch contents.
+#[test]
+fn test_eip712_batch_signature() {
+    let private_key = "0b43c0f5b5a13a7047408d1f8c8ad32ba5879902ea6212184e0a5d1157281d76"
+        .parse()
+        .unwrap();
+    let signer = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+
+    let txs = vec![
+        ZkSyncTx::from(get_transfer()),
+        ZkSyncTx::from(get_withdraw()),
+    ];
+    let signing_hash = EthBatchSignData::get_eip712_batch_signing_hash(txs.iter(), 1);
+    let signature = PackedEthSignature::sign_hash(&private_key, &signing_hash).unwrap();
+    assert_eq!(
+        signature
+            .signature_recover_signer_from_hash(&signing_hash)
+            .unwrap(),
+        signer
+    );
+
+    // The batch hash is the one used by the old batch message format.
+    assert_eq!(
+        EthBatchSignData::get_batch_hash(txs.iter()).as_bytes(),
+        EthBatchSignData::get_old_ethereum_batch_message(txs.iter()).as_slice()
+    );
+
+    // Changing the order of transactions changes the signed data.
+    let reordered_hash = EthBatchSignData::get_eip712_batch_signing_hash(txs.iter().rev(), 1);
+    assert_ne!(signing_hash, reordered_hash);
+    // As well as changing the network.
+    let other_network_hash = EthBatchSignData::get_eip712_batch_signing_hash(txs.iter(), 4);
+    assert_ne!(signing_hash, other_network_hash);
+}
+
+/// Checks the encoding of the EIP-712 domain separator.
+#[test]
+fn test_eip712_domain_separator() {
+    let domain = EIP712Domain::zksync(1);
+    let mut encoded = Vec::new();
+    encoded.extend_from_slice(&tiny_keccak::keccak256(
+        b"EIP712Domain(string name,string version,uint256 chainId)",
+    ));
+    encoded.extend_from_slice(&tiny_keccak::keccak256(b"zkSync"));
+    encoded.extend_from_slice(&tiny_keccak::keccak256(b"1"));
+    let mut chain_id = [0u8; 32];
+    chain_id[31] = 1;
+    encoded.extend_from_slice(&chain_id);
+
+    assert_eq!(
+        domain.separator().as_bytes(),
+        &tiny_keccak::keccak256(&encoded)[..]
+    );
+}
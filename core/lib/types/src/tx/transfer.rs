@@ -11,12 +11,12 @@ use crate::account::PubKeyHash;
 use crate::utils::ethereum_sign_message_part;
 use crate::Engine;
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
 use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::{format_units, BigUintSerdeAsRadix10Str};
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{EIP712Struct, TxSignature, VerifiedSignatureCache};
 
 /// `Transfer` transaction performs a move of funds from one zkSync account to another.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -190,6 +190,21 @@ impl Transfer {
         message
     }
 
+    /// Returns the EIP-712 `hashStruct` value of the transaction, which may be signed
+    /// by Ethereum keys of the account instead of the text message.
+    pub fn get_eip712_struct_hash(&self, token_symbol: &str) -> H256 {
+        EIP712Struct::new(
+            "Transfer(address from,address to,string token,uint256 amount,uint256 fee,uint32 nonce)",
+        )
+        .add_address(&self.from)
+        .add_address(&self.to)
+        .add_string(token_symbol)
+        .add_uint(&self.amount)
+        .add_uint(&self.fee)
+        .add_uint(&BigUint::from(*self.nonce))
+        .hash()
+    }
+
     /// Returns an old-format message that should be signed by Ethereum account key.
     /// Needed for backwards compatibility.
     pub fn get_old_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
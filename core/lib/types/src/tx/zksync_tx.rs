@@ -2,11 +2,13 @@ use num::BigUint;
 use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
 
-use zksync_basic_types::{AccountId, Address};
+use zksync_basic_types::{AccountId, Address, H256};
 
 use crate::{
     operations::ChangePubKeyOp,
-    tx::{ChangePubKey, Close, ForcedExit, Transfer, TxEthSignature, TxHash, Withdraw},
+    tx::{
+        ChangePubKey, Close, EIP712Domain, ForcedExit, Transfer, TxEthSignature, TxHash, Withdraw,
+    },
     utils::deserialize_eth_message,
     CloseOp, ForcedExitOp, Nonce, Token, TokenId, TokenLike, TransferOp, TxFeeTypes, WithdrawOp,
 };
@@ -200,6 +202,19 @@ impl ZkSyncTx {
         }
     }
 
+    /// Returns the EIP-712 hash that user may sign instead of the text message
+    /// returned by `get_ethereum_sign_message`.
+    /// If the transaction doesn't support typed data signatures, returns `None`.
+    pub fn get_eip712_signing_hash(&self, token: &Token, chain_id: u8) -> Option<H256> {
+        let struct_hash = match self {
+            ZkSyncTx::Transfer(tx) => tx.get_eip712_struct_hash(&token.symbol),
+            ZkSyncTx::Withdraw(tx) => tx.get_eip712_struct_hash(&token.symbol),
+            ZkSyncTx::ChangePubKey(tx) => tx.get_eip712_struct_hash(&token.symbol),
+            _ => return None,
+        };
+        Some(EIP712Domain::zksync(chain_id).signing_hash(struct_hash))
+    }
+
     /// Returns the corresponding part of the batch message user has to sign in order
     /// to send it. In this case we handle `ChangePubKey` on the server side and
     /// expect a line in the message for it.
@@ -282,7 +297,7 @@ impl ZkSyncTx {
                 ))
             }
             ZkSyncTx::ForcedExit(forced_exit) => Some((
-                TxFeeTypes::Withdraw,
+                TxFeeTypes::ForcedExit,
                 TokenLike::Id(forced_exit.token),
                 forced_exit.target,
                 forced_exit.fee.clone(),
@@ -0,0 +1,218 @@
+//! Reporting of the panics to the Sentry-compatible error tracking service.
+//!
+//! The reporter is enabled by setting the `MISC_SENTRY_DSN` env variable to the project DSN
+//! (`https://<public_key>@<host>/<project_id>`). Once enabled, every panic is sent to the service
+//! along with its location, the thread name and the name of the supervised task it happened in
+//! (see `TaskContext`). Events are sent by a dedicated thread, so the panic hook never blocks
+//! the async runtime.
+
+// Built-in uses
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, PanicInfo},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+// External uses
+use once_cell::sync::OnceCell;
+use serde_json::{json, Value};
+
+/// Name of the client reported to the service.
+const CLIENT_NAME: &str = "zksync-vlog/1.0";
+
+static EVENTS: OnceCell<Mutex<mpsc::Sender<Value>>> = OnceCell::new();
+static EVENT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static CURRENT_TASK: RefCell<Option<&'static str>> = RefCell::new(None);
+}
+
+/// Parsed data source name of the Sentry project.
+#[derive(Debug, Clone, PartialEq)]
+struct Dsn {
+    store_url: String,
+    public_key: String,
+}
+
+impl Dsn {
+    fn parse(dsn: &str) -> Result<Self, String> {
+        let error = || format!("Invalid Sentry DSN: {}", dsn);
+
+        let (scheme, rest) = dsn.split_at(dsn.find("://").ok_or_else(error)?);
+        let rest = &rest[3..];
+        let (public_key, rest) = rest.split_at(rest.find('@').ok_or_else(error)?);
+        let (host, project_id) = rest[1..].split_at(rest[1..].rfind('/').ok_or_else(error)?);
+        let project_id = &project_id[1..];
+        if public_key.is_empty() || host.is_empty() || project_id.is_empty() {
+            return Err(error());
+        }
+
+        Ok(Self {
+            store_url: format!("{}://{}/api/{}/store/", scheme, host, project_id),
+            public_key: public_key.to_string(),
+        })
+    }
+
+    fn auth_header(&self) -> String {
+        format!(
+            "Sentry sentry_version=7, sentry_client={}, sentry_key={}",
+            CLIENT_NAME, self.public_key
+        )
+    }
+}
+
+/// Marks the code running within the named task, so the panics happening in it are reported
+/// with the task name. The mark is removed once the returned guard is dropped.
+#[derive(Debug)]
+pub struct TaskContext {
+    previous: Option<&'static str>,
+}
+
+impl TaskContext {
+    pub fn enter(task: &'static str) -> Self {
+        let previous = CURRENT_TASK.with(|current| current.borrow_mut().replace(task));
+        Self { previous }
+    }
+}
+
+impl Drop for TaskContext {
+    fn drop(&mut self) {
+        CURRENT_TASK.with(|current| *current.borrow_mut() = self.previous);
+    }
+}
+
+/// Starts the reporter thread and installs the panic hook reporting the panics.
+/// The previously installed hook is still called, so the panics are printed as usual.
+pub(crate) fn init(dsn: &str) {
+    let dsn = Dsn::parse(dsn).unwrap_or_else(|err| panic!("{}", err));
+    let (sender, receiver) = mpsc::channel::<Value>();
+    if EVENTS.set(Mutex::new(sender)).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("crash_reporter".to_string())
+        .spawn(move || {
+            let client = reqwest::blocking::Client::new();
+            for event in receiver {
+                let response = client
+                    .post(&dsn.store_url)
+                    .header("X-Sentry-Auth", dsn.auth_header())
+                    .json(&event)
+                    .send();
+                if let Err(err) = response.and_then(|response| response.error_for_status()) {
+                    eprintln!("Failed to send the crash report: {}", err);
+                }
+            }
+        })
+        .expect("failed to start the crash reporter thread");
+
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous_hook(info);
+        report_panic(info);
+    }));
+}
+
+/// Sends the error event with the given message. Does nothing if the reporter is not enabled.
+pub fn report_error(message: &str) {
+    send_event(event("error", message, None));
+}
+
+/// Extracts the message from the panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<Any>")
+}
+
+fn report_panic(info: &PanicInfo<'_>) {
+    let message = panic_message(info.payload());
+    let location = info.location().map(|location| {
+        format!(
+            "{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        )
+    });
+
+    send_event(event("fatal", message, location));
+}
+
+fn event(level: &str, message: &str, location: Option<String>) -> Value {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is before the UNIX epoch");
+    let counter = EVENT_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let task = CURRENT_TASK
+        .try_with(|current| *current.borrow())
+        .ok()
+        .flatten();
+
+    json!({
+        "event_id": format!("{:016x}{:016x}", timestamp.as_nanos() as u64, counter),
+        "timestamp": timestamp.as_secs_f64(),
+        "platform": "native",
+        "level": level,
+        "logger": "vlog",
+        "message": message,
+        "tags": {
+            "thread": thread::current().name().unwrap_or("<unnamed>"),
+            "task": task.unwrap_or("<none>"),
+        },
+        "extra": {
+            "location": location,
+        },
+    })
+}
+
+fn send_event(event: Value) {
+    if let Some(events) = EVENTS.get() {
+        // The receiver lives as long as the process, so sending can only fail on shutdown.
+        let _ = events.lock().unwrap().send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dsn() {
+        let dsn = Dsn::parse("https://abcdef@sentry.example.com/42").unwrap();
+        assert_eq!(dsn.store_url, "https://sentry.example.com/api/42/store/");
+        assert_eq!(dsn.public_key, "abcdef");
+
+        assert!(Dsn::parse("sentry.example.com/42").is_err());
+        assert!(Dsn::parse("https://sentry.example.com/42").is_err());
+        assert!(Dsn::parse("https://abcdef@sentry.example.com/").is_err());
+    }
+
+    #[test]
+    fn task_context() {
+        assert_eq!(CURRENT_TASK.with(|current| *current.borrow()), None);
+        {
+            let _outer = TaskContext::enter("outer");
+            {
+                let _inner = TaskContext::enter("inner");
+                assert_eq!(
+                    CURRENT_TASK.with(|current| *current.borrow()),
+                    Some("inner")
+                );
+            }
+            assert_eq!(
+                CURRENT_TASK.with(|current| *current.borrow()),
+                Some("outer")
+            );
+        }
+        assert_eq!(CURRENT_TASK.with(|current| *current.borrow()), None);
+    }
+}
@@ -5,9 +5,23 @@
 //! For warn and error macros we are adding file line and column to tracing variables
 //!
 //! The format of the logs in stdout can be `plain` or` json` and is set by the `MISC_LOG_FORMAT` env variable.
+//! The log levels are set by the `RUST_LOG` env variable and can be changed at runtime via `set_log_filter`.
+//! If the `MISC_SENTRY_DSN` env variable is set, panics are also reported to the Sentry-compatible service.
 //!
 //! Full documentation for the `tracing` crate here https://docs.rs/tracing/
 
+use std::sync::Mutex;
+
+use once_cell::sync::OnceCell;
+use tracing_subscriber::{reload, EnvFilter};
+
+pub mod crash_reporter;
+pub mod task_supervisor;
+
+pub use crate::{
+    crash_reporter::{report_error, TaskContext},
+    task_supervisor::supervised,
+};
 pub use tracing as __tracing;
 pub use tracing::{debug, info, log, trace};
 
@@ -35,16 +49,89 @@ macro_rules! error {
     };
 }
 
+/// Replaces the filter of the installed subscriber.
+type FilterReloader = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Filter of the logs which can be replaced at runtime.
+struct LogFilter {
+    reload: FilterReloader,
+    directives: Mutex<String>,
+}
+
+static LOG_FILTER: OnceCell<LogFilter> = OnceCell::new();
+
+/// Initializes the logger. The initial per-module log levels are taken from the `RUST_LOG`
+/// env variable and can be changed later via `set_log_filter`. If `RUST_LOG` can't be parsed,
+/// the default filter is used, as if the variable was not set.
 pub fn init() {
+    let mut directives = std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default();
+    let (filter, parse_error) = match EnvFilter::try_new(&directives) {
+        Ok(filter) => (filter, None),
+        Err(err) => {
+            directives.clear();
+            (EnvFilter::default(), Some(err))
+        }
+    };
+
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
-    match log_format.as_str() {
-        "plain" => tracing_subscriber::fmt::init(),
+    let reload: FilterReloader = match log_format.as_str() {
+        "plain" => {
+            let builder = tracing_subscriber::fmt::Subscriber::builder()
+                .with_env_filter(filter)
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            builder.init();
+            Box::new(move |filter| handle.reload(filter))
+        }
         "json" => {
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            let builder = tracing_subscriber::fmt::Subscriber::builder()
+                .with_env_filter(filter)
                 .json()
-                .init();
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            builder.init();
+            Box::new(move |filter| handle.reload(filter))
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
     };
+
+    let _ = LOG_FILTER.set(LogFilter {
+        reload,
+        directives: Mutex::new(directives),
+    });
+
+    // Reported once the logger is installed, so the message is formatted as the other logs.
+    if let Some(err) = parse_error {
+        tracing::warn!(
+            "{} has an unexpected value, the default log filter is used: {}",
+            EnvFilter::DEFAULT_ENV,
+            err
+        );
+    }
+
+    let sentry_dsn = std::env::var("MISC_SENTRY_DSN").unwrap_or_default();
+    if !sentry_dsn.is_empty() {
+        crash_reporter::init(&sentry_dsn);
+    }
+}
+
+/// Returns the current filter directives, e.g. `info,zksync_api=debug`.
+/// Returns `None` if the logger is not initialized.
+pub fn log_filter() -> Option<String> {
+    LOG_FILTER
+        .get()
+        .map(|filter| filter.directives.lock().unwrap().clone())
+}
+
+/// Replaces the filter of the logs. The directives have the same format as the `RUST_LOG`
+/// variable, so the log level can be set for every module separately without a restart.
+pub fn set_log_filter(directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+    let log_filter = LOG_FILTER
+        .get()
+        .ok_or_else(|| "Logger is not initialized".to_string())?;
+
+    (log_filter.reload)(filter).map_err(|err| err.to_string())?;
+    *log_filter.directives.lock().unwrap() = directives.to_string();
+    Ok(())
 }
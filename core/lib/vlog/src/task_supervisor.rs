@@ -0,0 +1,64 @@
+//! Supervision of the long-running tasks.
+
+// Built-in deps
+use std::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    task::{Context, Poll},
+};
+// External uses
+use futures::FutureExt;
+// Local uses
+use crate::crash_reporter::{panic_message, TaskContext};
+
+/// Future which marks every poll of the inner future with the task name,
+/// so the panics happening inside are reported with it.
+struct NamedTask<F> {
+    name: &'static str,
+    inner: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for NamedTask<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let _context = TaskContext::enter(self.name);
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+/// Wraps the future of the long-running task, so its panic is logged (and reported if the crash
+/// reporter is enabled) with the task name instead of silently killing the task.
+///
+/// Should be used for the tasks which are spawned without their handles being awaited,
+/// e.g. `tokio::spawn(vlog::supervised("ticker_updater", updater.keep_updated(interval)))`.
+pub fn supervised<F>(name: &'static str, future: F) -> impl Future<Output = ()>
+where
+    F: Future<Output = ()>,
+{
+    let task = NamedTask {
+        name,
+        inner: Box::pin(future),
+    };
+    AssertUnwindSafe(task).catch_unwind().map(move |result| {
+        if let Err(payload) = result {
+            tracing::error!(
+                "Task `{}` panicked: {}",
+                name,
+                panic_message(payload.as_ref())
+            );
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_is_caught() {
+        let task = supervised("failing_task", async { panic!("Task failure") });
+        futures::executor::block_on(task);
+    }
+}
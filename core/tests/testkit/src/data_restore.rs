@@ -30,6 +30,7 @@ pub async fn verify_restore(
         0,
         true,
         Default::default(),
+        true,
         contract,
     );
 
@@ -18,10 +18,11 @@ export async function contract() {
     env.reload();
     const contractAddress = process.env.CONTRACTS_CONTRACT_ADDR;
     const governanceAddress = process.env.CONTRACTS_GOVERNANCE_ADDR;
-    await utils.exec(`${SQL()} "INSERT INTO server_config (contract_addr, gov_contract_addr)
-					 VALUES ('${contractAddress}', '${governanceAddress}')
+    const network = process.env.CHAIN_ETH_NETWORK;
+    await utils.exec(`${SQL()} "INSERT INTO server_config (contract_addr, gov_contract_addr, network)
+					 VALUES ('${contractAddress}', '${governanceAddress}', '${network}')
 					 ON CONFLICT (id) DO UPDATE
-					 SET (contract_addr, gov_contract_addr) = ('${contractAddress}', '${governanceAddress}')"`);
+					 SET (contract_addr, gov_contract_addr, network) = ('${contractAddress}', '${governanceAddress}', '${network}')"`);
     console.log('Successfully inserted contract address into the database');
 }
 
@@ -28,6 +28,10 @@ export async function resume() {
     await utils.spawn('cargo run --bin zksync_data_restore --release -- --continue');
 }
 
+export async function follow() {
+    await utils.spawn('cargo run --bin zksync_data_restore --release -- --continue --verify_roots');
+}
+
 export async function run() {
     await utils.spawn('cargo run --bin zksync_data_restore --release -- --genesis --finite');
 }
@@ -48,6 +52,10 @@ export const command = new Command('data-restore');
 
 command.command('restart').description('wipe the database and run data restore in finite mode').action(restart);
 command.command('resume').description('run data restore in "resume" mode').action(resume);
+command
+    .command('follow')
+    .description('continue data restore from the last restored block and check the root hash of every block')
+    .action(follow);
 command.command('run').description('do not wipe the database and run data restore in finite mode').action(run);
 
 command
@@ -12,6 +12,8 @@ pub enum ClientError {
     RpcError(RpcFailure),
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("REST API error: {0}")]
+    RestError(String),
 
     #[error("Provided account credentials are incorrect")]
     IncorrectCredentials,
@@ -3,6 +3,7 @@ pub mod error;
 pub mod ethereum;
 pub mod operations;
 pub mod provider;
+pub mod rest_provider;
 pub mod signer;
 pub mod tokens_cache;
 pub mod types;
@@ -11,7 +12,7 @@ pub mod wallet;
 
 pub use crate::{
     credentials::WalletCredentials, ethereum::EthereumProvider, provider::RpcProvider,
-    wallet::Wallet,
+    rest_provider::RestProvider, wallet::Wallet,
 };
 pub use zksync_types::network::Network;
 
@@ -0,0 +1,236 @@
+//! Provider for the REST API v1 of the zkSync server.
+//!
+//! Unlike the `RpcProvider`, it gives access to the API parts which are available
+//! only via REST: the fee breakdown, token fee statuses, receipts, priority operations
+//! and the deposits authorized by the EIP-2612 permit signatures.
+
+// Built-in imports
+use std::{future::Future, time::Duration};
+
+// Workspace uses
+use zksync_api_client::rest::v1::{
+    accounts::{AccountInfo, AccountQuery},
+    Client, ClientError as RestClientError, Contracts, IncomingPermitDeposit, PermitDepositStatus,
+    PriorityOpQuery, PriorityOpReceipt, Receipt, TxData,
+};
+use zksync_types::{
+    network::Network,
+    tokens::FeeTokenStatus,
+    tx::{EthBatchSignatures, TxEthSignature, TxHash},
+    Address, BatchFee, Fee, Token, TokenLike, TxFeeTypes, ZkSyncTx,
+};
+
+// Local uses
+use crate::{error::ClientError, provider::ResponseResult};
+
+/// Returns a corresponding REST API address for a provided network name.
+pub fn get_rest_addr(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "https://api.zksync.io",
+        Network::Rinkeby => "https://rinkeby-api.zksync.io",
+        Network::Ropsten => "https://ropsten-api.zksync.io",
+        Network::Localhost => "http://127.0.0.1:3001",
+        Network::Unknown => panic!("Attempt to create a provider from an unknown network"),
+        Network::Test => panic!("Attempt to create a provider from an test network"),
+    }
+}
+
+impl From<RestClientError> for ClientError {
+    fn from(err: RestClientError) -> Self {
+        match err {
+            RestClientError::Other(err) => ClientError::NetworkError(err.to_string()),
+            RestClientError::Parse(err) => ClientError::MalformedResponse(err.to_string()),
+            err => ClientError::RestError(err.to_string()),
+        }
+    }
+}
+
+/// `RestProvider` is capable of interacting with the zkSync node via its REST API.
+///
+/// Requests failed because of the network or internal server errors are repeated
+/// with exponential backoff, the same way as it's done by the `RpcProvider`.
+#[derive(Debug, Clone)]
+pub struct RestProvider {
+    client: Client,
+    network: Network,
+}
+
+impl RestProvider {
+    /// Creates a new `RestProvider` connected to the desired zkSync network.
+    pub fn new(network: Network) -> Self {
+        Self::from_addr_and_network(get_rest_addr(network), network)
+    }
+
+    /// Creates a new `RestProvider` object connected to a custom address.
+    pub fn from_addr(rest_addr: impl Into<String>) -> Self {
+        Self::from_addr_and_network(rest_addr, Network::Unknown)
+    }
+
+    /// Creates a new `RestProvider` object connected to a custom address and the desired zkSync network.
+    pub fn from_addr_and_network(rest_addr: impl Into<String>, network: Network) -> Self {
+        Self {
+            client: Client::new(rest_addr.into()),
+            network,
+        }
+    }
+
+    /// Type of network this provider is allowing access to.
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Underlying REST API client, for the methods not covered by the provider.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Requests and returns a list of tokens supported by zkSync.
+    pub async fn tokens(&self) -> ResponseResult<Vec<Token>> {
+        self.with_retries(|| self.client.tokens()).await
+    }
+
+    /// Requests and returns whether the token can be used to pay fees, and the reason if it can't.
+    pub async fn token_fee_status(
+        &self,
+        token: impl Into<TokenLike>,
+    ) -> ResponseResult<FeeTokenStatus> {
+        let token = token.into();
+        self.with_retries(|| self.client.token_fee_status(&token))
+            .await
+    }
+
+    /// Requests and returns information about a zkSync account.
+    pub async fn account_info(
+        &self,
+        account: impl Into<AccountQuery>,
+    ) -> ResponseResult<Option<AccountInfo>> {
+        let account = account.into();
+        self.with_retries(|| self.client.account_info(account))
+            .await
+    }
+
+    /// Obtains the fee required to process the transaction, along with its breakdown
+    /// into the gas and zero-knowledge proof parts.
+    pub async fn get_tx_fee(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: impl Into<TokenLike>,
+    ) -> ResponseResult<Fee> {
+        let token = token.into();
+        self.with_retries(|| self.client.get_txs_fee(tx_type, address, token.clone()))
+            .await
+    }
+
+    /// Obtains the fee required to process the transactions batch.
+    pub async fn get_txs_batch_fee(
+        &self,
+        tx_types: Vec<TxFeeTypes>,
+        addresses: Vec<Address>,
+        token: impl Into<TokenLike>,
+    ) -> ResponseResult<BatchFee> {
+        let token = token.into();
+        self.with_retries(|| {
+            self.client
+                .get_batched_txs_fee(tx_types.clone(), addresses.clone(), token.clone())
+        })
+        .await
+    }
+
+    /// Submits a transaction to the zkSync network.
+    /// Returns the hash of the created transaction.
+    pub async fn send_tx(
+        &self,
+        tx: ZkSyncTx,
+        eth_signature: Option<TxEthSignature>,
+        fast_processing: Option<bool>,
+    ) -> ResponseResult<TxHash> {
+        self.with_retries(|| {
+            self.client
+                .submit_tx(tx.clone(), eth_signature.clone(), fast_processing)
+        })
+        .await
+    }
+
+    /// Submits a batch of transactions to the zkSync network.
+    /// Returns the hashes of the created transactions.
+    pub async fn send_txs_batch(
+        &self,
+        txs: Vec<ZkSyncTx>,
+        eth_signatures: EthBatchSignatures,
+    ) -> ResponseResult<Vec<TxHash>> {
+        self.with_retries(|| {
+            self.client
+                .submit_tx_batch(txs.clone(), eth_signatures.clone())
+        })
+        .await
+    }
+
+    /// Requests and returns the latest receipt of the transaction.
+    pub async fn tx_status(&self, tx_hash: TxHash) -> ResponseResult<Option<Receipt>> {
+        self.with_retries(|| self.client.tx_status(tx_hash)).await
+    }
+
+    /// Requests and returns the transaction content.
+    pub async fn tx_data(&self, tx_hash: TxHash) -> ResponseResult<Option<TxData>> {
+        self.with_retries(|| self.client.tx_data(tx_hash)).await
+    }
+
+    /// Requests and returns the receipt of the priority operation.
+    pub async fn priority_op(
+        &self,
+        query: impl Into<PriorityOpQuery>,
+    ) -> ResponseResult<Option<PriorityOpReceipt>> {
+        let query = query.into();
+        self.with_retries(|| self.client.priority_op(query)).await
+    }
+
+    /// Sends the deposit authorized by the permit signature to the server relayer.
+    /// Returns the ID of the deposit request.
+    pub async fn send_permit_deposit(&self, deposit: IncomingPermitDeposit) -> ResponseResult<i64> {
+        self.with_retries(|| self.client.submit_permit_deposit(deposit.clone()))
+            .await
+    }
+
+    /// Requests and returns the state of the deposit authorized by the permit signature.
+    pub async fn permit_deposit_status(
+        &self,
+        request_id: i64,
+    ) -> ResponseResult<Option<PermitDepositStatus>> {
+        self.with_retries(|| self.client.permit_deposit_status(request_id))
+            .await
+    }
+
+    /// Requests and returns the addresses of the zkSync smart contracts.
+    pub async fn contracts(&self) -> ResponseResult<Contracts> {
+        self.with_retries(|| self.client.contracts()).await
+    }
+
+    /// Performs the request repeating it with exponential backoff until a response
+    /// which is not caused by the network or internal server error is received.
+    async fn with_retries<T, F, R>(&self, request: F) -> ResponseResult<T>
+    where
+        F: Fn() -> R,
+        R: Future<Output = Result<T, RestClientError>>,
+    {
+        const MAX_DURATION: Duration = Duration::from_secs(30);
+        let mut delay = Duration::from_millis(50);
+        loop {
+            let result = request().await;
+
+            let should_retry = match result.as_ref() {
+                Err(RestClientError::Other(..)) => true,
+                Err(RestClientError::BadRequest { http_code, .. }) => http_code.is_server_error(),
+                _ => false,
+            };
+
+            if should_retry && delay < MAX_DURATION {
+                delay *= 2;
+                tokio::time::delay_for(delay).await;
+                continue;
+            }
+
+            return result.map_err(ClientError::from);
+        }
+    }
+}
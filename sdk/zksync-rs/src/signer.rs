@@ -7,9 +7,9 @@ use zksync_types::tx::{ChangePubKeyECDSAData, ChangePubKeyEthAuthData, TimeRange
 use num::BigUint;
 // Workspace uses
 use zksync_crypto::PrivateKey;
-use zksync_types::tx::{ChangePubKey, PackedEthSignature};
+use zksync_types::tx::{ChangePubKey, EthBatchSignData, PackedEthSignature};
 use zksync_types::{
-    AccountId, Address, ForcedExit, Nonce, PubKeyHash, Token, Transfer, Withdraw, H256,
+    AccountId, Address, ForcedExit, Nonce, PubKeyHash, Token, Transfer, Withdraw, ZkSyncTx, H256,
 };
 // Local imports
 use crate::WalletCredentials;
@@ -117,6 +117,9 @@ impl<S: EthereumSigner> Signer<S> {
                 TxEthSignature::EIP1271Signature(..) => Err(SignerError::CustomError(
                     "Can't sign ChangePubKey message with EIP1271 signer".to_string(),
                 )),
+                TxEthSignature::EIP712Signature(..) => Err(SignerError::CustomError(
+                    "Can't sign ChangePubKey message with EIP712 signature".to_string(),
+                )),
             }?;
 
             ChangePubKeyEthAuthData::ECDSA(ChangePubKeyECDSAData {
@@ -253,4 +256,20 @@ impl<S: EthereumSigner> Signer<S> {
 
         Ok((forced_exit, eth_signature))
     }
+
+    /// Signs the whole batch with a single Ethereum signature. Every transaction is provided
+    /// along with the token it operates on and its sender address, the same way as the server
+    /// builds the batch message for the verification.
+    pub async fn sign_batch(
+        &self,
+        txs: Vec<(ZkSyncTx, Token, Address)>,
+    ) -> Result<TxEthSignature, SignerError> {
+        let eth_signer = self
+            .eth_signer
+            .as_ref()
+            .ok_or(SignerError::MissingEthSigner)?;
+
+        let message = EthBatchSignData::get_batch_sign_message(txs);
+        eth_signer.sign_message(&message).await
+    }
 }
@@ -147,6 +147,7 @@ pub enum OutputFeeType {
     FastWithdraw,
     Withdraw,
     ChangePubKey(ChangePubKeyFeeType),
+    PermitDeposit,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -161,8 +162,12 @@ pub struct Fee {
     pub gas_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub zkp_fee: BigUint,
+    /// Total fee, rounded up to the packable amount.
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Total fee before the rounding to the packable amount.
+    #[serde(default, with = "BigUintSerdeAsRadix10Str")]
+    pub exact_total_fee: BigUint,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]